@@ -52,7 +52,8 @@ export default function Layout() {
     onSuccess: () => queryClient.invalidateQueries({ queryKey: ['system-status'] }),
   });
 
-  const isPaused = status?.paused ?? false;
+  const isTripped = status?.circuit_breaker?.tripped ?? false;
+  const isPaused = (status?.paused ?? false) || isTripped;
   const mode = status?.mode ?? 'unknown';
   const modeColor = mode === 'live' ? 'text-emerald-400' : 'text-amber-400';
   const statusDotColor = isPaused ? 'text-red-400' : 'text-emerald-400';
@@ -103,7 +104,7 @@ export default function Layout() {
             <div className="flex items-center gap-1.5">
               <Circle size={8} className={`${statusDotColor} fill-current`} />
               <span className="text-xs text-slate-400">
-                {isPaused ? '已暂停' : '运行中'}
+                {isTripped ? '熔断中' : isPaused ? '已暂停' : '运行中'}
               </span>
             </div>
             <span className={`text-xs font-mono ${modeColor}`}>
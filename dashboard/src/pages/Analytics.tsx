@@ -1,6 +1,6 @@
 import { useMemo } from 'react';
 import { useQuery } from '@tanstack/react-query';
-import { fetchPnlHistory, fetchPerformance, fetchWhales, fetchTrades } from '../services/api';
+import { fetchEquityCurve, fetchPnlHistory, fetchPerformance, fetchRiskEvents, fetchWhales, fetchTrades } from '../services/api';
 import StatCard from '../components/StatCard';
 import {
   AreaChart,
@@ -32,6 +32,12 @@ export default function Analytics() {
     refetchInterval: 60_000,
   });
 
+  const { data: equityCurve } = useQuery({
+    queryKey: ['equity-curve'],
+    queryFn: () => fetchEquityCurve(),
+    refetchInterval: 60_000,
+  });
+
   const { data: whales } = useQuery({
     queryKey: ['whales'],
     queryFn: fetchWhales,
@@ -44,8 +50,28 @@ export default function Analytics() {
     refetchInterval: 30_000,
   });
 
+  const { data: riskEvents } = useQuery({
+    queryKey: ['risk-events'],
+    queryFn: () => fetchRiskEvents(),
+    refetchInterval: 60_000,
+  });
+
+  // Rejections per rule, most frequent first
+  const rejectionsByRule = useMemo(() => {
+    const counts = new Map<string, number>();
+    (riskEvents ?? []).forEach((e) => counts.set(e.rule, (counts.get(e.rule) ?? 0) + 1));
+    return [...counts.entries()].sort(([, a], [, b]) => b - a);
+  }, [riskEvents]);
+
+  const equityPoints = (equityCurve ?? []).map((s) => ({
+    time: new Date(s.taken_at).toLocaleString('zh-CN', { month: '2-digit', day: '2-digit', hour: '2-digit', minute: '2-digit' }),
+    equity: Number(s.equity),
+    bankroll: Number(s.bankroll),
+  }));
+
   const winRate = Number(performance?.win_rate ?? 0) * 100;
   const totalProfit = Number(performance?.total_profit ?? 0);
+  const netProfit = Number(performance?.net_profit ?? totalProfit);
 
   // Compute drawdown from PnL history
   const { drawdownData, maxDrawdown } = useMemo(() => {
@@ -174,6 +200,57 @@ export default function Analytics() {
         />
       </div>
 
+      <div className="grid grid-cols-2 sm:grid-cols-4 gap-2 sm:gap-3">
+        <StatCard
+          label="手续费"
+          value={`$${Number(performance?.total_fees ?? 0).toFixed(2)}`}
+          accent="amber"
+        />
+        <StatCard
+          label="Gas 费用"
+          value={`$${Number(performance?.total_gas ?? 0).toFixed(2)}`}
+          accent="amber"
+        />
+        <StatCard
+          label="净利润"
+          value={`$${netProfit.toFixed(2)}`}
+          accent={netProfit >= 0 ? 'emerald' : 'red'}
+          trend={netProfit >= 0 ? 'up' : 'down'}
+        />
+      </div>
+
+      {/* Equity curve */}
+      <div className="bg-slate-800/80 backdrop-blur rounded-xl border border-slate-700/50 p-4">
+        <h3 className="text-sm font-medium text-white mb-3">
+          权益曲线
+          <span className="text-xs text-slate-500 ml-2">近 30 天 (现金 + 持仓成本 + 浮动盈亏)</span>
+        </h3>
+        {equityPoints.length > 0 ? (
+          <ResponsiveContainer width="100%" height={240}>
+            <AreaChart data={equityPoints}>
+              <defs>
+                <linearGradient id="equityGrad" x1="0" y1="0" x2="0" y2="1">
+                  <stop offset="5%" stopColor="#6366f1" stopOpacity={0.3} />
+                  <stop offset="95%" stopColor="#6366f1" stopOpacity={0} />
+                </linearGradient>
+              </defs>
+              <CartesianGrid strokeDasharray="3 3" stroke="#1e293b" />
+              <XAxis dataKey="time" tick={{ fill: '#64748b', fontSize: 10 }} minTickGap={40} />
+              <YAxis tick={{ fill: '#64748b', fontSize: 10 }} domain={['auto', 'auto']} />
+              <Tooltip
+                contentStyle={{ backgroundColor: '#1e293b', border: '1px solid #334155', borderRadius: 8 }}
+                labelStyle={{ color: '#e2e8f0' }}
+                formatter={(v: number | undefined, name) => [`$${(v ?? 0).toFixed(2)}`, name]}
+              />
+              <Area type="monotone" dataKey="equity" name="权益" stroke="#6366f1" fill="url(#equityGrad)" strokeWidth={2} />
+              <Area type="monotone" dataKey="bankroll" name="现金" stroke="#64748b" fill="none" strokeWidth={1} strokeDasharray="4 4" />
+            </AreaChart>
+          </ResponsiveContainer>
+        ) : (
+          <div className="h-[240px] flex items-center justify-center text-slate-500 text-sm">暂无快照数据</div>
+        )}
+      </div>
+
       {/* Charts row 1: PnL + Drawdown */}
       <div className="grid grid-cols-1 lg:grid-cols-2 gap-3 sm:gap-4">
         {/* Cumulative PnL + Daily bars */}
@@ -385,6 +462,44 @@ export default function Analytics() {
           </div>
         </div>
       )}
+
+      {/* Risk rejections */}
+      {riskEvents && riskEvents.length > 0 && (
+        <div className="bg-slate-800/80 backdrop-blur rounded-xl border border-slate-700/50 p-4">
+          <h3 className="text-sm font-medium text-white mb-3">风控拦截记录 (最近 {riskEvents.length} 条)</h3>
+          <div className="flex flex-wrap gap-2 mb-3">
+            {rejectionsByRule.map(([rule, count]) => (
+              <span key={rule} className="text-xs font-mono bg-slate-700/40 text-slate-300 rounded px-2 py-1">
+                {rule} × {count}
+              </span>
+            ))}
+          </div>
+          <div className="overflow-x-auto max-h-[320px]">
+            <table className="w-full text-sm min-w-[600px]">
+              <thead>
+                <tr className="text-xs text-slate-400 uppercase border-b border-slate-700/50">
+                  <th className="text-left px-3 py-2 font-medium">时间</th>
+                  <th className="text-left px-3 py-2 font-medium">规则</th>
+                  <th className="text-left px-3 py-2 font-medium">范围</th>
+                  <th className="text-left px-3 py-2 font-medium">原因</th>
+                  <th className="text-right px-3 py-2 font-medium">数量</th>
+                </tr>
+              </thead>
+              <tbody>
+                {riskEvents.map((e) => (
+                  <tr key={e.id} className="border-b border-slate-700/30">
+                    <td className="px-3 py-1.5 text-xs text-slate-400 font-mono">{new Date(e.created_at).toLocaleString('zh-CN', { month: '2-digit', day: '2-digit', hour: '2-digit', minute: '2-digit' })}</td>
+                    <td className="px-3 py-1.5 text-xs text-amber-400 font-mono">{e.rule}</td>
+                    <td className="px-3 py-1.5 text-xs text-slate-400">{e.scope}</td>
+                    <td className="px-3 py-1.5 text-xs text-slate-300 max-w-[320px] truncate" title={e.message}>{e.message}</td>
+                    <td className="px-3 py-1.5 text-xs text-slate-300 font-mono text-right">{e.size ? Number(e.size).toFixed(2) : '--'}</td>
+                  </tr>
+                ))}
+              </tbody>
+            </table>
+          </div>
+        </div>
+      )}
     </div>
   );
 }
@@ -1,21 +1,23 @@
-import { useMemo, useState } from 'react';
+import { useCallback, useMemo, useState } from 'react';
 import { useQuery, useQueryClient, useMutation } from '@tanstack/react-query';
-import { fetchPositions, closePosition } from '../services/api';
+import { fetchPositions, fetchPositionAging, closePosition, updatePositionStop } from '../services/api';
 import type { Position } from '../types';
 import StatusBadge from '../components/StatusBadge';
 import StatCard from '../components/StatCard';
+import { useWebSocket } from '../hooks/useWebSocket';
 import { Search, ChevronUp, ChevronDown, X, Filter, Calendar } from 'lucide-react';
 
 type StatusFilter = 'all' | 'open' | 'exiting' | 'closed';
 type SortKey = 'opened_at' | 'closed_at' | 'unrealized_pnl' | 'realized_pnl' | 'size' | 'pnl_pct' | 'hold_days';
 type SortDir = 'asc' | 'desc';
-type ExitReasonFilter = '' | 'stop_loss' | 'take_profit' | 'trailing_stop' | 'time_exit' | 'whale_exit';
+type ExitReasonFilter = '' | 'stop_loss' | 'take_profit' | 'trailing_stop' | 'time_exit' | 'resolution_exit' | 'whale_exit';
 
 const EXIT_REASON_LABELS: Record<string, string> = {
   stop_loss: '止损',
   take_profit: '止盈',
   trailing_stop: '移动止损',
   time_exit: '时间止损',
+  resolution_exit: '临近结算',
   whale_exit: '巨鲸跟随',
 };
 
@@ -34,6 +36,13 @@ function pnlPct(p: Position): number {
   return (Number(p.unrealized_pnl ?? 0) / cost) * 100;
 }
 
+// Trailing-mode stop: stop_loss_pct below the highest price seen
+function trailingStopPrice(p: Position): number {
+  const entry = Number(p.avg_entry_price);
+  const high = Math.max(entry, Number(p.peak_price ?? entry), Number(p.current_price ?? entry));
+  return high * (1 - Number(p.stop_loss_pct ?? 15) / 100);
+}
+
 export default function Positions() {
   // --- filters ---
   const [statusFilter, setStatusFilter] = useState<StatusFilter>('all');
@@ -55,9 +64,30 @@ export default function Positions() {
   const { data: positions, isLoading } = useQuery({
     queryKey: ['positions'],
     queryFn: fetchPositions,
-    refetchInterval: 15_000,
+    // Live price/PnL arrives over the WebSocket; polling is only a fallback
+    refetchInterval: 60_000,
   });
 
+  const { data: aging } = useQuery({
+    queryKey: ['positions', 'aging'],
+    queryFn: fetchPositionAging,
+    refetchInterval: 300_000,
+  });
+  const unhealthy = (aging ?? []).filter((a) => a.flags.length > 0);
+
+  const onWsMessage = useCallback(
+    (msg: unknown) => {
+      const m = msg as { type?: string; data?: Position };
+      if (m.type !== 'position_update' || !m.data) return;
+      const update = m.data;
+      queryClient.setQueryData<Position[]>(['positions'], (prev) =>
+        prev?.map((p) => (p.id === update.id ? { ...p, ...update } : p)),
+      );
+    },
+    [queryClient],
+  );
+  useWebSocket(onWsMessage);
+
   const closeMutation = useMutation({
     mutationFn: ({ id, price }: { id: string; price?: string }) => closePosition(id, price),
     onSuccess: () => {
@@ -67,6 +97,11 @@ export default function Positions() {
     },
   });
 
+  const stopModeMutation = useMutation({
+    mutationFn: ({ id, mode }: { id: string; mode: 'static' | 'trailing' }) => updatePositionStop(id, mode),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['positions'] }),
+  });
+
   // --- computed stats (on ALL positions, ignoring filters) ---
   const allPositions = positions ?? [];
   const openCount = allPositions.filter((p) => p.status === 'open' || p.status === 'exiting').length;
@@ -244,6 +279,25 @@ export default function Positions() {
         />
       </div>
 
+      {/* Position health: stale holdings / collapsing liquidity */}
+      {unhealthy.length > 0 && (
+        <div className="bg-amber-500/10 border border-amber-500/30 rounded-lg p-3 space-y-1">
+          <p className="text-xs font-medium text-amber-400">{unhealthy.length} 个持仓需关注</p>
+          {unhealthy.map((a) => (
+            <div key={a.position_id} className="flex justify-between gap-2 text-xs text-slate-300">
+              <span className="truncate">{a.market_question ?? a.market_id}</span>
+              <span className="shrink-0 text-amber-300">
+                {a.flags
+                  .map((f) =>
+                    f === 'stale' ? `持仓 ${a.age_days} 天` : `流动性 ${Number(a.liquidity_change_pct ?? 0).toFixed(1)}%`,
+                  )
+                  .join(' · ')}
+              </span>
+            </div>
+          ))}
+        </div>
+      )}
+
       {/* Status tabs + search + filter toggle */}
       <div className="flex flex-col sm:flex-row gap-2 sm:items-center justify-between">
         {/* Status tabs */}
@@ -450,6 +504,13 @@ export default function Positions() {
                               : p.market_id.slice(0, 14) + '...'}
                           </span>
                         )}
+                        {(p.source_signal_id || p.source_whale_address) && (
+                          <span className="block text-[10px] text-slate-500 truncate" title={p.source_whale_address}>
+                            来源: {p.source_signal_id
+                              ? '篮子共识'
+                              : p.source_whale_label ?? `${p.source_whale_address!.slice(0, 6)}...${p.source_whale_address!.slice(-4)}`}
+                          </span>
+                        )}
                       </td>
                       {/* Outcome */}
                       <td className="px-4 py-2 text-slate-300 text-xs">
@@ -472,6 +533,11 @@ export default function Positions() {
                       {/* Current price */}
                       <td className="px-4 py-2 text-right font-mono text-slate-300 text-xs">
                         {p.current_price ? Number(p.current_price).toFixed(4) : '--'}
+                        {!isClosed && p.stop_mode === 'trailing' && (
+                          <div className="text-[10px] text-amber-400" title="移动止损价">
+                            止 {trailingStopPrice(p).toFixed(4)}
+                          </div>
+                        )}
                       </td>
                       {/* PnL % */}
                       <td className={`px-4 py-2 text-right font-mono text-xs font-semibold ${
@@ -498,6 +564,12 @@ export default function Positions() {
                       <td className="px-4 py-2">
                         <div className="flex flex-col gap-0.5">
                           <StatusBadge status={p.status ?? 'open'} />
+                          {p.hedge_of && (
+                            <span className="text-[10px] text-sky-400" title="对冲腿, 持有至结算">对冲腿</span>
+                          )}
+                          {!p.hedge_of && p.hedged_at && (
+                            <span className="text-[10px] text-sky-400">已对冲</span>
+                          )}
                           {p.exit_reason && (
                             <span className="text-[10px] text-slate-500">
                               {EXIT_REASON_LABELS[p.exit_reason] ?? p.exit_reason}
@@ -523,12 +595,26 @@ export default function Positions() {
                       {/* Actions */}
                       <td className="px-4 py-2 text-center">
                         {p.status === 'open' ? (
-                          <button
-                            onClick={() => openCloseModal(p)}
-                            className="px-2.5 py-1 rounded text-xs font-medium bg-red-500/20 text-red-400 hover:bg-red-500/30 transition-colors"
-                          >
-                            平仓
-                          </button>
+                          <div className="flex items-center justify-center gap-1">
+                            <button
+                              onClick={() => stopModeMutation.mutate({ id: p.id, mode: p.stop_mode === 'trailing' ? 'static' : 'trailing' })}
+                              disabled={stopModeMutation.isPending}
+                              title={p.stop_mode === 'trailing' ? '切换为固定止损' : '切换为移动止损'}
+                              className={`px-2 py-1 rounded text-[10px] font-medium transition-colors disabled:opacity-50 ${
+                                p.stop_mode === 'trailing'
+                                  ? 'bg-amber-500/20 text-amber-400 hover:bg-amber-500/30'
+                                  : 'bg-slate-700 text-slate-400 hover:text-white'
+                              }`}
+                            >
+                              {p.stop_mode === 'trailing' ? '移动' : '固定'}
+                            </button>
+                            <button
+                              onClick={() => openCloseModal(p)}
+                              className="px-2.5 py-1 rounded text-xs font-medium bg-red-500/20 text-red-400 hover:bg-red-500/30 transition-colors"
+                            >
+                              平仓
+                            </button>
+                          </div>
                         ) : p.status === 'exiting' ? (
                           <span className="text-[10px] text-amber-400">退出中...</span>
                         ) : null}
@@ -1,15 +1,35 @@
 import { useState, useEffect } from 'react';
 import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
-import { fetchConfig, updateConfig } from '../services/api';
-import { Save, Check, X } from 'lucide-react';
+import {
+  createComplianceRule,
+  deleteComplianceRule,
+  fetchComplianceRules,
+  fetchConfig,
+  fetchRiskLimits,
+  setComplianceRuleEnabled,
+  updateConfig,
+  updateRiskLimits,
+} from '../services/api';
+import type { ComplianceRule } from '../types';
+import { Save, Check, X, Plus, Trash2 } from 'lucide-react';
 
 interface FieldDef {
   key: string;
   label: string;
   type: 'text' | 'number' | 'boolean';
   description?: string;
+  /** Fields persisted through /api/risk-limits instead of runtime config */
+  source?: 'risk';
 }
 
+const RISK_INT_KEYS = ['max_open_positions', 'max_position_hold_days', 'min_hours_to_resolution', 'exit_hours_before_resolution'];
+
+const COMPLIANCE_RULE_LABELS: Record<ComplianceRule['rule_type'], string> = {
+  excluded_tag: '排除标签',
+  max_odds: '最高赔率',
+  banned_keyword: '禁用关键词',
+};
+
 const groups: { title: string; description: string; fields: FieldDef[] }[] = [
   {
     title: '跟单策略',
@@ -42,10 +62,27 @@ const groups: { title: string; description: string; fields: FieldDef[] }[] = [
     fields: [
       { key: 'default_stop_loss_pct', label: '止损', type: 'number', description: '例: 0.15 = 15%' },
       { key: 'default_take_profit_pct', label: '止盈', type: 'number', description: '例: 0.30 = 30%' },
-      { key: 'max_daily_loss', label: '日最大亏损', type: 'number', description: '单日最大允许亏损 (USDC)' },
-      { key: 'max_open_positions', label: '最大持仓数', type: 'number', description: '最大同时持仓数量' },
-      { key: 'trailing_stop_pct', label: '移动止损', type: 'number', description: '从最高价回撤百分比触发平仓 (例: 10 = 10%)' },
-      { key: 'max_position_hold_days', label: '最大持仓天数', type: 'number', description: '超过天数自动平仓 (0=禁用)' },
+      { key: 'default_stop_mode', label: '止损模式', type: 'text', description: 'static (固定) / trailing (随最高价上移)' },
+      { key: 'whale_exit_mode', label: '跟随巨鲸卖出', type: 'text', description: 'full (全部平仓) / proportional (按巨鲸卖出比例)' },
+    ],
+  },
+  {
+    title: '风控限额',
+    description: '实时生效，无需重启',
+    fields: [
+      { key: 'max_daily_loss', label: '日最大亏损', type: 'number', description: '单日最大允许亏损 (USDC)', source: 'risk' },
+      { key: 'max_open_positions', label: '最大持仓数', type: 'number', description: '最大同时持仓数量', source: 'risk' },
+      { key: 'max_position_pct', label: '单仓上限', type: 'number', description: '占资金池比例 (例: 0.20 = 20%)', source: 'risk' },
+      { key: 'max_slippage_pct', label: '最大滑点', type: 'number', description: '例: 0.03 = 3%', source: 'risk' },
+      { key: 'min_spread_to_resolution', label: '结算价距离', type: 'number', description: '价格距 0/1 的最小距离 (例: 0.05)', source: 'risk' },
+      { key: 'max_tail_loss_pct', label: '尾部风险上限', type: 'number', description: '95% VaR 占资金池比例 (例: 0.25)', source: 'risk' },
+      { key: 'trailing_stop_pct', label: '移动止损', type: 'number', description: '从最高价回撤百分比触发平仓 (例: 10 = 10%)', source: 'risk' },
+      { key: 'max_position_hold_days', label: '最大持仓天数', type: 'number', description: '超过天数自动平仓 (0=禁用)', source: 'risk' },
+      { key: 'min_hours_to_resolution', label: '距结算最短时间', type: 'number', description: '距市场结束不足该小时数时拒绝开仓 (0=禁用)', source: 'risk' },
+      { key: 'exit_hours_before_resolution', label: '结算前平仓', type: 'number', description: '距市场结束不足该小时数时强制平仓 (0=禁用)', source: 'risk' },
+      { key: 'min_matic_balance', label: '最低 MATIC 余额', type: 'number', description: '钱包 Gas 余额低于此值时拦截实盘订单 (0=禁用)', source: 'risk' },
+      { key: 'usdc_fee_buffer', label: 'USDC 手续费缓冲', type: 'number', description: '买入后需保留的 USDC 余额', source: 'risk' },
+      { key: 'reserve_floor_pct', label: '资金保留底线', type: 'number', description: '始终不用于开仓的资金比例，留给止损平仓和手续费 (例: 0.10 = 10%)', source: 'risk' },
     ],
   },
   {
@@ -75,20 +112,50 @@ export default function Settings() {
     queryFn: fetchConfig,
   });
 
+  const { data: riskLimits } = useQuery({
+    queryKey: ['risk-limits'],
+    queryFn: fetchRiskLimits,
+  });
+
   useEffect(() => {
     if (configEntries) {
       const map: Record<string, string> = {};
       for (const entry of configEntries) {
         map[entry.key] = entry.value;
       }
-      setForm(map);
+      setForm((prev) => ({ ...prev, ...map }));
     }
   }, [configEntries]);
 
+  useEffect(() => {
+    if (riskLimits) {
+      const map: Record<string, string> = {};
+      for (const [key, value] of Object.entries(riskLimits)) {
+        map[key] = String(value);
+      }
+      setForm((prev) => ({ ...prev, ...map }));
+    }
+  }, [riskLimits]);
+
+  const riskKeys = groups.flatMap((g) => g.fields).filter((f) => f.source === 'risk').map((f) => f.key);
+
   const saveMutation = useMutation({
-    mutationFn: () => updateConfig(form),
+    mutationFn: async () => {
+      const config: Record<string, string> = {};
+      const risk: Record<string, string | number> = {};
+      for (const [key, value] of Object.entries(form)) {
+        if (riskKeys.includes(key)) {
+          risk[key] = RISK_INT_KEYS.includes(key) ? Number(value) : value;
+        } else {
+          config[key] = value;
+        }
+      }
+      await updateConfig(config);
+      await updateRiskLimits(risk);
+    },
     onSuccess: () => {
       queryClient.invalidateQueries({ queryKey: ['config'] });
+      queryClient.invalidateQueries({ queryKey: ['risk-limits'] });
       setToast({ type: 'success', msg: '配置已保存' });
       setTimeout(() => setToast(null), 3000);
     },
@@ -177,6 +244,110 @@ export default function Settings() {
           </div>
         </div>
       ))}
+
+      <ComplianceRules />
+    </div>
+  );
+}
+
+function ComplianceRules() {
+  const queryClient = useQueryClient();
+  const [ruleType, setRuleType] = useState<ComplianceRule['rule_type']>('excluded_tag');
+  const [value, setValue] = useState('');
+
+  const { data: rules = [] } = useQuery({
+    queryKey: ['compliance-rules'],
+    queryFn: fetchComplianceRules,
+  });
+
+  const invalidate = () => queryClient.invalidateQueries({ queryKey: ['compliance-rules'] });
+
+  const createMutation = useMutation({
+    mutationFn: () => createComplianceRule({ rule_type: ruleType, value }),
+    onSuccess: () => {
+      setValue('');
+      invalidate();
+    },
+  });
+
+  const toggleMutation = useMutation({
+    mutationFn: (rule: ComplianceRule) => setComplianceRuleEnabled(rule.id, !rule.enabled),
+    onSuccess: invalidate,
+  });
+
+  const deleteMutation = useMutation({
+    mutationFn: (id: string) => deleteComplianceRule(id),
+    onSuccess: invalidate,
+  });
+
+  return (
+    <div className="bg-slate-800/80 backdrop-blur rounded-xl border border-slate-700/50 p-5 space-y-4">
+      <div className="border-b border-slate-700/50 pb-3">
+        <h3 className="text-sm font-medium text-white">合规规则</h3>
+        <p className="text-[10px] text-slate-500 mt-0.5">风控通过后执行的下单前检查，添加后立即生效</p>
+      </div>
+      <div className="flex items-center gap-2">
+        <select
+          value={ruleType}
+          onChange={(e) => setRuleType(e.target.value as ComplianceRule['rule_type'])}
+          className="bg-slate-900/80 border border-slate-600/50 rounded-lg px-3 py-2 text-sm text-white focus:outline-none focus:ring-1 focus:ring-indigo-500"
+        >
+          {Object.entries(COMPLIANCE_RULE_LABELS).map(([key, label]) => (
+            <option key={key} value={key}>{label}</option>
+          ))}
+        </select>
+        <input
+          value={value}
+          onChange={(e) => setValue(e.target.value)}
+          placeholder={ruleType === 'max_odds' ? '0.95' : ruleType === 'excluded_tag' ? 'us-politics' : '关键词'}
+          className="flex-1 bg-slate-900/80 border border-slate-600/50 rounded-lg px-3 py-2 text-sm text-white font-mono focus:outline-none focus:ring-1 focus:ring-indigo-500 focus:border-indigo-500"
+        />
+        <button
+          onClick={() => createMutation.mutate()}
+          disabled={!value.trim() || createMutation.isPending}
+          className="flex items-center gap-1 px-3 py-2 bg-indigo-600 hover:bg-indigo-500 disabled:opacity-50 text-white text-sm rounded-lg transition-colors"
+        >
+          <Plus size={14} />
+          添加
+        </button>
+      </div>
+      {createMutation.isError && (
+        <p className="text-xs text-red-400">添加失败，请检查规则值</p>
+      )}
+      {rules.length === 0 ? (
+        <p className="text-xs text-slate-500">暂无合规规则</p>
+      ) : (
+        <div className="space-y-1.5">
+          {rules.map((rule) => (
+            <div key={rule.id} className="flex items-center justify-between px-3 py-2 bg-slate-900/50 rounded-lg">
+              <div className="flex items-center gap-3">
+                <span className="text-[10px] px-1.5 py-0.5 rounded bg-slate-700 text-slate-300">
+                  {COMPLIANCE_RULE_LABELS[rule.rule_type] ?? rule.rule_type}
+                </span>
+                <span className={`text-sm font-mono ${rule.enabled ? 'text-white' : 'text-slate-500 line-through'}`}>
+                  {rule.value}
+                </span>
+              </div>
+              <div className="flex items-center gap-2">
+                <button
+                  onClick={() => toggleMutation.mutate(rule)}
+                  className={`relative w-10 h-5 rounded-full transition-colors ${rule.enabled ? 'bg-emerald-600' : 'bg-slate-700'}`}
+                >
+                  <span
+                    className={`absolute top-0.5 w-4 h-4 bg-white rounded-full transition-transform ${rule.enabled ? 'left-5' : 'left-0.5'}`}
+                  />
+                </button>
+                <button
+                  onClick={() => deleteMutation.mutate(rule.id)}
+                  className="p-1 text-slate-500 hover:text-red-400 transition-colors"
+                >
+                  <Trash2 size={14} />
+                </button>
+              </div>
+            </div>
+          ))}
+        </div>
+      )}
     </div>
   );
 }
@@ -1,6 +1,12 @@
 import { useParams, useNavigate } from 'react-router-dom';
-import { useQuery } from '@tanstack/react-query';
-import { fetchWhaleByAddress, fetchWhaleTrades } from '../services/api';
+import { useQuery, useMutation, useQueryClient } from '@tanstack/react-query';
+import {
+  fetchWhaleByAddress,
+  fetchWhaleCopyPerformance,
+  fetchWhaleTrades,
+  resumeWhaleCopying,
+  setWhaleClassificationOverride,
+} from '../services/api';
 import StatCard from '../components/StatCard';
 import StatusBadge from '../components/StatusBadge';
 import { ArrowLeft } from 'lucide-react';
@@ -9,6 +15,7 @@ import { BarChart, Bar, XAxis, YAxis, Tooltip, ResponsiveContainer, CartesianGri
 export default function WhaleDetail() {
   const { address } = useParams<{ address: string }>();
   const navigate = useNavigate();
+  const queryClient = useQueryClient();
 
   const { data: whale, isLoading } = useQuery({
     queryKey: ['whale', address],
@@ -22,6 +29,22 @@ export default function WhaleDetail() {
     enabled: !!whale?.id,
   });
 
+  const { data: copyPerf } = useQuery({
+    queryKey: ['whale-copy-performance', whale?.id],
+    queryFn: () => fetchWhaleCopyPerformance(whale!.id),
+    enabled: !!whale?.id,
+  });
+
+  const resumeMutation = useMutation({
+    mutationFn: () => resumeWhaleCopying(whale!.id),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['whale', address] }),
+  });
+
+  const overrideMutation = useMutation({
+    mutationFn: (classification: string | null) => setWhaleClassificationOverride(whale!.id, classification),
+    onSuccess: () => queryClient.invalidateQueries({ queryKey: ['whale', address] }),
+  });
+
   if (isLoading) {
     return (
       <div className="flex items-center justify-center h-64">
@@ -41,9 +64,15 @@ export default function WhaleDetail() {
     );
   }
 
+  const overrideActive =
+    !!whale.classification_override &&
+    (!whale.classification_override_until || new Date(whale.classification_override_until) > new Date());
+
   const pnl = Number(whale.total_pnl ?? 0);
   const winRate = Number(whale.win_rate ?? 0) * 100;
   const sharpe = Number(whale.sharpe_ratio ?? 0);
+  const sortino = Number(whale.sortino_ratio ?? 0);
+  const calmar = Number(whale.calmar_ratio ?? 0);
   const kelly = Number(whale.kelly_fraction ?? 0);
   const ev = Number(whale.expected_value ?? 0);
 
@@ -88,11 +117,33 @@ export default function WhaleDetail() {
               {whale.address.slice(0, 10)}...{whale.address.slice(-8)}
             </h2>
             {whale.classification && <StatusBadge status={whale.classification} />}
+            {overrideActive && (
+              <span
+                className="text-[10px] px-1.5 py-0.5 rounded bg-amber-500/10 text-amber-400 border border-amber-500/20"
+                title={whale.classification_override_until
+                  ? `至 ${new Date(whale.classification_override_until).toLocaleString('zh-CN')}`
+                  : '长期有效'}
+              >
+                人工: {whale.classification_override}
+              </span>
+            )}
             <StatusBadge status={whale.is_active ? 'open' : 'closed'} />
           </div>
           <p className="text-xs text-slate-500 font-mono mt-1">{whale.address}</p>
         </div>
         <div className="flex items-center gap-3">
+          <select
+            value={overrideActive ? whale.classification_override : ''}
+            onChange={(e) => overrideMutation.mutate(e.target.value || null)}
+            disabled={overrideMutation.isPending}
+            className="bg-slate-800 border border-slate-700 rounded-lg px-2 py-1 text-xs text-slate-300"
+            title="人工覆盖分类"
+          >
+            <option value="">自动分类</option>
+            <option value="informed">informed</option>
+            <option value="market_maker">market_maker</option>
+            <option value="bot">bot</option>
+          </select>
           <a
             href={`https://polymarket.com/profile/${whale.address}`}
             target="_blank"
@@ -112,8 +163,25 @@ export default function WhaleDetail() {
         </div>
       </div>
 
+      {/* Copy paused by the copy guard */}
+      {whale.copy_paused_at && (
+        <div className="flex items-center justify-between gap-3 bg-red-500/10 border border-red-500/20 rounded-xl px-4 py-3">
+          <div>
+            <p className="text-sm text-red-400 font-medium">跟单已暂停</p>
+            <p className="text-xs text-slate-400 mt-0.5">{whale.copy_pause_reason}</p>
+          </div>
+          <button
+            onClick={() => resumeMutation.mutate()}
+            disabled={resumeMutation.isPending}
+            className="px-3 py-1.5 rounded-lg text-xs font-medium bg-emerald-500/10 text-emerald-400 hover:bg-emerald-500/20 border border-emerald-500/20 transition-all"
+          >
+            恢复跟单
+          </button>
+        </div>
+      )}
+
       {/* Stats */}
-      <div className="grid grid-cols-2 md:grid-cols-3 lg:grid-cols-6 gap-3">
+      <div className="grid grid-cols-2 md:grid-cols-4 lg:grid-cols-8 gap-3">
         <StatCard
           label="总盈亏"
           value={`$${pnl.toLocaleString('en-US', { maximumFractionDigits: 0 })}`}
@@ -126,11 +194,56 @@ export default function WhaleDetail() {
           accent={winRate >= 55 ? 'emerald' : winRate >= 45 ? 'amber' : 'red'}
         />
         <StatCard label="夏普比率" value={sharpe.toFixed(2)} accent="indigo" />
+        <StatCard label="索提诺比率" value={sortino.toFixed(2)} accent="indigo" />
+        <StatCard label="卡玛比率" value={calmar.toFixed(2)} accent="indigo" />
         <StatCard label="凯利系数" value={kelly.toFixed(3)} accent="cyan" />
         <StatCard label="期望值" value={`$${ev.toFixed(2)}`} accent="amber" />
         <StatCard label="总交易数" value={whale.total_trades ?? 0} accent="default" />
       </div>
 
+      {/* Our copy performance for this whale */}
+      {copyPerf && copyPerf.total_orders > 0 && (
+        <div>
+          <h3 className="text-sm font-medium text-white mb-3">跟单表现</h3>
+          <div className="grid grid-cols-2 md:grid-cols-3 lg:grid-cols-6 gap-3">
+            <StatCard
+              label="跟单盈亏"
+              value={`$${Number(copyPerf.realized_pnl).toFixed(2)}`}
+              sub={`未实现 $${Number(copyPerf.unrealized_pnl).toFixed(2)}`}
+              accent={Number(copyPerf.realized_pnl) >= 0 ? 'emerald' : 'red'}
+              trend={Number(copyPerf.realized_pnl) >= 0 ? 'up' : 'down'}
+            />
+            <StatCard
+              label="跟单胜率"
+              value={copyPerf.copy_win_rate != null ? `${(Number(copyPerf.copy_win_rate) * 100).toFixed(1)}%` : '-'}
+              sub={`${copyPerf.winning_positions}/${copyPerf.closed_positions} 已平仓`}
+              accent="indigo"
+            />
+            <StatCard
+              label="成交订单"
+              value={`${copyPerf.filled_orders}/${copyPerf.total_orders}`}
+              sub={`失败 ${copyPerf.failed_orders}`}
+              accent="cyan"
+            />
+            <StatCard
+              label="成交金额"
+              value={`$${Number(copyPerf.filled_notional).toLocaleString('en-US', { maximumFractionDigits: 0 })}`}
+              accent="default"
+            />
+            <StatCard
+              label="平均滑点"
+              value={copyPerf.avg_slippage != null ? `${(Number(copyPerf.avg_slippage) * 100).toFixed(2)}%` : '-'}
+              accent="amber"
+            />
+            <StatCard
+              label="平均延迟"
+              value={copyPerf.avg_latency_secs != null ? `${Number(copyPerf.avg_latency_secs).toFixed(1)}s` : '-'}
+              accent="default"
+            />
+          </div>
+        </div>
+      )}
+
       {/* Volume chart */}
       {volumeData.length > 0 && (
         <div className="bg-slate-800/80 backdrop-blur rounded-xl border border-slate-700/50 p-4">
@@ -1,16 +1,26 @@
 import axios from 'axios';
 import type {
   ApiResponse,
+  BasketDetail,
+  BasketPerformance,
+  Candle,
+  ComplianceRule,
   ConfigEntry,
   ConsensusSignal,
   CopyOrder,
   DashboardSummary,
   PerformanceMetrics,
   PnlDataPoint,
+  PortfolioSnapshot,
   Position,
+  PositionAging,
+  RiskEvent,
+  RiskLimits,
   SystemStatus,
   Whale,
   WhaleBasket,
+  WhaleCapitalFlow,
+  WhaleCopyPerformance,
   WhaleTrade,
 } from '../types';
 
@@ -82,16 +92,84 @@ export async function fetchWhaleTrades(whaleId: string): Promise<WhaleTrade[]> {
   return data.data ?? [];
 }
 
+export async function fetchWhaleCopyPerformance(whaleId: string): Promise<WhaleCopyPerformance | null> {
+  const { data } = await api.get<ApiResponse<WhaleCopyPerformance>>(`/whales/${whaleId}/copy-performance`);
+  return data.data ?? null;
+}
+
+export async function fetchWhaleCapitalFlows(whaleId: string): Promise<WhaleCapitalFlow[]> {
+  const { data } = await api.get<ApiResponse<WhaleCapitalFlow[]>>(`/whales/${whaleId}/capital-flows`);
+  return data.data ?? [];
+}
+
+export async function resumeWhaleCopying(whaleId: string): Promise<Whale> {
+  const { data } = await api.post<ApiResponse<Whale>>(`/whales/${whaleId}/copy-resume`);
+  if (!data.success) {
+    throw new Error(data.error ?? 'Failed to resume copying');
+  }
+  return data.data!;
+}
+
+export async function setWhaleClassificationOverride(
+  whaleId: string,
+  classification: string | null,
+  expiresAt?: string,
+): Promise<Whale> {
+  const { data } = await api.patch<ApiResponse<Whale>>(`/whales/${whaleId}/classification`, {
+    classification,
+    expires_at: expiresAt ?? null,
+  });
+  if (!data.success) {
+    throw new Error(data.error ?? 'Failed to update classification');
+  }
+  return data.data!;
+}
+
 export async function fetchTrades(): Promise<CopyOrder[]> {
   const { data } = await api.get<ApiResponse<CopyOrder[]>>('/trades');
   return data.data ?? [];
 }
 
+export async function placeManualOrder(order: {
+  token_id: string;
+  side: 'BUY' | 'SELL';
+  size: string;
+  price?: string;
+  market_id?: string;
+  sleeve?: string;
+}): Promise<CopyOrder> {
+  const { data } = await api.post<ApiResponse<CopyOrder>>('/orders/manual', order);
+  if (!data.success) {
+    throw new Error(data.error ?? 'Failed to place manual order');
+  }
+  return data.data!;
+}
+
 export async function fetchPositions(): Promise<Position[]> {
   const { data } = await api.get<ApiResponse<Position[]>>('/positions');
   return data.data ?? [];
 }
 
+export async function fetchPositionAging(): Promise<PositionAging[]> {
+  const { data } = await api.get<ApiResponse<PositionAging[]>>('/positions/aging');
+  return data.data ?? [];
+}
+
+export async function updatePositionStop(
+  id: string,
+  stopMode: 'static' | 'trailing',
+  stopLossPct?: string,
+): Promise<Position> {
+  const { data } = await api.patch<ApiResponse<Position>>(`/positions/${id}/stop`, {
+    stop_mode: stopMode,
+    stop_loss_pct: stopLossPct || undefined,
+  });
+  if (!data.success) {
+    throw new Error(data.error ?? 'Failed to update stop mode');
+  }
+  return data.data!;
+}
+
 export async function closePosition(id: string, price?: string): Promise<Position> {
   const { data } = await api.post<ApiResponse<Position>>(`/positions/${id}/close`, {
     price: price || undefined,
@@ -109,8 +187,8 @@ export async function fetchBaskets(): Promise<WhaleBasket[]> {
   return data.data ?? [];
 }
 
-export async function fetchBasketDetail(id: string): Promise<WhaleBasket | null> {
-  const { data } = await api.get<ApiResponse<WhaleBasket>>(`/baskets/${id}`);
+export async function fetchBasketDetail(id: string): Promise<BasketDetail | null> {
+  const { data } = await api.get<ApiResponse<BasketDetail>>(`/baskets/${id}`);
   return data.data ?? null;
 }
 
@@ -142,11 +220,32 @@ export async function fetchConsensusHistory(basketId: string): Promise<Consensus
   return data.data ?? [];
 }
 
+export async function fetchBasketPerformance(basketId: string): Promise<BasketPerformance | null> {
+  const { data } = await api.get<ApiResponse<BasketPerformance>>(`/baskets/${basketId}/performance`);
+  return data.data ?? null;
+}
+
 export async function fetchRecentConsensus(): Promise<ConsensusSignal[]> {
   const { data } = await api.get<ApiResponse<ConsensusSignal[]>>('/consensus/recent');
   return data.data ?? [];
 }
 
+export async function fetchEquityCurve(days = 30): Promise<PortfolioSnapshot[]> {
+  const { data } = await api.get<ApiResponse<PortfolioSnapshot[]>>('/dashboard/equity-curve', {
+    params: { days },
+  });
+  return data.data ?? [];
+}
+
+// Markets
+
+export async function fetchCandles(tokenId: string, hours = 24): Promise<Candle[]> {
+  const { data } = await api.get<ApiResponse<Candle[]>>(`/markets/${tokenId}/candles`, {
+    params: { hours },
+  });
+  return data.data ?? [];
+}
+
 // Analytics
 
 export async function fetchPnlHistory(): Promise<PnlDataPoint[]> {
@@ -170,6 +269,55 @@ export async function updateConfig(entries: Record<string, string>): Promise<voi
   await api.put('/config', { entries });
 }
 
+// Risk limits
+
+export async function fetchRiskLimits(): Promise<RiskLimits | null> {
+  const { data } = await api.get<ApiResponse<RiskLimits>>('/risk-limits');
+  return data.data ?? null;
+}
+
+export async function updateRiskLimits(limits: Record<string, string | number>): Promise<RiskLimits> {
+  const { data } = await api.patch<ApiResponse<RiskLimits>>('/risk-limits', limits);
+  if (!data.success) {
+    throw new Error(data.error ?? 'Failed to update risk limits');
+  }
+  return data.data!;
+}
+
+export async function fetchRiskEvents(rule?: string, limit = 100): Promise<RiskEvent[]> {
+  const { data } = await api.get<ApiResponse<RiskEvent[]>>('/risk/events', {
+    params: { rule, limit },
+  });
+  return data.data ?? [];
+}
+
+// Compliance rules
+
+export async function fetchComplianceRules(): Promise<ComplianceRule[]> {
+  const { data } = await api.get<ApiResponse<ComplianceRule[]>>('/compliance/rules');
+  return data.data ?? [];
+}
+
+export async function createComplianceRule(rule: {
+  rule_type: ComplianceRule['rule_type'];
+  value: string;
+  note?: string;
+}): Promise<ComplianceRule> {
+  const { data } = await api.post<ApiResponse<ComplianceRule>>('/compliance/rules', rule);
+  if (!data.success) {
+    throw new Error(data.error ?? 'Failed to create compliance rule');
+  }
+  return data.data!;
+}
+
+export async function setComplianceRuleEnabled(id: string, enabled: boolean): Promise<void> {
+  await api.patch(`/compliance/rules/${id}`, { enabled });
+}
+
+export async function deleteComplianceRule(id: string): Promise<void> {
+  await api.delete(`/compliance/rules/${id}`);
+}
+
 // Control
 
 export async function controlStop(): Promise<void> {
@@ -14,6 +14,30 @@ export interface Whale {
   last_trade_at?: string;
   created_at?: string;
   updated_at?: string;
+  copy_paused_at?: string;
+  copy_pause_reason?: string;
+  copy_resumed_at?: string;
+  classification_override?: string;
+  classification_override_until?: string;
+  seed_leaderboard_pnl?: string;
+  seed_leaderboard_volume?: string;
+  leaderboard_pnl?: string;
+  leaderboard_volume?: string;
+  leaderboard_checked_at?: string;
+  sortino_ratio?: string;
+  calmar_ratio?: string;
+  max_drawdown?: string;
+  max_drawdown_pct?: string;
+  profit_factor?: string;
+  payoff_ratio?: string;
+  skill_score?: string;
+  entry_edge?: string;
+  entry_edge_samples?: number;
+  avg_hold_hours?: string;
+  early_exit_ratio?: string;
+  hold_profile_samples?: number;
+  exit_style?: 'mirror' | 'hold_to_resolution';
+  tier?: 'A' | 'B' | 'C';
 }
 
 export interface WhaleTrade {
@@ -26,6 +50,7 @@ export interface WhaleTrade {
   price: string;
   notional: string;
   tx_hash?: string;
+  fill_index?: number;
   traded_at: string;
 }
 
@@ -39,6 +64,12 @@ export interface CopyOrder {
   target_price: string;
   fill_price?: string;
   slippage?: string;
+  copy_lag?: string;
+  fee_usdc?: string;
+  gas_usdc?: string;
+  source_signal_id?: string;
+  exit_reprices?: number;
+  exit_alerted_at?: string;
   status: string;
   strategy: string;
   error_message?: string;
@@ -64,6 +95,16 @@ export interface Position {
   closed_at?: string;
   stop_loss_pct?: string;
   take_profit_pct?: string;
+  stop_mode?: 'static' | 'trailing';
+  peak_price?: string;
+  whale_id?: string;
+  whale_trade_id?: string;
+  source_signal_id?: string;
+  source_whale_address?: string;
+  source_whale_label?: string;
+  hedged_at?: string;
+  hedge_of?: string;
+  exit_style?: 'mirror' | 'hold_to_resolution';
   exit_reason?: string;
   exited_at?: string;
   market_slug?: string;
@@ -71,6 +112,24 @@ export interface Position {
   outcome_label?: string;
 }
 
+export interface PositionAging {
+  position_id: string;
+  market_id: string;
+  token_id: string;
+  market_question?: string;
+  sleeve: string;
+  size: string;
+  avg_entry_price: string;
+  current_price?: string;
+  unrealized_pnl?: string;
+  opened_at?: string;
+  age_days: number;
+  entry_liquidity?: string;
+  current_liquidity?: string;
+  liquidity_change_pct?: string;
+  flags: ('stale' | 'liquidity_collapse')[];
+}
+
 export interface DashboardSummary {
   tracked_whales: number;
   active_positions: number;
@@ -92,6 +151,7 @@ export interface WhaleBasket {
   is_active: boolean;
   created_at: string;
   updated_at: string;
+  vote_weighting: 'equal' | 'win_rate' | 'ev' | 'notional';
 }
 
 export interface ConsensusSignal {
@@ -103,6 +163,90 @@ export interface ConsensusSignal {
   participating_whales: number;
   total_whales: number;
   triggered_at: string;
+  cooldown_until: string;
+  blocked: boolean;
+}
+
+export interface BasketMembershipEvent {
+  id: string;
+  basket_id: string;
+  whale_id: string;
+  action: 'added' | 'removed';
+  actor: 'operator' | 'auto_assign' | 'rebalance';
+  reason: string;
+  created_at: string;
+}
+
+export interface BasketDetail extends WhaleBasket {
+  membership_events: BasketMembershipEvent[];
+}
+
+export interface TapePrint {
+  market_id: string;
+  token_id: string;
+  side: 'BUY' | 'SELL';
+  size: string;
+  price: string;
+  wallet?: string;
+  source: 'ws' | 'chain';
+  traded_at: string;
+}
+
+export interface FlowSignal {
+  id: string;
+  market_id: string;
+  token_id: string;
+  buy_notional: string;
+  sell_notional: string;
+  net_notional: string;
+  whales: number;
+  window_minutes: number;
+  triggered_at: string;
+}
+
+export interface BacktestConsensusEvent {
+  market_id: string;
+  direction: string;
+  consensus_pct: string;
+  participating: number;
+  total_whales: number;
+  fired_at: string;
+  price: string;
+  outcome: string | null;
+  return_per_usdc: string | null;
+}
+
+export interface BasketBacktest {
+  basket_id: string;
+  from: string;
+  to: string;
+  whales: number;
+  trades: number;
+  fired: number;
+  resolved: number;
+  wins: number;
+  losses: number;
+  hit_rate: string | null;
+  total_return: string;
+  events: BacktestConsensusEvent[];
+}
+
+export interface BasketPerformance {
+  basket_id: string;
+  consensus_signals: number;
+  settled_positions: number;
+  winning_positions: number;
+  win_rate: string | null;
+  realized_pnl: string;
+  open_positions: number;
+  unrealized_pnl: string;
+  pnl_curve: BasketPnlPoint[];
+}
+
+export interface BasketPnlPoint {
+  date: string;
+  daily_pnl: string;
+  cumulative_pnl: string;
 }
 
 export interface PnlDataPoint {
@@ -111,6 +255,17 @@ export interface PnlDataPoint {
   cumulative_pnl: string;
 }
 
+export interface PortfolioSnapshot {
+  id: string;
+  bankroll: string;
+  open_exposure: string;
+  unrealized_pnl: string;
+  realized_pnl: string;
+  equity: string;
+  open_positions: number;
+  taken_at: string;
+}
+
 export interface PerformanceMetrics {
   total_trades: number;
   win_count: number;
@@ -120,6 +275,9 @@ export interface PerformanceMetrics {
   avg_profit_per_trade: string;
   best_trade: string;
   worst_trade: string;
+  total_fees: string;
+  total_gas: string;
+  net_profit: string;
 }
 
 export interface ConfigEntry {
@@ -127,15 +285,203 @@ export interface ConfigEntry {
   value: string;
 }
 
+export interface RiskLimits {
+  max_position_pct: string;
+  max_open_positions: number;
+  max_daily_loss: string;
+  min_spread_to_resolution: string;
+  max_slippage_pct: string;
+  max_tail_loss_pct: string;
+  trailing_stop_pct: string;
+  max_position_hold_days: number;
+  min_hours_to_resolution: number;
+  min_matic_balance: string;
+  usdc_fee_buffer: string;
+  exit_hours_before_resolution: number;
+  reserve_floor_pct: string;
+}
+
+export interface GateProfile {
+  name: string;
+  description?: string;
+  is_active: boolean;
+  min_signal_win_rate?: string;
+  min_resolved_for_signal?: number;
+  min_total_trades_for_signal?: number;
+  min_signal_ev?: string;
+  min_signal_profit_factor?: string;
+  min_signal_skill_score?: string;
+  min_entry_edge?: string;
+  signal_notional_liquidity_pct?: string;
+  signal_notional_floor?: string;
+  max_signal_notional?: string;
+  assumed_slippage_pct?: string;
+  first_mover_size_multiplier?: string;
+  updated_at?: string;
+}
+
+export interface TierPolicy {
+  tier: 'A' | 'B' | 'C';
+  min_win_rate: string;
+  min_skill_score: string;
+  min_total_trades: number;
+  min_signal_ev: string;
+  signal_notional_floor: string;
+  sizing: 'kelly' | 'fixed' | 'proportional';
+  size_multiplier: string;
+  base_amount: string;
+  updated_at?: string;
+}
+
+export interface WhaleCorrelation {
+  whale_a: string;
+  address_a: string;
+  whale_b: string;
+  address_b: string;
+  shared_positions: number;
+  overlap: string;
+}
+
+export interface SeederCandidate {
+  id: string;
+  address: string;
+  note: string | null;
+  status: 'pending' | 'seeded' | 'rejected' | 'failed';
+  verdict: string | null;
+  whale_id: string | null;
+  requested_at: string;
+  evaluated_at: string | null;
+}
+
+export interface InsiderFlag {
+  id: string;
+  whale_id: string;
+  trade_id: string;
+  kind: 'pre_resolution' | 'pre_jump';
+  market_id: string;
+  token_id: string;
+  side: string;
+  entry_price: string;
+  notional: string;
+  traded_at: string;
+  lead_hours: string | null;
+  price_move: string;
+  evidence: string;
+  created_at: string;
+}
+
+export interface WhaleCapitalFlow {
+  id: string;
+  whale_id: string;
+  direction: 'deposit' | 'withdrawal';
+  amount: string;
+  counterparty: string;
+  balance_after: string | null;
+  capital_share_pct: string | null;
+  tx_hash: string;
+  log_index: number;
+  block_number: number;
+  occurred_at: string;
+  created_at: string;
+}
+
+export interface ClassificationAudit {
+  id: string;
+  whale_id: string;
+  classification: 'informed' | 'market_maker' | 'bot';
+  classifier: 'heuristic' | 'logistic';
+  trade_count: number;
+  trades_per_month: string;
+  dual_side_ratio: string;
+  avg_hold_hours: string;
+  notional_cv: string;
+  confidence: string | null;
+  rules: string[];
+  created_at: string;
+  updated_at: string;
+}
+
+export interface WhaleCopyPerformance {
+  whale_id: string;
+  total_orders: number;
+  filled_orders: number;
+  failed_orders: number;
+  filled_notional: string;
+  closed_positions: number;
+  winning_positions: number;
+  copy_win_rate?: string;
+  realized_pnl: string;
+  unrealized_pnl: string;
+  avg_slippage?: string;
+  avg_copy_lag?: string;
+  avg_latency_secs?: string;
+}
+
+export interface RiskEvent {
+  id: string;
+  rule: string;
+  scope: string;
+  message: string;
+  details: Record<string, unknown>;
+  whale_trade_id?: string;
+  wallet?: string;
+  market_id?: string;
+  condition_id?: string;
+  token_id?: string;
+  side?: string;
+  size?: string;
+  price?: string;
+  sleeve?: string;
+  created_at: string;
+}
+
+export interface ComplianceRule {
+  id: string;
+  rule_type: 'excluded_tag' | 'max_odds' | 'banned_keyword';
+  value: string;
+  note?: string;
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface DiscoveryExclusion {
+  id: string;
+  rule_type: 'category' | 'keyword' | 'resolves_within_hours' | 'max_spread';
+  value: string;
+  note?: string;
+  enabled: boolean;
+  created_at: string;
+}
+
+export interface Candle {
+  token_id: string;
+  bucket: string;
+  open: string;
+  high: string;
+  low: string;
+  close: string;
+  volume: string;
+  trade_count: number;
+}
+
 export interface ApiResponse<T> {
   success: boolean;
   data?: T;
   error?: string;
 }
 
+export interface CircuitBreakerStatus {
+  tripped: boolean;
+  recent_failures: number;
+  max_failures: number;
+  tripped_at?: string;
+  resets_at?: string;
+}
+
 export interface SystemStatus {
   mode: string;
   paused: boolean;
+  circuit_breaker?: CircuitBreakerStatus;
   wallet?: string;
   usdc_balance?: string;
   copy_enabled: boolean;
@@ -0,0 +1,5 @@
+-- Capital sleeves: tag orders and positions with the strategy sleeve they trade out of
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS sleeve VARCHAR(30) NOT NULL DEFAULT 'single_whale';
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS sleeve VARCHAR(30) NOT NULL DEFAULT 'single_whale';
+
+CREATE INDEX IF NOT EXISTS idx_positions_sleeve_status ON positions(sleeve, status);
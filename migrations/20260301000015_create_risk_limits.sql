@@ -0,0 +1,29 @@
+-- Runtime-editable risk limits (single row, edited via PATCH /api/risk-limits)
+CREATE TABLE IF NOT EXISTS risk_limits (
+    id BOOLEAN PRIMARY KEY DEFAULT TRUE CHECK (id),
+    max_position_pct DECIMAL(5,4) NOT NULL DEFAULT 0.20,
+    max_open_positions BIGINT NOT NULL DEFAULT 10,
+    max_daily_loss DECIMAL(18,6) NOT NULL DEFAULT 500,
+    min_spread_to_resolution DECIMAL(5,4) NOT NULL DEFAULT 0.05,
+    max_slippage_pct DECIMAL(5,4) NOT NULL DEFAULT 0.03,
+    max_tail_loss_pct DECIMAL(5,4) NOT NULL DEFAULT 0.25,
+    trailing_stop_pct DECIMAL(6,2) NOT NULL DEFAULT 10,
+    max_position_hold_days BIGINT NOT NULL DEFAULT 7,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Carry over limits previously stored as runtime_config overrides
+INSERT INTO risk_limits (id, max_daily_loss, max_open_positions, trailing_stop_pct, max_position_hold_days)
+SELECT TRUE,
+    COALESCE((SELECT value::DECIMAL FROM runtime_config WHERE key = 'max_daily_loss' AND value ~ '^[0-9]+(\.[0-9]+)?$'), 500),
+    COALESCE((SELECT value::BIGINT FROM runtime_config WHERE key = 'max_open_positions' AND value ~ '^[0-9]+$'), 10),
+    COALESCE((SELECT value::DECIMAL FROM runtime_config WHERE key = 'trailing_stop_pct' AND value ~ '^[0-9]+(\.[0-9]+)?$'), 10),
+    COALESCE((SELECT value::BIGINT FROM runtime_config WHERE key = 'max_position_hold_days' AND value ~ '^[0-9]+$'), 7)
+WHERE EXISTS (
+    SELECT 1 FROM runtime_config
+    WHERE key IN ('max_daily_loss', 'max_open_positions', 'trailing_stop_pct', 'max_position_hold_days')
+)
+ON CONFLICT (id) DO NOTHING;
+
+DELETE FROM runtime_config
+WHERE key IN ('max_daily_loss', 'max_open_positions', 'trailing_stop_pct', 'max_position_hold_days');
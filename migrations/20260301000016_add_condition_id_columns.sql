@@ -0,0 +1,75 @@
+-- Explicit market identifiers. `market_id` has held either a condition_id
+-- (WS listener, data API) or a CLOB token id (chain listener), so the same
+-- market could appear under two keys. `condition_id` is the canonical market
+-- key; `token_id` (already present) identifies the outcome.
+ALTER TABLE whale_trades ADD COLUMN IF NOT EXISTS condition_id VARCHAR(100);
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS condition_id VARCHAR(100);
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS condition_id VARCHAR(100);
+ALTER TABLE market_outcomes ADD COLUMN IF NOT EXISTS condition_id VARCHAR(100);
+
+-- Backfill where market_id already is a condition_id (0x-prefixed or bare hex)
+UPDATE whale_trades
+SET condition_id = CASE WHEN market_id LIKE '0x%' THEN market_id ELSE '0x' || market_id END
+WHERE condition_id IS NULL
+  AND (market_id ~ '^0x[0-9a-fA-F]{64}$' OR (market_id ~ '^[0-9a-fA-F]{64}$' AND market_id ~ '[a-fA-F]'));
+
+UPDATE copy_orders
+SET condition_id = CASE WHEN market_id LIKE '0x%' THEN market_id ELSE '0x' || market_id END
+WHERE condition_id IS NULL
+  AND (market_id ~ '^0x[0-9a-fA-F]{64}$' OR (market_id ~ '^[0-9a-fA-F]{64}$' AND market_id ~ '[a-fA-F]'));
+
+UPDATE positions
+SET condition_id = CASE WHEN market_id LIKE '0x%' THEN market_id ELSE '0x' || market_id END
+WHERE condition_id IS NULL
+  AND (market_id ~ '^0x[0-9a-fA-F]{64}$' OR (market_id ~ '^[0-9a-fA-F]{64}$' AND market_id ~ '[a-fA-F]'));
+
+UPDATE market_outcomes
+SET condition_id = CASE WHEN market_id LIKE '0x%' THEN market_id ELSE '0x' || market_id END
+WHERE condition_id IS NULL
+  AND (market_id ~ '^0x[0-9a-fA-F]{64}$' OR (market_id ~ '^[0-9a-fA-F]{64}$' AND market_id ~ '[a-fA-F]'));
+
+-- Backfill the rest (token-keyed rows) by looking the token up in active_markets
+UPDATE whale_trades t
+SET condition_id = am.condition_id
+FROM active_markets am
+WHERE t.condition_id IS NULL AND am.clob_token_ids LIKE '%"' || t.token_id || '"%';
+
+UPDATE copy_orders o
+SET condition_id = am.condition_id
+FROM active_markets am
+WHERE o.condition_id IS NULL AND am.clob_token_ids LIKE '%"' || o.token_id || '"%';
+
+UPDATE positions p
+SET condition_id = am.condition_id
+FROM active_markets am
+WHERE p.condition_id IS NULL AND am.clob_token_ids LIKE '%"' || p.token_id || '"%';
+
+UPDATE market_outcomes mo
+SET condition_id = am.condition_id
+FROM active_markets am
+WHERE mo.condition_id IS NULL
+  AND am.clob_token_ids LIKE '%"' || COALESCE(mo.token_id, mo.market_id) || '"%';
+
+-- Collapse market_outcomes rows that now share a condition_id, keeping the
+-- resolved row first, then the one already keyed by condition_id
+DELETE FROM market_outcomes
+WHERE id IN (
+    SELECT id FROM (
+        SELECT id, ROW_NUMBER() OVER (
+            PARTITION BY condition_id
+            ORDER BY (outcome <> 'unresolved') DESC, (market_id = condition_id) DESC, created_at
+        ) AS rn
+        FROM market_outcomes
+        WHERE condition_id IS NOT NULL
+    ) ranked
+    WHERE rn > 1
+);
+
+-- Re-key surviving rows by condition_id so lookups from any source converge
+UPDATE market_outcomes
+SET market_id = condition_id, updated_at = NOW()
+WHERE condition_id IS NOT NULL AND market_id <> condition_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_market_outcomes_condition ON market_outcomes(condition_id) WHERE condition_id IS NOT NULL;
+CREATE INDEX IF NOT EXISTS idx_whale_trades_condition ON whale_trades(condition_id);
+CREATE INDEX IF NOT EXISTS idx_positions_condition ON positions(condition_id);
@@ -0,0 +1,2 @@
+-- Time-to-resolution risk rule: skip markets that end within this many hours
+ALTER TABLE risk_limits ADD COLUMN IF NOT EXISTS min_hours_to_resolution BIGINT NOT NULL DEFAULT 24;
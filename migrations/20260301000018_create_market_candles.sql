@@ -0,0 +1,15 @@
+-- Per-token 1-minute OHLC candles built from WS last_trade_price / price_change events
+CREATE TABLE IF NOT EXISTS market_candles (
+    token_id VARCHAR(100) NOT NULL,
+    bucket TIMESTAMPTZ NOT NULL,
+    open DECIMAL(10,6) NOT NULL,
+    high DECIMAL(10,6) NOT NULL,
+    low DECIMAL(10,6) NOT NULL,
+    close DECIMAL(10,6) NOT NULL,
+    volume DECIMAL(18,6) NOT NULL DEFAULT 0,
+    trade_count INTEGER NOT NULL DEFAULT 0,
+    PRIMARY KEY (token_id, bucket)
+);
+
+-- Retention pruning scans by time
+CREATE INDEX IF NOT EXISTS idx_market_candles_bucket ON market_candles(bucket);
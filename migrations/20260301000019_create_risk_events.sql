@@ -0,0 +1,21 @@
+-- Audit trail of signals rejected by risk checks (GET /api/risk/events)
+CREATE TABLE IF NOT EXISTS risk_events (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule VARCHAR(50) NOT NULL,
+    scope VARCHAR(20) NOT NULL,
+    message TEXT NOT NULL,
+    details JSONB NOT NULL DEFAULT '{}',
+    whale_trade_id UUID,
+    wallet VARCHAR(42),
+    market_id VARCHAR(100),
+    condition_id VARCHAR(100),
+    token_id VARCHAR(100),
+    side VARCHAR(4),
+    size DECIMAL(18,6),
+    price DECIMAL(10,6),
+    sleeve VARCHAR(20),
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_risk_events_created ON risk_events(created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_risk_events_rule ON risk_events(rule, created_at DESC);
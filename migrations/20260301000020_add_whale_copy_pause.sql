@@ -0,0 +1,5 @@
+-- Per-whale copy pause: set when our copies of a whale lose money, cleared via
+-- POST /api/whales/:id/copy-resume. copy_resumed_at starts a fresh evaluation window.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS copy_paused_at TIMESTAMPTZ;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS copy_pause_reason TEXT;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS copy_resumed_at TIMESTAMPTZ;
@@ -0,0 +1,3 @@
+-- Wallet buffers checked before live orders: MATIC for gas, USDC kept back for fees
+ALTER TABLE risk_limits ADD COLUMN IF NOT EXISTS min_matic_balance DECIMAL(18,6) NOT NULL DEFAULT 0.5;
+ALTER TABLE risk_limits ADD COLUMN IF NOT EXISTS usdc_fee_buffer DECIMAL(18,6) NOT NULL DEFAULT 5;
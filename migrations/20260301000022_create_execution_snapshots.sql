@@ -0,0 +1,23 @@
+-- Top-of-book snapshot captured when each live order is placed, for
+-- post-trade slippage analysis (thin book vs our latency)
+CREATE TABLE IF NOT EXISTS execution_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    order_id UUID NOT NULL REFERENCES copy_orders(id),
+    token_id VARCHAR(100) NOT NULL,
+    side VARCHAR(4) NOT NULL,
+    target_price DECIMAL(10,6) NOT NULL,
+    order_price DECIMAL(10,6) NOT NULL,
+    best_bid DECIMAL(10,6),
+    best_ask DECIMAL(10,6),
+    bid_depth DECIMAL(18,6) NOT NULL DEFAULT 0,
+    ask_depth DECIMAL(18,6) NOT NULL DEFAULT 0,
+    bids JSONB NOT NULL DEFAULT '[]',
+    asks JSONB NOT NULL DEFAULT '[]',
+    book_hash VARCHAR(100),
+    fetch_ms INTEGER NOT NULL,
+    captured_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_execution_snapshots_order ON execution_snapshots(order_id);
+CREATE INDEX IF NOT EXISTS idx_execution_snapshots_token ON execution_snapshots(token_id, captured_at DESC);
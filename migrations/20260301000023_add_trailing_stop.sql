@@ -0,0 +1,3 @@
+-- Trailing stop mode: the stop price ratchets up with the market and never moves down
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS stop_mode VARCHAR(10) NOT NULL DEFAULT 'static';
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS trailing_stop_price DECIMAL(10,6);
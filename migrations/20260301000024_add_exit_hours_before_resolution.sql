@@ -0,0 +1,2 @@
+-- Time-based exit: close positions this many hours before their market ends (0 = disabled)
+ALTER TABLE risk_limits ADD COLUMN IF NOT EXISTS exit_hours_before_resolution BIGINT NOT NULL DEFAULT 0;
@@ -0,0 +1,16 @@
+-- Operator-defined pre-trade compliance rules, run after risk checks.
+-- rule_type: excluded_tag (value = tag slug/label), max_odds (value = max
+-- entry price, e.g. 0.95) or banned_keyword (value = phrase in question)
+CREATE TABLE IF NOT EXISTS compliance_rules (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule_type VARCHAR(30) NOT NULL,
+    value TEXT NOT NULL,
+    note TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_compliance_rules_enabled ON compliance_rules(enabled);
+
+-- Gamma tags per market (JSON array of slugs/labels), for tag exclusions
+ALTER TABLE active_markets ADD COLUMN IF NOT EXISTS tags TEXT;
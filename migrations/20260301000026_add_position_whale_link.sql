@@ -0,0 +1,6 @@
+-- Link positions to the whale / whale trade that opened them, so a whale's
+-- later sell can be mirrored against the right position
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS whale_id UUID REFERENCES whales(id);
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS whale_trade_id UUID REFERENCES whale_trades(id);
+
+CREATE INDEX IF NOT EXISTS idx_positions_whale ON positions(whale_id);
@@ -0,0 +1,4 @@
+-- Hedging: a losing position can be partially covered by buying the
+-- complementary outcome token instead of exiting
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS hedged_at TIMESTAMPTZ;
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS hedge_of UUID REFERENCES positions(id);
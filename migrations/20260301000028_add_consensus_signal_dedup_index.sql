@@ -0,0 +1,4 @@
+-- Lookup for the consensus signal cooldown: one signal per
+-- (basket, market, direction) within the basket's time window
+CREATE INDEX IF NOT EXISTS idx_consensus_signals_dedup
+    ON consensus_signals (basket_id, market_id, direction, triggered_at DESC);
@@ -0,0 +1,17 @@
+-- Periodic snapshots of the bot's equity for the dashboard equity curve
+CREATE TABLE IF NOT EXISTS portfolio_snapshots (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    -- Free USDC tracked by the capital pools (incl. reservations)
+    bankroll DECIMAL(18,6) NOT NULL,
+    -- Cost basis of open/exiting positions
+    open_exposure DECIMAL(18,6) NOT NULL DEFAULT 0,
+    unrealized_pnl DECIMAL(18,6) NOT NULL DEFAULT 0,
+    -- Cumulative realized PnL across all positions
+    realized_pnl DECIMAL(18,6) NOT NULL DEFAULT 0,
+    -- bankroll + open_exposure + unrealized_pnl
+    equity DECIMAL(18,6) NOT NULL,
+    open_positions INTEGER NOT NULL DEFAULT 0,
+    taken_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_portfolio_snapshots_taken_at ON portfolio_snapshots(taken_at);
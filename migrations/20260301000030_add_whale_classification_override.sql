@@ -0,0 +1,5 @@
+-- Operator override of a whale's automatic classification, set via
+-- PATCH /api/whales/:id/classification. A NULL expiry means it never lapses;
+-- once classification_override_until passes, automatic classification resumes.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS classification_override VARCHAR(20);
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS classification_override_until TIMESTAMPTZ;
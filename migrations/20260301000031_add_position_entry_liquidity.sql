@@ -0,0 +1,3 @@
+-- Market liquidity (from active_markets) when the position was opened, so the
+-- position health report can flag markets whose liquidity has since collapsed.
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS entry_liquidity NUMERIC;
@@ -0,0 +1,5 @@
+-- All-in execution costs of a filled order in USDC: exchange fee from the
+-- CLOB trade's fee rate, and gas when our own signer paid for the settlement
+-- transaction (converted from MATIC at the time of the fill). NULL = unknown.
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS fee_usdc NUMERIC;
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS gas_usdc NUMERIC;
@@ -0,0 +1,7 @@
+-- Leaderboard PnL/volume at seeding (the drift baseline) and at the last drift check.
+-- The baseline is reset to the last observed values when copying is resumed.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS seed_leaderboard_pnl NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS seed_leaderboard_volume NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS leaderboard_pnl NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS leaderboard_volume NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS leaderboard_checked_at TIMESTAMPTZ;
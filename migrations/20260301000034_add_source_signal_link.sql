@@ -0,0 +1,6 @@
+-- Link orders and positions to the basket consensus signal that produced them.
+-- Whale-sourced positions are already linked through whale_id / whale_trade_id.
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS source_signal_id UUID REFERENCES consensus_signals(id);
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS source_signal_id UUID REFERENCES consensus_signals(id);
+
+CREATE INDEX IF NOT EXISTS idx_positions_source_signal ON positions(source_signal_id);
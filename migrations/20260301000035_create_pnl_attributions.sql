@@ -0,0 +1,15 @@
+-- Realized PnL of settled positions attributed to the whales / baskets whose
+-- signals filled them, by share of the position's filled BUY size.
+CREATE TABLE IF NOT EXISTS pnl_attributions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    position_id UUID NOT NULL REFERENCES positions(id),
+    whale_id UUID REFERENCES whales(id),
+    basket_id UUID REFERENCES whale_baskets(id),
+    share NUMERIC NOT NULL,
+    realized_pnl NUMERIC NOT NULL,
+    attributed_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pnl_attributions_position ON pnl_attributions(position_id);
+CREATE INDEX IF NOT EXISTS idx_pnl_attributions_whale ON pnl_attributions(whale_id);
+CREATE INDEX IF NOT EXISTS idx_pnl_attributions_basket ON pnl_attributions(basket_id);
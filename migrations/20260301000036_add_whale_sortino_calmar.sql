@@ -0,0 +1,3 @@
+-- Sortino (downside deviation) and Calmar (return / max drawdown) ratios from the scorer.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS sortino_ratio NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS calmar_ratio NUMERIC;
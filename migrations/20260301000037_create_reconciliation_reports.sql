@@ -0,0 +1,16 @@
+-- End-of-day reconciliation runs: DB positions vs account holdings, DB vs CLOB
+-- open orders, capital ledger vs on-chain USDC (GET /api/reconciliation/reports)
+CREATE TABLE IF NOT EXISTS reconciliation_reports (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    passed BOOLEAN NOT NULL,
+    db_positions INTEGER NOT NULL,
+    account_positions INTEGER NOT NULL,
+    db_open_orders INTEGER NOT NULL,
+    clob_open_orders INTEGER,
+    ledger_balance NUMERIC,
+    chain_balance NUMERIC,
+    discrepancies JSONB NOT NULL DEFAULT '[]',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_reconciliation_reports_created ON reconciliation_reports(created_at DESC);
@@ -0,0 +1,4 @@
+-- Historical max drawdown of the whale's cumulative resolved-trade PnL:
+-- absolute (USDC) and as a percent of the peak (used by basket admission).
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS max_drawdown NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS max_drawdown_pct NUMERIC;
@@ -0,0 +1,4 @@
+-- Gross wins / gross losses and average win / average loss of the whale's
+-- resolved trades. NULL when undefined (no losses, or no wins and losses).
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS profit_factor NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS payoff_ratio NUMERIC;
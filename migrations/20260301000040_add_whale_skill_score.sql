@@ -0,0 +1,3 @@
+-- 1 − probability the whale's resolved-trade record is luck (one-sided t-test
+-- on per-trade returns). Used by basket admission and the signal gate.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS skill_score NUMERIC;
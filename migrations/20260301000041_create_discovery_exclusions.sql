@@ -0,0 +1,14 @@
+-- Operator-defined filters that keep markets out of discovery (and so out of
+-- the WS subscription list). rule_type: category (Gamma category or tag),
+-- keyword (phrase in question), resolves_within_hours (value = hours) or
+-- max_spread (value = max bid/ask spread, e.g. 0.05)
+CREATE TABLE IF NOT EXISTS discovery_exclusions (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    rule_type VARCHAR(30) NOT NULL,
+    value TEXT NOT NULL,
+    note TEXT,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_discovery_exclusions_enabled ON discovery_exclusions(enabled);
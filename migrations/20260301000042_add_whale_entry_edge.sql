@@ -0,0 +1,5 @@
+-- Average favourable price move (probability points) over a fixed horizon
+-- after each whale entry, from recorded candles, and the number of entries
+-- it is measured over. Lets unresolved trading history count as evidence.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS entry_edge NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS entry_edge_samples INTEGER;
@@ -0,0 +1,2 @@
+-- Fraction of capital never committed to entries, kept for stop-loss exits and fees
+ALTER TABLE risk_limits ADD COLUMN IF NOT EXISTS reserve_floor_pct DECIMAL(5,4) NOT NULL DEFAULT 0.10;
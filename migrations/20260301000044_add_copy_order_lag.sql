@@ -0,0 +1,8 @@
+-- Price lost to copying late: our fill vs. the whale's entry price, as a fraction
+-- of the whale's price (positive = we paid more on a buy / got less on a sell)
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS copy_lag DECIMAL(10,6);
+
+UPDATE copy_orders o
+SET copy_lag = CASE WHEN o.side = 'SELL' THEN t.price - o.fill_price ELSE o.fill_price - t.price END / t.price
+FROM whale_trades t
+WHERE t.id = o.whale_trade_id AND o.status = 'filled' AND o.fill_price IS NOT NULL AND t.price > 0;
@@ -0,0 +1,24 @@
+-- Suspicious-timing evidence against a whale trade. kind: pre_resolution
+-- (large winning entry shortly before the market resolved) or pre_jump
+-- (large entry shortly before a sharp favourable price move)
+CREATE TABLE IF NOT EXISTS insider_flags (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    whale_id UUID NOT NULL REFERENCES whales(id) ON DELETE CASCADE,
+    trade_id UUID NOT NULL REFERENCES whale_trades(id) ON DELETE CASCADE,
+    kind VARCHAR(20) NOT NULL,
+    market_id VARCHAR(256) NOT NULL,
+    token_id VARCHAR(256) NOT NULL,
+    side VARCHAR(4) NOT NULL,
+    entry_price DECIMAL(10,6) NOT NULL,
+    notional DECIMAL(18,6) NOT NULL,
+    traded_at TIMESTAMPTZ NOT NULL,
+    -- Hours from entry to resolution (pre_resolution only)
+    lead_hours DECIMAL(10,2),
+    -- Favourable price move after entry (to 1/0 at resolution, or to the jump extreme)
+    price_move DECIMAL(10,6) NOT NULL,
+    evidence TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (trade_id, kind)
+);
+
+CREATE INDEX IF NOT EXISTS idx_insider_flags_whale ON insider_flags(whale_id, created_at DESC);
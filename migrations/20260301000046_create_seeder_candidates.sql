@@ -0,0 +1,19 @@
+-- Addresses an operator queued for whale seeder evaluation. The next seeder
+-- cycle runs them through the same history fetch and anti-signal filters as
+-- leaderboard candidates. status: pending, seeded, rejected or failed
+-- (evaluation couldn't complete, e.g. the data API errored)
+CREATE TABLE IF NOT EXISTS seeder_candidates (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    address VARCHAR(42) NOT NULL,
+    note TEXT,
+    status VARCHAR(20) NOT NULL DEFAULT 'pending',
+    verdict TEXT,
+    whale_id UUID REFERENCES whales(id) ON DELETE SET NULL,
+    requested_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    evaluated_at TIMESTAMPTZ
+);
+
+-- One pending entry per address
+CREATE UNIQUE INDEX IF NOT EXISTS idx_seeder_candidates_pending
+    ON seeder_candidates(address) WHERE status = 'pending';
+CREATE INDEX IF NOT EXISTS idx_seeder_candidates_requested ON seeder_candidates(requested_at DESC);
@@ -0,0 +1,9 @@
+-- How long a whale typically holds a position and how often it sells before
+-- the market resolves, plus the exit style derived from it. Positions copy
+-- the style at open, so a later profile change doesn't flip live positions.
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS avg_hold_hours NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS early_exit_ratio NUMERIC;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS hold_profile_samples INTEGER;
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS exit_style VARCHAR(32);
+
+ALTER TABLE positions ADD COLUMN IF NOT EXISTS exit_style VARCHAR(32);
@@ -0,0 +1,17 @@
+-- Market-level net whale flow: buy-minus-sell notional from all tracked whales
+-- into one outcome token over a rolling window. Each row is a signal emitted
+-- into the flow sleeve; at most one per token within the window.
+CREATE TABLE IF NOT EXISTS flow_signals (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    market_id VARCHAR(256) NOT NULL,
+    token_id VARCHAR(256) NOT NULL,
+    buy_notional NUMERIC NOT NULL,
+    sell_notional NUMERIC NOT NULL,
+    net_notional NUMERIC NOT NULL,
+    whales INTEGER NOT NULL,
+    window_minutes INTEGER NOT NULL,
+    triggered_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_flow_signals_dedup ON flow_signals (token_id, triggered_at DESC);
+CREATE INDEX IF NOT EXISTS idx_whale_trades_token_time ON whale_trades (token_id, traded_at DESC);
@@ -0,0 +1,20 @@
+-- Trade tape: every trade print ingested from the market WS and the chain
+-- listener, whale-grade or not, for a per-market time-and-sales view.
+-- Short-lived; pruned after TRADE_TAPE_RETENTION_HOURS.
+CREATE TABLE IF NOT EXISTS market_trades (
+    id BIGSERIAL PRIMARY KEY,
+    market_id VARCHAR(256) NOT NULL,
+    token_id VARCHAR(256) NOT NULL,
+    side VARCHAR(4) NOT NULL,
+    size NUMERIC NOT NULL,
+    price NUMERIC NOT NULL,
+    -- Taker/maker wallet, when the source carries one (chain)
+    wallet VARCHAR(64),
+    -- ws | chain
+    source VARCHAR(16) NOT NULL,
+    traded_at TIMESTAMPTZ NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_market_trades_market ON market_trades (market_id, traded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_market_trades_token ON market_trades (token_id, traded_at DESC);
+CREATE INDEX IF NOT EXISTS idx_market_trades_traded_at ON market_trades (traded_at);
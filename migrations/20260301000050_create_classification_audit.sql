@@ -0,0 +1,21 @@
+-- Why the wallet classifier labelled a whale informed, bot or market_maker:
+-- the features it measured and the rules that decided it. A row is added when
+-- a whale's automatic classification changes; while it holds, the latest row
+-- is refreshed with the current features (GET /api/whales/:id/classification-audit)
+CREATE TABLE IF NOT EXISTS classification_audit (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    whale_id UUID NOT NULL REFERENCES whales(id) ON DELETE CASCADE,
+    classification VARCHAR(32) NOT NULL,
+    classifier VARCHAR(32) NOT NULL,
+    trade_count INTEGER NOT NULL,
+    trades_per_month NUMERIC NOT NULL,
+    dual_side_ratio NUMERIC NOT NULL,
+    avg_hold_hours NUMERIC NOT NULL,
+    notional_cv NUMERIC NOT NULL,
+    confidence NUMERIC,
+    rules TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_classification_audit_whale ON classification_audit (whale_id, created_at DESC);
@@ -0,0 +1,29 @@
+-- Whale tiers: a whale's scores place it in the first tier whose thresholds it
+-- meets (A before B before C). Each tier has its own signal gates and sizing,
+-- replacing the global gate set when WHALE_TIERS_ENABLED is on. Editable via
+-- PATCH /api/tiers/:tier.
+CREATE TABLE IF NOT EXISTS whale_tiers (
+    tier VARCHAR(1) PRIMARY KEY CHECK (tier IN ('A', 'B', 'C')),
+    -- Entry thresholds
+    min_win_rate NUMERIC NOT NULL,
+    min_skill_score NUMERIC NOT NULL DEFAULT 0,
+    min_total_trades INTEGER NOT NULL,
+    -- Signal gates
+    min_signal_ev NUMERIC NOT NULL,
+    signal_notional_floor NUMERIC NOT NULL,
+    -- Sizing
+    sizing VARCHAR(16) NOT NULL,
+    size_multiplier NUMERIC NOT NULL DEFAULT 1,
+    base_amount NUMERIC NOT NULL DEFAULT 0,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- A: full Kelly (twice the engine's half-Kelly); B: half-Kelly; C: small fixed bets
+INSERT INTO whale_tiers (tier, min_win_rate, min_skill_score, min_total_trades, min_signal_ev, signal_notional_floor, sizing, size_multiplier, base_amount)
+VALUES
+    ('A', 0.65, 0.95, 200, 100, 1000, 'kelly', 2, 0),
+    ('B', 0.60, 0, 100, 50, 1000, 'kelly', 1, 0),
+    ('C', 0.55, 0, 50, 25, 2500, 'fixed', 1, 10)
+ON CONFLICT (tier) DO NOTHING;
+
+ALTER TABLE whales ADD COLUMN IF NOT EXISTS tier VARCHAR(1);
@@ -0,0 +1,36 @@
+-- Named bundles of signal-gate thresholds (e.g. conservative vs aggressive).
+-- At most one is active; the pipeline layers it over the env gates and any
+-- runtime_config overrides on every trade, so switching is immediate
+-- (POST /api/gate-profiles/:name/activate). NULL gates keep the current value.
+CREATE TABLE IF NOT EXISTS gate_profiles (
+    name VARCHAR(64) PRIMARY KEY,
+    description TEXT,
+    is_active BOOLEAN NOT NULL DEFAULT FALSE,
+    min_signal_win_rate NUMERIC,
+    min_resolved_for_signal INTEGER,
+    min_total_trades_for_signal INTEGER,
+    min_signal_ev NUMERIC,
+    min_signal_profit_factor NUMERIC,
+    min_signal_skill_score NUMERIC,
+    min_entry_edge NUMERIC,
+    signal_notional_liquidity_pct NUMERIC,
+    signal_notional_floor NUMERIC,
+    max_signal_notional NUMERIC,
+    assumed_slippage_pct NUMERIC,
+    first_mover_size_multiplier NUMERIC,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE UNIQUE INDEX IF NOT EXISTS idx_gate_profiles_one_active ON gate_profiles (is_active) WHERE is_active;
+
+INSERT INTO gate_profiles (
+    name, description, min_signal_win_rate, min_resolved_for_signal, min_total_trades_for_signal,
+    min_signal_ev, min_signal_profit_factor, min_signal_skill_score, signal_notional_floor,
+    assumed_slippage_pct, first_mover_size_multiplier
+)
+VALUES
+    ('conservative', 'Proven whales only, large trades, pessimistic slippage',
+        0.65, 30, 200, 100, 1.5, 0.95, 2500, 0.03, 1),
+    ('aggressive', 'Copy more whales earlier, smaller trades, leaning on first movers',
+        0.55, 10, 50, 25, 1.1, 0.80, 1000, 0.01, 1.5)
+ON CONFLICT (name) DO NOTHING;
@@ -0,0 +1,4 @@
+-- How a basket weighs its whales' consensus votes: equal (one whale, one
+-- vote), win_rate, ev (the whale's current expected value per trade) or
+-- notional (the size of the voting trade).
+ALTER TABLE whale_baskets ADD COLUMN IF NOT EXISTS vote_weighting VARCHAR(16) NOT NULL DEFAULT 'equal';
@@ -0,0 +1,12 @@
+-- Audit log of basket membership: every whale added to or removed from a
+-- basket, by the rebalancing job or an operator, with the reason.
+CREATE TABLE IF NOT EXISTS basket_membership_changes (
+    id          UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    basket_id   UUID NOT NULL REFERENCES whale_baskets(id) ON DELETE CASCADE,
+    whale_id    UUID NOT NULL REFERENCES whales(id) ON DELETE CASCADE,
+    action      VARCHAR(8) NOT NULL CHECK (action IN ('added', 'removed')),
+    reason      TEXT NOT NULL,
+    created_at  TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX idx_basket_membership_changes_basket ON basket_membership_changes (basket_id, created_at DESC);
@@ -0,0 +1,13 @@
+-- Consensus cooldown per (basket, market): each signal records until when it
+-- suppresses further signals for its basket and market in either direction,
+-- so member trades arriving after consensus don't re-trigger it.
+ALTER TABLE consensus_signals ADD COLUMN IF NOT EXISTS cooldown_until TIMESTAMPTZ NOT NULL DEFAULT NOW();
+
+-- Existing signals cooled down for their basket's time window
+UPDATE consensus_signals cs
+SET cooldown_until = cs.triggered_at + make_interval(hours => wb.time_window_hours)
+FROM whale_baskets wb
+WHERE wb.id = cs.basket_id;
+
+CREATE INDEX IF NOT EXISTS idx_consensus_signals_cooldown
+    ON consensus_signals (basket_id, market_id, cooldown_until DESC);
@@ -0,0 +1,14 @@
+-- Early-warning alerts sent when a basket's vote share on a market crosses the
+-- building threshold without reaching consensus. No orders are placed; the log
+-- keeps one alert per (basket, market, direction) per cooldown.
+CREATE TABLE IF NOT EXISTS consensus_building_alerts (
+    id              UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    basket_id       UUID NOT NULL REFERENCES whale_baskets(id) ON DELETE CASCADE,
+    market_id       VARCHAR(256) NOT NULL,
+    direction       VARCHAR(4) NOT NULL,
+    consensus_pct   DECIMAL(5,4) NOT NULL,
+    alerted_at      TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_consensus_building_alerts_dedup
+    ON consensus_building_alerts (basket_id, market_id, direction, alerted_at DESC);
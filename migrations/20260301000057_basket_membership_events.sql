@@ -0,0 +1,17 @@
+-- Basket membership audit log becomes basket_membership_events and records
+-- who made each change: an operator, the pipeline's auto-assign or the
+-- rebalancing job.
+ALTER TABLE basket_membership_changes RENAME TO basket_membership_events;
+ALTER INDEX idx_basket_membership_changes_basket RENAME TO idx_basket_membership_events_basket;
+
+ALTER TABLE basket_membership_events
+    ADD COLUMN actor VARCHAR(16) NOT NULL DEFAULT 'operator'
+    CHECK (actor IN ('operator', 'auto_assign', 'rebalance'));
+
+UPDATE basket_membership_events SET actor = CASE
+    WHEN reason LIKE 'auto-assigned%' THEN 'auto_assign'
+    WHEN reason LIKE '%manually' THEN 'operator'
+    ELSE 'rebalance'
+END;
+
+ALTER TABLE basket_membership_events ALTER COLUMN actor DROP DEFAULT;
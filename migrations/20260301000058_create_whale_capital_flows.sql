@@ -0,0 +1,22 @@
+-- Large USDC deposits into / withdrawals out of tracked whale wallets seen
+-- on Polygon. Transfers with Polymarket's own contracts (trading, splits,
+-- merges) aren't capital flows and are never recorded.
+CREATE TABLE IF NOT EXISTS whale_capital_flows (
+    id UUID PRIMARY KEY DEFAULT gen_random_uuid(),
+    whale_id UUID NOT NULL REFERENCES whales(id) ON DELETE CASCADE,
+    direction VARCHAR(10) NOT NULL CHECK (direction IN ('deposit', 'withdrawal')),
+    amount DECIMAL(18,6) NOT NULL,
+    counterparty VARCHAR(42) NOT NULL,
+    -- Whale's USDC balance when the transfer was processed
+    balance_after DECIMAL(18,6),
+    -- Withdrawals only: percent of the whale's USDC that left
+    capital_share_pct DECIMAL(10,2),
+    tx_hash VARCHAR(66) NOT NULL,
+    log_index BIGINT NOT NULL,
+    block_number BIGINT NOT NULL,
+    occurred_at TIMESTAMPTZ NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (tx_hash, log_index, whale_id)
+);
+
+CREATE INDEX IF NOT EXISTS idx_whale_capital_flows_whale ON whale_capital_flows(whale_id, occurred_at DESC);
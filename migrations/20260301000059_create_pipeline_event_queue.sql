@@ -0,0 +1,17 @@
+-- Durable hand-off between ingestion and the pipeline (PIPELINE_DURABLE_QUEUE).
+-- Every WhaleTradeEvent is appended here before the pipeline sees it; each
+-- consumer records the last event it has fully processed, so events in
+-- flight when the process dies are replayed on restart (at-least-once).
+CREATE TABLE IF NOT EXISTS pipeline_events (
+    id BIGSERIAL PRIMARY KEY,
+    payload JSONB NOT NULL,
+    enqueued_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_pipeline_events_enqueued ON pipeline_events(enqueued_at);
+
+CREATE TABLE IF NOT EXISTS pipeline_consumer_offsets (
+    consumer VARCHAR(64) PRIMARY KEY,
+    last_event_id BIGINT NOT NULL,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
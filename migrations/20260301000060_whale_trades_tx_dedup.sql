@@ -0,0 +1,26 @@
+-- One row per whale trade however many sources report it: the chain
+-- listener and the whale trade poller both see the same fill. A trade is
+-- identified by wallet, transaction, token and side — a wallet's fills on
+-- one token and side within a transaction count as one trade. Rows without
+-- a tx hash (WS trades, older imports) are never deduplicated.
+ALTER TABLE whale_trades ADD COLUMN IF NOT EXISTS fill_index BIGINT;
+
+UPDATE whale_trades SET tx_hash = LOWER(tx_hash) WHERE tx_hash <> LOWER(tx_hash);
+
+-- Earlier double-counted trades keep their rows (orders and positions point
+-- at them) but only the first recorded keeps its tx hash
+UPDATE whale_trades t
+SET tx_hash = NULL
+FROM (
+    SELECT id, ROW_NUMBER() OVER (
+        PARTITION BY whale_id, tx_hash, token_id, side
+        ORDER BY created_at, id
+    ) AS n
+    FROM whale_trades
+    WHERE tx_hash IS NOT NULL
+) d
+WHERE t.id = d.id AND d.n > 1;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_whale_trades_tx
+    ON whale_trades (whale_id, tx_hash, token_id, side)
+    WHERE tx_hash IS NOT NULL;
@@ -0,0 +1,13 @@
+-- A taker order filled against several makers emits one fill per maker leg,
+-- all in the same transaction, token and side: chain fills are identified by
+-- their log index instead. Rows without one (trade poller, imports) are
+-- deduplicated against the transaction when they are inserted.
+DROP INDEX IF EXISTS uq_whale_trades_tx;
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_whale_trades_tx_fill
+    ON whale_trades (whale_id, tx_hash, token_id, side, fill_index)
+    WHERE tx_hash IS NOT NULL AND fill_index IS NOT NULL;
+
+CREATE INDEX IF NOT EXISTS idx_whale_trades_tx
+    ON whale_trades (tx_hash)
+    WHERE tx_hash IS NOT NULL;
@@ -0,0 +1,4 @@
+-- Consensus held back because another basket holds the opposite consensus
+-- (BASKET_CONFLICT_MODE=block). It is never traded or announced, but keeps
+-- the opposite side blocked for as long as it cools down.
+ALTER TABLE consensus_signals ADD COLUMN IF NOT EXISTS blocked BOOLEAN NOT NULL DEFAULT FALSE;
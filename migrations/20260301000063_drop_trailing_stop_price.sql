@@ -0,0 +1,2 @@
+-- The trailing stop is derived from peak_price, which only moves up
+ALTER TABLE positions DROP COLUMN IF EXISTS trailing_stop_price;
@@ -0,0 +1,3 @@
+-- Basket consensus signals record their subject as `basket:{name}`, which
+-- does not fit an address-sized column
+ALTER TABLE risk_events ALTER COLUMN wallet TYPE TEXT;
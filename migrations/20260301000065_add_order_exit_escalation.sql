@@ -0,0 +1,5 @@
+-- Exit escalation state, kept on the order so it survives restarts: how many
+-- reprices led to this exit order, and when the alert for running out of
+-- reprices was sent
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS exit_reprices INTEGER NOT NULL DEFAULT 0;
+ALTER TABLE copy_orders ADD COLUMN IF NOT EXISTS exit_alerted_at TIMESTAMPTZ;
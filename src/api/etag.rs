@@ -0,0 +1,140 @@
+use axum::{
+    body::Body,
+    extract::Request,
+    http::{header, HeaderValue, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use sha2::{Digest, Sha256};
+
+/// Largest response body the middleware will buffer to hash.
+const MAX_ETAG_BODY_BYTES: usize = 16 * 1024 * 1024;
+
+/// ETag / If-None-Match middleware for read-heavy GET endpoints.
+///
+/// Hashes the JSON body into a weak ETag and answers `304 Not Modified` when the
+/// client already holds the same representation, so dashboard polls of
+/// unchanged data skip the payload. Non-GET and non-200 responses pass through.
+pub async fn etag(req: Request, next: Next) -> Response {
+    if req.method() != Method::GET {
+        return next.run(req).await;
+    }
+
+    let if_none_match = req.headers().get(header::IF_NONE_MATCH).cloned();
+    let resp = next.run(req).await;
+    if resp.status() != StatusCode::OK {
+        return resp;
+    }
+
+    let (mut parts, body) = resp.into_parts();
+    let bytes = match axum::body::to_bytes(body, MAX_ETAG_BODY_BYTES).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            tracing::warn!(error = %e, "ETag: failed to buffer response body");
+            return StatusCode::INTERNAL_SERVER_ERROR.into_response();
+        }
+    };
+
+    let tag = etag_for(&bytes);
+    let Ok(tag_value) = HeaderValue::from_str(&tag) else {
+        return Response::from_parts(parts, Body::from(bytes));
+    };
+    parts.headers.insert(header::ETAG, tag_value.clone());
+    // Allow caching but force revalidation on every poll
+    parts
+        .headers
+        .insert(header::CACHE_CONTROL, HeaderValue::from_static("private, no-cache"));
+
+    if if_none_match
+        .as_ref()
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| etag_matches(v, &tag))
+    {
+        let mut not_modified = StatusCode::NOT_MODIFIED.into_response();
+        not_modified.headers_mut().insert(header::ETAG, tag_value);
+        if let Some(cc) = parts.headers.get(header::CACHE_CONTROL) {
+            not_modified.headers_mut().insert(header::CACHE_CONTROL, cc.clone());
+        }
+        return not_modified;
+    }
+
+    Response::from_parts(parts, Body::from(bytes))
+}
+
+/// Weak ETag over the body — weak because compression may re-encode it.
+fn etag_for(body: &[u8]) -> String {
+    let digest = Sha256::digest(body);
+    let hex: String = digest[..16].iter().map(|b| format!("{b:02x}")).collect();
+    format!("W/\"{hex}\"")
+}
+
+/// Weak comparison of an `If-None-Match` header against our ETag.
+fn etag_matches(if_none_match: &str, tag: &str) -> bool {
+    let opaque = |t: &str| t.trim().trim_start_matches("W/").to_string();
+    let ours = opaque(tag);
+    if_none_match
+        .split(',')
+        .any(|candidate| candidate.trim() == "*" || opaque(candidate) == ours)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{middleware, routing::get, Router};
+    use tower::ServiceExt;
+
+    fn app() -> Router {
+        Router::new()
+            .route("/data", get(|| async { r#"{"success":true,"data":[1,2,3]}"# }))
+            .layer(middleware::from_fn(etag))
+    }
+
+    #[tokio::test]
+    async fn test_etag_returns_304_when_unchanged() {
+        let resp = app()
+            .oneshot(Request::builder().uri("/data").body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+        let tag = resp.headers().get(header::ETAG).unwrap().to_str().unwrap().to_string();
+        assert!(tag.starts_with("W/\""));
+
+        let resp = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/data")
+                    .header(header::IF_NONE_MATCH, &tag)
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        let body = axum::body::to_bytes(resp.into_body(), usize::MAX).await.unwrap();
+        assert!(body.is_empty());
+
+        let resp = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/data")
+                    .header(header::IF_NONE_MATCH, "W/\"stale\"")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::OK);
+    }
+
+    #[test]
+    fn test_etag_matches_weak_and_lists() {
+        assert!(etag_matches("\"abc\"", "W/\"abc\""));
+        assert!(etag_matches("W/\"x\", W/\"abc\"", "W/\"abc\""));
+        assert!(etag_matches("*", "W/\"abc\""));
+        assert!(!etag_matches("W/\"abd\"", "W/\"abc\""));
+    }
+}
@@ -0,0 +1,84 @@
+use std::time::{Duration, Instant};
+
+use axum::extract::{Path, State};
+use axum::Json;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+use crate::errors::AppError;
+use crate::services::job_trigger::{Job, JobTrigger};
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// A cycle still running after this is reported as failed; the job finishes
+/// it in the background.
+const JOB_RUN_TIMEOUT: Duration = Duration::from_secs(300);
+
+#[derive(Serialize)]
+pub struct JobRun {
+    pub job: &'static str,
+    pub started_at: DateTime<Utc>,
+    pub duration_ms: u64,
+    /// The cycle's own summary stats.
+    pub summary: serde_json::Value,
+}
+
+/// POST /api/admin/run/:job — run one cycle of a background job now
+/// (whale_seeder, resolution, fill_poller, market_discovery, rescore,
+/// basket_rebalance) and return its summary instead of waiting for the next interval.
+pub async fn run_job(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<JobRun>>, AppError> {
+    let job = Job::parse(&name).ok_or_else(|| {
+        let known: Vec<&str> = Job::ALL.iter().map(Job::as_str).collect();
+        AppError::NotFound(format!("unknown job '{name}' (one of: {})", known.join(", ")))
+    })?;
+
+    let started_at = Utc::now();
+    let started = Instant::now();
+    let triggers = &state.job_triggers;
+    let summary = match job {
+        Job::WhaleSeeder => to_value(failed(job, run_cycle(job, &triggers.whale_seeder).await?)?)?,
+        Job::Resolution => to_value(failed(job, run_cycle(job, &triggers.resolution).await?)?)?,
+        Job::FillPoller => to_value(failed(job, run_cycle(job, &triggers.fill_poller).await?)?)?,
+        Job::MarketDiscovery => to_value(run_cycle(job, &triggers.market_discovery).await?)?,
+        Job::Rescore => to_value(failed(job, run_cycle(job, &triggers.rescore).await?)?)?,
+        Job::BasketRebalance => to_value(failed(job, run_cycle(job, &triggers.basket_rebalance).await?)?)?,
+    };
+
+    tracing::info!(job = %job, summary = %summary, "Job cycle run on demand");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(JobRun {
+            job: job.as_str(),
+            started_at,
+            duration_ms: started.elapsed().as_millis() as u64,
+            summary,
+        }),
+        error: None,
+    }))
+}
+
+/// Run one cycle of `job` and wait up to `JOB_RUN_TIMEOUT` for its reply.
+async fn run_cycle<S>(job: Job, trigger: &Option<JobTrigger<S>>) -> Result<S, AppError> {
+    let trigger = trigger
+        .as_ref()
+        .ok_or_else(|| AppError::BadRequest(format!("{job} is not running in this deployment")))?;
+
+    match tokio::time::timeout(JOB_RUN_TIMEOUT, trigger.run()).await {
+        Ok(Some(reply)) => Ok(reply),
+        Ok(None) => Err(anyhow::anyhow!("{job} stopped before finishing the cycle").into()),
+        Err(_) => Err(anyhow::anyhow!("{job} did not finish within {}s", JOB_RUN_TIMEOUT.as_secs()).into()),
+    }
+}
+
+fn failed<S>(job: Job, result: Result<S, String>) -> Result<S, AppError> {
+    result.map_err(|e| anyhow::anyhow!("{job} cycle failed: {e}").into())
+}
+
+fn to_value<S: Serialize>(summary: S) -> Result<serde_json::Value, AppError> {
+    serde_json::to_value(summary).map_err(|e| AppError::Internal(e.into()))
+}
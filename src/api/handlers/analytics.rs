@@ -3,6 +3,10 @@ use axum::Json;
 use rust_decimal::Decimal;
 use serde::Serialize;
 
+use crate::db::order_repo;
+use crate::execution::copy_profiles::parse_profiles;
+use crate::execution::sleeves::SleeveAllocation;
+use crate::models::Sleeve;
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -22,6 +26,12 @@ pub struct PerformanceMetrics {
     pub avg_profit_per_trade: String,
     pub best_trade: String,
     pub worst_trade: String,
+    /// Fees paid on filled orders (USDC).
+    pub total_fees: String,
+    /// Gas paid on filled orders (USDC).
+    pub total_gas: String,
+    /// Realized profit net of fees and gas.
+    pub net_profit: String,
 }
 
 pub async fn pnl_history(State(state): State<AppState>) -> Json<Vec<PnlDataPoint>> {
@@ -104,6 +114,10 @@ pub async fn performance(State(state): State<AppState>) -> Json<PerformanceMetri
     .map(|r| r.0.unwrap_or(Decimal::ZERO))
     .unwrap_or(Decimal::ZERO);
 
+    let (total_fees, total_gas) = order_repo::get_total_costs(&state.db)
+        .await
+        .unwrap_or((Decimal::ZERO, Decimal::ZERO));
+
     let win_rate = if total_trades > 0 {
         Decimal::from(win_count) / Decimal::from(total_trades)
     } else {
@@ -125,5 +139,94 @@ pub async fn performance(State(state): State<AppState>) -> Json<PerformanceMetri
         avg_profit_per_trade: avg_profit.to_string(),
         best_trade: best_trade.to_string(),
         worst_trade: worst_trade.to_string(),
+        total_fees: total_fees.to_string(),
+        total_gas: total_gas.to_string(),
+        net_profit: (total_profit - total_fees - total_gas).to_string(),
     })
 }
+
+#[derive(Serialize)]
+pub struct SleevePerformance {
+    /// Sleeve, or the copy profile's name for a profile's own pool.
+    pub sleeve: String,
+    /// Set on a copy profile's pool.
+    pub profile: Option<String>,
+    pub weight: String,
+    pub allocated_capital: String,
+    pub open_positions: i64,
+    pub open_cost_basis: String,
+    pub unrealized_pnl: String,
+    pub closed_trades: i64,
+    pub win_count: i64,
+    pub win_rate: String,
+    pub realized_pnl: String,
+}
+
+/// Per-sleeve and per-copy-profile allocation and PnL, so each strategy's
+/// drawdown is visible on its own.
+pub async fn sleeve_performance(State(state): State<AppState>) -> Json<Vec<SleevePerformance>> {
+    let profiles = parse_profiles(&state.config.copy_profiles);
+    let allocation = SleeveAllocation::parse(&state.config.sleeve_weights).with_profiles(&profiles);
+
+    #[allow(clippy::type_complexity)]
+    let rows: Vec<(String, i64, Option<Decimal>, Option<Decimal>, i64, i64, Option<Decimal>)> =
+        sqlx::query_as(
+            r#"
+            SELECT sleeve,
+                   COUNT(*) FILTER (WHERE status = 'open'),
+                   SUM(size * avg_entry_price) FILTER (WHERE status = 'open'),
+                   SUM(unrealized_pnl) FILTER (WHERE status = 'open'),
+                   COUNT(*) FILTER (WHERE status = 'closed' AND realized_pnl IS NOT NULL),
+                   COUNT(*) FILTER (WHERE status = 'closed' AND realized_pnl > 0),
+                   SUM(realized_pnl) FILTER (WHERE status = 'closed')
+            FROM positions
+            GROUP BY sleeve
+            "#,
+        )
+        .fetch_all(&state.db)
+        .await
+        .unwrap_or_default();
+
+    let labels = Sleeve::ALL
+        .iter()
+        .map(|s| (s.as_str(), None))
+        .chain(profiles.iter().map(|p| (p.name.as_str(), Some(p.name.clone()))));
+    let sleeves = labels
+        .map(|(label, profile)| {
+            let weight = allocation.label_weight(label);
+            let row = rows.iter().find(|r| r.0 == label);
+            let (open_positions, cost_basis, unrealized, closed, wins, realized) = match row {
+                Some(r) => (
+                    r.1,
+                    r.2.unwrap_or(Decimal::ZERO),
+                    r.3.unwrap_or(Decimal::ZERO),
+                    r.4,
+                    r.5,
+                    r.6.unwrap_or(Decimal::ZERO),
+                ),
+                None => (0, Decimal::ZERO, Decimal::ZERO, 0, 0, Decimal::ZERO),
+            };
+            let win_rate = if closed > 0 {
+                Decimal::from(wins) / Decimal::from(closed)
+            } else {
+                Decimal::ZERO
+            };
+
+            SleevePerformance {
+                sleeve: label.to_string(),
+                profile,
+                weight: weight.to_string(),
+                allocated_capital: (state.config.bankroll * weight).to_string(),
+                open_positions,
+                open_cost_basis: cost_basis.to_string(),
+                unrealized_pnl: unrealized.to_string(),
+                closed_trades: closed,
+                win_count: wins,
+                win_rate: win_rate.to_string(),
+                realized_pnl: realized.to_string(),
+            }
+        })
+        .collect();
+
+    Json(sleeves)
+}
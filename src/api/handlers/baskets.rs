@@ -1,13 +1,22 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::Json;
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::db::basket_repo;
+use crate::db::{attribution_repo, basket_repo, insider_repo};
 use crate::errors::AppError;
-use crate::intelligence::basket::check_admission;
-use crate::models::{ConsensusSignal, Whale, WhaleBasket};
+use crate::intelligence::basket::{check_admission, replay_consensus};
+use crate::models::{
+    BasketBacktest, BasketMembershipEvent, BasketPerformance, ConsensusSignal, VoteWeighting, Whale,
+    WhaleBasket,
+};
+
+/// Longest date range a backtest replays.
+const MAX_BACKTEST_DAYS: i64 = 365;
+/// Membership events embedded in the basket detail response.
+const DETAIL_MEMBERSHIP_EVENTS: i64 = 20;
 use crate::AppState;
 
 use super::whales::ApiResponse;
@@ -16,12 +25,65 @@ use super::whales::ApiResponse;
 // DTOs
 // ---------------------------------------------------------------------------
 
+#[derive(Deserialize)]
+pub struct BacktestQuery {
+    /// Range start (default 30 days before `to`).
+    pub from: Option<DateTime<Utc>>,
+    /// Range end (default now).
+    pub to: Option<DateTime<Utc>>,
+}
+
+#[derive(Serialize)]
+pub struct BasketDetail {
+    #[serde(flatten)]
+    pub basket: WhaleBasket,
+    /// Most recent adds and removes, newest first; the full log is at
+    /// `/api/baskets/{id}/membership-events`.
+    pub membership_events: Vec<BasketMembershipEvent>,
+}
+
 #[derive(Deserialize)]
 pub struct CreateBasketRequest {
     pub name: String,
     pub category: String,
     pub consensus_threshold: Option<Decimal>,
     pub time_window_hours: Option<i32>,
+    /// `equal` (default), `win_rate`, `ev` or `notional`.
+    pub vote_weighting: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateBasketRequest {
+    pub category: Option<String>,
+    pub consensus_threshold: Option<Decimal>,
+    pub time_window_hours: Option<i32>,
+    pub min_wallets: Option<i32>,
+    pub max_wallets: Option<i32>,
+    /// `equal`, `win_rate`, `ev` or `notional`.
+    pub vote_weighting: Option<String>,
+}
+
+impl UpdateBasketRequest {
+    fn apply_to(self, basket: &mut WhaleBasket) {
+        if let Some(v) = self.category {
+            basket.category = v.to_lowercase();
+        }
+        if let Some(v) = self.consensus_threshold {
+            basket.consensus_threshold = v;
+        }
+        if let Some(v) = self.time_window_hours {
+            basket.time_window_hours = v;
+        }
+        if let Some(v) = self.min_wallets {
+            basket.min_wallets = v;
+        }
+        if let Some(v) = self.max_wallets {
+            basket.max_wallets = v;
+        }
+        if let Some(v) = self.vote_weighting {
+            basket.vote_weighting = VoteWeighting::parse(&v).map_or(v, |w| w.as_str().to_string());
+        }
+    }
 }
 
 #[derive(Deserialize)]
@@ -29,6 +91,12 @@ pub struct AddWhaleRequest {
     pub whale_id: Uuid,
 }
 
+fn parse_weighting(s: &str) -> Result<VoteWeighting, AppError> {
+    VoteWeighting::parse(s).ok_or_else(|| {
+        AppError::BadRequest(format!("vote_weighting must be equal, win_rate, ev or notional, not '{s}'"))
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Handlers
 // ---------------------------------------------------------------------------
@@ -46,18 +114,23 @@ pub async fn list(
     }))
 }
 
-/// GET /api/baskets/{id} — basket detail
+/// GET /api/baskets/{id} — basket detail with its latest membership events
 pub async fn detail(
     State(state): State<AppState>,
     Path(id): Path<Uuid>,
-) -> Result<Json<ApiResponse<WhaleBasket>>, AppError> {
+) -> Result<Json<ApiResponse<BasketDetail>>, AppError> {
     let basket = basket_repo::get_basket_by_id(&state.db, id)
         .await?
         .ok_or_else(|| AppError::NotFound("basket not found".into()))?;
+    let membership_events =
+        basket_repo::get_membership_events(&state.db, id, DETAIL_MEMBERSHIP_EVENTS).await?;
 
     Ok(Json(ApiResponse {
         success: true,
-        data: Some(basket),
+        data: Some(BasketDetail {
+            basket,
+            membership_events,
+        }),
         error: None,
     }))
 }
@@ -87,8 +160,9 @@ pub async fn create(
     let window = body
         .time_window_hours
         .unwrap_or(state.config.basket_time_window_hours);
+    let weighting = body.vote_weighting.as_deref().map(parse_weighting).transpose()?;
 
-    let basket = basket_repo::create_basket(
+    let mut basket = basket_repo::create_basket(
         &state.db,
         &body.name,
         &body.category,
@@ -98,6 +172,54 @@ pub async fn create(
         state.config.basket_max_wallets,
     )
     .await?;
+    if let Some(weighting) = weighting.filter(|w| *w != VoteWeighting::Equal) {
+        basket = basket_repo::set_vote_weighting(&state.db, basket.id, weighting)
+            .await?
+            .ok_or_else(|| AppError::NotFound("basket not found".into()))?;
+    }
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(basket),
+        error: None,
+    }))
+}
+
+/// PATCH /api/baskets/{id} — change a basket's category, threshold, window,
+/// wallet limits or vote weighting; consensus checks pick them up on the next trade
+pub async fn update(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateBasketRequest>,
+) -> Result<Json<ApiResponse<WhaleBasket>>, AppError> {
+    let mut basket = basket_repo::get_basket_by_id(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("basket not found".into()))?;
+    body.apply_to(&mut basket);
+    basket.validate().map_err(AppError::BadRequest)?;
+
+    let members = basket_repo::count_basket_whales(&state.db, id).await?;
+    if members > basket.max_wallets as i64 {
+        return Err(AppError::BadRequest(format!(
+            "basket has {members} whales; remove some before lowering max_wallets to {}",
+            basket.max_wallets
+        )));
+    }
+
+    let basket = basket_repo::update_basket(&state.db, &basket)
+        .await?
+        .ok_or_else(|| AppError::NotFound("basket not found".into()))?;
+
+    tracing::info!(
+        basket = %basket.name,
+        category = %basket.category,
+        threshold = %basket.consensus_threshold,
+        window_hours = basket.time_window_hours,
+        min_wallets = basket.min_wallets,
+        max_wallets = basket.max_wallets,
+        weighting = %basket.vote_weighting,
+        "Basket settings updated"
+    );
 
     Ok(Json(ApiResponse {
         success: true,
@@ -143,18 +265,29 @@ pub async fn add_whale(
         .unwrap_or(0);
 
     let total_trades = whale.total_trades.unwrap_or(0);
-    let avg_monthly = if months_active > 0 {
+    // An operator override vouches for the wallet's trading frequency
+    let now = chrono::Utc::now();
+    let avg_monthly = if whale.active_classification_override(now).is_some() {
+        Decimal::ZERO
+    } else if months_active > 0 {
         Decimal::from(total_trades as i64) / Decimal::from(months_active)
     } else {
         Decimal::from(total_trades as i64)
     };
 
+    let insider_flags = insider_repo::count_whale_flags(&state.db, whale.id).await?;
     let admission = check_admission(
         whale.win_rate.unwrap_or(Decimal::ZERO),
-        whale.classification.as_deref(),
+        whale.effective_classification(now),
         months_active,
         total_trades,
         avg_monthly,
+        whale.max_drawdown_pct.unwrap_or(Decimal::ZERO),
+        state.config.basket_max_drawdown_pct,
+        whale.skill_score,
+        state.config.basket_min_skill_score,
+        insider_flags,
+        state.config.basket_max_insider_flags,
     );
 
     if let crate::intelligence::AdmissionResult::Rejected(reason) = admission {
@@ -164,7 +297,9 @@ pub async fn add_whale(
         )));
     }
 
-    basket_repo::add_whale_to_basket(&state.db, id, body.whale_id).await?;
+    if basket_repo::add_whale_to_basket(&state.db, id, body.whale_id).await? {
+        basket_repo::record_membership_event(&state.db, id, body.whale_id, "added", "operator", "added manually").await?;
+    }
 
     Ok(Json(ApiResponse {
         success: true,
@@ -178,7 +313,9 @@ pub async fn remove_whale(
     State(state): State<AppState>,
     Path((id, whale_id)): Path<(Uuid, Uuid)>,
 ) -> Result<Json<ApiResponse<()>>, AppError> {
-    basket_repo::remove_whale_from_basket(&state.db, id, whale_id).await?;
+    if basket_repo::remove_whale_from_basket(&state.db, id, whale_id).await? {
+        basket_repo::record_membership_event(&state.db, id, whale_id, "removed", "operator", "removed manually").await?;
+    }
 
     Ok(Json(ApiResponse {
         success: true,
@@ -201,6 +338,109 @@ pub async fn consensus_history(
     }))
 }
 
+/// GET /api/baskets/{id}/membership-events — whales added to or removed from a basket, and why
+pub async fn membership_events(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<BasketMembershipEvent>>>, AppError> {
+    let events = basket_repo::get_membership_events(&state.db, id, 100).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(events),
+        error: None,
+    }))
+}
+
+/// DELETE /api/baskets/{id} — disable a basket; it stops voting and emitting consensus signals
+pub async fn deactivate(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    let basket = basket_repo::get_basket_by_id(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("basket not found".into()))?;
+    basket_repo::deactivate_basket(&state.db, id).await?;
+
+    tracing::info!(basket = %basket.name, "Basket deactivated");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: None,
+        error: None,
+    }))
+}
+
+/// GET /api/baskets/{id}/performance — hit rate, realized and open PnL of the
+/// positions opened from a basket's consensus signals
+pub async fn performance(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<BasketPerformance>>, AppError> {
+    let performance = attribution_repo::get_basket_performance(&state.db, id).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(performance),
+        error: None,
+    }))
+}
+
+/// GET /api/baskets/{id}/backtest?from=&to= — replay the current members'
+/// trades in the range through consensus evaluation and score the consensus
+/// events that would have fired against the markets' outcomes
+pub async fn backtest(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Query(query): Query<BacktestQuery>,
+) -> Result<Json<ApiResponse<BasketBacktest>>, AppError> {
+    let basket = basket_repo::get_basket_by_id(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound("basket not found".into()))?;
+
+    let to = query.to.unwrap_or_else(Utc::now);
+    let from = query.from.unwrap_or(to - Duration::days(30));
+    if from >= to {
+        return Err(AppError::BadRequest("from must be before to".into()));
+    }
+    if to - from > Duration::days(MAX_BACKTEST_DAYS) {
+        return Err(AppError::BadRequest(format!(
+            "range must be at most {MAX_BACKTEST_DAYS} days"
+        )));
+    }
+
+    // Trades from one window before `from` still vote at its start
+    let since = from - Duration::hours(basket.time_window_hours as i64);
+    let trades = basket_repo::get_basket_trade_history(&state.db, id, since, to).await?;
+    let whales = basket_repo::count_basket_whales(&state.db, id).await? as i32;
+    let cooldown_hours = basket.cooldown_hours(state.config.basket_consensus_cooldown_hours);
+    let events = replay_consensus(&trades, from, &basket, whales, cooldown_hours);
+
+    let returns: Vec<Decimal> = events.iter().filter_map(|e| e.return_per_usdc).collect();
+    let wins = returns.iter().filter(|r| **r > Decimal::ZERO).count();
+    let hit_rate = (!returns.is_empty())
+        .then(|| (Decimal::from(wins) / Decimal::from(returns.len())).round_dp(4));
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(BasketBacktest {
+            basket_id: id,
+            from,
+            to,
+            whales,
+            trades: trades.iter().filter(|t| t.traded_at >= from).count(),
+            fired: events.len(),
+            resolved: returns.len(),
+            wins,
+            losses: returns.len() - wins,
+            hit_rate,
+            total_return: returns.iter().copied().sum::<Decimal>().round_dp(4),
+            events,
+        }),
+        error: None,
+    }))
+}
+
 /// GET /api/consensus/recent — global recent consensus signals
 pub async fn recent_consensus(
     State(state): State<AppState>,
@@ -0,0 +1,109 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::db::compliance_repo;
+use crate::errors::AppError;
+use crate::models::{ComplianceRule, ComplianceRuleType};
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+#[derive(Deserialize)]
+pub struct CreateRuleRequest {
+    /// `excluded_tag`, `max_odds` or `banned_keyword`.
+    pub rule_type: String,
+    pub value: String,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateRuleRequest {
+    pub enabled: bool,
+}
+
+/// GET /api/compliance/rules — all pre-trade compliance rules
+pub async fn list(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<ComplianceRule>>>, AppError> {
+    let rules = compliance_repo::list_rules(&state.db).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(rules),
+        error: None,
+    }))
+}
+
+/// POST /api/compliance/rules — add a rule; applies to the next signal
+pub async fn create(
+    State(state): State<AppState>,
+    Json(body): Json<CreateRuleRequest>,
+) -> Result<Json<ApiResponse<ComplianceRule>>, AppError> {
+    let rule_type = ComplianceRuleType::parse(&body.rule_type).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "unknown rule_type '{}' (excluded_tag | max_odds | banned_keyword)",
+            body.rule_type
+        ))
+    })?;
+
+    let value = body.value.trim();
+    if value.is_empty() {
+        return Err(AppError::BadRequest("value must not be empty".into()));
+    }
+    if rule_type == ComplianceRuleType::MaxOdds {
+        match value.parse::<Decimal>() {
+            Ok(v) if v > Decimal::ZERO && v < Decimal::ONE => {}
+            _ => return Err(AppError::BadRequest("max_odds value must be in (0, 1)".into())),
+        }
+    }
+
+    let rule =
+        compliance_repo::insert_rule(&state.db, rule_type.as_str(), value, body.note.as_deref())
+            .await?;
+
+    tracing::info!(rule_id = %rule.id, rule_type = %rule_type, value, "Compliance rule added");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(rule),
+        error: None,
+    }))
+}
+
+/// PATCH /api/compliance/rules/{id} — enable or disable a rule
+pub async fn update(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateRuleRequest>,
+) -> Result<Json<ApiResponse<ComplianceRule>>, AppError> {
+    let rule = compliance_repo::set_rule_enabled(&state.db, id, body.enabled)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("compliance rule {id}")))?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(rule),
+        error: None,
+    }))
+}
+
+/// DELETE /api/compliance/rules/{id}
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    if !compliance_repo::delete_rule(&state.db, id).await? {
+        return Err(AppError::NotFound(format!("compliance rule {id}")));
+    }
+
+    tracing::info!(rule_id = %id, "Compliance rule deleted");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(()),
+        error: None,
+    }))
+}
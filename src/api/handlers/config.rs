@@ -6,6 +6,7 @@ use axum::Json;
 use serde::{Deserialize, Serialize};
 
 use crate::db::config_repo;
+use crate::models::StopMode;
 use crate::AppState;
 
 const ALLOWED_KEYS: &[&str] = &[
@@ -15,22 +16,24 @@ const ALLOWED_KEYS: &[&str] = &[
     "dry_run",
     "copy_enabled",
     "min_signal_win_rate",
+    "min_signal_win_rate_lb",
     "min_total_trades_for_signal",
     "min_signal_ev",
+    "min_signal_profit_factor",
+    "min_signal_skill_score",
+    "first_mover_size_multiplier",
     "assumed_slippage_pct",
     "signal_notional_liquidity_pct",
     "signal_notional_floor",
     "max_signal_notional",
     "default_stop_loss_pct",
     "default_take_profit_pct",
+    "default_stop_mode",
+    "whale_exit_mode",
     "basket_consensus_threshold",
     "basket_time_window_hours",
     "notifications_enabled",
     "tracked_whale_min_notional",
-    "max_daily_loss",
-    "max_open_positions",
-    "trailing_stop_pct",
-    "max_position_hold_days",
 ];
 
 #[derive(Serialize)]
@@ -48,22 +51,24 @@ fn defaults_from_config(state: &AppState) -> HashMap<String, String> {
     m.insert("dry_run".into(), c.dry_run.to_string());
     m.insert("copy_enabled".into(), c.copy_enabled.to_string());
     m.insert("min_signal_win_rate".into(), c.min_signal_win_rate.to_string());
+    m.insert("min_signal_win_rate_lb".into(), c.min_signal_win_rate_lb.to_string());
     m.insert("min_total_trades_for_signal".into(), c.min_total_trades_for_signal.to_string());
     m.insert("min_signal_ev".into(), c.min_signal_ev.to_string());
+    m.insert("min_signal_profit_factor".into(), c.min_signal_profit_factor.to_string());
+    m.insert("min_signal_skill_score".into(), c.min_signal_skill_score.to_string());
+    m.insert("first_mover_size_multiplier".into(), c.first_mover_size_multiplier.to_string());
     m.insert("assumed_slippage_pct".into(), c.assumed_slippage_pct.to_string());
     m.insert("signal_notional_liquidity_pct".into(), c.signal_notional_liquidity_pct.to_string());
     m.insert("signal_notional_floor".into(), c.signal_notional_floor.to_string());
     m.insert("max_signal_notional".into(), c.max_signal_notional.to_string());
     m.insert("default_stop_loss_pct".into(), c.default_stop_loss_pct.to_string());
     m.insert("default_take_profit_pct".into(), c.default_take_profit_pct.to_string());
+    m.insert("default_stop_mode".into(), c.default_stop_mode.clone());
+    m.insert("whale_exit_mode".into(), c.whale_exit_mode.clone());
     m.insert("basket_consensus_threshold".into(), c.basket_consensus_threshold.to_string());
     m.insert("basket_time_window_hours".into(), c.basket_time_window_hours.to_string());
     m.insert("notifications_enabled".into(), c.notifications_enabled.to_string());
     m.insert("tracked_whale_min_notional".into(), c.tracked_whale_min_notional.to_string());
-    m.insert("max_daily_loss".into(), c.max_daily_loss.to_string());
-    m.insert("max_open_positions".into(), crate::execution::risk_manager::RiskLimits::default().max_open_positions.to_string());
-    m.insert("trailing_stop_pct".into(), "10".into());
-    m.insert("max_position_hold_days".into(), "7".into());
     m
 }
 
@@ -108,6 +113,12 @@ pub async fn update_config(
             Json(serde_json::json!({"error": "No valid config keys provided"})),
         ));
     }
+    if let Some(mode) = filtered.get("default_stop_mode").filter(|m| StopMode::parse(m).is_none()) {
+        return Err((
+            StatusCode::BAD_REQUEST,
+            Json(serde_json::json!({"error": format!("default_stop_mode must be 'static' or 'trailing', got '{mode}'")})),
+        ));
+    }
 
     match config_repo::upsert_config(&state.db, &filtered).await {
         Ok(()) => Ok(Json(serde_json::json!({
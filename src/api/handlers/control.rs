@@ -15,9 +15,10 @@ pub async fn stop(State(state): State<AppState>) -> impl IntoResponse {
     (StatusCode::OK, Json(json!({ "status": "paused" })))
 }
 
-/// POST /api/control/resume — Resume the copy engine.
+/// POST /api/control/resume — Resume the copy engine (also resets a tripped circuit breaker).
 pub async fn resume(State(state): State<AppState>) -> impl IntoResponse {
     state.pause_flag.store(false, Ordering::Relaxed);
+    state.circuit_breaker.reset().await;
     tracing::info!("Copy engine RESUMED via control API");
     (StatusCode::OK, Json(json!({ "status": "running" })))
 }
@@ -39,12 +40,17 @@ pub async fn status(State(state): State<AppState>) -> impl IntoResponse {
         None
     };
 
+    let circuit_breaker = state.circuit_breaker.status().await;
+
     Json(json!({
         "mode": mode,
         "paused": paused,
+        "circuit_breaker": circuit_breaker,
         "wallet": wallet_address,
         "usdc_balance": usdc_balance,
         "copy_enabled": state.config.copy_enabled,
+        "environment": state.config.environment,
+        "config_fingerprint": state.config.fingerprint(),
     }))
 }
 
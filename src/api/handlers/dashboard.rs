@@ -1,12 +1,19 @@
-use axum::extract::State;
+use axum::extract::{Query, State};
 use axum::Json;
 use chrono::{Duration, Utc};
 use rust_decimal::Decimal;
-use serde::Serialize;
+use serde::{Deserialize, Serialize};
 
-use crate::db::{basket_repo, position_repo, whale_repo};
+use crate::db::{basket_repo, portfolio_snapshot_repo, position_repo, whale_repo};
+use crate::errors::AppError;
+use crate::models::PortfolioSnapshot;
 use crate::AppState;
 
+use super::whales::ApiResponse;
+
+/// Longest lookback served by the equity curve endpoint.
+const MAX_EQUITY_CURVE_DAYS: i64 = 365;
+
 #[derive(Serialize)]
 pub struct DashboardSummary {
     pub tracked_whales: i64,
@@ -56,3 +63,31 @@ pub async fn summary(State(state): State<AppState>) -> Json<DashboardSummary> {
         recent_consensus_count,
     })
 }
+
+#[derive(Deserialize)]
+pub struct EquityCurveQuery {
+    /// Lookback window in days (default 30).
+    pub days: Option<i64>,
+}
+
+/// GET /api/dashboard/equity-curve — portfolio snapshots over time
+pub async fn equity_curve(
+    State(state): State<AppState>,
+    Query(query): Query<EquityCurveQuery>,
+) -> Result<Json<ApiResponse<Vec<PortfolioSnapshot>>>, AppError> {
+    let days = query.days.unwrap_or(30);
+    if !(1..=MAX_EQUITY_CURVE_DAYS).contains(&days) {
+        return Err(AppError::BadRequest(format!(
+            "days must be between 1 and {MAX_EQUITY_CURVE_DAYS}"
+        )));
+    }
+
+    let since = Utc::now() - Duration::days(days);
+    let snapshots = portfolio_snapshot_repo::get_snapshots_since(&state.db, since).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(snapshots),
+        error: None,
+    }))
+}
@@ -0,0 +1,142 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use uuid::Uuid;
+
+use crate::db::discovery_exclusion_repo;
+use crate::errors::AppError;
+use crate::models::{DiscoveryExclusion, DiscoveryExclusionType};
+use crate::services::market_discovery;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+#[derive(Deserialize)]
+pub struct CreateExclusionRequest {
+    /// `category`, `keyword`, `resolves_within_hours` or `max_spread`.
+    pub rule_type: String,
+    pub value: String,
+    pub note: Option<String>,
+}
+
+#[derive(Deserialize)]
+pub struct UpdateExclusionRequest {
+    pub enabled: bool,
+}
+
+/// GET /api/discovery/exclusions — all market discovery exclusions
+pub async fn list(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<DiscoveryExclusion>>>, AppError> {
+    let exclusions = discovery_exclusion_repo::list_exclusions(&state.db).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(exclusions),
+        error: None,
+    }))
+}
+
+/// POST /api/discovery/exclusions — add an exclusion and drop the markets it
+/// matches from active_markets; the next scan stops discovering them
+pub async fn create(
+    State(state): State<AppState>,
+    Json(body): Json<CreateExclusionRequest>,
+) -> Result<Json<ApiResponse<DiscoveryExclusion>>, AppError> {
+    let rule_type = DiscoveryExclusionType::parse(&body.rule_type).ok_or_else(|| {
+        AppError::BadRequest(format!(
+            "unknown rule_type '{}' (category | keyword | resolves_within_hours | max_spread)",
+            body.rule_type
+        ))
+    })?;
+
+    let value = body.value.trim();
+    if value.is_empty() {
+        return Err(AppError::BadRequest("value must not be empty".into()));
+    }
+    match rule_type {
+        DiscoveryExclusionType::ResolvesWithinHours => match value.parse::<i64>() {
+            Ok(h) if h > 0 => {}
+            _ => {
+                return Err(AppError::BadRequest(
+                    "resolves_within_hours value must be a positive whole number of hours".into(),
+                ))
+            }
+        },
+        DiscoveryExclusionType::MaxSpread => match value.parse::<Decimal>() {
+            Ok(v) if v > Decimal::ZERO && v < Decimal::ONE => {}
+            _ => return Err(AppError::BadRequest("max_spread value must be in (0, 1)".into())),
+        },
+        DiscoveryExclusionType::Category | DiscoveryExclusionType::Keyword => {}
+    }
+
+    let exclusion = discovery_exclusion_repo::insert_exclusion(
+        &state.db,
+        rule_type.as_str(),
+        value,
+        body.note.as_deref(),
+    )
+    .await?;
+
+    tracing::info!(exclusion_id = %exclusion.id, rule_type = %rule_type, value, "Discovery exclusion added");
+    remove_excluded_markets(&state, &exclusion).await;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(exclusion),
+        error: None,
+    }))
+}
+
+/// PATCH /api/discovery/exclusions/{id} — enable or disable an exclusion;
+/// enabling one drops the markets it matches from active_markets
+pub async fn update(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<UpdateExclusionRequest>,
+) -> Result<Json<ApiResponse<DiscoveryExclusion>>, AppError> {
+    let exclusion = discovery_exclusion_repo::set_exclusion_enabled(&state.db, id, body.enabled)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("discovery exclusion {id}")))?;
+    if exclusion.enabled {
+        remove_excluded_markets(&state, &exclusion).await;
+    }
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(exclusion),
+        error: None,
+    }))
+}
+
+/// Drop the active markets `exclusion` matches. The exclusion is already
+/// saved, so a failure is logged and left to the next discovery scan.
+async fn remove_excluded_markets(state: &AppState, exclusion: &DiscoveryExclusion) {
+    match market_discovery::remove_excluded_markets(&state.db, std::slice::from_ref(exclusion)).await {
+        Ok(removed) => tracing::info!(exclusion_id = %exclusion.id, removed, "Removed excluded active markets"),
+        Err(e) => tracing::warn!(
+            error = %e,
+            exclusion_id = %exclusion.id,
+            "Failed to remove excluded active markets — next scan will drop them"
+        ),
+    }
+}
+
+/// DELETE /api/discovery/exclusions/{id}
+pub async fn delete(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<()>>, AppError> {
+    if !discovery_exclusion_repo::delete_exclusion(&state.db, id).await? {
+        return Err(AppError::NotFound(format!("discovery exclusion {id}")));
+    }
+
+    tracing::info!(exclusion_id = %id, "Discovery exclusion deleted");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(()),
+        error: None,
+    }))
+}
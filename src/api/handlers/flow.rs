@@ -0,0 +1,22 @@
+use axum::extract::State;
+use axum::Json;
+
+use crate::db::flow_repo;
+use crate::errors::AppError;
+use crate::models::FlowSignal;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// GET /api/flow/recent — recent market-level net whale flow signals
+pub async fn recent(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<FlowSignal>>>, AppError> {
+    let signals = flow_repo::get_recent_flow_signals(&state.db, 50).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(signals),
+        error: None,
+    }))
+}
@@ -0,0 +1,117 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::db::gate_profile_repo;
+use crate::errors::AppError;
+use crate::models::GateProfile;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// Full replacement — omitted gates are stored as NULL and fall back to the
+/// env / runtime config values.
+#[derive(Deserialize)]
+pub struct PutGateProfileRequest {
+    pub description: Option<String>,
+    pub min_signal_win_rate: Option<Decimal>,
+    pub min_resolved_for_signal: Option<i32>,
+    pub min_total_trades_for_signal: Option<i32>,
+    pub min_signal_ev: Option<Decimal>,
+    pub min_signal_profit_factor: Option<Decimal>,
+    pub min_signal_skill_score: Option<Decimal>,
+    pub min_entry_edge: Option<Decimal>,
+    pub signal_notional_liquidity_pct: Option<Decimal>,
+    pub signal_notional_floor: Option<Decimal>,
+    pub max_signal_notional: Option<Decimal>,
+    pub assumed_slippage_pct: Option<Decimal>,
+    pub first_mover_size_multiplier: Option<Decimal>,
+}
+
+impl PutGateProfileRequest {
+    fn into_profile(self, name: String) -> GateProfile {
+        GateProfile {
+            name,
+            description: self.description,
+            is_active: false,
+            min_signal_win_rate: self.min_signal_win_rate,
+            min_resolved_for_signal: self.min_resolved_for_signal,
+            min_total_trades_for_signal: self.min_total_trades_for_signal,
+            min_signal_ev: self.min_signal_ev,
+            min_signal_profit_factor: self.min_signal_profit_factor,
+            min_signal_skill_score: self.min_signal_skill_score,
+            min_entry_edge: self.min_entry_edge,
+            signal_notional_liquidity_pct: self.signal_notional_liquidity_pct,
+            signal_notional_floor: self.signal_notional_floor,
+            max_signal_notional: self.max_signal_notional,
+            assumed_slippage_pct: self.assumed_slippage_pct,
+            first_mover_size_multiplier: self.first_mover_size_multiplier,
+            updated_at: None,
+        }
+    }
+}
+
+/// GET /api/gate-profiles — all gate profiles, with the active one flagged
+pub async fn list(State(state): State<AppState>) -> Result<Json<ApiResponse<Vec<GateProfile>>>, AppError> {
+    let profiles = gate_profile_repo::list_gate_profiles(&state.db).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(profiles),
+        error: None,
+    }))
+}
+
+/// PUT /api/gate-profiles/:name — create or replace a profile's gates
+pub async fn put(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+    Json(body): Json<PutGateProfileRequest>,
+) -> Result<Json<ApiResponse<GateProfile>>, AppError> {
+    let profile = body.into_profile(name);
+    profile.validate().map_err(AppError::BadRequest)?;
+
+    let saved = gate_profile_repo::upsert_gate_profile(&state.db, &profile).await?;
+    tracing::info!(profile = ?saved, "Gate profile saved");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(saved),
+        error: None,
+    }))
+}
+
+/// POST /api/gate-profiles/:name/activate — gate signals with this profile from the next trade on
+pub async fn activate(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<GateProfile>>, AppError> {
+    let profile = gate_profile_repo::activate_gate_profile(&state.db, &name)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Gate profile {name} not found")))?;
+    tracing::info!(profile = %profile.name, "Gate profile activated");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(profile),
+        error: None,
+    }))
+}
+
+/// DELETE /api/gate-profiles/:name/activate — back to the env / runtime config gates
+pub async fn deactivate(
+    State(state): State<AppState>,
+    Path(name): Path<String>,
+) -> Result<Json<ApiResponse<GateProfile>>, AppError> {
+    let profile = gate_profile_repo::deactivate_gate_profile(&state.db, &name)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("Gate profile {name} not found")))?;
+    tracing::info!(profile = %profile.name, "Gate profile deactivated");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(profile),
+        error: None,
+    }))
+}
@@ -0,0 +1,74 @@
+use axum::extract::{Path, Query, State};
+use axum::Json;
+use chrono::Utc;
+use serde::Deserialize;
+
+use crate::db::{candle_repo, tape_repo};
+use crate::errors::AppError;
+use crate::models::{Candle, TapePrint};
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// Longest lookback served by the candles endpoint.
+const MAX_CANDLE_HOURS: i64 = 24 * 7;
+/// Most prints served by the tape endpoint.
+const MAX_TAPE_LIMIT: i64 = 500;
+
+#[derive(Deserialize)]
+pub struct CandlesQuery {
+    /// Lookback window in hours (default 24).
+    pub hours: Option<i64>,
+}
+
+/// GET /api/markets/:token_id/candles — 1-minute OHLC candles for a token
+pub async fn candles(
+    State(state): State<AppState>,
+    Path(token_id): Path<String>,
+    Query(query): Query<CandlesQuery>,
+) -> Result<Json<ApiResponse<Vec<Candle>>>, AppError> {
+    let hours = query.hours.unwrap_or(24);
+    if !(1..=MAX_CANDLE_HOURS).contains(&hours) {
+        return Err(AppError::BadRequest(format!(
+            "hours must be between 1 and {MAX_CANDLE_HOURS}"
+        )));
+    }
+
+    let since = Utc::now() - chrono::Duration::hours(hours);
+    let candles = candle_repo::get_candles(&state.db, &token_id, since).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(candles),
+        error: None,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct TapeQuery {
+    /// Number of prints, newest first (default 100).
+    pub limit: Option<i64>,
+}
+
+/// GET /api/markets/:id/tape — recent WS and chain trade prints for a
+/// market (market key, condition id or token id)
+pub async fn tape(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(query): Query<TapeQuery>,
+) -> Result<Json<ApiResponse<Vec<TapePrint>>>, AppError> {
+    let limit = query.limit.unwrap_or(100);
+    if !(1..=MAX_TAPE_LIMIT).contains(&limit) {
+        return Err(AppError::BadRequest(format!(
+            "limit must be between 1 and {MAX_TAPE_LIMIT}"
+        )));
+    }
+
+    let prints = tape_repo::get_market_tape(&state.db, &id, limit).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(prints),
+        error: None,
+    }))
+}
@@ -1,11 +1,23 @@
+pub mod admin;
 pub mod analytics;
 pub mod baskets;
+pub mod compliance;
 pub mod config;
 pub mod control;
 pub mod dashboard;
+pub mod discovery;
+pub mod flow;
+pub mod gate_profiles;
 pub mod health;
+pub mod markets;
 pub mod metrics;
+pub mod orders;
 pub mod positions;
+pub mod reconciliation;
+pub mod risk_events;
+pub mod risk_limits;
+pub mod seeder;
+pub mod tiers;
 pub mod trades;
 pub mod whales;
 pub mod ws;
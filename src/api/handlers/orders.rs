@@ -0,0 +1,150 @@
+use std::time::Duration;
+
+use axum::extract::State;
+use axum::Json;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+use tokio::sync::oneshot;
+
+use crate::db::market_repo;
+use crate::errors::AppError;
+use crate::execution::copy_engine::ManualOrder;
+use crate::execution::copy_profiles::parse_profiles;
+use crate::models::{CopyOrder, CopySignal, Side, Sleeve};
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// How long to wait for the copy engine to process a manual order
+/// (it may be busy with queued signals or CLOB retries).
+const MANUAL_ORDER_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Deserialize)]
+pub struct ManualOrderRequest {
+    pub token_id: String,
+    /// `BUY` or `SELL`.
+    pub side: String,
+    /// Number of outcome tokens.
+    pub size: Decimal,
+    /// Limit price; defaults to the best ask (BUY) or best bid (SELL).
+    pub price: Option<Decimal>,
+    /// Market condition_id; looked up from the token when omitted.
+    pub market_id: Option<String>,
+    /// Capital sleeve or copy profile to trade out of (default `single_whale`).
+    pub sleeve: Option<String>,
+}
+
+/// POST /api/orders/manual — place an operator order through the copy engine,
+/// with the same risk checks, capital reservation, fill tracking and SL/TP as
+/// copied trades. A SELL closes (part of) the sleeve's or profile's open
+/// position in the token and is rejected when there is none.
+pub async fn manual(
+    State(state): State<AppState>,
+    Json(body): Json<ManualOrderRequest>,
+) -> Result<Json<ApiResponse<CopyOrder>>, AppError> {
+    let side = Side::from_api_str(&body.side)
+        .ok_or_else(|| AppError::BadRequest(format!("invalid side '{}' (BUY | SELL)", body.side)))?;
+    if body.size <= Decimal::ZERO {
+        return Err(AppError::BadRequest("size must be positive".into()));
+    }
+    let (sleeve, profile) = match body.sleeve.as_deref().map(str::trim) {
+        Some(s) => match Sleeve::parse(s) {
+            Some(sleeve) => (sleeve, None),
+            None if parse_profiles(&state.config.copy_profiles).iter().any(|p| p.name == s) => {
+                (Sleeve::SingleWhale, Some(s.to_string()))
+            }
+            None => return Err(AppError::BadRequest(format!("unknown sleeve or copy profile '{s}'"))),
+        },
+        None => (Sleeve::SingleWhale, None),
+    };
+    let Some(ref tx) = state.manual_order_tx else {
+        return Err(AppError::BadRequest(
+            "copy engine is not running (COPY_ENABLED=false)".into(),
+        ));
+    };
+
+    let price = match body.price {
+        Some(p) => p,
+        None => best_price(&state, &body.token_id, side).await?,
+    };
+    if price <= Decimal::ZERO || price >= Decimal::ONE {
+        return Err(AppError::BadRequest("price must be in (0, 1)".into()));
+    }
+
+    let market_id = body.market_id.as_deref().unwrap_or(&body.token_id);
+    let condition_id = market_repo::resolve_condition_id(&state.db, market_id, &body.token_id).await?;
+    let market_id = match (&body.market_id, &condition_id) {
+        (Some(m), _) => m.clone(),
+        (None, Some(c)) => c.clone(),
+        (None, None) => {
+            return Err(AppError::BadRequest(
+                "unknown token — pass market_id explicitly".into(),
+            ))
+        }
+    };
+
+    let mut signal = CopySignal::manual(
+        market_id,
+        condition_id,
+        body.token_id.clone(),
+        side,
+        body.size,
+        price,
+        sleeve,
+    );
+    signal.profile = profile;
+
+    let (reply_tx, reply_rx) = oneshot::channel();
+    tx.send(ManualOrder { signal, reply: reply_tx })
+        .await
+        .map_err(|_| anyhow::anyhow!("copy engine channel closed"))?;
+
+    let order = tokio::time::timeout(MANUAL_ORDER_TIMEOUT, reply_rx)
+        .await
+        .map_err(|_| anyhow::anyhow!("timed out waiting for the copy engine"))?
+        .map_err(|_| anyhow::anyhow!("copy engine dropped the order"))?
+        .map_err(AppError::BadRequest)?;
+
+    tracing::info!(
+        order_id = %order.id,
+        token_id = %order.token_id,
+        side = %order.side,
+        size = %order.size,
+        status = %order.status,
+        "Manual order placed"
+    );
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(order),
+        error: None,
+    }))
+}
+
+/// Best ask for a buy, best bid for a sell — from the WS price cache when
+/// fresh, else the CLOB orderbook.
+async fn best_price(state: &AppState, token_id: &str, side: Side) -> Result<Decimal, AppError> {
+    let cached = match side {
+        Side::Buy => state.price_cache.best_ask(token_id).await,
+        Side::Sell => state.price_cache.best_bid(token_id).await,
+    };
+    if let Some(price) = cached {
+        return Ok(price);
+    }
+
+    let Some(ref clob) = state.clob_client else {
+        return Err(AppError::BadRequest(
+            "no CLOB client configured — provide price manually".into(),
+        ));
+    };
+    let book = clob
+        .get_order_book(token_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("failed to fetch orderbook: {e}"))?;
+
+    let best = match side {
+        Side::Buy => book.asks.iter().map(|l| l.price).min(),
+        Side::Sell => book.bids.iter().map(|l| l.price).max(),
+    };
+    best.ok_or_else(|| AppError::BadRequest("orderbook has no liquidity on that side".into()))
+}
@@ -4,8 +4,11 @@ use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use crate::db::{market_repo, order_repo, position_repo};
-use crate::models::Position;
+use crate::db::{market_repo, order_repo, position_repo, whale_repo};
+use crate::errors::AppError;
+use crate::execution::close_preview::{self, ClosePreview};
+use crate::models::{Position, StopMode};
+use crate::services::position_health::{self, PositionAging, PositionHealthConfig};
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -23,6 +26,9 @@ pub struct PositionEnriched {
     pub market_question: Option<String>,
     /// Resolved outcome label, e.g. "G2 Esports" instead of "Yes"
     pub outcome_label: Option<String>,
+    /// Whale whose trade opened the position.
+    pub source_whale_address: Option<String>,
+    pub source_whale_label: Option<String>,
 }
 
 /// Resolve the human-readable outcome label for a token_id.
@@ -38,13 +44,30 @@ fn resolve_outcome_label(
     labels.get(idx).cloned()
 }
 
+/// Open positions with their age and liquidity change since entry, oldest first.
+pub async fn aging(State(state): State<AppState>) -> Json<ApiResponse<Vec<PositionAging>>> {
+    let config = PositionHealthConfig::from_config(&state.config);
+    match position_health::aging_report(&state.db, &config).await {
+        Ok(report) => Json(ApiResponse {
+            success: true,
+            data: Some(report),
+            error: None,
+        }),
+        Err(e) => Json(ApiResponse {
+            success: false,
+            data: None,
+            error: Some(e.to_string()),
+        }),
+    }
+}
+
 pub async fn list(State(state): State<AppState>) -> Json<ApiResponse<Vec<PositionEnriched>>> {
     match position_repo::get_all_positions(&state.db).await {
         Ok(positions) => {
             let mut enriched = Vec::with_capacity(positions.len());
             for pos in positions {
                 let (market_slug, market_question, outcome_label) =
-                    match market_repo::get_market_info(&state.db, &pos.market_id).await {
+                    match market_repo::get_market_info(&state.db, pos.market_key()).await {
                         Ok(Some((slug, question, clob_token_ids, outcomes))) => {
                             let label = resolve_outcome_label(
                                 &pos.token_id,
@@ -55,11 +78,17 @@ pub async fn list(State(state): State<AppState>) -> Json<ApiResponse<Vec<Positio
                         }
                         _ => (None, None, None),
                     };
+                let source_whale = match pos.whale_id {
+                    Some(id) => whale_repo::get_whale_by_id(&state.db, id).await.ok().flatten(),
+                    None => None,
+                };
                 enriched.push(PositionEnriched {
                     position: pos,
                     market_slug,
                     market_question,
                     outcome_label,
+                    source_whale_address: source_whale.as_ref().map(|w| w.address.clone()),
+                    source_whale_label: source_whale.and_then(|w| w.label),
                 });
             }
             Json(ApiResponse {
@@ -76,6 +105,43 @@ pub async fn list(State(state): State<AppState>) -> Json<ApiResponse<Vec<Positio
     }
 }
 
+/// GET /api/positions/:id/close-preview — what closing at market would cost
+/// now: the position sold into the current bids, with expected fill price,
+/// slippage, fees (at the last 30 days' fee + gas rate) and realized PnL.
+pub async fn close_preview(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+) -> Result<Json<ApiResponse<ClosePreview>>, AppError> {
+    let pos = position_repo::get_position_by_id(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("position {id}")))?;
+
+    let status = pos.status.as_deref().unwrap_or("open");
+    if status != "open" {
+        return Err(AppError::BadRequest(format!(
+            "Position status is '{status}', expected 'open'"
+        )));
+    }
+
+    let Some(ref clob) = state.clob_client else {
+        return Err(AppError::BadRequest("No CLOB client configured — cannot fetch the orderbook".into()));
+    };
+    let book = clob
+        .get_order_book(&pos.token_id)
+        .await
+        .map_err(|e| anyhow::anyhow!("Failed to fetch orderbook: {e}"))?;
+
+    let cost_rate = order_repo::get_cost_rate_since(&state.db, chrono::Utc::now() - chrono::Duration::days(30))
+        .await?
+        .unwrap_or(Decimal::ZERO);
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(close_preview::preview_close(&pos, &book.bids, cost_rate)),
+        error: None,
+    }))
+}
+
 #[derive(Deserialize)]
 pub struct CloseRequest {
     pub price: Option<String>,
@@ -126,8 +192,10 @@ pub async fn close(
                 });
             }
         }
+    } else if let Some(best_bid) = state.price_cache.best_bid(&pos.token_id).await {
+        best_bid
     } else {
-        // Auto-fetch best bid from orderbook
+        // No fresh WS quote — fetch best bid from the orderbook
         let Some(ref clob) = state.clob_client else {
             return Json(ApiResponse {
                 success: false,
@@ -178,6 +246,9 @@ pub async fn close(
                         pos.size,
                         exit_price,
                         "exit",
+                        &pos.sleeve,
+                        pos.condition_id.as_deref(),
+                        None,
                     )
                     .await
                     {
@@ -247,3 +318,40 @@ pub async fn close(
         }),
     }
 }
+
+#[derive(Deserialize)]
+pub struct UpdateStopRequest {
+    /// `static` or `trailing`.
+    pub stop_mode: String,
+    /// Stop distance in percent; keeps the current value when omitted.
+    pub stop_loss_pct: Option<Decimal>,
+}
+
+/// PATCH /api/positions/:id/stop — switch an open position between static and trailing stops
+pub async fn update_stop(
+    State(state): State<AppState>,
+    Path(id): Path<uuid::Uuid>,
+    Json(body): Json<UpdateStopRequest>,
+) -> Result<Json<ApiResponse<Position>>, AppError> {
+    let stop_mode = StopMode::parse(&body.stop_mode).ok_or_else(|| {
+        AppError::BadRequest(format!("unknown stop_mode '{}' (static | trailing)", body.stop_mode))
+    })?;
+
+    if let Some(pct) = body.stop_loss_pct {
+        if pct <= Decimal::ZERO || pct >= Decimal::ONE_HUNDRED {
+            return Err(AppError::BadRequest("stop_loss_pct must be in (0, 100)".into()));
+        }
+    }
+
+    let pos = position_repo::set_position_stop_mode(&state.db, id, stop_mode, body.stop_loss_pct)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("open position {id}")))?;
+
+    tracing::info!(position_id = %id, stop_mode = %stop_mode, "Position stop mode updated");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(pos),
+        error: None,
+    }))
+}
@@ -0,0 +1,31 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+
+use crate::db::reconciliation_repo;
+use crate::errors::AppError;
+use crate::models::ReconciliationReport;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+#[derive(Deserialize)]
+pub struct ReportsQuery {
+    /// Max rows (default 30, capped at 365).
+    pub limit: Option<i64>,
+}
+
+/// GET /api/reconciliation/reports — recent end-of-day reconciliation runs
+pub async fn reports(
+    State(state): State<AppState>,
+    Query(query): Query<ReportsQuery>,
+) -> Result<Json<ApiResponse<Vec<ReconciliationReport>>>, AppError> {
+    let limit = query.limit.unwrap_or(30).clamp(1, 365);
+    let reports = reconciliation_repo::get_recent_reports(&state.db, limit).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(reports),
+        error: None,
+    }))
+}
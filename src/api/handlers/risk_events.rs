@@ -0,0 +1,33 @@
+use axum::extract::{Query, State};
+use axum::Json;
+use serde::Deserialize;
+
+use crate::db::risk_event_repo;
+use crate::errors::AppError;
+use crate::models::RiskEvent;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+#[derive(Deserialize)]
+pub struct RiskEventsQuery {
+    /// Only events for this rule, e.g. `too_many_positions`.
+    pub rule: Option<String>,
+    /// Max rows (default 100, capped at 1000).
+    pub limit: Option<i64>,
+}
+
+/// GET /api/risk/events — recent signals rejected by risk checks
+pub async fn list(
+    State(state): State<AppState>,
+    Query(query): Query<RiskEventsQuery>,
+) -> Result<Json<ApiResponse<Vec<RiskEvent>>>, AppError> {
+    let limit = query.limit.unwrap_or(100).clamp(1, 1000);
+    let events = risk_event_repo::get_risk_events(&state.db, query.rule.as_deref(), limit).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(events),
+        error: None,
+    }))
+}
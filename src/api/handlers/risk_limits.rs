@@ -0,0 +1,108 @@
+use axum::extract::State;
+use axum::Json;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::db::risk_limits_repo;
+use crate::errors::AppError;
+use crate::execution::risk_manager::RiskLimits;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// Partial update — omitted fields keep their current value.
+#[derive(Deserialize)]
+pub struct UpdateRiskLimitsRequest {
+    pub max_position_pct: Option<Decimal>,
+    pub max_open_positions: Option<i64>,
+    pub max_daily_loss: Option<Decimal>,
+    pub min_spread_to_resolution: Option<Decimal>,
+    pub max_slippage_pct: Option<Decimal>,
+    pub max_tail_loss_pct: Option<Decimal>,
+    pub trailing_stop_pct: Option<Decimal>,
+    pub max_position_hold_days: Option<i64>,
+    pub min_hours_to_resolution: Option<i64>,
+    pub min_matic_balance: Option<Decimal>,
+    pub usdc_fee_buffer: Option<Decimal>,
+    pub exit_hours_before_resolution: Option<i64>,
+    pub reserve_floor_pct: Option<Decimal>,
+}
+
+impl UpdateRiskLimitsRequest {
+    fn apply_to(self, limits: &mut RiskLimits) {
+        if let Some(v) = self.max_position_pct {
+            limits.max_position_pct = v;
+        }
+        if let Some(v) = self.max_open_positions {
+            limits.max_open_positions = v;
+        }
+        if let Some(v) = self.max_daily_loss {
+            limits.max_daily_loss = v;
+        }
+        if let Some(v) = self.min_spread_to_resolution {
+            limits.min_spread_to_resolution = v;
+        }
+        if let Some(v) = self.max_slippage_pct {
+            limits.max_slippage_pct = v;
+        }
+        if let Some(v) = self.max_tail_loss_pct {
+            limits.max_tail_loss_pct = v;
+        }
+        if let Some(v) = self.trailing_stop_pct {
+            limits.trailing_stop_pct = v;
+        }
+        if let Some(v) = self.max_position_hold_days {
+            limits.max_position_hold_days = v;
+        }
+        if let Some(v) = self.min_hours_to_resolution {
+            limits.min_hours_to_resolution = v;
+        }
+        if let Some(v) = self.min_matic_balance {
+            limits.min_matic_balance = v;
+        }
+        if let Some(v) = self.usdc_fee_buffer {
+            limits.usdc_fee_buffer = v;
+        }
+        if let Some(v) = self.exit_hours_before_resolution {
+            limits.exit_hours_before_resolution = v;
+        }
+        if let Some(v) = self.reserve_floor_pct {
+            limits.reserve_floor_pct = v;
+        }
+    }
+}
+
+/// GET /api/risk-limits — limits currently enforced by the copy engine and position monitor
+pub async fn get(State(state): State<AppState>) -> Json<ApiResponse<RiskLimits>> {
+    let limits = state.risk_limits.read().await.clone();
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(limits),
+        error: None,
+    })
+}
+
+/// PATCH /api/risk-limits — validate, persist, and apply immediately
+pub async fn update(
+    State(state): State<AppState>,
+    Json(body): Json<UpdateRiskLimitsRequest>,
+) -> Result<Json<ApiResponse<RiskLimits>>, AppError> {
+    // Hold the write lock across the DB write so concurrent PATCHes don't interleave
+    let mut live = state.risk_limits.write().await;
+
+    let mut next = live.clone();
+    body.apply_to(&mut next);
+    next.validate().map_err(AppError::BadRequest)?;
+
+    let saved = risk_limits_repo::update_risk_limits(&state.db, &next).await?;
+    *live = saved.clone();
+
+    tracing::info!(limits = ?saved, "Risk limits updated");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(saved),
+        error: None,
+    }))
+}
@@ -0,0 +1,76 @@
+use axum::extract::State;
+use axum::Json;
+use serde::Deserialize;
+
+use crate::db::seeder_candidate_repo;
+use crate::errors::AppError;
+use crate::models::SeederCandidate;
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// Addresses accepted per enqueue request.
+const MAX_CANDIDATES_PER_REQUEST: usize = 50;
+
+#[derive(Deserialize)]
+pub struct EnqueueCandidatesRequest {
+    pub addresses: Vec<String>,
+    pub note: Option<String>,
+}
+
+/// GET /api/seeder/candidates — queued and evaluated seeder candidates with verdicts
+pub async fn list(
+    State(state): State<AppState>,
+) -> Result<Json<ApiResponse<Vec<SeederCandidate>>>, AppError> {
+    let candidates = seeder_candidate_repo::list_candidates(&state.db, 200).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(candidates),
+        error: None,
+    }))
+}
+
+/// POST /api/seeder/candidates — queue wallets for evaluation on the next seeder
+/// cycle (or run it now via POST /api/admin/run/whale_seeder)
+pub async fn enqueue(
+    State(state): State<AppState>,
+    Json(body): Json<EnqueueCandidatesRequest>,
+) -> Result<Json<ApiResponse<Vec<SeederCandidate>>>, AppError> {
+    if body.addresses.is_empty() {
+        return Err(AppError::BadRequest("addresses must not be empty".into()));
+    }
+    if body.addresses.len() > MAX_CANDIDATES_PER_REQUEST {
+        return Err(AppError::BadRequest(format!(
+            "at most {MAX_CANDIDATES_PER_REQUEST} addresses per request"
+        )));
+    }
+
+    let mut addresses = Vec::with_capacity(body.addresses.len());
+    for raw in &body.addresses {
+        let address = raw.trim().to_lowercase();
+        let is_address = address.len() == 42
+            && address.starts_with("0x")
+            && address[2..].chars().all(|c| c.is_ascii_hexdigit());
+        if !is_address {
+            return Err(AppError::BadRequest(format!("invalid wallet address '{raw}'")));
+        }
+        if !addresses.contains(&address) {
+            addresses.push(address);
+        }
+    }
+
+    let note = body.note.as_deref().map(str::trim).filter(|n| !n.is_empty());
+    let mut queued = Vec::with_capacity(addresses.len());
+    for address in &addresses {
+        queued.push(seeder_candidate_repo::enqueue_candidate(&state.db, address, note).await?);
+    }
+
+    tracing::info!(count = queued.len(), "Seeder candidates queued via API");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(queued),
+        error: None,
+    }))
+}
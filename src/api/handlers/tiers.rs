@@ -0,0 +1,96 @@
+use axum::extract::{Path, State};
+use axum::Json;
+use rust_decimal::Decimal;
+use serde::Deserialize;
+
+use crate::db::tier_repo;
+use crate::errors::AppError;
+use crate::intelligence::tiers;
+use crate::models::{TierPolicy, WhaleTier};
+use crate::AppState;
+
+use super::whales::ApiResponse;
+
+/// Partial update — omitted fields keep their current value.
+#[derive(Deserialize)]
+pub struct UpdateTierPolicyRequest {
+    pub min_win_rate: Option<Decimal>,
+    pub min_skill_score: Option<Decimal>,
+    pub min_total_trades: Option<i32>,
+    pub min_signal_ev: Option<Decimal>,
+    pub signal_notional_floor: Option<Decimal>,
+    pub sizing: Option<String>,
+    pub size_multiplier: Option<Decimal>,
+    pub base_amount: Option<Decimal>,
+}
+
+impl UpdateTierPolicyRequest {
+    fn apply_to(self, policy: &mut TierPolicy) {
+        if let Some(v) = self.min_win_rate {
+            policy.min_win_rate = v;
+        }
+        if let Some(v) = self.min_skill_score {
+            policy.min_skill_score = v;
+        }
+        if let Some(v) = self.min_total_trades {
+            policy.min_total_trades = v;
+        }
+        if let Some(v) = self.min_signal_ev {
+            policy.min_signal_ev = v;
+        }
+        if let Some(v) = self.signal_notional_floor {
+            policy.signal_notional_floor = v;
+        }
+        if let Some(v) = self.sizing {
+            policy.sizing = v.trim().to_lowercase();
+        }
+        if let Some(v) = self.size_multiplier {
+            policy.size_multiplier = v;
+        }
+        if let Some(v) = self.base_amount {
+            policy.base_amount = v;
+        }
+    }
+}
+
+/// GET /api/tiers — tier gates and sizing currently used by the pipeline and copy engine
+pub async fn list(State(state): State<AppState>) -> Json<ApiResponse<Vec<TierPolicy>>> {
+    let policies = state.tier_policies.read().await.clone();
+
+    Json(ApiResponse {
+        success: true,
+        data: Some(policies),
+        error: None,
+    })
+}
+
+/// PATCH /api/tiers/:tier — validate, persist, and apply immediately
+pub async fn update(
+    State(state): State<AppState>,
+    Path(tier): Path<String>,
+    Json(body): Json<UpdateTierPolicyRequest>,
+) -> Result<Json<ApiResponse<TierPolicy>>, AppError> {
+    let tier = WhaleTier::parse(&tier).ok_or_else(|| AppError::NotFound(format!("Tier {tier} not found")))?;
+
+    // Hold the write lock across the DB write so concurrent PATCHes don't interleave
+    let mut live = state.tier_policies.write().await;
+    let current = live
+        .iter_mut()
+        .find(|p| p.whale_tier() == Some(tier))
+        .ok_or_else(|| AppError::NotFound(format!("Tier {tier} has no policy")))?;
+
+    let mut next = current.clone();
+    body.apply_to(&mut next);
+    tiers::validate_policy(&next).map_err(AppError::BadRequest)?;
+
+    let saved = tier_repo::update_tier_policy(&state.db, &next).await?;
+    *current = saved.clone();
+
+    tracing::info!(policy = ?saved, "Whale tier policy updated");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(saved),
+        error: None,
+    }))
+}
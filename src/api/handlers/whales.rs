@@ -1,11 +1,18 @@
-use axum::extract::{Path, State};
+use axum::extract::{Path, Query, State};
 use axum::http::StatusCode;
+use axum::body::Bytes;
 use axum::Json;
-use serde::Serialize;
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
-use crate::db::{trade_repo, whale_repo};
-use crate::models::{Whale, WhaleTrade};
+use crate::db::{capital_flow_repo, classification_audit_repo, insider_repo, trade_repo, whale_repo};
+use crate::errors::AppError;
+use crate::ingestion::csv_import::{parse_trades_csv, RejectedRow};
+use crate::intelligence::Classification;
+use crate::models::{ClassificationAudit, InsiderFlag, Whale, WhaleCapitalFlow, WhaleCopyPerformance, WhaleCorrelation, WhaleTrade};
+use crate::services::rescore::rescore_whales;
 use crate::AppState;
 
 #[derive(Serialize)]
@@ -62,3 +69,273 @@ pub async fn trades(
         }),
     }
 }
+
+#[derive(Deserialize)]
+pub struct CorrelationsQuery {
+    /// Lookback window in days (default 30).
+    pub days: Option<i64>,
+    /// Minimum overlap to include a pair (default 0.3).
+    pub min_overlap: Option<Decimal>,
+}
+
+/// GET /api/whales/correlations — pairwise trade overlap between tracked whales
+pub async fn correlations(
+    State(state): State<AppState>,
+    Query(query): Query<CorrelationsQuery>,
+) -> Result<Json<ApiResponse<Vec<WhaleCorrelation>>>, AppError> {
+    let days = query.days.unwrap_or(30);
+    if !(1..=365).contains(&days) {
+        return Err(AppError::BadRequest("days must be between 1 and 365".into()));
+    }
+    let min_overlap = query.min_overlap.unwrap_or(Decimal::new(3, 1));
+
+    let since = Utc::now() - chrono::Duration::days(days);
+    let pairs = trade_repo::get_whale_correlations(&state.db, since, None, min_overlap).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(pairs),
+        error: None,
+    }))
+}
+
+/// GET /api/whales/:id/insider-flags — suspicious-timing flags with their evidence
+pub async fn insider_flags(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<InsiderFlag>>>, AppError> {
+    if whale_repo::get_whale_by_id(&state.db, id).await?.is_none() {
+        return Err(AppError::NotFound(format!("whale {id} not found")));
+    }
+
+    let flags = insider_repo::get_whale_flags(&state.db, id, 200).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(flags),
+        error: None,
+    }))
+}
+
+/// GET /api/whales/:id/capital-flows — large USDC deposits and withdrawals,
+/// newest first
+pub async fn capital_flows(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<WhaleCapitalFlow>>>, AppError> {
+    if whale_repo::get_whale_by_id(&state.db, id).await?.is_none() {
+        return Err(AppError::NotFound(format!("whale {id} not found")));
+    }
+
+    let flows = capital_flow_repo::get_whale_flows(&state.db, id, 200).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(flows),
+        error: None,
+    }))
+}
+
+/// GET /api/whales/:id/classification-audit — why the classifier labelled this
+/// whale as it did, one row per classification change, newest first
+pub async fn classification_audit(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Vec<ClassificationAudit>>>, AppError> {
+    if whale_repo::get_whale_by_id(&state.db, id).await?.is_none() {
+        return Err(AppError::NotFound(format!("whale {id} not found")));
+    }
+
+    let audit = classification_audit_repo::get_classification_audit(&state.db, id, 100).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(audit),
+        error: None,
+    }))
+}
+
+/// GET /api/whales/:id/copy-performance — how our copies of this whale performed
+pub async fn copy_performance(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<WhaleCopyPerformance>>, AppError> {
+    if whale_repo::get_whale_by_id(&state.db, id).await?.is_none() {
+        return Err(AppError::NotFound(format!("whale {id} not found")));
+    }
+
+    let performance = whale_repo::get_whale_copy_performance(&state.db, id, None).await?;
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(performance),
+        error: None,
+    }))
+}
+
+/// POST /api/whales/:id/copy-resume — re-enable copying a whale paused by the copy guard
+pub async fn copy_resume(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Whale>>, AppError> {
+    let whale = whale_repo::resume_whale_copying(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("whale {id} not found")))?;
+
+    tracing::info!(whale = %whale.address, "Whale copying resumed via API");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(whale),
+        error: None,
+    }))
+}
+
+/// POST /api/whales/:id/rescore — recompute this whale's scores from all its
+/// resolved trades now and return the updated whale
+pub async fn rescore(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+) -> Result<Json<ApiResponse<Whale>>, AppError> {
+    if whale_repo::get_whale_by_id(&state.db, id).await?.is_none() {
+        return Err(AppError::NotFound(format!("whale {id} not found")));
+    }
+
+    let summary = rescore_whales(&state.db, Some(&[id])).await?;
+    let whale = whale_repo::get_whale_by_id(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("whale {id} not found")))?;
+
+    tracing::info!(whale = %whale.address, trades = summary.trades, "Whale rescored via API");
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(whale),
+        error: None,
+    }))
+}
+
+#[derive(Deserialize)]
+pub struct ClassificationOverride {
+    /// `informed`, `market_maker` or `bot`; null clears the override.
+    pub classification: Option<String>,
+    /// When automatic classification resumes; null = until cleared.
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+/// PATCH /api/whales/:id/classification — override (or clear) a whale's classification
+pub async fn update_classification(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    Json(body): Json<ClassificationOverride>,
+) -> Result<Json<ApiResponse<Whale>>, AppError> {
+    let classification = body
+        .classification
+        .as_deref()
+        .map(|c| {
+            Classification::parse(c).ok_or_else(|| {
+                AppError::BadRequest(format!(
+                    "invalid classification '{c}' (expected informed, market_maker or bot)"
+                ))
+            })
+        })
+        .transpose()?;
+
+    if let Some(expires_at) = body.expires_at {
+        if classification.is_none() {
+            return Err(AppError::BadRequest("expires_at requires a classification".into()));
+        }
+        if expires_at <= Utc::now() {
+            return Err(AppError::BadRequest("expires_at must be in the future".into()));
+        }
+    }
+
+    let whale = whale_repo::set_classification_override(
+        &state.db,
+        id,
+        classification.map(|c| c.as_str()),
+        body.expires_at,
+    )
+    .await?
+    .ok_or_else(|| AppError::NotFound(format!("whale {id} not found")))?;
+
+    tracing::info!(
+        whale = %whale.address,
+        classification = ?whale.classification_override,
+        until = ?whale.classification_override_until,
+        "Whale classification override updated via API"
+    );
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(whale),
+        error: None,
+    }))
+}
+
+/// Rows rejected by a CSV import beyond this many are counted but not listed.
+const MAX_LISTED_REJECTIONS: usize = 50;
+
+#[derive(Serialize)]
+pub struct TradeImportResult {
+    pub rows: usize,
+    pub imported: usize,
+    /// Rows matching a trade the whale already has.
+    pub duplicates: usize,
+    pub rejected: usize,
+    /// Reasons for the first rejected rows.
+    pub rejections: Vec<RejectedRow>,
+    /// Whether the import left enough resolved trades to re-score the whale.
+    pub rescored: bool,
+}
+
+/// POST /api/whales/:id/trades/import — bulk-load historic trades from a CSV
+/// export (Dune, subgraph) when API backfill isn't possible. Rows are
+/// validated and deduplicated against the whale's existing trades, then the
+/// whale is re-scored from its full history.
+pub async fn import_trades(
+    State(state): State<AppState>,
+    Path(id): Path<Uuid>,
+    body: Bytes,
+) -> Result<Json<ApiResponse<TradeImportResult>>, AppError> {
+    let whale = whale_repo::get_whale_by_id(&state.db, id)
+        .await?
+        .ok_or_else(|| AppError::NotFound(format!("whale {id} not found")))?;
+
+    let parsed = parse_trades_csv(&body, Utc::now())
+        .map_err(|e| AppError::BadRequest(format!("invalid CSV: {e}")))?;
+
+    let mut imported = 0;
+    for trade in &parsed.trades {
+        if trade_repo::insert_imported_trade(&state.db, whale.id, trade).await?.is_some() {
+            imported += 1;
+        }
+    }
+
+    let rescored = imported > 0 && rescore_whales(&state.db, Some(&[whale.id])).await?.rescored > 0;
+
+    let result = TradeImportResult {
+        rows: parsed.trades.len() + parsed.rejected.len(),
+        imported,
+        duplicates: parsed.trades.len() - imported,
+        rejected: parsed.rejected.len(),
+        rejections: parsed.rejected.into_iter().take(MAX_LISTED_REJECTIONS).collect(),
+        rescored,
+    };
+
+    tracing::info!(
+        whale = %whale.address,
+        rows = result.rows,
+        imported = result.imported,
+        duplicates = result.duplicates,
+        rejected = result.rejected,
+        rescored,
+        "Whale trades imported from CSV"
+    );
+
+    Ok(Json(ApiResponse {
+        success: true,
+        data: Some(result),
+        error: None,
+    }))
+}
@@ -1,13 +1,19 @@
 use axum::extract::ws::{Message, WebSocket, WebSocketUpgrade};
 use axum::extract::State;
-use axum::response::IntoResponse;
+use axum::http::StatusCode;
+use axum::response::{IntoResponse, Response};
+use tokio::sync::broadcast;
 
+use crate::api::ws_types::WsMessage;
 use crate::AppState;
 
 pub async fn handler(
     ws: WebSocketUpgrade,
     State(state): State<AppState>,
-) -> impl IntoResponse {
+) -> Response {
+    if state.shutdown.is_draining() {
+        return (StatusCode::SERVICE_UNAVAILABLE, "server is shutting down").into_response();
+    }
     ws.on_upgrade(|socket| handle_socket(socket, state))
 }
 
@@ -18,6 +24,11 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
 
     loop {
         tokio::select! {
+            // Server shutting down: send what's already queued, then close
+            _ = state.shutdown.wait() => {
+                flush_and_close(&mut socket, &mut rx).await;
+                break;
+            }
             // Forward broadcast messages to client
             msg = rx.recv() => {
                 match msg {
@@ -59,3 +70,20 @@ async fn handle_socket(mut socket: WebSocket, state: AppState) {
 
     tracing::info!("Dashboard WebSocket client disconnected");
 }
+
+/// Forward messages already in the broadcast buffer, then send a close frame.
+async fn flush_and_close(socket: &mut WebSocket, rx: &mut broadcast::Receiver<WsMessage>) {
+    loop {
+        match rx.try_recv() {
+            Ok(ws_msg) => {
+                let Ok(json) = serde_json::to_string(&ws_msg) else { continue };
+                if socket.send(Message::Text(json)).await.is_err() {
+                    return;
+                }
+            }
+            Err(broadcast::error::TryRecvError::Lagged(_)) => continue,
+            Err(_) => break,
+        }
+    }
+    let _ = socket.send(Message::Close(None)).await;
+}
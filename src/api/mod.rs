@@ -1,4 +1,5 @@
 pub mod auth;
+pub mod etag;
 pub mod handlers;
 pub mod router;
 pub mod ws_types;
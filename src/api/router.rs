@@ -1,13 +1,36 @@
+use std::time::Duration;
+
+use axum::extract::DefaultBodyLimit;
+use axum::handler::Handler;
+use axum::http::{header, HeaderValue};
 use axum::middleware;
-use axum::routing::{delete, get, post};
+use axum::routing::{delete, get, patch, post, put, MethodRouter};
 use axum::Router;
-use tower_http::cors::{Any, CorsLayer};
+use tower::ServiceBuilder;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, Any, CorsLayer};
+use tower_http::set_header::SetResponseHeaderLayer;
 use tower_http::trace::TraceLayer;
 
 use crate::AppState;
 use super::auth::require_auth;
+use super::etag::etag;
 use super::handlers;
 
+/// GET route for a heavy read endpoint the dashboard polls: ETag revalidation
+/// plus gzip/brotli compression.
+fn cached_get<H, T>(handler: H) -> MethodRouter<AppState>
+where
+    H: Handler<T, AppState>,
+    T: 'static,
+{
+    get(handler).layer(
+        ServiceBuilder::new()
+            .layer(CompressionLayer::new())
+            .layer(middleware::from_fn(etag)),
+    )
+}
+
 pub fn create_router(state: AppState) -> Router {
     // Public routes — no authentication required
     let public = Router::new()
@@ -17,46 +40,144 @@ pub fn create_router(state: AppState) -> Router {
     // Protected API routes — require Bearer token when API_TOKEN is set
     let protected = Router::new()
         // Dashboard
-        .route("/api/dashboard/summary", get(handlers::dashboard::summary))
+        .route("/api/dashboard/summary", cached_get(handlers::dashboard::summary))
+        .route("/api/dashboard/equity-curve", cached_get(handlers::dashboard::equity_curve))
         // Whales
-        .route("/api/whales", get(handlers::whales::list))
+        .route("/api/whales", cached_get(handlers::whales::list))
+        .route("/api/whales/correlations", get(handlers::whales::correlations))
         .route("/api/whales/:address", get(handlers::whales::detail))
         .route("/api/whales/:id/trades", get(handlers::whales::trades))
+        .route("/api/whales/:id/trades/import", post(handlers::whales::import_trades))
+        .route("/api/whales/:id/copy-performance", get(handlers::whales::copy_performance))
+        .route("/api/whales/:id/insider-flags", get(handlers::whales::insider_flags))
+        .route("/api/whales/:id/capital-flows", get(handlers::whales::capital_flows))
+        .route("/api/whales/:id/classification-audit", get(handlers::whales::classification_audit))
+        .route("/api/whales/:id/copy-resume", post(handlers::whales::copy_resume))
+        .route("/api/whales/:id/rescore", post(handlers::whales::rescore))
+        .route("/api/whales/:id/classification", patch(handlers::whales::update_classification))
         // Trades (copy orders)
-        .route("/api/trades", get(handlers::trades::list))
+        .route("/api/trades", cached_get(handlers::trades::list))
+        .route("/api/orders/manual", post(handlers::orders::manual))
         // Positions
-        .route("/api/positions", get(handlers::positions::list))
+        .route("/api/positions", cached_get(handlers::positions::list))
+        .route("/api/positions/aging", cached_get(handlers::positions::aging))
         .route("/api/positions/:id/close", post(handlers::positions::close))
+        .route("/api/positions/:id/close-preview", get(handlers::positions::close_preview))
+        .route("/api/positions/:id/stop", patch(handlers::positions::update_stop))
         // Baskets
         .route("/api/baskets", get(handlers::baskets::list).post(handlers::baskets::create))
-        .route("/api/baskets/:id", get(handlers::baskets::detail))
+        .route(
+            "/api/baskets/:id",
+            get(handlers::baskets::detail)
+                .patch(handlers::baskets::update)
+                .delete(handlers::baskets::deactivate),
+        )
         .route("/api/baskets/:id/whales", get(handlers::baskets::whales).post(handlers::baskets::add_whale))
         .route("/api/baskets/:id/whales/:whale_id", delete(handlers::baskets::remove_whale))
         .route("/api/baskets/:id/consensus", get(handlers::baskets::consensus_history))
+        .route("/api/baskets/:id/performance", get(handlers::baskets::performance))
+        .route("/api/baskets/:id/backtest", get(handlers::baskets::backtest))
+        .route(
+            "/api/baskets/:id/membership-events",
+            get(handlers::baskets::membership_events),
+        )
         .route("/api/consensus/recent", get(handlers::baskets::recent_consensus))
+        .route("/api/flow/recent", get(handlers::flow::recent))
+        // Markets
+        .route("/api/markets/:token_id/candles", get(handlers::markets::candles))
+        .route("/api/markets/:id/tape", get(handlers::markets::tape))
         // Analytics
-        .route("/api/analytics/pnl-history", get(handlers::analytics::pnl_history))
-        .route("/api/analytics/performance", get(handlers::analytics::performance))
+        .route("/api/analytics/pnl-history", cached_get(handlers::analytics::pnl_history))
+        .route("/api/analytics/performance", cached_get(handlers::analytics::performance))
+        .route("/api/analytics/sleeves", cached_get(handlers::analytics::sleeve_performance))
         // Config
         .route("/api/config", get(handlers::config::get_config).put(handlers::config::update_config))
+        // Risk limits
+        .route("/api/risk-limits", get(handlers::risk_limits::get).patch(handlers::risk_limits::update))
+        .route("/api/risk/events", get(handlers::risk_events::list))
+        // Signal-gate profiles
+        .route("/api/gate-profiles", get(handlers::gate_profiles::list))
+        .route("/api/gate-profiles/:name", put(handlers::gate_profiles::put))
+        .route(
+            "/api/gate-profiles/:name/activate",
+            post(handlers::gate_profiles::activate).delete(handlers::gate_profiles::deactivate),
+        )
+        // Whale tiers
+        .route("/api/tiers", get(handlers::tiers::list))
+        .route("/api/tiers/:tier", patch(handlers::tiers::update))
+        // Reconciliation
+        .route("/api/reconciliation/reports", get(handlers::reconciliation::reports))
+        // Compliance rules
+        .route("/api/compliance/rules", get(handlers::compliance::list).post(handlers::compliance::create))
+        .route("/api/compliance/rules/:id", patch(handlers::compliance::update).delete(handlers::compliance::delete))
+        // Market discovery exclusions
+        .route("/api/discovery/exclusions", get(handlers::discovery::list).post(handlers::discovery::create))
+        .route("/api/discovery/exclusions/:id", patch(handlers::discovery::update).delete(handlers::discovery::delete))
+        // Whale seeder candidate queue
+        .route("/api/seeder/candidates", get(handlers::seeder::list).post(handlers::seeder::enqueue))
         // Control
         .route("/api/control/stop", post(handlers::control::stop))
         .route("/api/control/resume", post(handlers::control::resume))
         .route("/api/control/status", get(handlers::control::status))
         .route("/api/control/cancel-all", post(handlers::control::cancel_all))
+        // Admin
+        .route("/api/admin/run/:job", post(handlers::admin::run_job))
         // WebSocket
         .route("/ws", get(handlers::ws::handler))
         .layer(middleware::from_fn(require_auth));
 
-    // CORS: allow same-origin + common dashboard origins
-    let cors = CorsLayer::new()
-        .allow_origin(Any) // nginx proxies from same origin; direct API access needs token
-        .allow_methods(Any)
-        .allow_headers(Any);
+    let cors = cors_layer(&state.config.cors_allowed_origins);
+    let body_limit = DefaultBodyLimit::max(state.config.max_request_body_bytes);
 
     public
         .merge(protected)
+        .layer(body_limit)
         .layer(cors)
+        // Standard hardening headers for a JSON API, unless a handler set them
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_CONTENT_TYPE_OPTIONS,
+            HeaderValue::from_static("nosniff"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::X_FRAME_OPTIONS,
+            HeaderValue::from_static("DENY"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::REFERRER_POLICY,
+            HeaderValue::from_static("no-referrer"),
+        ))
+        .layer(SetResponseHeaderLayer::if_not_present(
+            header::CONTENT_SECURITY_POLICY,
+            HeaderValue::from_static("default-src 'none'; frame-ancestors 'none'"),
+        ))
         .layer(TraceLayer::new_for_http())
         .with_state(state)
 }
+
+/// CORS: any origin when none are configured (nginx proxies from the same
+/// origin; direct API access needs the token), otherwise only the listed ones.
+/// A configured list with no valid origin allows none rather than any.
+fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    let origins: Vec<HeaderValue> = allowed_origins
+        .iter()
+        .filter_map(|o| match HeaderValue::from_str(o) {
+            Ok(v) => Some(v),
+            Err(_) => {
+                tracing::warn!(origin = %o, "Ignoring invalid CORS origin");
+                None
+            }
+        })
+        .collect();
+
+    let allow_origin = if allowed_origins.is_empty() {
+        AllowOrigin::any()
+    } else {
+        AllowOrigin::list(origins)
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods(Any)
+        .allow_headers(Any)
+        .max_age(Duration::from_secs(3600))
+}
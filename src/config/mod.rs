@@ -1,13 +1,30 @@
+pub mod secrets;
+
 use rust_decimal::Decimal;
+use sha2::{Digest, Sha256};
 use std::env;
 
+use crate::execution::order_rules::PriceRounding;
+use crate::models::StopMode;
+
 const DEFAULT_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/market";
+const DEFAULT_USER_WS_URL: &str = "wss://ws-subscriptions-clob.polymarket.com/ws/user";
 
 #[derive(Debug, Clone)]
 pub struct AppConfig {
+    /// Deployment label (dev, staging, prod) shown in status, logs, metrics
+    /// and notifications.
+    pub environment: String,
     pub database_url: String,
     pub host: String,
     pub port: u16,
+    /// Browser origins allowed by CORS; empty = any origin.
+    pub cors_allowed_origins: Vec<String>,
+    /// Maximum accepted request body size for the API.
+    pub max_request_body_bytes: usize,
+    /// Upper bound on graceful shutdown; connections and background tasks
+    /// still running after it are aborted.
+    pub shutdown_deadline_secs: u64,
     pub redis_url: Option<String>,
 
     // Polymarket API credentials (optional — required for authenticated endpoints)
@@ -18,6 +35,12 @@ pub struct AppConfig {
     // WebSocket
     pub polymarket_ws_url: String,
     pub ws_subscribe_token_ids: Vec<String>,
+    /// Reconnect and resubscribe when the market WebSocket sends no data for
+    /// this many seconds while subscribed (0 = never).
+    pub ws_stale_timeout_secs: u64,
+    /// Authenticated user channel streaming our own order/trade events
+    /// (empty = off; fills are then only found by polling).
+    pub polymarket_user_ws_url: String,
 
     // Wallet & execution
     pub private_key: Option<String>,
@@ -30,10 +53,22 @@ pub struct AppConfig {
     pub base_copy_amount: Decimal,
     pub copy_enabled: bool,
 
+    // Capital sleeves (e.g. "single_whale:0.7,basket:0.3,momentum:0,flow:0")
+    pub sleeve_weights: String,
+    // Copy profiles, each with its own gates, size multiplier and sleeve
+    // (e.g. "conservative:sleeve=single_whale,win_rate=0.65,size=0.5;aggressive:sleeve=momentum,size=1.5")
+    pub copy_profiles: String,
+
     // Telegram notifications
     pub telegram_bot_token: Option<String>,
     pub telegram_chat_id: Option<String>,
     pub notifications_enabled: bool,
+    /// Batch notifications every N minutes (0 = send each immediately).
+    pub notify_digest_interval_mins: u64,
+    /// Comma-separated severities batched in digest mode (info, warning, critical).
+    pub notify_digest_severities: String,
+    /// Fills below this notional are `info` (digestible), larger ones `warning`.
+    pub notify_small_fill_usd: Decimal,
 
     // Basket consensus
     pub basket_consensus_threshold: Decimal,
@@ -41,6 +76,43 @@ pub struct AppConfig {
     pub basket_min_wallets: i32,
     pub basket_max_wallets: i32,
     pub basket_enabled: bool,
+    /// Basket admission rejects whales whose max drawdown (percent of peak equity) exceeds this; 0 = off.
+    pub basket_max_drawdown_pct: Decimal,
+    /// Basket admission rejects whales whose skill score (1 − probability the record is luck) is below this; 0 = off.
+    pub basket_min_skill_score: Decimal,
+    /// Basket whales whose trade overlap reaches this count as one consensus vote; 0 = off.
+    pub basket_correlation_threshold: Decimal,
+    pub basket_correlation_days: i64,
+    /// Basket admission rejects whales with at least this many insider flags (0 = off).
+    pub basket_max_insider_flags: i64,
+    /// After each seeder cycle, create missing category baskets and fill them with qualifying whales.
+    pub basket_auto_create: bool,
+    /// Rebalance baskets — evict members that no longer pass admission and
+    /// back-fill open slots — every this many seconds (0 = off).
+    pub basket_rebalance_interval_secs: u64,
+    /// After a consensus signal the basket stays quiet on that market for this
+    /// many hours, in either direction (0 = the basket's time window).
+    pub basket_consensus_cooldown_hours: i32,
+    /// Notify (without trading) when a basket's vote share on a market reaches
+    /// `basket_building_threshold` but not the basket's consensus threshold.
+    pub basket_building_alerts: bool,
+    pub basket_building_threshold: Decimal,
+    /// Two baskets in opposite consensus on one market: "allow" trades both,
+    /// "net" trades the later one by its margin, "stronger" only if it is the
+    /// stronger, "block" trades neither again.
+    pub basket_conflict_mode: String,
+    /// Basket signal size at 100% consensus among voters at `consensus_reference_win_rate`.
+    pub consensus_base_amount: Decimal,
+    /// Mean win rate of the consensus voters that earns the base amount.
+    pub consensus_reference_win_rate: Decimal,
+    /// Cap on the voter-quality multiple of the base amount.
+    pub consensus_max_multiplier: Decimal,
+
+    // Wallet classifier
+    pub wallet_classifier: String,
+    pub wallet_classifier_min_labels: usize,
+    pub wallet_classifier_min_confidence: f64,
+    pub wallet_classifier_min_trades: usize,
 
     // Market discovery
     pub market_discovery_enabled: bool,
@@ -55,34 +127,215 @@ pub struct AppConfig {
 
     // Whale trade poller
     pub whale_poller_interval_secs: u64,
+    pub whale_poll_max_backoff_secs: u64,
+    pub whale_poll_alert_hours: i64,
 
     // Chain listener (Polygon on-chain OrderFilled events)
     pub chain_listener_enabled: bool,
-    pub polygon_ws_url: Option<String>,
+    /// Polygon WSS endpoints, failed over between in order of health.
+    pub polygon_ws_urls: Vec<String>,
+    /// Blocks on top of a whale fill before it is forwarded (0 = immediately).
+    pub chain_confirmations: u64,
 
     // Exit strategy (SL/TP)
     pub default_stop_loss_pct: Decimal,
     pub default_take_profit_pct: Decimal,
+    /// Stop mode for new positions: `static` or `trailing`.
+    pub default_stop_mode: String,
+    /// How to follow a copied whale's sell: `full` or `proportional`.
+    pub whale_exit_mode: String,
     pub position_monitor_interval_secs: u64,
+    pub position_ws_delta_pct: Decimal,
+    /// WS quotes older than this are ignored and the orderbook is fetched instead.
+    pub price_cache_max_age_secs: i64,
+    /// Reprice unfilled stop-loss style exit orders after this many seconds (0 = off).
+    pub exit_reprice_secs: i64,
+    /// Each exit reprice sells this many percent further below the best bid.
+    pub exit_reprice_step_pct: Decimal,
+    /// Give up repricing (and alert) after this many attempts.
+    pub exit_max_reprices: u32,
+    /// Position health: flag positions held this many days (0 = off).
+    pub position_stale_days: i64,
+    /// Position health: flag markets whose liquidity fell this many percent since entry.
+    pub position_liquidity_drop_pct: Decimal,
+    /// UTC hour of the daily position health notification.
+    pub position_health_report_hour: u32,
+    /// MATIC/USD price endpoint used to convert gas paid on fills into USDC.
+    pub matic_usd_price_url: String,
+
+    // Market candles (1-minute OHLC from WS price events)
+    pub candle_retention_days: i64,
+    /// Hours of WS and chain trade prints kept for the trade tape (0 = off).
+    pub trade_tape_retention_hours: i64,
+
+    // Durable pipeline queue
+    /// Route ingested events through Postgres so a crash replays the ones in
+    /// flight instead of losing them.
+    pub pipeline_durable_queue: bool,
+    /// Hours processed events are kept in the queue.
+    pub pipeline_queue_retention_hours: i64,
+    /// Anonymous WS trades that can wait behind tracked-whale trades before
+    /// new ones are dropped.
+    pub pipeline_low_priority_capacity: usize,
+    /// Keep one in this many anonymous trades once their lane is half full.
+    pub pipeline_low_priority_sample: u64,
+    /// Pipeline workers; each wallet's trades always go to the same one.
+    pub pipeline_workers: usize,
+
+    // Research recorder (`polybot record`)
+    /// Directory the recorded tick/book dataset is written to.
+    pub recorder_dir: String,
+    pub recorder_max_markets: usize,
+    pub recorder_min_volume: Decimal,
+    pub recorder_refresh_secs: u64,
 
     // Pipeline signal quality
     pub tracked_whale_min_notional: Decimal,
+    /// UNKNOWN-wallet trades must reach this percentile of market-wide trade
+    /// sizes over the last `whale_notional_window_hours` to count as whale-grade.
+    pub whale_notional_percentile: Decimal,
+    pub whale_notional_window_hours: i64,
+    pub whale_notional_min_samples: usize,
+    pub whale_notional_floor: Decimal,
+    pub whale_notional_stats_interval_secs: u64,
     pub min_resolved_for_signal: i32,
+    /// Entry edge that stands in for resolved trades (0 = off), and the
+    /// entries it must be measured over.
+    pub min_entry_edge: Decimal,
+    pub min_entry_edge_samples: i32,
     pub min_signal_win_rate: Decimal,
+    /// Minimum 95% Wilson lower bound on the whale's win rate to emit a signal (0 = off).
+    pub min_signal_win_rate_lb: Decimal,
     pub min_total_trades_for_signal: i32,
     pub signal_notional_liquidity_pct: Decimal,
     pub signal_notional_floor: Decimal,
     pub max_signal_notional: Decimal,
     pub min_signal_ev: Decimal,
+    pub min_signal_profit_factor: Decimal,
+    pub min_signal_skill_score: Decimal,
     pub assumed_slippage_pct: Decimal,
+    /// Filled copies before a whale's measured copy lag replaces the assumed slippage (0 = off).
+    pub copy_lag_min_samples: i64,
+    /// Replayed trades (chain gap backfill, durable queue) older than this are not copied.
+    pub signal_ttl_secs: u64,
+    /// First large buy in a market: notional threshold (0 = off) and copy size multiplier.
+    pub first_mover_min_notional: Decimal,
+    pub first_mover_size_multiplier: Decimal,
 
     // Risk management
     pub max_daily_loss: Decimal,
+    pub max_tail_loss_pct: Decimal,
+
+    // Execution circuit breaker
+    pub circuit_breaker_max_failures: usize,
+    pub circuit_breaker_window_mins: i64,
+    pub circuit_breaker_cooldown_mins: i64,
+
+    // Per-whale copy guard (pause whales whose copies lose money)
+    pub copy_guard_min_closed: i64,
+    pub copy_guard_max_loss: Decimal,
+    pub copy_guard_interval_secs: u64,
+
+    // Leaderboard drift: whale leaderboard PnL vs. the baseline at seeding
+    /// Flag whales whose leaderboard PnL fell this many percent (0 = off).
+    pub leaderboard_drift_max_drawdown_pct: Decimal,
+    /// Pause copying flagged whales instead of only warning.
+    pub leaderboard_drift_auto_pause: bool,
+    pub leaderboard_drift_interval_secs: u64,
+
+    // Capital flows: large USDC deposits into / withdrawals out of whale wallets
+    /// Smallest transfer recorded, in USDC (0 = off).
+    pub capital_flow_min_usdc: Decimal,
+    /// Alert when a withdrawal takes at least this percent of a whale's USDC.
+    pub capital_flow_withdrawal_alert_pct: Decimal,
+    pub capital_flow_interval_secs: u64,
+
+    // Entry edge: favourable price move after whale entries, from candles
+    /// Hours after each entry at which the price is read (0 = off).
+    pub entry_edge_horizon_hours: i32,
+    pub entry_edge_interval_secs: u64,
+
+    // Holding profile: how long whales hold and whether they exit early
+    /// Profiling interval (0 = off; copies then always mirror whale exits).
+    pub holding_profile_interval_secs: u64,
+    pub holding_profile_min_samples: usize,
+    /// Early-exit share at or above which a whale's copies mirror its exits;
+    /// below it they are held to resolution.
+    pub holding_profile_swing_ratio: Decimal,
+
+    /// Batch rescoring of every whale from all its resolved trades (0 = off;
+    /// scores then only refresh when a whale trades).
+    pub rescore_interval_secs: u64,
+
+    // Insider detection: suspiciously well-timed large entries
+    /// Scan interval (0 = off).
+    pub insider_scan_interval_secs: u64,
+    pub insider_min_notional: Decimal,
+    pub insider_resolution_window_hours: i64,
+    pub insider_jump_window_hours: i64,
+    pub insider_min_price_move: Decimal,
+    pub insider_lookback_days: i64,
 
     // Maker mode
     pub maker_mode: bool,
     pub maker_order_ttl_secs: u64,
     pub maker_price_offset: Decimal,
+    /// Per-origin execution modes, e.g. `whale:marketable` (unset origins follow MAKER_MODE).
+    pub execution_modes: String,
+
+    // Venue order rules: rounding policy and fallbacks for markets whose
+    // tick size / minimum size can't be fetched
+    pub order_tick_size: Decimal,
+    /// Minimum order size in shares.
+    pub order_min_size: Decimal,
+    /// Decimal places order sizes are truncated to.
+    pub order_size_decimals: u32,
+    /// `conservative` (BUY rounds down, SELL up) or `nearest`.
+    pub order_price_rounding: String,
+
+    // Scale-in (DCA) ladder for high-conviction signals
+    pub scale_in_min_strength: Decimal,
+    pub scale_in_tranches: u32,
+    pub scale_in_step_pct: Decimal,
+
+    // Soft-launch ramp-up: per-position notional cap for a new live deployment
+    pub ramp_up_enabled: bool,
+    pub ramp_up_initial_cap: Decimal,
+    pub ramp_up_launch_trades: usize,
+    pub ramp_up_launch_days: i64,
+    pub ramp_up_step_trades: usize,
+    pub ramp_up_step_multiplier: Decimal,
+    pub ramp_up_max_tracking_error: Decimal,
+
+    // Net whale flow: buy-minus-sell notional from all tracked whales per token
+    /// Rolling window (0 = off).
+    pub flow_window_minutes: i32,
+    pub flow_min_net_notional: Decimal,
+    pub flow_min_whales: i64,
+    /// Minimum net / gross notional, so two-sided churn doesn't signal.
+    pub flow_min_imbalance: Decimal,
+    /// Flow sleeve size at a net flow of `flow_min_net_notional`.
+    pub flow_base_amount: Decimal,
+    pub flow_max_multiplier: Decimal,
+
+    /// Gate and size whale signals per A/B/C tier (policies live in `whale_tiers`)
+    /// instead of by the global signal gates.
+    pub whale_tiers_enabled: bool,
+
+    // Hedging: cover losing positions with the opposite outcome
+    pub hedge_loss_pct: Decimal,
+    pub hedge_ratio_pct: Decimal,
+
+    // Position reconciliation against the wallet's Polymarket holdings
+    pub reconcile_interval_secs: u64,
+    pub reconcile_auto_correct: bool,
+    /// Daily full reconciliation (positions, CLOB orders, capital ledger) at this UTC hour.
+    pub eod_reconcile_enabled: bool,
+    pub eod_reconcile_hour: u32,
+    pub eod_reconcile_balance_tolerance: Decimal,
+
+    // Equity curve snapshots
+    pub equity_snapshot_interval_secs: u64,
 }
 
 impl AppConfig {
@@ -94,13 +347,59 @@ impl AppConfig {
             .filter(|s| !s.is_empty())
             .collect();
 
+        let cors_allowed_origins: Vec<String> = env::var("CORS_ALLOWED_ORIGINS")
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().trim_end_matches('/').to_string())
+            .filter(|s| !s.is_empty() && s != "*")
+            .collect();
+        // A typo in the allowlist must not fall back to allowing any origin
+        if let Some(bad) = cors_allowed_origins
+            .iter()
+            .find(|o| axum::http::HeaderValue::from_str(o).is_err())
+        {
+            anyhow::bail!("CORS_ALLOWED_ORIGINS contains an invalid origin: '{bad}'");
+        }
+
+        // One URL or several separated by commas
+        let polygon_ws_urls: Vec<String> = env::var("POLYGON_WS_URL")
+            .unwrap_or_default()
+            .split(',')
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        let order_price_rounding =
+            env::var("ORDER_PRICE_ROUNDING").unwrap_or_else(|_| "conservative".into());
+        if PriceRounding::parse(&order_price_rounding).is_none() {
+            anyhow::bail!("ORDER_PRICE_ROUNDING must be 'conservative' or 'nearest', got '{order_price_rounding}'");
+        }
+        let default_stop_mode = env::var("STOP_MODE").unwrap_or_else(|_| "static".into());
+        if StopMode::parse(&default_stop_mode).is_none() {
+            anyhow::bail!("STOP_MODE must be 'static' or 'trailing', got '{default_stop_mode}'");
+        }
+
         Ok(Self {
+            environment: env::var("ENVIRONMENT")
+                .map(|e| e.trim().to_lowercase())
+                .ok()
+                .filter(|e| !e.is_empty())
+                .unwrap_or_else(|| "dev".into()),
             database_url: env::var("DATABASE_URL")
                 .map_err(|_| anyhow::anyhow!("DATABASE_URL must be set"))?,
             host: env::var("HOST").unwrap_or_else(|_| "0.0.0.0".into()),
             port: env::var("PORT")
                 .unwrap_or_else(|_| "8080".into())
                 .parse()?,
+            cors_allowed_origins,
+            max_request_body_bytes: env::var("MAX_REQUEST_BODY_BYTES")
+                .unwrap_or_else(|_| "1048576".into())
+                .parse()
+                .unwrap_or(1_048_576),
+            shutdown_deadline_secs: env::var("SHUTDOWN_DEADLINE_SECS")
+                .unwrap_or_else(|_| "20".into())
+                .parse()
+                .unwrap_or(20),
             redis_url: env::var("REDIS_URL").ok(),
 
             polymarket_api_key: env::var("POLYMARKET_API_KEY").ok(),
@@ -110,6 +409,12 @@ impl AppConfig {
             polymarket_ws_url: env::var("POLYMARKET_WS_URL")
                 .unwrap_or_else(|_| DEFAULT_WS_URL.into()),
             ws_subscribe_token_ids,
+            ws_stale_timeout_secs: env::var("WS_STALE_TIMEOUT_SECS")
+                .unwrap_or_else(|_| "120".into())
+                .parse()
+                .unwrap_or(120),
+            polymarket_user_ws_url: env::var("POLYMARKET_USER_WS_URL")
+                .unwrap_or_else(|_| DEFAULT_USER_WS_URL.into()),
 
             private_key: env::var("PRIVATE_KEY").ok(),
             polygon_rpc_url: env::var("RPC_URL")
@@ -133,12 +438,26 @@ impl AppConfig {
                 .parse()
                 .unwrap_or(false),
 
+            sleeve_weights: env::var("SLEEVE_WEIGHTS")
+                .unwrap_or_else(|_| "single_whale:0.7,basket:0.3,momentum:0,flow:0".into()),
+            copy_profiles: env::var("COPY_PROFILES").unwrap_or_default(),
+
             telegram_bot_token: env::var("TELEGRAM_BOT_TOKEN").ok(),
             telegram_chat_id: env::var("TELEGRAM_CHAT_ID").ok(),
             notifications_enabled: env::var("NOTIFICATIONS_ENABLED")
                 .unwrap_or_else(|_| "false".into())
                 .parse()
                 .unwrap_or(false),
+            notify_digest_interval_mins: env::var("NOTIFY_DIGEST_INTERVAL_MINS")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(0),
+            notify_digest_severities: env::var("NOTIFY_DIGEST_SEVERITIES")
+                .unwrap_or_else(|_| "info".into()),
+            notify_small_fill_usd: env::var("NOTIFY_SMALL_FILL_USD")
+                .unwrap_or_else(|_| "100".into())
+                .parse()
+                .unwrap_or(Decimal::from(100)),
 
             basket_consensus_threshold: env::var("BASKET_CONSENSUS_THRESHOLD")
                 .unwrap_or_else(|_| "0.80".into())
@@ -160,6 +479,74 @@ impl AppConfig {
                 .unwrap_or_else(|_| "false".into())
                 .parse()
                 .unwrap_or(false),
+            basket_max_drawdown_pct: env::var("BASKET_MAX_DRAWDOWN_PCT")
+                .unwrap_or_else(|_| "50".into())
+                .parse()
+                .unwrap_or(Decimal::from(50)),
+            basket_min_skill_score: env::var("BASKET_MIN_SKILL_SCORE")
+                .unwrap_or_else(|_| "0.90".into())
+                .parse()
+                .unwrap_or(Decimal::new(90, 2)),
+            basket_correlation_threshold: env::var("BASKET_CORRELATION_THRESHOLD")
+                .unwrap_or_else(|_| "0.6".into())
+                .parse()
+                .unwrap_or(Decimal::new(6, 1)),
+            basket_correlation_days: env::var("BASKET_CORRELATION_DAYS")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(30),
+            basket_max_insider_flags: env::var("BASKET_MAX_INSIDER_FLAGS")
+                .unwrap_or_else(|_| "2".into())
+                .parse()
+                .unwrap_or(2),
+            basket_auto_create: env::var("BASKET_AUTO_CREATE")
+                .unwrap_or_else(|_| "true".into())
+                .parse()
+                .unwrap_or(true),
+            basket_rebalance_interval_secs: env::var("BASKET_REBALANCE_INTERVAL")
+                .unwrap_or_else(|_| "21600".into())
+                .parse()
+                .unwrap_or(21600),
+            basket_consensus_cooldown_hours: env::var("BASKET_CONSENSUS_COOLDOWN_HOURS")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(0),
+            basket_building_alerts: env::var("BASKET_BUILDING_ALERTS")
+                .unwrap_or_else(|_| "false".into())
+                .parse()
+                .unwrap_or(false),
+            basket_building_threshold: env::var("BASKET_BUILDING_THRESHOLD")
+                .unwrap_or_else(|_| "0.6".into())
+                .parse()
+                .unwrap_or(Decimal::new(6, 1)),
+            basket_conflict_mode: env::var("BASKET_CONFLICT_MODE").unwrap_or_else(|_| "stronger".into()),
+            consensus_base_amount: env::var("CONSENSUS_BASE_AMOUNT")
+                .unwrap_or_else(|_| "50".into())
+                .parse()
+                .unwrap_or(Decimal::from(50)),
+            consensus_reference_win_rate: env::var("CONSENSUS_REFERENCE_WIN_RATE")
+                .unwrap_or_else(|_| "0.6".into())
+                .parse()
+                .unwrap_or(Decimal::new(6, 1)),
+            consensus_max_multiplier: env::var("CONSENSUS_MAX_MULTIPLIER")
+                .unwrap_or_else(|_| "1.5".into())
+                .parse()
+                .unwrap_or(Decimal::new(15, 1)),
+
+            wallet_classifier: env::var("WALLET_CLASSIFIER")
+                .unwrap_or_else(|_| "heuristic".into()),
+            wallet_classifier_min_labels: env::var("WALLET_CLASSIFIER_MIN_LABELS")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(30),
+            wallet_classifier_min_confidence: env::var("WALLET_CLASSIFIER_MIN_CONFIDENCE")
+                .unwrap_or_else(|_| "0.6".into())
+                .parse()
+                .unwrap_or(0.6),
+            wallet_classifier_min_trades: env::var("WALLET_CLASSIFIER_MIN_TRADES")
+                .unwrap_or_else(|_| "10".into())
+                .parse()
+                .unwrap_or(10),
 
             market_discovery_enabled: env::var("MARKET_DISCOVERY_ENABLED")
                 .unwrap_or_else(|_| "false".into())
@@ -195,12 +582,24 @@ impl AppConfig {
                 .unwrap_or_else(|_| "60".into())
                 .parse()
                 .unwrap_or(60),
+            whale_poll_max_backoff_secs: env::var("WHALE_POLL_MAX_BACKOFF_SECS")
+                .unwrap_or_else(|_| "3600".into())
+                .parse()
+                .unwrap_or(3600),
+            whale_poll_alert_hours: env::var("WHALE_POLL_ALERT_HOURS")
+                .unwrap_or_else(|_| "6".into())
+                .parse()
+                .unwrap_or(6),
 
             chain_listener_enabled: env::var("CHAIN_LISTENER_ENABLED")
                 .unwrap_or_else(|_| "false".into())
                 .parse()
                 .unwrap_or(false),
-            polygon_ws_url: env::var("POLYGON_WS_URL").ok(),
+            polygon_ws_urls,
+            chain_confirmations: env::var("CHAIN_CONFIRMATIONS")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(0),
 
             default_stop_loss_pct: env::var("STOP_LOSS_PCT")
                 .unwrap_or_else(|_| "15.0".into())
@@ -210,23 +609,135 @@ impl AppConfig {
                 .unwrap_or_else(|_| "20.0".into())
                 .parse()
                 .unwrap_or(Decimal::new(2000, 2)),
+            default_stop_mode,
+            whale_exit_mode: env::var("WHALE_EXIT_MODE").unwrap_or_else(|_| "full".into()),
             position_monitor_interval_secs: env::var("POSITION_MONITOR_INTERVAL")
                 .unwrap_or_else(|_| "30".into())
                 .parse()
                 .unwrap_or(30),
+            position_ws_delta_pct: env::var("POSITION_WS_DELTA_PCT")
+                .unwrap_or_else(|_| "0.5".into())
+                .parse()
+                .unwrap_or(Decimal::new(5, 1)),
+            price_cache_max_age_secs: env::var("PRICE_CACHE_MAX_AGE_SECS")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(30),
+            exit_reprice_secs: env::var("EXIT_REPRICE_SECS")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(30),
+            exit_reprice_step_pct: env::var("EXIT_REPRICE_STEP_PCT")
+                .unwrap_or_else(|_| "2".into())
+                .parse()
+                .unwrap_or(Decimal::TWO),
+            exit_max_reprices: env::var("EXIT_MAX_REPRICES")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .unwrap_or(5),
+            position_stale_days: env::var("POSITION_STALE_DAYS")
+                .unwrap_or_else(|_| "7".into())
+                .parse()
+                .unwrap_or(7),
+            position_liquidity_drop_pct: env::var("POSITION_LIQUIDITY_DROP_PCT")
+                .unwrap_or_else(|_| "50".into())
+                .parse()
+                .unwrap_or(Decimal::from(50)),
+            position_health_report_hour: env::var("POSITION_HEALTH_REPORT_HOUR")
+                .unwrap_or_else(|_| "8".into())
+                .parse()
+                .unwrap_or(8),
+            matic_usd_price_url: env::var("MATIC_USD_PRICE_URL")
+                .unwrap_or_else(|_| crate::execution::fill_costs::DEFAULT_MATIC_PRICE_URL.into()),
+
+            candle_retention_days: env::var("CANDLE_RETENTION_DAYS")
+                .unwrap_or_else(|_| "7".into())
+                .parse()
+                .unwrap_or(7),
+            trade_tape_retention_hours: env::var("TRADE_TAPE_RETENTION_HOURS")
+                .unwrap_or_else(|_| "48".into())
+                .parse()
+                .unwrap_or(48),
+
+            pipeline_durable_queue: env::var("PIPELINE_DURABLE_QUEUE")
+                .unwrap_or_else(|_| "false".into())
+                .parse()
+                .unwrap_or(false),
+            pipeline_queue_retention_hours: env::var("PIPELINE_QUEUE_RETENTION_HOURS")
+                .unwrap_or_else(|_| "24".into())
+                .parse()
+                .unwrap_or(24),
+            pipeline_low_priority_capacity: env::var("PIPELINE_LOW_PRIORITY_CAPACITY")
+                .unwrap_or_else(|_| "1000".into())
+                .parse()
+                .unwrap_or(1000),
+            pipeline_low_priority_sample: env::var("PIPELINE_LOW_PRIORITY_SAMPLE")
+                .unwrap_or_else(|_| "4".into())
+                .parse()
+                .unwrap_or(4),
+            pipeline_workers: env::var("PIPELINE_WORKERS")
+                .unwrap_or_else(|_| "4".into())
+                .parse()
+                .unwrap_or(4),
+
+            recorder_dir: env::var("RECORDER_DIR").unwrap_or_else(|_| "data/recordings".into()),
+            recorder_max_markets: env::var("RECORDER_MAX_MARKETS")
+                .unwrap_or_else(|_| "1000".into())
+                .parse()
+                .unwrap_or(1000),
+            recorder_min_volume: env::var("RECORDER_MIN_VOLUME")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
+            recorder_refresh_secs: env::var("RECORDER_REFRESH_SECS")
+                .unwrap_or_else(|_| "900".into())
+                .parse()
+                .unwrap_or(900),
 
             tracked_whale_min_notional: env::var("TRACKED_WHALE_MIN_NOTIONAL")
                 .unwrap_or_else(|_| "500".into())
                 .parse()
                 .unwrap_or(Decimal::from(500)),
+            whale_notional_percentile: env::var("WHALE_NOTIONAL_PERCENTILE")
+                .unwrap_or_else(|_| "99".into())
+                .parse()
+                .unwrap_or(Decimal::from(99)),
+            whale_notional_window_hours: env::var("WHALE_NOTIONAL_WINDOW_HOURS")
+                .unwrap_or_else(|_| "24".into())
+                .parse()
+                .unwrap_or(24),
+            whale_notional_min_samples: env::var("WHALE_NOTIONAL_MIN_SAMPLES")
+                .unwrap_or_else(|_| "500".into())
+                .parse()
+                .unwrap_or(500),
+            whale_notional_floor: env::var("WHALE_NOTIONAL_FLOOR")
+                .unwrap_or_else(|_| "1000".into())
+                .parse()
+                .unwrap_or(Decimal::from(1_000)),
+            whale_notional_stats_interval_secs: env::var("WHALE_NOTIONAL_STATS_INTERVAL")
+                .unwrap_or_else(|_| "60".into())
+                .parse()
+                .unwrap_or(60),
             min_resolved_for_signal: env::var("MIN_RESOLVED_FOR_SIGNAL")
                 .unwrap_or_else(|_| "5".into())
                 .parse()
                 .unwrap_or(5),
+            min_entry_edge: env::var("MIN_ENTRY_EDGE")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
+            min_entry_edge_samples: env::var("MIN_ENTRY_EDGE_SAMPLES")
+                .unwrap_or_else(|_| "20".into())
+                .parse()
+                .unwrap_or(20),
             min_signal_win_rate: env::var("MIN_SIGNAL_WIN_RATE")
                 .unwrap_or_else(|_| "0.60".into())
                 .parse()
                 .unwrap_or(Decimal::new(60, 2)),
+            min_signal_win_rate_lb: env::var("MIN_SIGNAL_WIN_RATE_LB")
+                .unwrap_or_else(|_| "0.50".into())
+                .parse()
+                .unwrap_or(Decimal::new(50, 2)),
             min_total_trades_for_signal: env::var("MIN_TOTAL_TRADES_FOR_SIGNAL")
                 .unwrap_or_else(|_| "100".into())
                 .parse()
@@ -247,15 +758,147 @@ impl AppConfig {
                 .unwrap_or_else(|_| "50".into())
                 .parse()
                 .unwrap_or(Decimal::from(50)),
+            min_signal_profit_factor: env::var("MIN_SIGNAL_PROFIT_FACTOR")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
+            min_signal_skill_score: env::var("MIN_SIGNAL_SKILL_SCORE")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
             assumed_slippage_pct: env::var("ASSUMED_SLIPPAGE_PCT")
                 .unwrap_or_else(|_| "0.02".into())
                 .parse()
                 .unwrap_or(Decimal::new(2, 2)),
+            copy_lag_min_samples: env::var("COPY_LAG_MIN_SAMPLES")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .unwrap_or(5),
+            signal_ttl_secs: env::var("SIGNAL_TTL_SECS")
+                .unwrap_or_else(|_| "300".into())
+                .parse()
+                .unwrap_or(300),
+            first_mover_min_notional: env::var("FIRST_MOVER_MIN_NOTIONAL")
+                .unwrap_or_else(|_| "10000".into())
+                .parse()
+                .unwrap_or(Decimal::from(10_000)),
+            first_mover_size_multiplier: env::var("FIRST_MOVER_SIZE_MULTIPLIER")
+                .unwrap_or_else(|_| "1".into())
+                .parse()
+                .unwrap_or(Decimal::ONE),
 
             max_daily_loss: env::var("MAX_DAILY_LOSS")
                 .unwrap_or_else(|_| "2000".into())
                 .parse()
                 .unwrap_or(Decimal::from(2_000)),
+            max_tail_loss_pct: env::var("MAX_TAIL_LOSS_PCT")
+                .unwrap_or_else(|_| "0.25".into())
+                .parse()
+                .unwrap_or(Decimal::new(25, 2)),
+
+            circuit_breaker_max_failures: env::var("CIRCUIT_BREAKER_MAX_FAILURES")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .unwrap_or(5),
+            circuit_breaker_window_mins: env::var("CIRCUIT_BREAKER_WINDOW_MINS")
+                .unwrap_or_else(|_| "10".into())
+                .parse()
+                .unwrap_or(10),
+            circuit_breaker_cooldown_mins: env::var("CIRCUIT_BREAKER_COOLDOWN_MINS")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(30),
+
+            copy_guard_min_closed: env::var("COPY_GUARD_MIN_CLOSED")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .unwrap_or(5),
+            copy_guard_max_loss: env::var("COPY_GUARD_MAX_LOSS")
+                .unwrap_or_else(|_| "100".into())
+                .parse()
+                .unwrap_or(Decimal::from(100)),
+            copy_guard_interval_secs: env::var("COPY_GUARD_INTERVAL")
+                .unwrap_or_else(|_| "900".into())
+                .parse()
+                .unwrap_or(900),
+
+            leaderboard_drift_max_drawdown_pct: env::var("LEADERBOARD_DRIFT_MAX_DRAWDOWN_PCT")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(Decimal::from(30)),
+            leaderboard_drift_auto_pause: env::var("LEADERBOARD_DRIFT_AUTO_PAUSE")
+                .unwrap_or_else(|_| "false".into())
+                .parse()
+                .unwrap_or(false),
+            leaderboard_drift_interval_secs: env::var("LEADERBOARD_DRIFT_INTERVAL")
+                .unwrap_or_else(|_| "21600".into())
+                .parse()
+                .unwrap_or(21600),
+
+            capital_flow_min_usdc: env::var("CAPITAL_FLOW_MIN_USDC")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
+            capital_flow_withdrawal_alert_pct: env::var("CAPITAL_FLOW_WITHDRAWAL_ALERT_PCT")
+                .unwrap_or_else(|_| "50".into())
+                .parse()
+                .unwrap_or(Decimal::from(50)),
+            capital_flow_interval_secs: env::var("CAPITAL_FLOW_INTERVAL")
+                .unwrap_or_else(|_| "60".into())
+                .parse()
+                .unwrap_or(60),
+
+            entry_edge_horizon_hours: env::var("ENTRY_EDGE_HORIZON_HOURS")
+                .unwrap_or_else(|_| "24".into())
+                .parse()
+                .unwrap_or(24),
+            entry_edge_interval_secs: env::var("ENTRY_EDGE_INTERVAL")
+                .unwrap_or_else(|_| "3600".into())
+                .parse()
+                .unwrap_or(3600),
+
+            rescore_interval_secs: env::var("RESCORE_INTERVAL")
+                .unwrap_or_else(|_| "21600".into())
+                .parse()
+                .unwrap_or(21600),
+
+            holding_profile_interval_secs: env::var("HOLDING_PROFILE_INTERVAL")
+                .unwrap_or_else(|_| "3600".into())
+                .parse()
+                .unwrap_or(3600),
+            holding_profile_min_samples: env::var("HOLDING_PROFILE_MIN_SAMPLES")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .unwrap_or(5),
+            holding_profile_swing_ratio: env::var("HOLDING_PROFILE_SWING_RATIO")
+                .unwrap_or_else(|_| "0.5".into())
+                .parse()
+                .unwrap_or(Decimal::new(5, 1)),
+
+            insider_scan_interval_secs: env::var("INSIDER_SCAN_INTERVAL")
+                .unwrap_or_else(|_| "3600".into())
+                .parse()
+                .unwrap_or(3600),
+            insider_min_notional: env::var("INSIDER_MIN_NOTIONAL")
+                .unwrap_or_else(|_| "5000".into())
+                .parse()
+                .unwrap_or(Decimal::from(5000)),
+            insider_resolution_window_hours: env::var("INSIDER_RESOLUTION_WINDOW_HOURS")
+                .unwrap_or_else(|_| "24".into())
+                .parse()
+                .unwrap_or(24),
+            insider_jump_window_hours: env::var("INSIDER_JUMP_WINDOW_HOURS")
+                .unwrap_or_else(|_| "6".into())
+                .parse()
+                .unwrap_or(6),
+            insider_min_price_move: env::var("INSIDER_MIN_PRICE_MOVE")
+                .unwrap_or_else(|_| "0.25".into())
+                .parse()
+                .unwrap_or(Decimal::new(25, 2)),
+            insider_lookback_days: env::var("INSIDER_LOOKBACK_DAYS")
+                .unwrap_or_else(|_| "30".into())
+                .parse()
+                .unwrap_or(30),
 
             maker_mode: env::var("MAKER_MODE")
                 .unwrap_or_else(|_| "true".into())
@@ -269,6 +912,124 @@ impl AppConfig {
                 .unwrap_or_else(|_| "0".into())
                 .parse()
                 .unwrap_or(Decimal::ZERO),
+            execution_modes: env::var("EXECUTION_MODES").unwrap_or_default(),
+
+            order_tick_size: env::var("ORDER_TICK_SIZE")
+                .unwrap_or_else(|_| "0.01".into())
+                .parse()
+                .unwrap_or(Decimal::new(1, 2)),
+            order_min_size: env::var("ORDER_MIN_SIZE")
+                .unwrap_or_else(|_| "5".into())
+                .parse()
+                .unwrap_or(Decimal::from(5)),
+            order_size_decimals: env::var("ORDER_SIZE_DECIMALS")
+                .unwrap_or_else(|_| "2".into())
+                .parse()
+                .unwrap_or(2),
+            order_price_rounding,
+
+            scale_in_min_strength: env::var("SCALE_IN_MIN_STRENGTH")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
+            scale_in_tranches: env::var("SCALE_IN_TRANCHES")
+                .unwrap_or_else(|_| "3".into())
+                .parse()
+                .unwrap_or(3),
+            scale_in_step_pct: env::var("SCALE_IN_STEP_PCT")
+                .unwrap_or_else(|_| "1.0".into())
+                .parse()
+                .unwrap_or(Decimal::ONE),
+
+            ramp_up_enabled: env::var("RAMP_UP_ENABLED")
+                .unwrap_or_else(|_| "false".into())
+                .parse()
+                .unwrap_or(false),
+            ramp_up_initial_cap: env::var("RAMP_UP_INITIAL_CAP")
+                .unwrap_or_else(|_| "25".into())
+                .parse()
+                .unwrap_or(Decimal::from(25)),
+            ramp_up_launch_trades: env::var("RAMP_UP_LAUNCH_TRADES")
+                .unwrap_or_else(|_| "20".into())
+                .parse()
+                .unwrap_or(20),
+            ramp_up_launch_days: env::var("RAMP_UP_LAUNCH_DAYS")
+                .unwrap_or_else(|_| "3".into())
+                .parse()
+                .unwrap_or(3),
+            ramp_up_step_trades: env::var("RAMP_UP_STEP_TRADES")
+                .unwrap_or_else(|_| "10".into())
+                .parse()
+                .unwrap_or(10),
+            ramp_up_step_multiplier: env::var("RAMP_UP_STEP_MULTIPLIER")
+                .unwrap_or_else(|_| "2".into())
+                .parse()
+                .unwrap_or(Decimal::from(2)),
+            ramp_up_max_tracking_error: env::var("RAMP_UP_MAX_TRACKING_ERROR")
+                .unwrap_or_else(|_| "2".into())
+                .parse()
+                .unwrap_or(Decimal::from(2)),
+            flow_window_minutes: env::var("FLOW_WINDOW_MINUTES")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(0),
+            flow_min_net_notional: env::var("FLOW_MIN_NET_NOTIONAL")
+                .unwrap_or_else(|_| "50000".into())
+                .parse()
+                .unwrap_or(Decimal::from(50_000)),
+            flow_min_whales: env::var("FLOW_MIN_WHALES")
+                .unwrap_or_else(|_| "3".into())
+                .parse()
+                .unwrap_or(3),
+            flow_min_imbalance: env::var("FLOW_MIN_IMBALANCE")
+                .unwrap_or_else(|_| "0.6".into())
+                .parse()
+                .unwrap_or(Decimal::new(6, 1)),
+            flow_base_amount: env::var("FLOW_BASE_AMOUNT")
+                .unwrap_or_else(|_| "50".into())
+                .parse()
+                .unwrap_or(Decimal::from(50)),
+            flow_max_multiplier: env::var("FLOW_MAX_MULTIPLIER")
+                .unwrap_or_else(|_| "3".into())
+                .parse()
+                .unwrap_or(Decimal::from(3)),
+            whale_tiers_enabled: env::var("WHALE_TIERS_ENABLED")
+                .unwrap_or_else(|_| "false".into())
+                .parse()
+                .unwrap_or(false),
+            hedge_loss_pct: env::var("HEDGE_LOSS_PCT")
+                .unwrap_or_else(|_| "0".into())
+                .parse()
+                .unwrap_or(Decimal::ZERO),
+            hedge_ratio_pct: env::var("HEDGE_RATIO_PCT")
+                .unwrap_or_else(|_| "50".into())
+                .parse()
+                .unwrap_or(Decimal::from(50)),
+            reconcile_interval_secs: env::var("RECONCILE_INTERVAL_SECS")
+                .unwrap_or_else(|_| "900".into())
+                .parse()
+                .unwrap_or(900),
+            reconcile_auto_correct: env::var("RECONCILE_AUTO_CORRECT")
+                .unwrap_or_else(|_| "false".into())
+                .parse()
+                .unwrap_or(false),
+            eod_reconcile_enabled: env::var("EOD_RECONCILE_ENABLED")
+                .unwrap_or_else(|_| "true".into())
+                .parse()
+                .unwrap_or(true),
+            eod_reconcile_hour: env::var("EOD_RECONCILE_HOUR")
+                .unwrap_or_else(|_| "23".into())
+                .parse::<u32>()
+                .unwrap_or(23)
+                .min(23),
+            eod_reconcile_balance_tolerance: env::var("EOD_RECONCILE_BALANCE_TOLERANCE")
+                .unwrap_or_else(|_| "1".into())
+                .parse()
+                .unwrap_or(Decimal::ONE),
+            equity_snapshot_interval_secs: env::var("EQUITY_SNAPSHOT_INTERVAL_SECS")
+                .unwrap_or_else(|_| "300".into())
+                .parse()
+                .unwrap_or(300),
         })
     }
 
@@ -288,4 +1049,34 @@ impl AppConfig {
     pub fn has_telegram(&self) -> bool {
         self.telegram_bot_token.is_some() && self.telegram_chat_id.is_some()
     }
+
+    /// Short hash of the effective config, so deployments running different
+    /// settings can be told apart. Credentials and connection strings are left
+    /// out: rotating a key doesn't change it, and it leaks nothing about them.
+    /// RPC URLs keep only their scheme and host, since providers put API keys
+    /// in the path or query.
+    pub fn fingerprint(&self) -> String {
+        let mut redacted = self.clone();
+        redacted.database_url.clear();
+        redacted.redis_url = None;
+        redacted.polygon_rpc_url = url_origin(&self.polygon_rpc_url);
+        redacted.polygon_ws_urls = self.polygon_ws_urls.iter().map(|u| url_origin(u)).collect();
+        redacted.polymarket_api_key = None;
+        redacted.polymarket_api_secret = None;
+        redacted.polymarket_passphrase = None;
+        redacted.private_key = None;
+        redacted.telegram_bot_token = None;
+        redacted.telegram_chat_id = None;
+
+        let digest = Sha256::digest(format!("{redacted:?}").as_bytes());
+        digest[..6].iter().map(|b| format!("{b:02x}")).collect()
+    }
+}
+
+/// `scheme://host[:port]` of `raw`, dropping credentials, path and query.
+/// Empty when it doesn't parse.
+fn url_origin(raw: &str) -> String {
+    reqwest::Url::parse(raw)
+        .map(|url| url.origin().ascii_serialization())
+        .unwrap_or_default()
 }
@@ -0,0 +1,139 @@
+//! Encrypted secrets file, decrypted at startup and layered into the environment.
+//!
+//! `SECRETS_FILE` points to an [age](https://age-encryption.org)-encrypted
+//! dotenv-style file (binary or ASCII-armored). The identity comes from
+//! `AGE_IDENTITY` (the `AGE-SECRET-KEY-1...` string, e.g. injected by a KMS /
+//! secret manager) or `AGE_IDENTITY_FILE`.
+//!
+//! Precedence: process environment > secrets file > `.env`.
+
+use std::collections::HashSet;
+use std::env;
+use std::io::Read;
+
+use anyhow::Context;
+
+/// Load `.env` and the encrypted secrets file into the process environment.
+/// Returns the number of variables taken from the secrets file.
+pub fn load_env_layers() -> anyhow::Result<usize> {
+    let process_keys: HashSet<String> = env::vars_os()
+        .filter_map(|(k, _)| k.into_string().ok())
+        .collect();
+
+    dotenvy::dotenv().ok();
+
+    let Ok(path) = env::var("SECRETS_FILE") else {
+        return Ok(0);
+    };
+    if path.trim().is_empty() {
+        return Ok(0);
+    }
+
+    let identity = match env::var("AGE_IDENTITY") {
+        Ok(key) if !key.trim().is_empty() => key,
+        _ => {
+            let identity_path = env::var("AGE_IDENTITY_FILE").map_err(|_| {
+                anyhow::anyhow!("SECRETS_FILE is set but neither AGE_IDENTITY nor AGE_IDENTITY_FILE is")
+            })?;
+            std::fs::read_to_string(&identity_path)
+                .with_context(|| format!("failed to read AGE_IDENTITY_FILE {identity_path}"))?
+        }
+    };
+
+    let ciphertext =
+        std::fs::read(&path).with_context(|| format!("failed to read SECRETS_FILE {path}"))?;
+    let plaintext = decrypt_secrets(&ciphertext, &identity)
+        .with_context(|| format!("failed to decrypt SECRETS_FILE {path}"))?;
+
+    let mut loaded = 0;
+    for (key, value) in parse_env_lines(&plaintext) {
+        if !process_keys.contains(&key) {
+            env::set_var(&key, value);
+            loaded += 1;
+        }
+    }
+
+    Ok(loaded)
+}
+
+/// Decrypt an age file (binary or armored) with the given identity file contents.
+pub fn decrypt_secrets(ciphertext: &[u8], identity: &str) -> anyhow::Result<String> {
+    let identities = age::IdentityFile::from_buffer(identity.as_bytes())?.into_identities()?;
+    if identities.is_empty() {
+        anyhow::bail!("no age identities found");
+    }
+
+    let decryptor = age::Decryptor::new(age::armor::ArmoredReader::new(ciphertext))?;
+    let mut reader = decryptor.decrypt(identities.iter().map(|i| i.as_ref() as &dyn age::Identity))?;
+
+    let mut plaintext = String::new();
+    reader.read_to_string(&mut plaintext)?;
+    Ok(plaintext)
+}
+
+/// Parse `KEY=VALUE` lines; blank lines, `#` comments and an `export ` prefix
+/// are allowed, and values may be wrapped in single or double quotes.
+pub fn parse_env_lines(contents: &str) -> Vec<(String, String)> {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .filter_map(|line| {
+            let line = line.strip_prefix("export ").unwrap_or(line);
+            let (key, value) = line.split_once('=')?;
+            let key = key.trim();
+            if key.is_empty() {
+                return None;
+            }
+            let value = value.trim();
+            let value = value
+                .strip_prefix('"')
+                .and_then(|v| v.strip_suffix('"'))
+                .or_else(|| value.strip_prefix('\'').and_then(|v| v.strip_suffix('\'')))
+                .unwrap_or(value);
+            Some((key.to_string(), value.to_string()))
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use age::secrecy::ExposeSecret;
+
+    #[test]
+    fn test_parse_env_lines() {
+        let parsed = parse_env_lines(
+            "# wallet\nPRIVATE_KEY=0xabc\n\nexport POLYMARKET_API_SECRET=\"s3cr=t\"\nTELEGRAM_BOT_TOKEN='123:xyz'\nnot a pair\n",
+        );
+        assert_eq!(
+            parsed,
+            vec![
+                ("PRIVATE_KEY".to_string(), "0xabc".to_string()),
+                ("POLYMARKET_API_SECRET".to_string(), "s3cr=t".to_string()),
+                ("TELEGRAM_BOT_TOKEN".to_string(), "123:xyz".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_decrypt_secrets_binary_and_armored() {
+        let identity = age::x25519::Identity::generate();
+        let recipient = identity.to_public();
+        let key = identity.to_string();
+        let plaintext = "PRIVATE_KEY=0xabc\n";
+
+        let binary = age::encrypt(&recipient, plaintext.as_bytes()).unwrap();
+        assert_eq!(decrypt_secrets(&binary, key.expose_secret()).unwrap(), plaintext);
+
+        let armored = age::encrypt_and_armor(&recipient, plaintext.as_bytes()).unwrap();
+        assert_eq!(decrypt_secrets(armored.as_bytes(), key.expose_secret()).unwrap(), plaintext);
+
+        let other = age::x25519::Identity::generate().to_string();
+        assert!(decrypt_secrets(&binary, other.expose_secret()).is_err());
+    }
+}
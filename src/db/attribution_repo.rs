@@ -0,0 +1,146 @@
+use chrono::NaiveDate;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{BasketPerformance, BasketPnlPoint, PnlAttribution};
+
+/// Attribute a closed position's realized PnL to the whales and baskets whose
+/// signals filled it, by their share of the filled BUY size during the
+/// position's lifetime. Fills without a whale or basket (manual orders) keep
+/// their share unattributed. Does nothing if the position was already attributed.
+pub async fn attribute_position_pnl(pool: &PgPool, position_id: Uuid) -> anyhow::Result<Vec<PnlAttribution>> {
+    let rows = sqlx::query_as::<_, PnlAttribution>(
+        r#"
+        WITH p AS (
+            SELECT id, token_id, realized_pnl, opened_at, closed_at
+            FROM positions
+            WHERE id = $1 AND status = 'closed' AND realized_pnl IS NOT NULL
+              AND NOT EXISTS (SELECT 1 FROM pnl_attributions WHERE position_id = $1)
+        ),
+        fills AS (
+            SELECT t.whale_id, cs.basket_id, o.size
+            FROM copy_orders o
+            JOIN p ON p.token_id = o.token_id
+            LEFT JOIN whale_trades t ON t.id = o.whale_trade_id
+            LEFT JOIN consensus_signals cs ON cs.id = o.source_signal_id
+            WHERE o.status = 'filled' AND o.side = 'BUY'
+              -- The opening fill is recorded just before the position row
+              AND o.filled_at >= p.opened_at - INTERVAL '5 minutes'
+              AND o.filled_at <= COALESCE(p.closed_at, NOW())
+        ),
+        total AS (SELECT SUM(size) AS size FROM fills)
+        INSERT INTO pnl_attributions (position_id, whale_id, basket_id, share, realized_pnl)
+        SELECT p.id, f.whale_id, f.basket_id,
+               SUM(f.size) / total.size,
+               p.realized_pnl * SUM(f.size) / total.size
+        FROM fills f, total, p
+        WHERE total.size > 0 AND (f.whale_id IS NOT NULL OR f.basket_id IS NOT NULL)
+        GROUP BY p.id, p.realized_pnl, f.whale_id, f.basket_id, total.size
+        RETURNING *
+        "#,
+    )
+    .bind(position_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Positions closed in the last `days` with no attribution yet, oldest first.
+/// Positions closed by reconciliation carry no real exit PnL and are skipped;
+/// manual-only positions stay unattributed and keep coming back until they
+/// age out of the window.
+pub async fn get_unattributed_closed_positions(pool: &PgPool, days: i32) -> anyhow::Result<Vec<Uuid>> {
+    let rows: Vec<(Uuid,)> = sqlx::query_as(
+        r#"
+        SELECT p.id
+        FROM positions p
+        WHERE p.status = 'closed'
+          AND p.realized_pnl IS NOT NULL
+          AND p.closed_at >= NOW() - make_interval(days => $1)
+          AND COALESCE(p.exit_reason, '') <> 'reconciled'
+          AND NOT EXISTS (SELECT 1 FROM pnl_attributions a WHERE a.position_id = p.id)
+        ORDER BY p.closed_at
+        "#,
+    )
+    .bind(days)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|r| r.0).collect())
+}
+
+/// Signals, settled positions, winners, attributed PnL and its daily curve,
+/// and open exposure for a basket.
+pub async fn get_basket_performance(pool: &PgPool, basket_id: Uuid) -> anyhow::Result<BasketPerformance> {
+    let (settled_positions, winning_positions, realized_pnl): (i64, i64, Option<Decimal>) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COUNT(*) FILTER (WHERE pnl > 0), SUM(pnl)
+        FROM (
+            SELECT position_id, SUM(realized_pnl) AS pnl
+            FROM pnl_attributions
+            WHERE basket_id = $1
+            GROUP BY position_id
+        ) per_position
+        "#,
+    )
+    .bind(basket_id)
+    .fetch_one(pool)
+    .await?;
+
+    let (consensus_signals, open_positions, unrealized_pnl): (i64, i64, Option<Decimal>) = sqlx::query_as(
+        r#"
+        SELECT
+            (SELECT COUNT(*) FROM consensus_signals WHERE basket_id = $1 AND NOT blocked),
+            COUNT(p.id),
+            SUM(p.unrealized_pnl)
+        FROM positions p
+        JOIN consensus_signals cs ON cs.id = p.source_signal_id
+        WHERE cs.basket_id = $1 AND p.status = 'open'
+        "#,
+    )
+    .bind(basket_id)
+    .fetch_one(pool)
+    .await?;
+
+    let daily: Vec<(NaiveDate, Decimal)> = sqlx::query_as(
+        r#"
+        SELECT p.closed_at::date AS day, SUM(a.realized_pnl)
+        FROM pnl_attributions a
+        JOIN positions p ON p.id = a.position_id
+        WHERE a.basket_id = $1 AND p.closed_at IS NOT NULL
+        GROUP BY day
+        ORDER BY day
+        "#,
+    )
+    .bind(basket_id)
+    .fetch_all(pool)
+    .await?;
+
+    let mut cumulative = Decimal::ZERO;
+    let pnl_curve = daily
+        .into_iter()
+        .map(|(date, daily_pnl)| {
+            cumulative += daily_pnl;
+            BasketPnlPoint {
+                date,
+                daily_pnl: daily_pnl.round_dp(6),
+                cumulative_pnl: cumulative.round_dp(6),
+            }
+        })
+        .collect();
+
+    Ok(BasketPerformance {
+        basket_id,
+        consensus_signals,
+        settled_positions,
+        winning_positions,
+        win_rate: (settled_positions > 0)
+            .then(|| Decimal::from(winning_positions) / Decimal::from(settled_positions)),
+        realized_pnl: realized_pnl.unwrap_or(Decimal::ZERO).round_dp(6),
+        open_positions,
+        unrealized_pnl: unrealized_pnl.unwrap_or(Decimal::ZERO).round_dp(6),
+        pnl_curve,
+    })
+}
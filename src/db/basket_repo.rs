@@ -3,7 +3,7 @@ use rust_decimal::Decimal;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::{ConsensusSignal, Whale, WhaleBasket};
+use crate::models::{BasketMembershipEvent, ConsensusSignal, VoteWeighting, Whale, WhaleBasket};
 
 /// Vote cast by a whale in the consensus window.
 #[derive(Debug, Clone, sqlx::FromRow)]
@@ -11,6 +11,28 @@ pub struct BasketTradeVote {
     pub whale_id: Uuid,
     pub side: String,
     pub traded_at: DateTime<Utc>,
+    /// Notional of the voting trade.
+    pub notional: Decimal,
+    pub win_rate: Option<Decimal>,
+    pub expected_value: Option<Decimal>,
+}
+
+/// A basket member's trade with its market's outcome, for replaying consensus.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct BasketHistoryTrade {
+    pub whale_id: Uuid,
+    /// condition_id, falling back to market_id — the key consensus is checked on.
+    pub market_key: String,
+    pub token_id: String,
+    pub side: String,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub notional: Decimal,
+    pub traded_at: DateTime<Utc>,
+    pub win_rate: Option<Decimal>,
+    pub expected_value: Option<Decimal>,
+    /// None while the market has no outcome row.
+    pub outcome: Option<String>,
 }
 
 // ---------------------------------------------------------------------------
@@ -66,6 +88,52 @@ pub async fn get_basket_by_id(pool: &PgPool, id: Uuid) -> anyhow::Result<Option<
     Ok(basket)
 }
 
+/// Save a basket's editable settings. None if the basket doesn't exist.
+pub async fn update_basket(pool: &PgPool, basket: &WhaleBasket) -> anyhow::Result<Option<WhaleBasket>> {
+    let updated = sqlx::query_as::<_, WhaleBasket>(
+        r#"
+        UPDATE whale_baskets
+        SET category = $2,
+            consensus_threshold = $3,
+            time_window_hours = $4,
+            min_wallets = $5,
+            max_wallets = $6,
+            vote_weighting = $7,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(basket.id)
+    .bind(&basket.category)
+    .bind(basket.consensus_threshold)
+    .bind(basket.time_window_hours)
+    .bind(basket.min_wallets)
+    .bind(basket.max_wallets)
+    .bind(&basket.vote_weighting)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(updated)
+}
+
+/// Change how a basket weighs consensus votes. None if the basket doesn't exist.
+pub async fn set_vote_weighting(
+    pool: &PgPool,
+    id: Uuid,
+    weighting: VoteWeighting,
+) -> anyhow::Result<Option<WhaleBasket>> {
+    let basket = sqlx::query_as::<_, WhaleBasket>(
+        "UPDATE whale_baskets SET vote_weighting = $2, updated_at = NOW() WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(weighting.as_str())
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(basket)
+}
+
 pub async fn deactivate_basket(pool: &PgPool, id: Uuid) -> anyhow::Result<()> {
     sqlx::query("UPDATE whale_baskets SET is_active = false, updated_at = NOW() WHERE id = $1")
         .bind(id)
@@ -75,6 +143,16 @@ pub async fn deactivate_basket(pool: &PgPool, id: Uuid) -> anyhow::Result<()> {
     Ok(())
 }
 
+/// Categories that have a basket, including deactivated ones: a disabled
+/// basket marks its category as declined by the operator.
+pub async fn get_basket_categories(pool: &PgPool) -> anyhow::Result<Vec<String>> {
+    let rows: Vec<(String,)> = sqlx::query_as("SELECT DISTINCT category FROM whale_baskets")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.into_iter().map(|(c,)| c).collect())
+}
+
 pub async fn count_active_baskets(pool: &PgPool) -> anyhow::Result<i64> {
     let row: (i64,) = sqlx::query_as(
         "SELECT COUNT(*) FROM whale_baskets WHERE is_active = true",
@@ -89,12 +167,13 @@ pub async fn count_active_baskets(pool: &PgPool) -> anyhow::Result<i64> {
 // Basket membership
 // ---------------------------------------------------------------------------
 
+/// Returns false when the whale was already a member.
 pub async fn add_whale_to_basket(
     pool: &PgPool,
     basket_id: Uuid,
     whale_id: Uuid,
-) -> anyhow::Result<()> {
-    sqlx::query(
+) -> anyhow::Result<bool> {
+    let result = sqlx::query(
         "INSERT INTO basket_wallets (basket_id, whale_id) VALUES ($1, $2) ON CONFLICT DO NOTHING",
     )
     .bind(basket_id)
@@ -102,23 +181,68 @@ pub async fn add_whale_to_basket(
     .execute(pool)
     .await?;
 
-    Ok(())
+    Ok(result.rows_affected() > 0)
 }
 
+/// Returns false when the whale wasn't a member.
 pub async fn remove_whale_from_basket(
     pool: &PgPool,
     basket_id: Uuid,
     whale_id: Uuid,
-) -> anyhow::Result<()> {
-    sqlx::query("DELETE FROM basket_wallets WHERE basket_id = $1 AND whale_id = $2")
+) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM basket_wallets WHERE basket_id = $1 AND whale_id = $2")
         .bind(basket_id)
         .bind(whale_id)
         .execute(pool)
         .await?;
 
+    Ok(result.rows_affected() > 0)
+}
+
+/// Log a membership change; `action` is "added" or "removed" and `actor` is
+/// "operator", "auto_assign" or "rebalance".
+pub async fn record_membership_event(
+    pool: &PgPool,
+    basket_id: Uuid,
+    whale_id: Uuid,
+    action: &str,
+    actor: &str,
+    reason: &str,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO basket_membership_events (basket_id, whale_id, action, actor, reason)
+        VALUES ($1, $2, $3, $4, $5)
+        "#,
+    )
+    .bind(basket_id)
+    .bind(whale_id)
+    .bind(action)
+    .bind(actor)
+    .bind(reason)
+    .execute(pool)
+    .await?;
+
     Ok(())
 }
 
+/// Most recent membership events of a basket, newest first.
+pub async fn get_membership_events(
+    pool: &PgPool,
+    basket_id: Uuid,
+    limit: i64,
+) -> anyhow::Result<Vec<BasketMembershipEvent>> {
+    let events = sqlx::query_as::<_, BasketMembershipEvent>(
+        "SELECT * FROM basket_membership_events WHERE basket_id = $1 ORDER BY created_at DESC LIMIT $2",
+    )
+    .bind(basket_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
+
 /// Get all whales in a basket (JOIN with whales table).
 pub async fn get_basket_whales(pool: &PgPool, basket_id: Uuid) -> anyhow::Result<Vec<Whale>> {
     let whales = sqlx::query_as::<_, Whale>(
@@ -170,41 +294,103 @@ pub async fn get_baskets_for_whale(
 // Consensus queries
 // ---------------------------------------------------------------------------
 
-/// Core consensus query: for each whale in the basket, get their most recent
-/// trade direction within the time window for a specific market.
-/// Only considers whales that are is_active = true.
+/// A whale's net position in the window must exceed this share of the shares
+/// it traded there to count as a vote; below it the whale has exited.
+pub const NET_STANCE_MIN_PCT: Decimal = Decimal::from_parts(1, 0, 0, false, 1); // 0.1
+
+/// Core consensus query: for each whale in the basket, its net stance within
+/// the time window in one outcome token of a market — buys minus sells in
+/// shares, so a whale that bought and has since sold out no longer votes, and
+/// shares of the other outcome never net against it. Whales whose net
+/// is within `NET_STANCE_MIN_PCT` of what they traded are flat and skipped.
+/// `notional` is the net notional and `traded_at` the whale's latest trade.
+/// Only considers whales that are is_active = true. `market_key` is matched
+/// against condition_id, falling back to market_id.
 pub async fn get_basket_trades_in_window(
     pool: &PgPool,
     basket_id: Uuid,
-    market_id: &str,
+    market_key: &str,
+    token_id: &str,
     since: DateTime<Utc>,
 ) -> anyhow::Result<Vec<BasketTradeVote>> {
     let votes = sqlx::query_as::<_, BasketTradeVote>(
         r#"
-        SELECT DISTINCT ON (wt.whale_id) wt.whale_id, wt.side, wt.traded_at
+        SELECT wt.whale_id,
+               CASE WHEN SUM(CASE WHEN UPPER(wt.side) = 'BUY' THEN wt.size ELSE -wt.size END) > 0
+                    THEN 'BUY' ELSE 'SELL' END AS side,
+               MAX(wt.traded_at) AS traded_at,
+               ABS(SUM(CASE WHEN UPPER(wt.side) = 'BUY' THEN wt.notional ELSE -wt.notional END)) AS notional,
+               w.win_rate, w.expected_value
         FROM whale_trades wt
         INNER JOIN basket_wallets bw ON bw.whale_id = wt.whale_id
         INNER JOIN whales w ON w.id = wt.whale_id
         WHERE bw.basket_id = $1
-          AND wt.market_id = $2
-          AND wt.traded_at >= $3
+          AND COALESCE(wt.condition_id, wt.market_id) = $2
+          AND wt.token_id = $3
+          AND wt.traded_at >= $4
           AND w.is_active = true
-        ORDER BY wt.whale_id, wt.traded_at DESC
+        GROUP BY wt.whale_id, wt.token_id, w.win_rate, w.expected_value
+        HAVING ABS(SUM(CASE WHEN UPPER(wt.side) = 'BUY' THEN wt.size ELSE -wt.size END))
+               > $5 * SUM(wt.size)
         "#,
     )
     .bind(basket_id)
-    .bind(market_id)
+    .bind(market_key)
+    .bind(token_id)
     .bind(since)
+    .bind(NET_STANCE_MIN_PCT)
     .fetch_all(pool)
     .await?;
 
     Ok(votes)
 }
 
+/// Trades of the basket's current members in `[since, until)`, grouped by
+/// market and in time order, each with its market's outcome.
+pub async fn get_basket_trade_history(
+    pool: &PgPool,
+    basket_id: Uuid,
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> anyhow::Result<Vec<BasketHistoryTrade>> {
+    let trades = sqlx::query_as::<_, BasketHistoryTrade>(
+        r#"
+        SELECT wt.whale_id, COALESCE(wt.condition_id, wt.market_id) AS market_key,
+               wt.token_id, wt.side, wt.size, wt.price, wt.notional, wt.traded_at,
+               w.win_rate, w.expected_value, o.outcome
+        FROM whale_trades wt
+        INNER JOIN basket_wallets bw ON bw.whale_id = wt.whale_id
+        INNER JOIN whales w ON w.id = wt.whale_id
+        LEFT JOIN market_outcomes o ON o.market_id = COALESCE(wt.condition_id, wt.market_id)
+        WHERE bw.basket_id = $1
+          AND wt.traded_at >= $2
+          AND wt.traded_at < $3
+        ORDER BY market_key, wt.traded_at
+        "#,
+    )
+    .bind(basket_id)
+    .bind(since)
+    .bind(until)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(trades)
+}
+
 // ---------------------------------------------------------------------------
 // Consensus signal recording
 // ---------------------------------------------------------------------------
 
+/// Record a consensus signal unless the basket is still cooling down on the
+/// market from an earlier traded (not blocked) signal in either direction.
+/// The new signal starts a
+/// `cooldown_hours` cooldown (persisted as its `cooldown_until`). Returns `None`
+/// when suppressed, so callers only notify and emit execution signals once.
+///
+/// Concurrent callers for the same basket and market are serialized with a
+/// transaction-scoped advisory lock, so two member trades landing together
+/// cannot both record.
+#[allow(clippy::too_many_arguments)]
 pub async fn record_consensus_signal(
     pool: &PgPool,
     basket_id: Uuid,
@@ -213,11 +399,25 @@ pub async fn record_consensus_signal(
     consensus_pct: Decimal,
     participating_whales: i32,
     total_whales: i32,
-) -> anyhow::Result<ConsensusSignal> {
+    cooldown_hours: i32,
+) -> anyhow::Result<Option<ConsensusSignal>> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext($1 || ':' || $2))")
+        .bind(basket_id.to_string())
+        .bind(market_id)
+        .execute(&mut *tx)
+        .await?;
+
     let signal = sqlx::query_as::<_, ConsensusSignal>(
         r#"
-        INSERT INTO consensus_signals (basket_id, market_id, direction, consensus_pct, participating_whales, total_whales)
-        VALUES ($1, $2, $3, $4, $5, $6)
+        INSERT INTO consensus_signals
+            (basket_id, market_id, direction, consensus_pct, participating_whales, total_whales, cooldown_until)
+        SELECT $1, $2, $3, $4, $5, $6, NOW() + make_interval(hours => $7)
+        WHERE NOT EXISTS (
+            SELECT 1 FROM consensus_signals
+            WHERE basket_id = $1 AND market_id = $2 AND cooldown_until > NOW() AND NOT blocked
+        )
         RETURNING *
         "#,
     )
@@ -227,12 +427,126 @@ pub async fn record_consensus_signal(
     .bind(consensus_pct)
     .bind(participating_whales)
     .bind(total_whales)
-    .fetch_one(pool)
+    .bind(cooldown_hours)
+    .fetch_optional(&mut *tx)
     .await?;
 
+    tx.commit().await?;
+
     Ok(signal)
 }
 
+/// Record a consensus blocked by an opposite consensus in another basket, so
+/// that side sees this one as opposing and stays blocked too. Suppressed while
+/// the basket already has a signal cooling down on the market.
+#[allow(clippy::too_many_arguments)]
+pub async fn record_blocked_consensus(
+    pool: &PgPool,
+    basket_id: Uuid,
+    market_id: &str,
+    direction: &str,
+    consensus_pct: Decimal,
+    participating_whales: i32,
+    total_whales: i32,
+    cooldown_hours: i32,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO consensus_signals
+            (basket_id, market_id, direction, consensus_pct, participating_whales, total_whales, cooldown_until, blocked)
+        SELECT $1, $2, $3, $4, $5, $6, NOW() + make_interval(hours => $7), TRUE
+        WHERE NOT EXISTS (
+            SELECT 1 FROM consensus_signals
+            WHERE basket_id = $1 AND market_id = $2 AND cooldown_until > NOW()
+        )
+        "#,
+    )
+    .bind(basket_id)
+    .bind(market_id)
+    .bind(direction)
+    .bind(consensus_pct)
+    .bind(participating_whales)
+    .bind(total_whales)
+    .bind(cooldown_hours)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Record a consensus-building alert unless the basket already alerted on
+/// this market and direction within `cooldown_hours`, or is cooling down on
+/// the market after reaching consensus. Returns false when suppressed.
+pub async fn record_building_alert(
+    pool: &PgPool,
+    basket_id: Uuid,
+    market_id: &str,
+    direction: &str,
+    consensus_pct: Decimal,
+    cooldown_hours: i32,
+) -> anyhow::Result<bool> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('building:' || $1 || ':' || $2 || ':' || $3))")
+        .bind(basket_id.to_string())
+        .bind(market_id)
+        .bind(direction)
+        .execute(&mut *tx)
+        .await?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO consensus_building_alerts (basket_id, market_id, direction, consensus_pct)
+        SELECT $1, $2, $3, $4
+        WHERE NOT EXISTS (
+            SELECT 1 FROM consensus_building_alerts
+            WHERE basket_id = $1 AND market_id = $2 AND direction = $3
+              AND alerted_at > NOW() - make_interval(hours => $5)
+        )
+        AND NOT EXISTS (
+            SELECT 1 FROM consensus_signals
+            WHERE basket_id = $1 AND market_id = $2 AND cooldown_until > NOW()
+        )
+        "#,
+    )
+    .bind(basket_id)
+    .bind(market_id)
+    .bind(direction)
+    .bind(consensus_pct)
+    .bind(cooldown_hours)
+    .execute(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Other baskets' consensus on the market in the opposite direction that is
+/// still cooling down.
+pub async fn get_opposing_consensus(
+    pool: &PgPool,
+    basket_id: Uuid,
+    market_id: &str,
+    direction: &str,
+) -> anyhow::Result<Vec<ConsensusSignal>> {
+    let signals = sqlx::query_as::<_, ConsensusSignal>(
+        r#"
+        SELECT * FROM consensus_signals
+        WHERE market_id = $2 AND basket_id <> $1 AND direction <> $3
+          AND cooldown_until > NOW()
+        ORDER BY consensus_pct DESC
+        "#,
+    )
+    .bind(basket_id)
+    .bind(market_id)
+    .bind(direction)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(signals)
+}
+
 pub async fn get_recent_consensus_signals(
     pool: &PgPool,
     limit: i64,
@@ -268,7 +582,7 @@ pub async fn count_recent_consensus_signals(
     since: DateTime<Utc>,
 ) -> anyhow::Result<i64> {
     let row: (i64,) = sqlx::query_as(
-        "SELECT COUNT(*) FROM consensus_signals WHERE triggered_at >= $1",
+        "SELECT COUNT(*) FROM consensus_signals WHERE triggered_at >= $1 AND NOT blocked",
     )
     .bind(since)
     .fetch_one(pool)
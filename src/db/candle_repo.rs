@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::Candle;
+
+/// Upsert candles. The recorder re-flushes the in-progress minute with its
+/// running aggregate, so the wider range / larger totals win and `open` stays.
+pub async fn upsert_candles(pool: &PgPool, candles: &[Candle]) -> anyhow::Result<()> {
+    for c in candles {
+        sqlx::query(
+            r#"
+            INSERT INTO market_candles (token_id, bucket, open, high, low, close, volume, trade_count)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (token_id, bucket) DO UPDATE SET
+                high = GREATEST(market_candles.high, EXCLUDED.high),
+                low = LEAST(market_candles.low, EXCLUDED.low),
+                close = EXCLUDED.close,
+                volume = GREATEST(market_candles.volume, EXCLUDED.volume),
+                trade_count = GREATEST(market_candles.trade_count, EXCLUDED.trade_count)
+            "#,
+        )
+        .bind(&c.token_id)
+        .bind(c.bucket)
+        .bind(c.open)
+        .bind(c.high)
+        .bind(c.low)
+        .bind(c.close)
+        .bind(c.volume)
+        .bind(c.trade_count)
+        .execute(pool)
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Get candles for a token since `since`, oldest first.
+pub async fn get_candles(
+    pool: &PgPool,
+    token_id: &str,
+    since: DateTime<Utc>,
+) -> anyhow::Result<Vec<Candle>> {
+    let candles = sqlx::query_as::<_, Candle>(
+        r#"
+        SELECT * FROM market_candles
+        WHERE token_id = $1 AND bucket >= $2
+        ORDER BY bucket ASC
+        "#,
+    )
+    .bind(token_id)
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(candles)
+}
+
+/// Delete candles older than `cutoff`. Returns the number of rows removed.
+pub async fn delete_candles_before(pool: &PgPool, cutoff: DateTime<Utc>) -> anyhow::Result<u64> {
+    let result = sqlx::query("DELETE FROM market_candles WHERE bucket < $1")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
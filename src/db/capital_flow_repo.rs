@@ -0,0 +1,63 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{CapitalFlowDirection, WhaleCapitalFlow};
+
+/// A large whale USDC transfer about to be recorded.
+#[derive(Debug, Clone)]
+pub struct NewCapitalFlow {
+    pub whale_id: Uuid,
+    pub direction: CapitalFlowDirection,
+    pub amount: Decimal,
+    pub counterparty: String,
+    pub balance_after: Option<Decimal>,
+    pub capital_share_pct: Option<Decimal>,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub block_number: i64,
+    pub occurred_at: DateTime<Utc>,
+}
+
+/// Record a capital flow. Returns None when this transfer was already
+/// recorded for the whale.
+pub async fn insert_flow(pool: &PgPool, flow: &NewCapitalFlow) -> anyhow::Result<Option<WhaleCapitalFlow>> {
+    let row = sqlx::query_as::<_, WhaleCapitalFlow>(
+        r#"
+        INSERT INTO whale_capital_flows
+            (whale_id, direction, amount, counterparty, balance_after, capital_share_pct,
+             tx_hash, log_index, block_number, occurred_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
+        ON CONFLICT (tx_hash, log_index, whale_id) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(flow.whale_id)
+    .bind(flow.direction.as_str())
+    .bind(flow.amount)
+    .bind(&flow.counterparty)
+    .bind(flow.balance_after)
+    .bind(flow.capital_share_pct)
+    .bind(&flow.tx_hash)
+    .bind(flow.log_index)
+    .bind(flow.block_number)
+    .bind(flow.occurred_at)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row)
+}
+
+/// A whale's recorded deposits and withdrawals, newest first.
+pub async fn get_whale_flows(pool: &PgPool, whale_id: Uuid, limit: i64) -> anyhow::Result<Vec<WhaleCapitalFlow>> {
+    let flows = sqlx::query_as::<_, WhaleCapitalFlow>(
+        "SELECT * FROM whale_capital_flows WHERE whale_id = $1 ORDER BY occurred_at DESC LIMIT $2",
+    )
+    .bind(whale_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(flows)
+}
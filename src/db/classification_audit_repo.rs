@@ -0,0 +1,83 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::intelligence::ClassificationExplanation;
+use crate::models::ClassificationAudit;
+
+fn to_decimal(v: f64) -> Decimal {
+    Decimal::try_from(v).unwrap_or_default().round_dp(4)
+}
+
+/// Store why a whale was classified. Refreshes the whale's latest audit row
+/// while its classification is unchanged and adds a new row when it changes,
+/// so the table holds one row per classification transition.
+pub async fn record_classification(
+    pool: &PgPool,
+    whale_id: Uuid,
+    explanation: &ClassificationExplanation,
+) -> anyhow::Result<()> {
+    let f = &explanation.features;
+    sqlx::query(
+        r#"
+        WITH latest AS (
+            SELECT id, classification FROM classification_audit
+            WHERE whale_id = $1
+            ORDER BY created_at DESC
+            LIMIT 1
+        ),
+        refreshed AS (
+            UPDATE classification_audit a
+            SET classifier = $3,
+                trade_count = $4,
+                trades_per_month = $5,
+                dual_side_ratio = $6,
+                avg_hold_hours = $7,
+                notional_cv = $8,
+                confidence = $9,
+                rules = $10,
+                updated_at = NOW()
+            FROM latest
+            WHERE a.id = latest.id AND latest.classification = $2
+            RETURNING a.id
+        )
+        INSERT INTO classification_audit (
+            whale_id, classification, classifier, trade_count, trades_per_month,
+            dual_side_ratio, avg_hold_hours, notional_cv, confidence, rules
+        )
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+        WHERE NOT EXISTS (SELECT 1 FROM refreshed)
+        "#,
+    )
+    .bind(whale_id)
+    .bind(explanation.classification.as_str())
+    .bind(explanation.classifier)
+    .bind(explanation.trade_count as i32)
+    .bind(to_decimal(f.trades_per_month))
+    .bind(to_decimal(f.dual_side_ratio))
+    .bind(to_decimal(f.avg_hold_hours))
+    .bind(to_decimal(f.notional_cv))
+    .bind(explanation.confidence.map(to_decimal))
+    .bind(&explanation.rules)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// A whale's classification history, newest first.
+pub async fn get_classification_audit(
+    pool: &PgPool,
+    whale_id: Uuid,
+    limit: i64,
+) -> anyhow::Result<Vec<ClassificationAudit>> {
+    let rows = sqlx::query_as::<_, ClassificationAudit>(
+        "SELECT * FROM classification_audit WHERE whale_id = $1 ORDER BY created_at DESC LIMIT $2",
+    )
+    .bind(whale_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
@@ -0,0 +1,76 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::ComplianceRule;
+
+/// All compliance rules, newest first.
+pub async fn list_rules(pool: &PgPool) -> anyhow::Result<Vec<ComplianceRule>> {
+    let rules = sqlx::query_as::<_, ComplianceRule>(
+        "SELECT * FROM compliance_rules ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rules)
+}
+
+/// Enabled rules, as loaded by the copy engine before each order.
+pub async fn get_enabled_rules(pool: &PgPool) -> anyhow::Result<Vec<ComplianceRule>> {
+    let rules = sqlx::query_as::<_, ComplianceRule>(
+        "SELECT * FROM compliance_rules WHERE enabled = TRUE ORDER BY created_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rules)
+}
+
+/// Add a new rule.
+pub async fn insert_rule(
+    pool: &PgPool,
+    rule_type: &str,
+    value: &str,
+    note: Option<&str>,
+) -> anyhow::Result<ComplianceRule> {
+    let rule = sqlx::query_as::<_, ComplianceRule>(
+        r#"
+        INSERT INTO compliance_rules (rule_type, value, note)
+        VALUES ($1, $2, $3)
+        RETURNING *
+        "#,
+    )
+    .bind(rule_type)
+    .bind(value)
+    .bind(note)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(rule)
+}
+
+/// Enable or disable a rule. Returns None if it does not exist.
+pub async fn set_rule_enabled(
+    pool: &PgPool,
+    id: Uuid,
+    enabled: bool,
+) -> anyhow::Result<Option<ComplianceRule>> {
+    let rule = sqlx::query_as::<_, ComplianceRule>(
+        "UPDATE compliance_rules SET enabled = $2 WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(enabled)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(rule)
+}
+
+/// Delete a rule. Returns false if it did not exist.
+pub async fn delete_rule(pool: &PgPool, id: Uuid) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM compliance_rules WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
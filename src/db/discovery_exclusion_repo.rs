@@ -0,0 +1,76 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::DiscoveryExclusion;
+
+/// All discovery exclusions, newest first.
+pub async fn list_exclusions(pool: &PgPool) -> anyhow::Result<Vec<DiscoveryExclusion>> {
+    let exclusions = sqlx::query_as::<_, DiscoveryExclusion>(
+        "SELECT * FROM discovery_exclusions ORDER BY created_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(exclusions)
+}
+
+/// Enabled exclusions, as loaded at the start of each discovery scan.
+pub async fn get_enabled_exclusions(pool: &PgPool) -> anyhow::Result<Vec<DiscoveryExclusion>> {
+    let exclusions = sqlx::query_as::<_, DiscoveryExclusion>(
+        "SELECT * FROM discovery_exclusions WHERE enabled = TRUE ORDER BY created_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(exclusions)
+}
+
+/// Add a new exclusion.
+pub async fn insert_exclusion(
+    pool: &PgPool,
+    rule_type: &str,
+    value: &str,
+    note: Option<&str>,
+) -> anyhow::Result<DiscoveryExclusion> {
+    let exclusion = sqlx::query_as::<_, DiscoveryExclusion>(
+        r#"
+        INSERT INTO discovery_exclusions (rule_type, value, note)
+        VALUES ($1, $2, $3)
+        RETURNING *
+        "#,
+    )
+    .bind(rule_type)
+    .bind(value)
+    .bind(note)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exclusion)
+}
+
+/// Enable or disable an exclusion. Returns None if it does not exist.
+pub async fn set_exclusion_enabled(
+    pool: &PgPool,
+    id: Uuid,
+    enabled: bool,
+) -> anyhow::Result<Option<DiscoveryExclusion>> {
+    let exclusion = sqlx::query_as::<_, DiscoveryExclusion>(
+        "UPDATE discovery_exclusions SET enabled = $2 WHERE id = $1 RETURNING *",
+    )
+    .bind(id)
+    .bind(enabled)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(exclusion)
+}
+
+/// Delete an exclusion. Returns false if it did not exist.
+pub async fn delete_exclusion(pool: &PgPool, id: Uuid) -> anyhow::Result<bool> {
+    let result = sqlx::query("DELETE FROM discovery_exclusions WHERE id = $1")
+        .bind(id)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected() > 0)
+}
@@ -0,0 +1,95 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::WhaleTradeEvent;
+
+/// Append an event to the durable pipeline queue. Returns its queue offset.
+pub async fn enqueue_event(pool: &PgPool, event: &WhaleTradeEvent) -> anyhow::Result<i64> {
+    let (id,): (i64,) = sqlx::query_as("INSERT INTO pipeline_events (payload) VALUES ($1) RETURNING id")
+        .bind(serde_json::to_value(event)?)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(id)
+}
+
+/// Up to `limit` queued events after offset `after_id`, oldest first, as
+/// (offset, JSON payload).
+pub async fn fetch_events_after(
+    pool: &PgPool,
+    after_id: i64,
+    limit: i64,
+) -> anyhow::Result<Vec<(i64, serde_json::Value)>> {
+    let rows = sqlx::query_as::<_, (i64, serde_json::Value)>(
+        r#"
+        SELECT id, payload
+        FROM pipeline_events
+        WHERE id > $1
+        ORDER BY id
+        LIMIT $2
+        "#,
+    )
+    .bind(after_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Highest offset in the queue (0 if it is empty).
+pub async fn get_max_event_id(pool: &PgPool) -> anyhow::Result<i64> {
+    let (id,): (i64,) = sqlx::query_as("SELECT COALESCE(MAX(id), 0) FROM pipeline_events")
+        .fetch_one(pool)
+        .await?;
+
+    Ok(id)
+}
+
+/// Last offset `consumer` has processed (0 if it has never committed).
+pub async fn get_offset(pool: &PgPool, consumer: &str) -> anyhow::Result<i64> {
+    let row: Option<(i64,)> =
+        sqlx::query_as("SELECT last_event_id FROM pipeline_consumer_offsets WHERE consumer = $1")
+            .bind(consumer)
+            .fetch_optional(pool)
+            .await?;
+
+    Ok(row.map(|(id,)| id).unwrap_or(0))
+}
+
+/// Record that `consumer` has processed everything up to `last_event_id`.
+/// Offsets never move backwards.
+pub async fn commit_offset(pool: &PgPool, consumer: &str, last_event_id: i64) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO pipeline_consumer_offsets (consumer, last_event_id)
+        VALUES ($1, $2)
+        ON CONFLICT (consumer) DO UPDATE
+        SET last_event_id = GREATEST(pipeline_consumer_offsets.last_event_id, EXCLUDED.last_event_id),
+            updated_at = NOW()
+        "#,
+    )
+    .bind(consumer)
+    .bind(last_event_id)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Delete events enqueued before `cutoff` that every consumer has processed.
+/// Returns the number of rows removed.
+pub async fn delete_consumed_before(pool: &PgPool, cutoff: DateTime<Utc>) -> anyhow::Result<u64> {
+    let result = sqlx::query(
+        r#"
+        DELETE FROM pipeline_events
+        WHERE enqueued_at < $1
+          AND id <= (SELECT COALESCE(MIN(last_event_id), 0) FROM pipeline_consumer_offsets)
+        "#,
+    )
+    .bind(cutoff)
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
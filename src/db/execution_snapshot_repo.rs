@@ -0,0 +1,46 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{BookSnapshot, ExecutionSnapshot};
+
+/// Store the book snapshot taken when an order was placed.
+pub async fn insert_snapshot(
+    pool: &PgPool,
+    order_id: Uuid,
+    token_id: &str,
+    side: &str,
+    target_price: Decimal,
+    order_price: Decimal,
+    book: &BookSnapshot,
+) -> anyhow::Result<ExecutionSnapshot> {
+    let snapshot = sqlx::query_as::<_, ExecutionSnapshot>(
+        r#"
+        INSERT INTO execution_snapshots (
+            order_id, token_id, side, target_price, order_price,
+            best_bid, best_ask, bid_depth, ask_depth, bids, asks,
+            book_hash, fetch_ms, captured_at
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        RETURNING *
+        "#,
+    )
+    .bind(order_id)
+    .bind(token_id)
+    .bind(side)
+    .bind(target_price)
+    .bind(order_price)
+    .bind(book.best_bid())
+    .bind(book.best_ask())
+    .bind(BookSnapshot::depth(&book.bids))
+    .bind(BookSnapshot::depth(&book.asks))
+    .bind(serde_json::to_value(&book.bids)?)
+    .bind(serde_json::to_value(&book.asks)?)
+    .bind(&book.book_hash)
+    .bind(book.fetch_ms)
+    .bind(book.captured_at)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(snapshot)
+}
@@ -0,0 +1,82 @@
+use sqlx::PgPool;
+
+use crate::intelligence::flow::TokenFlow;
+use crate::models::FlowSignal;
+
+/// Buy and sell notional from active tracked whales into a token over the
+/// last `window_minutes`, and how many distinct whales traded it.
+pub async fn get_token_flow(pool: &PgPool, token_id: &str, window_minutes: i32) -> anyhow::Result<TokenFlow> {
+    let flow = sqlx::query_as::<_, TokenFlow>(
+        r#"
+        SELECT COALESCE(SUM(t.notional) FILTER (WHERE t.side = 'BUY'), 0) AS buy_notional,
+               COALESCE(SUM(t.notional) FILTER (WHERE t.side = 'SELL'), 0) AS sell_notional,
+               COUNT(DISTINCT t.whale_id) AS whales
+        FROM whale_trades t
+        JOIN whales w ON w.id = t.whale_id AND w.is_active = TRUE
+        WHERE t.token_id = $1
+          AND t.traded_at > NOW() - make_interval(mins => $2)
+        "#,
+    )
+    .bind(token_id)
+    .bind(window_minutes)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(flow)
+}
+
+/// Record a flow signal unless the token already signalled within the
+/// window. Returns None for a duplicate.
+pub async fn record_flow_signal(
+    pool: &PgPool,
+    market_id: &str,
+    token_id: &str,
+    flow: &TokenFlow,
+    window_minutes: i32,
+) -> anyhow::Result<Option<FlowSignal>> {
+    let mut tx = pool.begin().await?;
+
+    sqlx::query("SELECT pg_advisory_xact_lock(hashtext('flow:' || $1))")
+        .bind(token_id)
+        .execute(&mut *tx)
+        .await?;
+
+    let signal = sqlx::query_as::<_, FlowSignal>(
+        r#"
+        INSERT INTO flow_signals (
+            market_id, token_id, buy_notional, sell_notional, net_notional, whales, window_minutes
+        )
+        SELECT $1, $2, $3, $4, $5, $6, $7
+        WHERE NOT EXISTS (
+            SELECT 1 FROM flow_signals
+            WHERE token_id = $2 AND triggered_at > NOW() - make_interval(mins => $7)
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(market_id)
+    .bind(token_id)
+    .bind(flow.buy_notional)
+    .bind(flow.sell_notional)
+    .bind(flow.net())
+    .bind(flow.whales as i32)
+    .bind(window_minutes)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    Ok(signal)
+}
+
+pub async fn get_recent_flow_signals(pool: &PgPool, limit: i64) -> anyhow::Result<Vec<FlowSignal>> {
+    let signals = sqlx::query_as::<_, FlowSignal>(
+        "SELECT * FROM flow_signals ORDER BY triggered_at DESC LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(signals)
+}
+
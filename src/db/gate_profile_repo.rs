@@ -0,0 +1,111 @@
+use sqlx::PgPool;
+
+use crate::models::GateProfile;
+
+/// All gate profiles, by name.
+pub async fn list_gate_profiles(pool: &PgPool) -> anyhow::Result<Vec<GateProfile>> {
+    let rows = sqlx::query_as::<_, GateProfile>("SELECT * FROM gate_profiles ORDER BY name")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows)
+}
+
+/// The profile the pipeline currently gates with, if any.
+pub async fn get_active_gate_profile(pool: &PgPool) -> anyhow::Result<Option<GateProfile>> {
+    let row = sqlx::query_as::<_, GateProfile>("SELECT * FROM gate_profiles WHERE is_active")
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(row)
+}
+
+/// Create a profile or replace its gates; its active flag is left as is.
+pub async fn upsert_gate_profile(pool: &PgPool, profile: &GateProfile) -> anyhow::Result<GateProfile> {
+    let saved = sqlx::query_as::<_, GateProfile>(
+        r#"
+        INSERT INTO gate_profiles (
+            name, description, min_signal_win_rate, min_resolved_for_signal,
+            min_total_trades_for_signal, min_signal_ev, min_signal_profit_factor,
+            min_signal_skill_score, min_entry_edge, signal_notional_liquidity_pct,
+            signal_notional_floor, max_signal_notional, assumed_slippage_pct,
+            first_mover_size_multiplier
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14)
+        ON CONFLICT (name) DO UPDATE SET
+            description = EXCLUDED.description,
+            min_signal_win_rate = EXCLUDED.min_signal_win_rate,
+            min_resolved_for_signal = EXCLUDED.min_resolved_for_signal,
+            min_total_trades_for_signal = EXCLUDED.min_total_trades_for_signal,
+            min_signal_ev = EXCLUDED.min_signal_ev,
+            min_signal_profit_factor = EXCLUDED.min_signal_profit_factor,
+            min_signal_skill_score = EXCLUDED.min_signal_skill_score,
+            min_entry_edge = EXCLUDED.min_entry_edge,
+            signal_notional_liquidity_pct = EXCLUDED.signal_notional_liquidity_pct,
+            signal_notional_floor = EXCLUDED.signal_notional_floor,
+            max_signal_notional = EXCLUDED.max_signal_notional,
+            assumed_slippage_pct = EXCLUDED.assumed_slippage_pct,
+            first_mover_size_multiplier = EXCLUDED.first_mover_size_multiplier,
+            updated_at = NOW()
+        RETURNING *
+        "#,
+    )
+    .bind(profile.name.trim())
+    .bind(&profile.description)
+    .bind(profile.min_signal_win_rate)
+    .bind(profile.min_resolved_for_signal)
+    .bind(profile.min_total_trades_for_signal)
+    .bind(profile.min_signal_ev)
+    .bind(profile.min_signal_profit_factor)
+    .bind(profile.min_signal_skill_score)
+    .bind(profile.min_entry_edge)
+    .bind(profile.signal_notional_liquidity_pct)
+    .bind(profile.signal_notional_floor)
+    .bind(profile.max_signal_notional)
+    .bind(profile.assumed_slippage_pct)
+    .bind(profile.first_mover_size_multiplier)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(saved)
+}
+
+/// Make `name` the only active profile. None if no such profile exists, in
+/// which case the active profile is unchanged.
+pub async fn activate_gate_profile(pool: &PgPool, name: &str) -> anyhow::Result<Option<GateProfile>> {
+    let mut tx = pool.begin().await?;
+
+    // Serialize switches so concurrent activations can't both deactivate and
+    // then race on the one-active index
+    sqlx::query("LOCK TABLE gate_profiles IN SHARE ROW EXCLUSIVE MODE")
+        .execute(&mut *tx)
+        .await?;
+    sqlx::query("UPDATE gate_profiles SET is_active = FALSE, updated_at = NOW() WHERE is_active AND name <> $1")
+        .bind(name)
+        .execute(&mut *tx)
+        .await?;
+    let activated = sqlx::query_as::<_, GateProfile>(
+        "UPDATE gate_profiles SET is_active = TRUE, updated_at = NOW() WHERE name = $1 RETURNING *",
+    )
+    .bind(name)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if activated.is_some() {
+        tx.commit().await?;
+    }
+    Ok(activated)
+}
+
+/// Deactivate `name`, returning to the env / runtime config gates.
+/// None if no such profile exists.
+pub async fn deactivate_gate_profile(pool: &PgPool, name: &str) -> anyhow::Result<Option<GateProfile>> {
+    let row = sqlx::query_as::<_, GateProfile>(
+        "UPDATE gate_profiles SET is_active = FALSE, updated_at = NOW() WHERE name = $1 RETURNING *",
+    )
+    .bind(name)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row)
+}
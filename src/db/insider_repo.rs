@@ -0,0 +1,151 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::{FromRow, PgPool};
+use uuid::Uuid;
+
+use crate::intelligence::insider::InsiderEvidence;
+use crate::models::InsiderFlag;
+
+/// A large whale trade with what happened after it: the market outcome and
+/// resolution time, or the most favourable candle price within the window.
+#[derive(Debug, Clone, FromRow)]
+pub struct InsiderCandidate {
+    pub trade_id: Uuid,
+    pub whale_id: Uuid,
+    /// Market key (condition_id when known).
+    pub market_id: String,
+    pub token_id: String,
+    pub side: String,
+    pub price: Decimal,
+    pub notional: Decimal,
+    pub traded_at: DateTime<Utc>,
+    pub outcome: Option<String>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    /// Highest high after a BUY, lowest low after a SELL.
+    pub extreme_price: Option<Decimal>,
+}
+
+/// Trades of at least `min_notional` since `since` whose market resolved
+/// within `window_hours` after the trade.
+pub async fn get_pre_resolution_candidates(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    min_notional: Decimal,
+    window_hours: i32,
+) -> anyhow::Result<Vec<InsiderCandidate>> {
+    let rows = sqlx::query_as::<_, InsiderCandidate>(
+        r#"
+        SELECT t.id AS trade_id, t.whale_id, COALESCE(t.condition_id, t.market_id) AS market_id,
+               t.token_id, t.side, t.price, t.notional, t.traded_at,
+               o.outcome, o.resolved_at, NULL::NUMERIC AS extreme_price
+        FROM whale_trades t
+        JOIN market_outcomes o ON o.market_id = COALESCE(t.condition_id, t.market_id)
+        WHERE t.whale_id IS NOT NULL
+          AND t.traded_at >= $1
+          AND t.notional >= $2
+          AND o.resolved_at > t.traded_at
+          AND o.resolved_at <= t.traded_at + make_interval(hours => $3)
+        "#,
+    )
+    .bind(since)
+    .bind(min_notional)
+    .bind(window_hours)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Trades of at least `min_notional` since `since`, with the most favourable
+/// recorded candle price in the `window_hours` after each.
+pub async fn get_pre_jump_candidates(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    min_notional: Decimal,
+    window_hours: i32,
+) -> anyhow::Result<Vec<InsiderCandidate>> {
+    let rows = sqlx::query_as::<_, InsiderCandidate>(
+        r#"
+        SELECT t.id AS trade_id, t.whale_id, COALESCE(t.condition_id, t.market_id) AS market_id,
+               t.token_id, t.side, t.price, t.notional, t.traded_at,
+               NULL::VARCHAR AS outcome, NULL::TIMESTAMPTZ AS resolved_at,
+               CASE WHEN t.side = 'BUY' THEN c.high ELSE c.low END AS extreme_price
+        FROM whale_trades t
+        JOIN LATERAL (
+            SELECT MAX(high) AS high, MIN(low) AS low
+            FROM market_candles
+            WHERE token_id = t.token_id
+              AND bucket > t.traded_at
+              AND bucket <= t.traded_at + make_interval(hours => $3)
+        ) c ON c.high IS NOT NULL
+        WHERE t.whale_id IS NOT NULL
+          AND t.traded_at >= $1
+          AND t.notional >= $2
+        "#,
+    )
+    .bind(since)
+    .bind(min_notional)
+    .bind(window_hours)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Record a flag. Returns None when this trade already carries a flag of
+/// the same kind.
+pub async fn insert_flag(
+    pool: &PgPool,
+    candidate: &InsiderCandidate,
+    evidence: &InsiderEvidence,
+) -> anyhow::Result<Option<InsiderFlag>> {
+    let flag = sqlx::query_as::<_, InsiderFlag>(
+        r#"
+        INSERT INTO insider_flags
+            (whale_id, trade_id, kind, market_id, token_id, side, entry_price, notional,
+             traded_at, lead_hours, price_move, evidence)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+        ON CONFLICT (trade_id, kind) DO NOTHING
+        RETURNING *
+        "#,
+    )
+    .bind(candidate.whale_id)
+    .bind(candidate.trade_id)
+    .bind(evidence.kind.as_str())
+    .bind(&candidate.market_id)
+    .bind(&candidate.token_id)
+    .bind(&candidate.side)
+    .bind(candidate.price)
+    .bind(candidate.notional)
+    .bind(candidate.traded_at)
+    .bind(evidence.lead_hours)
+    .bind(evidence.price_move)
+    .bind(&evidence.description)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(flag)
+}
+
+/// Number of insider flags recorded against a whale.
+pub async fn count_whale_flags(pool: &PgPool, whale_id: Uuid) -> anyhow::Result<i64> {
+    let (count,): (i64,) = sqlx::query_as("SELECT COUNT(*) FROM insider_flags WHERE whale_id = $1")
+        .bind(whale_id)
+        .fetch_one(pool)
+        .await?;
+
+    Ok(count)
+}
+
+/// A whale's insider flags, newest first.
+pub async fn get_whale_flags(pool: &PgPool, whale_id: Uuid, limit: i64) -> anyhow::Result<Vec<InsiderFlag>> {
+    let flags = sqlx::query_as::<_, InsiderFlag>(
+        "SELECT * FROM insider_flags WHERE whale_id = $1 ORDER BY traded_at DESC LIMIT $2",
+    )
+    .bind(whale_id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(flags)
+}
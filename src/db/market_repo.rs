@@ -1,25 +1,35 @@
-use chrono::Utc;
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use sqlx::PgPool;
 
-use crate::models::MarketOutcome;
+use crate::models::{normalize_condition_id, MarketOutcome};
+use crate::polymarket::gamma_client::parse_end_date;
 
 /// Insert a market_outcome record if it doesn't exist.
+///
+/// Rows are keyed by condition_id when it is known, so trades arriving from
+/// different sources (condition_id vs token id) share one outcome row.
 pub async fn upsert_market_outcome(
     pool: &PgPool,
     market_id: &str,
     token_id: Option<&str>,
+    condition_id: Option<&str>,
 ) -> anyhow::Result<MarketOutcome> {
     let row = sqlx::query_as::<_, MarketOutcome>(
         r#"
-        INSERT INTO market_outcomes (market_id, token_id)
-        VALUES ($1, $2)
-        ON CONFLICT (market_id) DO UPDATE SET updated_at = NOW()
+        INSERT INTO market_outcomes (market_id, token_id, condition_id)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (market_id) DO UPDATE
+        SET condition_id = COALESCE(market_outcomes.condition_id, EXCLUDED.condition_id),
+            updated_at = NOW()
         RETURNING *
         "#,
     )
-    .bind(market_id)
+    .bind(condition_id.unwrap_or(market_id))
     .bind(token_id)
+    .bind(condition_id)
     .fetch_one(pool)
     .await?;
 
@@ -175,6 +185,43 @@ pub async fn get_market_info(pool: &PgPool, market_id: &str) -> anyhow::Result<O
     Ok(row)
 }
 
+/// Get the question text and tag names for a market, for compliance checks.
+/// Same lookup order as `get_market_info`.
+pub async fn get_market_question_and_tags(
+    pool: &PgPool,
+    market_id: &str,
+) -> anyhow::Result<Option<(String, Vec<String>)>> {
+    let sql = "SELECT question, tags FROM active_markets WHERE condition_id = $1";
+
+    let mut row: Option<(String, Option<String>)> = sqlx::query_as(sql)
+        .bind(market_id)
+        .fetch_optional(pool)
+        .await?;
+
+    if row.is_none() && !market_id.starts_with("0x") {
+        row = sqlx::query_as(sql)
+            .bind(format!("0x{}", market_id))
+            .fetch_optional(pool)
+            .await?;
+    }
+
+    if row.is_none() {
+        row = sqlx::query_as(
+            "SELECT question, tags FROM active_markets WHERE clob_token_ids LIKE '%' || $1 || '%'",
+        )
+        .bind(market_id)
+        .fetch_optional(pool)
+        .await?;
+    }
+
+    Ok(row.map(|(question, tags)| {
+        let tags = tags
+            .and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok())
+            .unwrap_or_default();
+        (question, tags)
+    }))
+}
+
 /// Get a single market outcome by market_id.
 pub async fn get_market_outcome(
     pool: &PgPool,
@@ -189,3 +236,136 @@ pub async fn get_market_outcome(
 
     Ok(row)
 }
+
+/// Outcomes of the resolved markets among `market_ids`, keyed by market id.
+pub async fn get_resolved_outcomes(
+    pool: &PgPool,
+    market_ids: &[String],
+) -> anyhow::Result<HashMap<String, String>> {
+    let rows = sqlx::query_as::<_, (String, String)>(
+        "SELECT market_id, outcome FROM market_outcomes WHERE market_id = ANY($1) AND outcome != 'unresolved'",
+    )
+    .bind(market_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().collect())
+}
+
+/// Stored markets discovery hasn't refreshed for this long are treated as
+/// closed or delisted.
+pub const STALE_MARKET_HOURS: i64 = 24;
+
+/// Get the CLOB token IDs of markets in active_markets that are still live
+/// at `now`, deduplicated: refreshed by discovery within `STALE_MARKET_HOURS`
+/// and not past their end date. Used to seed WS subscriptions at startup
+/// before the first discovery scan.
+pub async fn get_active_market_token_ids(pool: &PgPool, now: DateTime<Utc>) -> anyhow::Result<Vec<String>> {
+    let rows: Vec<(Option<String>, Option<String>)> = sqlx::query_as(
+        r#"
+        SELECT clob_token_ids, end_date_iso
+        FROM active_markets
+        WHERE clob_token_ids IS NOT NULL AND updated_at >= $1
+        "#,
+    )
+    .bind(now - chrono::Duration::hours(STALE_MARKET_HOURS))
+    .fetch_all(pool)
+    .await?;
+
+    let mut token_ids: Vec<String> = rows
+        .into_iter()
+        .filter(|(_, end)| end.as_deref().and_then(parse_end_date).is_none_or(|end| end > now))
+        .filter_map(|(raw, _)| raw.and_then(|s| serde_json::from_str::<Vec<String>>(&s).ok()))
+        .flatten()
+        .filter(|t| !t.is_empty())
+        .collect();
+    token_ids.sort();
+    token_ids.dedup();
+
+    Ok(token_ids)
+}
+
+/// Condition ID, question, tags (JSON) and end date of every market in
+/// active_markets, for re-checking discovery exclusions.
+pub async fn get_active_markets_for_exclusion(
+    pool: &PgPool,
+) -> anyhow::Result<Vec<(String, String, Option<String>, Option<String>)>> {
+    let rows = sqlx::query_as("SELECT condition_id, question, tags, end_date_iso FROM active_markets")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows)
+}
+
+/// Remove markets from active_markets. Returns how many rows were deleted.
+pub async fn delete_active_markets(pool: &PgPool, condition_ids: &[String]) -> anyhow::Result<u64> {
+    if condition_ids.is_empty() {
+        return Ok(0);
+    }
+
+    let result = sqlx::query("DELETE FROM active_markets WHERE condition_id = ANY($1)")
+        .bind(condition_ids)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Resolve the condition_id for a trade.
+///
+/// `market_id` is used directly when it already is a condition_id; otherwise
+/// the token is looked up in active_markets, then in previously keyed outcomes.
+pub async fn resolve_condition_id(
+    pool: &PgPool,
+    market_id: &str,
+    token_id: &str,
+) -> anyhow::Result<Option<String>> {
+    if let Some(condition_id) = normalize_condition_id(market_id) {
+        return Ok(Some(condition_id));
+    }
+
+    let row: Option<(String,)> = sqlx::query_as(
+        "SELECT condition_id FROM active_markets WHERE clob_token_ids LIKE '%\"' || $1 || '\"%' LIMIT 1",
+    )
+    .bind(token_id)
+    .fetch_optional(pool)
+    .await?;
+
+    if let Some((condition_id,)) = row {
+        return Ok(Some(condition_id));
+    }
+
+    let row: Option<(Option<String>,)> = sqlx::query_as(
+        "SELECT condition_id FROM market_outcomes WHERE token_id = $1 AND condition_id IS NOT NULL LIMIT 1",
+    )
+    .bind(token_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(|r| r.0))
+}
+
+/// Condition ID and question of the active market `token_id` is an outcome
+/// token of.
+pub async fn get_market_by_token_id(pool: &PgPool, token_id: &str) -> anyhow::Result<Option<(String, String)>> {
+    let row: Option<(String, String)> = sqlx::query_as(
+        "SELECT condition_id, question FROM active_markets WHERE clob_token_ids LIKE '%\"' || $1 || '\"%' LIMIT 1",
+    )
+    .bind(token_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row)
+}
+
+/// Get the end date of a market from active_markets, if discovery has seen it.
+pub async fn get_market_end_date(pool: &PgPool, condition_id: &str) -> anyhow::Result<Option<DateTime<Utc>>> {
+    let row: Option<(Option<String>,)> = sqlx::query_as(
+        "SELECT end_date_iso FROM active_markets WHERE condition_id = $1",
+    )
+    .bind(condition_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(row.and_then(|r| r.0).as_deref().and_then(parse_end_date))
+}
@@ -1,8 +1,26 @@
+pub mod attribution_repo;
 pub mod basket_repo;
+pub mod candle_repo;
+pub mod capital_flow_repo;
+pub mod classification_audit_repo;
+pub mod compliance_repo;
 pub mod config_repo;
+pub mod discovery_exclusion_repo;
+pub mod event_queue_repo;
+pub mod execution_snapshot_repo;
+pub mod flow_repo;
+pub mod gate_profile_repo;
+pub mod insider_repo;
 pub mod market_repo;
 pub mod order_repo;
+pub mod portfolio_snapshot_repo;
 pub mod position_repo;
+pub mod reconciliation_repo;
+pub mod risk_event_repo;
+pub mod risk_limits_repo;
+pub mod seeder_candidate_repo;
+pub mod tape_repo;
+pub mod tier_repo;
 pub mod trade_repo;
 pub mod whale_repo;
 
@@ -5,7 +5,9 @@ use uuid::Uuid;
 
 use crate::models::CopyOrder;
 
-/// Insert a new copy order.
+/// Insert a new copy order. A nil `whale_trade_id` (exits, hedges, manual
+/// orders) is stored as NULL; `source_signal_id` is the basket consensus
+/// signal behind the order, if any.
 #[allow(clippy::too_many_arguments)]
 pub async fn insert_order(
     pool: &PgPool,
@@ -16,28 +18,48 @@ pub async fn insert_order(
     size: Decimal,
     target_price: Decimal,
     strategy: &str,
+    sleeve: &str,
+    condition_id: Option<&str>,
+    source_signal_id: Option<Uuid>,
 ) -> anyhow::Result<CopyOrder> {
     let order = sqlx::query_as::<_, CopyOrder>(
         r#"
-        INSERT INTO copy_orders (whale_trade_id, market_id, token_id, side, size, target_price, strategy)
-        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        INSERT INTO copy_orders (
+            whale_trade_id, market_id, token_id, side, size, target_price, strategy, sleeve, condition_id,
+            source_signal_id
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10)
         RETURNING *
         "#,
     )
-    .bind(whale_trade_id)
+    .bind((!whale_trade_id.is_nil()).then_some(whale_trade_id))
     .bind(market_id)
     .bind(token_id)
     .bind(side)
     .bind(size)
     .bind(target_price)
     .bind(strategy)
+    .bind(sleeve)
+    .bind(condition_id)
+    .bind(source_signal_id)
     .fetch_one(pool)
     .await?;
 
     Ok(order)
 }
 
-/// Mark an order as filled with actual fill price.
+/// Get a single order by ID.
+pub async fn get_order_by_id(pool: &PgPool, order_id: Uuid) -> anyhow::Result<Option<CopyOrder>> {
+    let order = sqlx::query_as::<_, CopyOrder>("SELECT * FROM copy_orders WHERE id = $1")
+        .bind(order_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(order)
+}
+
+/// Mark an order as filled with actual fill price, recording the copy lag
+/// against the whale trade it copies (if any).
 pub async fn fill_order(
     pool: &PgPool,
     order_id: Uuid,
@@ -47,7 +69,12 @@ pub async fn fill_order(
     sqlx::query(
         r#"
         UPDATE copy_orders
-        SET status = 'filled', fill_price = $2, slippage = $3, filled_at = $4
+        SET status = 'filled', fill_price = $2, slippage = $3, filled_at = $4,
+            copy_lag = (
+                SELECT CASE WHEN copy_orders.side = 'SELL' THEN t.price - $2 ELSE $2 - t.price END / t.price
+                FROM whale_trades t
+                WHERE t.id = copy_orders.whale_trade_id AND t.price > 0
+            )
         WHERE id = $1
         "#,
     )
@@ -61,6 +88,117 @@ pub async fn fill_order(
     Ok(())
 }
 
+/// Mark an order that left the book part-filled as filled for the matched
+/// size only.
+pub async fn fill_order_partially(
+    pool: &PgPool,
+    order_id: Uuid,
+    filled_size: Decimal,
+    fill_price: Decimal,
+    slippage: Decimal,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE copy_orders SET size = $2 WHERE id = $1")
+        .bind(order_id)
+        .bind(filled_size)
+        .execute(pool)
+        .await?;
+
+    fill_order(pool, order_id, fill_price, slippage).await
+}
+
+/// Record how many reprices led to an exit order.
+pub async fn set_exit_reprices(pool: &PgPool, order_id: Uuid, reprices: i32) -> anyhow::Result<()> {
+    sqlx::query("UPDATE copy_orders SET exit_reprices = $2 WHERE id = $1")
+        .bind(order_id)
+        .bind(reprices)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Record that the out-of-reprices alert for an exit order was sent.
+pub async fn mark_exit_alerted(pool: &PgPool, order_id: Uuid) -> anyhow::Result<()> {
+    sqlx::query("UPDATE copy_orders SET exit_alerted_at = NOW() WHERE id = $1")
+        .bind(order_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Record the fee and gas (both in USDC) paid for a filled order.
+pub async fn set_order_costs(
+    pool: &PgPool,
+    order_id: Uuid,
+    fee_usdc: Decimal,
+    gas_usdc: Option<Decimal>,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE copy_orders SET fee_usdc = $2, gas_usdc = $3 WHERE id = $1")
+        .bind(order_id)
+        .bind(fee_usdc)
+        .bind(gas_usdc)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Fees plus gas as a fraction of filled notional, over orders filled since
+/// `since` that have recorded costs. None when there is nothing to average.
+pub async fn get_cost_rate_since(
+    pool: &PgPool,
+    since: chrono::DateTime<Utc>,
+) -> anyhow::Result<Option<Decimal>> {
+    let row: (Option<Decimal>, Option<Decimal>) = sqlx::query_as(
+        r#"
+        SELECT SUM(COALESCE(fee_usdc, 0) + COALESCE(gas_usdc, 0)),
+               SUM(size * fill_price)
+        FROM copy_orders
+        WHERE status = 'filled' AND fee_usdc IS NOT NULL AND filled_at >= $1
+        "#,
+    )
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(match row {
+        (Some(costs), Some(notional)) if notional > Decimal::ZERO => Some(costs / notional),
+        _ => None,
+    })
+}
+
+/// Average copy lag over a whale's filled copies, and how many it covers.
+pub async fn get_whale_copy_lag(
+    pool: &PgPool,
+    whale_id: Uuid,
+) -> anyhow::Result<(Option<Decimal>, i64)> {
+    let row = sqlx::query_as(
+        r#"
+        SELECT AVG(o.copy_lag), COUNT(o.copy_lag)
+        FROM copy_orders o
+        JOIN whale_trades t ON t.id = o.whale_trade_id
+        WHERE t.whale_id = $1 AND o.status = 'filled'
+        "#,
+    )
+    .bind(whale_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row)
+}
+
+/// Total fees and gas (USDC) recorded across all filled orders.
+pub async fn get_total_costs(pool: &PgPool) -> anyhow::Result<(Decimal, Decimal)> {
+    let row: (Option<Decimal>, Option<Decimal>) = sqlx::query_as(
+        "SELECT SUM(fee_usdc), SUM(gas_usdc) FROM copy_orders WHERE status = 'filled'",
+    )
+    .fetch_one(pool)
+    .await?;
+
+    Ok((row.0.unwrap_or(Decimal::ZERO), row.1.unwrap_or(Decimal::ZERO)))
+}
+
 /// Mark an order as failed with error message.
 pub async fn fail_order(
     pool: &PgPool,
@@ -117,6 +255,46 @@ pub async fn get_submitted_orders(pool: &PgPool) -> anyhow::Result<Vec<CopyOrder
     Ok(orders)
 }
 
+/// Size of a sleeve's token still being sold by exit orders that have not
+/// filled yet. New exits are capped at the position size minus this.
+pub async fn get_pending_exit_size(pool: &PgPool, token_id: &str, sleeve: &str) -> anyhow::Result<Decimal> {
+    let size: Option<Decimal> = sqlx::query_scalar(
+        r#"
+        SELECT SUM(size) FROM copy_orders
+        WHERE token_id = $1 AND sleeve = $2 AND strategy = 'exit' AND side = 'SELL'
+          AND status IN ('pending', 'submitted')
+        "#,
+    )
+    .bind(token_id)
+    .bind(sleeve)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(size.unwrap_or_default())
+}
+
+/// Fill time and slippage (fraction) of every live (CLOB) fill of a copied
+/// trade since `since`, oldest first. Manual orders are left out.
+pub async fn get_live_fill_slippages(
+    pool: &PgPool,
+    since: chrono::DateTime<Utc>,
+) -> anyhow::Result<Vec<(chrono::DateTime<Utc>, Decimal)>> {
+    let rows = sqlx::query_as(
+        r#"
+        SELECT filled_at, COALESCE(slippage, 0)
+        FROM copy_orders
+        WHERE status = 'filled' AND clob_order_id IS NOT NULL
+          AND filled_at >= $1 AND strategy <> 'manual'
+        ORDER BY filled_at ASC
+        "#,
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
 /// Mark an order as cancelled.
 pub async fn cancel_order(pool: &PgPool, order_id: Uuid) -> anyhow::Result<()> {
     sqlx::query(
@@ -159,6 +337,12 @@ pub struct EnrichedCopyOrder {
     pub placed_at: Option<chrono::DateTime<chrono::Utc>>,
     pub filled_at: Option<chrono::DateTime<chrono::Utc>>,
     pub clob_order_id: Option<String>,
+    pub sleeve: String,
+    pub condition_id: Option<String>,
+    pub fee_usdc: Option<Decimal>,
+    pub gas_usdc: Option<Decimal>,
+    pub source_signal_id: Option<Uuid>,
+    pub copy_lag: Option<Decimal>,
     // joined whale info
     pub whale_address: Option<String>,
     pub whale_label: Option<String>,
@@ -176,7 +360,7 @@ pub async fn get_all_orders_enriched(pool: &PgPool) -> anyhow::Result<Vec<Enrich
         FROM copy_orders co
         LEFT JOIN whale_trades wt ON co.whale_trade_id = wt.id
         LEFT JOIN whales w ON wt.whale_id = w.id
-        LEFT JOIN active_markets am1 ON co.market_id = am1.condition_id
+        LEFT JOIN active_markets am1 ON COALESCE(co.condition_id, co.market_id) = am1.condition_id
         LEFT JOIN active_markets am2 ON am2.clob_token_ids LIKE '%' || co.token_id || '%'
         ORDER BY co.placed_at DESC
         LIMIT 200
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use crate::models::PortfolioSnapshot;
+
+/// Record a snapshot, deriving exposure and PnL from the positions table.
+pub async fn insert_snapshot(pool: &PgPool, bankroll: Decimal) -> anyhow::Result<PortfolioSnapshot> {
+    let snapshot = sqlx::query_as::<_, PortfolioSnapshot>(
+        r#"
+        WITH totals AS (
+            SELECT
+                COALESCE(SUM(size * avg_entry_price) FILTER (WHERE status IN ('open', 'exiting')), 0) AS open_exposure,
+                COALESCE(SUM(unrealized_pnl) FILTER (WHERE status IN ('open', 'exiting')), 0) AS unrealized_pnl,
+                COALESCE(SUM(realized_pnl), 0) AS realized_pnl,
+                COUNT(*) FILTER (WHERE status IN ('open', 'exiting'))::INT AS open_positions
+            FROM positions
+        )
+        INSERT INTO portfolio_snapshots (
+            bankroll, open_exposure, unrealized_pnl, realized_pnl, equity, open_positions
+        )
+        SELECT $1, open_exposure, unrealized_pnl, realized_pnl,
+               $1 + open_exposure + unrealized_pnl, open_positions
+        FROM totals
+        RETURNING *
+        "#,
+    )
+    .bind(bankroll)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(snapshot)
+}
+
+/// Snapshots taken since `since`, oldest first.
+pub async fn get_snapshots_since(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+) -> anyhow::Result<Vec<PortfolioSnapshot>> {
+    let snapshots = sqlx::query_as::<_, PortfolioSnapshot>(
+        "SELECT * FROM portfolio_snapshots WHERE taken_at >= $1 ORDER BY taken_at",
+    )
+    .bind(since)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(snapshots)
+}
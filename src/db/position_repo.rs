@@ -1,9 +1,12 @@
 use rust_decimal::Decimal;
 use sqlx::PgPool;
 
-use crate::models::Position;
+use crate::models::{Position, StopMode};
 
-/// Open a new position or add to an existing one in the same market/token.
+/// Open a new position or add to the open (or exiting) one in the same token
+/// and sleeve. Each sleeve holds its own position, so capital reserved from a sleeve is
+/// always booked against — and returned to — that sleeve.
+#[allow(clippy::too_many_arguments)]
 pub async fn upsert_position(
     pool: &PgPool,
     market_id: &str,
@@ -11,12 +14,23 @@ pub async fn upsert_position(
     outcome: &str,
     size: Decimal,
     entry_price: Decimal,
+    sleeve: &str,
+    condition_id: Option<&str>,
+    whale_trade_id: Option<uuid::Uuid>,
+    source_signal_id: Option<uuid::Uuid>,
 ) -> anyhow::Result<Position> {
-    // Try to find an existing open position for this token
+    // Try to find an existing position for this token in this sleeve; one with
+    // an exit in flight is added to rather than duplicated
     let existing = sqlx::query_as::<_, Position>(
-        "SELECT * FROM positions WHERE token_id = $1 AND status = 'open' LIMIT 1",
+        r#"
+        SELECT * FROM positions
+        WHERE token_id = $1 AND sleeve = $2 AND status IN ('open', 'exiting')
+        ORDER BY opened_at, id
+        LIMIT 1
+        "#,
     )
     .bind(token_id)
+    .bind(sleeve)
     .fetch_optional(pool)
     .await?;
 
@@ -29,7 +43,10 @@ pub async fn upsert_position(
             let updated = sqlx::query_as::<_, Position>(
                 r#"
                 UPDATE positions
-                SET size = $2, avg_entry_price = $3
+                SET size = $2, avg_entry_price = $3, condition_id = COALESCE(condition_id, $4),
+                    whale_trade_id = COALESCE(whale_trade_id, $5),
+                    whale_id = COALESCE(whale_id, (SELECT whale_id FROM whale_trades WHERE id = $5)),
+                    source_signal_id = COALESCE(source_signal_id, $6)
                 WHERE id = $1
                 RETURNING *
                 "#,
@@ -37,17 +54,31 @@ pub async fn upsert_position(
             .bind(pos.id)
             .bind(new_size)
             .bind(new_avg)
+            .bind(condition_id)
+            .bind(whale_trade_id)
+            .bind(source_signal_id)
             .fetch_one(pool)
             .await?;
 
             Ok(updated)
         }
         None => {
-            // Create new position
+            // Create new position, remembering the market's liquidity at entry
+            let entry_liquidity =
+                super::market_repo::get_market_liquidity(pool, condition_id.unwrap_or(market_id))
+                    .await
+                    .ok()
+                    .flatten();
             let pos = sqlx::query_as::<_, Position>(
                 r#"
-                INSERT INTO positions (market_id, token_id, outcome, size, avg_entry_price)
-                VALUES ($1, $2, $3, $4, $5)
+                INSERT INTO positions (
+                    market_id, token_id, outcome, size, avg_entry_price, sleeve, condition_id,
+                    whale_trade_id, whale_id, entry_liquidity, source_signal_id, exit_style
+                )
+                SELECT $1, $2, $3, $4, $5, $6, $7, $8, t.whale_id, $9, $10, w.exit_style
+                FROM (SELECT 1) AS one
+                LEFT JOIN whale_trades t ON t.id = $8
+                LEFT JOIN whales w ON w.id = t.whale_id
                 RETURNING *
                 "#,
             )
@@ -56,6 +87,11 @@ pub async fn upsert_position(
             .bind(outcome)
             .bind(size)
             .bind(entry_price)
+            .bind(sleeve)
+            .bind(condition_id)
+            .bind(whale_trade_id)
+            .bind(entry_liquidity)
+            .bind(source_signal_id)
             .fetch_one(pool)
             .await?;
 
@@ -87,6 +123,33 @@ pub async fn get_open_positions(pool: &PgPool) -> anyhow::Result<Vec<Position>>
     Ok(positions)
 }
 
+/// Distinct capital labels (sleeve or copy profile) on positions not yet
+/// closed and on orders still in flight.
+pub async fn get_live_capital_labels(pool: &PgPool) -> anyhow::Result<Vec<String>> {
+    let rows: Vec<(String,)> = sqlx::query_as(
+        r#"
+        SELECT sleeve FROM positions WHERE status IN ('open', 'exiting')
+        UNION
+        SELECT sleeve FROM copy_orders WHERE status IN ('pending', 'submitted', 'partial')
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows.into_iter().map(|(label,)| label).collect())
+}
+
+/// Get all positions not yet closed, including those with an exit in flight.
+pub async fn get_unclosed_positions(pool: &PgPool) -> anyhow::Result<Vec<Position>> {
+    let positions = sqlx::query_as::<_, Position>(
+        "SELECT * FROM positions WHERE status IN ('open', 'exiting') ORDER BY opened_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(positions)
+}
+
 /// Get all positions (most recent first), limited to 200.
 pub async fn get_all_positions(pool: &PgPool) -> anyhow::Result<Vec<Position>> {
     let positions = sqlx::query_as::<_, Position>(
@@ -109,24 +172,38 @@ pub async fn count_open_positions(pool: &PgPool) -> anyhow::Result<i64> {
     Ok(row.0)
 }
 
-/// Get all open positions for a specific market.
-pub async fn get_positions_for_market(pool: &PgPool, market_id: &str) -> anyhow::Result<Vec<Position>> {
+/// Count open positions in a single capital sleeve.
+pub async fn count_open_positions_in_sleeve(pool: &PgPool, sleeve: &str) -> anyhow::Result<i64> {
+    let row: (i64,) = sqlx::query_as(
+        "SELECT COUNT(*) FROM positions WHERE status = 'open' AND sleeve = $1",
+    )
+    .bind(sleeve)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.0)
+}
+
+/// Get all open positions for a specific market, matched by condition_id
+/// or, for positions opened before it was known, by the raw market_id.
+pub async fn get_positions_for_market(pool: &PgPool, market_key: &str) -> anyhow::Result<Vec<Position>> {
     let positions = sqlx::query_as::<_, Position>(
-        "SELECT * FROM positions WHERE market_id = $1 AND status = 'open'",
+        "SELECT * FROM positions WHERE (condition_id = $1 OR market_id = $1) AND status = 'open'",
     )
-    .bind(market_id)
+    .bind(market_key)
     .fetch_all(pool)
     .await?;
 
     Ok(positions)
 }
 
-/// Close a position with realized PnL.
+/// Close a position with realized PnL on the remaining size (added to any
+/// PnL already realized by partial exits).
 pub async fn close_position(pool: &PgPool, position_id: uuid::Uuid, realized_pnl: Decimal) -> anyhow::Result<()> {
     sqlx::query(
         r#"
         UPDATE positions
-        SET status = 'closed', realized_pnl = $2, closed_at = NOW()
+        SET status = 'closed', realized_pnl = COALESCE(realized_pnl, 0) + $2, closed_at = NOW()
         WHERE id = $1
         "#,
     )
@@ -149,6 +226,18 @@ pub async fn get_daily_realized_pnl(pool: &PgPool) -> anyhow::Result<Decimal> {
     Ok(row.0.unwrap_or(Decimal::ZERO))
 }
 
+/// Get today's realized PnL for a single capital sleeve.
+pub async fn get_daily_realized_pnl_in_sleeve(pool: &PgPool, sleeve: &str) -> anyhow::Result<Decimal> {
+    let row: (Option<Decimal>,) = sqlx::query_as(
+        "SELECT COALESCE(SUM(realized_pnl), 0) FROM positions WHERE closed_at >= CURRENT_DATE AND sleeve = $1",
+    )
+    .bind(sleeve)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(row.0.unwrap_or(Decimal::ZERO))
+}
+
 /// Update the current price and last_price_update timestamp for a position.
 pub async fn update_position_price(
     pool: &PgPool,
@@ -217,22 +306,114 @@ pub async fn mark_position_exiting(
     Ok(())
 }
 
-/// Find an open/exiting position by token_id.
+/// Return an exiting position to "open" after its exit order was cancelled,
+/// so the position monitor evaluates it again.
+pub async fn reopen_position(pool: &PgPool, position_id: uuid::Uuid) -> anyhow::Result<()> {
+    sqlx::query("UPDATE positions SET status = 'open' WHERE id = $1 AND status = 'exiting'")
+        .bind(position_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Sell part of a position: shrink its size and accumulate realized PnL.
+/// The position stays open.
+pub async fn reduce_position(
+    pool: &PgPool,
+    position_id: uuid::Uuid,
+    sold_size: Decimal,
+    realized_pnl: Decimal,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE positions
+        SET size = size - $2,
+            realized_pnl = COALESCE(realized_pnl, 0) + $3
+        WHERE id = $1
+        "#,
+    )
+    .bind(position_id)
+    .bind(sold_size)
+    .bind(realized_pnl)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Mark a position as hedged by a buy of its complementary outcome.
+pub async fn mark_position_hedged(pool: &PgPool, position_id: uuid::Uuid) -> anyhow::Result<()> {
+    sqlx::query("UPDATE positions SET hedged_at = NOW() WHERE id = $1")
+        .bind(position_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Link a hedge leg to the position it hedges.
+pub async fn set_hedge_of(
+    pool: &PgPool,
+    hedge_position_id: uuid::Uuid,
+    hedged_position_id: uuid::Uuid,
+) -> anyhow::Result<()> {
+    sqlx::query("UPDATE positions SET hedge_of = $2 WHERE id = $1")
+        .bind(hedge_position_id)
+        .bind(hedged_position_id)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Overwrite a position's size, e.g. to match the account after reconciliation.
+pub async fn set_position_size(pool: &PgPool, position_id: uuid::Uuid, size: Decimal) -> anyhow::Result<()> {
+    sqlx::query("UPDATE positions SET size = $2 WHERE id = $1")
+        .bind(position_id)
+        .bind(size)
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
+
+/// Find the open/exiting position a sleeve holds in a token.
 pub async fn get_position_by_token_id(
     pool: &PgPool,
     token_id: &str,
+    sleeve: &str,
 ) -> anyhow::Result<Option<Position>> {
     let pos = sqlx::query_as::<_, Position>(
-        "SELECT * FROM positions WHERE token_id = $1 AND status IN ('open', 'exiting') LIMIT 1",
+        r#"
+        SELECT * FROM positions
+        WHERE token_id = $1 AND sleeve = $2 AND status IN ('open', 'exiting')
+        ORDER BY opened_at, id
+        LIMIT 1
+        "#,
     )
     .bind(token_id)
+    .bind(sleeve)
     .fetch_optional(pool)
     .await?;
 
     Ok(pos)
 }
 
-/// Close a position with realized PnL and an exit reason (stop_loss / take_profit).
+/// All open/exiting positions in a token, one per sleeve holding it.
+pub async fn get_positions_by_token_id(pool: &PgPool, token_id: &str) -> anyhow::Result<Vec<Position>> {
+    let positions = sqlx::query_as::<_, Position>(
+        "SELECT * FROM positions WHERE token_id = $1 AND status IN ('open', 'exiting') ORDER BY opened_at",
+    )
+    .bind(token_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(positions)
+}
+
+/// Close a position with realized PnL on the remaining size and an exit reason
+/// (stop_loss / take_profit).
 pub async fn close_position_with_reason(
     pool: &PgPool,
     position_id: uuid::Uuid,
@@ -243,7 +424,7 @@ pub async fn close_position_with_reason(
         r#"
         UPDATE positions
         SET status = 'closed',
-            realized_pnl = $2,
+            realized_pnl = COALESCE(realized_pnl, 0) + $2,
             closed_at = NOW(),
             exit_reason = $3,
             exited_at = NOW()
@@ -259,25 +440,52 @@ pub async fn close_position_with_reason(
     Ok(())
 }
 
-/// Set stop-loss and take-profit percentages for a position.
+/// Set stop-loss and take-profit percentages and the stop mode for a position.
 pub async fn set_position_sl_tp(
     pool: &PgPool,
     position_id: uuid::Uuid,
     stop_loss_pct: Decimal,
     take_profit_pct: Decimal,
+    stop_mode: StopMode,
 ) -> anyhow::Result<()> {
     sqlx::query(
         r#"
         UPDATE positions
-        SET stop_loss_pct = $2, take_profit_pct = $3
+        SET stop_loss_pct = $2, take_profit_pct = $3, stop_mode = $4
         WHERE id = $1
         "#,
     )
     .bind(position_id)
     .bind(stop_loss_pct)
     .bind(take_profit_pct)
+    .bind(stop_mode.as_str())
     .execute(pool)
     .await?;
 
     Ok(())
 }
+
+/// Change a position's stop mode (and optionally its stop distance).
+pub async fn set_position_stop_mode(
+    pool: &PgPool,
+    position_id: uuid::Uuid,
+    stop_mode: StopMode,
+    stop_loss_pct: Option<Decimal>,
+) -> anyhow::Result<Option<Position>> {
+    let pos = sqlx::query_as::<_, Position>(
+        r#"
+        UPDATE positions
+        SET stop_mode = $2,
+            stop_loss_pct = COALESCE($3, stop_loss_pct)
+        WHERE id = $1 AND status = 'open'
+        RETURNING *
+        "#,
+    )
+    .bind(position_id)
+    .bind(stop_mode.as_str())
+    .bind(stop_loss_pct)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(pos)
+}
@@ -0,0 +1,53 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use crate::models::ReconciliationReport;
+
+/// Store the result of a reconciliation run.
+#[allow(clippy::too_many_arguments)]
+pub async fn insert_report(
+    pool: &PgPool,
+    passed: bool,
+    db_positions: i32,
+    account_positions: i32,
+    db_open_orders: i32,
+    clob_open_orders: Option<i32>,
+    ledger_balance: Option<Decimal>,
+    chain_balance: Option<Decimal>,
+    discrepancies: serde_json::Value,
+) -> anyhow::Result<ReconciliationReport> {
+    let report = sqlx::query_as::<_, ReconciliationReport>(
+        r#"
+        INSERT INTO reconciliation_reports (
+            passed, db_positions, account_positions, db_open_orders, clob_open_orders,
+            ledger_balance, chain_balance, discrepancies
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING *
+        "#,
+    )
+    .bind(passed)
+    .bind(db_positions)
+    .bind(account_positions)
+    .bind(db_open_orders)
+    .bind(clob_open_orders)
+    .bind(ledger_balance)
+    .bind(chain_balance)
+    .bind(discrepancies)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(report)
+}
+
+/// Most recent reconciliation reports.
+pub async fn get_recent_reports(pool: &PgPool, limit: i64) -> anyhow::Result<Vec<ReconciliationReport>> {
+    let reports = sqlx::query_as::<_, ReconciliationReport>(
+        "SELECT * FROM reconciliation_reports ORDER BY created_at DESC LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(reports)
+}
@@ -0,0 +1,63 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use crate::execution::risk_manager::RiskViolation;
+use crate::models::{CopySignal, RiskEvent};
+
+/// Record a signal rejected by a risk check.
+pub async fn insert_risk_event(
+    pool: &PgPool,
+    scope: &str,
+    violation: &RiskViolation,
+    signal: &CopySignal,
+    size: Decimal,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO risk_events (
+            rule, scope, message, details, whale_trade_id, wallet, market_id,
+            condition_id, token_id, side, size, price, sleeve
+        )
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        "#,
+    )
+    .bind(violation.rule())
+    .bind(scope)
+    .bind(violation.to_string())
+    .bind(serde_json::to_value(violation)?)
+    .bind(signal.whale_trade_id)
+    .bind(&signal.wallet)
+    .bind(&signal.market_id)
+    .bind(&signal.condition_id)
+    .bind(&signal.asset_id)
+    .bind(signal.side.to_string())
+    .bind(size)
+    .bind(signal.price)
+    .bind(signal.capital_label())
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Most recent risk events, optionally filtered by rule.
+pub async fn get_risk_events(
+    pool: &PgPool,
+    rule: Option<&str>,
+    limit: i64,
+) -> anyhow::Result<Vec<RiskEvent>> {
+    let events = sqlx::query_as::<_, RiskEvent>(
+        r#"
+        SELECT * FROM risk_events
+        WHERE $1::TEXT IS NULL OR rule = $1
+        ORDER BY created_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(rule)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(events)
+}
@@ -0,0 +1,87 @@
+use sqlx::PgPool;
+
+use crate::execution::risk_manager::RiskLimits;
+
+/// Load the persisted risk limits, inserting `seed` on first run.
+pub async fn get_or_init(pool: &PgPool, seed: &RiskLimits) -> anyhow::Result<RiskLimits> {
+    sqlx::query(
+        r#"
+        INSERT INTO risk_limits (
+            id, max_position_pct, max_open_positions, max_daily_loss, min_spread_to_resolution,
+            max_slippage_pct, max_tail_loss_pct, trailing_stop_pct, max_position_hold_days,
+            min_hours_to_resolution, min_matic_balance, usdc_fee_buffer, exit_hours_before_resolution,
+            reserve_floor_pct
+        )
+        VALUES (TRUE, $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13)
+        ON CONFLICT (id) DO NOTHING
+        "#,
+    )
+    .bind(seed.max_position_pct)
+    .bind(seed.max_open_positions)
+    .bind(seed.max_daily_loss)
+    .bind(seed.min_spread_to_resolution)
+    .bind(seed.max_slippage_pct)
+    .bind(seed.max_tail_loss_pct)
+    .bind(seed.trailing_stop_pct)
+    .bind(seed.max_position_hold_days)
+    .bind(seed.min_hours_to_resolution)
+    .bind(seed.min_matic_balance)
+    .bind(seed.usdc_fee_buffer)
+    .bind(seed.exit_hours_before_resolution)
+    .bind(seed.reserve_floor_pct)
+    .execute(pool)
+    .await?;
+
+    get_risk_limits(pool).await
+}
+
+/// Get the current risk limits.
+pub async fn get_risk_limits(pool: &PgPool) -> anyhow::Result<RiskLimits> {
+    let limits = sqlx::query_as::<_, RiskLimits>("SELECT * FROM risk_limits WHERE id")
+        .fetch_one(pool)
+        .await?;
+
+    Ok(limits)
+}
+
+/// Overwrite the persisted risk limits.
+pub async fn update_risk_limits(pool: &PgPool, limits: &RiskLimits) -> anyhow::Result<RiskLimits> {
+    let updated = sqlx::query_as::<_, RiskLimits>(
+        r#"
+        UPDATE risk_limits
+        SET max_position_pct = $1,
+            max_open_positions = $2,
+            max_daily_loss = $3,
+            min_spread_to_resolution = $4,
+            max_slippage_pct = $5,
+            max_tail_loss_pct = $6,
+            trailing_stop_pct = $7,
+            max_position_hold_days = $8,
+            min_hours_to_resolution = $9,
+            min_matic_balance = $10,
+            usdc_fee_buffer = $11,
+            exit_hours_before_resolution = $12,
+            reserve_floor_pct = $13,
+            updated_at = NOW()
+        WHERE id
+        RETURNING *
+        "#,
+    )
+    .bind(limits.max_position_pct)
+    .bind(limits.max_open_positions)
+    .bind(limits.max_daily_loss)
+    .bind(limits.min_spread_to_resolution)
+    .bind(limits.max_slippage_pct)
+    .bind(limits.max_tail_loss_pct)
+    .bind(limits.trailing_stop_pct)
+    .bind(limits.max_position_hold_days)
+    .bind(limits.min_hours_to_resolution)
+    .bind(limits.min_matic_balance)
+    .bind(limits.usdc_fee_buffer)
+    .bind(limits.exit_hours_before_resolution)
+    .bind(limits.reserve_floor_pct)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(updated)
+}
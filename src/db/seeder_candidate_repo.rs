@@ -0,0 +1,77 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{SeederCandidate, SeederCandidateStatus};
+
+/// Queue an address for the next seeder cycle. An address already pending
+/// keeps its queue entry (the note is updated when one is given).
+pub async fn enqueue_candidate(
+    pool: &PgPool,
+    address: &str,
+    note: Option<&str>,
+) -> anyhow::Result<SeederCandidate> {
+    let candidate = sqlx::query_as::<_, SeederCandidate>(
+        r#"
+        INSERT INTO seeder_candidates (address, note)
+        VALUES ($1, $2)
+        ON CONFLICT (address) WHERE status = 'pending' DO UPDATE
+        SET note = COALESCE(EXCLUDED.note, seeder_candidates.note)
+        RETURNING *
+        "#,
+    )
+    .bind(address)
+    .bind(note)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(candidate)
+}
+
+/// Recent candidates with their verdicts, newest first.
+pub async fn list_candidates(pool: &PgPool, limit: i64) -> anyhow::Result<Vec<SeederCandidate>> {
+    let candidates = sqlx::query_as::<_, SeederCandidate>(
+        "SELECT * FROM seeder_candidates ORDER BY requested_at DESC LIMIT $1",
+    )
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(candidates)
+}
+
+/// Candidates waiting for evaluation, oldest first.
+pub async fn get_pending_candidates(pool: &PgPool) -> anyhow::Result<Vec<SeederCandidate>> {
+    let candidates = sqlx::query_as::<_, SeederCandidate>(
+        "SELECT * FROM seeder_candidates WHERE status = 'pending' ORDER BY requested_at",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(candidates)
+}
+
+/// Store the evaluation result for a candidate.
+pub async fn record_verdict(
+    pool: &PgPool,
+    id: Uuid,
+    status: SeederCandidateStatus,
+    verdict: &str,
+    whale_id: Option<Uuid>,
+) -> anyhow::Result<SeederCandidate> {
+    let candidate = sqlx::query_as::<_, SeederCandidate>(
+        r#"
+        UPDATE seeder_candidates
+        SET status = $2, verdict = $3, whale_id = $4, evaluated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(id)
+    .bind(status.as_str())
+    .bind(verdict)
+    .bind(whale_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(candidate)
+}
@@ -0,0 +1,67 @@
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+
+use crate::models::TapePrint;
+
+/// Append prints to the trade tape.
+pub async fn insert_prints(pool: &PgPool, prints: &[TapePrint]) -> anyhow::Result<()> {
+    let mut tx = pool.begin().await?;
+    for p in prints {
+        sqlx::query(
+            r#"
+            INSERT INTO market_trades (market_id, token_id, side, size, price, wallet, source, traded_at)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            "#,
+        )
+        .bind(&p.market_id)
+        .bind(&p.token_id)
+        .bind(&p.side)
+        .bind(p.size)
+        .bind(p.price)
+        .bind(&p.wallet)
+        .bind(&p.source)
+        .bind(p.traded_at)
+        .execute(&mut *tx)
+        .await?;
+    }
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Most recent prints for a market, newest first. `id` matches the market
+/// key as received, a token id, or a condition id whose tokens discovery
+/// has seen (chain prints are keyed by token).
+pub async fn get_market_tape(pool: &PgPool, id: &str, limit: i64) -> anyhow::Result<Vec<TapePrint>> {
+    let prints = sqlx::query_as::<_, TapePrint>(
+        r#"
+        SELECT market_id, token_id, side, size, price, wallet, source, traded_at
+        FROM market_trades t
+        WHERE t.market_id = $1
+           OR t.token_id = $1
+           OR EXISTS (
+               SELECT 1 FROM active_markets m
+               WHERE m.condition_id = $1
+                 AND m.clob_token_ids LIKE '%"' || t.token_id || '"%'
+           )
+        ORDER BY traded_at DESC
+        LIMIT $2
+        "#,
+    )
+    .bind(id)
+    .bind(limit)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(prints)
+}
+
+/// Delete prints older than `cutoff`. Returns the number of rows removed.
+pub async fn delete_prints_before(pool: &PgPool, cutoff: DateTime<Utc>) -> anyhow::Result<u64> {
+    let result = sqlx::query("DELETE FROM market_trades WHERE traded_at < $1")
+        .bind(cutoff)
+        .execute(pool)
+        .await?;
+
+    Ok(result.rows_affected())
+}
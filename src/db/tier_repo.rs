@@ -0,0 +1,57 @@
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::models::{TierPolicy, WhaleTier};
+
+/// All tier policies, A first.
+pub async fn get_tier_policies(pool: &PgPool) -> anyhow::Result<Vec<TierPolicy>> {
+    let rows = sqlx::query_as::<_, TierPolicy>("SELECT * FROM whale_tiers ORDER BY tier")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows)
+}
+
+/// Overwrite one tier's policy.
+pub async fn update_tier_policy(pool: &PgPool, policy: &TierPolicy) -> anyhow::Result<TierPolicy> {
+    let updated = sqlx::query_as::<_, TierPolicy>(
+        r#"
+        UPDATE whale_tiers
+        SET min_win_rate = $2,
+            min_skill_score = $3,
+            min_total_trades = $4,
+            min_signal_ev = $5,
+            signal_notional_floor = $6,
+            sizing = $7,
+            size_multiplier = $8,
+            base_amount = $9,
+            updated_at = NOW()
+        WHERE tier = $1
+        RETURNING *
+        "#,
+    )
+    .bind(&policy.tier)
+    .bind(policy.min_win_rate)
+    .bind(policy.min_skill_score)
+    .bind(policy.min_total_trades)
+    .bind(policy.min_signal_ev)
+    .bind(policy.signal_notional_floor)
+    .bind(&policy.sizing)
+    .bind(policy.size_multiplier)
+    .bind(policy.base_amount)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(updated)
+}
+
+/// Record the tier a whale currently qualifies for (None = below every tier).
+pub async fn set_whale_tier(pool: &PgPool, whale_id: Uuid, tier: Option<WhaleTier>) -> anyhow::Result<()> {
+    sqlx::query("UPDATE whales SET tier = $2 WHERE id = $1")
+        .bind(whale_id)
+        .bind(tier.map(|t| t.as_str()))
+        .execute(pool)
+        .await?;
+
+    Ok(())
+}
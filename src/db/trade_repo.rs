@@ -3,9 +3,15 @@ use rust_decimal::Decimal;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::WhaleTrade;
+use crate::ingestion::csv_import::ImportedTrade;
+use crate::intelligence::scorer::HoldingPeriod;
+use crate::models::{WhaleCorrelation, WhaleTrade};
 
-/// Insert a new whale trade record.
+/// Insert a new whale trade record. Returns None when another ingestion
+/// source already reported it: a chain fill with the same log index, or —
+/// when either report lacks a log index — any trade by the whale from the
+/// same transaction in this token and side. Maker legs of one taker order
+/// carry distinct log indexes and are all recorded.
 #[allow(clippy::too_many_arguments)]
 pub async fn insert_trade(
     pool: &PgPool,
@@ -17,11 +23,22 @@ pub async fn insert_trade(
     price: Decimal,
     notional: Decimal,
     traded_at: DateTime<Utc>,
-) -> anyhow::Result<WhaleTrade> {
+    condition_id: Option<&str>,
+    tx_hash: Option<&str>,
+    fill_index: Option<i64>,
+) -> anyhow::Result<Option<WhaleTrade>> {
     let trade = sqlx::query_as::<_, WhaleTrade>(
         r#"
-        INSERT INTO whale_trades (whale_id, market_id, token_id, side, size, price, notional, traded_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        INSERT INTO whale_trades (
+            whale_id, market_id, token_id, side, size, price, notional, traded_at, condition_id, tx_hash, fill_index
+        )
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11
+        WHERE NOT EXISTS (
+            SELECT 1 FROM whale_trades
+            WHERE tx_hash = $10 AND whale_id = $1 AND token_id = $3 AND side = $4
+              AND (fill_index IS NULL OR $11::BIGINT IS NULL)
+        )
+        ON CONFLICT DO NOTHING
         RETURNING *
         "#,
     )
@@ -33,12 +50,225 @@ pub async fn insert_trade(
     .bind(price)
     .bind(notional)
     .bind(traded_at)
-    .fetch_one(pool)
+    .bind(condition_id)
+    .bind(tx_hash)
+    .bind(fill_index)
+    .fetch_optional(pool)
     .await?;
 
     Ok(trade)
 }
 
+/// Insert a trade from a CSV import unless the whale already has a trade in
+/// the same token, side, size, price and time. Returns None for duplicates.
+pub async fn insert_imported_trade(
+    pool: &PgPool,
+    whale_id: Uuid,
+    trade: &ImportedTrade,
+) -> anyhow::Result<Option<WhaleTrade>> {
+    let inserted = sqlx::query_as::<_, WhaleTrade>(
+        r#"
+        INSERT INTO whale_trades (whale_id, market_id, token_id, side, size, price, notional, tx_hash, traded_at, condition_id)
+        SELECT $1, $2, $3, $4, $5, $6, $7, $8, $9, $10
+        WHERE NOT EXISTS (
+            SELECT 1 FROM whale_trades
+            WHERE whale_id = $1 AND token_id = $3 AND side = $4
+              AND size = $5 AND price = $6 AND traded_at = $9
+        )
+        RETURNING *
+        "#,
+    )
+    .bind(whale_id)
+    .bind(&trade.market_id)
+    .bind(&trade.token_id)
+    .bind(trade.side)
+    .bind(trade.size)
+    .bind(trade.price)
+    .bind(trade.notional)
+    .bind(&trade.tx_hash)
+    .bind(trade.traded_at)
+    .bind(&trade.condition_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(inserted)
+}
+
+/// Get a single whale trade by id.
+pub async fn get_trade_by_id(pool: &PgPool, id: Uuid) -> anyhow::Result<Option<WhaleTrade>> {
+    let trade = sqlx::query_as::<_, WhaleTrade>("SELECT * FROM whale_trades WHERE id = $1")
+        .bind(id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(trade)
+}
+
+/// Whether any whale trade other than `exclude_id`, at or before `before`, in
+/// the market (by condition_id or market_id) had notional of at least `min_notional`.
+pub async fn has_prior_large_trade(
+    pool: &PgPool,
+    market_key: &str,
+    exclude_id: Uuid,
+    before: DateTime<Utc>,
+    min_notional: Decimal,
+) -> anyhow::Result<bool> {
+    let (exists,): (bool,) = sqlx::query_as(
+        r#"
+        SELECT EXISTS (
+            SELECT 1 FROM whale_trades
+            WHERE (condition_id = $1 OR market_id = $1)
+              AND id <> $2
+              AND traded_at <= $3
+              AND notional >= $4
+        )
+        "#,
+    )
+    .bind(market_key)
+    .bind(exclude_id)
+    .bind(before)
+    .bind(min_notional)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(exists)
+}
+
+/// (side, entry price, later close) for each of a whale's trades that has a
+/// recorded candle within the hour after `horizon_hours` past the trade.
+pub async fn get_entry_followups(
+    pool: &PgPool,
+    whale_id: Uuid,
+    horizon_hours: i32,
+) -> anyhow::Result<Vec<(String, Decimal, Decimal)>> {
+    let rows = sqlx::query_as::<_, (String, Decimal, Decimal)>(
+        r#"
+        SELECT t.side, t.price, c.close
+        FROM whale_trades t
+        JOIN LATERAL (
+            SELECT close FROM market_candles
+            WHERE token_id = t.token_id
+              AND bucket >= t.traded_at + make_interval(hours => $2)
+              AND bucket < t.traded_at + make_interval(hours => $2 + 1)
+            ORDER BY bucket ASC
+            LIMIT 1
+        ) c ON TRUE
+        WHERE t.whale_id = $1
+        "#,
+    )
+    .bind(whale_id)
+    .bind(horizon_hours)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Per token a whale bought: its first buy, its first sell after that, and
+/// when the market resolved, for holding-period profiling.
+pub async fn get_holding_periods(pool: &PgPool, whale_id: Uuid) -> anyhow::Result<Vec<HoldingPeriod>> {
+    let rows = sqlx::query_as::<_, HoldingPeriod>(
+        r#"
+        SELECT b.opened_at, s.exited_at, o.resolved_at
+        FROM (
+            SELECT token_id, COALESCE(condition_id, market_id) AS market_key,
+                   MIN(traded_at) AS opened_at
+            FROM whale_trades
+            WHERE whale_id = $1 AND side = 'BUY'
+            GROUP BY token_id, COALESCE(condition_id, market_id)
+        ) b
+        JOIN LATERAL (
+            SELECT MIN(traded_at) AS exited_at
+            FROM whale_trades
+            WHERE whale_id = $1 AND token_id = b.token_id AND side = 'SELL'
+              AND traded_at > b.opened_at
+        ) s ON TRUE
+        LEFT JOIN market_outcomes o
+            ON o.market_id = b.market_key AND o.outcome != 'unresolved'
+        "#,
+    )
+    .bind(whale_id)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Pairwise trade overlap between active whales since `since`, optionally
+/// restricted to `whale_ids`, keeping pairs with overlap of at least
+/// `min_overlap`. Most correlated pairs first.
+pub async fn get_whale_correlations(
+    pool: &PgPool,
+    since: DateTime<Utc>,
+    whale_ids: Option<&[Uuid]>,
+    min_overlap: Decimal,
+) -> anyhow::Result<Vec<WhaleCorrelation>> {
+    let pairs = sqlx::query_as::<_, WhaleCorrelation>(
+        r#"
+        WITH positions AS (
+            SELECT DISTINCT t.whale_id, t.token_id, t.side
+            FROM whale_trades t
+            JOIN whales w ON w.id = t.whale_id
+            WHERE t.traded_at >= $1 AND w.is_active = true
+              AND ($2::UUID[] IS NULL OR t.whale_id = ANY($2))
+        ),
+        counts AS (
+            SELECT whale_id, COUNT(*) AS n FROM positions GROUP BY whale_id
+        ),
+        shared AS (
+            SELECT a.whale_id AS whale_a, b.whale_id AS whale_b, COUNT(*) AS shared
+            FROM positions a
+            JOIN positions b ON b.token_id = a.token_id AND b.side = a.side AND a.whale_id < b.whale_id
+            GROUP BY a.whale_id, b.whale_id
+        ),
+        scored AS (
+            SELECT s.whale_a, s.whale_b, s.shared,
+                   ROUND(s.shared::NUMERIC / (ca.n + cb.n - s.shared), 4) AS overlap
+            FROM shared s
+            JOIN counts ca ON ca.whale_id = s.whale_a
+            JOIN counts cb ON cb.whale_id = s.whale_b
+        )
+        SELECT sc.whale_a, wa.address AS address_a, sc.whale_b, wb.address AS address_b,
+               sc.shared AS shared_positions, sc.overlap
+        FROM scored sc
+        JOIN whales wa ON wa.id = sc.whale_a
+        JOIN whales wb ON wb.id = sc.whale_b
+        WHERE sc.overlap >= $3
+        ORDER BY sc.overlap DESC, sc.shared DESC
+        LIMIT 500
+        "#,
+    )
+    .bind(since)
+    .bind(whale_ids)
+    .bind(min_overlap)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(pairs)
+}
+
+/// Net size a whale holds in a token according to its recorded trades
+/// (buys minus sells). Only covers trades seen since tracking began.
+pub async fn get_net_token_size(
+    pool: &PgPool,
+    whale_id: Uuid,
+    token_id: &str,
+) -> anyhow::Result<Decimal> {
+    let (net,): (Decimal,) = sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(CASE WHEN side = 'BUY' THEN size ELSE -size END), 0)
+        FROM whale_trades
+        WHERE whale_id = $1 AND token_id = $2
+        "#,
+    )
+    .bind(whale_id)
+    .bind(token_id)
+    .fetch_one(pool)
+    .await?;
+
+    Ok(net)
+}
+
 /// Get all trades for a whale, ordered by time descending.
 pub async fn get_trades_by_whale(
     pool: &PgPool,
@@ -54,6 +284,78 @@ pub async fn get_trades_by_whale(
     Ok(trades)
 }
 
+/// A whale trade in a resolved market, with the market's outcome.
+#[derive(Debug, Clone, sqlx::FromRow)]
+pub struct ResolvedTrade {
+    pub whale_id: Uuid,
+    pub side: String,
+    pub price: Decimal,
+    pub notional: Decimal,
+    pub outcome: String,
+    pub traded_at: DateTime<Utc>,
+}
+
+/// Every trade in a resolved market, joined to its outcome in one query, for
+/// bulk rescoring. Limited to `whale_ids` when given; ordered by whale, newest
+/// trade first (the order `get_trades_by_whale` scores in).
+pub async fn get_resolved_trades(
+    pool: &PgPool,
+    whale_ids: Option<&[Uuid]>,
+) -> anyhow::Result<Vec<ResolvedTrade>> {
+    let trades = sqlx::query_as::<_, ResolvedTrade>(
+        r#"
+        SELECT t.whale_id, t.side, t.price, t.notional, o.outcome, t.traded_at
+        FROM whale_trades t
+        JOIN market_outcomes o ON o.market_id = COALESCE(t.condition_id, t.market_id)
+        WHERE o.outcome != 'unresolved'
+          AND ($1::uuid[] IS NULL OR t.whale_id = ANY($1))
+        ORDER BY t.whale_id, t.traded_at DESC
+        "#,
+    )
+    .bind(whale_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(trades)
+}
+
+/// Questions of the active markets each whale has traded, with how many of
+/// its trades were in each.
+pub async fn get_whale_market_questions(
+    pool: &PgPool,
+    whale_ids: &[Uuid],
+) -> anyhow::Result<Vec<(Uuid, String, i64)>> {
+    let rows = sqlx::query_as::<_, (Uuid, String, i64)>(
+        r#"
+        SELECT t.whale_id, m.question, COUNT(*)
+        FROM whale_trades t
+        JOIN active_markets m ON m.condition_id = COALESCE(t.condition_id, t.market_id)
+        WHERE t.whale_id = ANY($1)
+        GROUP BY t.whale_id, m.question
+        "#,
+    )
+    .bind(whale_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
+/// Each whale's earliest recorded trade.
+pub async fn get_first_trade_times(
+    pool: &PgPool,
+    whale_ids: &[Uuid],
+) -> anyhow::Result<Vec<(Uuid, DateTime<Utc>)>> {
+    let rows = sqlx::query_as::<_, (Uuid, DateTime<Utc>)>(
+        "SELECT whale_id, MIN(traded_at) FROM whale_trades WHERE whale_id = ANY($1) GROUP BY whale_id",
+    )
+    .bind(whale_ids)
+    .fetch_all(pool)
+    .await?;
+
+    Ok(rows)
+}
+
 /// Get the N most recent trades for a whale.
 pub async fn get_recent_trades(
     pool: &PgPool,
@@ -84,21 +386,22 @@ pub async fn count_trades(pool: &PgPool, whale_id: Uuid) -> anyhow::Result<i64>
 }
 
 /// Get trades within a time window for a whale in a specific market.
+/// `market_key` is matched against condition_id, falling back to market_id.
 pub async fn get_trades_in_window(
     pool: &PgPool,
     whale_id: Uuid,
-    market_id: &str,
+    market_key: &str,
     since: DateTime<Utc>,
 ) -> anyhow::Result<Vec<WhaleTrade>> {
     let trades = sqlx::query_as::<_, WhaleTrade>(
         r#"
         SELECT * FROM whale_trades
-        WHERE whale_id = $1 AND market_id = $2 AND traded_at >= $3
+        WHERE whale_id = $1 AND COALESCE(condition_id, market_id) = $2 AND traded_at >= $3
         ORDER BY traded_at DESC
         "#,
     )
     .bind(whale_id)
-    .bind(market_id)
+    .bind(market_key)
     .bind(since)
     .fetch_all(pool)
     .await?;
@@ -3,7 +3,8 @@ use rust_decimal::Decimal;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::models::Whale;
+use crate::intelligence::scorer::{HoldingProfile, WalletScore};
+use crate::models::{ExitStyle, Whale, WhaleCopyPerformance};
 
 /// Insert a new whale or return existing one by address.
 pub async fn upsert_whale(pool: &PgPool, address: &str) -> anyhow::Result<Whale> {
@@ -34,6 +35,16 @@ pub async fn get_whale_by_address(pool: &PgPool, address: &str) -> anyhow::Resul
     Ok(whale)
 }
 
+/// Fetch a whale by id.
+pub async fn get_whale_by_id(pool: &PgPool, whale_id: Uuid) -> anyhow::Result<Option<Whale>> {
+    let whale = sqlx::query_as::<_, Whale>("SELECT * FROM whales WHERE id = $1")
+        .bind(whale_id)
+        .fetch_optional(pool)
+        .await?;
+
+    Ok(whale)
+}
+
 /// Fetch all active whales.
 pub async fn get_active_whales(pool: &PgPool) -> anyhow::Result<Vec<Whale>> {
     let whales = sqlx::query_as::<_, Whale>(
@@ -45,12 +56,33 @@ pub async fn get_active_whales(pool: &PgPool) -> anyhow::Result<Vec<Whale>> {
     Ok(whales)
 }
 
-/// Update scoring metrics for a whale.
+/// Fetch whales with an operator classification override (expired or not),
+/// used as training labels for the wallet classifier.
+pub async fn get_labeled_whales(pool: &PgPool) -> anyhow::Result<Vec<Whale>> {
+    let whales = sqlx::query_as::<_, Whale>(
+        "SELECT * FROM whales WHERE classification_override IS NOT NULL ORDER BY updated_at DESC",
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(whales)
+}
+
+/// Update scoring metrics for a whale. Sortino/Calmar, drawdown, the win/loss
+/// ratios and skill score are None for estimated scores, which have no trade
+/// history to derive them from.
 #[allow(clippy::too_many_arguments)]
 pub async fn update_whale_scores(
     pool: &PgPool,
     whale_id: Uuid,
     sharpe_ratio: Decimal,
+    sortino_ratio: Option<Decimal>,
+    calmar_ratio: Option<Decimal>,
+    max_drawdown: Option<Decimal>,
+    max_drawdown_pct: Option<Decimal>,
+    profit_factor: Option<Decimal>,
+    payoff_ratio: Option<Decimal>,
+    skill_score: Option<Decimal>,
     win_rate: Decimal,
     kelly_fraction: Decimal,
     expected_value: Decimal,
@@ -66,6 +98,13 @@ pub async fn update_whale_scores(
             expected_value = $5,
             total_trades = $6,
             total_pnl = $7,
+            sortino_ratio = $8,
+            calmar_ratio = $9,
+            max_drawdown = $10,
+            max_drawdown_pct = $11,
+            profit_factor = $12,
+            payoff_ratio = $13,
+            skill_score = $14,
             updated_at = NOW()
         WHERE id = $1
         "#,
@@ -77,6 +116,114 @@ pub async fn update_whale_scores(
     .bind(expected_value)
     .bind(total_trades)
     .bind(total_pnl)
+    .bind(sortino_ratio)
+    .bind(calmar_ratio)
+    .bind(max_drawdown)
+    .bind(max_drawdown_pct)
+    .bind(profit_factor)
+    .bind(payoff_ratio)
+    .bind(skill_score)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Write many whales' scores in a single statement. Used by the batch
+/// rescoring job; sets the same columns as `update_whale_scores`.
+pub async fn update_whale_scores_bulk(pool: &PgPool, scores: &[(Uuid, WalletScore)]) -> anyhow::Result<u64> {
+    if scores.is_empty() {
+        return Ok(0);
+    }
+    let ids: Vec<Uuid> = scores.iter().map(|(id, _)| *id).collect();
+    let column = |f: fn(&WalletScore) -> Decimal| scores.iter().map(|(_, s)| f(s)).collect::<Vec<_>>();
+    let optional = |f: fn(&WalletScore) -> Option<Decimal>| scores.iter().map(|(_, s)| f(s)).collect::<Vec<_>>();
+
+    let result = sqlx::query(
+        r#"
+        UPDATE whales w
+        SET sharpe_ratio = s.sharpe_ratio,
+            win_rate = s.win_rate,
+            kelly_fraction = s.kelly_fraction,
+            expected_value = s.expected_value,
+            total_trades = s.total_trades,
+            total_pnl = s.total_pnl,
+            sortino_ratio = s.sortino_ratio,
+            calmar_ratio = s.calmar_ratio,
+            max_drawdown = s.max_drawdown,
+            max_drawdown_pct = s.max_drawdown_pct,
+            profit_factor = s.profit_factor,
+            payoff_ratio = s.payoff_ratio,
+            skill_score = s.skill_score,
+            updated_at = NOW()
+        FROM UNNEST(
+            $1::uuid[], $2::numeric[], $3::numeric[], $4::numeric[], $5::numeric[], $6::int[], $7::numeric[],
+            $8::numeric[], $9::numeric[], $10::numeric[], $11::numeric[], $12::numeric[], $13::numeric[], $14::numeric[]
+        ) AS s(id, sharpe_ratio, win_rate, kelly_fraction, expected_value, total_trades, total_pnl,
+               sortino_ratio, calmar_ratio, max_drawdown, max_drawdown_pct, profit_factor, payoff_ratio, skill_score)
+        WHERE w.id = s.id
+        "#,
+    )
+    .bind(&ids)
+    .bind(column(|s| s.sharpe_ratio))
+    .bind(column(|s| s.win_rate))
+    .bind(column(|s| s.kelly_fraction))
+    .bind(column(|s| s.expected_value))
+    .bind(scores.iter().map(|(_, s)| s.total_trades).collect::<Vec<_>>())
+    .bind(column(|s| s.total_pnl))
+    .bind(column(|s| s.sortino_ratio))
+    .bind(column(|s| s.calmar_ratio))
+    .bind(column(|s| s.max_drawdown))
+    .bind(column(|s| s.max_drawdown_pct))
+    .bind(optional(|s| s.profit_factor))
+    .bind(optional(|s| s.payoff_ratio))
+    .bind(optional(|s| s.skill_score))
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Store a whale's entry edge and the number of entries it was measured over.
+pub async fn update_entry_edge(
+    pool: &PgPool,
+    whale_id: Uuid,
+    entry_edge: Decimal,
+    samples: i32,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE whales SET entry_edge = $2, entry_edge_samples = $3, updated_at = NOW() WHERE id = $1",
+    )
+    .bind(whale_id)
+    .bind(entry_edge)
+    .bind(samples)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Store a whale's holding profile. `exit_style` is None until the profile
+/// covers enough positions, so its copies keep mirroring its exits.
+pub async fn update_holding_profile(
+    pool: &PgPool,
+    whale_id: Uuid,
+    profile: &HoldingProfile,
+    exit_style: Option<ExitStyle>,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE whales
+        SET avg_hold_hours = $2, early_exit_ratio = $3, hold_profile_samples = $4,
+            exit_style = $5, updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(whale_id)
+    .bind(profile.avg_hold_hours)
+    .bind(profile.early_exit_ratio)
+    .bind(profile.samples as i32)
+    .bind(exit_style.map(|s| s.as_str()))
     .execute(pool)
     .await?;
 
@@ -157,3 +304,261 @@ pub async fn touch_whale_last_trade(
 
     Ok(())
 }
+
+/// Summarize our copy orders and resulting positions triggered by a whale.
+///
+/// `since` limits the window to orders placed after it (all time when `None`).
+pub async fn get_whale_copy_performance(
+    pool: &PgPool,
+    whale_id: Uuid,
+    since: Option<DateTime<Utc>>,
+) -> anyhow::Result<WhaleCopyPerformance> {
+    #[allow(clippy::type_complexity)]
+    let (
+        total_orders,
+        filled_orders,
+        failed_orders,
+        filled_notional,
+        avg_slippage,
+        avg_copy_lag,
+        avg_latency_secs,
+    ): (
+        i64,
+        i64,
+        i64,
+        Option<Decimal>,
+        Option<Decimal>,
+        Option<Decimal>,
+        Option<Decimal>,
+    ) = sqlx::query_as(
+        r#"
+        SELECT
+            COUNT(*),
+            COUNT(*) FILTER (WHERE o.status = 'filled'),
+            COUNT(*) FILTER (WHERE o.status IN ('failed', 'cancelled')),
+            SUM(o.size * COALESCE(o.fill_price, o.target_price)) FILTER (WHERE o.status = 'filled'),
+            AVG(o.slippage) FILTER (WHERE o.status = 'filled'),
+            AVG(o.copy_lag) FILTER (WHERE o.status = 'filled'),
+            AVG(EXTRACT(EPOCH FROM (o.placed_at - t.traded_at)))::NUMERIC
+        FROM copy_orders o
+        JOIN whale_trades t ON t.id = o.whale_trade_id
+        WHERE t.whale_id = $1 AND ($2::TIMESTAMPTZ IS NULL OR o.placed_at >= $2)
+        "#,
+    )
+    .bind(whale_id)
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    // Positions are aggregated per token, so attribute each one to this whale
+    // by its share of the filled BUY size on that token. Settled positions
+    // already carry an exact attribution and are counted from the ledger below.
+    let (closed_positions, winning_positions, realized_pnl, unrealized_pnl): (
+        i64,
+        i64,
+        Option<Decimal>,
+        Option<Decimal>,
+    ) = sqlx::query_as(
+        r#"
+        WITH whale_fills AS (
+            SELECT o.token_id, SUM(o.size) AS whale_size
+            FROM copy_orders o
+            JOIN whale_trades t ON t.id = o.whale_trade_id
+            WHERE t.whale_id = $1 AND o.status = 'filled' AND o.side = 'BUY'
+              AND ($2::TIMESTAMPTZ IS NULL OR o.placed_at >= $2)
+            GROUP BY o.token_id
+        ),
+        all_fills AS (
+            SELECT token_id, SUM(size) AS total_size
+            FROM copy_orders
+            WHERE status = 'filled' AND side = 'BUY'
+              AND token_id IN (SELECT token_id FROM whale_fills)
+            GROUP BY token_id
+        ),
+        settled AS (
+            SELECT DISTINCT position_id FROM pnl_attributions
+        )
+        SELECT
+            COUNT(*) FILTER (WHERE p.status = 'closed' AND s.position_id IS NULL),
+            COUNT(*) FILTER (WHERE p.status = 'closed' AND s.position_id IS NULL AND p.realized_pnl > 0),
+            SUM(p.realized_pnl * wf.whale_size / af.total_size)
+                FILTER (WHERE p.status = 'closed' AND s.position_id IS NULL),
+            SUM(p.unrealized_pnl * wf.whale_size / af.total_size) FILTER (WHERE p.status = 'open')
+        FROM positions p
+        JOIN whale_fills wf ON wf.token_id = p.token_id
+        JOIN all_fills af ON af.token_id = p.token_id
+        LEFT JOIN settled s ON s.position_id = p.id
+        WHERE $2::TIMESTAMPTZ IS NULL OR p.opened_at >= $2 OR p.closed_at >= $2
+        "#,
+    )
+    .bind(whale_id)
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    // Settled positions attributed to this whale at resolution
+    let (settled_positions, settled_wins, settled_pnl): (i64, i64, Option<Decimal>) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*), COUNT(*) FILTER (WHERE pnl > 0), SUM(pnl)
+        FROM (
+            SELECT a.position_id, SUM(a.realized_pnl) AS pnl
+            FROM pnl_attributions a
+            JOIN positions p ON p.id = a.position_id
+            WHERE a.whale_id = $1 AND ($2::TIMESTAMPTZ IS NULL OR p.opened_at >= $2 OR p.closed_at >= $2)
+            GROUP BY a.position_id
+        ) per_position
+        "#,
+    )
+    .bind(whale_id)
+    .bind(since)
+    .fetch_one(pool)
+    .await?;
+
+    let closed_positions = closed_positions + settled_positions;
+    let winning_positions = winning_positions + settled_wins;
+    let realized_pnl = realized_pnl.unwrap_or(Decimal::ZERO) + settled_pnl.unwrap_or(Decimal::ZERO);
+
+    let copy_win_rate = (closed_positions > 0)
+        .then(|| Decimal::from(winning_positions) / Decimal::from(closed_positions));
+
+    Ok(WhaleCopyPerformance {
+        whale_id,
+        total_orders,
+        filled_orders,
+        failed_orders,
+        filled_notional: filled_notional.unwrap_or(Decimal::ZERO),
+        closed_positions,
+        winning_positions,
+        copy_win_rate,
+        realized_pnl: realized_pnl.round_dp(6),
+        unrealized_pnl: unrealized_pnl.unwrap_or(Decimal::ZERO).round_dp(6),
+        avg_slippage,
+        avg_copy_lag,
+        avg_latency_secs: avg_latency_secs.map(|d| d.round_dp(3)),
+    })
+}
+
+/// Whales that have copy orders and are not currently copy-paused.
+pub async fn get_copied_whales(pool: &PgPool) -> anyhow::Result<Vec<Whale>> {
+    let whales = sqlx::query_as::<_, Whale>(
+        r#"
+        SELECT * FROM whales w
+        WHERE w.copy_paused_at IS NULL
+          AND EXISTS (
+              SELECT 1 FROM copy_orders o
+              JOIN whale_trades t ON t.id = o.whale_trade_id
+              WHERE t.whale_id = w.id
+          )
+        "#,
+    )
+    .fetch_all(pool)
+    .await?;
+
+    Ok(whales)
+}
+
+/// Stop emitting copy signals for a whale (its trades are still tracked).
+pub async fn pause_whale_copying(pool: &PgPool, whale_id: Uuid, reason: &str) -> anyhow::Result<()> {
+    sqlx::query(
+        "UPDATE whales SET copy_paused_at = NOW(), copy_pause_reason = $2, updated_at = NOW() WHERE id = $1",
+    )
+    .bind(whale_id)
+    .bind(reason)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Re-enable copying; the copy-loss rule only looks at copies made after this,
+/// and leaderboard drift is measured from the last observed leaderboard values.
+pub async fn resume_whale_copying(pool: &PgPool, whale_id: Uuid) -> anyhow::Result<Option<Whale>> {
+    let whale = sqlx::query_as::<_, Whale>(
+        r#"
+        UPDATE whales
+        SET copy_paused_at = NULL, copy_pause_reason = NULL, copy_resumed_at = NOW(),
+            seed_leaderboard_pnl = COALESCE(leaderboard_pnl, seed_leaderboard_pnl),
+            seed_leaderboard_volume = COALESCE(leaderboard_volume, seed_leaderboard_volume),
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(whale_id)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(whale)
+}
+
+/// Record the leaderboard drift baseline, unless one is already set.
+pub async fn set_leaderboard_baseline(
+    pool: &PgPool,
+    whale_id: Uuid,
+    pnl: Decimal,
+    volume: Decimal,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE whales
+        SET seed_leaderboard_pnl = COALESCE(seed_leaderboard_pnl, $2),
+            seed_leaderboard_volume = COALESCE(seed_leaderboard_volume, $3),
+            updated_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(whale_id)
+    .bind(pnl)
+    .bind(volume)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Store the leaderboard PnL/volume seen by the latest drift check.
+pub async fn record_leaderboard_check(
+    pool: &PgPool,
+    whale_id: Uuid,
+    pnl: Decimal,
+    volume: Decimal,
+) -> anyhow::Result<()> {
+    sqlx::query(
+        r#"
+        UPDATE whales
+        SET leaderboard_pnl = $2, leaderboard_volume = $3, leaderboard_checked_at = NOW()
+        WHERE id = $1
+        "#,
+    )
+    .bind(whale_id)
+    .bind(pnl)
+    .bind(volume)
+    .execute(pool)
+    .await?;
+
+    Ok(())
+}
+
+/// Set (or clear, with `None`) the operator classification override.
+pub async fn set_classification_override(
+    pool: &PgPool,
+    whale_id: Uuid,
+    classification: Option<&str>,
+    until: Option<DateTime<Utc>>,
+) -> anyhow::Result<Option<Whale>> {
+    let whale = sqlx::query_as::<_, Whale>(
+        r#"
+        UPDATE whales
+        SET classification_override = $2, classification_override_until = $3, updated_at = NOW()
+        WHERE id = $1
+        RETURNING *
+        "#,
+    )
+    .bind(whale_id)
+    .bind(classification)
+    .bind(until)
+    .fetch_optional(pool)
+    .await?;
+
+    Ok(whale)
+}
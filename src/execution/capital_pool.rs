@@ -10,6 +10,9 @@ use uuid::Uuid;
 /// When an order is placed, capital is *reserved* so that concurrent signals
 /// cannot double-spend the same USDC.  On fill the reservation is confirmed
 /// (capital is now in a position); on failure/cancel it is released.
+///
+/// A reserve floor — a fraction of the pool's capital — is never handed out,
+/// so there is always USDC left for exit fees and stop-loss exits.
 #[derive(Clone)]
 pub struct CapitalPool {
     inner: Arc<Mutex<CapitalInner>>,
@@ -20,6 +23,22 @@ struct CapitalInner {
     total_balance: Decimal,
     /// Capital reserved for in-flight orders (order_id → amount).
     reservations: HashMap<Uuid, Decimal>,
+    /// High-water mark of `total_balance`; the floor is a fraction of this,
+    /// so it does not shrink as capital moves into positions.
+    capital_base: Decimal,
+    /// Fraction of `capital_base` that can never be reserved.
+    reserve_floor_pct: Decimal,
+}
+
+impl CapitalInner {
+    fn reserve_floor(&self) -> Decimal {
+        self.capital_base * self.reserve_floor_pct
+    }
+
+    fn available(&self) -> Decimal {
+        let reserved: Decimal = self.reservations.values().copied().sum();
+        (self.total_balance - reserved - self.reserve_floor()).max(Decimal::ZERO)
+    }
 }
 
 impl CapitalPool {
@@ -29,28 +48,43 @@ impl CapitalPool {
             inner: Arc::new(Mutex::new(CapitalInner {
                 total_balance: initial_balance,
                 reservations: HashMap::new(),
+                capital_base: initial_balance,
+                reserve_floor_pct: Decimal::ZERO,
             })),
         }
     }
 
-    /// Available capital = total_balance − sum(reservations).
+    /// Available capital = total_balance − sum(reservations) − reserve floor.
     pub async fn available(&self) -> Decimal {
-        let inner = self.inner.lock().await;
-        let reserved: Decimal = inner.reservations.values().copied().sum();
-        (inner.total_balance - reserved).max(Decimal::ZERO)
+        self.inner.lock().await.available()
+    }
+
+    /// Set the fraction of capital kept back from reservations (0 = none).
+    pub async fn set_reserve_floor_pct(&self, pct: Decimal) {
+        self.inner.lock().await.reserve_floor_pct = pct.max(Decimal::ZERO);
+    }
+
+    /// USDC held back for exits and fees.
+    pub async fn reserve_floor(&self) -> Decimal {
+        self.inner.lock().await.reserve_floor()
+    }
+
+    /// Total balance including capital currently reserved for in-flight orders.
+    pub async fn total_balance(&self) -> Decimal {
+        self.inner.lock().await.total_balance
     }
 
     /// Reserve capital for a pending order.  Returns `false` if insufficient.
     pub async fn reserve(&self, order_id: Uuid, amount: Decimal) -> bool {
         let mut inner = self.inner.lock().await;
-        let reserved: Decimal = inner.reservations.values().copied().sum();
-        let available = (inner.total_balance - reserved).max(Decimal::ZERO);
+        let available = inner.available();
 
         if amount > available {
             tracing::warn!(
                 order_id = %order_id,
                 required = %amount,
                 available = %available,
+                reserve_floor = %inner.reserve_floor(),
                 "Capital pool: insufficient funds to reserve"
             );
             return false;
@@ -93,10 +127,28 @@ impl CapitalPool {
         }
     }
 
+    /// Confirm the filled fraction of a reservation and release the rest
+    /// (order cancelled after a partial fill).
+    pub async fn confirm_partial(&self, order_id: &Uuid, filled_fraction: Decimal) {
+        let mut inner = self.inner.lock().await;
+        if let Some(amount) = inner.reservations.remove(order_id) {
+            let filled = amount * filled_fraction.clamp(Decimal::ZERO, Decimal::ONE);
+            inner.total_balance -= filled;
+            tracing::debug!(
+                order_id = %order_id,
+                filled = %filled,
+                released = %(amount - filled),
+                new_balance = %inner.total_balance,
+                "Capital pool: confirmed partial fill, remainder released"
+            );
+        }
+    }
+
     /// Return capital when a position is closed (dry-run exits, SL/TP, etc.).
     pub async fn return_capital(&self, amount: Decimal) {
         let mut inner = self.inner.lock().await;
         inner.total_balance += amount;
+        inner.capital_base = inner.capital_base.max(inner.total_balance);
         tracing::info!(
             amount = %amount,
             new_balance = %inner.total_balance,
@@ -110,6 +162,7 @@ impl CapitalPool {
         let mut inner = self.inner.lock().await;
         let old = inner.total_balance;
         inner.total_balance = external_balance;
+        inner.capital_base = inner.capital_base.max(external_balance);
         tracing::info!(
             old_balance = %old,
             new_balance = %external_balance,
@@ -152,6 +205,35 @@ mod tests {
         assert_eq!(pool.available().await, Decimal::from(700));
     }
 
+    #[tokio::test]
+    async fn test_confirm_partial_releases_remainder() {
+        let pool = CapitalPool::new(Decimal::from(1000));
+        let id = Uuid::new_v4();
+
+        assert!(pool.reserve(id, Decimal::from(400)).await);
+        pool.confirm_partial(&id, Decimal::new(25, 2)).await;
+
+        // A quarter filled: 100 spent, the other 300 available again
+        assert_eq!(pool.available().await, Decimal::from(900));
+    }
+
+    #[tokio::test]
+    async fn test_reserve_floor_is_untouchable() {
+        let pool = CapitalPool::new(Decimal::from(1000));
+        pool.set_reserve_floor_pct(Decimal::new(10, 2)).await;
+        assert_eq!(pool.available().await, Decimal::from(900));
+
+        // Cannot reserve into the floor
+        assert!(!pool.reserve(Uuid::new_v4(), Decimal::from(950)).await);
+
+        // Filling positions doesn't shrink the floor
+        let id = Uuid::new_v4();
+        assert!(pool.reserve(id, Decimal::from(900)).await);
+        pool.confirm(&id).await;
+        assert_eq!(pool.reserve_floor().await, Decimal::from(100));
+        assert_eq!(pool.available().await, Decimal::ZERO);
+    }
+
     #[tokio::test]
     async fn test_sync_balance() {
         let pool = CapitalPool::new(Decimal::from(1000));
@@ -0,0 +1,224 @@
+use std::collections::VecDeque;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use metrics::gauge;
+use serde::Serialize;
+use tokio::sync::Mutex;
+
+/// Trips after too many order execution failures in a short window.
+///
+/// While tripped the copy engine skips all signals. The breaker closes again
+/// on its own once the cool-off elapses, or immediately on a manual resume.
+#[derive(Clone)]
+pub struct CircuitBreaker {
+    config: CircuitBreakerConfig,
+    inner: Arc<Mutex<BreakerInner>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct CircuitBreakerConfig {
+    /// Failures within `window` that trip the breaker (0 = disabled).
+    pub max_failures: usize,
+    pub window: Duration,
+    /// How long the breaker stays open before auto-resetting.
+    pub cooldown: Duration,
+}
+
+impl Default for CircuitBreakerConfig {
+    fn default() -> Self {
+        Self {
+            max_failures: 5,
+            window: Duration::minutes(10),
+            cooldown: Duration::minutes(30),
+        }
+    }
+}
+
+struct BreakerInner {
+    /// Timestamps of recent failures, oldest first.
+    failures: VecDeque<DateTime<Utc>>,
+    tripped_at: Option<DateTime<Utc>>,
+}
+
+/// Snapshot of the breaker state for the API.
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitBreakerStatus {
+    pub tripped: bool,
+    pub recent_failures: usize,
+    pub max_failures: usize,
+    pub tripped_at: Option<DateTime<Utc>>,
+    pub resets_at: Option<DateTime<Utc>>,
+}
+
+impl CircuitBreaker {
+    pub fn new(config: CircuitBreakerConfig) -> Self {
+        Self {
+            config,
+            inner: Arc::new(Mutex::new(BreakerInner {
+                failures: VecDeque::new(),
+                tripped_at: None,
+            })),
+        }
+    }
+
+    pub fn config(&self) -> &CircuitBreakerConfig {
+        &self.config
+    }
+
+    /// Record a failed execution. Returns `true` if this failure tripped the breaker.
+    pub async fn record_failure(&self) -> bool {
+        self.record_failure_at(Utc::now()).await
+    }
+
+    /// True while the breaker is open. Auto-resets once the cool-off has elapsed.
+    pub async fn is_tripped(&self) -> bool {
+        self.is_tripped_at(Utc::now()).await
+    }
+
+    /// Close the breaker and forget past failures (manual resume).
+    pub async fn reset(&self) {
+        let mut inner = self.inner.lock().await;
+        inner.failures.clear();
+        inner.tripped_at = None;
+        gauge!("circuit_breaker_tripped").set(0.0);
+    }
+
+    pub async fn status(&self) -> CircuitBreakerStatus {
+        let now = Utc::now();
+        // Refresh the tripped state first so an expired cool-off reads as closed
+        self.is_tripped_at(now).await;
+
+        let mut inner = self.inner.lock().await;
+        prune(&mut inner.failures, now - self.config.window);
+
+        CircuitBreakerStatus {
+            tripped: inner.tripped_at.is_some(),
+            recent_failures: inner.failures.len(),
+            max_failures: self.config.max_failures,
+            tripped_at: inner.tripped_at,
+            resets_at: inner.tripped_at.map(|t| t + self.config.cooldown),
+        }
+    }
+
+    async fn record_failure_at(&self, now: DateTime<Utc>) -> bool {
+        if self.config.max_failures == 0 {
+            return false;
+        }
+
+        let mut inner = self.inner.lock().await;
+        if inner.tripped_at.is_some() {
+            return false;
+        }
+
+        inner.failures.push_back(now);
+        prune(&mut inner.failures, now - self.config.window);
+
+        if inner.failures.len() >= self.config.max_failures {
+            inner.tripped_at = Some(now);
+            gauge!("circuit_breaker_tripped").set(1.0);
+            tracing::error!(
+                failures = inner.failures.len(),
+                window_mins = self.config.window.num_minutes(),
+                cooldown_mins = self.config.cooldown.num_minutes(),
+                "Circuit breaker TRIPPED — live execution paused"
+            );
+            return true;
+        }
+
+        false
+    }
+
+    async fn is_tripped_at(&self, now: DateTime<Utc>) -> bool {
+        let mut inner = self.inner.lock().await;
+        match inner.tripped_at {
+            Some(t) if now - t >= self.config.cooldown => {
+                inner.tripped_at = None;
+                inner.failures.clear();
+                gauge!("circuit_breaker_tripped").set(0.0);
+                tracing::info!("Circuit breaker cool-off elapsed — execution resumed");
+                false
+            }
+            Some(_) => true,
+            None => false,
+        }
+    }
+}
+
+fn prune(failures: &mut VecDeque<DateTime<Utc>>, cutoff: DateTime<Utc>) {
+    while failures.front().is_some_and(|t| *t < cutoff) {
+        failures.pop_front();
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn breaker() -> CircuitBreaker {
+        CircuitBreaker::new(CircuitBreakerConfig {
+            max_failures: 3,
+            window: Duration::minutes(5),
+            cooldown: Duration::minutes(15),
+        })
+    }
+
+    #[tokio::test]
+    async fn test_trips_after_max_failures_in_window() {
+        let cb = breaker();
+        let t0 = Utc::now();
+
+        assert!(!cb.record_failure_at(t0).await);
+        assert!(!cb.record_failure_at(t0 + Duration::minutes(1)).await);
+        assert!(cb.record_failure_at(t0 + Duration::minutes(2)).await);
+        assert!(cb.is_tripped_at(t0 + Duration::minutes(3)).await);
+    }
+
+    #[tokio::test]
+    async fn test_failures_outside_window_are_forgotten() {
+        let cb = breaker();
+        let t0 = Utc::now();
+
+        cb.record_failure_at(t0).await;
+        cb.record_failure_at(t0 + Duration::minutes(1)).await;
+        // First two have aged out by now
+        assert!(!cb.record_failure_at(t0 + Duration::minutes(10)).await);
+        assert!(!cb.is_tripped_at(t0 + Duration::minutes(10)).await);
+    }
+
+    #[tokio::test]
+    async fn test_auto_reset_after_cooldown() {
+        let cb = breaker();
+        let t0 = Utc::now();
+
+        for i in 0..3 {
+            cb.record_failure_at(t0 + Duration::seconds(i)).await;
+        }
+        assert!(cb.is_tripped_at(t0 + Duration::minutes(14)).await);
+        assert!(!cb.is_tripped_at(t0 + Duration::minutes(16)).await);
+    }
+
+    #[tokio::test]
+    async fn test_manual_reset_and_disabled() {
+        let cb = breaker();
+        let t0 = Utc::now();
+
+        for i in 0..3 {
+            cb.record_failure_at(t0 + Duration::seconds(i)).await;
+        }
+        cb.reset().await;
+        assert!(!cb.is_tripped_at(t0 + Duration::minutes(1)).await);
+
+        let disabled = CircuitBreaker::new(CircuitBreakerConfig {
+            max_failures: 0,
+            ..CircuitBreakerConfig::default()
+        });
+        for _ in 0..10 {
+            assert!(!disabled.record_failure_at(t0).await);
+        }
+    }
+}
@@ -0,0 +1,143 @@
+use rust_decimal::Decimal;
+use serde::Serialize;
+use uuid::Uuid;
+
+use crate::models::Position;
+use crate::polymarket::types::ApiOrderBookLevel;
+
+/// What closing a position at market would cost right now: the position's
+/// size sold into the bids, level by level.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ClosePreview {
+    pub position_id: Uuid,
+    pub token_id: String,
+    pub size: Decimal,
+    pub avg_entry_price: Decimal,
+    pub best_bid: Option<Decimal>,
+    /// Volume-weighted price of the fillable part. None with no bids.
+    pub expected_fill_price: Option<Decimal>,
+    /// Expected fill price below the best bid, in percent.
+    pub expected_slippage_pct: Option<Decimal>,
+    pub fillable_size: Decimal,
+    /// Size the book is too thin to absorb.
+    pub unfilled_size: Decimal,
+    pub levels_consumed: usize,
+    /// Gross USDC from the fillable part.
+    pub proceeds: Decimal,
+    /// Fees and gas at `cost_rate` of proceeds.
+    pub estimated_fees: Decimal,
+    /// Recent fee + gas rate per USDC filled.
+    pub cost_rate: Decimal,
+    /// Proceeds less fees and the entry cost of the fillable part.
+    pub expected_realized_pnl: Decimal,
+}
+
+/// Walk `bids` from the best price down, selling up to `size`.
+/// Returns (filled size, gross proceeds, levels touched).
+pub fn walk_bids(bids: &[ApiOrderBookLevel], size: Decimal) -> (Decimal, Decimal, usize) {
+    let mut levels: Vec<&ApiOrderBookLevel> = bids.iter().filter(|l| l.size > Decimal::ZERO).collect();
+    levels.sort_by_key(|l| std::cmp::Reverse(l.price));
+
+    let mut remaining = size;
+    let mut proceeds = Decimal::ZERO;
+    let mut consumed = 0;
+    for level in levels {
+        if remaining <= Decimal::ZERO {
+            break;
+        }
+        let take = remaining.min(level.size);
+        proceeds += take * level.price;
+        remaining -= take;
+        consumed += 1;
+    }
+
+    (size - remaining.max(Decimal::ZERO), proceeds, consumed)
+}
+
+/// Price closing `pos` against the current `bids`, charging `cost_rate` of
+/// proceeds for fees and gas.
+pub fn preview_close(pos: &Position, bids: &[ApiOrderBookLevel], cost_rate: Decimal) -> ClosePreview {
+    let best_bid = bids.iter().filter(|l| l.size > Decimal::ZERO).map(|l| l.price).max();
+    let (fillable_size, proceeds, levels_consumed) = walk_bids(bids, pos.size);
+
+    let expected_fill_price = (fillable_size > Decimal::ZERO).then(|| proceeds / fillable_size);
+    let expected_slippage_pct = match (best_bid, expected_fill_price) {
+        (Some(bid), Some(fill)) if bid > Decimal::ZERO => Some((bid - fill) / bid * Decimal::ONE_HUNDRED),
+        _ => None,
+    };
+    let estimated_fees = proceeds * cost_rate;
+
+    ClosePreview {
+        position_id: pos.id,
+        token_id: pos.token_id.clone(),
+        size: pos.size,
+        avg_entry_price: pos.avg_entry_price,
+        best_bid,
+        expected_fill_price,
+        expected_slippage_pct,
+        fillable_size,
+        unfilled_size: pos.size - fillable_size,
+        levels_consumed,
+        proceeds,
+        estimated_fees,
+        cost_rate,
+        expected_realized_pnl: proceeds - estimated_fees - fillable_size * pos.avg_entry_price,
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn level(price: i64, size: i64) -> ApiOrderBookLevel {
+        ApiOrderBookLevel {
+            price: Decimal::new(price, 2),
+            size: Decimal::from(size),
+        }
+    }
+
+    #[test]
+    fn test_walk_bids_across_levels() {
+        // Unsorted on purpose: best bid 0.60
+        let bids = vec![level(55, 100), level(60, 50), level(50, 1_000)];
+        let (filled, proceeds, consumed) = walk_bids(&bids, Decimal::from(120));
+        assert_eq!(filled, Decimal::from(120));
+        // 50 × 0.60 + 70 × 0.55
+        assert_eq!(proceeds, Decimal::new(685, 1));
+        assert_eq!(consumed, 2);
+    }
+
+    #[test]
+    fn test_walk_bids_thin_book() {
+        let (filled, proceeds, consumed) = walk_bids(&[level(40, 10)], Decimal::from(25));
+        assert_eq!(filled, Decimal::from(10));
+        assert_eq!(proceeds, Decimal::from(4));
+        assert_eq!(consumed, 1);
+
+        assert_eq!(walk_bids(&[], Decimal::from(25)), (Decimal::ZERO, Decimal::ZERO, 0));
+    }
+
+    #[test]
+    fn test_preview_close() {
+        let pos = Position {
+            avg_entry_price: Decimal::new(40, 2),
+            ..Position::test_fixture()
+        };
+        let bids = vec![level(60, 50), level(50, 100)];
+
+        let p = preview_close(&pos, &bids, Decimal::new(1, 2));
+        assert_eq!(p.best_bid, Some(Decimal::new(60, 2)));
+        assert_eq!(p.proceeds, Decimal::from(55));
+        assert_eq!(p.expected_fill_price, Some(Decimal::new(55, 2)));
+        // (0.60 - 0.55) / 0.60
+        assert_eq!(p.expected_slippage_pct.unwrap().round_dp(2), Decimal::new(833, 2));
+        assert_eq!(p.estimated_fees, Decimal::new(55, 2));
+        // 55 - 0.55 - 100 × 0.40
+        assert_eq!(p.expected_realized_pnl, Decimal::new(1445, 2));
+        assert_eq!(p.unfilled_size, Decimal::ZERO);
+    }
+}
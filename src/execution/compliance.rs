@@ -0,0 +1,231 @@
+use rust_decimal::Decimal;
+
+use crate::models::{ComplianceRule, ComplianceRuleType, Side};
+
+use super::risk_manager::RiskViolation;
+
+/// What a pre-trade check gets to look at for a candidate order.
+#[derive(Debug, Clone)]
+pub struct PreTradeContext<'a> {
+    /// Market question text, if the market is known.
+    pub question: Option<&'a str>,
+    /// Lowercased Gamma tag slugs and labels.
+    pub tags: &'a [String],
+    pub side: Side,
+    pub price: Decimal,
+}
+
+/// A single pre-trade compliance constraint. `Err` carries a human-readable reason.
+pub trait PreTradeCheck: Send + Sync {
+    fn name(&self) -> &'static str;
+    fn check(&self, ctx: &PreTradeContext<'_>) -> Result<(), String>;
+}
+
+/// Reject markets carrying any of the excluded tags (e.g. jurisdictional exclusions).
+pub struct ExcludedTags {
+    tags: Vec<String>,
+}
+
+impl PreTradeCheck for ExcludedTags {
+    fn name(&self) -> &'static str {
+        "excluded_tag"
+    }
+
+    fn check(&self, ctx: &PreTradeContext<'_>) -> Result<(), String> {
+        match ctx.tags.iter().find(|t| self.tags.contains(t)) {
+            Some(tag) => Err(format!("market tagged '{tag}'")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Reject buys priced above the maximum odds.
+pub struct MaxOdds {
+    max: Decimal,
+}
+
+impl PreTradeCheck for MaxOdds {
+    fn name(&self) -> &'static str {
+        "max_odds"
+    }
+
+    fn check(&self, ctx: &PreTradeContext<'_>) -> Result<(), String> {
+        if ctx.side == Side::Buy && ctx.price > self.max {
+            return Err(format!("price {} above max {}", ctx.price, self.max));
+        }
+        Ok(())
+    }
+}
+
+/// Reject markets whose question contains a banned keyword (case-insensitive).
+pub struct BannedKeywords {
+    keywords: Vec<String>,
+}
+
+impl PreTradeCheck for BannedKeywords {
+    fn name(&self) -> &'static str {
+        "banned_keyword"
+    }
+
+    fn check(&self, ctx: &PreTradeContext<'_>) -> Result<(), String> {
+        let Some(question) = ctx.question else {
+            return Ok(());
+        };
+        let question = question.to_lowercase();
+        match self.keywords.iter().find(|k| question.contains(k.as_str())) {
+            Some(keyword) => Err(format!("question contains '{keyword}'")),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Ordered list of pre-trade checks, run after the risk checks.
+#[derive(Default)]
+pub struct ComplianceChain {
+    checks: Vec<Box<dyn PreTradeCheck>>,
+}
+
+impl ComplianceChain {
+    /// Build the chain from enabled DB rules. Rules of the same type are merged
+    /// into one check; the strictest `max_odds` wins. Invalid rules are skipped.
+    pub fn from_rules(rules: &[ComplianceRule]) -> Self {
+        let mut tags = Vec::new();
+        let mut keywords = Vec::new();
+        let mut max_odds: Option<Decimal> = None;
+
+        for rule in rules.iter().filter(|r| r.enabled) {
+            let value = rule.value.trim();
+            match rule.rule_type() {
+                Some(ComplianceRuleType::ExcludedTag) if !value.is_empty() => {
+                    tags.push(value.to_lowercase());
+                }
+                Some(ComplianceRuleType::BannedKeyword) if !value.is_empty() => {
+                    keywords.push(value.to_lowercase());
+                }
+                Some(ComplianceRuleType::MaxOdds) => match value.parse::<Decimal>() {
+                    Ok(max) => max_odds = Some(max_odds.map_or(max, |m| m.min(max))),
+                    Err(_) => {
+                        tracing::warn!(rule_id = %rule.id, value, "Invalid max_odds rule — skipped");
+                    }
+                },
+                _ => {
+                    tracing::warn!(
+                        rule_id = %rule.id,
+                        rule_type = %rule.rule_type,
+                        "Unusable compliance rule — skipped"
+                    );
+                }
+            }
+        }
+
+        let mut chain = Self::default();
+        if !tags.is_empty() {
+            chain = chain.with_check(Box::new(ExcludedTags { tags }));
+        }
+        if let Some(max) = max_odds {
+            chain = chain.with_check(Box::new(MaxOdds { max }));
+        }
+        if !keywords.is_empty() {
+            chain = chain.with_check(Box::new(BannedKeywords { keywords }));
+        }
+        chain
+    }
+
+    /// Append a custom check.
+    pub fn with_check(mut self, check: Box<dyn PreTradeCheck>) -> Self {
+        self.checks.push(check);
+        self
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.checks.is_empty()
+    }
+
+    /// Run every check in order; the first failure rejects the order.
+    pub fn run(&self, ctx: &PreTradeContext<'_>) -> Result<(), RiskViolation> {
+        for check in &self.checks {
+            check.check(ctx).map_err(|reason| RiskViolation::ComplianceBlocked {
+                check: check.name().to_string(),
+                reason,
+            })?;
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use uuid::Uuid;
+
+    fn rule(rule_type: &str, value: &str) -> ComplianceRule {
+        ComplianceRule {
+            id: Uuid::new_v4(),
+            rule_type: rule_type.into(),
+            value: value.into(),
+            note: None,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn ctx<'a>(question: &'a str, tags: &'a [String], price: Decimal) -> PreTradeContext<'a> {
+        PreTradeContext {
+            question: Some(question),
+            tags,
+            side: Side::Buy,
+            price,
+        }
+    }
+
+    #[test]
+    fn test_chain_blocks_each_rule_type() {
+        let chain = ComplianceChain::from_rules(&[
+            rule("excluded_tag", "US-Politics"),
+            rule("max_odds", "0.95"),
+            rule("max_odds", "0.90"),
+            rule("banned_keyword", "Assassinated"),
+        ]);
+        let no_tags: Vec<String> = vec![];
+
+        let ok = ctx("Will BTC hit 100k?", &no_tags, Decimal::new(50, 2));
+        assert!(chain.run(&ok).is_ok());
+
+        let tags = vec!["us-politics".to_string()];
+        let err = chain.run(&ctx("Who wins?", &tags, Decimal::new(50, 2))).unwrap_err();
+        assert!(matches!(err, RiskViolation::ComplianceBlocked { ref check, .. } if check == "excluded_tag"));
+
+        // Strictest max_odds applies
+        let err = chain.run(&ctx("Who wins?", &no_tags, Decimal::new(92, 2))).unwrap_err();
+        assert!(matches!(err, RiskViolation::ComplianceBlocked { ref check, .. } if check == "max_odds"));
+
+        let err = chain
+            .run(&ctx("Will the leader be assassinated?", &no_tags, Decimal::new(10, 2)))
+            .unwrap_err();
+        assert!(matches!(err, RiskViolation::ComplianceBlocked { ref check, .. } if check == "banned_keyword"));
+    }
+
+    #[test]
+    fn test_chain_skips_disabled_and_invalid_rules() {
+        let mut disabled = rule("banned_keyword", "btc");
+        disabled.enabled = false;
+        let chain = ComplianceChain::from_rules(&[
+            disabled,
+            rule("max_odds", "not-a-number"),
+            rule("unknown", "x"),
+        ]);
+        assert!(chain.is_empty());
+
+        // max_odds does not apply to sells
+        let chain = ComplianceChain::from_rules(&[rule("max_odds", "0.9")]);
+        let tags: Vec<String> = vec![];
+        let mut sell = ctx("Will BTC hit 100k?", &tags, Decimal::new(97, 2));
+        sell.side = Side::Sell;
+        assert!(chain.run(&sell).is_ok());
+    }
+}
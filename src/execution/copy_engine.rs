@@ -1,25 +1,88 @@
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicI64, Ordering};
 use std::sync::Arc;
 
-use metrics::counter;
+use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
+use rust_decimal::prelude::ToPrimitive;
 use rust_decimal::Decimal;
 use sqlx::PgPool;
-use tokio::sync::mpsc;
+use tokio::sync::{mpsc, oneshot};
 
-use crate::db::{config_repo, market_repo, order_repo, position_repo};
-use crate::models::CopySignal;
+use crate::db::{
+    compliance_repo, execution_snapshot_repo, market_repo, order_repo, position_repo,
+    risk_event_repo, trade_repo,
+};
+use crate::models::{CopyOrder, CopySignal, ExitStyle, Position, Side, SignalOrigin, StopMode};
 use crate::polymarket::balance::BalanceChecker;
+use crate::polymarket::gamma_client::{parse_end_date, GammaClient};
+use crate::intelligence::tiers::{self, SharedTierPolicies};
 use crate::services::notifier::Notifier;
 
 use super::capital_pool::CapitalPool;
-use super::order_executor::{ExecutionError, OrderExecutor};
-use super::position_sizer::{self, SizingStrategy};
-use super::risk_manager::{self, PendingOrder, PortfolioSnapshot, RiskLimits};
+use super::circuit_breaker::CircuitBreaker;
+use super::compliance::{ComplianceChain, PreTradeContext};
+use super::order_executor::{ExecutionError, ExecutionMode, ExecutionModes, OrderExecutor, OrderResult};
+use super::order_rules;
+use super::portfolio_risk::{self, PositionExposure};
+use super::position_sizer::{self, ConsensusSizing, FlowSizing, RampFill, RampUpConfig, SizingStrategy};
+use super::risk_manager::{
+    self, PendingOrder, PortfolioSnapshot, RiskLimits, RiskViolation, SharedRiskLimits,
+};
+use super::scale_in::{self, ScaleInConfig, Tranche, LADDER_STRATEGY};
+use super::sleeves::{self, SleeveAllocation, SleevePools};
 
 /// Maximum number of retries for transient CLOB errors.
 const MAX_RETRIES: u32 = 3;
 /// Base delay for exponential backoff (doubles each retry).
 const RETRY_BASE_MS: u64 = 500;
+/// Minimum gap between wallet-buffer alerts, so a dry wallet doesn't spam.
+const BUFFER_ALERT_INTERVAL_SECS: i64 = 3600;
+
+/// Unix time of the last wallet-buffer alert.
+static LAST_BUFFER_ALERT: AtomicI64 = AtomicI64::new(0);
+
+/// `copy_orders.strategy` for operator orders placed via the API.
+pub const MANUAL_STRATEGY: &str = "manual";
+
+/// An operator order (`POST /api/orders/manual`) for the copy engine. It goes
+/// through the same risk checks, capital reservation and fill tracking as a
+/// copied trade; a sell exits the sleeve's open position in the token instead.
+/// The engine replies with the recorded order, or why none was placed.
+#[derive(Debug)]
+pub struct ManualOrder {
+    pub signal: CopySignal,
+    pub reply: oneshot::Sender<Result<CopyOrder, String>>,
+}
+
+/// How to follow a copied whale out of a position.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WhaleExitMode {
+    /// Close our whole position on any sell by the whale.
+    Full,
+    /// Sell the same fraction of our position as the whale sold of theirs.
+    Proportional,
+}
+
+impl WhaleExitMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "full" => Some(WhaleExitMode::Full),
+            "proportional" | "partial" => Some(WhaleExitMode::Proportional),
+            _ => None,
+        }
+    }
+}
+
+/// Fraction of its holding the whale just sold, given the sold size and its
+/// net size after the sale. Falls back to 1 (full exit) when the whale's
+/// holding predates our trade history.
+pub fn whale_exit_fraction(sold: Decimal, net_after: Decimal) -> Decimal {
+    let before = net_after + sold;
+    if sold <= Decimal::ZERO || before <= Decimal::ZERO || net_after <= Decimal::ZERO {
+        return Decimal::ONE;
+    }
+    (sold / before).min(Decimal::ONE)
+}
 
 /// Configuration for the copy engine.
 #[derive(Debug, Clone)]
@@ -27,12 +90,27 @@ pub struct CopyEngineConfig {
     pub strategy: SizingStrategy,
     pub bankroll: Decimal,
     pub base_amount: Decimal,
-    pub risk_limits: RiskLimits,
+    /// Live risk limits, editable at runtime via the API.
+    pub risk_limits: SharedRiskLimits,
     pub dry_run: bool,
     pub default_stop_loss_pct: Decimal,
     pub default_take_profit_pct: Decimal,
+    pub default_stop_mode: StopMode,
+    pub whale_exit_mode: WhaleExitMode,
     pub maker_mode: bool,
     pub maker_order_ttl_secs: u64,
+    /// Lot precision of order sizes; an exit leaving less than one lot
+    /// closes the position.
+    pub order_size_decimals: u32,
+    /// Live execution mode per signal origin.
+    pub execution_modes: ExecutionModes,
+    pub sleeves: SleeveAllocation,
+    pub scale_in: ScaleInConfig,
+    pub ramp_up: RampUpConfig,
+    pub flow_sizing: FlowSizing,
+    pub consensus_sizing: ConsensusSizing,
+    /// Live whale tier policies; signals carrying a tier are sized by its policy.
+    pub tiers: SharedTierPolicies,
 }
 
 impl Default for CopyEngineConfig {
@@ -41,26 +119,41 @@ impl Default for CopyEngineConfig {
             strategy: SizingStrategy::Kelly,
             bankroll: Decimal::from(1_000),
             base_amount: Decimal::from(50),
-            risk_limits: RiskLimits::default(),
+            risk_limits: RiskLimits::default().into_shared(),
             dry_run: true,
             default_stop_loss_pct: Decimal::new(1500, 2),  // 15.00%
             default_take_profit_pct: Decimal::new(2000, 2), // 20.00%
+            default_stop_mode: StopMode::Static,
+            whale_exit_mode: WhaleExitMode::Full,
             maker_mode: true,
             maker_order_ttl_secs: 600,
+            order_size_decimals: 2,
+            execution_modes: ExecutionModes::parse("", ExecutionMode::Maker),
+            sleeves: SleeveAllocation::default(),
+            scale_in: ScaleInConfig::default(),
+            ramp_up: RampUpConfig::default(),
+            flow_sizing: FlowSizing::default(),
+            consensus_sizing: ConsensusSizing::default(),
+            tiers: Default::default(),
         }
     }
 }
 
-/// Run the copy engine loop. Receives CopySignals and executes trades.
+/// Run the copy engine loop. Receives CopySignals and manual orders and
+/// executes trades.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_copy_engine(
     mut rx: mpsc::Receiver<CopySignal>,
+    mut manual_rx: mpsc::Receiver<ManualOrder>,
     pool: PgPool,
     executor: OrderExecutor,
     config: CopyEngineConfig,
     notifier: Option<Arc<Notifier>>,
     balance_checker: Option<BalanceChecker>,
     pause_flag: Arc<AtomicBool>,
-    capital_pool: CapitalPool,
+    capital_pools: SleevePools,
+    gamma_client: GammaClient,
+    circuit_breaker: CircuitBreaker,
 ) {
     tracing::info!(
         strategy = %config.strategy,
@@ -69,7 +162,13 @@ pub async fn run_copy_engine(
         "Copy engine started"
     );
 
-    while let Some(signal) = rx.recv().await {
+    loop {
+        let (signal, reply) = tokio::select! {
+            Some(signal) = rx.recv() => (signal, None),
+            Some(order) = manual_rx.recv() => (order.signal, Some(order.reply)),
+            else => break,
+        };
+
         // Check pause flag
         if pause_flag.load(Ordering::Relaxed) {
             tracing::info!(
@@ -77,6 +176,22 @@ pub async fn run_copy_engine(
                 market = %signal.market_id,
                 "Copy engine paused — skipping signal"
             );
+            if let Some(reply) = reply {
+                let _ = reply.send(Err("trading is paused".into()));
+            }
+            continue;
+        }
+
+        // Check circuit breaker (auto-resets after cool-off)
+        if circuit_breaker.is_tripped().await {
+            tracing::warn!(
+                wallet = %signal.wallet,
+                market = %signal.market_id,
+                "Circuit breaker open — skipping signal"
+            );
+            if let Some(reply) = reply {
+                let _ = reply.send(Err("execution circuit breaker is open".into()));
+            }
             continue;
         }
 
@@ -88,17 +203,19 @@ pub async fn run_copy_engine(
             "Processing copy signal"
         );
 
-        if let Err(e) = process_signal(
+        let result = process_signal(
             &signal,
             &pool,
             &executor,
             &config,
             notifier.as_deref(),
             balance_checker.as_ref(),
-            &capital_pool,
+            &capital_pools,
+            &gamma_client,
+            &circuit_breaker,
         )
-        .await
-        {
+        .await;
+        if let Err(ref e) = result {
             tracing::error!(
                 error = %e,
                 wallet = %signal.wallet,
@@ -106,11 +223,30 @@ pub async fn run_copy_engine(
                 "Copy trade execution failed"
             );
         }
+
+        if let Some(reply) = reply {
+            let outcome = match result {
+                // Reply with the order's current status (filled / submitted / failed)
+                Ok(Some(order)) => Ok(order_repo::get_order_by_id(&pool, order.id)
+                    .await
+                    .ok()
+                    .flatten()
+                    .unwrap_or(order)),
+                Ok(None) => Err(
+                    "order not placed: rejected by risk/compliance checks or insufficient capital \
+                     (see /api/risk/events)"
+                        .into(),
+                ),
+                Err(e) => Err(e.to_string()),
+            };
+            let _ = reply.send(outcome);
+        }
     }
 
     tracing::warn!("Copy engine channel closed — shutting down");
 }
 
+#[allow(clippy::too_many_arguments)]
 async fn process_signal(
     signal: &CopySignal,
     pool: &PgPool,
@@ -118,31 +254,100 @@ async fn process_signal(
     config: &CopyEngineConfig,
     notifier: Option<&Notifier>,
     balance_checker: Option<&BalanceChecker>,
-    capital_pool: &CapitalPool,
-) -> anyhow::Result<()> {
+    capital_pools: &SleevePools,
+    gamma_client: &GammaClient,
+    circuit_breaker: &CircuitBreaker,
+) -> anyhow::Result<Option<CopyOrder>> {
     // 0. Whale exit shortcut — bypass all sizing/risk gates
     if signal.is_whale_exit {
-        return handle_whale_exit(signal, pool, executor, config, notifier, capital_pool).await;
+        handle_whale_exit(signal, pool, executor, config, notifier, capital_pools).await?;
+        return Ok(None);
+    }
+
+    // 0a. A manual sell closes (part of) the sleeve's position in the token
+    if signal.is_manual() && signal.side == Side::Sell {
+        let order = handle_manual_sell(signal, pool, executor, config, notifier, capital_pools).await?;
+        return Ok(Some(order));
+    }
+
+    // 0b. Sleeve or copy profile with no allocation never trades
+    let sleeve_weight = config.sleeves.label_weight(signal.capital_label());
+    if sleeve_weight.is_zero() {
+        tracing::debug!(
+            sleeve = signal.capital_label(),
+            wallet = %signal.wallet,
+            "Sleeve has no capital allocation — skipping signal"
+        );
+        return Ok(None);
     }
+    let capital_pool = capital_pools.get_by_label(signal.capital_label());
+
+    // Snapshot the live risk limits for this signal; the reserve floor is
+    // applied to the pools first so sizing never counts it as available.
+    let risk_limits = config.risk_limits.read().await.clone();
+    capital_pools.set_reserve_floor_pct(risk_limits.reserve_floor_pct).await;
 
-    // 1. Calculate position size using dynamic available capital
+    // 1. Calculate position size using the sleeve's available capital
     let available_capital = capital_pool.available().await;
     let bankroll_for_sizing = if available_capital > Decimal::ZERO {
         available_capital
     } else {
-        config.bankroll
+        config.bankroll * sleeve_weight
     };
 
     let signal_strength = signal.whale_win_rate;
-    let size = position_sizer::calculate_size(
-        config.strategy,
-        bankroll_for_sizing,
-        signal.whale_notional,
-        signal.whale_win_rate,
-        signal.whale_kelly,
-        config.base_amount,
-        signal_strength,
-    );
+    let tier_policy = match signal.tier {
+        Some(tier) => tiers::policy_for(&config.tiers.read().await, tier).cloned(),
+        None => None,
+    };
+    let size = match signal.manual_size {
+        Some(size) => size,
+        // Net flow signals have their own sizing: no single whale behind them
+        None if signal.origin() == SignalOrigin::Flow => {
+            position_sizer::flow_size(&config.flow_sizing, bankroll_for_sizing, signal.whale_notional)
+        }
+        // Basket signals by the consensus behind them rather than one whale's Kelly
+        None if signal.consensus_pct.is_some() => position_sizer::consensus_size(
+            &config.consensus_sizing,
+            bankroll_for_sizing,
+            signal.consensus_pct.unwrap_or(Decimal::ZERO),
+            signal.whale_win_rate,
+        ),
+        None => match &tier_policy {
+            // Tiered whales are sized by their tier's strategy and multiplier
+            Some(tier) => {
+                position_sizer::calculate_size(
+                    SizingStrategy::parse_strategy(&tier.sizing),
+                    bankroll_for_sizing,
+                    signal.whale_notional,
+                    signal.whale_win_rate,
+                    signal.whale_kelly,
+                    tier.base_amount,
+                    signal_strength,
+                    tier.size_multiplier * signal.size_multiplier,
+                )
+            }
+            None => {
+                position_sizer::calculate_size(
+                    config.strategy,
+                    bankroll_for_sizing,
+                    signal.whale_notional,
+                    signal.whale_win_rate,
+                    signal.whale_kelly,
+                    config.base_amount,
+                    signal_strength,
+                    signal.size_multiplier,
+                )
+            }
+        },
+    };
+
+    // 1a. Soft-launch ramp-up caps copied positions on a new live deployment
+    let size = if signal.manual_size.is_none() && config.ramp_up.enabled && !config.dry_run {
+        ramp_up_size(pool, config, signal, size, bankroll_for_sizing).await
+    } else {
+        size
+    };
 
     // Minimum position value: $1 (prevents ghost positions from rounding)
     let min_notional = Decimal::ONE;
@@ -154,11 +359,14 @@ async fn process_signal(
             notional = %notional_value,
             "Position size too small (< $1), skipping"
         );
-        return Ok(());
+        return Ok(None);
     }
 
     tracing::info!(
         strategy = %config.strategy,
+        sleeve = signal.capital_label(),
+        tier = ?signal.tier,
+        size_multiplier = %signal.size_multiplier,
         size = %size,
         available_capital = %available_capital,
         "Position sized"
@@ -167,24 +375,57 @@ async fn process_signal(
     // 1b. Balance pre-check (only when not dry-run and checker available)
     if !config.dry_run {
         if let Some(checker) = balance_checker {
+            // Gas: never start an order the wallet can't pay gas for
+            if risk_limits.min_matic_balance > Decimal::ZERO {
+                match checker.get_matic_balance().await {
+                    Ok(matic) => {
+                        if let Err(violation) = risk_manager::check_gas_balance(matic, &risk_limits) {
+                            tracing::warn!(violation = %violation, "Gas balance too low — skipping order");
+                            record_rejection(pool, "wallet", &violation, signal, size).await;
+                            alert_wallet_buffer(notifier, &violation).await;
+                            return Ok(None);
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(error = %e, "Failed to check MATIC balance — skipping order");
+                        return Ok(None);
+                    }
+                }
+            }
+
             let side_str = signal.side.to_string();
             match side_str.as_str() {
                 "BUY" => {
-                    let required = size * signal.price;
+                    let order_cost = size * signal.price;
                     match checker.get_usdc_balance().await {
-                        Ok(usdc) if usdc < required => {
-                            tracing::warn!(
-                                required = %required,
-                                available = %usdc,
-                                "Insufficient USDC balance — skipping order"
-                            );
-                            return Ok(());
+                        Ok(usdc) => {
+                            if let Err(violation) =
+                                risk_manager::check_fee_buffer(usdc, order_cost, &risk_limits)
+                            {
+                                tracing::warn!(
+                                    violation = %violation,
+                                    "Insufficient USDC (incl. fee buffer) — skipping order"
+                                );
+                                record_rejection(pool, "wallet", &violation, signal, size).await;
+                                alert_wallet_buffer(notifier, &violation).await;
+                                return Ok(None);
+                            }
+                            let floor = capital_pools.reserve_floor().await;
+                            if let Err(violation) =
+                                risk_manager::check_reserve_floor(usdc, order_cost, floor)
+                            {
+                                tracing::warn!(
+                                    violation = %violation,
+                                    "Order would dip into the reserve floor — skipping order"
+                                );
+                                record_rejection(pool, "wallet", &violation, signal, size).await;
+                                return Ok(None);
+                            }
                         }
                         Err(e) => {
                             tracing::warn!(error = %e, "Failed to check USDC balance — skipping order");
-                            return Ok(());
+                            return Ok(None);
                         }
-                        _ => {}
                     }
                 }
                 "SELL" => {
@@ -196,11 +437,11 @@ async fn process_signal(
                                 token_id = %signal.asset_id,
                                 "Insufficient token balance — skipping order"
                             );
-                            return Ok(());
+                            return Ok(None);
                         }
                         Err(e) => {
                             tracing::warn!(error = %e, "Failed to check token balance — skipping order");
-                            return Ok(());
+                            return Ok(None);
                         }
                         _ => {}
                     }
@@ -225,20 +466,20 @@ async fn process_signal(
         price: signal.price,
     };
 
-    // 2b. Apply runtime override for max_daily_loss
-    let mut risk_limits = config.risk_limits.clone();
-    if let Ok(entries) = config_repo::get_all_config(pool).await {
-        for entry in entries {
-            if entry.key == "max_daily_loss" {
-                if let Ok(v) = entry.value.parse() {
-                    risk_limits.max_daily_loss = v;
-                }
-            }
-            if entry.key == "max_open_positions" {
-                if let Ok(v) = entry.value.parse() {
-                    risk_limits.max_open_positions = v;
-                }
-            }
+    // 2c. Time-to-resolution check — skip markets about to end
+    if risk_limits.min_hours_to_resolution > 0 {
+        let end_date = market_end_date(pool, gamma_client, signal).await;
+        if let Err(violation) =
+            risk_manager::check_time_to_resolution(end_date, Utc::now(), &risk_limits)
+        {
+            tracing::warn!(
+                violation = %violation,
+                wallet = %signal.wallet,
+                market = %signal.market_id,
+                "Time-to-resolution check failed — order rejected"
+            );
+            record_rejection(pool, "resolution", &violation, signal, size).await;
+            return Ok(None);
         }
     }
 
@@ -253,43 +494,164 @@ async fn process_signal(
             wallet = %signal.wallet,
             "Risk check failed — order rejected"
         );
-        return Ok(());
+        record_rejection(pool, "global", &violation, signal, size).await;
+        return Ok(None);
+    }
+
+    // 3a. Sleeve risk check — the sleeve's share of the global limits
+    let sleeve_label = signal.capital_label();
+    let sleeve_portfolio = PortfolioSnapshot {
+        bankroll: bankroll_for_sizing,
+        open_positions: position_repo::count_open_positions_in_sleeve(pool, sleeve_label)
+            .await
+            .unwrap_or(0),
+        daily_pnl: position_repo::get_daily_realized_pnl_in_sleeve(pool, sleeve_label)
+            .await
+            .unwrap_or(Decimal::ZERO),
+    };
+    let sleeve_limits = sleeves::sleeve_risk_limits(&risk_limits, sleeve_weight);
+
+    if let Err(violation) = risk_manager::check_risk(
+        &pending_order,
+        &sleeve_portfolio,
+        &sleeve_limits,
+    ) {
+        tracing::warn!(
+            violation = %violation,
+            sleeve = sleeve_label,
+            wallet = %signal.wallet,
+            "Sleeve risk check failed — order rejected"
+        );
+        record_rejection(pool, "sleeve", &violation, signal, size).await;
+        return Ok(None);
+    }
+
+    // 3b. Portfolio tail-risk check (VaR across open positions + this order)
+    let open_positions = match position_repo::get_open_positions(pool).await {
+        Ok(positions) => positions,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load open positions for tail-risk check — skipping signal");
+            let violation = RiskViolation::PortfolioUnavailable { reason: e.to_string() };
+            record_rejection(pool, "portfolio", &violation, signal, size).await;
+            return Ok(None);
+        }
+    };
+    let open_exposures: Vec<PositionExposure> =
+        open_positions.iter().map(PositionExposure::from_position).collect();
+    let pending_exposure = PositionExposure {
+        market_id: signal.condition_id.clone().unwrap_or_else(|| signal.market_id.clone()),
+        token_id: signal.asset_id.clone(),
+        size,
+        price: signal.price,
+    };
+    match portfolio_risk::check_tail_risk(
+        &open_exposures,
+        pending_exposure,
+        config.bankroll,
+        risk_limits.max_tail_loss_pct,
+    ) {
+        Ok(estimate) => {
+            gauge!("portfolio_value_at_risk").set(estimate.value_at_risk.to_f64().unwrap_or(0.0));
+            tracing::debug!(
+                var = %estimate.value_at_risk,
+                expected_loss = %estimate.expected_loss,
+                "Portfolio tail risk estimated"
+            );
+        }
+        Err(violation) => {
+            tracing::warn!(
+                violation = %violation,
+                wallet = %signal.wallet,
+                "Portfolio tail-risk check failed — order rejected"
+            );
+            record_rejection(pool, "portfolio", &violation, signal, size).await;
+            return Ok(None);
+        }
     }
 
     tracing::info!("Risk check passed");
 
-    // 3b. Reserve capital in the pool
-    let reserve_amount = size * signal.price;
-    if !capital_pool.reserve(signal.whale_trade_id, reserve_amount).await {
+    // 3c. Pre-trade compliance checks (operator-defined rules)
+    if let Err(violation) = run_compliance_checks(pool, signal).await {
+        tracing::warn!(
+            violation = %violation,
+            wallet = %signal.wallet,
+            market = %signal.market_id,
+            "Compliance check failed — order rejected"
+        );
+        record_rejection(pool, "compliance", &violation, signal, size).await;
+        return Ok(None);
+    }
+
+    // 3d. Scale-in: split high-conviction entries into a limit ladder
+    let ladder = if config.scale_in.applies_to(signal_strength) {
+        scale_in::plan_ladder(size, signal.price, signal.side, &config.scale_in)
+    } else {
+        vec![Tranche { size, price: signal.price }]
+    };
+    let entry_size = ladder[0].size;
+    if ladder.len() > 1 {
+        tracing::info!(
+            tranches = ladder.len(),
+            entry_size = %entry_size,
+            signal_strength = %signal_strength,
+            "High-conviction signal — scaling in with limit ladder"
+        );
+    }
+
+    // 3e. Reserve capital in the pool. Manual orders have no whale trade to
+    // key the reservation on, so they reserve under their order id once recorded.
+    let reserve_amount = entry_size * signal.price;
+    if !signal.is_manual() && !capital_pool.reserve(signal.whale_trade_id, reserve_amount).await {
         tracing::warn!(
             wallet = %signal.wallet,
             required = %reserve_amount,
             "Capital pool reservation failed — skipping order"
         );
-        return Ok(());
+        return Ok(None);
     }
 
     // 4. Record order in DB
     let side_str = signal.side.to_string();
+    let strategy_label = if signal.is_manual() {
+        MANUAL_STRATEGY.to_string()
+    } else {
+        config.strategy.to_string()
+    };
     let order = order_repo::insert_order(
         pool,
         signal.whale_trade_id,
         &signal.market_id,
         &signal.asset_id,
         &side_str,
-        size,
+        entry_size,
         signal.price,
-        &config.strategy.to_string(),
+        &strategy_label,
+        sleeve_label,
+        signal.condition_id.as_deref(),
+        signal.source_signal_id,
     )
     .await?;
 
     tracing::info!(order_id = %order.id, "Order recorded");
 
+    let reservation_key = if signal.is_manual() { order.id } else { signal.whale_trade_id };
+    if signal.is_manual() && !capital_pool.reserve(reservation_key, reserve_amount).await {
+        tracing::warn!(
+            order_id = %order.id,
+            required = %reserve_amount,
+            "Capital pool reservation failed — manual order not placed"
+        );
+        order_repo::fail_order(pool, order.id, "insufficient capital in sleeve").await?;
+        return Ok(None);
+    }
+
     // 5. Execute with retry for transient CLOB errors
     let mut last_error: Option<ExecutionError> = None;
+    let mode = config.execution_modes.for_origin(signal.origin());
 
     for attempt in 0..MAX_RETRIES {
-        match executor.execute(&signal.asset_id, &side_str, size, signal.price).await {
+        match executor.execute(&signal.asset_id, &side_str, entry_size, signal.price, mode).await {
             Ok(result) => {
                 tracing::info!(
                     order_id = %order.id,
@@ -301,11 +663,12 @@ async fn process_signal(
                 );
 
                 counter!("orders_filled").increment(1);
+                record_snapshot(pool, order.id, &signal.asset_id, &side_str, signal.price, &result).await;
 
                 if config.dry_run || result.order_id.is_none() {
                     // Dry-run or no-wallet: immediate fill + position creation
                     order_repo::fill_order(pool, order.id, result.fill_price, result.slippage).await?;
-                    capital_pool.confirm(&signal.whale_trade_id).await;
+                    capital_pool.confirm(&reservation_key).await;
 
                     let outcome = match signal.side {
                         crate::models::Side::Buy => "Yes",
@@ -317,8 +680,12 @@ async fn process_signal(
                         &signal.market_id,
                         &signal.asset_id,
                         outcome,
-                        size,
+                        entry_size,
                         result.fill_price,
+                        sleeve_label,
+                        signal.condition_id.as_deref(),
+                        signal.whale_trade(),
+                        signal.source_signal_id,
                     )
                     .await?;
 
@@ -327,6 +694,7 @@ async fn process_signal(
                         position.id,
                         config.default_stop_loss_pct,
                         config.default_take_profit_pct,
+                        config.default_stop_mode,
                     )
                     .await
                     {
@@ -349,15 +717,21 @@ async fn process_signal(
 
                 // Notify order result
                 if let Some(n) = notifier {
-                    let market_question = market_repo::get_market_question(pool, &signal.market_id)
+                    let market_question = market_repo::get_market_question(pool, signal.condition_id.as_deref().unwrap_or(&signal.market_id))
                         .await
                         .ok()
                         .flatten();
                     let msg = crate::services::notifier::format_order_result(&order, true, None, market_question.as_deref());
-                    n.send(&msg).await;
+                    let notional = order.size * order.fill_price.unwrap_or(order.target_price);
+                    n.notify(n.fill_severity(notional), &msg).await;
+                }
+
+                // 6. Rest the remaining ladder rungs at better prices
+                if ladder.len() > 1 {
+                    place_ladder_rungs(signal, &ladder[1..], pool, executor, config, capital_pool).await;
                 }
 
-                return Ok(());
+                return Ok(Some(order));
             }
             Err(e) => {
                 // Only retry on transient CLOB errors
@@ -397,22 +771,42 @@ async fn process_signal(
 
     counter!("orders_failed").increment(1);
     order_repo::fail_order(pool, order.id, &err_msg).await?;
-    capital_pool.release(&signal.whale_trade_id).await;
+
+    // Slippage rejections from the executor are risk events too
+    if let Some(ExecutionError::RiskViolation(violation)) = &last_error {
+        record_rejection(pool, "execution", violation, signal, size).await;
+    }
+    capital_pool.release(&reservation_key).await;
 
     // Notify order failure
     if let Some(n) = notifier {
-        let market_question = market_repo::get_market_question(pool, &signal.market_id)
+        let market_question = market_repo::get_market_question(pool, signal.condition_id.as_deref().unwrap_or(&signal.market_id))
             .await
             .ok()
             .flatten();
         let msg = crate::services::notifier::format_order_result(&order, false, Some(&err_msg), market_question.as_deref());
-        n.send(&msg).await;
+        n.notify(crate::services::notifier::Severity::Warning, &msg).await;
     }
 
-    Ok(())
+    // Live execution failures count towards the circuit breaker
+    if !config.dry_run && circuit_breaker.record_failure().await {
+        if let Some(n) = notifier {
+            let cb = circuit_breaker.config();
+            let msg = crate::services::notifier::format_circuit_breaker_tripped(
+                cb.max_failures,
+                cb.window.num_minutes(),
+                cb.cooldown.num_minutes(),
+            );
+            n.notify(crate::services::notifier::Severity::Critical, &msg).await;
+        }
+    }
+
+    Ok(Some(order))
 }
 
-/// Handle a whale exit signal: sell our entire position in this token.
+/// Handle a whale exit signal: sell our position in this token — all of it, or
+/// the fraction the whale sold in proportional mode. Positions copied from a
+/// long-horizon whale are held to resolution and ignore the sell.
 /// Bypasses all sizing/risk gates since we're following the whale out.
 async fn handle_whale_exit(
     signal: &CopySignal,
@@ -420,9 +814,9 @@ async fn handle_whale_exit(
     executor: &OrderExecutor,
     config: &CopyEngineConfig,
     notifier: Option<&Notifier>,
-    capital_pool: &CapitalPool,
+    capital_pools: &SleevePools,
 ) -> anyhow::Result<()> {
-    let pos = match position_repo::get_position_by_token_id(pool, &signal.asset_id).await? {
+    let pos = match position_repo::get_position_by_token_id(pool, &signal.asset_id, signal.capital_label()).await? {
         Some(p) if p.status.as_deref() == Some("open") => p,
         _ => {
             tracing::debug!(
@@ -433,13 +827,98 @@ async fn handle_whale_exit(
         }
     };
 
+    if pos.exit_style() == ExitStyle::HoldToResolution {
+        tracing::info!(
+            wallet = %signal.wallet,
+            token_id = %signal.asset_id,
+            "Whale exit: position is held to resolution — not following"
+        );
+        return Ok(());
+    }
+
+    // Earlier exits still resting on the book already sell part of the position
+    let unsold = pos.size - order_repo::get_pending_exit_size(pool, &pos.token_id, &pos.sleeve).await?;
+    let exit_size = match config.whale_exit_mode {
+        WhaleExitMode::Full => unsold,
+        WhaleExitMode::Proportional => {
+            let fraction = whale_sold_fraction(pool, signal).await;
+            let size = order_rules::truncate_size(pos.size * fraction, config.order_size_decimals).min(unsold);
+            // Sell the lot-sized remainder too rather than leave unsellable dust
+            if order_rules::is_dust(unsold - size, config.order_size_decimals) {
+                unsold
+            } else {
+                size
+            }
+        }
+    };
+    if exit_size <= Decimal::ZERO {
+        tracing::debug!(
+            token_id = %signal.asset_id,
+            "Whale exit: nothing left to sell beyond pending exits — skipping"
+        );
+        return Ok(());
+    }
     tracing::info!(
         wallet = %signal.wallet,
         token_id = %signal.asset_id,
         size = %pos.size,
+        exit_size = %exit_size,
         "Whale exit: closing position"
     );
 
+    sell_position(signal, &pos, exit_size, "whale_exit", pool, executor, config, notifier, capital_pools).await?;
+    counter!("whale_exits_executed").increment(1);
+    Ok(())
+}
+
+/// Operator sell (`POST /api/orders/manual` with side SELL): goes through the
+/// exit path against the sleeve's open position in the token, never opens one.
+async fn handle_manual_sell(
+    signal: &CopySignal,
+    pool: &PgPool,
+    executor: &OrderExecutor,
+    config: &CopyEngineConfig,
+    notifier: Option<&Notifier>,
+    capital_pools: &SleevePools,
+) -> anyhow::Result<CopyOrder> {
+    let size = signal.manual_size.unwrap_or_default();
+    let pos = match position_repo::get_position_by_token_id(pool, &signal.asset_id, signal.capital_label()).await? {
+        Some(p) if p.status.as_deref() == Some("open") => p,
+        _ => anyhow::bail!("no open position in this token in the {} sleeve to sell", signal.capital_label()),
+    };
+    let unsold = pos.size - order_repo::get_pending_exit_size(pool, &pos.token_id, &pos.sleeve).await?;
+    if size > unsold {
+        anyhow::bail!("sell size {size} exceeds the {unsold} of the position not already being sold");
+    }
+
+    tracing::info!(
+        token_id = %signal.asset_id,
+        size = %pos.size,
+        exit_size = %size,
+        "Manual sell: closing position"
+    );
+
+    sell_position(signal, &pos, size, "manual", pool, executor, config, notifier, capital_pools).await
+}
+
+/// Sell `exit_size` of a position: records the exit order and executes it. In
+/// dry-run (or on an immediate fill) the position is reduced or closed with
+/// `reason` right away; live, the fill poller books it once the order fills.
+#[allow(clippy::too_many_arguments)]
+async fn sell_position(
+    signal: &CopySignal,
+    pos: &Position,
+    exit_size: Decimal,
+    reason: &str,
+    pool: &PgPool,
+    executor: &OrderExecutor,
+    config: &CopyEngineConfig,
+    notifier: Option<&Notifier>,
+    capital_pools: &SleevePools,
+) -> anyhow::Result<CopyOrder> {
+    // Less than a lot left behind can never be sold: close it with the rest
+    let is_partial = !order_rules::is_dust(pos.size - exit_size, config.order_size_decimals);
+
     // Record exit order
     let order = order_repo::insert_order(
         pool,
@@ -447,47 +926,62 @@ async fn handle_whale_exit(
         &pos.market_id,
         &pos.token_id,
         "SELL",
-        pos.size,
+        exit_size,
         signal.price,
         "exit",
+        &pos.sleeve,
+        pos.condition_id.as_deref(),
+        None,
     )
     .await?;
 
     // Execute sell via the order executor (handles dry-run vs live, orderbook price, etc.)
-    match executor.execute(&pos.token_id, "SELL", pos.size, signal.price).await {
+    let mode = config.execution_modes.for_origin(signal.origin());
+    match executor.execute(&pos.token_id, "SELL", exit_size, signal.price, mode).await {
         Ok(result) => {
+            record_snapshot(pool, order.id, &pos.token_id, "SELL", signal.price, &result).await;
+
             if config.dry_run || result.order_id.is_none() {
                 // Dry-run: fill immediately and close position
                 order_repo::fill_order(pool, order.id, result.fill_price, result.slippage).await?;
-                let realized_pnl = (result.fill_price - pos.avg_entry_price) * pos.size;
-                position_repo::close_position_with_reason(pool, pos.id, realized_pnl, "whale_exit")
-                    .await?;
+                let realized_pnl = (result.fill_price - pos.avg_entry_price) * exit_size;
+                if is_partial {
+                    position_repo::reduce_position(pool, pos.id, exit_size, realized_pnl).await?;
+                } else {
+                    position_repo::close_position_with_reason(pool, pos.id, realized_pnl, reason).await?;
+                }
 
-                // Return capital to pool
-                let returned = pos.avg_entry_price * pos.size + realized_pnl;
-                capital_pool.return_capital(returned).await;
+                // Return capital to the sleeve that opened the position
+                let returned = pos.avg_entry_price * exit_size + realized_pnl;
+                capital_pools.get_by_label(&pos.sleeve).return_capital(returned).await;
 
                 tracing::info!(
                     position_id = %pos.id,
                     realized_pnl = %realized_pnl,
-                    "Whale exit: position closed (dry-run)"
+                    partial = is_partial,
+                    reason,
+                    "Exit: position closed (dry-run)"
                 );
             } else {
                 // Live: mark as submitted, fill poller will close
                 let clob_id = result.order_id.as_deref().unwrap_or("");
                 order_repo::mark_order_submitted(pool, order.id, clob_id).await?;
-                position_repo::mark_position_exiting(pool, pos.id, "whale_exit").await?;
+                // A partial exit leaves the rest of the position open and monitored
+                if !is_partial {
+                    position_repo::mark_position_exiting(pool, pos.id, reason).await?;
+                }
 
                 tracing::info!(
                     position_id = %pos.id,
                     clob_order_id = clob_id,
-                    "Whale exit: exit order submitted to CLOB"
+                    reason,
+                    "Exit: exit order submitted to CLOB"
                 );
             }
 
             // Notify
             if let Some(n) = notifier {
-                let market_question = market_repo::get_market_question(pool, &pos.market_id)
+                let market_question = market_repo::get_market_question(pool, pos.market_key())
                     .await
                     .ok()
                     .flatten();
@@ -497,31 +991,331 @@ async fn handle_whale_exit(
                 } else {
                     Decimal::ZERO
                 };
-                let realized_pnl = (result.fill_price - pos.avg_entry_price) * pos.size;
+                let realized_pnl = (result.fill_price - pos.avg_entry_price) * exit_size;
                 let msg = crate::services::notifier::format_position_exit(
                     market_question.as_deref(),
                     &pos.market_id,
-                    "whale_exit",
+                    reason,
                     pos.avg_entry_price,
                     result.fill_price,
                     realized_pnl,
                     pnl_pct,
                 );
-                n.send(&msg).await;
+                n.notify(crate::services::notifier::Severity::Info, &msg).await;
             }
 
-            counter!("whale_exits_executed").increment(1);
+            Ok(order)
         }
         Err(e) => {
             let err_msg = e.to_string();
             tracing::error!(
                 error = %err_msg,
                 token_id = %signal.asset_id,
-                "Whale exit: failed to execute sell order"
+                reason,
+                "Exit: failed to execute sell order"
             );
             order_repo::fail_order(pool, order.id, &err_msg).await?;
+            Err(e.into())
         }
     }
+}
 
-    Ok(())
+/// Place scale-in rungs as resting orders. Each rung is its own copy order
+/// (strategy `ladder`) with its own capital reservation, keyed by the order id;
+/// fills aggregate into the same position via the fill poller, and unfilled
+/// rungs are cancelled after the maker TTL like any resting order.
+async fn place_ladder_rungs(
+    signal: &CopySignal,
+    rungs: &[Tranche],
+    pool: &PgPool,
+    executor: &OrderExecutor,
+    config: &CopyEngineConfig,
+    capital_pool: &CapitalPool,
+) {
+    let side_str = signal.side.to_string();
+    let sleeve_label = signal.capital_label();
+
+    for (step, rung) in rungs.iter().enumerate() {
+        let order = match order_repo::insert_order(
+            pool,
+            signal.whale_trade_id,
+            &signal.market_id,
+            &signal.asset_id,
+            &side_str,
+            rung.size,
+            rung.price,
+            LADDER_STRATEGY,
+            sleeve_label,
+            signal.condition_id.as_deref(),
+            signal.source_signal_id,
+        )
+        .await
+        {
+            Ok(order) => order,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to record ladder order");
+                return;
+            }
+        };
+
+        if !capital_pool.reserve(order.id, rung.size * rung.price).await {
+            tracing::warn!(order_id = %order.id, "Capital pool exhausted — stopping ladder");
+            let _ = order_repo::fail_order(pool, order.id, "insufficient capital for ladder rung").await;
+            return;
+        }
+
+        match executor.place_resting(&signal.asset_id, &side_str, rung.size, rung.price).await {
+            Ok(result) => match result.order_id.as_deref() {
+                Some(clob_id) if !config.dry_run => {
+                    let _ = order_repo::mark_order_submitted(pool, order.id, clob_id).await;
+                }
+                _ => {
+                    // Dry-run: treat the rung as filled at its limit price
+                    let _ = order_repo::fill_order(pool, order.id, rung.price, Decimal::ZERO).await;
+                    capital_pool.confirm(&order.id).await;
+                    if let Err(e) = position_repo::upsert_position(
+                        pool,
+                        &signal.market_id,
+                        &signal.asset_id,
+                        if signal.side == Side::Buy { "Yes" } else { "No" },
+                        rung.size,
+                        rung.price,
+                        sleeve_label,
+                        signal.condition_id.as_deref(),
+                        Some(signal.whale_trade_id),
+                        signal.source_signal_id,
+                    )
+                    .await
+                    {
+                        tracing::warn!(error = %e, "Failed to add ladder fill to position");
+                    }
+                }
+            },
+            Err(e) => {
+                tracing::warn!(
+                    order_id = %order.id,
+                    step = step + 1,
+                    error = %e,
+                    "Ladder rung placement failed — stopping ladder"
+                );
+                let _ = order_repo::fail_order(pool, order.id, &e.to_string()).await;
+                capital_pool.release(&order.id).await;
+                return;
+            }
+        }
+
+        counter!("scale_in_rungs_placed").increment(1);
+    }
+}
+
+/// Fraction of its holding the whale sold in the trade behind this exit signal.
+/// Defaults to a full exit if the trade or holding can't be determined.
+async fn whale_sold_fraction(pool: &PgPool, signal: &CopySignal) -> Decimal {
+    let Ok(Some(trade)) = trade_repo::get_trade_by_id(pool, signal.whale_trade_id).await else {
+        return Decimal::ONE;
+    };
+    let Some(whale_id) = trade.whale_id else {
+        return Decimal::ONE;
+    };
+    match trade_repo::get_net_token_size(pool, whale_id, &trade.token_id).await {
+        Ok(net_after) => whale_exit_fraction(trade.size, net_after),
+        Err(e) => {
+            tracing::warn!(error = %e, "Whale exit: failed to load whale holding — exiting fully");
+            Decimal::ONE
+        }
+    }
+}
+
+/// Cap `size` at the ramp-up's current per-position notional. The cap is
+/// derived from the last `RAMP_UP_WINDOW_DAYS` of live fills; if they cannot be
+/// loaded the soft-launch cap applies.
+async fn ramp_up_size(
+    pool: &PgPool,
+    config: &CopyEngineConfig,
+    signal: &CopySignal,
+    size: Decimal,
+    bankroll: Decimal,
+) -> Decimal {
+    let since = Utc::now() - chrono::Duration::days(position_sizer::RAMP_UP_WINDOW_DAYS);
+    let fills: Vec<RampFill> = match order_repo::get_live_fill_slippages(pool, since).await {
+        Ok(rows) => rows
+            .into_iter()
+            .map(|(filled_at, slippage)| RampFill {
+                filled_at,
+                slippage: slippage * Decimal::ONE_HUNDRED,
+            })
+            .collect(),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load live fills for ramp-up — using soft-launch cap");
+            Vec::new()
+        }
+    };
+    let position_limit = bankroll * config.risk_limits.read().await.max_position_pct;
+
+    let Some(cap) = position_sizer::ramp_up_cap(&config.ramp_up, &fills, position_limit, Utc::now()) else {
+        gauge!("position_ramp_up_cap").set(0.0);
+        return size;
+    };
+    gauge!("position_ramp_up_cap").set(cap.to_f64().unwrap_or(0.0));
+
+    let capped = position_sizer::apply_notional_cap(size, signal.price, cap);
+    if capped < size {
+        tracing::info!(
+            wallet = %signal.wallet,
+            size = %size,
+            capped = %capped,
+            cap = %cap,
+            live_fills = fills.len(),
+            "Ramp-up: position capped"
+        );
+    }
+    capped
+}
+
+/// Run the enabled compliance rules against this signal's market.
+/// Rules are read on every signal so edits apply without a restart. If the
+/// rules or the market can't be loaded the signal is rejected.
+async fn run_compliance_checks(pool: &PgPool, signal: &CopySignal) -> Result<(), RiskViolation> {
+    let rules = match compliance_repo::get_enabled_rules(pool).await {
+        Ok(rules) => rules,
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to load compliance rules — skipping signal");
+            return Err(RiskViolation::ComplianceBlocked {
+                check: "rules_unavailable".into(),
+                reason: e.to_string(),
+            });
+        }
+    };
+    let chain = ComplianceChain::from_rules(&rules);
+    if chain.is_empty() {
+        return Ok(());
+    }
+
+    let market_key = signal.condition_id.as_deref().unwrap_or(&signal.market_id);
+    let (question, tags) = match market_repo::get_market_question_and_tags(pool, market_key).await {
+        Ok(info) => info.map(|(q, t)| (Some(q), t)).unwrap_or_default(),
+        Err(e) => {
+            tracing::error!(error = %e, market = %market_key, "Failed to load market for compliance checks — skipping signal");
+            return Err(RiskViolation::ComplianceBlocked {
+                check: "market_unavailable".into(),
+                reason: e.to_string(),
+            });
+        }
+    };
+
+    chain.run(&PreTradeContext {
+        question: question.as_deref(),
+        tags: &tags,
+        side: signal.side,
+        price: signal.price,
+    })
+}
+
+/// Persist a risk rejection to the audit table. Failures are logged, never fatal.
+async fn record_rejection(
+    pool: &PgPool,
+    scope: &str,
+    violation: &RiskViolation,
+    signal: &CopySignal,
+    size: Decimal,
+) {
+    counter!("risk_rejections_total", "rule" => violation.rule()).increment(1);
+
+    if let Err(e) = risk_event_repo::insert_risk_event(pool, scope, violation, signal, size).await {
+        tracing::warn!(error = %e, rule = violation.rule(), "Failed to record risk event");
+    }
+}
+
+/// Persist the book the executor saw when placing this order, if it fetched one.
+async fn record_snapshot(
+    pool: &PgPool,
+    order_id: uuid::Uuid,
+    token_id: &str,
+    side: &str,
+    target_price: Decimal,
+    result: &OrderResult,
+) {
+    let Some(book) = &result.book else { return };
+
+    if let Err(e) = execution_snapshot_repo::insert_snapshot(
+        pool,
+        order_id,
+        token_id,
+        side,
+        target_price,
+        result.fill_price,
+        book,
+    )
+    .await
+    {
+        tracing::warn!(error = %e, order_id = %order_id, "Failed to record execution snapshot");
+    }
+}
+
+/// Alert that a wallet buffer is breached, at most once per
+/// `BUFFER_ALERT_INTERVAL_SECS`.
+async fn alert_wallet_buffer(notifier: Option<&Notifier>, violation: &RiskViolation) {
+    let Some(n) = notifier else { return };
+
+    let now = Utc::now().timestamp();
+    let last = LAST_BUFFER_ALERT.load(Ordering::Relaxed);
+    if now - last < BUFFER_ALERT_INTERVAL_SECS
+        || LAST_BUFFER_ALERT
+            .compare_exchange(last, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_err()
+    {
+        return;
+    }
+
+    let msg = crate::services::notifier::format_wallet_buffer_alert(&violation.to_string());
+    n.notify(crate::services::notifier::Severity::Critical, &msg).await;
+}
+
+/// End date for the signal's market: active_markets first, then the Gamma API.
+async fn market_end_date(
+    pool: &PgPool,
+    gamma_client: &GammaClient,
+    signal: &CopySignal,
+) -> Option<DateTime<Utc>> {
+    let condition_id = signal.condition_id.as_deref()?;
+
+    if let Ok(Some(end)) = market_repo::get_market_end_date(pool, condition_id).await {
+        return Some(end);
+    }
+
+    match gamma_client.get_market_by_condition_id(condition_id).await {
+        Ok(market) => market
+            .as_ref()
+            .and_then(|m| m.end_date_raw())
+            .and_then(parse_end_date),
+        Err(e) => {
+            tracing::debug!(error = %e, condition_id, "Gamma end-date lookup failed");
+            None
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whale_exit_fraction() {
+        // Sold 25 of 100 → 25%
+        assert_eq!(
+            whale_exit_fraction(Decimal::from(25), Decimal::from(75)),
+            Decimal::new(25, 2)
+        );
+        // Sold everything
+        assert_eq!(whale_exit_fraction(Decimal::from(40), Decimal::ZERO), Decimal::ONE);
+        // Holding predates tracking (net goes negative) → full exit
+        assert_eq!(whale_exit_fraction(Decimal::from(40), Decimal::from(-10)), Decimal::ONE);
+
+        assert_eq!(WhaleExitMode::parse("Proportional"), Some(WhaleExitMode::Proportional));
+        assert_eq!(WhaleExitMode::parse("half"), None);
+    }
 }
@@ -0,0 +1,186 @@
+use rust_decimal::Decimal;
+
+use crate::models::Sleeve;
+
+/// Longest profile name: it is stored as the capital label on orders,
+/// positions and risk events.
+const MAX_NAME_LEN: usize = 20;
+
+/// A named set of signal gates and sizing that copies whale trades out of its
+/// own capital pool. Several profiles are evaluated side by side for every
+/// whale trade, so e.g. an aggressive and a conservative appetite can run in
+/// one deployment and be compared per profile. Each profile's orders and
+/// positions are tagged with the profile name in place of a strategy sleeve,
+/// so its copies are held as their own position and followed out separately.
+///
+/// Parsed from `COPY_PROFILES`, profiles separated by `;`:
+/// `conservative:weight=0.3,win_rate=0.65,min_ev=100,size=0.5;aggressive:weight=0.2,win_rate=0.55,size=1.5`.
+/// `weight` is the profile's share of the bankroll, alongside `SLEEVE_WEIGHTS`.
+/// Gates left out fall back to the pipeline defaults.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CopyProfile {
+    pub name: String,
+    /// Fraction of the bankroll in the profile's capital pool.
+    pub weight: Decimal,
+    /// Multiplier applied to the strategy-computed order size.
+    pub size_multiplier: Decimal,
+    pub min_signal_win_rate: Option<Decimal>,
+    pub min_total_trades_for_signal: Option<i32>,
+    pub min_signal_ev: Option<Decimal>,
+    pub min_signal_profit_factor: Option<Decimal>,
+    pub min_signal_skill_score: Option<Decimal>,
+    pub signal_notional_floor: Option<Decimal>,
+    pub max_signal_notional: Option<Decimal>,
+}
+
+impl CopyProfile {
+    fn new(name: &str, weight: Decimal) -> Self {
+        Self {
+            name: name.to_string(),
+            weight,
+            size_multiplier: Decimal::ONE,
+            min_signal_win_rate: None,
+            min_total_trades_for_signal: None,
+            min_signal_ev: None,
+            min_signal_profit_factor: None,
+            min_signal_skill_score: None,
+            signal_notional_floor: None,
+            max_signal_notional: None,
+        }
+    }
+
+    /// Parse one `name:key=value,...` entry.
+    fn parse_entry(entry: &str) -> Result<Self, String> {
+        let (name, params) = entry.split_once(':').unwrap_or((entry, ""));
+        let name = name.trim();
+        if name.is_empty() {
+            return Err("missing profile name".into());
+        }
+        if name.len() > MAX_NAME_LEN
+            || !name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_' || c == '-')
+        {
+            return Err(format!(
+                "profile names must be at most {MAX_NAME_LEN} lowercase letters, digits, '_' or '-'"
+            ));
+        }
+        // The name labels the profile's capital, so it can't shadow a strategy sleeve
+        if Sleeve::parse(name).is_some() {
+            return Err(format!("'{name}' is a strategy sleeve name"));
+        }
+
+        let mut weight = None;
+        let mut values = Vec::new();
+        for pair in params.split(',').filter(|p| !p.trim().is_empty()) {
+            let Some((key, value)) = pair.split_once('=') else {
+                return Err(format!("expected key=value, got '{}'", pair.trim()));
+            };
+            let (key, value) = (key.trim(), value.trim());
+            if key == "weight" {
+                let w: Decimal = value.parse().map_err(|_| format!("invalid weight '{value}'"))?;
+                if w <= Decimal::ZERO || w > Decimal::ONE {
+                    return Err(format!("weight must be in (0, 1], got '{value}'"));
+                }
+                weight = Some(w);
+            } else {
+                values.push((key, value));
+            }
+        }
+
+        let weight = weight.ok_or("missing weight")?;
+
+        let mut profile = Self::new(name, weight);
+        for (key, value) in values {
+            let bad = || format!("invalid {key} '{value}'");
+            match key {
+                "size" => {
+                    let v: Decimal = value.parse().map_err(|_| bad())?;
+                    if v <= Decimal::ZERO {
+                        return Err(bad());
+                    }
+                    profile.size_multiplier = v;
+                }
+                "win_rate" => profile.min_signal_win_rate = Some(value.parse().map_err(|_| bad())?),
+                "min_trades" => profile.min_total_trades_for_signal = Some(value.parse().map_err(|_| bad())?),
+                "min_ev" => profile.min_signal_ev = Some(value.parse().map_err(|_| bad())?),
+                "min_pf" => profile.min_signal_profit_factor = Some(value.parse().map_err(|_| bad())?),
+                "min_skill" => profile.min_signal_skill_score = Some(value.parse().map_err(|_| bad())?),
+                "min_notional" => profile.signal_notional_floor = Some(value.parse().map_err(|_| bad())?),
+                "max_notional" => profile.max_signal_notional = Some(value.parse().map_err(|_| bad())?),
+                _ => return Err(format!("unknown key '{key}'")),
+            }
+        }
+
+        Ok(profile)
+    }
+}
+
+/// Parse `COPY_PROFILES`. Invalid entries, and entries reusing the name of an
+/// earlier profile, are skipped with a warning. An empty list means the single
+/// built-in profile (pipeline gates, whale sleeve).
+pub fn parse_profiles(raw: &str) -> Vec<CopyProfile> {
+    let mut profiles: Vec<CopyProfile> = Vec::new();
+
+    for entry in raw.split(';').map(str::trim).filter(|e| !e.is_empty()) {
+        let profile = match CopyProfile::parse_entry(entry) {
+            Ok(p) => p,
+            Err(reason) => {
+                tracing::warn!(entry, reason = %reason, "Ignoring invalid copy profile");
+                continue;
+            }
+        };
+        if profiles.iter().any(|p| p.name == profile.name) {
+            tracing::warn!(profile = %profile.name, "Ignoring copy profile — name already used by another profile");
+            continue;
+        }
+        profiles.push(profile);
+    }
+
+    profiles
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_profiles() {
+        let profiles = parse_profiles(
+            "conservative:weight=0.3,win_rate=0.65,min_ev=100,size=0.5; \
+             aggressive:weight=0.2,win_rate=0.55,min_trades=20,min_pf=1.2,min_skill=0.9,max_notional=1000000",
+        );
+        assert_eq!(profiles.len(), 2);
+
+        let c = &profiles[0];
+        assert_eq!(c.name, "conservative");
+        assert_eq!(c.weight, Decimal::new(3, 1));
+        assert_eq!(c.size_multiplier, Decimal::new(5, 1));
+        assert_eq!(c.min_signal_win_rate, Some(Decimal::new(65, 2)));
+        assert_eq!(c.min_signal_ev, Some(Decimal::from(100)));
+        assert_eq!(c.min_total_trades_for_signal, None);
+
+        let a = &profiles[1];
+        assert_eq!(a.weight, Decimal::new(2, 1));
+        assert_eq!(a.size_multiplier, Decimal::ONE);
+        assert_eq!(a.min_total_trades_for_signal, Some(20));
+        assert_eq!(a.min_signal_profit_factor, Some(Decimal::new(12, 1)));
+        assert_eq!(a.min_signal_skill_score, Some(Decimal::new(9, 1)));
+        assert_eq!(a.max_signal_notional, Some(Decimal::from(1_000_000)));
+    }
+
+    #[test]
+    fn test_parse_profiles_skips_invalid_and_duplicates() {
+        let profiles = parse_profiles(
+            "a:weight=0.2;b:weight=0.2;c:weight=0;d:win_rate=0.6;e:weight=0.1,size=0;\
+             f:weight=0.1,bogus=1;a:weight=0.3;momentum:weight=0.1;Big:weight=0.1;g:weight=1.5",
+        );
+        assert_eq!(profiles.len(), 2);
+        assert_eq!(profiles[0].name, "a");
+        assert_eq!(profiles[0].weight, Decimal::new(2, 1));
+        assert_eq!(profiles[1].name, "b");
+        assert!(parse_profiles("").is_empty());
+    }
+}
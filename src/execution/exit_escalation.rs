@@ -0,0 +1,124 @@
+use std::sync::Arc;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::ingestion::price_cache::PriceCache;
+use crate::polymarket::clob_client::ClobClient;
+use crate::services::notifier::Notifier;
+
+/// Polymarket price tick.
+const PRICE_TICK: Decimal = Decimal::from_parts(1, 0, 0, false, 2); // 0.01
+
+/// Exit reasons whose unfilled orders are repriced. Take-profit exits can wait
+/// for their price; everything else is limiting a loss or time-critical.
+fn escalates(exit_reason: Option<&str>) -> bool {
+    exit_reason != Some("take_profit")
+}
+
+/// Settings for repricing exit orders that sit unfilled.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExitEscalationConfig {
+    /// Reprice an exit order once it has rested this long. Zero disables.
+    pub reprice_secs: i64,
+    /// Each reprice sells this many percent further below the best bid.
+    pub step_pct: Decimal,
+    /// After this many reprices the order is left resting and an alert sent.
+    pub max_reprices: u32,
+}
+
+impl Default for ExitEscalationConfig {
+    fn default() -> Self {
+        Self {
+            reprice_secs: 0,
+            step_pct: Decimal::TWO,
+            max_reprices: 5,
+        }
+    }
+}
+
+impl ExitEscalationConfig {
+    pub fn enabled(&self) -> bool {
+        self.reprice_secs > 0
+    }
+
+    /// True if exits for a position with this exit reason are escalated.
+    pub fn applies_to(&self, exit_reason: Option<&str>) -> bool {
+        self.enabled() && escalates(exit_reason)
+    }
+
+    /// Price for reprice number `attempt` (1-based): `step_pct × attempt`
+    /// below the best bid, never above the previous order's price, rounded
+    /// down to the tick and floored at one tick.
+    pub fn reprice(&self, best_bid: Decimal, previous_price: Decimal, attempt: u32) -> Decimal {
+        let discount = self.step_pct * Decimal::from(attempt) / Decimal::ONE_HUNDRED;
+        let price = best_bid.min(previous_price) * (Decimal::ONE - discount);
+        price
+            .round_dp_with_strategy(2, RoundingStrategy::ToZero)
+            .max(PRICE_TICK)
+    }
+}
+
+/// Exit escalation settings plus the price sources and alerting it needs.
+#[derive(Clone)]
+pub struct ExitEscalation {
+    pub config: ExitEscalationConfig,
+    pub price_cache: PriceCache,
+    /// Orderbook fallback when the cache has no fresh quote.
+    pub clob_client: Option<ClobClient>,
+    pub notifier: Option<Arc<Notifier>>,
+}
+
+impl ExitEscalation {
+    /// Current best bid for a token: fresh WS quote, else the orderbook.
+    pub async fn best_bid(&self, token_id: &str) -> Option<Decimal> {
+        if let Some(bid) = self.price_cache.best_bid(token_id).await {
+            return Some(bid);
+        }
+        let clob = self.clob_client.as_ref()?;
+        match clob.get_order_book(token_id).await {
+            Ok(book) => book.bids.iter().map(|l| l.price).max(),
+            Err(e) => {
+                tracing::warn!(error = %e, token_id, "Exit escalation: failed to fetch orderbook");
+                None
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ExitEscalationConfig {
+        ExitEscalationConfig {
+            reprice_secs: 30,
+            step_pct: Decimal::from(5),
+            max_reprices: 3,
+        }
+    }
+
+    #[test]
+    fn test_applies_to_loss_exits_only() {
+        let c = config();
+        assert!(c.applies_to(Some("stop_loss")));
+        assert!(c.applies_to(Some("resolution_exit")));
+        assert!(c.applies_to(None));
+        assert!(!c.applies_to(Some("take_profit")));
+        assert!(!ExitEscalationConfig::default().applies_to(Some("stop_loss")));
+    }
+
+    #[test]
+    fn test_reprice_steps_below_bid() {
+        let c = config();
+        // 0.40 bid, first reprice 5% below
+        assert_eq!(c.reprice(Decimal::new(40, 2), Decimal::new(45, 2), 1), Decimal::new(38, 2));
+        // Never above the previous order even if the bid recovered
+        assert_eq!(c.reprice(Decimal::new(50, 2), Decimal::new(40, 2), 2), Decimal::new(36, 2));
+        // Floored at one tick
+        assert_eq!(c.reprice(Decimal::new(1, 2), Decimal::new(1, 2), 3), PRICE_TICK);
+    }
+}
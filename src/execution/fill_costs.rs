@@ -0,0 +1,282 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use polymarket_client_sdk::clob::types::response::{OpenOrderResponse, TradeResponse};
+use polymarket_client_sdk::clob::types::TraderSide;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::sync::RwLock;
+
+use crate::db::order_repo;
+use crate::polymarket::balance::BalanceChecker;
+use crate::polymarket::trading::TradingClient;
+
+/// Default MATIC/USD source (CoinGecko simple price for POL, formerly MATIC).
+pub const DEFAULT_MATIC_PRICE_URL: &str =
+    "https://api.coingecko.com/api/v3/simple/price?ids=polygon-ecosystem-token&vs_currencies=usd";
+
+/// Reuse a fetched MATIC price for this long.
+const PRICE_TTL_MINS: i64 = 10;
+
+/// Reuse a computed cost rate for this long.
+const COST_RATE_TTL_MINS: i64 = 10;
+
+/// Average the cost rate over orders filled in this many days.
+const COST_RATE_WINDOW_DAYS: i64 = 30;
+
+/// Fee and gas paid for one filled order, in USDC.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FillCosts {
+    pub fee_usdc: Decimal,
+    /// None when gas could not be priced (no RPC, receipt or MATIC price).
+    pub gas_usdc: Option<Decimal>,
+}
+
+/// Polymarket fee on a fill: `rate × min(price, 1 − price) × size`, so fees
+/// shrink towards the extremes where the payout is nearly certain.
+pub fn trade_fee_usdc(fee_rate_bps: Decimal, price: Decimal, size: Decimal) -> Decimal {
+    let rate = fee_rate_bps / Decimal::from(10_000);
+    rate * price.min(Decimal::ONE - price).max(Decimal::ZERO) * size
+}
+
+/// Our fee rate, price and matched size in a trade, whether we were the
+/// taker or one of the makers.
+fn our_side_of_trade(trade: &TradeResponse, order_id: &str) -> Option<(Decimal, Decimal, Decimal)> {
+    if trade.trader_side == TraderSide::Taker && trade.taker_order_id == order_id {
+        return Some((trade.fee_rate_bps, trade.price, trade.size));
+    }
+    trade
+        .maker_orders
+        .iter()
+        .find(|m| m.order_id == order_id)
+        .map(|m| (m.fee_rate_bps, m.price, m.matched_amount))
+}
+
+/// First `usd` quote in a CoinGecko-style `{"<id>": {"usd": 0.23}}` body.
+fn parse_usd_price(body: &serde_json::Value) -> Option<Decimal> {
+    body.as_object()?
+        .values()
+        .find_map(|v| v.get("usd"))
+        .and_then(|p| p.to_string().parse().ok())
+        .filter(|p: &Decimal| *p > Decimal::ZERO)
+}
+
+/// Last fetched price and when it was fetched.
+type CachedPrice = Arc<RwLock<Option<(Decimal, DateTime<Utc>)>>>;
+
+/// MATIC/USD price from an HTTP API, cached for a few minutes.
+#[derive(Clone)]
+pub struct MaticPriceFeed {
+    http: reqwest::Client,
+    url: String,
+    cached: CachedPrice,
+}
+
+impl MaticPriceFeed {
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            http: reqwest::Client::new(),
+            url: url.into(),
+            cached: Arc::new(RwLock::new(None)),
+        }
+    }
+
+    pub async fn usd_price(&self) -> Option<Decimal> {
+        if let Some((price, at)) = *self.cached.read().await {
+            if Utc::now() - at < Duration::minutes(PRICE_TTL_MINS) {
+                return Some(price);
+            }
+        }
+
+        let fetched = async {
+            let body: serde_json::Value = self
+                .http
+                .get(&self.url)
+                .timeout(std::time::Duration::from_secs(10))
+                .send()
+                .await?
+                .error_for_status()?
+                .json()
+                .await?;
+            parse_usd_price(&body).ok_or_else(|| anyhow::anyhow!("no usd price in response"))
+        }
+        .await;
+
+        match fetched {
+            Ok(price) => {
+                *self.cached.write().await = Some((price, Utc::now()));
+                Some(price)
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to fetch MATIC price — using last known");
+                self.cached.read().await.map(|(price, _)| price)
+            }
+        }
+    }
+}
+
+/// Realised fee + gas as a fraction of filled notional over the last
+/// `COST_RATE_WINDOW_DAYS`, recomputed at most every `COST_RATE_TTL_MINS`.
+#[derive(Debug, Clone, Default)]
+pub struct CostRate {
+    cached: CachedPrice,
+}
+
+impl CostRate {
+    pub async fn get(&self, pool: &PgPool) -> Decimal {
+        if let Some((rate, at)) = *self.cached.read().await {
+            if Utc::now() - at < Duration::minutes(COST_RATE_TTL_MINS) {
+                return rate;
+            }
+        }
+
+        match order_repo::get_cost_rate_since(pool, Utc::now() - Duration::days(COST_RATE_WINDOW_DAYS)).await {
+            Ok(rate) => {
+                let rate = rate.unwrap_or(Decimal::ZERO);
+                *self.cached.write().await = Some((rate, Utc::now()));
+                rate
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to load cost rate — using last known");
+                self.cached.read().await.map(|(rate, _)| rate).unwrap_or(Decimal::ZERO)
+            }
+        }
+    }
+}
+
+/// Works out fee and gas for filled CLOB orders from their trades.
+#[derive(Clone)]
+pub struct FillCostEstimator {
+    trading_client: Arc<TradingClient>,
+    balance_checker: Option<Arc<BalanceChecker>>,
+    matic_price: MaticPriceFeed,
+}
+
+impl FillCostEstimator {
+    pub fn new(
+        trading_client: Arc<TradingClient>,
+        balance_checker: Option<Arc<BalanceChecker>>,
+        matic_price: MaticPriceFeed,
+    ) -> Self {
+        Self {
+            trading_client,
+            balance_checker,
+            matic_price,
+        }
+    }
+
+    /// Costs of a matched order. None if its trades could not be loaded.
+    pub async fn estimate(&self, order: &OpenOrderResponse) -> Option<FillCosts> {
+        if order.associate_trades.is_empty() {
+            return None;
+        }
+
+        let mut fee_usdc = Decimal::ZERO;
+        let mut tx_hashes = HashSet::new();
+        for trade_id in &order.associate_trades {
+            let trade = match self.trading_client.get_trade(trade_id).await {
+                Ok(Some(t)) => t,
+                Ok(None) => {
+                    tracing::warn!(trade_id = %trade_id, "Fill costs: trade not found");
+                    return None;
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, trade_id = %trade_id, "Fill costs: failed to fetch trade");
+                    return None;
+                }
+            };
+            if let Some((rate, price, size)) = our_side_of_trade(&trade, &order.id) {
+                fee_usdc += trade_fee_usdc(rate, price, size);
+            }
+            tx_hashes.insert(trade.transaction_hash.to_string());
+        }
+
+        Some(FillCosts {
+            fee_usdc,
+            gas_usdc: self.gas_usdc(&tx_hashes).await,
+        })
+    }
+
+    /// Volume-weighted price of our side of a matched order's trades. None if
+    /// it has none or they could not be loaded.
+    pub async fn fill_price(&self, order: &OpenOrderResponse) -> Option<Decimal> {
+        let mut notional = Decimal::ZERO;
+        let mut matched = Decimal::ZERO;
+        for trade_id in &order.associate_trades {
+            let trade = match self.trading_client.get_trade(trade_id).await {
+                Ok(Some(t)) => t,
+                Ok(None) => {
+                    tracing::warn!(trade_id = %trade_id, "Fill price: trade not found");
+                    return None;
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, trade_id = %trade_id, "Fill price: failed to fetch trade");
+                    return None;
+                }
+            };
+            if let Some((_, price, size)) = our_side_of_trade(&trade, &order.id) {
+                notional += price * size;
+                matched += size;
+            }
+        }
+
+        (matched > Decimal::ZERO).then(|| notional / matched)
+    }
+
+    /// Gas our signer paid across the settlement transactions, in USDC.
+    async fn gas_usdc(&self, tx_hashes: &HashSet<String>) -> Option<Decimal> {
+        let checker = self.balance_checker.as_ref()?;
+
+        let mut matic = Decimal::ZERO;
+        for tx in tx_hashes {
+            match checker.get_gas_paid_matic(tx).await {
+                Ok(paid) => matic += paid,
+                Err(e) => {
+                    tracing::debug!(error = %e, tx_hash = %tx, "Fill costs: gas lookup failed");
+                    return None;
+                }
+            }
+        }
+
+        if matic.is_zero() {
+            return Some(Decimal::ZERO);
+        }
+        self.matic_price.usd_price().await.map(|price| matic * price)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trade_fee_usdc() {
+        // 200 bps on 100 shares at 0.30 → 0.02 × 0.30 × 100
+        assert_eq!(
+            trade_fee_usdc(Decimal::from(200), Decimal::new(30, 2), Decimal::from(100)),
+            Decimal::new(6, 1)
+        );
+        // Priced off the cheaper side near certainty
+        assert_eq!(
+            trade_fee_usdc(Decimal::from(200), Decimal::new(95, 2), Decimal::from(100)),
+            Decimal::new(1, 1)
+        );
+        assert_eq!(
+            trade_fee_usdc(Decimal::ZERO, Decimal::new(50, 2), Decimal::from(100)),
+            Decimal::ZERO
+        );
+    }
+
+    #[test]
+    fn test_parse_usd_price() {
+        let body = serde_json::json!({"polygon-ecosystem-token": {"usd": 0.2345}});
+        assert_eq!(parse_usd_price(&body), Some(Decimal::new(2345, 4)));
+        assert_eq!(parse_usd_price(&serde_json::json!({})), None);
+        assert_eq!(parse_usd_price(&serde_json::json!({"x": {"usd": 0}})), None);
+    }
+}
@@ -0,0 +1,149 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use crate::db::position_repo;
+use crate::models::Position;
+
+/// `copy_orders.strategy` for hedge buys of the complementary outcome token.
+pub const HEDGE_STRATEGY: &str = "hedge";
+
+/// Hedging settings: instead of stopping out, a losing position is partially
+/// covered by buying the opposite outcome, which pays out if ours loses.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HedgeConfig {
+    /// Unrealized loss (percent of entry) that triggers a hedge. Zero disables hedging.
+    pub loss_pct: Decimal,
+    /// Hedge size as a percent of the position's token size.
+    pub ratio_pct: Decimal,
+}
+
+impl Default for HedgeConfig {
+    fn default() -> Self {
+        Self {
+            loss_pct: Decimal::ZERO,
+            ratio_pct: Decimal::from(50),
+        }
+    }
+}
+
+impl HedgeConfig {
+    pub fn enabled(&self) -> bool {
+        self.loss_pct > Decimal::ZERO && self.ratio_pct > Decimal::ZERO
+    }
+
+    /// True if `pos` is down at least `loss_pct` and has not been hedged yet.
+    /// Hedge legs themselves are never hedged.
+    pub fn should_hedge(&self, pos: &Position, pnl_pct: Decimal) -> bool {
+        self.enabled()
+            && pos.hedged_at.is_none()
+            && pos.hedge_of.is_none()
+            && pnl_pct <= -self.loss_pct
+    }
+
+    /// Number of complementary tokens to buy for `pos`.
+    pub fn hedge_size(&self, pos: &Position) -> Decimal {
+        (pos.size * self.ratio_pct / Decimal::ONE_HUNDRED).round_dp(2)
+    }
+}
+
+/// Find the complementary outcome of `token_id` in a binary market, given the
+/// market's `clob_token_ids` and `outcomes` JSON arrays from active_markets.
+/// Returns (token_id, outcome label).
+pub fn complementary_token(
+    clob_token_ids: &str,
+    outcomes: Option<&str>,
+    token_id: &str,
+) -> Option<(String, String)> {
+    let tokens: Vec<String> = serde_json::from_str(clob_token_ids).ok()?;
+    if tokens.len() != 2 {
+        return None;
+    }
+    let idx = tokens.iter().position(|t| t == token_id)?;
+    let other = 1 - idx;
+
+    let label = outcomes
+        .and_then(|s| serde_json::from_str::<Vec<String>>(s).ok())
+        .and_then(|o| o.get(other).cloned())
+        .unwrap_or_else(|| if other == 0 { "Yes".into() } else { "No".into() });
+
+    Some((tokens[other].clone(), label))
+}
+
+/// True if `pos`'s sleeve already holds `hedge_token`. A hedge bought into
+/// that sleeve would merge into the held position and turn all of it into a
+/// hedge leg held to resolution, so such positions are not hedged.
+pub async fn complementary_token_held(pool: &PgPool, pos: &Position, hedge_token: &str) -> anyhow::Result<bool> {
+    Ok(position_repo::get_position_by_token_id(pool, hedge_token, &pos.sleeve).await?.is_some())
+}
+
+/// Link a filled hedge leg to the hedged position it covers in the same
+/// market (the open, hedged position on the other token).
+pub async fn link_hedge_leg(pool: &PgPool, hedge: &Position, market_key: &str) {
+    let positions = match position_repo::get_positions_for_market(pool, market_key).await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::warn!(error = %e, "Hedge: failed to load market positions");
+            return;
+        }
+    };
+
+    let Some(hedged) = positions
+        .iter()
+        .find(|p| p.hedged_at.is_some() && p.hedge_of.is_none() && p.token_id != hedge.token_id)
+    else {
+        tracing::warn!(position_id = %hedge.id, "Hedge: no hedged position found for leg");
+        return;
+    };
+
+    if let Err(e) = position_repo::set_hedge_of(pool, hedge.id, hedged.id).await {
+        tracing::error!(error = %e, "Hedge: failed to link hedge leg");
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_complementary_token() {
+        let tokens = r#"["111", "222"]"#;
+        let outcomes = Some(r#"["Up", "Down"]"#);
+
+        assert_eq!(
+            complementary_token(tokens, outcomes, "111"),
+            Some(("222".into(), "Down".into()))
+        );
+        assert_eq!(
+            complementary_token(tokens, None, "222"),
+            Some(("111".into(), "Yes".into()))
+        );
+        assert_eq!(complementary_token(tokens, outcomes, "333"), None);
+        // Not a binary market
+        assert_eq!(complementary_token(r#"["1", "2", "3"]"#, None, "1"), None);
+    }
+
+    #[test]
+    fn test_hedge_trigger_and_size() {
+        let config = HedgeConfig {
+            loss_pct: Decimal::from(10),
+            ratio_pct: Decimal::from(50),
+        };
+        assert!(!HedgeConfig::default().enabled());
+
+        let mut pos = Position {
+            size: Decimal::from(101),
+            ..Position::test_fixture()
+        };
+
+        assert!(config.should_hedge(&pos, Decimal::from(-12)));
+        assert!(!config.should_hedge(&pos, Decimal::from(-5)));
+        assert_eq!(config.hedge_size(&pos), Decimal::new(5050, 2));
+
+        pos.hedged_at = Some(chrono::Utc::now());
+        assert!(!config.should_hedge(&pos, Decimal::from(-12)));
+    }
+}
@@ -1,5 +1,16 @@
 pub mod capital_pool;
+pub mod circuit_breaker;
+pub mod close_preview;
+pub mod compliance;
 pub mod copy_engine;
+pub mod copy_profiles;
+pub mod exit_escalation;
+pub mod fill_costs;
+pub mod hedging;
 pub mod order_executor;
+pub mod order_rules;
+pub mod portfolio_risk;
 pub mod position_sizer;
 pub mod risk_manager;
+pub mod scale_in;
+pub mod sleeves;
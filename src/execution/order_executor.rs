@@ -1,10 +1,22 @@
-use rust_decimal::Decimal;
+use std::collections::HashMap;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use chrono::Utc;
+use rust_decimal::{Decimal, RoundingStrategy};
 use thiserror::Error;
 
+use crate::models::execution_snapshot::BOOK_SNAPSHOT_LEVELS;
+use crate::models::{BookLevel, BookSnapshot, SignalOrigin};
 use crate::polymarket::clob_client::ClobClient;
 use crate::polymarket::trading::TradingClient;
+use crate::polymarket::types::{ApiOrderBook, ApiOrderBookLevel};
+
+use super::order_rules::{normalize_order, MarketRules, OrderRuleViolation, OrderRulesConfig};
+use super::risk_manager::{check_slippage, RiskViolation, SharedRiskLimits};
 
-use super::risk_manager::{check_slippage, RiskLimits, RiskViolation};
+/// How long a market's fetched tick size and minimum are reused.
+const MARKET_RULES_TTL: Duration = Duration::from_secs(300);
 
 #[derive(Debug, Error)]
 pub enum ExecutionError {
@@ -22,6 +34,9 @@ pub enum ExecutionError {
 
     #[error("order rejected by CLOB: {0}")]
     OrderRejected(String),
+
+    #[error("order violates market rules: {0}")]
+    MarketRules(#[from] OrderRuleViolation),
 }
 
 /// Result of an executed order.
@@ -34,6 +49,90 @@ pub struct OrderResult {
     pub order_id: Option<String>,
     /// True if the order is resting on the book (maker), false if filled immediately (taker).
     pub resting: bool,
+    /// Orderbook seen right before placing the order (live orders only).
+    pub book: Option<BookSnapshot>,
+}
+
+/// How a live order meets the book.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ExecutionMode {
+    /// Post-only at the top of our own side of the book (zero taker fees).
+    Maker,
+    /// Limit at the best opposite price; any unfilled remainder rests.
+    Taker,
+    /// Fill-and-kill limit at the worst price the slippage limit allows, so it
+    /// sweeps the book up to that price and never rests.
+    Marketable,
+}
+
+impl ExecutionMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExecutionMode::Maker => "maker",
+            ExecutionMode::Taker => "taker",
+            ExecutionMode::Marketable => "marketable",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "maker" => Some(ExecutionMode::Maker),
+            "taker" => Some(ExecutionMode::Taker),
+            "marketable" | "market" => Some(ExecutionMode::Marketable),
+            _ => None,
+        }
+    }
+}
+
+/// Execution mode per signal origin.
+///
+/// Parsed from `origin:mode` pairs (e.g. `whale:marketable,exit:marketable`);
+/// origins not listed use the default mode.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExecutionModes {
+    default: ExecutionMode,
+    overrides: HashMap<SignalOrigin, ExecutionMode>,
+}
+
+impl ExecutionModes {
+    pub fn parse(raw: &str, default: ExecutionMode) -> Self {
+        let mut overrides = HashMap::new();
+
+        for pair in raw.split(',').filter(|p| !p.trim().is_empty()) {
+            let Some((origin, mode)) = pair.split_once(':') else {
+                tracing::warn!(entry = %pair.trim(), "Ignoring invalid execution mode");
+                continue;
+            };
+            let (Some(origin), Some(mode)) = (SignalOrigin::parse(origin), ExecutionMode::parse(mode))
+            else {
+                tracing::warn!(entry = %pair.trim(), "Ignoring invalid execution mode");
+                continue;
+            };
+            overrides.insert(origin, mode);
+        }
+
+        Self { default, overrides }
+    }
+
+    pub fn for_origin(&self, origin: SignalOrigin) -> ExecutionMode {
+        self.overrides.get(&origin).copied().unwrap_or(self.default)
+    }
+}
+
+/// Worst price a marketable order may fill at: `max_slippage_pct` past the
+/// target, rounded to a whole cent towards the target and kept inside (0, 1).
+pub fn max_acceptable_price(side: &str, target_price: Decimal, max_slippage_pct: Decimal) -> Decimal {
+    let min_price = Decimal::new(1, 2);
+    let max_price = Decimal::new(99, 2);
+
+    let guard = if side.eq_ignore_ascii_case("SELL") {
+        (target_price * (Decimal::ONE - max_slippage_pct))
+            .round_dp_with_strategy(2, RoundingStrategy::ToPositiveInfinity)
+    } else {
+        (target_price * (Decimal::ONE + max_slippage_pct))
+            .round_dp_with_strategy(2, RoundingStrategy::ToNegativeInfinity)
+    };
+    guard.clamp(min_price, max_price)
 }
 
 /// Executes orders against the Polymarket CLOB.
@@ -42,45 +141,87 @@ pub struct OrderResult {
 /// - **dry_run=true**: Logs intent, returns simulated success.
 /// - **dry_run=false + TradingClient**: Real on-chain order via SDK.
 /// - **No TradingClient**: Falls back to dry-run regardless of flag.
+///
+/// Live orders are rounded to the market's tick size and lot precision before
+/// placement; orders below the venue minimum are rejected without a CLOB call.
 pub struct OrderExecutor {
     clob_client: Option<ClobClient>,
     trading_client: Option<TradingClient>,
-    risk_limits: RiskLimits,
+    risk_limits: SharedRiskLimits,
     dry_run: bool,
-    maker_mode: bool,
+    order_rules: OrderRulesConfig,
+    /// Per-token venue rules seen recently, with when they were fetched.
+    market_rules: Mutex<HashMap<String, (MarketRules, Instant)>>,
 }
 
 impl OrderExecutor {
     pub fn new(
         trading_client: Option<TradingClient>,
         clob_client: Option<ClobClient>,
-        risk_limits: RiskLimits,
+        risk_limits: SharedRiskLimits,
         dry_run: bool,
-        maker_mode: bool,
+        order_rules: OrderRulesConfig,
     ) -> Self {
         Self {
             clob_client,
             trading_client,
             risk_limits,
             dry_run,
-            maker_mode,
+            order_rules,
+            market_rules: Mutex::new(HashMap::new()),
+        }
+    }
+
+    fn cache_market_rules(&self, token_id: &str, rules: MarketRules) {
+        if let Ok(mut cache) = self.market_rules.lock() {
+            cache.insert(token_id.to_string(), (rules, Instant::now()));
+        }
+    }
+
+    /// Tick size and minimum for `token_id`: cached from a recent book, else
+    /// the tick size endpoint, else the configured defaults.
+    async fn market_rules(&self, token_id: &str) -> MarketRules {
+        let cached = self.market_rules.lock().ok().and_then(|cache| {
+            cache
+                .get(token_id)
+                .filter(|(_, fetched)| fetched.elapsed() < MARKET_RULES_TTL)
+                .map(|(rules, _)| *rules)
+        });
+        if let Some(rules) = cached {
+            return rules;
+        }
+
+        let Some(client) = &self.clob_client else {
+            return self.order_rules.market_rules(None, None);
+        };
+        match client.get_tick_size(token_id).await {
+            Ok(tick) => {
+                let rules = self.order_rules.market_rules(Some(tick), None);
+                self.cache_market_rules(token_id, rules);
+                rules
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, token_id, "Failed to fetch tick size, using defaults");
+                self.order_rules.market_rules(None, None)
+            }
         }
     }
 
     /// Execute a copy-trade order:
     /// 1. Fetch orderbook to get current price
     /// 2. Check slippage vs target
-    /// 3. Place limit order (or dry-run log)
+    /// 3. Place the order for `mode` (or dry-run log)
     pub async fn execute(
         &self,
         token_id: &str,
         side: &str,
         size: Decimal,
         target_price: Decimal,
+        mode: ExecutionMode,
     ) -> Result<OrderResult, ExecutionError> {
         // If dry_run or no trading client → simulated execution
         if self.dry_run || self.trading_client.is_none() {
-            let mode = if self.trading_client.is_none() {
+            let run_mode = if self.trading_client.is_none() {
                 "no-wallet"
             } else {
                 "dry-run"
@@ -90,7 +231,8 @@ impl OrderExecutor {
                 side,
                 size = %size,
                 target_price = %target_price,
-                mode,
+                mode = run_mode,
+                execution = mode.as_str(),
                 "[DRY-RUN] Would place limit order"
             );
             return Ok(OrderResult {
@@ -99,18 +241,28 @@ impl OrderExecutor {
                 success: true,
                 order_id: None,
                 resting: false,
+                book: None,
             });
         }
 
         // --- Live execution path ---
 
         // 1. Fetch orderbook for slippage validation (use ClobClient if available)
+        let mut book_snapshot = None;
         let current_price = if let Some(client) = &self.clob_client {
+            let started = Instant::now();
             match client.get_order_book(token_id).await {
                 Ok(book) => {
+                    let fetch_ms = started.elapsed().as_millis().min(i32::MAX as u128) as i32;
+                    book_snapshot = Some(snapshot_book(&book, fetch_ms));
+                    self.cache_market_rules(
+                        token_id,
+                        self.order_rules.market_rules(book.tick_size, book.min_order_size),
+                    );
+
                     match side.to_uppercase().as_str() {
                         "BUY" => {
-                            if self.maker_mode {
+                            if mode == ExecutionMode::Maker {
                                 // Maker: use best_bid (rest on buy side of the book)
                                 book.bids
                                     .first()
@@ -125,7 +277,7 @@ impl OrderExecutor {
                             }
                         }
                         "SELL" => {
-                            if self.maker_mode {
+                            if mode == ExecutionMode::Maker {
                                 // Maker: use best_ask (rest on sell side of the book)
                                 book.asks
                                     .first()
@@ -156,35 +308,48 @@ impl OrderExecutor {
         };
 
         // 2. Slippage check
-        let slippage = check_slippage(target_price, current_price, &self.risk_limits)?;
+        let risk_limits = self.risk_limits.read().await.clone();
+        let slippage = check_slippage(target_price, current_price, &risk_limits)?;
+
+        // Marketable orders cross at the slippage guard rather than the top of book
+        let order_price = match mode {
+            ExecutionMode::Marketable => {
+                max_acceptable_price(side, target_price, risk_limits.max_slippage_pct)
+            }
+            _ => current_price,
+        };
+
+        // 3. Round onto the market's tick grid and lot size
+        let rules = self.market_rules(token_id).await;
+        let order = normalize_order(side, size, order_price, &rules, &self.order_rules)?;
+        let (size, order_price) = (order.size, order.price);
 
-        let mode_label = if self.maker_mode { "maker" } else { "taker" };
         tracing::info!(
             token_id,
             side,
             size = %size,
             target_price = %target_price,
             current_price = %current_price,
+            order_price = %order_price,
+            tick_size = %rules.tick_size,
             slippage = %slippage,
-            mode = mode_label,
+            mode = mode.as_str(),
             "Placing live limit order on CLOB"
         );
 
-        // 3. Place real order via SDK (maker uses post_only, taker uses regular limit)
+        // 4. Place real order via SDK (maker uses post_only, taker a regular
+        // limit, marketable a fill-and-kill limit at the guard price)
         let trading = self.trading_client.as_ref().expect("checked above");
-        let response = if self.maker_mode {
-            trading
-                .place_maker_order(token_id, side, size, current_price)
-                .await
-                .map_err(|e| ExecutionError::ClobError(e.to_string()))?
-        } else {
-            trading
-                .place_limit_order(token_id, side, size, current_price)
-                .await
-                .map_err(|e| ExecutionError::ClobError(e.to_string()))?
-        };
+        let response = match mode {
+            ExecutionMode::Maker => trading.place_maker_order(token_id, side, size, order_price).await,
+            ExecutionMode::Taker => trading.place_limit_order(token_id, side, size, order_price).await,
+            ExecutionMode::Marketable => {
+                trading.place_marketable_order(token_id, side, size, order_price).await
+            }
+        }
+        .map_err(|e| ExecutionError::ClobError(e.to_string()))?;
 
-        // 4. Check response
+        // 5. Check response
         if !response.success {
             let msg = response
                 .error_msg
@@ -198,23 +363,128 @@ impl OrderExecutor {
             Some(response.order_id.clone())
         };
 
+        // Whatever matched on placement tells the real price; an order that
+        // only rests is priced at its limit until the fill poller sees it fill
+        let matched_price = average_fill_price(side, response.making_amount, response.taking_amount);
+        let fill_price = matched_price.unwrap_or(order_price);
+        let slippage = match matched_price {
+            Some(price) if !target_price.is_zero() => ((price - target_price) / target_price).abs(),
+            _ => slippage,
+        };
+
         tracing::info!(
             order_id = ?order_id,
-            fill_price = %current_price,
+            fill_price = %fill_price,
+            matched = matched_price.is_some(),
             slippage = %slippage,
             "Live order placed successfully"
         );
 
         Ok(OrderResult {
-            fill_price: current_price,
+            fill_price,
             slippage,
             success: true,
             order_id,
-            resting: self.maker_mode,
+            resting: mode == ExecutionMode::Maker,
+            book: book_snapshot,
+        })
+    }
+
+    /// Place a post-only order resting at exactly `price` — a scale-in ladder
+    /// rung below (BUY) or above (SELL) the market. No slippage check: the
+    /// price is at least as good as the signal price by construction.
+    pub async fn place_resting(
+        &self,
+        token_id: &str,
+        side: &str,
+        size: Decimal,
+        price: Decimal,
+    ) -> Result<OrderResult, ExecutionError> {
+        let Some(trading) = self.trading_client.as_ref().filter(|_| !self.dry_run) else {
+            tracing::info!(
+                token_id,
+                side,
+                size = %size,
+                price = %price,
+                "[DRY-RUN] Would place resting ladder order"
+            );
+            return Ok(OrderResult {
+                fill_price: price,
+                slippage: Decimal::ZERO,
+                success: true,
+                order_id: None,
+                resting: false,
+                book: None,
+            });
+        };
+
+        let rules = self.market_rules(token_id).await;
+        let order = normalize_order(side, size, price, &rules, &self.order_rules)?;
+        let price = order.price;
+
+        let response = trading
+            .place_maker_order(token_id, side, order.size, price)
+            .await
+            .map_err(|e| ExecutionError::ClobError(e.to_string()))?;
+
+        if !response.success {
+            let msg = response
+                .error_msg
+                .unwrap_or_else(|| "unknown CLOB error".into());
+            return Err(ExecutionError::OrderRejected(msg));
+        }
+
+        let order_id = (!response.order_id.is_empty()).then(|| response.order_id.clone());
+        tracing::info!(order_id = ?order_id, price = %price, "Resting ladder order placed");
+
+        Ok(OrderResult {
+            fill_price: price,
+            slippage: Decimal::ZERO,
+            success: true,
+            order_id,
+            resting: true,
+            book: None,
         })
     }
 }
 
+/// Average price of what an order matched on placement, from the amounts the
+/// CLOB reports: a BUY gives USDC (making) for tokens (taking), a SELL the
+/// reverse. None when nothing matched.
+pub fn average_fill_price(side: &str, making_amount: Decimal, taking_amount: Decimal) -> Option<Decimal> {
+    let (usdc, tokens) = if side.eq_ignore_ascii_case("SELL") {
+        (taking_amount, making_amount)
+    } else {
+        (making_amount, taking_amount)
+    };
+    if usdc <= Decimal::ZERO || tokens <= Decimal::ZERO {
+        return None;
+    }
+    Some(usdc / tokens)
+}
+
+/// Keep the top `BOOK_SNAPSHOT_LEVELS` of each side of a fetched orderbook.
+fn snapshot_book(book: &ApiOrderBook, fetch_ms: i32) -> BookSnapshot {
+    let top = |levels: &[ApiOrderBookLevel]| -> Vec<BookLevel> {
+        levels
+            .iter()
+            .take(BOOK_SNAPSHOT_LEVELS)
+            .map(|l| BookLevel {
+                price: l.price,
+                size: l.size,
+            })
+            .collect()
+    };
+
+    BookSnapshot {
+        bids: top(&book.bids),
+        asks: top(&book.asks),
+        book_hash: book.hash.clone(),
+        captured_at: Utc::now(),
+        fetch_ms,
+    }
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -222,16 +492,18 @@ impl OrderExecutor {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::execution::risk_manager::RiskLimits;
 
     #[tokio::test]
     async fn test_dry_run_returns_success() {
-        let executor = OrderExecutor::new(None, None, RiskLimits::default(), true, false);
+        let executor = OrderExecutor::new(None, None, RiskLimits::default().into_shared(), true, Default::default());
         let result = executor
             .execute(
                 "12345",
                 "BUY",
                 Decimal::from(50),
                 Decimal::new(55, 2), // 0.55
+                ExecutionMode::Maker,
             )
             .await;
         assert!(result.is_ok());
@@ -246,13 +518,14 @@ mod tests {
     #[tokio::test]
     async fn test_no_trading_client_auto_dry_run() {
         // Even with dry_run=false, missing trading_client forces dry-run
-        let executor = OrderExecutor::new(None, None, RiskLimits::default(), false, false);
+        let executor = OrderExecutor::new(None, None, RiskLimits::default().into_shared(), false, Default::default());
         let result = executor
             .execute(
                 "12345",
                 "SELL",
                 Decimal::from(100),
                 Decimal::new(40, 2),
+                ExecutionMode::Marketable,
             )
             .await;
         assert!(result.is_ok());
@@ -261,4 +534,69 @@ mod tests {
         assert!(r.order_id.is_none());
         assert!(!r.resting);
     }
+
+    #[test]
+    fn test_max_acceptable_price() {
+        let slippage = Decimal::new(3, 2); // 3%
+        // 0.55 × 1.03 = 0.5665 → 0.56 for a BUY, 0.55 × 0.97 = 0.5335 → 0.54 for a SELL
+        assert_eq!(max_acceptable_price("BUY", Decimal::new(55, 2), slippage), Decimal::new(56, 2));
+        assert_eq!(max_acceptable_price("SELL", Decimal::new(55, 2), slippage), Decimal::new(54, 2));
+        // Never outside (0, 1)
+        assert_eq!(max_acceptable_price("BUY", Decimal::new(98, 2), slippage), Decimal::new(99, 2));
+        assert_eq!(max_acceptable_price("SELL", Decimal::new(1, 2), slippage), Decimal::new(1, 2));
+    }
+
+    #[test]
+    fn test_average_fill_price() {
+        // Bought 200 tokens for 92 USDC
+        assert_eq!(
+            average_fill_price("BUY", Decimal::from(92), Decimal::from(200)),
+            Some(Decimal::new(46, 2))
+        );
+        // Sold 50 tokens for 30 USDC
+        assert_eq!(
+            average_fill_price("SELL", Decimal::from(50), Decimal::from(30)),
+            Some(Decimal::new(6, 1))
+        );
+        // Nothing matched: the order only rests
+        assert_eq!(average_fill_price("BUY", Decimal::ZERO, Decimal::ZERO), None);
+    }
+
+    #[test]
+    fn test_execution_modes_per_origin() {
+        let modes = ExecutionModes::parse("whale:marketable, exit:taker, bogus:maker", ExecutionMode::Maker);
+        assert_eq!(modes.for_origin(SignalOrigin::Whale), ExecutionMode::Marketable);
+        assert_eq!(modes.for_origin(SignalOrigin::Exit), ExecutionMode::Taker);
+        assert_eq!(modes.for_origin(SignalOrigin::Basket), ExecutionMode::Maker);
+
+        let empty = ExecutionModes::parse("", ExecutionMode::Taker);
+        assert_eq!(empty.for_origin(SignalOrigin::Manual), ExecutionMode::Taker);
+    }
+
+    #[test]
+    fn test_snapshot_book_keeps_top_levels() {
+        let level = |p: i64, s: i64| ApiOrderBookLevel {
+            price: Decimal::new(p, 2),
+            size: Decimal::from(s),
+        };
+        let book = ApiOrderBook {
+            market: None,
+            asset_id: Some("12345".into()),
+            bids: (0..8).map(|i| level(50 - i, 10)).collect(),
+            asks: vec![level(52, 3), level(53, 7)],
+            hash: Some("abc".into()),
+            timestamp: None,
+            tick_size: None,
+            min_order_size: None,
+        };
+
+        let snap = snapshot_book(&book, 42);
+        assert_eq!(snap.bids.len(), BOOK_SNAPSHOT_LEVELS);
+        assert_eq!(snap.asks.len(), 2);
+        assert_eq!(snap.best_bid(), Some(Decimal::new(50, 2)));
+        assert_eq!(snap.best_ask(), Some(Decimal::new(52, 2)));
+        assert_eq!(BookSnapshot::depth(&snap.bids), Decimal::from(50));
+        assert_eq!(BookSnapshot::depth(&snap.asks), Decimal::from(10));
+        assert_eq!(snap.fetch_ms, 42);
+    }
 }
@@ -0,0 +1,215 @@
+use rust_decimal::{Decimal, RoundingStrategy};
+use thiserror::Error;
+
+/// How a price off the market's tick grid is moved onto it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceRounding {
+    /// Never worse than computed: BUY prices round down, SELL prices up.
+    Conservative,
+    /// To the nearest tick, midpoint away from zero.
+    Nearest,
+}
+
+impl PriceRounding {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PriceRounding::Conservative => "conservative",
+            PriceRounding::Nearest => "nearest",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "conservative" => Some(PriceRounding::Conservative),
+            "nearest" => Some(PriceRounding::Nearest),
+            _ => None,
+        }
+    }
+}
+
+/// Rounding policy and the venue rules assumed when a market's own are unknown.
+#[derive(Debug, Clone)]
+pub struct OrderRulesConfig {
+    pub default_tick_size: Decimal,
+    /// Minimum order size in shares.
+    pub default_min_order_size: Decimal,
+    /// Decimal places order sizes are truncated to.
+    pub size_decimals: u32,
+    pub price_rounding: PriceRounding,
+}
+
+impl Default for OrderRulesConfig {
+    fn default() -> Self {
+        Self {
+            default_tick_size: Decimal::new(1, 2),
+            default_min_order_size: Decimal::from(5),
+            size_decimals: 2,
+            price_rounding: PriceRounding::Conservative,
+        }
+    }
+}
+
+impl OrderRulesConfig {
+    /// A market's rules, filling whatever the venue didn't report from the defaults.
+    pub fn market_rules(&self, tick_size: Option<Decimal>, min_order_size: Option<Decimal>) -> MarketRules {
+        MarketRules {
+            tick_size: tick_size.filter(|t| *t > Decimal::ZERO).unwrap_or(self.default_tick_size),
+            min_order_size: min_order_size.unwrap_or(self.default_min_order_size),
+        }
+    }
+}
+
+/// Price tick and minimum size the venue enforces for one market.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MarketRules {
+    pub tick_size: Decimal,
+    pub min_order_size: Decimal,
+}
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum OrderRuleViolation {
+    #[error("size {size} is below the market minimum of {min} shares")]
+    BelowMinSize { size: Decimal, min: Decimal },
+
+    #[error("price {price} is outside the tradable range for tick size {tick}")]
+    PriceOutOfRange { price: Decimal, tick: Decimal },
+}
+
+/// An order's size and price after rounding to the market's rules.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizedOrder {
+    pub size: Decimal,
+    pub price: Decimal,
+}
+
+/// Round `price` onto the market's tick grid and truncate `size` to the lot
+/// precision, then check the result against the venue's limits: prices must
+/// stay within [tick, 1 − tick] and sizes at or above the market minimum.
+pub fn normalize_order(
+    side: &str,
+    size: Decimal,
+    price: Decimal,
+    rules: &MarketRules,
+    config: &OrderRulesConfig,
+) -> Result<NormalizedOrder, OrderRuleViolation> {
+    let tick = rules.tick_size;
+    let ticks = price / tick;
+    let ticks = match config.price_rounding {
+        PriceRounding::Conservative if side.eq_ignore_ascii_case("SELL") => ticks.ceil(),
+        PriceRounding::Conservative => ticks.floor(),
+        PriceRounding::Nearest => ticks.round_dp_with_strategy(0, RoundingStrategy::MidpointAwayFromZero),
+    };
+    let rounded_price = (ticks * tick).normalize();
+    if rounded_price < tick || rounded_price > Decimal::ONE - tick {
+        return Err(OrderRuleViolation::PriceOutOfRange { price, tick });
+    }
+
+    let rounded_size = size
+        .round_dp_with_strategy(config.size_decimals, RoundingStrategy::ToZero)
+        .normalize();
+    if rounded_size < rules.min_order_size || rounded_size <= Decimal::ZERO {
+        return Err(OrderRuleViolation::BelowMinSize {
+            size,
+            min: rules.min_order_size,
+        });
+    }
+
+    Ok(NormalizedOrder {
+        size: rounded_size,
+        price: rounded_price,
+    })
+}
+
+/// Truncate `size` to whole lots at `size_decimals`, as `normalize_order` does.
+pub fn truncate_size(size: Decimal, size_decimals: u32) -> Decimal {
+    size.round_dp_with_strategy(size_decimals, RoundingStrategy::ToZero)
+}
+
+/// True when `size` is less than one lot at `size_decimals` — too small to
+/// place as an order. A sold position's dust is closed rather than left open.
+pub fn is_dust(size: Decimal, size_decimals: u32) -> bool {
+    truncate_size(size, size_decimals) <= Decimal::ZERO
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(tick: Decimal) -> MarketRules {
+        MarketRules {
+            tick_size: tick,
+            min_order_size: Decimal::from(5),
+        }
+    }
+
+    #[test]
+    fn test_normalize_order_rounds_to_tick_and_lot() {
+        let config = OrderRulesConfig::default();
+        let cent = rules(Decimal::new(1, 2));
+
+        let buy = normalize_order("BUY", Decimal::new(123456, 4), Decimal::new(5567, 4), &cent, &config).unwrap();
+        assert_eq!(buy.price, Decimal::new(55, 2));
+        assert_eq!(buy.size, Decimal::new(1234, 2));
+        let sell = normalize_order("SELL", Decimal::from(10), Decimal::new(5533, 4), &cent, &config).unwrap();
+        assert_eq!(sell.price, Decimal::new(56, 2));
+
+        // Finer ticks keep more precision
+        let milli = rules(Decimal::new(1, 3));
+        let buy = normalize_order("BUY", Decimal::from(10), Decimal::new(9678, 4), &milli, &config).unwrap();
+        assert_eq!(buy.price, Decimal::new(967, 3));
+
+        let nearest = OrderRulesConfig {
+            price_rounding: PriceRounding::Nearest,
+            ..OrderRulesConfig::default()
+        };
+        let buy = normalize_order("BUY", Decimal::from(10), Decimal::new(5567, 4), &cent, &nearest).unwrap();
+        assert_eq!(buy.price, Decimal::new(56, 2));
+    }
+
+    #[test]
+    fn test_normalize_order_rejects_venue_violations() {
+        let config = OrderRulesConfig::default();
+        let cent = rules(Decimal::new(1, 2));
+
+        let err = normalize_order("BUY", Decimal::new(4999, 3), Decimal::new(50, 2), &cent, &config).unwrap_err();
+        assert_eq!(
+            err,
+            OrderRuleViolation::BelowMinSize {
+                size: Decimal::new(4999, 3),
+                min: Decimal::from(5)
+            }
+        );
+        // Rounds down to zero ticks, or up to a full dollar
+        assert!(matches!(
+            normalize_order("BUY", Decimal::from(10), Decimal::new(5, 3), &cent, &config),
+            Err(OrderRuleViolation::PriceOutOfRange { .. })
+        ));
+        assert!(matches!(
+            normalize_order("SELL", Decimal::from(10), Decimal::new(995, 3), &cent, &config),
+            Err(OrderRuleViolation::PriceOutOfRange { .. })
+        ));
+    }
+
+    #[test]
+    fn test_market_rules_fall_back_to_defaults() {
+        let config = OrderRulesConfig::default();
+        let rules = config.market_rules(Some(Decimal::new(1, 3)), None);
+        assert_eq!(rules.tick_size, Decimal::new(1, 3));
+        assert_eq!(rules.min_order_size, Decimal::from(5));
+        assert_eq!(config.market_rules(Some(Decimal::ZERO), None).tick_size, Decimal::new(1, 2));
+        assert_eq!(PriceRounding::parse("Nearest"), Some(PriceRounding::Nearest));
+    }
+
+    #[test]
+    fn test_is_dust_below_one_lot() {
+        assert!(is_dust(Decimal::new(3456, 6), 2));
+        assert!(is_dust(Decimal::ZERO, 2));
+        assert!(!is_dust(Decimal::new(1, 2), 2));
+        // Sizes are truncated to lots, never rounded up past what is held
+        assert_eq!(truncate_size(Decimal::new(12_999, 3), 1), Decimal::new(129, 1));
+    }
+}
@@ -0,0 +1,224 @@
+use std::collections::HashMap;
+
+use rust_decimal::{Decimal, MathematicalOps};
+
+use crate::models::Position;
+
+use super::risk_manager::RiskViolation;
+
+/// One-sided z-score for a 95% confidence tail (1.645).
+pub const VAR_Z_95: Decimal = Decimal::from_parts(1645, 0, 0, false, 3);
+
+/// Capital at risk in a single binary outcome.
+///
+/// A position either resolves to 1 (win) or 0 (lose everything marked in it).
+/// The outcome price is used as the market-implied win probability.
+#[derive(Debug, Clone)]
+pub struct PositionExposure {
+    pub market_id: String,
+    pub token_id: String,
+    pub size: Decimal,
+    pub price: Decimal,
+}
+
+impl PositionExposure {
+    /// Build from an open position, marking at the last known price.
+    pub fn from_position(pos: &Position) -> Self {
+        Self {
+            market_id: pos.market_key().to_string(),
+            token_id: pos.token_id.clone(),
+            size: pos.size,
+            price: pos.current_price.unwrap_or(pos.avg_entry_price),
+        }
+    }
+
+    /// Mark value lost if the outcome resolves against us.
+    pub fn loss_if_wrong(&self) -> Decimal {
+        self.size * self.price
+    }
+}
+
+/// Portfolio-level tail-loss estimate.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TailRiskEstimate {
+    /// Sum of probability-weighted losses.
+    pub expected_loss: Decimal,
+    /// Standard deviation of loss, treating markets as independent.
+    pub std_dev: Decimal,
+    /// expected_loss + z · std_dev, capped at `max_loss`.
+    pub value_at_risk: Decimal,
+    /// Loss if every position resolved against us.
+    pub max_loss: Decimal,
+}
+
+/// Estimate tail loss across a set of binary exposures.
+///
+/// Exposures are first combined per market, since positions in one market
+/// resolve together: all holdings of a token win or lose as one, and exactly
+/// one outcome of the market wins. Each market's loss is then a discrete
+/// variable over which outcome wins (priced as its probability, with any
+/// unpriced remainder going to an outcome we don't hold). Assuming markets
+/// are independent, their means and variances add, and the VaR is the
+/// normal-approximation quantile at `z`, never exceeding the total marked
+/// exposure.
+pub fn estimate_tail_loss(exposures: &[PositionExposure], z: Decimal) -> TailRiskEstimate {
+    // market → token → (loss if the token loses, shares held)
+    let mut by_market: HashMap<&str, HashMap<&str, (Decimal, Decimal)>> = HashMap::new();
+    for e in exposures {
+        let held = by_market
+            .entry(e.market_id.as_str())
+            .or_default()
+            .entry(e.token_id.as_str())
+            .or_insert((Decimal::ZERO, Decimal::ZERO));
+        held.0 += e.loss_if_wrong();
+        held.1 += e.size;
+    }
+
+    let mut expected_loss = Decimal::ZERO;
+    let mut variance = Decimal::ZERO;
+    let mut max_loss = Decimal::ZERO;
+
+    for tokens in by_market.values() {
+        let total: Decimal = tokens.values().map(|(loss, _)| *loss).sum();
+        // Win probability of each held token: its value-weighted mark
+        let mut outcomes: Vec<(Decimal, Decimal)> = tokens
+            .values()
+            .filter(|(_, shares)| !shares.is_zero())
+            .map(|&(loss, shares)| ((loss / shares).clamp(Decimal::ZERO, Decimal::ONE), total - loss))
+            .collect();
+        let priced: Decimal = outcomes.iter().map(|(p, _)| *p).sum();
+        if priced > Decimal::ONE {
+            for (p, _) in outcomes.iter_mut() {
+                *p /= priced;
+            }
+        } else {
+            // Some outcome we don't hold wins: everything held is lost
+            outcomes.push((Decimal::ONE - priced, total));
+        }
+
+        let mean: Decimal = outcomes.iter().map(|(p, loss)| p * loss).sum();
+        let second_moment: Decimal = outcomes.iter().map(|(p, loss)| p * loss * loss).sum();
+        expected_loss += mean;
+        variance += (second_moment - mean * mean).max(Decimal::ZERO);
+        max_loss += total;
+    }
+
+    let std_dev = variance.sqrt().unwrap_or(Decimal::ZERO);
+    let value_at_risk = (expected_loss + z * std_dev).min(max_loss);
+
+    TailRiskEstimate {
+        expected_loss,
+        std_dev,
+        value_at_risk,
+        max_loss,
+    }
+}
+
+/// Check whether adding `pending` to the open book keeps the VaR estimate
+/// within `max_tail_loss_pct` of `bankroll`.
+pub fn check_tail_risk(
+    open: &[PositionExposure],
+    pending: PositionExposure,
+    bankroll: Decimal,
+    max_tail_loss_pct: Decimal,
+) -> Result<TailRiskEstimate, RiskViolation> {
+    let mut exposures = open.to_vec();
+    exposures.push(pending);
+
+    let estimate = estimate_tail_loss(&exposures, VAR_Z_95);
+    let limit = bankroll * max_tail_loss_pct;
+
+    if estimate.value_at_risk > limit {
+        return Err(RiskViolation::TailRiskExceeded {
+            var: estimate.value_at_risk.round_dp(2),
+            limit: limit.round_dp(2),
+        });
+    }
+
+    Ok(estimate)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exposure(market: &str, size: i64, price: Decimal) -> PositionExposure {
+        leg(market, &format!("{market}_yes"), size, price)
+    }
+
+    fn leg(market: &str, token: &str, size: i64, price: Decimal) -> PositionExposure {
+        PositionExposure {
+            market_id: market.into(),
+            token_id: token.into(),
+            size: Decimal::from(size),
+            price,
+        }
+    }
+
+    #[test]
+    fn test_empty_portfolio_has_no_risk() {
+        let est = estimate_tail_loss(&[], VAR_Z_95);
+        assert_eq!(est.value_at_risk, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_single_position_var_capped_at_max_loss() {
+        // 100 shares at 0.50 → $50 at risk, 50% chance of losing it
+        let est = estimate_tail_loss(&[exposure("m1", 100, Decimal::new(5, 1))], VAR_Z_95);
+        assert_eq!(est.max_loss, Decimal::from(50));
+        assert_eq!(est.expected_loss, Decimal::from(25));
+        assert_eq!(est.value_at_risk, Decimal::from(50));
+    }
+
+    #[test]
+    fn test_diversified_var_below_max_loss() {
+        let exposures: Vec<_> = (0..10)
+            .map(|i| exposure(&format!("m{i}"), 100, Decimal::new(8, 1)))
+            .collect();
+        let est = estimate_tail_loss(&exposures, VAR_Z_95);
+        // 10 × $80, each with 20% loss probability
+        assert_eq!(est.max_loss, Decimal::from(800));
+        assert_eq!(est.expected_loss, Decimal::from(160));
+        assert!(est.value_at_risk > est.expected_loss);
+        assert!(est.value_at_risk < est.max_loss);
+    }
+
+    #[test]
+    fn test_positions_in_one_market_are_not_diversified() {
+        let price = Decimal::new(8, 1);
+        let independent = estimate_tail_loss(&[exposure("m1", 100, price), exposure("m2", 100, price)], VAR_Z_95);
+        // Two sleeves holding the same token lose together
+        let same_token = estimate_tail_loss(&[exposure("m1", 100, price), exposure("m1", 100, price)], VAR_Z_95);
+        assert_eq!(same_token.expected_loss, independent.expected_loss);
+        assert!(same_token.std_dev > independent.std_dev);
+        assert!(same_token.value_at_risk > independent.value_at_risk);
+
+        // Both legs of one market: exactly one of them is lost
+        let legs = estimate_tail_loss(
+            &[leg("m1", "yes", 100, Decimal::new(5, 1)), leg("m1", "no", 100, Decimal::new(5, 1))],
+            VAR_Z_95,
+        );
+        assert_eq!(legs.max_loss, Decimal::from(100));
+        assert_eq!(legs.expected_loss, Decimal::from(50));
+        assert_eq!(legs.std_dev, Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_check_tail_risk_blocks_large_order() {
+        let open = vec![exposure("m1", 1000, Decimal::new(5, 1))];
+        let pending = exposure("m2", 1000, Decimal::new(5, 1));
+        let result = check_tail_risk(&open, pending, Decimal::from(1000), Decimal::new(25, 2));
+        assert!(matches!(result, Err(RiskViolation::TailRiskExceeded { .. })));
+    }
+
+    #[test]
+    fn test_check_tail_risk_allows_small_order() {
+        let pending = exposure("m1", 10, Decimal::new(6, 1));
+        let result = check_tail_risk(&[], pending, Decimal::from(1000), Decimal::new(25, 2));
+        assert!(result.is_ok());
+    }
+}
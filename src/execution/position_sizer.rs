@@ -1,3 +1,4 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use std::fmt;
@@ -30,7 +31,8 @@ impl fmt::Display for SizingStrategy {
     }
 }
 
-/// Calculate position size based on strategy.
+/// Calculate position size based on strategy, scaled by `multiplier`.
+#[allow(clippy::too_many_arguments)]
 pub fn calculate_size(
     strategy: SizingStrategy,
     bankroll: Decimal,
@@ -39,6 +41,7 @@ pub fn calculate_size(
     whale_kelly: Decimal,
     base_amount: Decimal,
     signal_strength: Decimal,
+    multiplier: Decimal,
 ) -> Decimal {
     let raw = match strategy {
         SizingStrategy::Proportional => {
@@ -52,8 +55,8 @@ pub fn calculate_size(
         }
     };
 
-    // Clamp: at least $1, at most the bankroll
-    raw.max(Decimal::ZERO).min(bankroll)
+    // Clamp after scaling so a multiplier can't push the size past the bankroll
+    (raw * multiplier).max(Decimal::ZERO).min(bankroll)
 }
 
 /// Proportional: mirror the whale's position percentage of our bankroll.
@@ -85,6 +88,187 @@ fn kelly_size(bankroll: Decimal, _win_rate: Decimal, kelly_fraction: Decimal) ->
     bankroll * half_kelly
 }
 
+// ---------------------------------------------------------------------------
+// Net whale flow sizing
+// ---------------------------------------------------------------------------
+
+/// Sizing for market-level net flow signals, which have no single whale to
+/// take a win rate or Kelly fraction from.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FlowSizing {
+    /// Size at a net flow of exactly `reference_notional`.
+    pub base_amount: Decimal,
+    /// Net flow (USDC) that earns `base_amount`; the flow gate.
+    pub reference_notional: Decimal,
+    /// Cap on the net flow / reference multiple.
+    pub max_multiplier: Decimal,
+}
+
+impl Default for FlowSizing {
+    fn default() -> Self {
+        Self {
+            base_amount: Decimal::from(50),
+            reference_notional: Decimal::from(50_000),
+            max_multiplier: Decimal::from(3),
+        }
+    }
+}
+
+/// Base amount scaled by how far net flow exceeds the reference, at most
+/// `max_multiplier` times and never more than the bankroll.
+pub fn flow_size(config: &FlowSizing, bankroll: Decimal, net_notional: Decimal) -> Decimal {
+    let multiple = if config.reference_notional > Decimal::ZERO {
+        (net_notional / config.reference_notional).min(config.max_multiplier)
+    } else {
+        Decimal::ONE
+    };
+    (config.base_amount * multiple).max(Decimal::ZERO).min(bankroll)
+}
+
+/// Sizing for basket consensus signals: no single whale's Kelly is behind
+/// them, so size follows how strong the consensus is and how good its voters are.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConsensusSizing {
+    /// Size at 100% consensus among voters at `reference_win_rate`.
+    pub base_amount: Decimal,
+    /// Mean voter win rate that earns `base_amount`.
+    pub reference_win_rate: Decimal,
+    /// Cap on the voter win rate / reference multiple.
+    pub max_multiplier: Decimal,
+}
+
+impl Default for ConsensusSizing {
+    fn default() -> Self {
+        Self {
+            base_amount: Decimal::from(50),
+            reference_win_rate: Decimal::new(6, 1),
+            max_multiplier: Decimal::new(15, 1),
+        }
+    }
+}
+
+/// Base amount scaled by the consensus share and by the voters' mean win
+/// rate relative to the reference (at most `max_multiplier` times), never
+/// more than the bankroll.
+pub fn consensus_size(
+    config: &ConsensusSizing,
+    bankroll: Decimal,
+    consensus_pct: Decimal,
+    voter_win_rate: Decimal,
+) -> Decimal {
+    let quality = if config.reference_win_rate > Decimal::ZERO {
+        (voter_win_rate / config.reference_win_rate).min(config.max_multiplier)
+    } else {
+        Decimal::ONE
+    };
+    (config.base_amount * consensus_pct.min(Decimal::ONE) * quality)
+        .max(Decimal::ZERO)
+        .min(bankroll)
+}
+
+// ---------------------------------------------------------------------------
+// Soft-launch ramp-up
+// ---------------------------------------------------------------------------
+
+/// The ramp-up only looks at live fills from this many days back.
+pub const RAMP_UP_WINDOW_DAYS: i64 = 90;
+
+/// Ramp-up for a new live deployment: every position is capped at a small
+/// notional during a soft launch, then the cap is raised step by step while
+/// realized tracking error stays within bounds, until it reaches the
+/// configured position limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RampUpConfig {
+    pub enabled: bool,
+    /// Per-position notional cap (USDC) during the soft launch.
+    pub initial_cap: Decimal,
+    /// The soft launch lasts at least this many live fills...
+    pub launch_trades: usize,
+    /// ...and at least this many days after the first live fill.
+    pub launch_days: i64,
+    /// Live fills per ramp step after the soft launch.
+    pub step_trades: usize,
+    /// Cap multiplier applied per good step.
+    pub step_multiplier: Decimal,
+    /// Max mean slippage (percent) of a step's fills for the cap to go up;
+    /// a step above it takes the cap one step back down.
+    pub max_tracking_error: Decimal,
+}
+
+impl Default for RampUpConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            initial_cap: Decimal::from(25),
+            launch_trades: 20,
+            launch_days: 3,
+            step_trades: 10,
+            step_multiplier: Decimal::from(2),
+            max_tracking_error: Decimal::from(2),
+        }
+    }
+}
+
+/// A live fill as seen by the ramp-up: when it filled and its slippage
+/// (percent) versus the whale's price.
+#[derive(Debug, Clone, PartialEq)]
+pub struct RampFill {
+    pub filled_at: DateTime<Utc>,
+    pub slippage: Decimal,
+}
+
+/// Per-position notional cap (USDC) from the live fills so far, oldest first.
+/// None when the ramp-up is disabled or the cap has reached `position_limit`.
+pub fn ramp_up_cap(
+    config: &RampUpConfig,
+    fills: &[RampFill],
+    position_limit: Decimal,
+    now: DateTime<Utc>,
+) -> Option<Decimal> {
+    if !config.enabled {
+        return None;
+    }
+
+    let Some(first) = fills.first() else {
+        return Some(config.initial_cap);
+    };
+    let launch_end = first.filled_at + chrono::Duration::days(config.launch_days);
+    if fills.len() < config.launch_trades || now < launch_end || config.step_trades == 0 {
+        return Some(config.initial_cap);
+    }
+
+    // Fills after the launch ones are scored in steps of `step_trades`
+    let mut steps: u32 = 0;
+    for step in fills[config.launch_trades..].chunks_exact(config.step_trades) {
+        let tracking_error =
+            step.iter().map(|f| f.slippage.abs()).sum::<Decimal>() / Decimal::from(step.len() as i64);
+        if tracking_error <= config.max_tracking_error {
+            steps += 1;
+        } else {
+            steps = steps.saturating_sub(1);
+        }
+
+        // Reaching the configured limit ends the ramp for good
+        if ramp_step_cap(config, steps) >= position_limit {
+            return None;
+        }
+    }
+
+    Some(ramp_step_cap(config, steps))
+}
+
+fn ramp_step_cap(config: &RampUpConfig, steps: u32) -> Decimal {
+    (0..steps).fold(config.initial_cap, |cap, _| cap * config.step_multiplier)
+}
+
+/// Shrink `size` (shares) so its notional at `price` fits within `cap`.
+pub fn apply_notional_cap(size: Decimal, price: Decimal, cap: Decimal) -> Decimal {
+    if price <= Decimal::ZERO || size * price <= cap {
+        return size;
+    }
+    (cap / price).round_dp(2)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -123,6 +307,107 @@ mod tests {
         assert_eq!(size, Decimal::ZERO);
     }
 
+    fn ramp_config() -> RampUpConfig {
+        RampUpConfig {
+            enabled: true,
+            initial_cap: Decimal::from(10),
+            launch_trades: 4,
+            launch_days: 1,
+            step_trades: 2,
+            step_multiplier: Decimal::from(2),
+            max_tracking_error: Decimal::from(2),
+        }
+    }
+
+    /// `slippages` filled one hour apart, starting two days before `now`.
+    fn ramp_fills(now: DateTime<Utc>, slippages: &[i64]) -> Vec<RampFill> {
+        slippages
+            .iter()
+            .enumerate()
+            .map(|(i, &s)| RampFill {
+                filled_at: now - chrono::Duration::days(2) + chrono::Duration::hours(i as i64),
+                slippage: Decimal::from(s),
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_ramp_up_soft_launch() {
+        let now = Utc::now();
+        let config = ramp_config();
+        let limit = Decimal::from(1_000);
+
+        assert_eq!(ramp_up_cap(&config, &[], limit, now), Some(Decimal::from(10)));
+        // Too few fills yet
+        assert_eq!(ramp_up_cap(&config, &ramp_fills(now, &[0, 0, 0]), limit, now), Some(Decimal::from(10)));
+        // Enough fills, but still inside the launch days
+        let fills = ramp_fills(now, &[0, 0, 0, 0, 0, 0]);
+        assert_eq!(ramp_up_cap(&config, &fills, limit, now - chrono::Duration::hours(36)), Some(Decimal::from(10)));
+
+        let disabled = RampUpConfig { enabled: false, ..config };
+        assert_eq!(ramp_up_cap(&disabled, &[], limit, now), None);
+    }
+
+    #[test]
+    fn test_ramp_up_steps_on_tracking_error() {
+        let now = Utc::now();
+        let config = ramp_config();
+        let limit = Decimal::from(1_000);
+
+        // Launch (4 fills) then two good steps → 10 × 2 × 2
+        let fills = ramp_fills(now, &[5, 5, 5, 5, 1, 1, 2, 2]);
+        assert_eq!(ramp_up_cap(&config, &fills, limit, now), Some(Decimal::from(40)));
+
+        // Good step, then a step over the bound → back to the initial cap
+        let fills = ramp_fills(now, &[0, 0, 0, 0, 1, 1, 3, 3]);
+        assert_eq!(ramp_up_cap(&config, &fills, limit, now), Some(Decimal::from(10)));
+
+        // Incomplete step does not count
+        let fills = ramp_fills(now, &[0, 0, 0, 0, 1]);
+        assert_eq!(ramp_up_cap(&config, &fills, limit, now), Some(Decimal::from(10)));
+
+        // Reaching the position limit completes the ramp
+        assert_eq!(ramp_up_cap(&config, &ramp_fills(now, &[0, 0, 0, 0, 1, 1, 1, 1]), Decimal::from(40), now), None);
+    }
+
+    #[test]
+    fn test_flow_size() {
+        let config = FlowSizing::default();
+        let bankroll = Decimal::from(1_000);
+        assert_eq!(flow_size(&config, bankroll, Decimal::from(75_000)), Decimal::from(75));
+        // Multiple capped at 3×, then at the bankroll
+        assert_eq!(flow_size(&config, bankroll, Decimal::from(1_000_000)), Decimal::from(150));
+        assert_eq!(flow_size(&config, Decimal::from(100), Decimal::from(1_000_000)), Decimal::from(100));
+    }
+
+    #[test]
+    fn test_consensus_size() {
+        let config = ConsensusSizing::default();
+        let bankroll = Decimal::from(1_000);
+        // 80% consensus of reference-quality voters
+        assert_eq!(
+            consensus_size(&config, bankroll, Decimal::new(8, 1), Decimal::new(6, 1)),
+            Decimal::from(40)
+        );
+        // Stronger voters size up, capped at 1.5×, then at the bankroll
+        assert_eq!(
+            consensus_size(&config, bankroll, Decimal::ONE, Decimal::new(75, 2)),
+            Decimal::new(625, 1)
+        );
+        assert_eq!(consensus_size(&config, bankroll, Decimal::ONE, Decimal::ONE), Decimal::from(75));
+        assert_eq!(
+            consensus_size(&config, Decimal::from(30), Decimal::ONE, Decimal::ONE),
+            Decimal::from(30)
+        );
+    }
+
+    #[test]
+    fn test_apply_notional_cap() {
+        let price = Decimal::new(5, 1);
+        assert_eq!(apply_notional_cap(Decimal::from(100), price, Decimal::from(25)), Decimal::from(50));
+        assert_eq!(apply_notional_cap(Decimal::from(40), price, Decimal::from(25)), Decimal::from(40));
+    }
+
     #[test]
     fn test_calculate_size_clamped() {
         // Ensure result doesn't exceed bankroll
@@ -134,7 +419,21 @@ mod tests {
             Decimal::ZERO,
             Decimal::from(500),    // base_amount > bankroll
             Decimal::ONE,          // signal_strength
+            Decimal::ONE,
         );
         assert_eq!(size, Decimal::from(100)); // clamped to bankroll
+
+        // The multiplier is applied before the clamp, not on top of it
+        let size = calculate_size(
+            SizingStrategy::Fixed,
+            Decimal::from(100),
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::ZERO,
+            Decimal::from(80),
+            Decimal::ONE,
+            Decimal::from(2),
+        );
+        assert_eq!(size, Decimal::from(100));
     }
 }
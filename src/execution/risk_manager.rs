@@ -1,9 +1,17 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
+use tokio::sync::RwLock;
+
+/// Risk limits shared between the API and the trading tasks, so edits made
+/// through `PATCH /api/risk-limits` apply without a restart.
+pub type SharedRiskLimits = Arc<RwLock<RiskLimits>>;
 
-/// Configurable risk limits.
-#[derive(Debug, Clone, Serialize, Deserialize)]
+/// Configurable risk limits, persisted in the `risk_limits` table.
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
 pub struct RiskLimits {
     /// Max single position as fraction of bankroll (default 20%).
     pub max_position_pct: Decimal,
@@ -15,6 +23,22 @@ pub struct RiskLimits {
     pub min_spread_to_resolution: Decimal,
     /// Max acceptable slippage percentage (default 3%).
     pub max_slippage_pct: Decimal,
+    /// Max 95% VaR of open positions as fraction of bankroll (default 25%).
+    pub max_tail_loss_pct: Decimal,
+    /// Drawdown from peak price that triggers a trailing-stop exit, in percent (default 10).
+    pub trailing_stop_pct: Decimal,
+    /// Force-exit positions held longer than this many days; 0 disables (default 7).
+    pub max_position_hold_days: i64,
+    /// Reject entries into markets resolving within this many hours; 0 disables (default 24).
+    pub min_hours_to_resolution: i64,
+    /// Min MATIC in the signer wallet for gas before live orders; 0 disables (default 0.5).
+    pub min_matic_balance: Decimal,
+    /// USDC kept back for fees — a BUY needs cost + buffer available (default 5).
+    pub usdc_fee_buffer: Decimal,
+    /// Force-exit positions whose market ends within this many hours; 0 disables (default 0).
+    pub exit_hours_before_resolution: i64,
+    /// Fraction of capital never committed to entries, kept for exits and fees (default 10%).
+    pub reserve_floor_pct: Decimal,
 }
 
 impl Default for RiskLimits {
@@ -25,7 +49,64 @@ impl Default for RiskLimits {
             max_daily_loss: Decimal::from(500),
             min_spread_to_resolution: Decimal::new(5, 2), // 0.05
             max_slippage_pct: Decimal::new(3, 2),         // 0.03
+            max_tail_loss_pct: Decimal::new(25, 2),       // 0.25
+            trailing_stop_pct: Decimal::from(10),
+            max_position_hold_days: 7,
+            min_hours_to_resolution: 24,
+            min_matic_balance: Decimal::new(5, 1), // 0.5
+            usdc_fee_buffer: Decimal::from(5),
+            exit_hours_before_resolution: 0,
+            reserve_floor_pct: Decimal::new(10, 2), // 0.10
+        }
+    }
+}
+
+impl RiskLimits {
+    pub fn into_shared(self) -> SharedRiskLimits {
+        Arc::new(RwLock::new(self))
+    }
+
+    /// Reject limits that would silently disable or invert a check.
+    pub fn validate(&self) -> Result<(), String> {
+        let fractions = [
+            ("max_position_pct", self.max_position_pct),
+            ("min_spread_to_resolution", self.min_spread_to_resolution),
+            ("max_slippage_pct", self.max_slippage_pct),
+            ("max_tail_loss_pct", self.max_tail_loss_pct),
+        ];
+        for (name, value) in fractions {
+            if value <= Decimal::ZERO || value > Decimal::ONE {
+                return Err(format!("{name} must be in (0, 1], got {value}"));
+            }
+        }
+        if self.min_spread_to_resolution >= Decimal::new(5, 1) {
+            return Err("min_spread_to_resolution must be below 0.5".into());
+        }
+        if self.max_open_positions < 1 {
+            return Err("max_open_positions must be at least 1".into());
+        }
+        if self.max_daily_loss <= Decimal::ZERO {
+            return Err("max_daily_loss must be positive".into());
         }
+        if self.trailing_stop_pct < Decimal::ZERO || self.trailing_stop_pct >= Decimal::ONE_HUNDRED {
+            return Err("trailing_stop_pct must be in [0, 100)".into());
+        }
+        if self.max_position_hold_days < 0 {
+            return Err("max_position_hold_days must not be negative".into());
+        }
+        if self.min_hours_to_resolution < 0 {
+            return Err("min_hours_to_resolution must not be negative".into());
+        }
+        if self.exit_hours_before_resolution < 0 {
+            return Err("exit_hours_before_resolution must not be negative".into());
+        }
+        if self.reserve_floor_pct < Decimal::ZERO || self.reserve_floor_pct >= Decimal::ONE {
+            return Err("reserve_floor_pct must be in [0, 1)".into());
+        }
+        if self.min_matic_balance < Decimal::ZERO || self.usdc_fee_buffer < Decimal::ZERO {
+            return Err("wallet buffers must not be negative".into());
+        }
+        Ok(())
     }
 }
 
@@ -37,8 +118,9 @@ pub struct PortfolioSnapshot {
     pub daily_pnl: Decimal,
 }
 
-/// Risk check violation.
-#[derive(Debug, Error)]
+/// Risk check violation. Serializes as `{"rule": "...", <values>}` for the audit table.
+#[derive(Debug, Error, Serialize)]
+#[serde(tag = "rule", rename_all = "snake_case")]
 pub enum RiskViolation {
     #[error("position size {size} exceeds max {max} ({pct}% of bankroll)")]
     PositionTooLarge {
@@ -58,6 +140,47 @@ pub enum RiskViolation {
 
     #[error("slippage too high: {actual}% > max {max}%")]
     SlippageTooHigh { actual: Decimal, max: Decimal },
+
+    #[error("portfolio tail loss too high: VaR {var} > limit {limit}")]
+    TailRiskExceeded { var: Decimal, limit: Decimal },
+
+    #[error("market resolves too soon: {hours_left}h left, min {min}h")]
+    TooCloseToResolution { hours_left: i64, min: i64 },
+
+    #[error("gas balance too low: {balance} MATIC, min {min}")]
+    GasBalanceLow { balance: Decimal, min: Decimal },
+
+    #[error("USDC fee buffer breached: {available} available, {required} required")]
+    FeeBufferBreached { available: Decimal, required: Decimal },
+
+    #[error("reserve floor breached: {remaining} USDC left after order, floor {floor}")]
+    ReserveFloorBreached { remaining: Decimal, floor: Decimal },
+
+    #[error("compliance check {check} failed: {reason}")]
+    ComplianceBlocked { check: String, reason: String },
+
+    #[error("open positions unavailable for the tail-risk check: {reason}")]
+    PortfolioUnavailable { reason: String },
+}
+
+impl RiskViolation {
+    /// Stable rule name, matching the serialized `rule` tag.
+    pub fn rule(&self) -> &'static str {
+        match self {
+            RiskViolation::PositionTooLarge { .. } => "position_too_large",
+            RiskViolation::TooManyPositions { .. } => "too_many_positions",
+            RiskViolation::DailyLossExceeded { .. } => "daily_loss_exceeded",
+            RiskViolation::SpreadTooNarrow { .. } => "spread_too_narrow",
+            RiskViolation::SlippageTooHigh { .. } => "slippage_too_high",
+            RiskViolation::TailRiskExceeded { .. } => "tail_risk_exceeded",
+            RiskViolation::TooCloseToResolution { .. } => "too_close_to_resolution",
+            RiskViolation::GasBalanceLow { .. } => "gas_balance_low",
+            RiskViolation::FeeBufferBreached { .. } => "fee_buffer_breached",
+            RiskViolation::ReserveFloorBreached { .. } => "reserve_floor_breached",
+            RiskViolation::ComplianceBlocked { .. } => "compliance_blocked",
+            RiskViolation::PortfolioUnavailable { .. } => "portfolio_unavailable",
+        }
+    }
 }
 
 /// A pending order to be validated by risk checks.
@@ -133,6 +256,73 @@ pub fn check_slippage(
     Ok(slippage)
 }
 
+/// Reject entries into markets that end within `min_hours_to_resolution`.
+/// Late entries have poor risk/reward and thin exit liquidity. Markets with
+/// an unknown end date pass.
+pub fn check_time_to_resolution(
+    end_date: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    limits: &RiskLimits,
+) -> Result<(), RiskViolation> {
+    if limits.min_hours_to_resolution <= 0 {
+        return Ok(());
+    }
+    let Some(end_date) = end_date else {
+        return Ok(());
+    };
+
+    let hours_left = (end_date - now).num_hours();
+    if hours_left < limits.min_hours_to_resolution {
+        return Err(RiskViolation::TooCloseToResolution {
+            hours_left,
+            min: limits.min_hours_to_resolution,
+        });
+    }
+
+    Ok(())
+}
+
+/// Live orders need MATIC in the signer wallet for gas.
+pub fn check_gas_balance(matic: Decimal, limits: &RiskLimits) -> Result<(), RiskViolation> {
+    if matic < limits.min_matic_balance {
+        return Err(RiskViolation::GasBalanceLow {
+            balance: matic,
+            min: limits.min_matic_balance,
+        });
+    }
+    Ok(())
+}
+
+/// A BUY must leave `usdc_fee_buffer` USDC untouched after paying `order_cost`.
+pub fn check_fee_buffer(
+    usdc: Decimal,
+    order_cost: Decimal,
+    limits: &RiskLimits,
+) -> Result<(), RiskViolation> {
+    let required = order_cost + limits.usdc_fee_buffer;
+    if usdc < required {
+        return Err(RiskViolation::FeeBufferBreached {
+            available: usdc,
+            required,
+        });
+    }
+    Ok(())
+}
+
+/// A BUY must leave the reserve floor untouched, so stop-loss exits and their
+/// fees can always be funded.
+pub fn check_reserve_floor(
+    usdc: Decimal,
+    order_cost: Decimal,
+    floor: Decimal,
+) -> Result<(), RiskViolation> {
+    let remaining = usdc - order_cost;
+    if remaining < floor {
+        return Err(RiskViolation::ReserveFloorBreached { remaining, floor });
+    }
+    Ok(())
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -228,4 +418,90 @@ mod tests {
         );
         assert!(matches!(result, Err(RiskViolation::SlippageTooHigh { .. })));
     }
+
+    #[test]
+    fn test_default_limits_are_valid() {
+        assert!(RiskLimits::default().validate().is_ok());
+    }
+
+    #[test]
+    fn test_validate_rejects_out_of_range() {
+        let limits = RiskLimits {
+            max_position_pct: Decimal::new(15, 1), // 150%
+            ..Default::default()
+        };
+        assert!(limits.validate().is_err());
+
+        let limits = RiskLimits {
+            max_open_positions: 0,
+            ..Default::default()
+        };
+        assert!(limits.validate().is_err());
+    }
+
+    #[test]
+    fn test_time_to_resolution_rejects_late_entry() {
+        let now = Utc::now();
+        let result = check_time_to_resolution(
+            Some(now + chrono::Duration::hours(6)),
+            now,
+            &RiskLimits::default(),
+        );
+        assert!(matches!(result, Err(RiskViolation::TooCloseToResolution { hours_left: 5..=6, .. })));
+    }
+
+    #[test]
+    fn test_time_to_resolution_allows_distant_or_unknown_end() {
+        let now = Utc::now();
+        let limits = RiskLimits::default();
+        assert!(check_time_to_resolution(Some(now + chrono::Duration::days(3)), now, &limits).is_ok());
+        assert!(check_time_to_resolution(None, now, &limits).is_ok());
+
+        let disabled = RiskLimits {
+            min_hours_to_resolution: 0,
+            ..Default::default()
+        };
+        assert!(check_time_to_resolution(Some(now), now, &disabled).is_ok());
+    }
+
+    #[test]
+    fn test_violation_serializes_with_rule_tag() {
+        let violation = RiskViolation::TooManyPositions { current: 10, max: 10 };
+        let json = serde_json::to_value(&violation).unwrap();
+        assert_eq!(json["rule"], violation.rule());
+        assert_eq!(json["current"], 10);
+    }
+
+    #[test]
+    fn test_wallet_buffers() {
+        let limits = RiskLimits::default();
+        assert!(check_gas_balance(Decimal::ONE, &limits).is_ok());
+        assert!(matches!(
+            check_gas_balance(Decimal::new(1, 1), &limits),
+            Err(RiskViolation::GasBalanceLow { .. })
+        ));
+
+        // $100 order needs $105 with the default $5 buffer
+        assert!(check_fee_buffer(Decimal::from(105), Decimal::from(100), &limits).is_ok());
+        assert!(matches!(
+            check_fee_buffer(Decimal::from(104), Decimal::from(100), &limits),
+            Err(RiskViolation::FeeBufferBreached { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reserve_floor() {
+        let floor = Decimal::from(100);
+        assert!(check_reserve_floor(Decimal::from(500), Decimal::from(400), floor).is_ok());
+        assert!(matches!(
+            check_reserve_floor(Decimal::from(500), Decimal::from(401), floor),
+            Err(RiskViolation::ReserveFloorBreached { .. })
+        ));
+
+        let limits = RiskLimits {
+            reserve_floor_pct: Decimal::ONE,
+            ..Default::default()
+        };
+        assert!(limits.validate().is_err());
+    }
 }
@@ -0,0 +1,141 @@
+use rust_decimal::Decimal;
+
+use crate::models::Side;
+
+/// `copy_orders.strategy` for resting ladder rungs after the first tranche.
+pub const LADDER_STRATEGY: &str = "ladder";
+
+/// Polymarket price tick.
+const PRICE_TICK: Decimal = Decimal::from_parts(1, 0, 0, false, 2); // 0.01
+/// Smallest tranche worth placing, in USDC notional.
+const MIN_TRANCHE_NOTIONAL: Decimal = Decimal::ONE;
+
+/// Scale-in (DCA) settings: high-conviction entries are split into a limit
+/// ladder instead of a single order.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ScaleInConfig {
+    /// Minimum signal strength (whale win rate) to ladder in. Zero disables scale-in.
+    pub min_strength: Decimal,
+    /// Total number of tranches, including the first one at market.
+    pub tranches: u32,
+    /// Price improvement per ladder step, in percent of the signal price.
+    pub step_pct: Decimal,
+}
+
+impl Default for ScaleInConfig {
+    fn default() -> Self {
+        Self {
+            min_strength: Decimal::ZERO,
+            tranches: 3,
+            step_pct: Decimal::ONE,
+        }
+    }
+}
+
+impl ScaleInConfig {
+    pub fn applies_to(&self, signal_strength: Decimal) -> bool {
+        self.min_strength > Decimal::ZERO && self.tranches > 1 && signal_strength >= self.min_strength
+    }
+}
+
+/// One rung of the entry ladder.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Tranche {
+    pub size: Decimal,
+    pub price: Decimal,
+}
+
+/// Split `size` into equal tranches: the first at `price`, each following one
+/// `step_pct` further below (BUY) or above (SELL) it, rounded to the tick.
+///
+/// Rungs that would not improve on the previous price or fall below the
+/// minimum notional are dropped and their size folded into the last kept
+/// tranche, so the tranche sizes always sum to `size`.
+pub fn plan_ladder(size: Decimal, price: Decimal, side: Side, config: &ScaleInConfig) -> Vec<Tranche> {
+    let single = vec![Tranche { size, price }];
+    if config.tranches <= 1 || size <= Decimal::ZERO {
+        return single;
+    }
+
+    let per_tranche = (size / Decimal::from(config.tranches)).round_dp(2);
+    if per_tranche * price < MIN_TRANCHE_NOTIONAL {
+        return single;
+    }
+
+    let mut ladder = vec![Tranche { size: per_tranche, price }];
+    for step in 1..config.tranches {
+        let offset = price * config.step_pct * Decimal::from(step) / Decimal::ONE_HUNDRED;
+        let rung_price = match side {
+            Side::Buy => (price - offset).round_dp(2).max(PRICE_TICK),
+            Side::Sell => (price + offset).round_dp(2).min(Decimal::ONE - PRICE_TICK),
+        };
+        let prev = ladder.last().map(|t| t.price).unwrap_or(price);
+        let improves = match side {
+            Side::Buy => rung_price < prev,
+            Side::Sell => rung_price > prev,
+        };
+        if !improves || per_tranche * rung_price < MIN_TRANCHE_NOTIONAL {
+            break;
+        }
+        ladder.push(Tranche {
+            size: per_tranche,
+            price: rung_price,
+        });
+    }
+
+    // Rounding remainder and any dropped rungs go to the last tranche
+    let placed: Decimal = ladder.iter().map(|t| t.size).sum();
+    if let Some(last) = ladder.last_mut() {
+        last.size += size - placed;
+    }
+    ladder
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> ScaleInConfig {
+        ScaleInConfig {
+            min_strength: Decimal::new(70, 2),
+            tranches: 3,
+            step_pct: Decimal::from(2),
+        }
+    }
+
+    #[test]
+    fn test_applies_to_conviction_threshold() {
+        assert!(config().applies_to(Decimal::new(75, 2)));
+        assert!(!config().applies_to(Decimal::new(65, 2)));
+        assert!(!ScaleInConfig::default().applies_to(Decimal::ONE));
+    }
+
+    #[test]
+    fn test_buy_ladder_steps_down_and_sums_to_size() {
+        let ladder = plan_ladder(Decimal::from(100), Decimal::new(50, 2), Side::Buy, &config());
+        let prices: Vec<Decimal> = ladder.iter().map(|t| t.price).collect();
+        assert_eq!(prices, vec![Decimal::new(50, 2), Decimal::new(49, 2), Decimal::new(48, 2)]);
+        assert_eq!(ladder.iter().map(|t| t.size).sum::<Decimal>(), Decimal::from(100));
+        assert_eq!(ladder[2].size, Decimal::new(3334, 2));
+    }
+
+    #[test]
+    fn test_small_or_flat_ladders_collapse() {
+        // Tranches under $1 notional → single order
+        let ladder = plan_ladder(Decimal::from(4), Decimal::new(50, 2), Side::Buy, &config());
+        assert_eq!(ladder.len(), 1);
+
+        // Step too small to move a tick → rungs dropped, size kept
+        let tiny_step = ScaleInConfig {
+            step_pct: Decimal::new(1, 1),
+            ..config()
+        };
+        let ladder = plan_ladder(Decimal::from(100), Decimal::new(20, 2), Side::Buy, &tiny_step);
+        assert_eq!(ladder.len(), 1);
+        assert_eq!(ladder[0].size, Decimal::from(100));
+    }
+}
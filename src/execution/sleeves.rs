@@ -0,0 +1,325 @@
+use std::collections::HashMap;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+
+use crate::models::Sleeve;
+
+use super::capital_pool::CapitalPool;
+use super::copy_profiles::CopyProfile;
+use super::risk_manager::RiskLimits;
+
+/// Default split when `SLEEVE_WEIGHTS` is unset or unparseable.
+pub const DEFAULT_SLEEVE_WEIGHTS: &str = "single_whale:0.7,basket:0.3,momentum:0,flow:0";
+
+/// Fraction of the bankroll earmarked for each sleeve, and for each copy
+/// profile's own pool.
+///
+/// Weights are parsed from `sleeve:weight` pairs (e.g. `single_whale:0.6,basket:0.4`);
+/// profile weights are added with `with_profiles`. If the weights sum to more
+/// than 1 they are normalised; any remainder below 1 is simply left unallocated.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SleeveAllocation {
+    weights: HashMap<Sleeve, Decimal>,
+    profile_weights: HashMap<String, Decimal>,
+}
+
+impl SleeveAllocation {
+    pub fn parse(raw: &str) -> Self {
+        let mut weights: HashMap<Sleeve, Decimal> = HashMap::new();
+
+        for pair in raw.split(',') {
+            let Some((name, weight)) = pair.split_once(':') else {
+                continue;
+            };
+            let (Some(sleeve), Ok(weight)) = (Sleeve::parse(name), weight.trim().parse::<Decimal>())
+            else {
+                tracing::warn!(entry = %pair.trim(), "Ignoring invalid sleeve weight");
+                continue;
+            };
+            if weight >= Decimal::ZERO {
+                weights.insert(sleeve, weight);
+            }
+        }
+
+        if weights.is_empty() && raw != DEFAULT_SLEEVE_WEIGHTS {
+            return Self::parse(DEFAULT_SLEEVE_WEIGHTS);
+        }
+
+        let mut alloc = Self {
+            weights,
+            profile_weights: HashMap::new(),
+        };
+        alloc.normalise();
+        alloc
+    }
+
+    /// Add each copy profile's weight, re-normalising across sleeves and profiles.
+    pub fn with_profiles(mut self, profiles: &[CopyProfile]) -> Self {
+        for p in profiles {
+            self.profile_weights.insert(p.name.clone(), p.weight);
+        }
+        self.normalise();
+        self
+    }
+
+    fn normalise(&mut self) {
+        let total = self.total_weight();
+        if total > Decimal::ONE {
+            for w in self.weights.values_mut().chain(self.profile_weights.values_mut()) {
+                *w /= total;
+            }
+        }
+    }
+
+    /// Weight for a sleeve (0 if not configured).
+    pub fn weight(&self, sleeve: Sleeve) -> Decimal {
+        self.weights.get(&sleeve).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    /// Weight for a capital label as stored on orders and positions: a copy
+    /// profile's name or a sleeve (unknown labels count as single_whale).
+    pub fn label_weight(&self, label: &str) -> Decimal {
+        match self.profile_weights.get(label) {
+            Some(&w) => w,
+            None => self.weight(Sleeve::parse(label).unwrap_or(Sleeve::SingleWhale)),
+        }
+    }
+
+    /// True if `label` names a sleeve or a configured copy profile, i.e. it
+    /// doesn't fall back to single_whale.
+    pub fn knows_label(&self, label: &str) -> bool {
+        self.profile_weights.contains_key(label) || Sleeve::parse(label).is_some()
+    }
+
+    /// Weight of a copy profile's pool (0 if not configured).
+    pub fn profile_weight(&self, name: &str) -> Decimal {
+        self.profile_weights.get(name).copied().unwrap_or(Decimal::ZERO)
+    }
+
+    /// Sum of all configured weights (≤ 1).
+    pub fn total_weight(&self) -> Decimal {
+        self.weights.values().chain(self.profile_weights.values()).copied().sum()
+    }
+}
+
+impl Default for SleeveAllocation {
+    fn default() -> Self {
+        Self::parse(DEFAULT_SLEEVE_WEIGHTS)
+    }
+}
+
+/// Scale the global risk limits down to a single sleeve.
+///
+/// Daily loss and open-position budgets shrink with the sleeve weight; per-order
+/// limits (position %, spread, slippage) are relative already and stay as-is.
+pub fn sleeve_risk_limits(global: &RiskLimits, weight: Decimal) -> RiskLimits {
+    let max_open_positions = (Decimal::from(global.max_open_positions) * weight)
+        .ceil()
+        .to_i64()
+        .unwrap_or(0);
+
+    RiskLimits {
+        max_open_positions,
+        max_daily_loss: global.max_daily_loss * weight,
+        ..global.clone()
+    }
+}
+
+/// One independent `CapitalPool` per sleeve and per copy profile.
+#[derive(Clone)]
+pub struct SleevePools {
+    allocation: SleeveAllocation,
+    pools: HashMap<Sleeve, CapitalPool>,
+    profile_pools: HashMap<String, CapitalPool>,
+}
+
+impl SleevePools {
+    /// Split `total_balance` across sleeves and profiles according to `allocation`.
+    pub fn new(total_balance: Decimal, allocation: SleeveAllocation) -> Self {
+        let pools = Sleeve::ALL
+            .iter()
+            .map(|&s| (s, CapitalPool::new(total_balance * allocation.weight(s))))
+            .collect();
+        let profile_pools = allocation
+            .profile_weights
+            .iter()
+            .map(|(name, &w)| (name.clone(), CapitalPool::new(total_balance * w)))
+            .collect();
+        Self {
+            allocation,
+            pools,
+            profile_pools,
+        }
+    }
+
+    pub fn allocation(&self) -> &SleeveAllocation {
+        &self.allocation
+    }
+
+    /// Capital pool for a sleeve.
+    pub fn get(&self, sleeve: Sleeve) -> &CapitalPool {
+        // Every sleeve gets a pool in `new`, so indexing cannot fail.
+        &self.pools[&sleeve]
+    }
+
+    /// Capital pool for a label stored on orders and positions: a copy
+    /// profile's own pool, else the sleeve's (falls back to single_whale).
+    pub fn get_by_label(&self, label: &str) -> &CapitalPool {
+        match self.profile_pools.get(label) {
+            Some(pool) => pool,
+            None => self.get(Sleeve::parse(label).unwrap_or(Sleeve::SingleWhale)),
+        }
+    }
+
+    /// Every pool with its capital label.
+    fn labelled_pools(&self) -> impl Iterator<Item = (&str, &CapitalPool)> {
+        self.pools
+            .iter()
+            .map(|(s, pool)| (s.as_str(), pool))
+            .chain(self.profile_pools.iter().map(|(name, pool)| (name.as_str(), pool)))
+    }
+
+    /// Sum of all sleeve and profile balances, including in-flight reservations.
+    pub async fn total_balance(&self) -> Decimal {
+        let mut total = Decimal::ZERO;
+        for (_, pool) in self.labelled_pools() {
+            total += pool.total_balance().await;
+        }
+        total
+    }
+
+    /// Apply the reserve floor fraction to every pool.
+    pub async fn set_reserve_floor_pct(&self, pct: Decimal) {
+        for (_, pool) in self.labelled_pools() {
+            pool.set_reserve_floor_pct(pct).await;
+        }
+    }
+
+    /// Total USDC held back for exits and fees across pools.
+    pub async fn reserve_floor(&self) -> Decimal {
+        let mut total = Decimal::ZERO;
+        for (_, pool) in self.labelled_pools() {
+            total += pool.reserve_floor().await;
+        }
+        total
+    }
+
+    /// Re-calibrate against the on-chain USDC balance.
+    ///
+    /// Only the drift between the external balance and the sum of pool
+    /// balances is redistributed (by weight), so each sleeve and profile keeps
+    /// its own realised gains and losses.
+    pub async fn sync_balance(&self, external_balance: Decimal) {
+        let tracked = self.total_balance().await;
+        let drift = external_balance - tracked;
+        let total_weight = self.allocation.total_weight();
+        if drift.is_zero() || total_weight.is_zero() {
+            return;
+        }
+
+        for (label, pool) in self.labelled_pools() {
+            let share = drift * self.allocation.label_weight(label) / total_weight;
+            if !share.is_zero() {
+                let current = pool.total_balance().await;
+                pool.sync_balance(current + share).await;
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_weights() {
+        let alloc = SleeveAllocation::parse("single_whale:0.6, basket:0.4");
+        assert_eq!(alloc.weight(Sleeve::SingleWhale), Decimal::new(6, 1));
+        assert_eq!(alloc.weight(Sleeve::Basket), Decimal::new(4, 1));
+        assert_eq!(alloc.weight(Sleeve::Momentum), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_parse_normalises_overallocation() {
+        let alloc = SleeveAllocation::parse("single_whale:3,basket:1");
+        assert_eq!(alloc.weight(Sleeve::SingleWhale), Decimal::new(75, 2));
+        assert_eq!(alloc.weight(Sleeve::Basket), Decimal::new(25, 2));
+    }
+
+    #[test]
+    fn test_parse_invalid_falls_back_to_default() {
+        let alloc = SleeveAllocation::parse("garbage");
+        assert_eq!(alloc, SleeveAllocation::default());
+    }
+
+    #[test]
+    fn test_sleeve_risk_limits_scaled() {
+        let global = RiskLimits::default();
+        let limits = sleeve_risk_limits(&global, Decimal::new(3, 1));
+        assert_eq!(limits.max_open_positions, 3);
+        assert_eq!(limits.max_daily_loss, Decimal::from(150));
+        assert_eq!(limits.max_position_pct, global.max_position_pct);
+    }
+
+    #[tokio::test]
+    async fn test_pools_are_independent() {
+        let pools = SleevePools::new(
+            Decimal::from(1000),
+            SleeveAllocation::parse("single_whale:0.7,basket:0.3"),
+        );
+        assert_eq!(pools.get(Sleeve::SingleWhale).available().await, Decimal::from(700));
+        assert_eq!(pools.get(Sleeve::Basket).available().await, Decimal::from(300));
+
+        // Draining one sleeve leaves the other untouched
+        let id = uuid::Uuid::new_v4();
+        assert!(pools.get(Sleeve::SingleWhale).reserve(id, Decimal::from(700)).await);
+        pools.get(Sleeve::SingleWhale).confirm(&id).await;
+        assert_eq!(pools.get(Sleeve::SingleWhale).available().await, Decimal::ZERO);
+        assert_eq!(pools.get(Sleeve::Basket).available().await, Decimal::from(300));
+    }
+
+    #[tokio::test]
+    async fn test_sync_redistributes_drift_only() {
+        let pools = SleevePools::new(
+            Decimal::from(1000),
+            SleeveAllocation::parse("single_whale:0.5,basket:0.5"),
+        );
+        let id = uuid::Uuid::new_v4();
+        assert!(pools.get(Sleeve::Basket).reserve(id, Decimal::from(200)).await);
+        pools.get(Sleeve::Basket).confirm(&id).await;
+
+        // On-chain balance is 100 higher than tracked (800 → 900)
+        pools.sync_balance(Decimal::from(900)).await;
+        assert_eq!(pools.get(Sleeve::SingleWhale).total_balance().await, Decimal::from(550));
+        assert_eq!(pools.get(Sleeve::Basket).total_balance().await, Decimal::from(350));
+    }
+
+    #[tokio::test]
+    async fn test_profiles_get_their_own_pools() {
+        let profiles = crate::execution::copy_profiles::parse_profiles("careful:weight=0.5;bold:weight=0.5");
+        let alloc = SleeveAllocation::parse("single_whale:0.5,basket:0.5").with_profiles(&profiles);
+        // Sleeves and profiles are normalised together
+        assert_eq!(alloc.weight(Sleeve::SingleWhale), Decimal::new(25, 2));
+        assert_eq!(alloc.label_weight("bold"), Decimal::new(25, 2));
+        assert_eq!(alloc.label_weight("unknown"), Decimal::new(25, 2));
+        assert!(alloc.knows_label("bold") && alloc.knows_label("basket"));
+        assert!(!alloc.knows_label("unknown"));
+
+        let pools = SleevePools::new(Decimal::from(1000), alloc);
+        assert_eq!(pools.get_by_label("careful").available().await, Decimal::from(250));
+
+        // Spending a profile's capital leaves the other profile and the sleeves untouched
+        let id = uuid::Uuid::new_v4();
+        assert!(pools.get_by_label("bold").reserve(id, Decimal::from(250)).await);
+        pools.get_by_label("bold").confirm(&id).await;
+        assert_eq!(pools.get_by_label("bold").available().await, Decimal::ZERO);
+        assert_eq!(pools.get_by_label("careful").available().await, Decimal::from(250));
+        assert_eq!(pools.get(Sleeve::SingleWhale).available().await, Decimal::from(250));
+        assert_eq!(pools.total_balance().await, Decimal::from(750));
+    }
+}
@@ -0,0 +1,127 @@
+//! Typed ABI definitions of the Polymarket CTF Exchange and NegRisk adapter
+//! events the chain listener decodes, and of the USDC calls and transfers
+//! the capital flow monitor reads. New events or fields are added here
+//! and decoded with `SolEvent::decode_log_data` instead of slicing hex.
+
+use alloy::primitives::{Address, Bytes, LogData, B256, U256};
+use alloy::sol;
+use rust_decimal::Decimal;
+
+sol! {
+    /// CTF Exchange: a maker order was (partially) filled.
+    #[derive(Debug, PartialEq)]
+    event OrderFilled(
+        bytes32 indexed orderHash,
+        address indexed maker,
+        address indexed taker,
+        uint256 makerAssetId,
+        uint256 takerAssetId,
+        uint256 makerAmountFilled,
+        uint256 takerAmountFilled,
+        uint256 fee
+    );
+
+    /// CTF Exchange: a taker order was matched against maker orders. Amounts
+    /// are the taker order's totals, from its maker's side.
+    #[derive(Debug, PartialEq)]
+    event OrdersMatched(
+        bytes32 indexed takerOrderHash,
+        address indexed takerOrderMaker,
+        uint256 makerAssetId,
+        uint256 takerAssetId,
+        uint256 makerAmountFilled,
+        uint256 takerAmountFilled
+    );
+
+    /// NegRisk adapter: USDC split into a full set of outcome tokens.
+    #[derive(Debug, PartialEq)]
+    event PositionSplit(address indexed stakeholder, bytes32 indexed conditionId, uint256 amount);
+
+    /// NegRisk adapter: a full set of outcome tokens merged back into USDC.
+    #[derive(Debug, PartialEq)]
+    event PositionsMerge(address indexed stakeholder, bytes32 indexed conditionId, uint256 amount);
+
+    /// NegRisk adapter: NO tokens converted into YES tokens of the other outcomes.
+    #[derive(Debug, PartialEq)]
+    event PositionsConverted(
+        address indexed stakeholder,
+        bytes32 indexed marketId,
+        uint256 indexed indexSet,
+        uint256 amount
+    );
+
+    /// ERC-20: `value` moved from `from` to `to`.
+    #[derive(Debug, PartialEq)]
+    event Transfer(address indexed from, address indexed to, uint256 value);
+
+    /// ERC-20 balance of `account`.
+    function balanceOf(address account) external view returns (uint256);
+}
+
+/// Topics and data of a JSON-RPC log (eth_subscribe / eth_getLogs).
+/// None if either is missing or not valid hex.
+pub fn log_data(log: &serde_json::Value) -> Option<LogData> {
+    let topics = log
+        .get("topics")?
+        .as_array()?
+        .iter()
+        .map(|t| t.as_str()?.parse::<B256>().ok())
+        .collect::<Option<Vec<_>>>()?;
+    let data: Bytes = log.get("data")?.as_str()?.parse().ok()?;
+    LogData::new(topics, data)
+}
+
+/// Lowercase 0x-prefixed address, the form whale addresses are kept in.
+pub fn address_key(address: Address) -> String {
+    format!("{address:#x}")
+}
+
+/// A token amount in base units as a Decimal with `decimals` places
+/// (0 if it doesn't fit a Decimal).
+pub fn token_amount(amount: U256, decimals: u32) -> Decimal {
+    i128::try_from(amount)
+        .ok()
+        .and_then(|v| Decimal::try_from_i128_with_scale(v, decimals).ok())
+        .map(|d| d.normalize())
+        .unwrap_or(Decimal::ZERO)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::sol_types::SolEvent;
+
+    #[test]
+    fn test_address_key() {
+        let address: Address = "0x4BFB41d5B3570DeFd03C39a9A4D8dE6bd8B8982E".parse().unwrap();
+        assert_eq!(address_key(address), "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e");
+    }
+
+    #[test]
+    fn test_token_amount() {
+        assert_eq!(token_amount(U256::from(1_000_000u64), 6), Decimal::from(1));
+        // 50 USDC
+        assert_eq!(token_amount(U256::from(0x2faf080u64), 6), Decimal::from(50));
+        assert_eq!(token_amount(U256::from(1_500_000u64), 6), Decimal::new(15, 1));
+        assert_eq!(token_amount(U256::MAX, 6), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_log_data() {
+        let log = serde_json::json!({
+            "topics": [
+                "0xbbed930dbfb7907ae2d60ddf78345610214f26419a0128df39b6cc3d9e5df9b0",
+                "0x000000000000000000000000abcdef1234567890abcdef1234567890abcdef12",
+                "0x7581b394f5a4dd19ec46e4ff36baa3a841c9eeb80af0f0850be552c0fece2d00"
+            ],
+            "data": "0x0000000000000000000000000000000000000000000000000000000002faf080"
+        });
+        let split = PositionSplit::decode_log_data(&log_data(&log).unwrap()).unwrap();
+        assert_eq!(address_key(split.stakeholder), "0xabcdef1234567890abcdef1234567890abcdef12");
+        assert_eq!(token_amount(split.amount, 6), Decimal::from(50));
+
+        // Topics that aren't 32 bytes aren't a log
+        let log = serde_json::json!({ "topics": ["0x01"], "data": "0x" });
+        assert!(log_data(&log).is_none());
+    }
+}
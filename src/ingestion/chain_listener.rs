@@ -1,41 +1,368 @@
-use chrono::Utc;
+use alloy::primitives::{LogData, B256, U256};
+use alloy::sol_types::SolEvent;
+use chrono::{DateTime, Utc};
 use futures_util::{SinkExt, StreamExt};
+use metrics::counter;
 use rust_decimal::Decimal;
 use sqlx::PgPool;
-use std::collections::HashSet;
-use std::time::Duration;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 use tokio::time::sleep;
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
 use crate::db::whale_repo;
+use crate::ingestion::chain_events::{
+    address_key, log_data, token_amount, OrderFilled, OrdersMatched, PositionSplit, PositionsConverted,
+    PositionsMerge,
+};
 use crate::models::{Side, WhaleTradeEvent};
 
 /// CTF Exchange contract on Polygon.
-const CTF_EXCHANGE: &str = "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e";
+pub(crate) const CTF_EXCHANGE: &str = "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e";
 
 /// NegRisk CTF Exchange contract on Polygon.
-const NEG_RISK_CTF_EXCHANGE: &str = "0xc5d563a36ae78145c45a50134d48a1215220f80a";
+pub(crate) const NEG_RISK_CTF_EXCHANGE: &str = "0xc5d563a36ae78145c45a50134d48a1215220f80a";
+
+/// NegRisk adapter contract on Polygon (splits/merges/conversions for neg-risk markets).
+pub(crate) const NEG_RISK_ADAPTER: &str = "0xd91e80cf2e7be2e162c6513ced06f1dd0da35296";
 
 /// Keccak256 of OrderFilled(bytes32,address,address,uint256,uint256,uint256,uint256,uint256)
 const ORDER_FILLED_TOPIC: &str =
     "0xd0a08e8c493f9c94f29311604c9de1b4e8c8d4c06bd0c789af57f2d65bfec0f6";
 
+/// Keccak256 of OrdersMatched(bytes32,address,uint256,uint256,uint256,uint256)
+const ORDERS_MATCHED_TOPIC: &str =
+    "0x63bf4d16b7fa898ef4c4b2b6d90fd201e9c56313b65638af6088d149d2ce956c";
+
+/// Keccak256 of PositionSplit(address,bytes32,uint256)
+const POSITION_SPLIT_TOPIC: &str =
+    "0xbbed930dbfb7907ae2d60ddf78345610214f26419a0128df39b6cc3d9e5df9b0";
+
+/// Keccak256 of PositionsMerge(address,bytes32,uint256)
+const POSITIONS_MERGE_TOPIC: &str =
+    "0xba33ac50d8894676597e6e35dc09cff59854708b642cd069d21eb9c7ca072a04";
+
+/// Keccak256 of PositionsConverted(address,bytes32,uint256,uint256)
+const POSITIONS_CONVERTED_TOPIC: &str =
+    "0xb03d19dddbc72a87e735ff0ea3b57bef133ebe44e1894284916a84044deb367e";
+
+/// How long a whale's adapter transaction or fill is remembered, so later logs
+/// from the same transaction are recognised as part of it.
+const CONVERSION_TX_TTL: Duration = Duration::from_secs(120);
+
 const BASE_RECONNECT_DELAY: Duration = Duration::from_secs(2);
 const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
 const WHALE_REFRESH_INTERVAL: Duration = Duration::from_secs(300);
 
-/// USDC on Polygon has 6 decimals.
+/// Consecutive errors on one endpoint before failing over to another.
+const FAILOVER_AFTER_ERRORS: u32 = 3;
+/// A connection that stays up at least this long counts as healthy.
+const HEALTHY_SESSION: Duration = Duration::from_secs(60);
+/// Endpoint health score bounds and how much one session moves it.
+const MAX_HEALTH: i32 = 100;
+const ERROR_PENALTY: i32 = 20;
+const HEALTHY_REWARD: i32 = 10;
+
+/// Blocks per eth_getLogs request when backfilling a gap.
+const BACKFILL_CHUNK_BLOCKS: u64 = 1_000;
+/// Longest gap backfilled after a reconnect (~5.5 hours of Polygon blocks);
+/// anything older is left to the whale trade poller.
+const MAX_BACKFILL_BLOCKS: u64 = 10_000;
+/// Wait between attempts at a gap backfill that failed.
+const BACKFILL_RETRY_INTERVAL: Duration = Duration::from_secs(30);
+
+/// USDC on Polygon has 6 decimals (CTF outcome tokens use the same).
 const USDC_DECIMALS: u32 = 6;
 
-/// Run the Polygon chain listener, subscribing to OrderFilled events on
-/// CTF Exchange contracts and forwarding matching whale trades into the pipeline.
+/// Recent whale transactions that later logs of the same transaction are
+/// checked against.
+#[derive(Debug, Default)]
+struct SeenTxs {
+    /// tx hash → when a tracked whale touched the NegRisk adapter in it
+    conversions: HashMap<String, Instant>,
+    /// "tx:order hash" and "tx:wallet" of whale fills already forwarded, so
+    /// an OrdersMatched summary isn't forwarded on top of its fills
+    whale_fills: HashMap<String, Instant>,
+}
+
+impl SeenTxs {
+    fn in_conversion(&self, tx_hash: &str) -> bool {
+        self.conversions
+            .get(tx_hash)
+            .is_some_and(|seen| seen.elapsed() < CONVERSION_TX_TTL)
+    }
+
+    fn has_fill(&self, key: &str) -> bool {
+        self.whale_fills
+            .get(key)
+            .is_some_and(|seen| seen.elapsed() < CONVERSION_TX_TTL)
+    }
+
+    fn prune(&mut self) {
+        self.conversions.retain(|_, seen| seen.elapsed() < CONVERSION_TX_TTL);
+        self.whale_fills.retain(|_, seen| seen.elapsed() < CONVERSION_TX_TTL);
+    }
+}
+
+/// A fill decoded from OrderFilled, or a whole match from OrdersMatched.
+#[derive(Debug, Clone, PartialEq)]
+struct Fill {
+    order_hash: B256,
+    /// Maker of the filled order (the taker order's maker for a match).
+    maker: String,
+    /// None for an OrdersMatched summary.
+    taker: Option<String>,
+    maker_asset_id: U256,
+    taker_asset_id: U256,
+    maker_amount: Decimal,
+    taker_amount: Decimal,
+}
+
+impl Fill {
+    /// Decode an OrderFilled or OrdersMatched log; None for any other log.
+    fn decode(data: &LogData) -> Option<Self> {
+        let signature = *data.topics().first()?;
+        let decoded = if signature == OrderFilled::SIGNATURE_HASH {
+            OrderFilled::decode_log_data(data).map(|f| Fill {
+                order_hash: f.orderHash,
+                maker: address_key(f.maker),
+                taker: Some(address_key(f.taker)),
+                maker_asset_id: f.makerAssetId,
+                taker_asset_id: f.takerAssetId,
+                maker_amount: token_amount(f.makerAmountFilled, USDC_DECIMALS),
+                taker_amount: token_amount(f.takerAmountFilled, USDC_DECIMALS),
+            })
+        } else if signature == OrdersMatched::SIGNATURE_HASH {
+            OrdersMatched::decode_log_data(data).map(|m| Fill {
+                order_hash: m.takerOrderHash,
+                maker: address_key(m.takerOrderMaker),
+                taker: None,
+                maker_asset_id: m.makerAssetId,
+                taker_asset_id: m.takerAssetId,
+                maker_amount: token_amount(m.makerAmountFilled, USDC_DECIMALS),
+                taker_amount: token_amount(m.takerAmountFilled, USDC_DECIMALS),
+            })
+        } else {
+            return None;
+        };
+
+        decoded
+            .map_err(|e| tracing::warn!(error = %e, "Chain event: undecodable exchange log"))
+            .ok()
+    }
+}
+
+/// Position-neutral action a whale performed through the NegRisk adapter.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum NegRiskEventKind {
+    /// USDC → full set of outcome tokens.
+    Split,
+    /// Full set of outcome tokens → USDC.
+    Merge,
+    /// NO tokens → YES tokens of the other outcomes (+ USDC).
+    Conversion,
+}
+
+impl NegRiskEventKind {
+    fn from_signature(signature: B256) -> Option<Self> {
+        match signature {
+            PositionSplit::SIGNATURE_HASH => Some(Self::Split),
+            PositionsMerge::SIGNATURE_HASH => Some(Self::Merge),
+            PositionsConverted::SIGNATURE_HASH => Some(Self::Conversion),
+            _ => None,
+        }
+    }
+
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Split => "split",
+            Self::Merge => "merge",
+            Self::Conversion => "conversion",
+        }
+    }
+}
+
+/// A decoded NegRisk adapter event.
+#[derive(Debug, Clone, PartialEq)]
+struct NegRiskEvent {
+    kind: NegRiskEventKind,
+    stakeholder: String,
+    /// Condition ID (split/merge) or neg-risk market ID (conversion), 0x-prefixed.
+    market: String,
+    amount: Decimal,
+    tx_hash: Option<String>,
+}
+
+/// A Polygon WSS endpoint and how it has behaved.
+#[derive(Debug, Clone)]
+struct Endpoint {
+    url: String,
+    /// 0..=MAX_HEALTH: drops on errors, recovers on healthy sessions.
+    health: i32,
+    consecutive_errors: u32,
+}
+
+/// The listener's WSS endpoints. It stays on the current one until it fails
+/// `FAILOVER_AFTER_ERRORS` times in a row, then moves to the healthiest
+/// other endpoint (the next in order on a tie).
+#[derive(Debug)]
+struct EndpointRotation {
+    endpoints: Vec<Endpoint>,
+    current: usize,
+}
+
+impl EndpointRotation {
+    fn new(urls: Vec<String>) -> Self {
+        let endpoints = urls
+            .into_iter()
+            .map(|url| Endpoint {
+                url,
+                health: MAX_HEALTH,
+                consecutive_errors: 0,
+            })
+            .collect();
+        Self { endpoints, current: 0 }
+    }
+
+    fn current(&self) -> &Endpoint {
+        &self.endpoints[self.current]
+    }
+
+    /// The current connection stayed up for a healthy session.
+    fn record_healthy(&mut self) {
+        let endpoint = &mut self.endpoints[self.current];
+        endpoint.health = (endpoint.health + HEALTHY_REWARD).min(MAX_HEALTH);
+        endpoint.consecutive_errors = 0;
+    }
+
+    /// The current endpoint failed to connect or dropped early. Returns the
+    /// URL failed over to, if this error triggered a failover.
+    fn record_error(&mut self) -> Option<&str> {
+        let endpoint = &mut self.endpoints[self.current];
+        endpoint.health = (endpoint.health - ERROR_PENALTY).max(0);
+        endpoint.consecutive_errors += 1;
+        if endpoint.consecutive_errors < FAILOVER_AFTER_ERRORS || self.endpoints.len() < 2 {
+            return None;
+        }
+
+        let n = self.endpoints.len();
+        let next = (1..n)
+            .map(|offset| (self.current + offset) % n)
+            .max_by_key(|&i| (self.endpoints[i].health, std::cmp::Reverse((i + n - self.current) % n)))
+            .unwrap_or(self.current);
+        self.current = next;
+        // A fresh run of attempts on the new endpoint; its health carries over
+        self.endpoints[next].consecutive_errors = 0;
+        Some(&self.endpoints[next].url)
+    }
+}
+
+/// Whale logs held until their block is `depth` blocks below the head, so
+/// logs a reorg removes are dropped before they are copied.
+#[derive(Debug)]
+struct ConfirmationBuffer {
+    depth: u64,
+    head: u64,
+    /// block → logs in arrival order
+    pending: BTreeMap<u64, Vec<PendingLog>>,
+}
+
+/// A whale log waiting for its confirmations.
+#[derive(Debug)]
+struct PendingLog {
+    log: serde_json::Value,
+    /// When it was seen live, or its block's time when backfilled.
+    timestamp: DateTime<Utc>,
+    /// Fetched by a gap backfill rather than seen live.
+    replayed: bool,
+}
+
+impl ConfirmationBuffer {
+    fn new(depth: u64) -> Self {
+        Self {
+            depth,
+            head: 0,
+            pending: BTreeMap::new(),
+        }
+    }
+
+    fn push(&mut self, block: u64, log: serde_json::Value, timestamp: DateTime<Utc>, replayed: bool) {
+        self.pending.entry(block).or_default().push(PendingLog { log, timestamp, replayed });
+    }
+
+    /// Drop a pending log that a reorg removed. Returns whether it was pending.
+    fn remove(&mut self, removed: &serde_json::Value) -> bool {
+        let key = |log: &serde_json::Value| (log.get("transactionHash").cloned(), log.get("logIndex").cloned());
+        let Some(logs) = log_block_number(removed).and_then(|b| self.pending.get_mut(&b)) else {
+            return false;
+        };
+        let before = logs.len();
+        logs.retain(|pending| key(&pending.log) != key(removed));
+        before != logs.len()
+    }
+
+    /// Record the chain head and take the logs it confirms, oldest first.
+    fn advance(&mut self, head: u64) -> Vec<PendingLog> {
+        self.head = self.head.max(head);
+        let Some(through) = self.confirmed_through() else {
+            return Vec::new();
+        };
+        let unconfirmed = self.pending.split_off(&(through + 1));
+        std::mem::replace(&mut self.pending, unconfirmed)
+            .into_values()
+            .flatten()
+            .collect()
+    }
+
+    /// Highest block whose logs are all confirmed.
+    fn confirmed_through(&self) -> Option<u64> {
+        (self.head > 0).then_some(self.head).and_then(|h| h.checked_sub(self.depth))
+    }
+
+    /// Forget pending logs and the head; after a reconnect the gap backfill
+    /// fetches them again.
+    fn clear(&mut self) {
+        self.head = 0;
+        self.pending.clear();
+    }
+}
+
+/// Run the Polygon chain listener, subscribing to OrderFilled and
+/// OrdersMatched events on CTF Exchange contracts and forwarding matching
+/// whale trades into the pipeline. An OrdersMatched summary is only
+/// forwarded when none of its fills already were.
+///
+/// NegRisk adapter splits/merges/conversions are also decoded. They don't
+/// change a whale's directional exposure, so they are never forwarded as
+/// trades, and OrderFilled legs in the same transaction are dropped too.
+///
+/// With several `ws_urls` the listener fails over between them (see
+/// `EndpointRotation`), so one flaky RPC provider doesn't stop ingestion.
+///
+/// On reconnect, logs emitted since the last processed block are replayed
+/// via eth_getLogs on `rpc_url` before live notifications are handled, and
+/// retried until that succeeds. Replayed fills are marked as such, so the
+/// pipeline doesn't copy them once they are older than the signal TTL.
+///
+/// Whale logs are forwarded once their block is `confirmations` blocks deep
+/// (0 = immediately); logs reorged out before that are dropped.
 pub async fn run_chain_listener(
-    ws_url: String,
+    ws_urls: Vec<String>,
+    rpc_url: String,
+    confirmations: u64,
     pool: PgPool,
     trade_tx: mpsc::Sender<WhaleTradeEvent>,
 ) {
-    let mut attempt: u32 = 0;
+    if ws_urls.is_empty() {
+        tracing::warn!("Chain listener has no Polygon WSS endpoints — not starting");
+        return;
+    }
+    let mut endpoints = EndpointRotation::new(ws_urls);
+    let http = reqwest::Client::new();
+    let mut pending = ConfirmationBuffer::new(confirmations);
+    // Block through which every log was handled, where a gap backfill resumes
+    let mut last_block: Option<u64> = None;
 
     // Load initial whale address set
     let mut whale_addresses = load_whale_addresses(&pool).await;
@@ -44,14 +371,18 @@ pub async fn run_chain_listener(
         "Chain listener loaded whale addresses"
     );
     let mut last_refresh = tokio::time::Instant::now();
+    let mut seen_txs = SeenTxs::default();
 
     loop {
+        let ws_url = endpoints.current().url.clone();
         tracing::info!(url = %ws_url, "Chain listener connecting to Polygon WSS...");
+        let mut connected_at = None;
 
         match connect_async(&ws_url).await {
             Ok((ws_stream, _response)) => {
                 tracing::info!("Chain listener connected to Polygon WSS");
-                attempt = 0;
+                connected_at = Some(Instant::now());
+                pending.clear();
 
                 let (mut write, mut read) = ws_stream.split();
 
@@ -62,63 +393,146 @@ pub async fn run_chain_listener(
                     "method": "eth_subscribe",
                     "params": ["logs", {
                         "address": [CTF_EXCHANGE, NEG_RISK_CTF_EXCHANGE],
-                        "topics": [[ORDER_FILLED_TOPIC]]
+                        "topics": [[ORDER_FILLED_TOPIC, ORDERS_MATCHED_TOPIC]]
+                    }]
+                });
+
+                let adapter_subscribe_msg = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 2,
+                    "method": "eth_subscribe",
+                    "params": ["logs", {
+                        "address": [NEG_RISK_ADAPTER],
+                        "topics": [[POSITION_SPLIT_TOPIC, POSITIONS_MERGE_TOPIC, POSITIONS_CONVERTED_TOPIC]]
                     }]
                 });
 
+                // Heads release whale logs once they are deep enough
+                let heads_subscribe_msg = serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": 3,
+                    "method": "eth_subscribe",
+                    "params": ["newHeads"]
+                });
+
+                // A failed subscribe falls through to the error accounting below
                 if let Err(e) = write
                     .send(Message::Text(subscribe_msg.to_string().into()))
                     .await
                 {
                     tracing::error!(error = %e, "Failed to send eth_subscribe");
-                    continue;
-                }
-                tracing::info!("Subscribed to OrderFilled events on 2 contracts");
-
-                loop {
-                    // Periodically refresh whale addresses
-                    if last_refresh.elapsed() >= WHALE_REFRESH_INTERVAL {
-                        whale_addresses = load_whale_addresses(&pool).await;
-                        last_refresh = tokio::time::Instant::now();
-                        tracing::debug!(
-                            whale_count = whale_addresses.len(),
-                            "Refreshed whale address set"
-                        );
-                    }
+                } else if let Err(e) = write
+                    .send(Message::Text(adapter_subscribe_msg.to_string().into()))
+                    .await
+                {
+                    tracing::error!(error = %e, "Failed to send eth_subscribe for NegRisk adapter");
+                } else if let Err(e) = write
+                    .send(Message::Text(heads_subscribe_msg.to_string().into()))
+                    .await
+                {
+                    tracing::error!(error = %e, "Failed to send eth_subscribe for newHeads");
+                } else {
+                    tracing::info!("Subscribed to OrderFilled/OrdersMatched events on 2 contracts and NegRisk adapter events");
+
+                    // Replay the gap while live notifications queue up on the
+                    // socket. A failed backfill is retried, and `last_block`
+                    // stays put until it succeeds so the gap is never lost;
+                    // live fills a retry fetches again are dropped as
+                    // duplicates by the trade dedup
+                    let mut skip_through = None;
+                    let mut gap_from = last_block.map(|b| b + 1);
+                    let mut backfill_attempted: Option<Instant> = None;
 
-                    tokio::select! {
-                        msg = read.next() => {
-                            match msg {
-                                Some(Ok(Message::Text(text))) => {
-                                    handle_rpc_message(
-                                        text.as_ref(),
-                                        &whale_addresses,
-                                        &trade_tx,
-                                    ).await;
+                    loop {
+                        if let Some(from) =
+                            gap_from.filter(|_| backfill_attempted.is_none_or(|t| t.elapsed() >= BACKFILL_RETRY_INTERVAL))
+                        {
+                            backfill_attempted = Some(Instant::now());
+                            match backfill_gap(
+                                &http,
+                                &rpc_url,
+                                from,
+                                &whale_addresses,
+                                &trade_tx,
+                                &mut seen_txs,
+                                &mut pending,
+                            )
+                            .await
+                            {
+                                Ok((head, replayed)) => {
+                                    tracing::info!(
+                                        from_block = from,
+                                        to_block = head,
+                                        replayed,
+                                        "Chain listener backfilled missed logs"
+                                    );
+                                    gap_from = None;
+                                    last_block = last_block.max(pending.confirmed_through());
+                                    skip_through = Some(head);
                                 }
-                                Some(Ok(Message::Ping(data))) => {
-                                    if let Err(e) = write.send(Message::Pong(data)).await {
-                                        tracing::warn!(error = %e, "Failed to send pong");
+                                Err(e) => {
+                                    counter!("chain_backfill_failures_total").increment(1);
+                                    tracing::warn!(
+                                        error = %e,
+                                        from_block = from,
+                                        retry_secs = BACKFILL_RETRY_INTERVAL.as_secs(),
+                                        "Chain listener gap backfill failed — will retry"
+                                    );
+                                }
+                            }
+                        }
+
+                        // Periodically refresh whale addresses
+                        if last_refresh.elapsed() >= WHALE_REFRESH_INTERVAL {
+                            whale_addresses = load_whale_addresses(&pool).await;
+                            last_refresh = tokio::time::Instant::now();
+                            seen_txs.prune();
+                            tracing::debug!(
+                                whale_count = whale_addresses.len(),
+                                "Refreshed whale address set"
+                            );
+                        }
+
+                        tokio::select! {
+                            msg = read.next() => {
+                                match msg {
+                                    Some(Ok(Message::Text(text))) => {
+                                        let block = handle_rpc_message(
+                                            text.as_ref(),
+                                            &whale_addresses,
+                                            &trade_tx,
+                                            &mut seen_txs,
+                                            &mut pending,
+                                            skip_through,
+                                        ).await;
+                                        if gap_from.is_none() {
+                                            last_block = last_block.max(block);
+                                        }
+                                    }
+                                    Some(Ok(Message::Ping(data))) => {
+                                        if let Err(e) = write.send(Message::Pong(data)).await {
+                                            tracing::warn!(error = %e, "Failed to send pong");
+                                            break;
+                                        }
+                                    }
+                                    Some(Ok(Message::Close(_))) => {
+                                        tracing::warn!("Chain listener: server sent close frame");
+                                        break;
+                                    }
+                                    Some(Ok(_)) => {}
+                                    Some(Err(e)) => {
+                                        tracing::error!(error = %e, "Chain listener: WS read error");
+                                        break;
+                                    }
+                                    None => {
+                                        tracing::warn!("Chain listener: WS stream ended");
                                         break;
                                     }
-                                }
-                                Some(Ok(Message::Close(_))) => {
-                                    tracing::warn!("Chain listener: server sent close frame");
-                                    break;
-                                }
-                                Some(Ok(_)) => {}
-                                Some(Err(e)) => {
-                                    tracing::error!(error = %e, "Chain listener: WS read error");
-                                    break;
-                                }
-                                None => {
-                                    tracing::warn!("Chain listener: WS stream ended");
-                                    break;
                                 }
                             }
-                        }
-                        _ = sleep(WHALE_REFRESH_INTERVAL) => {
-                            // Triggers the refresh check at the top of the loop
+                            _ = sleep(WHALE_REFRESH_INTERVAL) => {
+                                // Triggers the refresh check at the top of the loop
+                            }
                         }
                     }
                 }
@@ -128,11 +542,23 @@ pub async fn run_chain_listener(
             }
         }
 
-        // Exponential backoff
+        if connected_at.is_some_and(|at| at.elapsed() >= HEALTHY_SESSION) {
+            endpoints.record_healthy();
+        } else if let Some(next_url) = endpoints.record_error() {
+            counter!("chain_listener_failovers_total").increment(1);
+            tracing::warn!(from = %ws_url, to = %next_url, "Chain listener failing over to another endpoint");
+        }
+
+        // Exponential backoff on the endpoint's run of errors
+        let attempt = endpoints.current().consecutive_errors;
         let delay = BASE_RECONNECT_DELAY * 2u32.saturating_pow(attempt);
         let delay = delay.min(MAX_RECONNECT_DELAY);
-        attempt = attempt.saturating_add(1);
-        tracing::info!(delay_secs = delay.as_secs(), attempt, "Chain listener reconnecting...");
+        tracing::info!(
+            delay_secs = delay.as_secs(),
+            attempt,
+            health = endpoints.current().health,
+            "Chain listener reconnecting..."
+        );
         sleep(delay).await;
     }
 }
@@ -151,16 +577,186 @@ async fn load_whale_addresses(pool: &PgPool) -> HashSet<String> {
     }
 }
 
-/// Handle an incoming JSON-RPC message from the Polygon WSS node.
+/// Replay logs missed while disconnected, from `from_block` through the
+/// current head, over HTTP RPC. Returns the head and how many whale logs
+/// were replayed; fills carry their block's timestamp and still wait for
+/// their confirmation depth.
+async fn backfill_gap(
+    http: &reqwest::Client,
+    rpc_url: &str,
+    from_block: u64,
+    whale_addresses: &HashSet<String>,
+    trade_tx: &mpsc::Sender<WhaleTradeEvent>,
+    seen_txs: &mut SeenTxs,
+    confirmations: &mut ConfirmationBuffer,
+) -> anyhow::Result<(u64, usize)> {
+    let head_hex = rpc_call(http, rpc_url, "eth_blockNumber", serde_json::json!([])).await?;
+    let head = head_hex
+        .as_str()
+        .and_then(parse_hex_u64)
+        .ok_or_else(|| anyhow::anyhow!("bad eth_blockNumber result: {head_hex}"))?;
+    if from_block > head || whale_addresses.is_empty() {
+        return Ok((head, 0));
+    }
+
+    let mut from = from_block;
+    if head - from_block + 1 > MAX_BACKFILL_BLOCKS {
+        from = head + 1 - MAX_BACKFILL_BLOCKS;
+        tracing::warn!(
+            missed_from = from_block,
+            backfill_from = from,
+            "Chain listener gap too long — backfilling only the most recent blocks"
+        );
+    }
+
+    let mut logs: Vec<serde_json::Value> = Vec::new();
+    while from <= head {
+        let to = (from + BACKFILL_CHUNK_BLOCKS - 1).min(head);
+        for filter in backfill_filters(whale_addresses, from, to) {
+            let result = rpc_call(http, rpc_url, "eth_getLogs", serde_json::json!([filter])).await?;
+            logs.extend(result.as_array().cloned().unwrap_or_default());
+        }
+        from = to + 1;
+    }
+
+    // Whale-to-whale fills match both the maker and taker filters; adapter
+    // logs precede the fills of their transaction
+    let log_key = |log: &serde_json::Value| {
+        let index = log.get("logIndex").and_then(|i| i.as_str()).and_then(parse_hex_u64);
+        (log_block_number(log), index)
+    };
+    logs.sort_by_key(log_key);
+    logs.dedup_by(|a, b| log_key(a) == log_key(b));
+
+    // Blocks within the confirmation depth of the head wait in the buffer
+    let replayed = logs.len();
+    let mut block_times: HashMap<u64, DateTime<Utc>> = HashMap::new();
+    for log in logs {
+        let Some(block) = log_block_number(&log) else {
+            continue;
+        };
+        let timestamp = match block_times.get(&block) {
+            Some(t) => *t,
+            None => {
+                let t = block_timestamp(http, rpc_url, block).await.unwrap_or_else(|_| Utc::now());
+                block_times.insert(block, t);
+                t
+            }
+        };
+        confirmations.push(block, log, timestamp, true);
+    }
+    for p in confirmations.advance(head) {
+        handle_log(&p.log, whale_addresses, trade_tx, seen_txs, p.timestamp, p.replayed).await;
+    }
+    counter!("chain_backfill_logs_total").increment(replayed as u64);
+
+    Ok((head, replayed))
+}
+
+/// eth_getLogs filters for whale activity in blocks `from..=to`: fills with a
+/// whale as maker, fills with a whale as taker, matches of a whale's taker
+/// order, and whale NegRisk adapter events.
+fn backfill_filters(whale_addresses: &HashSet<String>, from: u64, to: u64) -> Vec<serde_json::Value> {
+    let mut whales: Vec<String> = whale_addresses
+        .iter()
+        .map(|a| format!("0x{:0>64}", a.trim_start_matches("0x")))
+        .collect();
+    whales.sort();
+    let (from, to) = (format!("{from:#x}"), format!("{to:#x}"));
+
+    vec![
+        serde_json::json!({
+            "fromBlock": from, "toBlock": to,
+            "address": [CTF_EXCHANGE, NEG_RISK_CTF_EXCHANGE],
+            "topics": [[ORDER_FILLED_TOPIC], null, whales]
+        }),
+        serde_json::json!({
+            "fromBlock": from, "toBlock": to,
+            "address": [CTF_EXCHANGE, NEG_RISK_CTF_EXCHANGE],
+            "topics": [[ORDER_FILLED_TOPIC], null, null, whales]
+        }),
+        serde_json::json!({
+            "fromBlock": from, "toBlock": to,
+            "address": [CTF_EXCHANGE, NEG_RISK_CTF_EXCHANGE],
+            "topics": [[ORDERS_MATCHED_TOPIC], null, whales]
+        }),
+        serde_json::json!({
+            "fromBlock": from, "toBlock": to,
+            "address": [NEG_RISK_ADAPTER],
+            "topics": [[POSITION_SPLIT_TOPIC, POSITIONS_MERGE_TOPIC, POSITIONS_CONVERTED_TOPIC], whales]
+        }),
+    ]
+}
+
+/// Timestamp of a block.
+pub(crate) async fn block_timestamp(http: &reqwest::Client, rpc_url: &str, block: u64) -> anyhow::Result<DateTime<Utc>> {
+    let result = rpc_call(
+        http,
+        rpc_url,
+        "eth_getBlockByNumber",
+        serde_json::json!([format!("{block:#x}"), false]),
+    )
+    .await?;
+    result["timestamp"]
+        .as_str()
+        .and_then(parse_hex_u64)
+        .and_then(|secs| DateTime::from_timestamp(secs as i64, 0))
+        .ok_or_else(|| anyhow::anyhow!("block {block} has no timestamp"))
+}
+
+/// Polygon JSON-RPC call over HTTP returning the `result` field.
+pub(crate) async fn rpc_call(
+    http: &reqwest::Client,
+    rpc_url: &str,
+    method: &str,
+    params: serde_json::Value,
+) -> anyhow::Result<serde_json::Value> {
+    let body = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": method,
+        "params": params,
+    });
+
+    let mut resp: serde_json::Value = http
+        .post(rpc_url)
+        .json(&body)
+        .send()
+        .await?
+        .error_for_status()?
+        .json()
+        .await?;
+
+    if let Some(err) = resp.get("error") {
+        anyhow::bail!("{method} failed: {err}");
+    }
+    Ok(resp["result"].take())
+}
+
+/// Block number of a log.
+pub(crate) fn log_block_number(log: &serde_json::Value) -> Option<u64> {
+    log.get("blockNumber")?.as_str().and_then(parse_hex_u64)
+}
+
+/// Parse a JSON-RPC hex quantity (`0x...`).
+pub(crate) fn parse_hex_u64(hex: &str) -> Option<u64> {
+    u64::from_str_radix(hex.strip_prefix("0x")?, 16).ok()
+}
+
+/// Handle an incoming JSON-RPC message from the Polygon WSS node: whale
+/// logs go into `confirmations` and new heads (or later logs) release the
+/// ones deep enough. Logs at or below `skip_through` were already replayed
+/// by the gap backfill and are not handled again. Returns the block through
+/// which every log has been handled.
 async fn handle_rpc_message(
     text: &str,
     whale_addresses: &HashSet<String>,
     trade_tx: &mpsc::Sender<WhaleTradeEvent>,
-) {
-    let msg: serde_json::Value = match serde_json::from_str(text) {
-        Ok(v) => v,
-        Err(_) => return,
-    };
+    seen_txs: &mut SeenTxs,
+    confirmations: &mut ConfirmationBuffer,
+    skip_through: Option<u64>,
+) -> Option<u64> {
+    let msg: serde_json::Value = serde_json::from_str(text).ok()?;
 
     // Subscription confirmations: {"jsonrpc":"2.0","id":1,"result":"0x..."}
     if msg.get("id").is_some() && msg.get("result").is_some() {
@@ -168,102 +764,182 @@ async fn handle_rpc_message(
             result = %msg["result"],
             "Chain listener: subscription confirmed"
         );
-        return;
+        return None;
     }
 
     // Subscription notifications: {"jsonrpc":"2.0","method":"eth_subscription","params":{...}}
-    let params = match msg.get("params") {
-        Some(p) => p,
-        None => return,
-    };
-    let result = match params.get("result") {
-        Some(r) => r,
-        None => return,
-    };
-
-    // Parse the log entry
-    let topics = match result.get("topics").and_then(|t| t.as_array()) {
-        Some(t) => t,
-        None => return,
+    let result = msg.get("params")?.get("result")?;
+    let head = if result.get("topics").is_none() {
+        // newHeads notification
+        result.get("number").and_then(|n| n.as_str()).and_then(parse_hex_u64)
+    } else if result.get("removed").and_then(|r| r.as_bool()) == Some(true) {
+        handle_removed_log(result, whale_addresses, confirmations);
+        None
+    } else {
+        let block = log_block_number(result);
+        match block {
+            // Nothing to confirm against
+            None => handle_log(result, whale_addresses, trade_tx, seen_txs, Utc::now(), false).await,
+            Some(b) if skip_through.is_some_and(|done| b <= done) => {}
+            Some(b) => {
+                if involves_whale(result, whale_addresses) {
+                    confirmations.push(b, result.clone(), Utc::now(), false);
+                }
+            }
+        }
+        block
     };
 
-    if topics.len() < 4 {
-        return;
+    if let Some(head) = head {
+        for p in confirmations.advance(head) {
+            handle_log(&p.log, whale_addresses, trade_tx, seen_txs, p.timestamp, p.replayed).await;
+        }
     }
+    confirmations.confirmed_through()
+}
 
-    // Verify event signature
-    let event_sig = topics[0].as_str().unwrap_or_default();
-    if event_sig != ORDER_FILLED_TOPIC {
+/// A log the node reorged out of the chain. Dropped if it was still waiting
+/// for confirmation; a whale fill that was already forwarded can't be
+/// unwound here, so it is reported for review.
+fn handle_removed_log(log: &serde_json::Value, whale_addresses: &HashSet<String>, confirmations: &mut ConfirmationBuffer) {
+    if !involves_whale(log, whale_addresses) {
         return;
     }
+    let tx_hash = log.get("transactionHash").and_then(|h| h.as_str()).unwrap_or_default();
+    let block = log_block_number(log);
 
-    // topics[1] = orderHash (ignored)
-    // topics[2] = maker address (bytes32 padded)
-    // topics[3] = taker address (bytes32 padded)
-    let maker = extract_address(topics[2].as_str().unwrap_or_default());
-    let taker = extract_address(topics[3].as_str().unwrap_or_default());
+    if confirmations.remove(log) {
+        counter!("chain_reorged_logs_total", "outcome" => "dropped").increment(1);
+        tracing::info!(tx_hash, block, "Chain event: whale log reorged out before confirmation — dropped");
+    } else {
+        counter!("chain_reorged_logs_total", "outcome" => "forwarded").increment(1);
+        tracing::warn!(
+            tx_hash,
+            block,
+            "Chain event: whale log reorged out after it was forwarded — its copy may never have settled"
+        );
+    }
+}
 
-    let maker_is_whale = whale_addresses.contains(&maker);
-    let taker_is_whale = whale_addresses.contains(&taker);
+/// Whether a fill or match has a tracked whale as maker or taker, or an
+/// adapter event has one as stakeholder.
+fn involves_whale(log: &serde_json::Value, whale_addresses: &HashSet<String>) -> bool {
+    if let Some(fill) = log_data(log).and_then(|data| Fill::decode(&data)) {
+        return whale_addresses.contains(&fill.maker)
+            || fill.taker.is_some_and(|taker| whale_addresses.contains(&taker));
+    }
+    decode_neg_risk_event(log).is_some_and(|event| whale_addresses.contains(&event.stakeholder))
+}
 
-    if !maker_is_whale && !taker_is_whale {
+/// Handle one OrderFilled, OrdersMatched or NegRisk adapter log, forwarding
+/// whale fills stamped with `timestamp`.
+async fn handle_log(
+    log: &serde_json::Value,
+    whale_addresses: &HashSet<String>,
+    trade_tx: &mpsc::Sender<WhaleTradeEvent>,
+    seen_txs: &mut SeenTxs,
+    timestamp: DateTime<Utc>,
+    replayed: bool,
+) {
+    // NegRisk adapter events: remember the tx, never forward as a trade
+    if let Some(event) = decode_neg_risk_event(log) {
+        if whale_addresses.contains(&event.stakeholder) {
+            counter!("chain_neg_risk_events_total", "kind" => event.kind.as_str()).increment(1);
+            tracing::info!(
+                wallet = %event.stakeholder,
+                kind = event.kind.as_str(),
+                market = %event.market,
+                amount = %event.amount,
+                "Chain event: whale NegRisk adapter action (position-neutral, not copied)"
+            );
+            if let Some(tx_hash) = event.tx_hash {
+                seen_txs.conversions.insert(tx_hash, Instant::now());
+            }
+        }
         return;
     }
 
-    // Parse data: 5 x uint256 (makerAssetId, takerAssetId, makerAmountFilled, takerAmountFilled, fee)
-    let data_hex = result
-        .get("data")
-        .and_then(|d| d.as_str())
-        .unwrap_or_default();
-    let data_hex = data_hex.strip_prefix("0x").unwrap_or(data_hex);
+    let Some(fill) = log_data(log).and_then(|data| Fill::decode(&data)) else {
+        return;
+    };
+    let tx_hash = log
+        .get("transactionHash")
+        .and_then(|h| h.as_str())
+        .map(|h| h.to_lowercase());
 
-    if data_hex.len() < 320 {
-        // 5 * 64 hex chars
-        tracing::warn!(
-            data_len = data_hex.len(),
-            "Chain event: data too short for OrderFilled"
-        );
+    // Fills inside a transaction where the whale used the NegRisk adapter are
+    // legs of that conversion, not directional trades (adapter logs come first).
+    if let Some(tx_hash) = tx_hash.as_deref().filter(|tx| seen_txs.in_conversion(tx)) {
+        tracing::debug!(tx_hash, "Chain event: fill is part of a NegRisk conversion — skipping");
         return;
     }
 
-    let maker_asset_id = &data_hex[0..64];
-    let taker_asset_id = &data_hex[64..128];
-    let maker_amount_filled = parse_uint256_decimal(&data_hex[128..192], USDC_DECIMALS);
-    let taker_amount_filled = parse_uint256_decimal(&data_hex[192..256], USDC_DECIMALS);
-    // fee = data_hex[256..320] — not used
+    let maker_is_whale = whale_addresses.contains(&fill.maker);
+    let taker = fill.taker.as_ref().filter(|t| !maker_is_whale && whale_addresses.contains(*t));
+    if !maker_is_whale && taker.is_none() {
+        return;
+    }
+
+    // A match emits the maker legs, then the taker order's own OrderFilled
+    // (taker = exchange), then its OrdersMatched summary: the own fill and the
+    // summary repeat the legs, so forward them only when no leg reached us
+    if let Some(tx_hash) = &tx_hash {
+        let order_key = format!("{tx_hash}:{:#x}", fill.order_hash);
+        let wallet_key = format!("{tx_hash}:{}", fill.maker);
+        if fill.taker.is_none() && (seen_txs.has_fill(&order_key) || seen_txs.has_fill(&wallet_key)) {
+            tracing::debug!(tx_hash, wallet = %fill.maker, "Chain event: OrdersMatched already seen as fills — skipping");
+            return;
+        }
+        if maker_is_whale && fill.taker.is_some() && (seen_txs.has_fill(&order_key) || seen_txs.has_fill(&wallet_key)) {
+            tracing::debug!(tx_hash, wallet = %fill.maker, "Chain event: taker order fill already seen as legs — skipping");
+            return;
+        }
+        let key = match taker {
+            Some(taker) => format!("{tx_hash}:{taker}"),
+            None => order_key,
+        };
+        seen_txs.whale_fills.insert(key, Instant::now());
+    }
 
     // Determine whale wallet, side, asset_id, size, price
-    let (wallet, side, asset_id, size, price) = if maker_is_whale {
-        determine_trade_params(
-            &maker,
+    let (wallet, side, asset_id, size, price) = match taker {
+        None => determine_trade_params(
+            &fill.maker,
             true, // is_maker
-            maker_asset_id,
-            taker_asset_id,
-            maker_amount_filled,
-            taker_amount_filled,
-        )
-    } else {
-        determine_trade_params(
-            &taker,
+            fill.maker_asset_id,
+            fill.taker_asset_id,
+            fill.maker_amount,
+            fill.taker_amount,
+        ),
+        Some(taker) => determine_trade_params(
+            taker,
             false, // is_taker
-            maker_asset_id,
-            taker_asset_id,
-            maker_amount_filled,
-            taker_amount_filled,
-        )
+            fill.maker_asset_id,
+            fill.taker_asset_id,
+            fill.maker_amount,
+            fill.taker_amount,
+        ),
     };
 
     let notional = size * price;
 
     let event = WhaleTradeEvent {
         wallet,
+        // Keyed by condition ID before the pipeline (see `market_enricher`)
         market_id: asset_id.clone(),
         asset_id,
         side,
         size,
         price,
         notional,
-        timestamp: Utc::now(),
+        timestamp,
+        tx_hash,
+        fill_index: log
+            .get("logIndex")
+            .and_then(|i| i.as_str())
+            .and_then(parse_hex_u64)
+            .and_then(|i| i64::try_from(i).ok()),
+        replayed,
     };
 
     tracing::info!(
@@ -280,27 +956,41 @@ async fn handle_rpc_message(
     }
 }
 
-/// Extract a 20-byte address from a 32-byte zero-padded hex topic.
-/// Input: "0x000000000000000000000000abcdef1234567890abcdef1234567890abcdef12"
-/// Output: "0xabcdef1234567890abcdef1234567890abcdef12"
-fn extract_address(topic: &str) -> String {
-    let hex = topic.strip_prefix("0x").unwrap_or(topic);
-    if hex.len() < 40 {
-        return format!("0x{hex}");
-    }
-    // Take last 40 hex chars (20 bytes)
-    let addr = &hex[hex.len() - 40..];
-    format!("0x{addr}").to_lowercase()
-}
+/// Decode a NegRisk adapter log (PositionSplit / PositionsMerge / PositionsConverted).
+/// Returns None for any other log.
+fn decode_neg_risk_event(log: &serde_json::Value) -> Option<NegRiskEvent> {
+    let data = log_data(log)?;
+    let kind = NegRiskEventKind::from_signature(*data.topics().first()?)?;
+    let (stakeholder, market, amount) = match kind {
+        NegRiskEventKind::Split => {
+            let e = PositionSplit::decode_log_data(&data).ok()?;
+            (e.stakeholder, e.conditionId, e.amount)
+        }
+        NegRiskEventKind::Merge => {
+            let e = PositionsMerge::decode_log_data(&data).ok()?;
+            (e.stakeholder, e.conditionId, e.amount)
+        }
+        NegRiskEventKind::Conversion => {
+            let e = PositionsConverted::decode_log_data(&data).ok()?;
+            (e.stakeholder, e.marketId, e.amount)
+        }
+    };
+    let stakeholder = address_key(stakeholder);
+    let market = format!("{market:#x}");
+    let amount = token_amount(amount, USDC_DECIMALS);
 
-/// Parse a 64-char hex uint256 into a Decimal with the given decimal places.
-fn parse_uint256_decimal(hex: &str, decimals: u32) -> Decimal {
-    // Use u128 which handles up to ~3.4e38 — sufficient for USDC amounts
-    let value = u128::from_str_radix(hex.trim_start_matches('0'), 16).unwrap_or(0);
-    let mut d = Decimal::from(value);
-    let divisor = Decimal::from(10u64.pow(decimals));
-    d /= divisor;
-    d
+    let tx_hash = log
+        .get("transactionHash")
+        .and_then(|h| h.as_str())
+        .map(|h| h.to_lowercase());
+
+    Some(NegRiskEvent {
+        kind,
+        stakeholder,
+        market,
+        amount,
+        tx_hash,
+    })
 }
 
 /// Determine trade parameters based on whether the whale is maker or taker.
@@ -321,23 +1011,24 @@ fn parse_uint256_decimal(hex: &str, decimals: u32) -> Decimal {
 fn determine_trade_params(
     whale_addr: &str,
     is_maker: bool,
-    maker_asset_id_hex: &str,
-    taker_asset_id_hex: &str,
+    maker_asset_id: U256,
+    taker_asset_id: U256,
     maker_amount: Decimal,
     taker_amount: Decimal,
 ) -> (String, Side, String, Decimal, Decimal) {
-    let maker_asset_is_zero = is_zero_asset(maker_asset_id_hex);
-    let taker_asset_is_zero = is_zero_asset(taker_asset_id_hex);
+    // Asset ID 0 is the USDC side of the trade
+    let maker_asset_is_zero = maker_asset_id.is_zero();
+    let taker_asset_is_zero = taker_asset_id.is_zero();
 
     if is_maker {
         if maker_asset_is_zero {
             // Maker gives USDC → buying outcome tokens (takerAssetId)
-            let asset_id = format_asset_id(taker_asset_id_hex);
+            let asset_id = taker_asset_id.to_string();
             let price = safe_divide(maker_amount, taker_amount);
             (whale_addr.to_string(), Side::Buy, asset_id, taker_amount, price)
         } else {
             // Maker gives outcome tokens → selling
-            let asset_id = format_asset_id(maker_asset_id_hex);
+            let asset_id = maker_asset_id.to_string();
             let price = safe_divide(taker_amount, maker_amount);
             (whale_addr.to_string(), Side::Sell, asset_id, maker_amount, price)
         }
@@ -345,77 +1036,18 @@ fn determine_trade_params(
         // Taker
         if taker_asset_is_zero {
             // Taker gives USDC → buying outcome tokens (makerAssetId)
-            let asset_id = format_asset_id(maker_asset_id_hex);
+            let asset_id = maker_asset_id.to_string();
             let price = safe_divide(taker_amount, maker_amount);
             (whale_addr.to_string(), Side::Buy, asset_id, maker_amount, price)
         } else {
             // Taker gives outcome tokens → selling
-            let asset_id = format_asset_id(taker_asset_id_hex);
+            let asset_id = taker_asset_id.to_string();
             let price = safe_divide(maker_amount, taker_amount);
             (whale_addr.to_string(), Side::Sell, asset_id, taker_amount, price)
         }
     }
 }
 
-/// Check if a hex-encoded uint256 asset ID is zero (represents USDC side of the trade).
-fn is_zero_asset(hex: &str) -> bool {
-    hex.trim_start_matches('0').is_empty()
-}
-
-/// Convert a 64-char hex uint256 to its full decimal string representation.
-/// ERC-1155 token IDs are 256-bit values that overflow u128, so we use
-/// manual hex-to-decimal conversion via digit-by-digit arithmetic.
-fn format_asset_id(hex: &str) -> String {
-    let hex = hex.trim_start_matches('0');
-    if hex.is_empty() {
-        return "0".to_string();
-    }
-
-    // Try u128 first (fast path for small values)
-    if hex.len() <= 32 {
-        if let Ok(v) = u128::from_str_radix(hex, 16) {
-            return v.to_string();
-        }
-    }
-
-    // Full uint256: accumulate decimal digits from hex
-    let mut digits: Vec<u8> = vec![0];
-    for ch in hex.chars() {
-        let hex_digit = match ch {
-            '0'..='9' => ch as u8 - b'0',
-            'a'..='f' => ch as u8 - b'a' + 10,
-            'A'..='F' => ch as u8 - b'A' + 10,
-            _ => return hex.to_string(),
-        };
-
-        // Multiply current decimal number by 16
-        let mut carry: u16 = 0;
-        for d in digits.iter_mut().rev() {
-            let val = *d as u16 * 16 + carry;
-            *d = (val % 10) as u8;
-            carry = val / 10;
-        }
-        while carry > 0 {
-            digits.insert(0, (carry % 10) as u8);
-            carry /= 10;
-        }
-
-        // Add the hex digit
-        carry = hex_digit as u16;
-        for d in digits.iter_mut().rev() {
-            let val = *d as u16 + carry;
-            *d = (val % 10) as u8;
-            carry = val / 10;
-        }
-        while carry > 0 {
-            digits.insert(0, (carry % 10) as u8);
-            carry /= 10;
-        }
-    }
-
-    digits.iter().map(|d| (d + b'0') as char).collect()
-}
-
 /// Safe division that returns ZERO on divide-by-zero.
 fn safe_divide(numerator: Decimal, denominator: Decimal) -> Decimal {
     if denominator.is_zero() {
@@ -429,68 +1061,73 @@ fn safe_divide(numerator: Decimal, denominator: Decimal) -> Decimal {
 mod tests {
     use super::*;
 
-    #[test]
-    fn test_extract_address() {
-        let topic = "0x0000000000000000000000004bfb41d5b3570defd03c39a9a4d8de6bd8b8982e";
-        assert_eq!(
-            extract_address(topic),
-            "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e"
-        );
-    }
-
-    #[test]
-    fn test_extract_address_short() {
-        assert_eq!(extract_address("0xabcd"), "0xabcd");
-    }
+    /// An order hash / condition ID topic.
+    const ORDER_HASH: &str = "0x00000000000000000000000000000000000000000000000000000000000000a1";
+    /// An untracked counterparty, as an address topic.
+    const COUNTERPARTY: &str = "0x0000000000000000000000000000000000000000000000000000000000000b02";
 
     #[test]
-    fn test_parse_uint256_decimal() {
-        // 1_000_000 in hex = 0xF4240, padded to 64 chars
-        let hex = "00000000000000000000000000000000000000000000000000000000000f4240";
-        let result = parse_uint256_decimal(hex, 6);
-        assert_eq!(result, Decimal::from(1)); // 1_000_000 / 10^6 = 1.0
+    fn test_event_signatures_match_subscribed_topics() {
+        let topic = |hash: B256| format!("{hash:#x}");
+        assert_eq!(topic(OrderFilled::SIGNATURE_HASH), ORDER_FILLED_TOPIC);
+        assert_eq!(topic(OrdersMatched::SIGNATURE_HASH), ORDERS_MATCHED_TOPIC);
+        assert_eq!(topic(PositionSplit::SIGNATURE_HASH), POSITION_SPLIT_TOPIC);
+        assert_eq!(topic(PositionsMerge::SIGNATURE_HASH), POSITIONS_MERGE_TOPIC);
+        assert_eq!(topic(PositionsConverted::SIGNATURE_HASH), POSITIONS_CONVERTED_TOPIC);
     }
 
-    #[test]
-    fn test_parse_uint256_decimal_large() {
-        // 50_000_000 (50 USDC) = 0x2FAF080
-        let hex = "0000000000000000000000000000000000000000000000000000000002faf080";
-        let result = parse_uint256_decimal(hex, 6);
-        assert_eq!(result, Decimal::from(50));
-    }
-
-    #[test]
-    fn test_is_zero_asset() {
-        let zero = "0000000000000000000000000000000000000000000000000000000000000000";
-        assert!(is_zero_asset(zero));
-
-        let nonzero = "0000000000000000000000000000000000000000000000000000000002faf080";
-        assert!(!is_zero_asset(nonzero));
-    }
+    #[tokio::test]
+    async fn test_order_filled_fixture_decodes_to_whale_buy() {
+        // eth_subscription notification for a fill of a whale's maker order:
+        // the whale pays 52.5 USDC (asset 0) for 75 outcome tokens of a
+        // CLOB token ID that overflows u128
+        let fixture = r#"{
+            "jsonrpc": "2.0",
+            "method": "eth_subscription",
+            "params": {
+                "subscription": "0x4a8a4c0517381924f9838102c5a4dcb7",
+                "result": {
+                    "address": "0x4bfb41d5b3570defd03c39a9a4d8de6bd8b8982e",
+                    "topics": [
+                        "0xd0a08e8c493f9c94f29311604c9de1b4e8c8d4c06bd0c789af57f2d65bfec0f6",
+                        "0x9c3e5f0c1d6a8b7e2f4d1c0b3a29e8f7d6c5b4a39281706f5e4d3c2b1a0f9e8d",
+                        "0x000000000000000000000000abcdef1234567890abcdef1234567890abcdef12",
+                        "0x000000000000000000000000c5d563a36ae78145c45a50134d48a1215220f80a"
+                    ],
+                    "data": "0x00000000000000000000000000000000000000000000000000000000000000007581b394f5a4dd19ec46e4ff36baa3a841c9eeb80af0f0850be552c0fece2d87000000000000000000000000000000000000000000000000000000000321162000000000000000000000000000000000000000000000000000000000047868c00000000000000000000000000000000000000000000000000000000000000000",
+                    "blockNumber": "0x3a2c1f4",
+                    "transactionHash": "0x5e1f0c7a9b3d2e4f6a8c0b1d3e5f7a9c2b4d6e8f0a1c3e5b7d9f2a4c6e8b0d1f",
+                    "transactionIndex": "0x1c",
+                    "blockHash": "0x8f2e4c6a0b1d3f5e7c9a2b4d6f8e0c1a3b5d7f9e2c4a6b8d0f1e3c5a7b9d2f4e",
+                    "logIndex": "0x5b",
+                    "removed": false
+                }
+            }
+        }"#;
 
-    #[test]
-    fn test_format_asset_id_small() {
-        let hex = "0000000000000000000000000000000000000000000000000000000002faf080";
-        assert_eq!(format_asset_id(hex), "50000000");
-    }
+        let whales: HashSet<String> = ["0xabcdef1234567890abcdef1234567890abcdef12".to_string()].into();
+        let (tx, mut rx) = mpsc::channel(1);
+        let block = handle_rpc_message(
+            fixture,
+            &whales,
+            &tx,
+            &mut SeenTxs::default(),
+            &mut ConfirmationBuffer::new(0),
+            None,
+        )
+        .await;
+        assert_eq!(block, Some(0x3a2c1f4));
 
-    #[test]
-    fn test_format_asset_id_uint256() {
-        // Real Polymarket CLOB token ID that overflows u128
-        let hex = "7581b394f5a4dd19ec46e4ff36baa3a841c9eeb80af0f0850be552c0fece2d87";
-        let result = format_asset_id(hex);
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.wallet, "0xabcdef1234567890abcdef1234567890abcdef12");
+        assert_eq!(event.side, Side::Buy);
         assert_eq!(
-            result,
+            event.asset_id,
             "53149765984136093709083310870325314268796238675098813080656099381431327665543"
         );
-        // Verify it's a large decimal string (not hex fallback)
-        assert!(result.chars().all(|c| c.is_ascii_digit()));
-    }
-
-    #[test]
-    fn test_format_asset_id_zero() {
-        let hex = "0000000000000000000000000000000000000000000000000000000000000000";
-        assert_eq!(format_asset_id(hex), "0");
+        assert_eq!(event.size, Decimal::from(75));
+        assert_eq!(event.price, Decimal::new(7, 1));
+        assert_eq!(event.notional, Decimal::new(525, 1));
     }
 
     #[test]
@@ -502,8 +1139,8 @@ mod tests {
     #[test]
     fn test_determine_trade_params_maker_buy() {
         // Maker gives USDC (asset 0), receives outcome tokens
-        let zero_asset = "0000000000000000000000000000000000000000000000000000000000000000";
-        let token_asset = "0000000000000000000000000000000000000000000000000000000000000064"; // 100
+        let zero_asset = U256::ZERO;
+        let token_asset = U256::from(100);
 
         let (wallet, side, asset_id, size, price) = determine_trade_params(
             "0xwhale",
@@ -522,11 +1159,350 @@ mod tests {
         assert_eq!(price, Decimal::new(5, 1));
     }
 
+    #[test]
+    fn test_decode_neg_risk_conversion() {
+        let log = serde_json::json!({
+            "address": NEG_RISK_ADAPTER,
+            "topics": [
+                POSITIONS_CONVERTED_TOPIC,
+                "0x000000000000000000000000AbCdEf1234567890abcdef1234567890abcdef12",
+                "0x7581b394f5a4dd19ec46e4ff36baa3a841c9eeb80af0f0850be552c0fece2d00",
+                "0x0000000000000000000000000000000000000000000000000000000000000005"
+            ],
+            "data": "0x0000000000000000000000000000000000000000000000000000000002faf080",
+            "transactionHash": "0xABC123"
+        });
+
+        let event = decode_neg_risk_event(&log).unwrap();
+        assert_eq!(event.kind, NegRiskEventKind::Conversion);
+        assert_eq!(event.stakeholder, "0xabcdef1234567890abcdef1234567890abcdef12");
+        assert_eq!(event.amount, Decimal::from(50));
+        assert_eq!(event.tx_hash.as_deref(), Some("0xabc123"));
+    }
+
+    #[test]
+    fn test_decode_neg_risk_ignores_order_filled() {
+        let log = serde_json::json!({
+            "topics": [ORDER_FILLED_TOPIC, ORDER_HASH, COUNTERPARTY, COUNTERPARTY],
+            "data": format!("0x{}", "0".repeat(320)),
+        });
+        assert!(decode_neg_risk_event(&log).is_none());
+    }
+
+    #[tokio::test]
+    async fn test_order_filled_in_conversion_tx_not_forwarded() {
+        let whale = "0xabcdef1234567890abcdef1234567890abcdef12";
+        let whales: HashSet<String> = [whale.to_string()].into();
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut seen_txs = SeenTxs::default();
+        let mut pending = ConfirmationBuffer::new(0);
+
+        let padded = format!("0x000000000000000000000000{}", &whale[2..]);
+        let merge = serde_json::json!({
+            "method": "eth_subscription",
+            "params": { "result": {
+                "topics": [POSITIONS_MERGE_TOPIC, padded, ORDER_HASH],
+                "data": "0x0000000000000000000000000000000000000000000000000000000002faf080",
+                "transactionHash": "0xfeed"
+            }}
+        });
+        handle_rpc_message(&merge.to_string(), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        assert!(seen_txs.conversions.contains_key("0xfeed"));
+
+        // Whale sells 100 tokens for 30 USDC in the same tx
+        let zero = "0".repeat(64);
+        let token = format!("{:0>64}", "64");
+        let data = format!("0x{token}{zero}{:0>64}{:0>64}{zero}", "5f5e100", "1c9c380");
+        let fill = serde_json::json!({
+            "method": "eth_subscription",
+            "params": { "result": {
+                "topics": [ORDER_FILLED_TOPIC, ORDER_HASH, padded, COUNTERPARTY],
+                "data": data,
+                "transactionHash": "0xFEED"
+            }}
+        });
+        handle_rpc_message(&fill.to_string(), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        assert!(rx.try_recv().is_err());
+
+        // The same fill in an unrelated tx is a normal trade
+        let mut fill = fill;
+        fill["params"]["result"]["transactionHash"] = "0xbeef".into();
+        handle_rpc_message(&fill.to_string(), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.side, Side::Sell);
+        assert_eq!(event.size, Decimal::from(100));
+    }
+
+    #[tokio::test]
+    async fn test_orders_matched_forwarded_only_without_its_fills() {
+        let whale = "0xabcdef1234567890abcdef1234567890abcdef12";
+        let whales: HashSet<String> = [whale.to_string()].into();
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut seen_txs = SeenTxs::default();
+        let mut pending = ConfirmationBuffer::new(0);
+
+        // The whale's taker order buys 100 tokens for 70 USDC
+        let padded = format!("0x000000000000000000000000{}", &whale[2..]);
+        let exchange = format!("0x000000000000000000000000{}", &CTF_EXCHANGE[2..]);
+        let zero = "0".repeat(64);
+        let token = format!("{:0>64}", "64");
+        let amounts = format!("{:0>64}{:0>64}", "42c1d80", "5f5e100");
+        let log = |topics: serde_json::Value, data: String, tx_hash: &str| {
+            serde_json::json!({
+                "method": "eth_subscription",
+                "params": { "result": { "topics": topics, "data": data, "transactionHash": tx_hash } }
+            })
+            .to_string()
+        };
+        let matched = |tx_hash: &str| {
+            log(
+                serde_json::json!([ORDERS_MATCHED_TOPIC, ORDER_HASH, padded]),
+                format!("0x{zero}{token}{amounts}"),
+                tx_hash,
+            )
+        };
+
+        // Only the summary reached us: it is the whale's trade
+        handle_rpc_message(&matched("0xaa"), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.side, Side::Buy);
+        assert_eq!(event.size, Decimal::from(100));
+        assert_eq!(event.price, Decimal::new(7, 1));
+
+        // The taker order's own OrderFilled came first
+        let own_fill = log(
+            serde_json::json!([ORDER_FILLED_TOPIC, ORDER_HASH, padded, exchange]),
+            format!("0x{zero}{token}{amounts}{zero}"),
+            "0xbb",
+        );
+        handle_rpc_message(&own_fill, &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        assert_eq!(rx.try_recv().unwrap().side, Side::Buy);
+        handle_rpc_message(&matched("0xbb"), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        assert!(rx.try_recv().is_err());
+
+        // A maker leg with the whale as taker came first
+        let leg = log(
+            serde_json::json!([ORDER_FILLED_TOPIC, COUNTERPARTY, COUNTERPARTY, padded]),
+            format!("0x{token}{zero}{:0>64}{:0>64}{zero}", "5f5e100", "42c1d80"),
+            "0xcc",
+        );
+        handle_rpc_message(&leg, &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        assert_eq!(rx.try_recv().unwrap().side, Side::Buy);
+        handle_rpc_message(&matched("0xcc"), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_taker_order_fill_not_forwarded_after_its_legs() {
+        let whale = "0xabcdef1234567890abcdef1234567890abcdef12";
+        let whales: HashSet<String> = [whale.to_string()].into();
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut seen_txs = SeenTxs::default();
+        let mut pending = ConfirmationBuffer::new(0);
+
+        let padded = format!("0x000000000000000000000000{}", &whale[2..]);
+        let exchange = format!("0x000000000000000000000000{}", &CTF_EXCHANGE[2..]);
+        let zero = "0".repeat(64);
+        let token = format!("{:0>64}", "64");
+        let log = |topics: serde_json::Value, data: String| {
+            serde_json::json!({
+                "method": "eth_subscription",
+                "params": { "result": { "topics": topics, "data": data, "transactionHash": "0xdd" } }
+            })
+            .to_string()
+        };
+
+        // The whale's taker order buys 100 tokens for 70 USDC against two makers
+        let leg = |maker_hash: &str| {
+            log(
+                serde_json::json!([ORDER_FILLED_TOPIC, maker_hash, COUNTERPARTY, padded]),
+                format!("0x{token}{zero}{:0>64}{:0>64}{zero}", "2faf080", "2160ec0"),
+            )
+        };
+        let own_fill = log(
+            serde_json::json!([ORDER_FILLED_TOPIC, ORDER_HASH, padded, exchange]),
+            format!("0x{zero}{token}{:0>64}{:0>64}{zero}", "42c1d80", "5f5e100"),
+        );
+
+        handle_rpc_message(&leg(COUNTERPARTY), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        handle_rpc_message(&leg(&format!("0x{:0>64}", "b03")), &whales, &tx, &mut seen_txs, &mut pending, None).await;
+        handle_rpc_message(&own_fill, &whales, &tx, &mut seen_txs, &mut pending, None).await;
+
+        let total: Decimal = std::iter::from_fn(|| rx.try_recv().ok()).map(|e| e.size).sum();
+        assert_eq!(total, Decimal::from(100));
+    }
+
+    #[tokio::test]
+    async fn test_live_logs_already_backfilled_are_skipped() {
+        let whale = "0xabcdef1234567890abcdef1234567890abcdef12";
+        let whales: HashSet<String> = [whale.to_string()].into();
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut seen_txs = SeenTxs::default();
+        let mut pending = ConfirmationBuffer::new(0);
+
+        let padded = format!("0x000000000000000000000000{}", &whale[2..]);
+        let zero = "0".repeat(64);
+        let token = format!("{:0>64}", "64");
+        let data = format!("0x{token}{zero}{:0>64}{:0>64}{zero}", "5f5e100", "1c9c380");
+        let fill = serde_json::json!({
+            "method": "eth_subscription",
+            "params": { "result": {
+                "topics": [ORDER_FILLED_TOPIC, ORDER_HASH, padded, COUNTERPARTY],
+                "data": data,
+                "blockNumber": "0x64",
+                "transactionHash": "0xbeef"
+            }}
+        });
+
+        let block = handle_rpc_message(&fill.to_string(), &whales, &tx, &mut seen_txs, &mut pending, Some(100)).await;
+        assert_eq!(block, Some(100));
+        assert!(rx.try_recv().is_err());
+
+        let block = handle_rpc_message(&fill.to_string(), &whales, &tx, &mut seen_txs, &mut pending, Some(99)).await;
+        assert_eq!(block, Some(100));
+        assert!(rx.try_recv().is_ok());
+    }
+
+    #[tokio::test]
+    async fn test_fills_wait_for_confirmations_and_reorged_ones_are_dropped() {
+        let whale = "0xabcdef1234567890abcdef1234567890abcdef12";
+        let whales: HashSet<String> = [whale.to_string()].into();
+        let (tx, mut rx) = mpsc::channel(4);
+        let mut seen_txs = SeenTxs::default();
+        let mut pending = ConfirmationBuffer::new(2);
+
+        let padded = format!("0x000000000000000000000000{}", &whale[2..]);
+        let zero = "0".repeat(64);
+        let token = format!("{:0>64}", "64");
+        let data = format!("0x{token}{zero}{:0>64}{:0>64}{zero}", "5f5e100", "1c9c380");
+        let fill = |tx_hash: &str, removed: bool| {
+            serde_json::json!({
+                "method": "eth_subscription",
+                "params": { "result": {
+                    "topics": [ORDER_FILLED_TOPIC, ORDER_HASH, padded, COUNTERPARTY],
+                    "data": data,
+                    "blockNumber": "0x64",
+                    "logIndex": "0x0",
+                    "transactionHash": tx_hash,
+                    "removed": removed
+                }}
+            })
+            .to_string()
+        };
+        let head = |number: &str| {
+            serde_json::json!({
+                "method": "eth_subscription",
+                "params": { "result": { "number": number } }
+            })
+            .to_string()
+        };
+
+        // Block 100 holds two whale fills, only block 98 is confirmed; the first fill is reorged out
+        assert_eq!(handle_rpc_message(&fill("0xaa", false), &whales, &tx, &mut seen_txs, &mut pending, None).await, Some(98));
+        assert_eq!(handle_rpc_message(&fill("0xbb", false), &whales, &tx, &mut seen_txs, &mut pending, None).await, Some(98));
+        assert_eq!(handle_rpc_message(&fill("0xaa", true), &whales, &tx, &mut seen_txs, &mut pending, None).await, Some(98));
+        assert!(rx.try_recv().is_err());
+
+        // One block on top isn't enough
+        assert_eq!(handle_rpc_message(&head("0x65"), &whales, &tx, &mut seen_txs, &mut pending, None).await, Some(99));
+        assert!(rx.try_recv().is_err());
+
+        // Two blocks on top confirm block 100: only the surviving fill is copied
+        assert_eq!(handle_rpc_message(&head("0x66"), &whales, &tx, &mut seen_txs, &mut pending, None).await, Some(100));
+        let event = rx.try_recv().unwrap();
+        assert_eq!(event.side, Side::Sell);
+        assert!(!event.replayed);
+        assert!(rx.try_recv().is_err());
+    }
+
+    #[tokio::test]
+    async fn test_backfill_gap_replays_whale_fills_in_order() {
+        use axum::{routing::post, Json, Router};
+
+        let whale = "0xabcdef1234567890abcdef1234567890abcdef12";
+        let padded = format!("0x000000000000000000000000{}", &whale[2..]);
+        let zero = "0".repeat(64);
+        let token = format!("{:0>64}", "64");
+        let sell = format!("0x{token}{zero}{:0>64}{:0>64}{zero}", "5f5e100", "1c9c380");
+        let buy = format!("0x{zero}{token}{:0>64}{:0>64}{zero}", "1c9c380", "5f5e100");
+        let fill = |data: &str, maker: &str, taker: &str, block: &str, index: &str| {
+            serde_json::json!({
+                "topics": [ORDER_FILLED_TOPIC, ORDER_HASH, maker, taker],
+                "data": data,
+                "blockNumber": block,
+                "logIndex": index,
+                "transactionHash": format!("0x{block}{index}"),
+            })
+        };
+        // Whale as maker in block 0x66; as taker (against itself) in block 0x65
+        let maker_logs = serde_json::json!([
+            fill(&buy, &padded, &padded, "0x65", "0x1"),
+            fill(&sell, &padded, COUNTERPARTY, "0x66", "0x0"),
+        ]);
+        let taker_logs = serde_json::json!([fill(&buy, &padded, &padded, "0x65", "0x1")]);
+
+        let app = Router::new().route(
+            "/",
+            post(move |Json(req): Json<serde_json::Value>| {
+                let (maker_logs, taker_logs) = (maker_logs.clone(), taker_logs.clone());
+                async move {
+                    let result = match req["method"].as_str().unwrap() {
+                        "eth_blockNumber" => serde_json::json!("0x66"),
+                        "eth_getBlockByNumber" => serde_json::json!({ "timestamp": "0x6553f100" }),
+                        "eth_getLogs" => {
+                            let topics = req["params"][0]["topics"].as_array().unwrap().clone();
+                            match topics.len() {
+                                3 if topics[0][0] == ORDER_FILLED_TOPIC => maker_logs,
+                                4 => taker_logs,
+                                _ => serde_json::json!([]),
+                            }
+                        }
+                        other => panic!("unexpected method {other}"),
+                    };
+                    Json(serde_json::json!({ "jsonrpc": "2.0", "id": 1, "result": result }))
+                }
+            }),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let rpc_url = format!("http://{}", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        let whales: HashSet<String> = [whale.to_string()].into();
+        let (tx, mut rx) = mpsc::channel(4);
+        let (head, replayed) = backfill_gap(
+            &reqwest::Client::new(),
+            &rpc_url,
+            0x64,
+            &whales,
+            &tx,
+            &mut SeenTxs::default(),
+            &mut ConfirmationBuffer::new(0),
+        )
+        .await
+        .unwrap();
+
+        assert_eq!((head, replayed), (0x66, 2));
+        let first = rx.try_recv().unwrap();
+        assert_eq!(first.side, Side::Buy);
+        assert_eq!(first.timestamp, DateTime::from_timestamp(0x6553f100, 0).unwrap());
+        assert!(first.replayed);
+        assert_eq!(rx.try_recv().unwrap().side, Side::Sell);
+        assert!(rx.try_recv().is_err());
+
+        // Nothing to replay once caught up
+        let mut pending = ConfirmationBuffer::new(0);
+        let (head, replayed) =
+            backfill_gap(&reqwest::Client::new(), &rpc_url, 0x67, &whales, &tx, &mut SeenTxs::default(), &mut pending)
+                .await
+                .unwrap();
+        assert_eq!((head, replayed), (0x66, 0));
+    }
+
     #[test]
     fn test_determine_trade_params_taker_sell() {
         // Taker gives outcome tokens, receives USDC
-        let zero_asset = "0000000000000000000000000000000000000000000000000000000000000000";
-        let token_asset = "0000000000000000000000000000000000000000000000000000000000000064"; // 100
+        let zero_asset = U256::ZERO;
+        let token_asset = U256::from(100);
 
         let (wallet, side, asset_id, size, price) = determine_trade_params(
             "0xwhale",
@@ -544,4 +1520,49 @@ mod tests {
         // price = 30/100 = 0.3
         assert_eq!(price, Decimal::new(3, 1));
     }
+
+    #[test]
+    fn test_endpoint_rotation_fails_over_to_healthiest() {
+        let urls = ["wss://a", "wss://b", "wss://c"].map(String::from).to_vec();
+        let mut rotation = EndpointRotation::new(urls);
+
+        // Errors below the failover threshold stay on the endpoint
+        for _ in 1..FAILOVER_AFTER_ERRORS {
+            assert_eq!(rotation.record_error(), None);
+        }
+        assert_eq!(rotation.record_error(), Some("wss://b"));
+        assert_eq!(rotation.current().consecutive_errors, 0);
+
+        // b fails too; c is healthier than a, which just failed
+        for _ in 1..FAILOVER_AFTER_ERRORS {
+            rotation.record_error();
+        }
+        assert_eq!(rotation.record_error(), Some("wss://c"));
+
+        // A healthy session resets the run of errors
+        rotation.record_error();
+        rotation.record_healthy();
+        assert_eq!(rotation.current().consecutive_errors, 0);
+        assert_eq!(rotation.current().health, MAX_HEALTH - ERROR_PENALTY + HEALTHY_REWARD);
+
+        // With every endpoint equally unhealthy the next in order wins
+        let mut rotation = EndpointRotation::new(["wss://a", "wss://b", "wss://c"].map(String::from).to_vec());
+        rotation.current = 1;
+        rotation.endpoints[0].health = ERROR_PENALTY;
+        rotation.endpoints[2].health = ERROR_PENALTY;
+        for _ in 1..FAILOVER_AFTER_ERRORS {
+            rotation.record_error();
+        }
+        assert_eq!(rotation.record_error(), Some("wss://c"));
+    }
+
+    #[test]
+    fn test_endpoint_rotation_single_endpoint_never_fails_over() {
+        let mut rotation = EndpointRotation::new(vec!["wss://only".into()]);
+        for _ in 0..FAILOVER_AFTER_ERRORS * 2 {
+            assert_eq!(rotation.record_error(), None);
+        }
+        assert_eq!(rotation.current().url, "wss://only");
+        assert_eq!(rotation.current().health, 0);
+    }
 }
@@ -0,0 +1,228 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+
+/// One historic whale trade parsed from a CSV export.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ImportedTrade {
+    pub market_id: String,
+    pub token_id: String,
+    pub condition_id: Option<String>,
+    pub side: &'static str,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub notional: Decimal,
+    pub tx_hash: Option<String>,
+    pub traded_at: DateTime<Utc>,
+}
+
+/// A CSV row that failed validation. `line` is 1-based and counts the header.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct RejectedRow {
+    pub line: u64,
+    pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct ParsedCsv {
+    pub trades: Vec<ImportedTrade>,
+    pub rejected: Vec<RejectedRow>,
+}
+
+/// Accepted header names per field, matched case-insensitively. Covers the
+/// Polymarket data API, subgraph and common Dune column names.
+const TOKEN_ID: &[&str] = &["token_id", "asset", "asset_id", "outcome_token_id"];
+const SIDE: &[&str] = &["side", "direction"];
+const SIZE: &[&str] = &["size", "shares", "amount"];
+const PRICE: &[&str] = &["price"];
+const TIMESTAMP: &[&str] = &["traded_at", "timestamp", "block_time", "time"];
+const MARKET_ID: &[&str] = &["market_id", "market"];
+const CONDITION_ID: &[&str] = &["condition_id", "conditionid"];
+const NOTIONAL: &[&str] = &["notional", "usdc_size", "usd_amount", "volume"];
+const TX_HASH: &[&str] = &["tx_hash", "transaction_hash", "hash", "tx"];
+
+/// Parse a whale trade CSV export. Requires token, side, size, price and
+/// timestamp columns; market, condition id, notional and tx hash are optional
+/// (market falls back to condition id, then token id; notional to size × price).
+///
+/// Fails only on an unusable header — bad rows are collected in `rejected`.
+pub fn parse_trades_csv(data: &[u8], now: DateTime<Utc>) -> anyhow::Result<ParsedCsv> {
+    let mut reader = csv::ReaderBuilder::new()
+        .trim(csv::Trim::All)
+        .flexible(true)
+        .from_reader(data);
+
+    let headers: Vec<String> = reader.headers()?.iter().map(|h| h.to_lowercase()).collect();
+    let column = |names: &[&str]| headers.iter().position(|h| names.contains(&h.as_str()));
+    let required = |names: &[&str]| {
+        column(names).ok_or_else(|| anyhow::anyhow!("missing '{}' column", names[0]))
+    };
+    let token_col = required(TOKEN_ID)?;
+    let side_col = required(SIDE)?;
+    let size_col = required(SIZE)?;
+    let price_col = required(PRICE)?;
+    let ts_col = required(TIMESTAMP)?;
+    let market_col = column(MARKET_ID);
+    let condition_col = column(CONDITION_ID);
+    let notional_col = column(NOTIONAL);
+    let tx_col = column(TX_HASH);
+
+    let mut parsed = ParsedCsv::default();
+    for record in reader.records() {
+        let record = match record {
+            Ok(r) => r,
+            Err(e) => {
+                let line = e.position().map(|p| p.line()).unwrap_or(0);
+                parsed.rejected.push(RejectedRow { line, reason: e.to_string() });
+                continue;
+            }
+        };
+        let line = record.position().map(|p| p.line()).unwrap_or(0);
+        let field = |col: usize| record.get(col).filter(|v| !v.is_empty());
+        let optional = |col: Option<usize>| col.and_then(field).map(str::to_string);
+
+        let row = || -> Result<ImportedTrade, String> {
+            let token_id = field(token_col).ok_or("missing token id")?.to_string();
+            let side = parse_side(field(side_col).unwrap_or_default())?;
+            let size = parse_decimal(field(size_col), "size")?.round_dp(6);
+            if size <= Decimal::ZERO {
+                return Err(format!("size {size} must be positive"));
+            }
+            let price = parse_decimal(field(price_col), "price")?.round_dp(6);
+            if price <= Decimal::ZERO || price >= Decimal::ONE {
+                return Err(format!("price {price} outside (0, 1)"));
+            }
+            let traded_at = field(ts_col)
+                .and_then(parse_timestamp)
+                .ok_or("missing or unparseable timestamp")?;
+            if traded_at > now {
+                return Err(format!("timestamp {traded_at} is in the future"));
+            }
+            let notional = match notional_col.and_then(field) {
+                Some(v) => parse_decimal(Some(v), "notional")?.round_dp(6),
+                None => (size * price).round_dp(6),
+            };
+            if notional < Decimal::ZERO {
+                return Err(format!("notional {notional} is negative"));
+            }
+            let condition_id = optional(condition_col);
+            let market_id = optional(market_col)
+                .or_else(|| condition_id.clone())
+                .unwrap_or_else(|| token_id.clone());
+
+            Ok(ImportedTrade {
+                market_id,
+                token_id,
+                condition_id,
+                side,
+                size,
+                price,
+                notional,
+                tx_hash: optional(tx_col),
+                traded_at,
+            })
+        };
+
+        match row() {
+            Ok(trade) => parsed.trades.push(trade),
+            Err(reason) => parsed.rejected.push(RejectedRow { line, reason }),
+        }
+    }
+
+    Ok(parsed)
+}
+
+fn parse_side(s: &str) -> Result<&'static str, String> {
+    match s.to_uppercase().as_str() {
+        "BUY" | "B" => Ok("BUY"),
+        "SELL" | "S" => Ok("SELL"),
+        other => Err(format!("invalid side '{other}' (expected BUY or SELL)")),
+    }
+}
+
+fn parse_decimal(s: Option<&str>, name: &str) -> Result<Decimal, String> {
+    let s = s.ok_or_else(|| format!("missing {name}"))?;
+    s.parse::<Decimal>()
+        .or_else(|_| Decimal::from_scientific(s))
+        .map_err(|_| format!("invalid {name} '{s}'"))
+}
+
+/// Unix seconds or milliseconds, RFC 3339, or Dune's `YYYY-MM-DD HH:MM:SS[.fff] UTC`.
+fn parse_timestamp(s: &str) -> Option<DateTime<Utc>> {
+    if let Ok(secs) = s.parse::<i64>() {
+        return if secs > 1_000_000_000_000 {
+            DateTime::from_timestamp(secs / 1000, ((secs % 1000) * 1_000_000) as u32)
+        } else {
+            DateTime::from_timestamp(secs, 0)
+        };
+    }
+    if let Ok(dt) = DateTime::parse_from_rfc3339(s) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    let naive = s.strip_suffix(" UTC").unwrap_or(s);
+    NaiveDateTime::parse_from_str(naive, "%Y-%m-%d %H:%M:%S%.f")
+        .ok()
+        .map(|dt| dt.and_utc())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn now() -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc)
+    }
+
+    #[test]
+    fn test_parse_trades_csv() {
+        let csv = "\
+Asset,Side,Shares,Price,Block_Time,Condition_Id,Transaction_Hash
+tok1,buy,100,0.42,2025-06-01 12:00:00.000 UTC,0xcond,0xabc
+tok2,SELL,10.5,0.9,1748779200,,
+";
+        let parsed = parse_trades_csv(csv.as_bytes(), now()).unwrap();
+        assert!(parsed.rejected.is_empty());
+        assert_eq!(parsed.trades.len(), 2);
+
+        let first = &parsed.trades[0];
+        assert_eq!(first.side, "BUY");
+        assert_eq!(first.market_id, "0xcond");
+        assert_eq!(first.condition_id.as_deref(), Some("0xcond"));
+        assert_eq!(first.notional, Decimal::from(42));
+        assert_eq!(first.tx_hash.as_deref(), Some("0xabc"));
+        assert_eq!(first.traded_at.to_rfc3339(), "2025-06-01T12:00:00+00:00");
+
+        let second = &parsed.trades[1];
+        assert_eq!(second.market_id, "tok2");
+        assert_eq!(second.condition_id, None);
+        assert_eq!(second.tx_hash, None);
+        assert_eq!(second.traded_at, parsed.trades[0].traded_at);
+    }
+
+    #[test]
+    fn test_parse_trades_csv_rejects_bad_rows() {
+        let csv = "\
+token_id,side,size,price,timestamp
+tok,HOLD,1,0.5,1748779200
+tok,BUY,0,0.5,1748779200
+tok,BUY,1,1.2,1748779200
+tok,BUY,1,0.5,2030-01-01T00:00:00Z
+tok,BUY,1,0.5,yesterday
+tok,BUY,1,0.5,1748779200
+";
+        let parsed = parse_trades_csv(csv.as_bytes(), now()).unwrap();
+        assert_eq!(parsed.trades.len(), 1);
+        let lines: Vec<u64> = parsed.rejected.iter().map(|r| r.line).collect();
+        assert_eq!(lines, vec![2, 3, 4, 5, 6]);
+    }
+
+    #[test]
+    fn test_parse_trades_csv_missing_column() {
+        let err = parse_trades_csv(b"token_id,side,size,timestamp\n", now()).unwrap_err();
+        assert!(err.to_string().contains("'price'"));
+    }
+}
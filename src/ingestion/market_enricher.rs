@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use metrics::counter;
+use sqlx::PgPool;
+use tokio::sync::{mpsc, RwLock};
+
+use crate::db::market_repo;
+use crate::models::{normalize_condition_id, WhaleTradeEvent};
+use crate::polymarket::GammaClient;
+use crate::services::shutdown::TaskRegistry;
+
+/// How long a token no market was found for is left alone before it is
+/// looked up again (discovery may pick its market up in the meantime).
+const MISS_RETRY: Duration = Duration::from_secs(600);
+
+/// The market an outcome token belongs to.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MarketRef {
+    pub condition_id: String,
+    pub question: String,
+}
+
+/// Maps raw outcome token IDs — all an on-chain fill carries — to their
+/// market's condition ID and question: active_markets first, then the Gamma
+/// API. Found markets are cached for the life of the process; tokens with no
+/// market are retried after `MISS_RETRY`, lookup errors on the next event.
+#[derive(Clone)]
+pub struct MarketEnricher {
+    pool: PgPool,
+    gamma: GammaClient,
+    markets: Arc<RwLock<HashMap<String, MarketRef>>>,
+    misses: Arc<RwLock<HashMap<String, Instant>>>,
+}
+
+impl MarketEnricher {
+    pub fn new(pool: PgPool, gamma: GammaClient) -> Self {
+        Self {
+            pool,
+            gamma,
+            markets: Arc::new(RwLock::new(HashMap::new())),
+            misses: Arc::new(RwLock::new(HashMap::new())),
+        }
+    }
+
+    /// Market of an outcome token, None if it can't be found (yet).
+    pub async fn lookup(&self, token_id: &str) -> Option<MarketRef> {
+        if let Some(market) = self.markets.read().await.get(token_id) {
+            return Some(market.clone());
+        }
+        let recent_miss = self
+            .misses
+            .read()
+            .await
+            .get(token_id)
+            .is_some_and(|at| at.elapsed() < MISS_RETRY);
+        if recent_miss {
+            return None;
+        }
+
+        match self.fetch(token_id).await {
+            Ok(Some(market)) => {
+                self.misses.write().await.remove(token_id);
+                self.markets.write().await.insert(token_id.to_string(), market.clone());
+                Some(market)
+            }
+            Ok(None) => {
+                let mut misses = self.misses.write().await;
+                misses.retain(|_, at| at.elapsed() < MISS_RETRY);
+                misses.insert(token_id.to_string(), Instant::now());
+                None
+            }
+            Err(e) => {
+                tracing::warn!(token_id, error = %e, "Market lookup for token failed");
+                None
+            }
+        }
+    }
+
+    async fn fetch(&self, token_id: &str) -> anyhow::Result<Option<MarketRef>> {
+        if let Some((condition_id, question)) = market_repo::get_market_by_token_id(&self.pool, token_id).await? {
+            return Ok(Some(MarketRef { condition_id, question }));
+        }
+        let market = self.gamma.get_market_by_token_id(token_id).await?;
+        Ok(market.map(|m| MarketRef {
+            condition_id: m.condition_id,
+            question: m.question,
+        }))
+    }
+
+    /// Key an event by its market's condition ID, like API-sourced trades,
+    /// so resolution lookups and dedup match. Events already keyed by a
+    /// condition ID are left alone; events whose token has no known market
+    /// keep the token as market_id.
+    pub async fn enrich(&self, event: &mut WhaleTradeEvent) -> Option<MarketRef> {
+        if normalize_condition_id(&event.market_id).is_some() {
+            return None;
+        }
+        let Some(market) = self.lookup(&event.asset_id).await else {
+            counter!("market_enrichment_total", "outcome" => "unknown").increment(1);
+            tracing::debug!(token_id = %event.asset_id, "No market known for token — keeping token as market_id");
+            return None;
+        };
+        counter!("market_enrichment_total", "outcome" => "enriched").increment(1);
+        event.market_id = market.condition_id.clone();
+        Some(market)
+    }
+}
+
+/// Sender for an ingestion source whose events carry raw token IDs as
+/// market_id: events pass through a task that enriches each one before
+/// forwarding it to `pipeline_tx`.
+pub fn enrich_events(
+    tasks: &mut TaskRegistry,
+    enricher: MarketEnricher,
+    pipeline_tx: &mpsc::Sender<WhaleTradeEvent>,
+) -> mpsc::Sender<WhaleTradeEvent> {
+    let pipeline_tx = pipeline_tx.clone();
+    let (tx, mut rx) = mpsc::channel::<WhaleTradeEvent>(1000);
+
+    tasks.spawn("market_enricher", async move {
+        while let Some(mut event) = rx.recv().await {
+            if let Some(market) = enricher.enrich(&mut event).await {
+                tracing::debug!(
+                    token_id = %event.asset_id,
+                    condition_id = %market.condition_id,
+                    question = %market.question,
+                    "Trade event enriched with market"
+                );
+            }
+            if pipeline_tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tx
+}
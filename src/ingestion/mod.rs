@@ -1,3 +1,10 @@
+pub mod chain_events;
 pub mod chain_listener;
+pub mod csv_import;
+pub mod market_enricher;
 pub mod pipeline;
+pub mod price_cache;
+pub mod priority_lanes;
+pub mod user_ws_listener;
+pub mod wallet_workers;
 pub mod ws_listener;
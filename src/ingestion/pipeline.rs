@@ -1,44 +1,109 @@
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use metrics::{counter, histogram};
 use rust_decimal::Decimal;
 use sqlx::PgPool;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
 
-use crate::db::{basket_repo, config_repo, market_repo, position_repo, trade_repo, whale_repo};
+use crate::db::{
+    basket_repo, classification_audit_repo, config_repo, flow_repo, gate_profile_repo, insider_repo, market_repo, order_repo, position_repo,
+    tier_repo, trade_repo, whale_repo,
+};
+use crate::execution::copy_profiles::CopyProfile;
+use crate::execution::fill_costs::CostRate;
 use crate::intelligence::basket::{
-    auto_assign_to_baskets, check_admission, check_basket_consensus, infer_market_category,
-    AdmissionResult,
+    auto_assign_to_baskets, check_admission, check_basket_consensus, infer_market_category, resolve_conflict,
+    AdmissionResult, ConflictMode, ConflictResolution,
 };
-use crate::intelligence::classifier::Classification;
-use crate::intelligence::{classify_wallet, score_wallet};
-use crate::intelligence::scorer::WalletScore;
-use crate::models::{CopySignal, Side, TradeResult, WhaleTradeEvent};
+use crate::intelligence::classifier::{Classification, WalletClassifier};
+use crate::intelligence::flow::{flow_rejection, FlowConfig};
+use crate::intelligence::score_wallet;
+use crate::intelligence::scorer::{resolved_profit, wilson_lower_bound, WalletScore};
+use crate::intelligence::tiers::{assign_tier, SharedTierPolicies};
+use crate::models::{CopySignal, GateProfile, Side, Sleeve, TierPolicy, TradeResult, WhaleTrade, WhaleTradeEvent};
 use crate::services::notifier::Notifier;
-
-/// Minimum notional value (in USDC) to consider a trade from an UNKNOWN wallet.
-const WHALE_NOTIONAL_THRESHOLD: i64 = 10_000;
+use crate::services::whale_seeder::SEEDER_TIERS;
+use crate::services::trade_size_stats::WhaleNotionalThreshold;
 
 /// Pipeline configuration for signal quality gates.
 #[derive(Debug, Clone)]
 pub struct PipelineConfig {
     pub tracked_whale_min_notional: Decimal,
+    /// Minimum notional (USDC) for a trade from an UNKNOWN wallet, kept at a
+    /// percentile of recent market-wide trade sizes.
+    pub whale_notional: WhaleNotionalThreshold,
     pub min_signal_win_rate: Decimal,
+    /// Minimum 95% Wilson lower bound on the whale's win rate (0 = off).
+    pub min_signal_win_rate_lb: Decimal,
     pub min_resolved_for_signal: i32,
+    /// An entry edge of at least this (0 = off), measured over at least
+    /// `min_entry_edge_samples` entries, stands in for resolved trades.
+    pub min_entry_edge: Decimal,
+    pub min_entry_edge_samples: i32,
     pub min_total_trades_for_signal: i32,
     pub signal_notional_liquidity_pct: Decimal,
     pub signal_notional_floor: Decimal,
     pub max_signal_notional: Decimal,
     pub min_signal_ev: Decimal,
+    /// Minimum whale profit factor to emit a signal (0 = off). Whales with no
+    /// losing trades yet have no profit factor and pass.
+    pub min_signal_profit_factor: Decimal,
+    /// Minimum whale skill score (1 − probability its record is luck) to emit
+    /// a signal (0 = off). Whales without a score yet pass.
+    pub min_signal_skill_score: Decimal,
+    /// Slippage assumed by the EV gate for whales without enough measured copy lag.
     pub assumed_slippage_pct: Decimal,
+    /// Filled copies needed before a whale's measured copy lag replaces
+    /// `assumed_slippage_pct` (0 = always use the assumption).
+    pub copy_lag_min_samples: i64,
+    /// Realised fee + gas rate charged by the EV gate.
+    pub cost_rate: CostRate,
+    /// Replayed trades older than this are recorded and scored but not copied.
+    pub signal_ttl_secs: u64,
     pub signal_dedup_window_secs: u64,
+    /// A buy of at least this notional with no earlier trade this large in the
+    /// market is a first-mover trade (0 = detection off).
+    pub first_mover_min_notional: Decimal,
+    /// Size multiplier on first-mover signals (1 = detect and log only).
+    pub first_mover_size_multiplier: Decimal,
+    /// Basket admission rejects whales whose max drawdown exceeds this percent (0 = off).
+    pub basket_max_drawdown_pct: Decimal,
+    /// Basket admission rejects whales scoring below this skill score (0 = off).
+    pub basket_min_skill_score: Decimal,
+    /// Basket whales with at least this trade overlap over the last
+    /// `basket_correlation_days` cast one consensus vote (0 = off).
+    pub basket_correlation_threshold: Decimal,
+    pub basket_correlation_days: i64,
+    /// Hours a basket stays quiet on a market after signalling consensus
+    /// there (0 = the basket's time window).
+    pub basket_consensus_cooldown_hours: i32,
+    /// Vote share at which a forming consensus is notified without trading (None = off).
+    pub basket_building_alert_pct: Option<Decimal>,
+    /// How a basket consensus opposite to another basket's live consensus on
+    /// the same market is traded.
+    pub basket_conflict_mode: ConflictMode,
+    /// Basket admission rejects whales with at least this many insider flags (0 = off).
+    pub basket_max_insider_flags: i64,
+    /// Copy profiles evaluated side by side; empty = one built-in profile.
+    pub profiles: Vec<CopyProfile>,
+    /// Classifies wallets that have neither an operator override nor a seeder tier.
+    pub classifier: Arc<dyn WalletClassifier>,
+    /// Gates for the market-level net whale flow signal.
+    pub flow: FlowConfig,
+    /// Whale tiers; when set, each whale's tier gates and sizes its signals
+    /// in place of the global gates, and whales below every tier are not copied.
+    pub tiers: Option<SharedTierPolicies>,
+    /// The active gate profile, set by `apply_runtime_overrides`. It is
+    /// re-applied over a whale's tier gates so an operator switch always wins.
+    pub gate_profile: Option<GateProfile>,
 }
 
 /// Process a single WhaleTradeEvent through the intelligence pipeline:
 /// 1. Filter by notional threshold
 /// 2. Upsert whale record
-/// 3. Persist trade to DB
+/// 3. Persist trade to DB (then whale exit and net flow checks)
 /// 4. Re-score and re-classify the wallet
 /// 5. Basket admission check
 /// 6. Emit CopySignal if wallet qualifies
@@ -65,7 +130,7 @@ pub async fn process_trade_event(
     let threshold = if is_tracked {
         config.tracked_whale_min_notional
     } else {
-        Decimal::from(WHALE_NOTIONAL_THRESHOLD)
+        config.whale_notional.get()
     };
 
     if event.notional < threshold {
@@ -91,8 +156,15 @@ pub async fn process_trade_event(
     // Step 2: Upsert whale
     let whale = whale_repo::upsert_whale(pool, &event.wallet).await?;
 
-    // Step 3: Persist trade
-    let trade = trade_repo::insert_trade(
+    // Resolve the canonical market key — chain listener events only carry the token id
+    let condition_id = market_repo::resolve_condition_id(pool, &event.market_id, &event.asset_id)
+        .await
+        .ok()
+        .flatten();
+    let market_key = condition_id.as_deref().unwrap_or(&event.market_id);
+
+    // Step 3: Persist trade — once, however many sources report it
+    let Some(trade) = trade_repo::insert_trade(
         pool,
         whale.id,
         &event.market_id,
@@ -102,24 +174,78 @@ pub async fn process_trade_event(
         event.price,
         event.notional,
         event.timestamp,
+        condition_id.as_deref(),
+        event.tx_hash.as_deref(),
+        event.fill_index,
     )
-    .await?;
+    .await?
+    else {
+        counter!("trade_events_duplicate").increment(1);
+        tracing::debug!(
+            wallet = %event.wallet,
+            tx_hash = event.tx_hash.as_deref().unwrap_or_default(),
+            "Trade already recorded from another source, skipping"
+        );
+        return Ok(());
+    };
+
+    // First mover: the first large buy we have seen in this market, before
+    // the news is priced in — boost the copy size if configured
+    let is_first_mover = event.side == Side::Buy
+        && !config.first_mover_min_notional.is_zero()
+        && event.notional >= config.first_mover_min_notional
+        && !trade_repo::has_prior_large_trade(
+            pool,
+            market_key,
+            trade.id,
+            event.timestamp,
+            config.first_mover_min_notional,
+        )
+        .await
+        .unwrap_or(true);
+    let first_mover_multiplier = if is_first_mover {
+        counter!("first_mover_trades").increment(1);
+        tracing::info!(
+            wallet = %event.wallet,
+            market = %market_key,
+            notional = %event.notional,
+            multiplier = %config.first_mover_size_multiplier,
+            "First-mover trade: no earlier large trade in this market"
+        );
+        config.first_mover_size_multiplier
+    } else {
+        Decimal::ONE
+    };
 
     // Ensure market_outcome record exists for this market
-    let _ = market_repo::upsert_market_outcome(pool, &event.market_id, Some(&event.asset_id)).await;
+    let _ = market_repo::upsert_market_outcome(
+        pool,
+        &event.market_id,
+        Some(&event.asset_id),
+        condition_id.as_deref(),
+    )
+    .await;
 
     // Update last_trade_at
     whale_repo::touch_whale_last_trade(pool, whale.id, event.timestamp).await?;
 
-    // Whale exit detection: if whale is SELLing a token we hold, emit exit signal immediately
+    // Whale exit detection: if the whale we copied into a token is SELLing it,
+    // emit an exit signal immediately — one per sleeve or profile holding the token.
+    // Positions without a linked whale (manual, adopted) are never followed out.
     if event.side == Side::Sell {
-        if let Ok(Some(pos)) = position_repo::get_position_by_token_id(pool, &event.asset_id).await {
-            if pos.status.as_deref() == Some("open") {
+        let positions = position_repo::get_positions_by_token_id(pool, &event.asset_id)
+            .await
+            .unwrap_or_default();
+        for pos in positions {
+            let copied_whale = pos.whale_id == Some(whale.id);
+            if pos.status.as_deref() == Some("open") && copied_whale {
                 if let Some(tx) = signal_tx {
+                    let sleeve = Sleeve::parse(&pos.sleeve);
                     let exit_signal = CopySignal {
                         whale_trade_id: trade.id,
                         wallet: event.wallet.clone(),
                         market_id: event.market_id.clone(),
+                        condition_id: condition_id.clone(),
                         asset_id: event.asset_id.clone(),
                         side: event.side,
                         price: event.price,
@@ -127,11 +253,21 @@ pub async fn process_trade_event(
                         whale_kelly: Decimal::ZERO,
                         whale_notional: event.notional,
                         is_whale_exit: true,
+                        sleeve: sleeve.unwrap_or(Sleeve::SingleWhale),
+                        // Anything but a strategy sleeve is a copy profile's capital
+                        profile: sleeve.is_none().then(|| pos.sleeve.clone()),
+                        manual_size: None,
+                        size_multiplier: Decimal::ONE,
+                        source_signal_id: None,
+                        consensus_pct: None,
+                        flow_signal_id: None,
+                        tier: None,
                     };
                     let _ = tx.send(exit_signal).await;
                     tracing::info!(
                         wallet = %event.wallet,
                         token_id = %event.asset_id,
+                        sleeve = %pos.sleeve,
                         "Whale exit detected — exit signal emitted"
                     );
                 }
@@ -139,20 +275,45 @@ pub async fn process_trade_event(
         }
     }
 
+    // A replayed trade too old to act on still counts towards the whale's
+    // record and admission, but no longer triggers flow, copy or consensus
+    // signals (whale exits above still apply)
+    let stale_replay = is_stale_replay(event, config.signal_ttl_secs, Utc::now());
+
+    // Step 3b: Market-level net flow from all tracked whales, independent of
+    // whether this whale qualifies on its own
+    if !stale_replay && event.side == Side::Buy && config.flow.enabled() {
+        check_net_flow(event, pool, signal_tx, notifier, &config.flow, market_key, condition_id.as_deref(), trade.id)
+            .await;
+    }
+
     // Step 4: Fetch trade history and re-score
     let all_trades = trade_repo::get_trades_by_whale(pool, whale.id).await?;
 
     // Seeder-tier classifications indicate leaderboard-vetted whales.
     // Don't override with pipeline re-classification (avoids false bot/MM).
-    const SEEDER_TIERS: &[&str] = &["top_tier", "high_performer", "profitable"];
     let is_seeder_vetted = whale
         .classification
         .as_deref()
         .map(|c| SEEDER_TIERS.contains(&c))
         .unwrap_or(false);
 
+    // An operator override wins over both seeder tiers and automatic
+    // classification until it expires
+    let classification_override = whale
+        .active_classification_override(Utc::now())
+        .and_then(Classification::parse);
+
     // Classify wallet — preserve seeder classifications
-    let classification = if is_seeder_vetted {
+    let classification = if let Some(c) = classification_override {
+        tracing::debug!(
+            wallet = %event.wallet,
+            classification = %c,
+            until = ?whale.classification_override_until,
+            "Classification overridden by operator"
+        );
+        c
+    } else if is_seeder_vetted {
         tracing::debug!(
             wallet = %event.wallet,
             existing = ?whale.classification,
@@ -160,46 +321,17 @@ pub async fn process_trade_event(
         );
         Classification::Informed
     } else {
-        let c = classify_wallet(&all_trades);
+        let explanation = config.classifier.explain(&all_trades);
+        let c = explanation.classification;
         whale_repo::update_whale_classification(pool, whale.id, c.as_str()).await?;
+        if let Err(e) = classification_audit_repo::record_classification(pool, whale.id, &explanation).await {
+            tracing::warn!(error = %e, wallet = %event.wallet, "Failed to record classification audit");
+        }
         c
     };
 
     // Score wallet — use real market outcomes when available
-    let trade_results: Vec<TradeResult> = {
-        let mut results = Vec::with_capacity(all_trades.len());
-        for t in &all_trades {
-            let outcome = market_repo::get_market_outcome(pool, &t.market_id).await.ok().flatten();
-            let profit = match outcome.as_ref().map(|o| o.outcome.as_str()) {
-                Some("resolved_yes") => {
-                    if t.side == "BUY" {
-                        t.notional * (Decimal::ONE - t.price) / t.price
-                    } else {
-                        -t.notional
-                    }
-                }
-                Some("resolved_no") => {
-                    if t.side == "BUY" {
-                        -t.notional
-                    } else {
-                        t.notional * t.price / (Decimal::ONE - t.price)
-                    }
-                }
-                _ => Decimal::ZERO,
-            };
-            results.push(TradeResult {
-                profit,
-                traded_at: t.traded_at,
-            });
-        }
-        results
-    };
-
-    // Only include resolved trades for scoring (filter out zero-profit unresolved)
-    let resolved_results: Vec<TradeResult> = trade_results
-        .into_iter()
-        .filter(|r| r.profit != Decimal::ZERO)
-        .collect();
+    let resolved_results = resolved_trade_results(pool, &all_trades).await;
     let resolved_count = resolved_results.len() as i32;
 
     // Build score: prefer resolved trade data, fall back to existing DB scores (from seeder)
@@ -211,6 +343,13 @@ pub async fn process_trade_event(
             pool,
             whale.id,
             s.sharpe_ratio,
+            Some(s.sortino_ratio),
+            Some(s.calmar_ratio),
+            Some(s.max_drawdown),
+            Some(s.max_drawdown_pct),
+            s.profit_factor,
+            s.payoff_ratio,
+            s.skill_score,
             s.win_rate,
             s.kelly_fraction,
             s.expected_value,
@@ -237,7 +376,15 @@ pub async fn process_trade_event(
 
         Some(WalletScore {
             sharpe_ratio: whale.sharpe_ratio.unwrap_or(Decimal::ZERO),
+            sortino_ratio: whale.sortino_ratio.unwrap_or(Decimal::ZERO),
+            calmar_ratio: whale.calmar_ratio.unwrap_or(Decimal::ZERO),
+            max_drawdown: whale.max_drawdown.unwrap_or(Decimal::ZERO),
+            max_drawdown_pct: whale.max_drawdown_pct.unwrap_or(Decimal::ZERO),
+            profit_factor: whale.profit_factor,
+            payoff_ratio: whale.payoff_ratio,
+            skill_score: whale.skill_score,
             win_rate,
+            win_rate_lower_bound: wilson_lower_bound(win_rate, total_trades as i64),
             kelly_fraction: kelly,
             expected_value: whale.expected_value.unwrap_or(Decimal::ZERO),
             total_trades,
@@ -266,7 +413,14 @@ pub async fn process_trade_event(
         wallet = %event.wallet,
         classification = %classification,
         sharpe = %score.sharpe_ratio,
+        sortino = %score.sortino_ratio,
+        calmar = %score.calmar_ratio,
+        max_drawdown_pct = %score.max_drawdown_pct.round_dp(1),
+        profit_factor = ?score.profit_factor.map(|v| v.round_dp(2)),
+        payoff_ratio = ?score.payoff_ratio.map(|v| v.round_dp(2)),
+        skill_score = ?score.skill_score.map(|v| v.round_dp(3)),
         win_rate = %score.win_rate,
+        win_rate_lb = %score.win_rate_lower_bound.round_dp(3),
         kelly = %score.kelly_fraction,
         ev = %score.expected_value,
         trades = score.total_trades,
@@ -301,12 +455,20 @@ pub async fn process_trade_event(
         let diff = Utc::now().signed_duration_since(earliest);
         (diff.num_days() / 30).max(1)
     };
-    let avg_monthly_trades = if months_active > 0 {
+    // An operator override vouches for the wallet's trading frequency
+    let avg_monthly_trades = if classification_override.is_some() {
+        Decimal::ZERO
+    } else if months_active > 0 {
         Decimal::from(score.total_trades) / Decimal::from(months_active)
     } else {
         Decimal::from(score.total_trades)
     };
 
+    let insider_flags = if config.basket_max_insider_flags > 0 {
+        insider_repo::count_whale_flags(pool, whale.id).await.unwrap_or(0)
+    } else {
+        0
+    };
     let admission = if is_seeder_vetted {
         tracing::info!(wallet = %event.wallet, "Seeder-vetted whale — bypassing admission");
         AdmissionResult::Accepted
@@ -317,6 +479,12 @@ pub async fn process_trade_event(
             months_active,
             score.total_trades,
             avg_monthly_trades,
+            score.max_drawdown_pct,
+            config.basket_max_drawdown_pct,
+            score.skill_score,
+            config.basket_min_skill_score,
+            insider_flags,
+            config.basket_max_insider_flags,
         )
     };
 
@@ -332,7 +500,7 @@ pub async fn process_trade_event(
     }
 
     // Fetch market question once for notifications and category inference
-    let market_question = market_repo::get_market_question(pool, &event.market_id)
+    let market_question = market_repo::get_market_question(pool, market_key)
         .await
         .ok()
         .flatten();
@@ -353,145 +521,380 @@ pub async fn process_trade_event(
         }
     }
 
+    if stale_replay {
+        counter!("stale_replays_not_copied").increment(1);
+        tracing::info!(
+            wallet = %event.wallet,
+            market = %event.market_id,
+            traded_at = %event.timestamp,
+            "Replayed trade older than the signal TTL — recorded without copying"
+        );
+        let elapsed = start.elapsed().as_secs_f64();
+        histogram!("pipeline_latency_seconds").record(elapsed);
+        return Ok(());
+    }
+
     // Step 6: Emit CopySignal if wallet passes classification, validated scores,
-    // total trades, notional range, and win rate gates.
+    // total trades, notional range, and win rate gates — once per copy profile,
+    // each with its own gates and sleeve.
     let is_valid_classification = classification != Classification::Bot
         && classification != Classification::MarketMaker;
 
-    // Seeder-vetted whales have leaderboard-validated scores — skip resolved gate
-    let has_validated_scores = is_seeder_vetted || resolved_count >= config.min_resolved_for_signal;
+    // Seeder-vetted whales have leaderboard-validated scores — skip resolved gate.
+    // So do whales whose entries reliably precede favourable price moves.
+    let has_entry_edge = !config.min_entry_edge.is_zero()
+        && whale.entry_edge_samples.unwrap_or(0) >= config.min_entry_edge_samples
+        && whale.entry_edge.is_some_and(|e| e >= config.min_entry_edge);
+    let has_validated_scores =
+        is_seeder_vetted || has_entry_edge || resolved_count >= config.min_resolved_for_signal;
 
     // Effective total trades: max of observed trades and seeded/leaderboard total
     let effective_total_trades = (all_trades.len() as i32).max(score.total_trades);
 
-    let has_enough_total_trades = effective_total_trades >= config.min_total_trades_for_signal;
-
-    // EV_copy = EV * (1 - assumed_slippage) — slippage-adjusted expected value per trade
-    let ev_copy = score.expected_value * (Decimal::ONE - config.assumed_slippage_pct);
-    let has_sufficient_ev = ev_copy >= config.min_signal_ev;
-
-    // Dynamic notional gate: threshold = max(liquidity × pct, floor)
-    let market_liquidity = market_repo::get_market_liquidity(pool, &event.market_id)
+    let market_liquidity = market_repo::get_market_liquidity(pool, market_key)
         .await
         .ok()
         .flatten();
-    let dynamic_min_notional = market_liquidity
-        .map(|liq| (liq * config.signal_notional_liquidity_pct).max(config.signal_notional_floor))
-        .unwrap_or(config.signal_notional_floor);
-    let notional_above_min = event.notional >= dynamic_min_notional;
 
-    if !is_valid_classification {
-        tracing::info!(
-            wallet = %event.wallet,
-            classification = %classification,
-            "Signal blocked: classified as {}",
-            classification.as_str()
-        );
-    } else if !has_validated_scores {
-        tracing::info!(
-            wallet = %event.wallet,
-            resolved = resolved_count,
-            required = config.min_resolved_for_signal,
-            "Signal blocked: only {} resolved trades (need {})",
-            resolved_count,
-            config.min_resolved_for_signal
-        );
-    } else if !has_enough_total_trades {
-        tracing::info!(
-            wallet = %event.wallet,
-            total_trades = effective_total_trades,
-            required = config.min_total_trades_for_signal,
-            "Signal blocked: only {} total trades (need {})",
-            effective_total_trades,
-            config.min_total_trades_for_signal
-        );
-    } else if !notional_above_min {
-        tracing::info!(
-            wallet = %event.wallet,
-            notional = %event.notional,
-            dynamic_min = %dynamic_min_notional,
-            liquidity = ?market_liquidity,
-            "Signal blocked: notional ${} below dynamic minimum ${}",
-            event.notional,
-            dynamic_min_notional
-        );
-    } else if event.notional > config.max_signal_notional {
-        tracing::info!(
-            wallet = %event.wallet,
-            notional = %event.notional,
-            max = %config.max_signal_notional,
-            "Signal blocked: notional ${} above ${} maximum",
-            event.notional,
-            config.max_signal_notional
-        );
-    } else if !has_sufficient_ev {
-        tracing::info!(
-            wallet = %event.wallet,
-            ev = %score.expected_value,
-            ev_copy = %ev_copy,
-            min = %config.min_signal_ev,
-            slippage_pct = %config.assumed_slippage_pct,
-            "Signal blocked: EV_copy ${} below ${} minimum (EV=${}, slippage={}%)",
-            ev_copy,
-            config.min_signal_ev,
-            score.expected_value,
-            config.assumed_slippage_pct * Decimal::ONE_HUNDRED
-        );
-    } else if score.win_rate >= config.min_signal_win_rate && whale.is_active.unwrap_or(true) {
-        // Dedup check: skip if same (wallet, asset_id, side) emitted within window
-        let dedup_key = format!("{}:{}:{}", event.wallet, event.asset_id, event.side);
-        let is_dup = {
-            let mut dedup_map = dedup.lock().await;
-            dedup_map.retain(|_, t| t.elapsed() < Duration::from_secs(config.signal_dedup_window_secs));
-            use std::collections::hash_map::Entry;
-            match dedup_map.entry(dedup_key.clone()) {
-                Entry::Occupied(_) => true,
-                Entry::Vacant(e) => {
-                    e.insert(Instant::now());
-                    false
+    // No configured profiles = one built-in profile on the pipeline gates
+    let profiles: Vec<Option<&CopyProfile>> = if config.profiles.is_empty() {
+        vec![None]
+    } else {
+        config.profiles.iter().map(Some).collect()
+    };
+
+    // Why each profile blocked the signal, for the notification digest
+    let mut blocked: Vec<String> = Vec::new();
+    let mut signal_emitted = false;
+
+    // Realised fee + gas as a fraction of filled notional, charged on the
+    // whale's average trade size since EV is in USDC per trade
+    let cost_rate = config.cost_rate.get(pool).await;
+    let avg_notional = if all_trades.is_empty() {
+        event.notional
+    } else {
+        all_trades.iter().map(|t| t.notional).sum::<Decimal>() / Decimal::from(all_trades.len() as i64)
+    };
+
+    // Copy lag measured on this whale's fills, once there are enough of them.
+    // A favourable (negative) lag is not credited.
+    let measured_lag = if config.copy_lag_min_samples > 0 {
+        match order_repo::get_whale_copy_lag(pool, whale.id).await {
+            Ok((Some(lag), samples)) if samples >= config.copy_lag_min_samples => {
+                Some(lag.max(Decimal::ZERO))
+            }
+            _ => None,
+        }
+    } else {
+        None
+    };
+
+    // EV_copy = EV * (1 - slippage) - cost_rate * notional — all-in expected value per trade
+    let slippage_pct = measured_lag.unwrap_or(config.assumed_slippage_pct);
+    let ev_copy = score.expected_value * (Decimal::ONE - slippage_pct) - cost_rate * avg_notional;
+
+    // Whale tier: the tier's thresholds and gates replace the global ones
+    let tier_policy = match &config.tiers {
+        Some(tiers) => {
+            let policies = tiers.read().await;
+            let policy = assign_tier(&policies, score.win_rate_lower_bound, score.skill_score, effective_total_trades)
+                .cloned();
+            let tier = policy.as_ref().and_then(|p| p.whale_tier());
+            if whale.tier.as_deref() != tier.map(|t| t.as_str()) {
+                match tier_repo::set_whale_tier(pool, whale.id, tier).await {
+                    Ok(()) => tracing::info!(
+                        wallet = %event.wallet,
+                        from = ?whale.tier,
+                        to = ?tier,
+                        "Whale tier changed"
+                    ),
+                    Err(e) => tracing::warn!(error = %e, wallet = %event.wallet, "Failed to store whale tier"),
                 }
             }
+            policy
+        }
+        None => None,
+    };
+    let whale_tier = tier_policy.as_ref().and_then(|p| p.whale_tier());
+
+    for profile in profiles {
+        let base = match &tier_policy {
+            Some(t) => tier_config(config, t),
+            None => config.clone(),
         };
+        let gates = match profile {
+            Some(p) => profile_config(&base, p),
+            None => base,
+        };
+        let profile_name = profile.map(|p| p.name.as_str()).unwrap_or("default");
+
+        let has_enough_total_trades = effective_total_trades >= gates.min_total_trades_for_signal;
 
-        if is_dup {
-            tracing::debug!(key = %dedup_key, "Signal deduped — skipping");
-        } else if let Some(tx) = signal_tx {
-            let signal = CopySignal {
-                whale_trade_id: trade.id,
-                wallet: event.wallet.clone(),
-                market_id: event.market_id.clone(),
-                asset_id: event.asset_id.clone(),
-                side: event.side,
-                price: event.price,
-                whale_win_rate: score.win_rate,
-                whale_kelly: score.kelly_fraction,
-                whale_notional: event.notional,
-                is_whale_exit: false,
+        let has_sufficient_ev = ev_copy >= gates.min_signal_ev;
+
+        let has_sufficient_profit_factor = gates.min_signal_profit_factor.is_zero()
+            || score.profit_factor.is_none_or(|pf| pf >= gates.min_signal_profit_factor);
+        let has_sufficient_skill = gates.min_signal_skill_score.is_zero()
+            || score.skill_score.is_none_or(|s| s >= gates.min_signal_skill_score);
+
+        // Dynamic notional gate: threshold = max(liquidity × pct, floor)
+        let dynamic_min_notional = market_liquidity
+            .map(|liq| (liq * gates.signal_notional_liquidity_pct).max(gates.signal_notional_floor))
+            .unwrap_or(gates.signal_notional_floor);
+        let notional_above_min = event.notional >= dynamic_min_notional;
+
+        let mut reason: Option<String> = None;
+
+        if !is_valid_classification {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                classification = %classification,
+                "Signal blocked: classified as {}",
+                classification.as_str()
+            );
+            reason = Some(format!("分类为 {}", classification.as_str()));
+        } else if config.tiers.is_some() && whale_tier.is_none() {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                win_rate_lb = %score.win_rate_lower_bound.round_dp(3),
+                total_trades = effective_total_trades,
+                "Signal blocked: whale below every tier"
+            );
+            reason = Some("未达到任何巨鲸等级".into());
+        } else if !has_validated_scores {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                resolved = resolved_count,
+                required = gates.min_resolved_for_signal,
+                "Signal blocked: only {} resolved trades (need {})",
+                resolved_count,
+                gates.min_resolved_for_signal
+            );
+            reason = Some(format!(
+                "已结算交易 {} 笔 (需 {})",
+                resolved_count, gates.min_resolved_for_signal
+            ));
+        } else if !has_enough_total_trades {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                total_trades = effective_total_trades,
+                required = gates.min_total_trades_for_signal,
+                "Signal blocked: only {} total trades (need {})",
+                effective_total_trades,
+                gates.min_total_trades_for_signal
+            );
+            reason = Some(format!(
+                "总交易 {} 笔 (需 {})",
+                effective_total_trades, gates.min_total_trades_for_signal
+            ));
+        } else if !notional_above_min {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                notional = %event.notional,
+                dynamic_min = %dynamic_min_notional,
+                liquidity = ?market_liquidity,
+                "Signal blocked: notional ${} below dynamic minimum ${}",
+                event.notional,
+                dynamic_min_notional
+            );
+            reason = Some(format!("金额低于下限 ${}", dynamic_min_notional.round_dp(2)));
+        } else if event.notional > gates.max_signal_notional {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                notional = %event.notional,
+                max = %gates.max_signal_notional,
+                "Signal blocked: notional ${} above ${} maximum",
+                event.notional,
+                gates.max_signal_notional
+            );
+            reason = Some(format!("金额高于上限 ${}", gates.max_signal_notional));
+        } else if !has_sufficient_ev {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                ev = %score.expected_value,
+                ev_copy = %ev_copy,
+                min = %gates.min_signal_ev,
+                slippage_pct = %slippage_pct,
+                measured_lag = measured_lag.is_some(),
+                cost_rate = %cost_rate,
+                "Signal blocked: EV_copy ${} below ${} minimum (EV=${}, slippage={}%, costs={}%)",
+                ev_copy,
+                gates.min_signal_ev,
+                score.expected_value,
+                (slippage_pct * Decimal::ONE_HUNDRED).round_dp(3),
+                (cost_rate * Decimal::ONE_HUNDRED).round_dp(3)
+            );
+            reason = Some(format!(
+                "调整后EV ${} 低于 ${}",
+                ev_copy.round_dp(2),
+                gates.min_signal_ev
+            ));
+        } else if !has_sufficient_profit_factor {
+            let profit_factor = score.profit_factor.unwrap_or_default().round_dp(2);
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                profit_factor = %profit_factor,
+                min = %gates.min_signal_profit_factor,
+                "Signal blocked: profit factor {} below {} minimum",
+                profit_factor,
+                gates.min_signal_profit_factor
+            );
+            reason = Some(format!(
+                "盈亏比 {} 低于 {}",
+                profit_factor, gates.min_signal_profit_factor
+            ));
+        } else if !has_sufficient_skill {
+            let skill = score.skill_score.unwrap_or_default().round_dp(3);
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                skill_score = %skill,
+                min = %gates.min_signal_skill_score,
+                "Signal blocked: skill score {} below {} minimum",
+                skill,
+                gates.min_signal_skill_score
+            );
+            reason = Some(format!(
+                "技能分 {} 低于 {}",
+                skill, gates.min_signal_skill_score
+            ));
+        } else if whale.copy_paused_at.is_some() {
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                reason = whale.copy_pause_reason.as_deref().unwrap_or(""),
+                "Signal blocked: copying paused for this whale"
+            );
+            reason = Some("该巨鲸跟单已暂停".into());
+        } else if score.win_rate < gates.min_signal_win_rate {
+            let win_rate = score.win_rate.round_dp(3);
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                win_rate = %win_rate,
+                min = %gates.min_signal_win_rate,
+                "Signal blocked: win rate {} below {} minimum",
+                win_rate,
+                gates.min_signal_win_rate
+            );
+            reason = Some(format!(
+                "胜率 {} 低于 {}",
+                win_rate, gates.min_signal_win_rate
+            ));
+        } else if score.win_rate_lower_bound < gates.min_signal_win_rate_lb {
+            let lower_bound = score.win_rate_lower_bound.round_dp(3);
+            tracing::info!(
+                wallet = %event.wallet,
+                profile = profile_name,
+                win_rate = %score.win_rate,
+                win_rate_lb = %lower_bound,
+                trades = score.total_trades,
+                min = %gates.min_signal_win_rate_lb,
+                "Signal blocked: win rate lower bound {} below {} minimum",
+                lower_bound,
+                gates.min_signal_win_rate_lb
+            );
+            reason = Some(format!(
+                "胜率置信下限 {} 低于 {}",
+                lower_bound, gates.min_signal_win_rate_lb
+            ));
+        } else if whale.is_active.unwrap_or(true) {
+            // Dedup check: skip if same (wallet, asset_id, side, profile) emitted within window
+            let dedup_key = format!("{}:{}:{}:{}", event.wallet, event.asset_id, event.side, profile_name);
+            let is_dup = {
+                let mut dedup_map = dedup.lock().await;
+                dedup_map.retain(|_, t| t.elapsed() < Duration::from_secs(gates.signal_dedup_window_secs));
+                use std::collections::hash_map::Entry;
+                match dedup_map.entry(dedup_key.clone()) {
+                    Entry::Occupied(_) => true,
+                    Entry::Vacant(e) => {
+                        e.insert(Instant::now());
+                        false
+                    }
+                }
             };
 
-            if let Err(e) = tx.send(signal).await {
-                tracing::error!(error = %e, "Failed to send CopySignal to execution layer");
-            } else {
-                counter!("copy_signals_emitted").increment(1);
-                tracing::info!(
-                    wallet = %event.wallet,
-                    market = %event.market_id,
-                    "CopySignal emitted to execution layer"
-                );
-
-                // Notify copy signal via Telegram
-                if let Some(n) = notifier {
-                    let msg = crate::services::notifier::format_copy_signal(
-                        event,
-                        score.win_rate,
-                        score.kelly_fraction,
-                        ev_copy,
-                        market_question.as_deref(),
+            if is_dup {
+                tracing::debug!(key = %dedup_key, "Signal deduped — skipping");
+            } else if let Some(tx) = signal_tx {
+                let signal = CopySignal {
+                    whale_trade_id: trade.id,
+                    wallet: event.wallet.clone(),
+                    market_id: event.market_id.clone(),
+                    condition_id: condition_id.clone(),
+                    asset_id: event.asset_id.clone(),
+                    side: event.side,
+                    price: event.price,
+                    whale_win_rate: score.win_rate,
+                    whale_kelly: score.kelly_fraction,
+                    whale_notional: event.notional,
+                    is_whale_exit: false,
+                    sleeve: Sleeve::SingleWhale,
+                    profile: profile.map(|p| p.name.clone()),
+                    manual_size: None,
+                    size_multiplier: profile.map(|p| p.size_multiplier).unwrap_or(Decimal::ONE)
+                        * first_mover_multiplier,
+                    source_signal_id: None,
+                    consensus_pct: None,
+                    flow_signal_id: None,
+                    tier: whale_tier,
+                };
+
+                if let Err(e) = tx.send(signal).await {
+                    tracing::error!(error = %e, "Failed to send CopySignal to execution layer");
+                } else {
+                    counter!("copy_signals_emitted").increment(1);
+                    signal_emitted = true;
+                    tracing::info!(
+                        wallet = %event.wallet,
+                        market = %event.market_id,
+                        profile = profile_name,
+                        tier = ?whale_tier,
+                        first_mover = is_first_mover,
+                        "CopySignal emitted to execution layer"
                     );
-                    n.send(&msg).await;
                 }
             }
         }
+
+        if let Some(reason) = reason {
+            blocked.push(match profile {
+                Some(p) => format!("[{}] {}", p.name, reason),
+                None => reason,
+            });
+        }
+    }
+
+    // Notify copy signal via Telegram (once, however many profiles took it)
+    if let (true, Some(n)) = (signal_emitted, notifier) {
+        let msg = crate::services::notifier::format_copy_signal(
+            event,
+            score.win_rate,
+            score.kelly_fraction,
+            ev_copy,
+            market_question.as_deref(),
+        );
+        n.notify(crate::services::notifier::Severity::Info, &msg).await;
+    }
+
+    // Blocked signals are too frequent to send one by one — digest only
+    if let (false, Some(n)) = (blocked.is_empty(), notifier) {
+        if n.digests(crate::services::notifier::Severity::Info) {
+            let msg = crate::services::notifier::format_signal_blocked(
+                event,
+                &blocked.join("; "),
+                market_question.as_deref(),
+            );
+            n.notify(crate::services::notifier::Severity::Info, &msg).await;
+        }
     }
 
     // Step 7: Basket consensus check (only if wallet passed admission)
@@ -507,9 +910,102 @@ pub async fn process_trade_event(
 
     if let Ok(baskets) = basket_repo::get_baskets_for_whale(pool, whale.id).await {
         for basket in &baskets {
-            match check_basket_consensus(pool, basket, &event.market_id, event.price).await {
+            match check_basket_consensus(
+                pool,
+                basket,
+                market_key,
+                &event.asset_id,
+                event.price,
+                config.basket_correlation_threshold,
+                config.basket_correlation_days,
+            )
+            .await
+            {
                 Ok(check) => {
                     if check.reached {
+                        let cooldown_hours = basket.cooldown_hours(config.basket_consensus_cooldown_hours);
+
+                        // Another basket may hold the opposite consensus on this
+                        // market: resolve that first, so only traded consensus is
+                        // recorded and announced
+                        let opposing = match basket_repo::get_opposing_consensus(
+                            pool,
+                            basket.id,
+                            market_key,
+                            &check.direction,
+                        )
+                        .await
+                        {
+                            Ok(signals) => signals.iter().map(|s| s.consensus_pct).collect::<Vec<_>>(),
+                            Err(e) => {
+                                tracing::error!(error = %e, "Failed to load opposing consensus");
+                                Vec::new()
+                            }
+                        };
+                        let size_multiplier = match resolve_conflict(
+                            config.basket_conflict_mode,
+                            check.consensus_pct,
+                            &opposing,
+                        ) {
+                            ConflictResolution::Emit(multiplier) => multiplier,
+                            ConflictResolution::Skip(reason) => {
+                                tracing::info!(
+                                    basket = %basket.name,
+                                    market = %event.market_id,
+                                    direction = %check.direction,
+                                    reason = %reason,
+                                    "Basket consensus conflicts with another basket — not trading"
+                                );
+                                // Block mode: remembered so the opposite side is blocked too
+                                if config.basket_conflict_mode == ConflictMode::Block {
+                                    if let Err(e) = basket_repo::record_blocked_consensus(
+                                        pool,
+                                        basket.id,
+                                        market_key,
+                                        &check.direction,
+                                        check.consensus_pct,
+                                        check.participating,
+                                        check.total,
+                                        cooldown_hours,
+                                    )
+                                    .await
+                                    {
+                                        tracing::error!(error = %e, "Failed to record blocked consensus");
+                                    }
+                                }
+                                continue;
+                            }
+                        };
+
+                        // Record consensus signal — at most once per basket/market per cooldown
+                        let consensus = match basket_repo::record_consensus_signal(
+                            pool,
+                            basket.id,
+                            market_key,
+                            &check.direction,
+                            check.consensus_pct,
+                            check.participating,
+                            check.total,
+                            cooldown_hours,
+                        )
+                        .await
+                        {
+                            Ok(Some(signal)) => signal,
+                            Ok(None) => {
+                                tracing::debug!(
+                                    basket = %basket.name,
+                                    market = %event.market_id,
+                                    direction = %check.direction,
+                                    "Basket consensus cooling down on this market — skipping"
+                                );
+                                continue;
+                            }
+                            Err(e) => {
+                                tracing::error!(error = %e, "Failed to record consensus signal");
+                                continue;
+                            }
+                        };
+
                         tracing::info!(
                             basket = %basket.name,
                             market = %event.market_id,
@@ -530,27 +1026,12 @@ pub async fn process_trade_event(
                                 check.consensus_pct,
                                 check.participating,
                                 check.total,
-                                &event.market_id,
+                                market_key,
                                 market_question.as_deref(),
                                 event.price,
                                 event.notional,
                             );
-                            n.send(&msg).await;
-                        }
-
-                        // Record consensus signal
-                        if let Err(e) = basket_repo::record_consensus_signal(
-                            pool,
-                            basket.id,
-                            &event.market_id,
-                            &check.direction,
-                            check.consensus_pct,
-                            check.participating,
-                            check.total,
-                        )
-                        .await
-                        {
-                            tracing::error!(error = %e, "Failed to record consensus signal");
+                            n.notify(crate::services::notifier::Severity::Info, &msg).await;
                         }
 
                         // Emit enhanced CopySignal from basket
@@ -562,13 +1043,22 @@ pub async fn process_trade_event(
                                 whale_trade_id: trade.id,
                                 wallet: format!("basket:{}", basket.name),
                                 market_id: event.market_id.clone(),
+                                condition_id: condition_id.clone(),
                                 asset_id: event.asset_id.clone(),
                                 side,
                                 price: event.price,
-                                whale_win_rate: score.win_rate,
+                                whale_win_rate: check.voter_win_rate,
                                 whale_kelly: score.kelly_fraction,
                                 whale_notional: event.notional,
                                 is_whale_exit: false,
+                                sleeve: Sleeve::Basket,
+                                profile: None,
+                                manual_size: None,
+                                size_multiplier,
+                                source_signal_id: Some(consensus.id),
+                                consensus_pct: Some(check.consensus_pct),
+                                flow_signal_id: None,
+                                tier: None,
                             };
 
                             if let Err(e) = tx.send(basket_signal).await {
@@ -582,6 +1072,48 @@ pub async fn process_trade_event(
                             reason = %check.reason,
                             "Basket consensus not reached"
                         );
+
+                        // Early warning that consensus is forming — notify only, never trade
+                        let building = config
+                            .basket_building_alert_pct
+                            .is_some_and(|pct| !check.consensus_pct.is_zero() && check.consensus_pct >= pct);
+                        if let Some(n) = notifier.filter(|_| building) {
+                            let cooldown_hours = basket.cooldown_hours(config.basket_consensus_cooldown_hours);
+                            match basket_repo::record_building_alert(
+                                pool,
+                                basket.id,
+                                market_key,
+                                &check.direction,
+                                check.consensus_pct,
+                                cooldown_hours,
+                            )
+                            .await
+                            {
+                                Ok(true) => {
+                                    tracing::info!(
+                                        basket = %basket.name,
+                                        market = %event.market_id,
+                                        direction = %check.direction,
+                                        pct = %check.consensus_pct,
+                                        "Basket consensus building"
+                                    );
+                                    let msg = crate::services::notifier::format_consensus_building_alert(
+                                        &basket.name,
+                                        &check.direction,
+                                        check.consensus_pct,
+                                        basket.consensus_threshold,
+                                        check.participating,
+                                        check.total,
+                                        market_key,
+                                        market_question.as_deref(),
+                                        event.price,
+                                    );
+                                    n.notify(crate::services::notifier::Severity::Info, &msg).await;
+                                }
+                                Ok(false) => {}
+                                Err(e) => tracing::error!(error = %e, "Failed to record consensus building alert"),
+                            }
+                        }
                     }
                 }
                 Err(e) => {
@@ -601,7 +1133,157 @@ pub async fn process_trade_event(
     Ok(())
 }
 
-/// Apply runtime config overrides from the database on top of the base config.
+/// Emit a flow signal when net buying by tracked whales into the traded token
+/// over the window passes the flow gates. At most one per token per window.
+#[allow(clippy::too_many_arguments)]
+async fn check_net_flow(
+    event: &WhaleTradeEvent,
+    pool: &PgPool,
+    signal_tx: Option<&mpsc::Sender<CopySignal>>,
+    notifier: Option<&Notifier>,
+    config: &FlowConfig,
+    market_key: &str,
+    condition_id: Option<&str>,
+    trade_id: uuid::Uuid,
+) {
+    let flow = match flow_repo::get_token_flow(pool, &event.asset_id, config.window_minutes).await {
+        Ok(f) => f,
+        Err(e) => {
+            tracing::error!(error = %e, token_id = %event.asset_id, "Failed to aggregate whale flow");
+            return;
+        }
+    };
+    if let Some(reason) = flow_rejection(&flow, config) {
+        tracing::debug!(token_id = %event.asset_id, reason = %reason, "Net whale flow below gates");
+        return;
+    }
+
+    let signal = match flow_repo::record_flow_signal(pool, market_key, &event.asset_id, &flow, config.window_minutes).await {
+        Ok(Some(s)) => s,
+        Ok(None) => {
+            tracing::debug!(token_id = %event.asset_id, "Net flow already signalled within window — skipping");
+            return;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Failed to record flow signal");
+            return;
+        }
+    };
+
+    tracing::info!(
+        market = %market_key,
+        token_id = %event.asset_id,
+        net = %signal.net_notional,
+        whales = signal.whales,
+        "Net whale flow signal"
+    );
+    counter!("flow_signals_total").increment(1);
+
+    if let Some(n) = notifier {
+        let market_question = market_repo::get_market_question(pool, market_key).await.ok().flatten();
+        let msg = crate::services::notifier::format_flow_signal(&signal, market_question.as_deref(), event.price);
+        n.notify(crate::services::notifier::Severity::Info, &msg).await;
+    }
+
+    if let Some(tx) = signal_tx {
+        let flow_signal = CopySignal {
+            whale_trade_id: trade_id,
+            wallet: "flow".into(),
+            market_id: event.market_id.clone(),
+            condition_id: condition_id.map(str::to_string),
+            asset_id: event.asset_id.clone(),
+            side: Side::Buy,
+            price: event.price,
+            whale_win_rate: Decimal::ZERO,
+            whale_kelly: Decimal::ZERO,
+            whale_notional: signal.net_notional,
+            is_whale_exit: false,
+            sleeve: Sleeve::Flow,
+            profile: None,
+            manual_size: None,
+            size_multiplier: Decimal::ONE,
+            source_signal_id: None,
+            consensus_pct: None,
+            flow_signal_id: Some(signal.id),
+            tier: None,
+        };
+        if let Err(e) = tx.send(flow_signal).await {
+            tracing::error!(error = %e, "Failed to send flow CopySignal");
+        }
+    }
+}
+
+/// A replayed event older than `ttl_secs`: the moment to copy it has passed.
+pub fn is_stale_replay(event: &WhaleTradeEvent, ttl_secs: u64, now: DateTime<Utc>) -> bool {
+    event.replayed && now - event.timestamp > chrono::Duration::seconds(ttl_secs as i64)
+}
+
+/// Signal gates for a whale tier: the tier's entry thresholds and gates in
+/// place of the global ones, then the active gate profile's on top.
+pub fn tier_config(base: &PipelineConfig, tier: &TierPolicy) -> PipelineConfig {
+    let mut cfg = base.clone();
+    // Tier entry is decided on the win rate lower bound
+    cfg.min_signal_win_rate_lb = tier.min_win_rate;
+    cfg.min_signal_skill_score = tier.min_skill_score;
+    cfg.min_total_trades_for_signal = tier.min_total_trades;
+    cfg.min_signal_ev = tier.min_signal_ev;
+    cfg.signal_notional_floor = tier.signal_notional_floor;
+    if let Some(profile) = &base.gate_profile {
+        apply_gate_profile(&mut cfg, profile);
+    }
+    cfg
+}
+
+/// Signal gates for a copy profile: the pipeline config with the profile's
+/// own gates layered on top.
+fn profile_config(base: &PipelineConfig, profile: &CopyProfile) -> PipelineConfig {
+    let mut cfg = base.clone();
+    if let Some(v) = profile.min_signal_win_rate {
+        cfg.min_signal_win_rate = v;
+    }
+    if let Some(v) = profile.min_total_trades_for_signal {
+        cfg.min_total_trades_for_signal = v;
+    }
+    if let Some(v) = profile.min_signal_ev {
+        cfg.min_signal_ev = v;
+    }
+    if let Some(v) = profile.min_signal_profit_factor {
+        cfg.min_signal_profit_factor = v;
+    }
+    if let Some(v) = profile.min_signal_skill_score {
+        cfg.min_signal_skill_score = v;
+    }
+    if let Some(v) = profile.signal_notional_floor {
+        cfg.signal_notional_floor = v;
+    }
+    if let Some(v) = profile.max_signal_notional {
+        cfg.max_signal_notional = v;
+    }
+    cfg
+}
+
+/// Profit of each of `trades` whose market has resolved, for scoring.
+/// Trades in unresolved markets are left out. Outcomes are fetched in one query.
+pub async fn resolved_trade_results(pool: &PgPool, trades: &[WhaleTrade]) -> Vec<TradeResult> {
+    let keys: Vec<String> = trades.iter().map(|t| t.market_key().to_string()).collect();
+    let outcomes = market_repo::get_resolved_outcomes(pool, &keys).await.unwrap_or_default();
+
+    trades
+        .iter()
+        .filter_map(|t| {
+            let outcome = outcomes.get(t.market_key())?;
+            let profit = resolved_profit(&t.side, t.price, t.notional, outcome)?;
+            Some(TradeResult {
+                profit,
+                notional: t.notional,
+                traded_at: t.traded_at,
+            })
+        })
+        .collect()
+}
+
+/// Apply runtime config overrides from the database on top of the base config,
+/// then the active gate profile's gates on top of those.
 pub async fn apply_runtime_overrides(base: &PipelineConfig, pool: &PgPool) -> PipelineConfig {
     let mut cfg = base.clone();
 
@@ -615,12 +1297,24 @@ pub async fn apply_runtime_overrides(base: &PipelineConfig, pool: &PgPool) -> Pi
             "min_signal_win_rate" => {
                 if let Ok(v) = entry.value.parse() { cfg.min_signal_win_rate = v; }
             }
+            "min_signal_win_rate_lb" => {
+                if let Ok(v) = entry.value.parse() { cfg.min_signal_win_rate_lb = v; }
+            }
             "min_total_trades_for_signal" => {
                 if let Ok(v) = entry.value.parse() { cfg.min_total_trades_for_signal = v; }
             }
             "min_signal_ev" => {
                 if let Ok(v) = entry.value.parse() { cfg.min_signal_ev = v; }
             }
+            "min_signal_profit_factor" => {
+                if let Ok(v) = entry.value.parse() { cfg.min_signal_profit_factor = v; }
+            }
+            "min_signal_skill_score" => {
+                if let Ok(v) = entry.value.parse() { cfg.min_signal_skill_score = v; }
+            }
+            "first_mover_size_multiplier" => {
+                if let Ok(v) = entry.value.parse() { cfg.first_mover_size_multiplier = v; }
+            }
             "assumed_slippage_pct" => {
                 if let Ok(v) = entry.value.parse() { cfg.assumed_slippage_pct = v; }
             }
@@ -640,5 +1334,54 @@ pub async fn apply_runtime_overrides(base: &PipelineConfig, pool: &PgPool) -> Pi
         }
     }
 
+    match gate_profile_repo::get_active_gate_profile(pool).await {
+        Ok(Some(profile)) => {
+            apply_gate_profile(&mut cfg, &profile);
+            cfg.gate_profile = Some(profile);
+        }
+        Ok(None) => {}
+        Err(e) => tracing::warn!(error = %e, "Failed to load active gate profile"),
+    }
+
     cfg
 }
+
+/// Overwrite the gates a profile sets; the ones it leaves NULL are kept.
+pub fn apply_gate_profile(cfg: &mut PipelineConfig, profile: &GateProfile) {
+    if let Some(v) = profile.min_signal_win_rate {
+        cfg.min_signal_win_rate = v;
+    }
+    if let Some(v) = profile.min_resolved_for_signal {
+        cfg.min_resolved_for_signal = v;
+    }
+    if let Some(v) = profile.min_total_trades_for_signal {
+        cfg.min_total_trades_for_signal = v;
+    }
+    if let Some(v) = profile.min_signal_ev {
+        cfg.min_signal_ev = v;
+    }
+    if let Some(v) = profile.min_signal_profit_factor {
+        cfg.min_signal_profit_factor = v;
+    }
+    if let Some(v) = profile.min_signal_skill_score {
+        cfg.min_signal_skill_score = v;
+    }
+    if let Some(v) = profile.min_entry_edge {
+        cfg.min_entry_edge = v;
+    }
+    if let Some(v) = profile.signal_notional_liquidity_pct {
+        cfg.signal_notional_liquidity_pct = v;
+    }
+    if let Some(v) = profile.signal_notional_floor {
+        cfg.signal_notional_floor = v;
+    }
+    if let Some(v) = profile.max_signal_notional {
+        cfg.max_signal_notional = v;
+    }
+    if let Some(v) = profile.assumed_slippage_pct {
+        cfg.assumed_slippage_pct = v;
+    }
+    if let Some(v) = profile.first_mover_size_multiplier {
+        cfg.first_mover_size_multiplier = v;
+    }
+}
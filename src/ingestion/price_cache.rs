@@ -0,0 +1,106 @@
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration, Utc};
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+
+/// Latest top-of-book seen on the market WebSocket for one token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quote {
+    pub best_bid: Decimal,
+    pub best_ask: Decimal,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Shared in-memory best bid/ask per token, fed by the WS listener.
+///
+/// Readers only get quotes younger than `max_age`; anything older is treated
+/// as missing so callers fall back to the REST orderbook.
+#[derive(Clone)]
+pub struct PriceCache {
+    quotes: Arc<RwLock<HashMap<String, Quote>>>,
+    max_age: Duration,
+}
+
+impl PriceCache {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            quotes: Arc::new(RwLock::new(HashMap::new())),
+            max_age,
+        }
+    }
+
+    /// Record a quote, ignoring updates older than the one already held.
+    pub async fn update(&self, token_id: &str, quote: Quote) {
+        let mut quotes = self.quotes.write().await;
+        match quotes.get_mut(token_id) {
+            Some(existing) if existing.updated_at > quote.updated_at => {}
+            Some(existing) => *existing = quote,
+            None => {
+                quotes.insert(token_id.to_string(), quote);
+            }
+        }
+    }
+
+    /// Quote for a token if it is fresh as of `now`.
+    pub async fn fresh_quote(&self, token_id: &str, now: DateTime<Utc>) -> Option<Quote> {
+        let quotes = self.quotes.read().await;
+        quotes
+            .get(token_id)
+            .filter(|q| now - q.updated_at <= self.max_age)
+            .copied()
+    }
+
+    /// Fresh best bid — the price a held position would exit at.
+    pub async fn best_bid(&self, token_id: &str) -> Option<Decimal> {
+        self.fresh_quote(token_id, Utc::now()).await.map(|q| q.best_bid)
+    }
+
+    /// Fresh best ask — the price we would buy at.
+    pub async fn best_ask(&self, token_id: &str) -> Option<Decimal> {
+        self.fresh_quote(token_id, Utc::now()).await.map(|q| q.best_ask)
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(bid: i64, ask: i64, at: DateTime<Utc>) -> Quote {
+        Quote {
+            best_bid: Decimal::new(bid, 2),
+            best_ask: Decimal::new(ask, 2),
+            updated_at: at,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_stale_quotes_are_ignored() {
+        let cache = PriceCache::new(Duration::seconds(30));
+        let now = Utc::now();
+
+        cache.update("tok", quote(40, 42, now - Duration::seconds(10))).await;
+        assert_eq!(
+            cache.fresh_quote("tok", now).await.map(|q| q.best_bid),
+            Some(Decimal::new(40, 2))
+        );
+        assert!(cache.fresh_quote("tok", now + Duration::seconds(25)).await.is_none());
+        assert!(cache.fresh_quote("other", now).await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_out_of_order_update_does_not_regress() {
+        let cache = PriceCache::new(Duration::seconds(30));
+        let now = Utc::now();
+
+        cache.update("tok", quote(50, 52, now)).await;
+        cache.update("tok", quote(40, 42, now - Duration::seconds(5))).await;
+
+        assert_eq!(cache.fresh_quote("tok", now).await, Some(quote(50, 52, now)));
+    }
+}
@@ -0,0 +1,187 @@
+use metrics::{counter, gauge};
+use tokio::sync::mpsc;
+
+use crate::models::WhaleTradeEvent;
+use crate::services::shutdown::TaskRegistry;
+
+/// Events waiting in the high-priority lane before intake blocks.
+const HIGH_LANE_CAPACITY: usize = 1000;
+
+/// Which lane an event waits in on its way to the pipeline.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lane {
+    /// Trades with a wallet: tracked whales from the chain listener and
+    /// trade poller. Never dropped.
+    High,
+    /// Anonymous WS market trades. Sampled, then dropped, when backed up.
+    Low,
+}
+
+impl Lane {
+    pub fn of(event: &WhaleTradeEvent) -> Self {
+        if event.is_anonymous() {
+            Lane::Low
+        } else {
+            Lane::High
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Lane::High => "high",
+            Lane::Low => "low",
+        }
+    }
+}
+
+/// Backpressure policy for the low-priority lane.
+#[derive(Debug, Clone)]
+pub struct LaneConfig {
+    /// Low-priority events that can wait; further ones are dropped.
+    pub low_capacity: usize,
+    /// Once the low lane is half full, keep only one in this many of its
+    /// events (1 = no sampling).
+    pub low_sample_every: u64,
+}
+
+/// Whether a low-priority event is let into a lane holding `depth` of
+/// `capacity` events; `seq` counts the low events seen so far.
+fn admit_low(depth: usize, capacity: usize, seq: u64, sample_every: u64) -> Result<(), &'static str> {
+    if depth >= capacity {
+        return Err("full");
+    }
+    if depth * 2 >= capacity && sample_every > 1 && !seq.is_multiple_of(sample_every) {
+        return Err("sampled");
+    }
+    Ok(())
+}
+
+/// Put priority lanes in front of `pipeline_tx`. Ingestion sources send to
+/// the returned sender; tracked-whale trades always go ahead of queued
+/// anonymous WS trades, and anonymous trades are shed under backpressure
+/// instead of delaying them. Lane depths and drops are exported as metrics.
+pub fn prioritize(
+    tasks: &mut TaskRegistry,
+    pipeline_tx: mpsc::Sender<WhaleTradeEvent>,
+    config: LaneConfig,
+) -> mpsc::Sender<WhaleTradeEvent> {
+    let (tx, mut rx) = mpsc::channel::<WhaleTradeEvent>(HIGH_LANE_CAPACITY);
+    let (high_tx, mut high_rx) = mpsc::channel::<WhaleTradeEvent>(HIGH_LANE_CAPACITY);
+    let (low_tx, mut low_rx) = mpsc::channel::<WhaleTradeEvent>(config.low_capacity.max(1));
+
+    tasks.spawn("pipeline_lane_intake", async move {
+        let mut low_seen: u64 = 0;
+        while let Some(event) = rx.recv().await {
+            match Lane::of(&event) {
+                Lane::High => {
+                    if high_tx.send(event).await.is_err() {
+                        break;
+                    }
+                }
+                Lane::Low => {
+                    let depth = low_tx.max_capacity() - low_tx.capacity();
+                    let admitted = admit_low(depth, low_tx.max_capacity(), low_seen, config.low_sample_every)
+                        .and_then(|()| low_tx.try_send(event).map_err(|_| "full"));
+                    if let Err(reason) = admitted {
+                        counter!("pipeline_events_dropped", "lane" => "low", "reason" => reason).increment(1);
+                    }
+                    low_seen += 1;
+                }
+            }
+        }
+    });
+
+    tasks.spawn("pipeline_lanes", async move {
+        loop {
+            let (lane, event) = tokio::select! {
+                biased;
+                Some(event) = high_rx.recv() => (Lane::High, event),
+                Some(event) = low_rx.recv() => (Lane::Low, event),
+                else => break,
+            };
+            gauge!("pipeline_lane_depth", "lane" => "high").set(high_rx.len() as f64);
+            gauge!("pipeline_lane_depth", "lane" => "low").set(low_rx.len() as f64);
+            counter!("pipeline_lane_events_total", "lane" => lane.as_str()).increment(1);
+            if pipeline_tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tx
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use rust_decimal::Decimal;
+    use std::time::Duration;
+
+    use crate::models::{Side, ANONYMOUS_WALLET};
+
+    fn event(wallet: &str, size: i64) -> WhaleTradeEvent {
+        WhaleTradeEvent {
+            wallet: wallet.into(),
+            market_id: "0xcond".into(),
+            asset_id: "tok".into(),
+            side: Side::Buy,
+            size: Decimal::from(size),
+            price: Decimal::new(50, 2),
+            notional: Decimal::from(size) / Decimal::TWO,
+            timestamp: Utc::now(),
+            tx_hash: None,
+            fill_index: None,
+            replayed: false,
+        }
+    }
+
+    #[test]
+    fn test_admit_low_samples_then_drops() {
+        // Under half full: everything
+        assert_eq!(admit_low(3, 10, 1, 4), Ok(()));
+        // Half full: one in four
+        assert_eq!(admit_low(5, 10, 4, 4), Ok(()));
+        assert_eq!(admit_low(5, 10, 5, 4), Err("sampled"));
+        // No sampling configured
+        assert_eq!(admit_low(9, 10, 5, 1), Ok(()));
+        // Full: nothing
+        assert_eq!(admit_low(10, 10, 0, 1), Err("full"));
+    }
+
+    #[tokio::test]
+    async fn test_whale_trade_overtakes_queued_anonymous_trades() {
+        let mut tasks = TaskRegistry::default();
+        let (pipeline_tx, mut pipeline_rx) = mpsc::channel(1);
+        let tx = prioritize(
+            &mut tasks,
+            pipeline_tx,
+            LaneConfig {
+                low_capacity: 4,
+                low_sample_every: 1,
+            },
+        );
+
+        // The pipeline is stalled: anonymous trades back up and overflow
+        for size in 1..=10 {
+            tx.send(event(ANONYMOUS_WALLET, size)).await.unwrap();
+        }
+        tx.send(event("0xwhale", 1000)).await.unwrap();
+        tokio::time::sleep(Duration::from_millis(50)).await;
+
+        let mut received = Vec::new();
+        while let Ok(Some(e)) = tokio::time::timeout(Duration::from_millis(50), pipeline_rx.recv()).await {
+            received.push(e);
+        }
+
+        // Only what was already in flight beats the whale trade
+        let whale_at = received.iter().position(|e| !e.is_anonymous()).expect("whale trade delivered");
+        assert!(whale_at <= 2, "whale trade at position {whale_at}");
+        // Overflowing anonymous trades were dropped
+        assert!(received.len() < 11);
+    }
+}
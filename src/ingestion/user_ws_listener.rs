@@ -0,0 +1,217 @@
+use futures_util::{SinkExt, StreamExt};
+use metrics::counter;
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::time::{interval, sleep};
+use tokio_tungstenite::{connect_async, tungstenite::Message};
+
+use crate::polymarket::types::{WsAuth, WsUserEvent, WsUserSubscribe};
+use crate::polymarket::PolymarketAuth;
+use crate::services::job_trigger::JobTrigger;
+use crate::services::order_fill_poller::FillPollSummary;
+
+const PING_INTERVAL: Duration = Duration::from_secs(25);
+const BASE_RECONNECT_DELAY: Duration = Duration::from_secs(2);
+const MAX_RECONNECT_DELAY: Duration = Duration::from_secs(60);
+
+/// CLOB order IDs whose state an event may have changed: both sides of a
+/// trade, and the order of an update or cancellation. Placements change
+/// nothing the fill poller tracks.
+pub fn affected_order_ids(event: &WsUserEvent) -> Vec<String> {
+    match event.event_type.as_deref() {
+        Some("trade") => event
+            .taker_order_id
+            .iter()
+            .chain(event.maker_orders.iter().filter_map(|m| m.order_id.as_ref()))
+            .cloned()
+            .collect(),
+        Some("order") if event.msg_type.as_deref() != Some("PLACEMENT") => event.id.iter().cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Order IDs affected by a user channel frame (one event or an array of them).
+fn frame_order_ids(text: &str) -> Vec<String> {
+    let events: Vec<WsUserEvent> = match serde_json::from_str::<serde_json::Value>(text) {
+        Ok(value @ serde_json::Value::Array(_)) => serde_json::from_value(value).unwrap_or_default(),
+        Ok(value) => serde_json::from_value(value).map(|e| vec![e]).unwrap_or_default(),
+        Err(_) => Vec::new(),
+    };
+
+    let mut order_ids = Vec::new();
+    for event in &events {
+        let event_type = event.event_type.clone().unwrap_or_default();
+        counter!("user_ws_events_total", "event_type" => event_type).increment(1);
+        order_ids.extend(affected_order_ids(event));
+    }
+    order_ids
+}
+
+/// Run the user channel listener: stream our own order and trade events
+/// and run a fill poller cycle as soon as one touches an order, so fills
+/// are confirmed in seconds rather than on the next poll. Events arriving
+/// while a cycle runs are folded into one follow-up cycle.
+pub async fn run_user_ws_listener(ws_url: String, auth: PolymarketAuth, fill_poller: JobTrigger<Result<FillPollSummary, String>>) {
+    let (kick_tx, mut kick_rx) = mpsc::channel::<()>(1);
+    tokio::spawn(async move {
+        while kick_rx.recv().await.is_some() {
+            if let Some(Ok(summary)) = fill_poller.run().await {
+                tracing::debug!(
+                    filled = summary.filled,
+                    cancelled = summary.cancelled,
+                    "User channel: fill poller cycle complete"
+                );
+            }
+        }
+    });
+
+    let subscribe = WsUserSubscribe::user(WsAuth {
+        api_key: auth.api_key,
+        secret: auth.api_secret,
+        passphrase: auth.passphrase,
+    });
+    let subscribe = match serde_json::to_string(&subscribe) {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::error!(error = %e, "User channel: failed to build subscribe message");
+            return;
+        }
+    };
+
+    let mut attempt: u32 = 0;
+    loop {
+        tracing::info!(url = %ws_url, "Connecting to Polymarket user channel...");
+
+        match connect_async(&ws_url).await {
+            Ok((ws_stream, _response)) => {
+                let (mut write, mut read) = ws_stream.split();
+
+                if let Err(e) = write.send(Message::Text(subscribe.clone().into())).await {
+                    tracing::error!(error = %e, "User channel: failed to send subscribe message");
+                } else {
+                    tracing::info!("Subscribed to Polymarket user channel");
+                    attempt = 0;
+
+                    let mut ping_timer = interval(PING_INTERVAL);
+                    ping_timer.tick().await; // consume the first immediate tick
+
+                    loop {
+                        tokio::select! {
+                            msg = read.next() => {
+                                match msg {
+                                    Some(Ok(Message::Text(text))) => {
+                                        let order_ids = frame_order_ids(text.as_ref());
+                                        if !order_ids.is_empty() {
+                                            tracing::debug!(?order_ids, "User channel: order activity — running fill poller");
+                                            // A cycle already queued covers these orders too
+                                            let _ = kick_tx.try_send(());
+                                        }
+                                    }
+                                    Some(Ok(Message::Ping(data))) => {
+                                        if let Err(e) = write.send(Message::Pong(data)).await {
+                                            tracing::warn!(error = %e, "User channel: failed to send pong");
+                                            break;
+                                        }
+                                    }
+                                    Some(Ok(Message::Close(_))) => {
+                                        tracing::warn!("User channel: server sent close frame");
+                                        break;
+                                    }
+                                    Some(Ok(_)) => {} // Binary, Pong, Frame — ignore
+                                    Some(Err(e)) => {
+                                        tracing::error!(error = %e, "User channel read error");
+                                        break;
+                                    }
+                                    None => {
+                                        tracing::warn!("User channel stream ended");
+                                        break;
+                                    }
+                                }
+                            }
+                            _ = ping_timer.tick() => {
+                                if let Err(e) = write.send(Message::Ping(vec![].into())).await {
+                                    tracing::warn!(error = %e, "User channel: failed to send ping");
+                                    break;
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "User channel connection failed");
+            }
+        }
+
+        // Orders may have filled while disconnected: let the poller catch up
+        let _ = kick_tx.try_send(());
+
+        // Exponential backoff with cap
+        let delay = BASE_RECONNECT_DELAY * 2u32.saturating_pow(attempt);
+        let delay = delay.min(MAX_RECONNECT_DELAY);
+        attempt = attempt.saturating_add(1);
+        tracing::info!(delay_secs = delay.as_secs(), attempt, "User channel reconnecting...");
+        sleep(delay).await;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subscribe_message_format() {
+        let sub = WsUserSubscribe::user(WsAuth {
+            api_key: "key".into(),
+            secret: "secret".into(),
+            passphrase: "pass".into(),
+        });
+        let json: serde_json::Value = serde_json::to_value(&sub).unwrap();
+        assert_eq!(json["type"], "user");
+        assert_eq!(json["auth"]["apiKey"], "key");
+        assert_eq!(json["auth"]["passphrase"], "pass");
+        assert_eq!(json["markets"], serde_json::json!([]));
+    }
+
+    #[test]
+    fn test_trade_event_affects_taker_and_maker_orders() {
+        let text = r#"{
+            "event_type": "trade",
+            "id": "28c4d2eb-bbea-40e7-a9f0-b2fdb56b2c2e",
+            "type": "TRADE",
+            "status": "MATCHED",
+            "taker_order_id": "0x06bc63e346ed4ceddce9efd6b3af37c8f8f440c92fe7da6b2d0f9e4ccbc50c42",
+            "maker_orders": [
+                {"order_id": "0xff354cd7ca7539dfa9c28d90943ab5779a4eac34b9b37a757d7b32bdfb11790b", "matched_amount": "10"}
+            ],
+            "side": "BUY",
+            "size": "10",
+            "price": "0.57"
+        }"#;
+
+        assert_eq!(
+            frame_order_ids(text),
+            vec![
+                "0x06bc63e346ed4ceddce9efd6b3af37c8f8f440c92fe7da6b2d0f9e4ccbc50c42".to_string(),
+                "0xff354cd7ca7539dfa9c28d90943ab5779a4eac34b9b37a757d7b32bdfb11790b".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_order_events() {
+        let update = r#"[{"event_type": "order", "id": "0xabc", "type": "UPDATE", "size_matched": "5"}]"#;
+        assert_eq!(frame_order_ids(update), vec!["0xabc".to_string()]);
+
+        let cancel = r#"{"event_type": "order", "id": "0xdef", "type": "CANCELLATION"}"#;
+        assert_eq!(frame_order_ids(cancel), vec!["0xdef".to_string()]);
+
+        // Placements and anything unparseable change nothing
+        assert!(frame_order_ids(r#"{"event_type": "order", "id": "0x1", "type": "PLACEMENT"}"#).is_empty());
+        assert!(frame_order_ids("PONG").is_empty());
+    }
+}
@@ -0,0 +1,120 @@
+use std::collections::hash_map::DefaultHasher;
+use std::future::Future;
+use std::hash::{Hash, Hasher};
+
+use metrics::gauge;
+use tokio::sync::mpsc;
+
+use crate::models::WhaleTradeEvent;
+use crate::services::shutdown::TaskRegistry;
+
+/// Events waiting per worker before dispatch blocks.
+const WORKER_QUEUE: usize = 64;
+
+/// Key that must be processed in order: the wallet, or for anonymous WS
+/// trades (no wallet, nothing per-wallet to keep in order) the market, so
+/// they spread across workers.
+pub fn ordering_key(event: &WhaleTradeEvent) -> String {
+    if event.is_anonymous() {
+        event.market_id.clone()
+    } else {
+        event.wallet.to_lowercase()
+    }
+}
+
+/// Worker a key is pinned to.
+pub fn worker_index(key: &str, workers: usize) -> usize {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    (hasher.finish() % workers.max(1) as u64) as usize
+}
+
+/// Fixed pool of tasks running `handler` concurrently. Every item with the
+/// same ordering key goes to the same worker, which handles its items one at
+/// a time, so a wallet's trades are processed in arrival order while
+/// different wallets' run in parallel.
+pub struct WalletWorkers<T> {
+    workers: Vec<mpsc::Sender<T>>,
+}
+
+impl<T: Send + 'static> WalletWorkers<T> {
+    pub fn spawn<F, Fut>(tasks: &mut TaskRegistry, count: usize, handler: F) -> Self
+    where
+        F: Fn(T) -> Fut + Clone + Send + 'static,
+        Fut: Future<Output = ()> + Send,
+    {
+        let workers = (0..count.max(1))
+            .map(|i| {
+                let (tx, mut rx) = mpsc::channel::<T>(WORKER_QUEUE);
+                let handler = handler.clone();
+                tasks.spawn("pipeline_worker", async move {
+                    let worker = i.to_string();
+                    while let Some(item) = rx.recv().await {
+                        gauge!("pipeline_worker_queue_depth", "worker" => worker.clone()).set(rx.len() as f64);
+                        handler(item).await;
+                    }
+                });
+                tx
+            })
+            .collect();
+        Self { workers }
+    }
+
+    /// Queue an item on its key's worker, waiting while that worker is
+    /// backed up. False once the worker has stopped.
+    pub async fn dispatch(&self, key: &str, item: T) -> bool {
+        let worker = &self.workers[worker_index(key, self.workers.len())];
+        worker.send(item).await.is_ok()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+
+    #[test]
+    fn test_worker_index_is_stable_and_in_range() {
+        for key in ["0xabc", "0xdef", "market_1"] {
+            let index = worker_index(key, 4);
+            assert!(index < 4);
+            assert_eq!(index, worker_index(key, 4));
+        }
+        assert_eq!(worker_index("0xabc", 0), 0);
+    }
+
+    #[tokio::test]
+    async fn test_per_key_order_kept_across_concurrent_workers() {
+        let mut tasks = TaskRegistry::default();
+        let seen = Arc::new(Mutex::new(Vec::<(String, u32)>::new()));
+        let record = Arc::clone(&seen);
+        let workers = WalletWorkers::spawn(&mut tasks, 4, move |(key, n): (String, u32)| {
+            let record = Arc::clone(&record);
+            async move {
+                // Early items take longest: a shared queue would reorder them
+                tokio::time::sleep(Duration::from_millis(u64::from(10 - n))).await;
+                record.lock().unwrap().push((key, n));
+            }
+        });
+
+        let keys = ["0xaaa", "0xbbb", "0xccc"];
+        for n in 0..10 {
+            for key in keys {
+                assert!(workers.dispatch(key, (key.to_string(), n)).await);
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), 30);
+        for key in keys {
+            let order: Vec<u32> = seen.iter().filter(|(k, _)| k == key).map(|(_, n)| *n).collect();
+            assert_eq!(order, (0..10).collect::<Vec<_>>());
+        }
+    }
+}
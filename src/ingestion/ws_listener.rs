@@ -1,14 +1,20 @@
 use chrono::Utc;
 use futures_util::{SinkExt, StreamExt};
+use metrics::counter;
 use rust_decimal::Decimal;
+use std::collections::HashSet;
+use std::future::Future;
 use std::str::FromStr;
 use std::time::Duration;
 use tokio::sync::{mpsc, watch};
-use tokio::time::{interval, sleep};
+use tokio::time::{interval, sleep, sleep_until, Instant};
 use tokio_tungstenite::{connect_async, tungstenite::Message};
 
-use crate::models::{Side, WhaleTradeEvent};
-use crate::polymarket::types::{WsSubscribe, WsTrade, WsTradeEvent};
+use crate::ingestion::price_cache::{PriceCache, Quote};
+use crate::models::{PriceTick, Side, WhaleTradeEvent, ANONYMOUS_WALLET};
+use crate::polymarket::types::{
+    WsBookEvent, WsBookLevel, WsPriceChangeEvent, WsSubscribe, WsSubscriptionUpdate, WsTrade, WsTradeEvent,
+};
 
 const PING_INTERVAL: Duration = Duration::from_secs(25);
 const BASE_RECONNECT_DELAY: Duration = Duration::from_secs(2);
@@ -34,15 +40,90 @@ fn build_subscribe_messages(token_ids: &[String]) -> Vec<String> {
         .collect()
 }
 
+/// Tokens in `new_tokens` not yet subscribed (in list order), and subscribed
+/// tokens no longer in it (sorted).
+fn diff_subscriptions(subscribed: &HashSet<String>, new_tokens: &[String]) -> (Vec<String>, Vec<String>) {
+    let wanted: HashSet<&String> = new_tokens.iter().collect();
+    let mut seen = HashSet::new();
+    let added = new_tokens
+        .iter()
+        .filter(|t| !subscribed.contains(*t) && seen.insert(*t))
+        .cloned()
+        .collect();
+    let mut removed: Vec<String> = subscribed.iter().filter(|t| !wanted.contains(t)).cloned().collect();
+    removed.sort();
+    (added, removed)
+}
+
+/// Batched subscribe messages for `added` followed by unsubscribe messages
+/// for `removed`, for an already-open connection.
+fn build_update_messages(added: &[String], removed: &[String]) -> Vec<String> {
+    let subscribes = added.chunks(SUBSCRIBE_BATCH_SIZE).map(WsSubscriptionUpdate::subscribe);
+    let unsubscribes = removed.chunks(SUBSCRIBE_BATCH_SIZE).map(WsSubscriptionUpdate::unsubscribe);
+    subscribes
+        .chain(unsubscribes)
+        .filter_map(|update| serde_json::to_string(&update).ok())
+        .collect()
+}
+
+/// Receives the text frames of a market WebSocket connection. Lets the live
+/// listener and the research recorder share one connection loop.
+pub trait WsTextHandler: Send {
+    fn on_text(&mut self, text: &str) -> impl Future<Output = ()> + Send;
+}
+
+/// Live-trading handler: trades to the pipeline, ticks to the candle
+/// recorder, quotes to the price cache.
+struct LiveHandler {
+    tx: mpsc::Sender<WhaleTradeEvent>,
+    tick_tx: mpsc::Sender<PriceTick>,
+    price_cache: PriceCache,
+}
+
+impl WsTextHandler for LiveHandler {
+    async fn on_text(&mut self, text: &str) {
+        handle_text_message(text, &self.tx, &self.tick_tx, &self.price_cache).await;
+    }
+}
+
 /// Run the WebSocket listener loop with dynamic token subscription updates.
 ///
 /// `token_rx` is a `watch::Receiver` that emits updated token ID lists
 /// from the market discovery service. When new tokens arrive, the listener
-/// sends fresh subscribe messages on the existing connection.
+/// subscribes to the new ones and unsubscribes from the dropped ones on the
+/// existing connection.
+///
+/// Price observations (trades and quote midpoints) go to `tick_tx` for the
+/// candle recorder; ticks are dropped rather than stalling the socket.
+/// Best bid/ask updates are also written to `price_cache`.
 pub async fn run_ws_listener(
     ws_url: String,
     token_rx: watch::Receiver<Vec<String>>,
     tx: mpsc::Sender<WhaleTradeEvent>,
+    tick_tx: mpsc::Sender<PriceTick>,
+    price_cache: PriceCache,
+    stale_timeout: Duration,
+) {
+    let handler = LiveHandler {
+        tx,
+        tick_tx,
+        price_cache,
+    };
+    run_ws_connection(ws_url, token_rx, handler, stale_timeout).await;
+}
+
+/// Keep a market WebSocket connection open — subscribing to the tokens from
+/// `token_rx`, applying changes to the list incrementally, reconnecting with backoff —
+/// and pass every text frame to `handler`.
+///
+/// A socket can die without a close frame; when subscribed to any tokens and
+/// no data arrives for `stale_timeout` (zero = never), the connection is
+/// dropped and re-established, which resubscribes.
+pub async fn run_ws_connection<H: WsTextHandler>(
+    ws_url: String,
+    token_rx: watch::Receiver<Vec<String>>,
+    mut handler: H,
+    stale_timeout: Duration,
 ) {
     let mut attempt: u32 = 0;
     let mut token_rx = token_rx;
@@ -71,16 +152,20 @@ pub async fn run_ws_listener(
                     batches = msgs.len(),
                     "Subscribed to initial token list"
                 );
+                let mut subscribed: HashSet<String> = current_tokens.into_iter().collect();
 
                 let mut ping_timer = interval(PING_INTERVAL);
                 ping_timer.tick().await; // consume the first immediate tick
+                let mut last_data = Instant::now();
 
                 loop {
+                    let watchdog_armed = !stale_timeout.is_zero() && !subscribed.is_empty();
                     tokio::select! {
                         msg = read.next() => {
                             match msg {
                                 Some(Ok(Message::Text(text))) => {
-                                    handle_text_message(text.as_ref(), &tx).await;
+                                    last_data = Instant::now();
+                                    handler.on_text(text.as_ref()).await;
                                 }
                                 Some(Ok(Message::Ping(data))) => {
                                     if let Err(e) = write.send(Message::Pong(data)).await {
@@ -109,24 +194,49 @@ pub async fn run_ws_listener(
                                 break;
                             }
                         }
+                        _ = sleep_until(last_data + stale_timeout), if watchdog_armed => {
+                            counter!("ws_stale_reconnects_total").increment(1);
+                            tracing::warn!(
+                                silent_secs = last_data.elapsed().as_secs(),
+                                "No WebSocket data within the stale timeout — forcing reconnect"
+                            );
+                            break;
+                        }
                         result = token_rx.changed() => {
                             if result.is_err() {
                                 tracing::warn!("Token watch channel closed");
                                 break;
                             }
                             let new_tokens = token_rx.borrow().clone();
-                            let msgs = build_subscribe_messages(&new_tokens);
+                            let (added, removed) = diff_subscriptions(&subscribed, &new_tokens);
+                            if added.is_empty() && removed.is_empty() {
+                                continue;
+                            }
+                            let msgs = build_update_messages(&added, &removed);
                             tracing::info!(
                                 token_count = new_tokens.len(),
+                                added = added.len(),
+                                removed = removed.len(),
                                 batches = msgs.len(),
-                                "Received updated token list — resubscribing"
+                                "Received updated token list — updating subscriptions"
                             );
+                            // Give new subscriptions a full timeout to start streaming
+                            if !added.is_empty() {
+                                last_data = Instant::now();
+                            }
+                            let mut sent = true;
                             for msg in &msgs {
                                 if let Err(e) = write.send(Message::Text(msg.clone().into())).await {
-                                    tracing::error!(error = %e, "Failed to send subscribe message");
+                                    tracing::error!(error = %e, "Failed to send subscription update");
+                                    sent = false;
                                     break;
                                 }
                             }
+                            if !sent {
+                                // Subscribed set unknown — reconnect and subscribe afresh
+                                break;
+                            }
+                            subscribed = new_tokens.into_iter().collect();
                         }
                     }
                 }
@@ -150,11 +260,44 @@ pub async fn run_ws_listener(
 /// - Book events: `{"event_type": "book", ...}`
 /// - Price changes: `{"event_type": "price_change", ...}`
 /// - Legacy format: `[{...}, ...]` arrays of trades
-async fn handle_text_message(text: &str, tx: &mpsc::Sender<WhaleTradeEvent>) {
+async fn handle_text_message(
+    text: &str,
+    tx: &mpsc::Sender<WhaleTradeEvent>,
+    tick_tx: &mpsc::Sender<PriceTick>,
+    price_cache: &PriceCache,
+) {
     // Try the new Polymarket WS event format first
     if let Ok(event) = serde_json::from_str::<WsTradeEvent>(text) {
+        if event.event_type.as_deref() == Some("price_change") {
+            if let Ok(change) = serde_json::from_str::<WsPriceChangeEvent>(text) {
+                for (token_id, quote) in convert_price_change_quotes(&change) {
+                    price_cache.update(&token_id, quote).await;
+                }
+                for tick in convert_price_change(&change) {
+                    send_tick(tick_tx, tick);
+                }
+            }
+            return;
+        }
+        if event.event_type.as_deref() == Some("book") {
+            if let Ok(book) = serde_json::from_str::<WsBookEvent>(text) {
+                if let Some((token_id, quote)) = convert_book_quote(&book) {
+                    price_cache.update(&token_id, quote).await;
+                }
+            }
+            return;
+        }
         if event.event_type.as_deref() == Some("last_trade_price") {
             if let Some(trade_event) = convert_ws_trade_event(&event) {
+                send_tick(
+                    tick_tx,
+                    PriceTick {
+                        token_id: trade_event.asset_id.clone(),
+                        price: trade_event.price,
+                        size: trade_event.size,
+                        timestamp: trade_event.timestamp,
+                    },
+                );
                 tracing::info!(
                     market = %trade_event.market_id,
                     side = %trade_event.side,
@@ -169,7 +312,7 @@ async fn handle_text_message(text: &str, tx: &mpsc::Sender<WhaleTradeEvent>) {
             }
             return;
         }
-        // Non-trade events (book, price_change) — skip silently
+        // Other events (tick_size_change) — skip silently
         if event.event_type.is_some() {
             return;
         }
@@ -228,22 +371,10 @@ fn convert_ws_trade_event(event: &WsTradeEvent) -> Option<WhaleTradeEvent> {
 
     let notional = size * price;
 
-    let timestamp = event
-        .timestamp
-        .as_deref()
-        .and_then(|t| {
-            // Polymarket sends millisecond timestamps
-            if let Ok(ms) = t.parse::<i64>() {
-                return chrono::DateTime::from_timestamp(ms / 1000, ((ms % 1000) * 1_000_000) as u32);
-            }
-            chrono::DateTime::parse_from_rfc3339(t)
-                .ok()
-                .map(|dt| dt.with_timezone(&Utc))
-        })
-        .unwrap_or_else(Utc::now);
+    let timestamp = parse_event_timestamp(event.timestamp.as_deref());
 
     Some(WhaleTradeEvent {
-        wallet: "ws_anonymous".to_string(),
+        wallet: ANONYMOUS_WALLET.to_string(),
         market_id: market_id.to_string(),
         asset_id: asset_id.to_string(),
         side,
@@ -251,7 +382,101 @@ fn convert_ws_trade_event(event: &WsTradeEvent) -> Option<WhaleTradeEvent> {
         price,
         notional,
         timestamp,
+        tx_hash: None,
+        fill_index: None,
+        replayed: false,
+    })
+}
+
+/// Convert a `price_change` event into quote-midpoint ticks (one per asset
+/// with both a best bid and best ask). Ticks carry zero size.
+fn convert_price_change(event: &WsPriceChangeEvent) -> Vec<PriceTick> {
+    convert_price_change_quotes(event)
+        .into_iter()
+        .map(|(token_id, quote)| PriceTick {
+            token_id,
+            price: (quote.best_bid + quote.best_ask) / Decimal::TWO,
+            size: Decimal::ZERO,
+            timestamp: quote.updated_at,
+        })
+        .collect()
+}
+
+/// Top-of-book per asset in a `price_change` event, skipping one-sided or
+/// crossed books.
+fn convert_price_change_quotes(event: &WsPriceChangeEvent) -> Vec<(String, Quote)> {
+    let timestamp = parse_event_timestamp(event.timestamp.as_deref());
+    let parse = |s: Option<&str>| s.and_then(|v| Decimal::from_str(v).ok());
+
+    event
+        .price_changes
+        .iter()
+        .filter_map(|c| {
+            let token_id = c.asset_id.clone()?;
+            let bid = parse(c.best_bid.as_deref())?;
+            let ask = parse(c.best_ask.as_deref())?;
+            if bid <= Decimal::ZERO || ask <= bid {
+                return None;
+            }
+            Some((
+                token_id,
+                Quote {
+                    best_bid: bid,
+                    best_ask: ask,
+                    updated_at: timestamp,
+                },
+            ))
+        })
+        .collect()
+}
+
+/// Top-of-book of a `book` snapshot: highest bid and lowest ask with size
+/// left. One-sided or crossed books yield nothing.
+fn convert_book_quote(event: &WsBookEvent) -> Option<(String, Quote)> {
+    let token_id = event.asset_id.clone()?;
+    let live_prices = |levels: &[WsBookLevel]| -> Vec<Decimal> {
+        levels
+            .iter()
+            .filter_map(|l| {
+                let price = Decimal::from_str(&l.price).ok()?;
+                let size = Decimal::from_str(&l.size).ok()?;
+                (size > Decimal::ZERO).then_some(price)
+            })
+            .collect()
+    };
+    let bid = live_prices(&event.bids).into_iter().max()?;
+    let ask = live_prices(&event.asks).into_iter().min()?;
+    if bid <= Decimal::ZERO || ask <= bid {
+        return None;
+    }
+    Some((
+        token_id,
+        Quote {
+            best_bid: bid,
+            best_ask: ask,
+            updated_at: parse_event_timestamp(event.timestamp.as_deref()),
+        },
+    ))
+}
+
+/// Forward a tick to the candle recorder without blocking the socket.
+fn send_tick(tick_tx: &mpsc::Sender<PriceTick>, tick: PriceTick) {
+    if let Err(mpsc::error::TrySendError::Full(_)) = tick_tx.try_send(tick) {
+        tracing::debug!("Candle tick channel full — dropping tick");
+    }
+}
+
+/// Polymarket sends millisecond epoch timestamps; fall back to RFC3339, then now.
+fn parse_event_timestamp(raw: Option<&str>) -> chrono::DateTime<Utc> {
+    raw.and_then(|t| {
+        if let Ok(ms) = t.parse::<i64>() {
+            return chrono::DateTime::from_timestamp(ms / 1000, ((ms % 1000) * 1_000_000) as u32);
+        }
+        chrono::DateTime::parse_from_rfc3339(t)
+            .ok()
+            .map(|dt| dt.with_timezone(&Utc))
     })
+    .unwrap_or_else(Utc::now)
 }
 
 fn parse_trades_legacy(text: &str) -> Vec<WsTrade> {
@@ -324,5 +549,114 @@ fn convert_ws_trade(ws: &WsTrade) -> Option<WhaleTradeEvent> {
         price,
         notional,
         timestamp,
+        tx_hash: ws.transaction_hash.as_deref().filter(|h| !h.is_empty()).map(str::to_lowercase),
+        fill_index: None,
+        replayed: false,
     })
 }
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_book_snapshot_updates_price_cache() {
+        let (tx, _rx) = mpsc::channel(1);
+        let (tick_tx, _tick_rx) = mpsc::channel(1);
+        let cache = PriceCache::new(chrono::Duration::seconds(30));
+        let now_ms = Utc::now().timestamp_millis();
+
+        // Unsorted levels; the emptied 0.55 bid must not count as best bid
+        let text = format!(
+            r#"{{"event_type":"book","asset_id":"tok1","market":"0xabc",
+                "bids":[{{"price":"0.48","size":"30"}},{{"price":"0.55","size":"0"}},{{"price":"0.50","size":"10"}}],
+                "asks":[{{"price":"0.60","size":"5"}},{{"price":"0.52","size":"20"}}],
+                "timestamp":"{now_ms}"}}"#
+        );
+        handle_text_message(&text, &tx, &tick_tx, &cache).await;
+
+        assert_eq!(cache.best_bid("tok1").await, Some(Decimal::new(50, 2)));
+        assert_eq!(cache.best_ask("tok1").await, Some(Decimal::new(52, 2)));
+
+        // One-sided book leaves the cache alone
+        let one_sided = format!(
+            r#"{{"event_type":"book","asset_id":"tok2","bids":[{{"price":"0.40","size":"1"}}],"asks":[],"timestamp":"{now_ms}"}}"#
+        );
+        handle_text_message(&one_sided, &tx, &tick_tx, &cache).await;
+        assert_eq!(cache.best_bid("tok2").await, None);
+    }
+
+    fn tokens(ids: &[&str]) -> Vec<String> {
+        ids.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_diff_subscriptions() {
+        let subscribed: HashSet<String> = tokens(&["a", "b", "c"]).into_iter().collect();
+
+        let (added, removed) = diff_subscriptions(&subscribed, &tokens(&["c", "d", "a", "d", "e"]));
+        assert_eq!(added, tokens(&["d", "e"]));
+        assert_eq!(removed, tokens(&["b"]));
+
+        let (added, removed) = diff_subscriptions(&subscribed, &tokens(&["b", "a", "c"]));
+        assert!(added.is_empty() && removed.is_empty());
+    }
+
+    #[test]
+    fn test_update_messages_batched_by_operation() {
+        let added: Vec<String> = (0..SUBSCRIBE_BATCH_SIZE + 1).map(|i| format!("t{i}")).collect();
+        let msgs = build_update_messages(&added, &tokens(&["old"]));
+        assert_eq!(msgs.len(), 3);
+
+        let parsed: Vec<serde_json::Value> = msgs.iter().map(|m| serde_json::from_str(m).unwrap()).collect();
+        assert_eq!(parsed[0]["operation"], "subscribe");
+        assert_eq!(parsed[0]["assets_ids"].as_array().unwrap().len(), SUBSCRIBE_BATCH_SIZE);
+        assert_eq!(parsed[1]["operation"], "subscribe");
+        assert_eq!(parsed[1]["assets_ids"], serde_json::json!(["t100"]));
+        assert_eq!(parsed[2]["operation"], "unsubscribe");
+        assert_eq!(parsed[2]["assets_ids"], serde_json::json!(["old"]));
+    }
+
+    struct IgnoreText;
+
+    impl WsTextHandler for IgnoreText {
+        async fn on_text(&mut self, _text: &str) {}
+    }
+
+    #[tokio::test]
+    async fn test_silent_connection_is_resubscribed() {
+        // A server that takes the subscription and then never sends anything
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let ws_url = format!("ws://{}", listener.local_addr().unwrap());
+        let (sub_tx, mut sub_rx) = mpsc::channel::<String>(4);
+        tokio::spawn(async move {
+            loop {
+                let (stream, _) = listener.accept().await.unwrap();
+                let sub_tx = sub_tx.clone();
+                tokio::spawn(async move {
+                    let mut ws = tokio_tungstenite::accept_async(stream).await.unwrap();
+                    while let Some(Ok(msg)) = ws.next().await {
+                        if let Message::Text(text) = msg {
+                            let _ = sub_tx.send(text.to_string()).await;
+                        }
+                    }
+                });
+            }
+        });
+
+        let (_token_tx, token_rx) = watch::channel(vec!["tok1".to_string()]);
+        tokio::spawn(run_ws_connection(ws_url, token_rx, IgnoreText, Duration::from_millis(200)));
+
+        for _ in 0..2 {
+            let sub = tokio::time::timeout(Duration::from_secs(10), sub_rx.recv())
+                .await
+                .expect("subscribe message")
+                .unwrap();
+            assert!(sub.contains("tok1"));
+        }
+    }
+}
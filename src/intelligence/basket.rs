@@ -1,10 +1,20 @@
-use chrono::{Duration, Utc};
+use std::collections::HashMap;
+
+use chrono::{DateTime, Duration, Utc};
 use rust_decimal::Decimal;
 use sqlx::PgPool;
 use uuid::Uuid;
 
-use crate::db::basket_repo::{self, BasketTradeVote};
-use crate::models::{BasketCategory, WhaleBasket};
+use crate::db::basket_repo::{self, BasketHistoryTrade, BasketTradeVote, NET_STANCE_MIN_PCT};
+use crate::db::trade_repo;
+use crate::models::{BacktestConsensusEvent, BasketCategory, VoteWeighting, WhaleBasket};
+
+use super::correlation::{correlation_clusters, dedup_correlated_votes, independent_count};
+use super::scorer::resolved_profit;
+
+/// Consensus is only acted on while the market price is at least this far
+/// from 0 and 1.
+const CONSENSUS_MIN_SPREAD: Decimal = Decimal::from_parts(5, 0, 0, false, 2); // 0.05
 
 // ---------------------------------------------------------------------------
 // Admission
@@ -24,12 +34,22 @@ pub enum AdmissionResult {
 /// - Not classified as bot or market_maker
 /// - Average monthly trades < 100 (reject bots)
 /// - Reject insider pattern: very few trades (< 5) but high win rate and short history
+/// - Max drawdown (percent of peak equity) within `max_drawdown_limit_pct` (0 = off)
+/// - Skill score at least `min_skill_score` (0 = off; unscored whales pass)
+/// - Fewer than `max_insider_flags` suspicious-timing flags (0 = off)
+#[allow(clippy::too_many_arguments)]
 pub fn check_admission(
     win_rate: Decimal,
     classification: Option<&str>,
     months_active: i64,
     total_trades: i32,
     avg_monthly_trades: Decimal,
+    max_drawdown_pct: Decimal,
+    max_drawdown_limit_pct: Decimal,
+    skill_score: Option<Decimal>,
+    min_skill_score: Decimal,
+    insider_flags: i64,
+    max_insider_flags: i64,
 ) -> AdmissionResult {
     // Win rate must exceed 60%
     if win_rate < Decimal::new(60, 2) {
@@ -62,6 +82,31 @@ pub fn check_admission(
         );
     }
 
+    // Repeatedly well-timed entries suggest non-public information
+    if max_insider_flags > 0 && insider_flags >= max_insider_flags {
+        return AdmissionResult::Rejected(format!(
+            "suspected insider: {insider_flags} suspicious-timing flags"
+        ));
+    }
+
+    // A high win rate can hide a few large losses
+    if max_drawdown_limit_pct > Decimal::ZERO && max_drawdown_pct > max_drawdown_limit_pct {
+        return AdmissionResult::Rejected(format!(
+            "max drawdown {}% exceeds {}%",
+            max_drawdown_pct.round_dp(1),
+            max_drawdown_limit_pct
+        ));
+    }
+
+    // A good record over few or wildly varying trades may just be luck
+    if let Some(skill) = skill_score.filter(|s| min_skill_score > Decimal::ZERO && *s < min_skill_score) {
+        return AdmissionResult::Rejected(format!(
+            "skill score {} below {}",
+            skill.round_dp(2),
+            min_skill_score
+        ));
+    }
+
     AdmissionResult::Accepted
 }
 
@@ -76,6 +121,9 @@ pub struct ConsensusCheck {
     pub consensus_pct: Decimal,
     pub participating: i32,
     pub total: i32,
+    /// Mean win rate of the whales voting with the majority, unknown counting
+    /// as a coin flip; zero when there is no majority to speak of.
+    pub voter_win_rate: Decimal,
     pub reason: String,
 }
 
@@ -87,12 +135,17 @@ pub struct ConsensusCheck {
 /// 1. Same-direction vote ratio >= threshold (default 80%)
 /// 2. Market price > 5¢ away from 0 or 1 (min_spread)
 /// 3. At least 1 vote exists
+///
+/// With a weighting other than `Equal`, votes are scaled by `vote_weight` and
+/// rescaled to still sum to the number of voters, so the threshold keeps its
+/// meaning while one strong whale can outvote two marginal ones.
 pub fn evaluate_consensus(
     votes: &[BasketTradeVote],
     total_whales: i32,
     threshold: Decimal,
     market_price: Decimal,
     min_spread: Decimal,
+    weighting: VoteWeighting,
 ) -> ConsensusCheck {
     let no_consensus = |reason: &str| ConsensusCheck {
         reached: false,
@@ -100,6 +153,7 @@ pub fn evaluate_consensus(
         consensus_pct: Decimal::ZERO,
         participating: votes.len() as i32,
         total: total_whales,
+        voter_win_rate: Decimal::ZERO,
         reason: reason.to_string(),
     };
 
@@ -128,10 +182,26 @@ pub fn evaluate_consensus(
         return no_consensus("market price too close to resolution");
     }
 
-    // Count BUY vs SELL
-    let buy_count = votes.iter().filter(|v| v.side.to_uppercase() == "BUY").count() as i32;
-    let sell_count = votes.iter().filter(|v| v.side.to_uppercase() == "SELL").count() as i32;
+    // Weigh BUY vs SELL; if no voter carries any weight, fall back to equal votes
     let total_votes = votes.len() as i32;
+    let mut weights: Vec<Decimal> = votes.iter().map(|v| vote_weight(v, weighting)).collect();
+    let weight_sum: Decimal = weights.iter().copied().sum();
+    if weight_sum.is_zero() {
+        weights = vec![Decimal::ONE; votes.len()];
+    } else {
+        let scale = Decimal::from(total_votes) / weight_sum;
+        weights.iter_mut().for_each(|w| *w *= scale);
+    }
+    let side_weight = |side: &str| -> Decimal {
+        votes
+            .iter()
+            .zip(&weights)
+            .filter(|(v, _)| v.side.to_uppercase() == side)
+            .map(|(_, w)| *w)
+            .sum()
+    };
+    let buy_count = side_weight("BUY");
+    let sell_count = side_weight("SELL");
 
     let (majority_direction, majority_count) = if buy_count >= sell_count {
         ("BUY", buy_count)
@@ -139,22 +209,41 @@ pub fn evaluate_consensus(
         ("SELL", sell_count)
     };
 
+    let majority_win_rates: Vec<Decimal> = votes
+        .iter()
+        .filter(|v| v.side.to_uppercase() == majority_direction)
+        .map(|v| v.win_rate.unwrap_or(Decimal::new(5, 1)))
+        .collect();
+    let voter_win_rate = if majority_win_rates.is_empty() {
+        Decimal::ZERO
+    } else {
+        (majority_win_rates.iter().copied().sum::<Decimal>() / Decimal::from(majority_win_rates.len())).round_dp(4)
+    };
+
     let consensus_pct = if total_whales > 0 {
-        Decimal::from(majority_count) / Decimal::from(total_whales)
+        (majority_count / Decimal::from(total_whales)).round_dp(4)
     } else {
         Decimal::ZERO
     };
 
     if consensus_pct >= threshold {
+        let weighted = match weighting {
+            VoteWeighting::Equal => String::new(),
+            w => format!(" (weighted by {w})"),
+        };
         ConsensusCheck {
             reached: true,
             direction: majority_direction.to_string(),
             consensus_pct,
             participating: total_votes,
             total: total_whales,
+            voter_win_rate,
             reason: format!(
-                "consensus reached: {}/{} whales vote {}",
-                majority_count, total_whales, majority_direction
+                "consensus reached: {}/{} whales vote {}{}",
+                majority_count.round_dp(2).normalize(),
+                total_whales,
+                majority_direction,
+                weighted
             ),
         }
     } else {
@@ -164,6 +253,7 @@ pub fn evaluate_consensus(
             consensus_pct,
             participating: total_votes,
             total: total_whales,
+            voter_win_rate,
             reason: format!(
                 "consensus not reached: {:.1}% < {:.1}% threshold",
                 consensus_pct * Decimal::ONE_HUNDRED,
@@ -173,6 +263,180 @@ pub fn evaluate_consensus(
     }
 }
 
+/// How much a vote counts before rescaling. Whales without the metric yet
+/// count as much as a coin flip (win rate) or nothing (EV).
+pub fn vote_weight(vote: &BasketTradeVote, weighting: VoteWeighting) -> Decimal {
+    match weighting {
+        VoteWeighting::Equal => Decimal::ONE,
+        VoteWeighting::WinRate => vote.win_rate.unwrap_or(Decimal::new(5, 1)),
+        VoteWeighting::Ev => vote.expected_value.unwrap_or(Decimal::ZERO).max(Decimal::ZERO),
+        VoteWeighting::Notional => vote.notional.max(Decimal::ZERO),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Cross-basket conflicts
+// ---------------------------------------------------------------------------
+
+/// What to do when a basket reaches consensus opposite to another basket's
+/// consensus on the same market that is still cooling down. The earlier
+/// signal has already traded, so the resolver acts on the later one.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictMode {
+    /// Take both trades.
+    Allow,
+    /// Trade only the margin by which the new consensus beats the opposing one.
+    Net,
+    /// Trade only if the new consensus is stronger than the opposing one.
+    Stronger,
+    /// Trade neither side again while they conflict.
+    Block,
+}
+
+impl ConflictMode {
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "allow" | "off" => Some(ConflictMode::Allow),
+            "net" => Some(ConflictMode::Net),
+            "stronger" => Some(ConflictMode::Stronger),
+            "block" => Some(ConflictMode::Block),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConflictResolution {
+    /// Emit the signal with its size scaled by this multiplier.
+    Emit(Decimal),
+    /// Drop the signal.
+    Skip(String),
+}
+
+/// Resolve a new consensus of `consensus_pct` against the opposing consensus
+/// percentages of other baskets on the same market.
+pub fn resolve_conflict(mode: ConflictMode, consensus_pct: Decimal, opposing: &[Decimal]) -> ConflictResolution {
+    let Some(strongest) = opposing.iter().copied().max() else {
+        return ConflictResolution::Emit(Decimal::ONE);
+    };
+    match mode {
+        ConflictMode::Allow => ConflictResolution::Emit(Decimal::ONE),
+        ConflictMode::Block => ConflictResolution::Skip("opposite consensus in another basket".into()),
+        ConflictMode::Stronger if consensus_pct > strongest => ConflictResolution::Emit(Decimal::ONE),
+        ConflictMode::Net if consensus_pct > strongest => {
+            ConflictResolution::Emit(((consensus_pct - strongest) / consensus_pct).round_dp(4))
+        }
+        ConflictMode::Stronger | ConflictMode::Net => ConflictResolution::Skip(format!(
+            "opposite consensus in another basket is as strong ({strongest} >= {consensus_pct})"
+        )),
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Consensus replay
+// ---------------------------------------------------------------------------
+
+/// Each whale's net stance in `token_id` over `trades` (one market and
+/// window) as a vote, computed as `get_basket_trades_in_window` does in SQL.
+pub fn net_stance_votes(trades: &[BasketHistoryTrade], token_id: &str) -> Vec<BasketTradeVote> {
+    let mut by_whale: HashMap<Uuid, Vec<&BasketHistoryTrade>> = HashMap::new();
+    for t in trades.iter().filter(|t| t.token_id == token_id) {
+        by_whale.entry(t.whale_id).or_default().push(t);
+    }
+
+    by_whale
+        .into_iter()
+        .filter_map(|(whale_id, trades)| {
+            let signed = |t: &BasketHistoryTrade, v: Decimal| {
+                if t.side.eq_ignore_ascii_case("BUY") {
+                    v
+                } else {
+                    -v
+                }
+            };
+            let net_size: Decimal = trades.iter().map(|t| signed(t, t.size)).sum();
+            let traded: Decimal = trades.iter().map(|t| t.size).sum();
+            if net_size.abs() <= NET_STANCE_MIN_PCT * traded {
+                return None;
+            }
+            let latest = trades.iter().max_by_key(|t| t.traded_at)?;
+            Some(BasketTradeVote {
+                whale_id,
+                side: if net_size > Decimal::ZERO { "BUY" } else { "SELL" }.to_string(),
+                traded_at: latest.traded_at,
+                notional: trades.iter().map(|t| signed(t, t.notional)).sum::<Decimal>().abs(),
+                win_rate: latest.win_rate,
+                expected_value: latest.expected_value,
+            })
+        })
+        .collect()
+}
+
+/// Replay a basket's consensus over its members' trade history, ordered by
+/// market then time as `get_basket_trade_history` returns it. After every
+/// trade from `from` on, the basket's votes over the preceding window are
+/// evaluated at that trade's price; a reached consensus fires unless the
+/// market fired within the last `cooldown_hours`, as live recording suppresses
+/// it. Correlated whales are not merged.
+pub fn replay_consensus(
+    trades: &[BasketHistoryTrade],
+    from: DateTime<Utc>,
+    basket: &WhaleBasket,
+    total_whales: i32,
+    cooldown_hours: i32,
+) -> Vec<BacktestConsensusEvent> {
+    let window = Duration::hours(basket.time_window_hours as i64);
+    let cooldown = Duration::hours(cooldown_hours as i64);
+    let mut events: Vec<BacktestConsensusEvent> = Vec::new();
+
+    for market in trades.chunk_by(|a, b| a.market_key == b.market_key) {
+        let first_event = events.len();
+        for (i, trade) in market.iter().enumerate() {
+            if trade.traded_at < from {
+                continue;
+            }
+            let since = trade.traded_at - window;
+            let start = market[..i].partition_point(|t| t.traded_at < since);
+            let votes = net_stance_votes(&market[start..=i], &trade.token_id);
+            let check = evaluate_consensus(
+                &votes,
+                total_whales,
+                basket.consensus_threshold,
+                trade.price,
+                CONSENSUS_MIN_SPREAD,
+                basket.weighting(),
+            );
+            if !check.reached {
+                continue;
+            }
+            let cooling_down = events[first_event..]
+                .iter()
+                .any(|e| e.fired_at + cooldown > trade.traded_at);
+            if cooling_down {
+                continue;
+            }
+
+            let return_per_usdc = trade
+                .outcome
+                .as_deref()
+                .and_then(|o| resolved_profit(&check.direction, trade.price, Decimal::ONE, o));
+            events.push(BacktestConsensusEvent {
+                market_id: trade.market_key.clone(),
+                direction: check.direction,
+                consensus_pct: check.consensus_pct,
+                participating: check.participating,
+                total_whales: check.total,
+                fired_at: trade.traded_at,
+                price: trade.price,
+                outcome: trade.outcome.clone(),
+                return_per_usdc: return_per_usdc.map(|r| r.round_dp(4)),
+            });
+        }
+    }
+
+    events
+}
+
 // ---------------------------------------------------------------------------
 // Market category inference
 // ---------------------------------------------------------------------------
@@ -214,7 +478,8 @@ pub fn infer_market_category(question: &str) -> Option<BasketCategory> {
 // ---------------------------------------------------------------------------
 
 /// Automatically add a whale to active baskets that match the given category
-/// and have room (count < max_wallets). Returns names of baskets assigned to.
+/// and have room (count < max_wallets). Returns names of the baskets it
+/// newly joined.
 pub async fn auto_assign_to_baskets(
     pool: &PgPool,
     whale_id: Uuid,
@@ -239,8 +504,11 @@ pub async fn auto_assign_to_baskets(
             continue;
         }
 
-        basket_repo::add_whale_to_basket(pool, basket.id, whale_id).await?;
-        assigned.push(basket.name.clone());
+        if basket_repo::add_whale_to_basket(pool, basket.id, whale_id).await? {
+            let reason = format!("auto-assigned: admitted and trades {category}");
+            basket_repo::record_membership_event(pool, basket.id, whale_id, "added", "auto_assign", &reason).await?;
+            assigned.push(basket.name.clone());
+        }
     }
 
     Ok(assigned)
@@ -250,28 +518,65 @@ pub async fn auto_assign_to_baskets(
 // Async pipeline — ties DB queries to pure evaluation
 // ---------------------------------------------------------------------------
 
-/// Check basket consensus for a specific market, using DB queries.
+/// Check basket consensus on one outcome token of a market, using DB queries.
+///
+/// Whales whose trade overlap over the last `correlation_days` reaches
+/// `correlation_threshold` count as one voter, in the votes and the basket
+/// size alike (0 = every whale votes independently).
 pub async fn check_basket_consensus(
     pool: &PgPool,
     basket: &WhaleBasket,
     market_id: &str,
+    token_id: &str,
     market_price: Decimal,
+    correlation_threshold: Decimal,
+    correlation_days: i64,
 ) -> anyhow::Result<ConsensusCheck> {
     let since = Utc::now() - Duration::hours(basket.time_window_hours as i64);
 
-    let votes =
-        basket_repo::get_basket_trades_in_window(pool, basket.id, market_id, since).await?;
+    let mut votes =
+        basket_repo::get_basket_trades_in_window(pool, basket.id, market_id, token_id, since).await?;
 
-    let total_whales = basket_repo::count_basket_whales(pool, basket.id).await? as i32;
+    let mut total_whales = basket_repo::count_basket_whales(pool, basket.id).await? as i32;
 
-    let min_spread = Decimal::new(5, 2); // 0.05 = 5¢
+    if !correlation_threshold.is_zero() {
+        let members: Vec<Uuid> = basket_repo::get_basket_whales(pool, basket.id)
+            .await?
+            .iter()
+            .map(|w| w.id)
+            .collect();
+        let pairs = trade_repo::get_whale_correlations(
+            pool,
+            Utc::now() - Duration::days(correlation_days),
+            Some(&members),
+            correlation_threshold,
+        )
+        .await?;
+        let clusters = correlation_clusters(&members, &pairs, correlation_threshold);
+
+        let deduped = dedup_correlated_votes(&votes, &clusters);
+        let independent = independent_count(&clusters) as i32;
+        if deduped.len() < votes.len() || independent < total_whales {
+            tracing::debug!(
+                basket = %basket.name,
+                votes = votes.len(),
+                independent_votes = deduped.len(),
+                whales = total_whales,
+                independent_whales = independent,
+                "Correlated whales merged for consensus"
+            );
+        }
+        votes = deduped;
+        total_whales = independent;
+    }
 
     let check = evaluate_consensus(
         &votes,
         total_whales,
         basket.consensus_threshold,
         market_price,
-        min_spread,
+        CONSENSUS_MIN_SPREAD,
+        basket.weighting(),
     );
 
     Ok(check)
@@ -292,6 +597,9 @@ mod tests {
             whale_id,
             side: side.to_string(),
             traded_at: Utc::now(),
+            notional: Decimal::from(1000),
+            win_rate: None,
+            expected_value: None,
         }
     }
 
@@ -305,6 +613,12 @@ mod tests {
             6,   // 6 months
             50,  // 50 trades
             Decimal::from(10),
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert_eq!(result, AdmissionResult::Accepted);
     }
@@ -317,6 +631,12 @@ mod tests {
             6,
             50,
             Decimal::from(10),
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert!(matches!(result, AdmissionResult::Rejected(ref r) if r.contains("win rate")));
     }
@@ -329,6 +649,12 @@ mod tests {
             2, // only 2 months
             50,
             Decimal::from(10),
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert!(matches!(result, AdmissionResult::Rejected(ref r) if r.contains("4 months")));
     }
@@ -340,7 +666,13 @@ mod tests {
             Some("informed"),
             6,
             500,
-            Decimal::from(150), // 150 trades/month
+            Decimal::from(150), // 150 trades/month,
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert!(
             matches!(result, AdmissionResult::Rejected(ref r) if r.contains("bot pattern"))
@@ -355,6 +687,12 @@ mod tests {
             6,
             50,
             Decimal::from(10),
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert!(matches!(result, AdmissionResult::Rejected(ref r) if r.contains("bot")));
 
@@ -364,6 +702,12 @@ mod tests {
             6,
             50,
             Decimal::from(10),
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert!(
             matches!(result2, AdmissionResult::Rejected(ref r) if r.contains("market_maker"))
@@ -378,10 +722,102 @@ mod tests {
             5, // meets 4-month minimum, but still short
             3, // very few trades
             Decimal::from(1),
+            Decimal::ZERO,
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
         );
         assert!(matches!(result, AdmissionResult::Rejected(ref r) if r.contains("insider")));
     }
 
+    #[test]
+    fn test_admission_insider_flags() {
+        let admit = |flags: i64, max: i64| {
+            check_admission(
+                Decimal::new(70, 2),
+                Some("informed"),
+                6,
+                50,
+                Decimal::from(10),
+                Decimal::ZERO,
+                Decimal::from(50),
+                None,
+                Decimal::ZERO,
+                flags,
+                max,
+            )
+        };
+        assert!(matches!(
+            admit(2, 2),
+            AdmissionResult::Rejected(ref r) if r.contains("suspicious-timing")
+        ));
+        assert_eq!(admit(1, 2), AdmissionResult::Accepted);
+        assert_eq!(admit(5, 0), AdmissionResult::Accepted);
+    }
+
+    #[test]
+    fn test_admission_max_drawdown() {
+        let result = check_admission(
+            Decimal::new(80, 2), // high win rate
+            Some("informed"),
+            6,
+            50,
+            Decimal::from(10),
+            Decimal::from(70), // gave back 70% of peak profits
+            Decimal::from(50),
+            None,
+            Decimal::ZERO,
+            0,
+            0,
+        );
+        assert!(matches!(result, AdmissionResult::Rejected(ref r) if r.contains("drawdown")));
+
+        // Limit disabled
+        let result = check_admission(
+            Decimal::new(80, 2),
+            Some("informed"),
+            6,
+            50,
+            Decimal::from(10),
+            Decimal::from(70),
+            Decimal::ZERO,
+            None,
+            Decimal::ZERO,
+            0,
+            0,
+        );
+        assert_eq!(result, AdmissionResult::Accepted);
+    }
+
+    #[test]
+    fn test_admission_skill_score() {
+        let admit = |skill: Option<Decimal>, min: Decimal| {
+            check_admission(
+                Decimal::new(70, 2),
+                Some("informed"),
+                6,
+                50,
+                Decimal::from(10),
+                Decimal::ZERO,
+                Decimal::from(50),
+                skill,
+                min,
+                0,
+                0,
+            )
+        };
+        let min = Decimal::new(90, 2);
+        assert!(matches!(
+            admit(Some(Decimal::new(75, 2)), min),
+            AdmissionResult::Rejected(ref r) if r.contains("skill score")
+        ));
+        assert_eq!(admit(Some(Decimal::new(95, 2)), min), AdmissionResult::Accepted);
+        assert_eq!(admit(None, min), AdmissionResult::Accepted);
+        assert_eq!(admit(Some(Decimal::new(75, 2)), Decimal::ZERO), AdmissionResult::Accepted);
+    }
+
     // --- Consensus tests ---
 
     #[test]
@@ -396,6 +832,7 @@ mod tests {
             Decimal::new(80, 2),
             Decimal::new(50, 2), // 0.50 price
             Decimal::new(5, 2),  // 0.05 min spread
+            VoteWeighting::Equal,
         );
 
         assert!(check.reached);
@@ -419,6 +856,7 @@ mod tests {
             Decimal::new(80, 2),
             Decimal::new(50, 2),
             Decimal::new(5, 2),
+            VoteWeighting::Equal,
         );
 
         assert!(!check.reached);
@@ -441,6 +879,7 @@ mod tests {
             Decimal::new(80, 2), // threshold = 0.80
             Decimal::new(50, 2),
             Decimal::new(5, 2),
+            VoteWeighting::Equal,
         );
 
         assert!(check.reached);
@@ -460,6 +899,7 @@ mod tests {
             Decimal::new(80, 2),
             Decimal::new(97, 2), // 0.97
             Decimal::new(5, 2),
+            VoteWeighting::Equal,
         );
 
         assert!(!check.reached);
@@ -474,6 +914,7 @@ mod tests {
             Decimal::new(80, 2),
             Decimal::new(50, 2),
             Decimal::new(5, 2),
+            VoteWeighting::Equal,
         );
 
         assert!(!check.reached);
@@ -530,6 +971,153 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_resolve_conflict() {
+        let pct = |n| Decimal::new(n, 2);
+        for mode in [ConflictMode::Allow, ConflictMode::Net, ConflictMode::Stronger, ConflictMode::Block] {
+            assert_eq!(resolve_conflict(mode, pct(80), &[]), ConflictResolution::Emit(Decimal::ONE));
+        }
+
+        let opposing = [pct(60), pct(70)];
+        assert_eq!(
+            resolve_conflict(ConflictMode::Allow, pct(80), &opposing),
+            ConflictResolution::Emit(Decimal::ONE)
+        );
+        assert_eq!(
+            resolve_conflict(ConflictMode::Stronger, pct(80), &opposing),
+            ConflictResolution::Emit(Decimal::ONE)
+        );
+        // Netted against the strongest opposing consensus: (0.80 - 0.70) / 0.80
+        assert_eq!(
+            resolve_conflict(ConflictMode::Net, pct(80), &opposing),
+            ConflictResolution::Emit(Decimal::new(125, 3))
+        );
+        assert!(matches!(resolve_conflict(ConflictMode::Block, pct(80), &opposing), ConflictResolution::Skip(_)));
+        assert!(matches!(resolve_conflict(ConflictMode::Stronger, pct(70), &opposing), ConflictResolution::Skip(_)));
+        assert!(matches!(resolve_conflict(ConflictMode::Net, pct(65), &opposing), ConflictResolution::Skip(_)));
+        assert_eq!(ConflictMode::parse("Stronger"), Some(ConflictMode::Stronger));
+        assert_eq!(ConflictMode::parse("both"), None);
+    }
+
+    #[test]
+    fn test_replay_consensus() {
+        let now = Utc::now();
+        let basket = WhaleBasket {
+            id: Uuid::new_v4(),
+            name: "replay".into(),
+            category: "politics".into(),
+            consensus_threshold: Decimal::new(60, 2),
+            time_window_hours: 48,
+            min_wallets: 1,
+            max_wallets: 10,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            vote_weighting: "equal".into(),
+        };
+        let (w1, w2, w3) = (Uuid::new_v4(), Uuid::new_v4(), Uuid::new_v4());
+        let trade = |whale_id, market: &str, side: &str, hours_ago: i64, outcome: Option<&str>| BasketHistoryTrade {
+            whale_id,
+            market_key: market.into(),
+            token_id: format!("{market}-yes"),
+            side: side.into(),
+            size: Decimal::from(100),
+            price: Decimal::new(40, 2),
+            notional: Decimal::from(40),
+            traded_at: now - Duration::hours(hours_ago),
+            win_rate: None,
+            expected_value: None,
+            outcome: outcome.map(Into::into),
+        };
+        let from = now - Duration::hours(20);
+        let trades = vec![
+            // w1's vote predates the range but still counts at its start
+            trade(w1, "m1", "BUY", 30, Some("resolved_yes")),
+            trade(w2, "m1", "BUY", 10, Some("resolved_yes")),
+            // Within the cooldown: suppressed
+            trade(w3, "m1", "BUY", 5, Some("resolved_yes")),
+            // w1 is flat in m2, leaving a single voter
+            trade(w1, "m2", "BUY", 8, None),
+            trade(w1, "m2", "SELL", 7, None),
+            trade(w2, "m2", "BUY", 6, None),
+        ];
+
+        let events = replay_consensus(&trades, from, &basket, 3, 48);
+        assert_eq!(events.len(), 1);
+        let event = &events[0];
+        assert_eq!(event.market_id, "m1");
+        assert_eq!(event.direction, "BUY");
+        assert_eq!(event.participating, 2);
+        assert_eq!(event.fired_at, now - Duration::hours(10));
+        // Bought at 0.40 and resolved yes: 1.5 per USDC
+        assert_eq!(event.return_per_usdc, Some(Decimal::new(15, 1)));
+
+        // Without w1's earlier vote, w2 alone can't reach consensus at 10h ago
+        let events = replay_consensus(&trades[1..], from, &basket, 3, 48);
+        assert_eq!(events[0].fired_at, now - Duration::hours(5));
+    }
+
+    #[test]
+    fn test_net_stance_votes_per_token() {
+        let now = Utc::now();
+        let whale = Uuid::new_v4();
+        let trade = |token: &str, side: &str| BasketHistoryTrade {
+            whale_id: whale,
+            market_key: "m1".into(),
+            token_id: token.into(),
+            side: side.into(),
+            size: Decimal::from(100),
+            price: Decimal::new(40, 2),
+            notional: Decimal::from(40),
+            traded_at: now,
+            win_rate: None,
+            expected_value: None,
+            outcome: None,
+        };
+        // Selling NO shares doesn't cancel a YES buy
+        let trades = vec![trade("yes", "BUY"), trade("no", "SELL")];
+
+        let votes = net_stance_votes(&trades, "yes");
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].side, "BUY");
+        let votes = net_stance_votes(&trades, "no");
+        assert_eq!(votes.len(), 1);
+        assert_eq!(votes[0].side, "SELL");
+    }
+
+    #[test]
+    fn test_weighted_consensus_favours_proven_whale() {
+        let vote = |side: &str, win_rate: i64, notional: i64| BasketTradeVote {
+            win_rate: Some(Decimal::new(win_rate, 2)),
+            notional: Decimal::from(notional),
+            ..make_vote(Uuid::new_v4(), side)
+        };
+        // One large proven BUY against two marginal SELLs
+        let votes = vec![vote("BUY", 90, 50_000), vote("SELL", 52, 2_000), vote("SELL", 51, 3_000)];
+        let check = |weighting| {
+            evaluate_consensus(&votes, 3, Decimal::new(70, 2), Decimal::new(50, 2), Decimal::new(5, 2), weighting)
+        };
+
+        let equal = check(VoteWeighting::Equal);
+        assert_eq!(equal.direction, "SELL");
+        assert!(!equal.reached);
+        // Quality of the majority's voters: mean of 0.52 and 0.51
+        assert_eq!(equal.voter_win_rate, Decimal::new(515, 3));
+
+        // 50k of 55k notional: 3 × 50/55 ≈ 2.73 of 3 votes
+        let notional = check(VoteWeighting::Notional);
+        assert_eq!(notional.direction, "BUY");
+        assert!(notional.reached);
+        assert_eq!(notional.consensus_pct, Decimal::new(9091, 4));
+        assert_eq!(notional.voter_win_rate, Decimal::new(90, 2));
+
+        // Win rate tilts less: 0.90 vs 1.03 still loses
+        assert_eq!(check(VoteWeighting::WinRate).direction, "SELL");
+
+        // No whale has an EV yet: equal votes
+        assert_eq!(check(VoteWeighting::Ev).consensus_pct, equal.consensus_pct);
+    }
+
     #[test]
     fn test_consensus_sell_direction() {
         let votes: Vec<BasketTradeVote> = (0..5)
@@ -542,6 +1130,7 @@ mod tests {
             Decimal::new(80, 2),
             Decimal::new(50, 2),
             Decimal::new(5, 2),
+            VoteWeighting::Equal,
         );
 
         assert!(check.reached);
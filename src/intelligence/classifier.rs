@@ -1,10 +1,13 @@
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::fmt;
+use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
+use sqlx::PgPool;
 
+use crate::db::{trade_repo, whale_repo};
 use crate::models::WhaleTrade;
 
 /// Wallet classification categories.
@@ -26,6 +29,15 @@ impl Classification {
             Classification::Bot => "bot",
         }
     }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "informed" => Some(Classification::Informed),
+            "market_maker" => Some(Classification::MarketMaker),
+            "bot" => Some(Classification::Bot),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Classification {
@@ -34,6 +46,19 @@ impl fmt::Display for Classification {
     }
 }
 
+/// Why a wallet got its classification: the features measured on its trades
+/// and the rules that decided it, in the order they were applied.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ClassificationExplanation {
+    pub classification: Classification,
+    pub classifier: &'static str,
+    pub trade_count: usize,
+    pub features: WalletFeatures,
+    /// Model probability of the chosen class (model classifiers only).
+    pub confidence: Option<f64>,
+    pub rules: Vec<String>,
+}
+
 /// Classify a wallet based on its trade history.
 ///
 /// Rules:
@@ -41,37 +66,62 @@ impl fmt::Display for Classification {
 /// - **Bot**: >100 trades/month on average.
 /// - **Informed**: everything else.
 pub fn classify_wallet(trades: &[WhaleTrade]) -> Classification {
+    explain_wallet(trades).classification
+}
+
+/// `classify_wallet` with the rules it checked and what each found.
+pub fn explain_wallet(trades: &[WhaleTrade]) -> ClassificationExplanation {
+    let features = WalletFeatures::from_trades(trades);
+    let explanation = |classification, rules| ClassificationExplanation {
+        classification,
+        classifier: "heuristic",
+        trade_count: trades.len(),
+        features,
+        confidence: None,
+        rules,
+    };
     if trades.is_empty() {
-        return Classification::Informed;
+        return explanation(Classification::Informed, vec!["informed: no trades yet".into()]);
     }
 
     // Check for market-maker pattern: dual-side positions in same market
+    let dual_pct = features.dual_side_ratio * 100.0;
     if is_market_maker(trades) {
-        return Classification::MarketMaker;
+        let rule = format!("market_maker: {dual_pct:.0}% of markets traded on both sides (> 50%)");
+        return explanation(Classification::MarketMaker, vec![rule]);
     }
+    let mut rules = vec![format!("not market_maker: {dual_pct:.0}% of markets traded on both sides (<= 50%)")];
 
     // Check for bot pattern: high-frequency trading
+    let per_month = features.trades_per_month;
     if is_bot(trades) {
-        return Classification::Bot;
+        rules.push(format!("bot: {per_month:.1} trades/month (> 100)"));
+        return explanation(Classification::Bot, rules);
+    }
+    if trades.len() < 10 {
+        rules.push(format!("not bot: only {} trades (10 needed)", trades.len()));
+    } else {
+        rules.push(format!("not bot: {per_month:.1} trades/month (<= 100)"));
     }
 
-    Classification::Informed
+    rules.push("informed: no exclusion rule matched".into());
+    explanation(Classification::Informed, rules)
 }
 
 /// Detect market-maker behavior: same wallet has both BUY and SELL
 /// in the same market within a short period.
 fn is_market_maker(trades: &[WhaleTrade]) -> bool {
-    // Group by market_id, track which sides appear
+    // Group by market, track which sides appear
     let mut market_buy: HashSet<String> = HashSet::new();
     let mut market_sell: HashSet<String> = HashSet::new();
 
     for trade in trades {
         match trade.side.to_uppercase().as_str() {
             "BUY" => {
-                market_buy.insert(trade.market_id.clone());
+                market_buy.insert(trade.market_key().to_string());
             }
             "SELL" => {
-                market_sell.insert(trade.market_id.clone());
+                market_sell.insert(trade.market_key().to_string());
             }
             _ => {}
         }
@@ -121,6 +171,331 @@ fn is_bot(trades: &[WhaleTrade]) -> bool {
     trades_per_month > Decimal::from(100)
 }
 
+// ---------------------------------------------------------------------------
+// Pluggable classifiers
+// ---------------------------------------------------------------------------
+
+/// Classifies a wallet from its trade history. The pipeline holds one behind
+/// an `Arc`, chosen at startup by `WALLET_CLASSIFIER`.
+pub trait WalletClassifier: fmt::Debug + Send + Sync {
+    /// Classify and record why, for the classification audit.
+    fn explain(&self, trades: &[WhaleTrade]) -> ClassificationExplanation;
+
+    fn classify(&self, trades: &[WhaleTrade]) -> Classification {
+        self.explain(trades).classification
+    }
+
+    fn name(&self) -> &'static str;
+}
+
+/// The rule-based classifier (`classify_wallet`).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct HeuristicClassifier;
+
+impl WalletClassifier for HeuristicClassifier {
+    fn explain(&self, trades: &[WhaleTrade]) -> ClassificationExplanation {
+        explain_wallet(trades)
+    }
+
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+}
+
+/// Behavioural features a model classifies on.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct WalletFeatures {
+    pub trades_per_month: f64,
+    /// Share of markets traded on both sides.
+    pub dual_side_ratio: f64,
+    /// Mean hours from first BUY to first later SELL, over markets with both.
+    pub avg_hold_hours: f64,
+    /// Coefficient of variation of trade notional.
+    pub notional_cv: f64,
+}
+
+const FEATURE_COUNT: usize = 4;
+
+impl WalletFeatures {
+    pub fn from_trades(trades: &[WhaleTrade]) -> Self {
+        let count = trades.len() as f64;
+        let oldest = trades.iter().map(|t| t.traded_at).min();
+        let newest = trades.iter().map(|t| t.traded_at).max();
+        let span_days = match (oldest, newest) {
+            (Some(o), Some(n)) => (n - o).num_days().max(1) as f64,
+            _ => 1.0,
+        };
+        let trades_per_month = count * 30.0 / span_days;
+
+        let mut first_buy: HashMap<&str, DateTime<Utc>> = HashMap::new();
+        let mut sells: HashMap<&str, Vec<DateTime<Utc>>> = HashMap::new();
+        for trade in trades {
+            match trade.side.to_uppercase().as_str() {
+                "BUY" => {
+                    let at = first_buy.entry(trade.market_key()).or_insert(trade.traded_at);
+                    *at = (*at).min(trade.traded_at);
+                }
+                "SELL" => sells.entry(trade.market_key()).or_default().push(trade.traded_at),
+                _ => {}
+            }
+        }
+        let markets = first_buy.keys().chain(sells.keys()).collect::<HashSet<_>>().len();
+        let dual = first_buy.keys().filter(|m| sells.contains_key(*m)).count();
+        let dual_side_ratio = if markets == 0 { 0.0 } else { dual as f64 / markets as f64 };
+
+        let holds: Vec<f64> = first_buy
+            .iter()
+            .filter_map(|(market, bought)| {
+                let sold = sells.get(market)?.iter().filter(|s| *s >= bought).min()?;
+                Some((*sold - *bought).num_minutes() as f64 / 60.0)
+            })
+            .collect();
+        let avg_hold_hours = if holds.is_empty() {
+            0.0
+        } else {
+            holds.iter().sum::<f64>() / holds.len() as f64
+        };
+
+        let notionals: Vec<f64> = trades
+            .iter()
+            .map(|t| t.notional.to_string().parse().unwrap_or(0.0))
+            .collect();
+        let mean = if count > 0.0 { notionals.iter().sum::<f64>() / count } else { 0.0 };
+        let notional_cv = if mean > 0.0 {
+            let var = notionals.iter().map(|n| (n - mean).powi(2)).sum::<f64>() / count;
+            var.sqrt() / mean
+        } else {
+            0.0
+        };
+
+        Self { trades_per_month, dual_side_ratio, avg_hold_hours, notional_cv }
+    }
+
+    /// Model inputs; counts and durations are log-scaled.
+    fn vector(&self) -> [f64; FEATURE_COUNT] {
+        [
+            self.trades_per_month.ln_1p(),
+            self.dual_side_ratio,
+            self.avg_hold_hours.ln_1p(),
+            self.notional_cv,
+        ]
+    }
+}
+
+const CLASSES: [Classification; 3] = [
+    Classification::Informed,
+    Classification::MarketMaker,
+    Classification::Bot,
+];
+
+/// Multinomial logistic regression over `WalletFeatures`. Wallets with too
+/// few trades, or where no class reaches `min_confidence`, fall back to the
+/// heuristic rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LogisticClassifier {
+    /// One row of feature weights plus a trailing bias per class, in `CLASSES` order.
+    weights: Vec<[f64; FEATURE_COUNT + 1]>,
+    means: [f64; FEATURE_COUNT],
+    scales: [f64; FEATURE_COUNT],
+    pub min_confidence: f64,
+    pub min_trades: usize,
+}
+
+impl LogisticClassifier {
+    /// Fit by batch gradient descent on standardised features with light L2
+    /// regularisation. Returns None without samples.
+    pub fn train(samples: &[(WalletFeatures, Classification)], epochs: usize, learning_rate: f64) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+        let n = samples.len() as f64;
+        let inputs: Vec<[f64; FEATURE_COUNT]> = samples.iter().map(|(f, _)| f.vector()).collect();
+
+        let mut means = [0.0; FEATURE_COUNT];
+        let mut scales = [1.0; FEATURE_COUNT];
+        for j in 0..FEATURE_COUNT {
+            means[j] = inputs.iter().map(|x| x[j]).sum::<f64>() / n;
+            let var = inputs.iter().map(|x| (x[j] - means[j]).powi(2)).sum::<f64>() / n;
+            if var > 1e-12 {
+                scales[j] = var.sqrt();
+            }
+        }
+
+        let mut model = Self {
+            weights: vec![[0.0; FEATURE_COUNT + 1]; CLASSES.len()],
+            means,
+            scales,
+            min_confidence: 0.0,
+            min_trades: 0,
+        };
+        let standardised: Vec<[f64; FEATURE_COUNT]> = inputs.iter().map(|x| model.standardise(x)).collect();
+        const L2: f64 = 1e-3;
+
+        for _ in 0..epochs {
+            let mut grad = vec![[0.0; FEATURE_COUNT + 1]; CLASSES.len()];
+            for (x, (_, label)) in standardised.iter().zip(samples) {
+                let probs = model.probabilities(x);
+                for (k, class) in CLASSES.iter().enumerate() {
+                    let err = probs[k] - if class == label { 1.0 } else { 0.0 };
+                    for j in 0..FEATURE_COUNT {
+                        grad[k][j] += err * x[j];
+                    }
+                    grad[k][FEATURE_COUNT] += err;
+                }
+            }
+            for (w, g) in model.weights.iter_mut().zip(&grad) {
+                for j in 0..=FEATURE_COUNT {
+                    let reg = if j < FEATURE_COUNT { L2 * w[j] } else { 0.0 };
+                    w[j] -= learning_rate * (g[j] / n + reg);
+                }
+            }
+        }
+
+        Some(model)
+    }
+
+    pub fn with_fallback(mut self, min_trades: usize, min_confidence: f64) -> Self {
+        self.min_trades = min_trades;
+        self.min_confidence = min_confidence;
+        self
+    }
+
+    fn standardise(&self, x: &[f64; FEATURE_COUNT]) -> [f64; FEATURE_COUNT] {
+        let mut out = [0.0; FEATURE_COUNT];
+        for j in 0..FEATURE_COUNT {
+            out[j] = (x[j] - self.means[j]) / self.scales[j];
+        }
+        out
+    }
+
+    fn probabilities(&self, x: &[f64; FEATURE_COUNT]) -> Vec<f64> {
+        let logits: Vec<f64> = self
+            .weights
+            .iter()
+            .map(|w| w[FEATURE_COUNT] + (0..FEATURE_COUNT).map(|j| w[j] * x[j]).sum::<f64>())
+            .collect();
+        let max = logits.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+        let exps: Vec<f64> = logits.iter().map(|l| (l - max).exp()).collect();
+        let total: f64 = exps.iter().sum();
+        exps.iter().map(|e| e / total).collect()
+    }
+
+    /// Most likely class and its probability.
+    pub fn predict(&self, features: &WalletFeatures) -> (Classification, f64) {
+        let probs = self.probabilities(&self.standardise(&features.vector()));
+        let (k, p) = probs
+            .iter()
+            .enumerate()
+            .max_by(|a, b| a.1.total_cmp(b.1))
+            .map(|(k, p)| (k, *p))
+            .unwrap_or((0, 0.0));
+        (CLASSES[k], p)
+    }
+}
+
+impl WalletClassifier for LogisticClassifier {
+    fn explain(&self, trades: &[WhaleTrade]) -> ClassificationExplanation {
+        let fallback = |reason: String, confidence: Option<f64>| {
+            let mut explanation = explain_wallet(trades);
+            explanation.classifier = self.name();
+            explanation.confidence = confidence;
+            explanation.rules.insert(0, reason);
+            explanation
+        };
+        if trades.is_empty() || trades.len() < self.min_trades {
+            let reason = format!("heuristic fallback: {} trades ({} needed)", trades.len(), self.min_trades);
+            return fallback(reason, None);
+        }
+
+        let features = WalletFeatures::from_trades(trades);
+        let (class, confidence) = self.predict(&features);
+        if confidence < self.min_confidence {
+            let reason = format!(
+                "heuristic fallback: model p({class})={confidence:.2} (< {:.2})",
+                self.min_confidence
+            );
+            return fallback(reason, Some(confidence));
+        }
+
+        ClassificationExplanation {
+            classification: class,
+            classifier: self.name(),
+            trade_count: trades.len(),
+            features,
+            confidence: Some(confidence),
+            rules: vec![format!("{class}: model p={confidence:.2} (>= {:.2})", self.min_confidence)],
+        }
+    }
+
+    fn name(&self) -> &'static str {
+        "logistic"
+    }
+}
+
+/// Settings for `build_wallet_classifier`.
+#[derive(Debug, Clone)]
+pub struct ClassifierConfig {
+    /// `heuristic` or `logistic`.
+    pub kind: String,
+    /// Labeled wallets needed to train; with fewer the heuristics are used.
+    pub min_labels: usize,
+    pub min_confidence: f64,
+    pub min_trades: usize,
+}
+
+/// Build the configured classifier. The logistic model is trained on
+/// operator classification overrides, which serve as labels; it falls back
+/// to the heuristic classifier when there are too few of them.
+pub async fn build_wallet_classifier(pool: &PgPool, config: &ClassifierConfig) -> Arc<dyn WalletClassifier> {
+    match config.kind.trim().to_lowercase().as_str() {
+        "logistic" => {}
+        "heuristic" | "" => return Arc::new(HeuristicClassifier),
+        other => {
+            tracing::warn!(classifier = other, "Unknown WALLET_CLASSIFIER — using heuristic");
+            return Arc::new(HeuristicClassifier);
+        }
+    }
+
+    let samples = match labeled_samples(pool).await {
+        Ok(s) => s,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load classifier labels — using heuristic");
+            return Arc::new(HeuristicClassifier);
+        }
+    };
+    if samples.len() < config.min_labels {
+        tracing::warn!(
+            labels = samples.len(),
+            required = config.min_labels,
+            "Too few labeled wallets to train classifier — using heuristic"
+        );
+        return Arc::new(HeuristicClassifier);
+    }
+
+    match LogisticClassifier::train(&samples, 500, 0.5) {
+        Some(model) => {
+            tracing::info!(labels = samples.len(), "Logistic wallet classifier trained");
+            Arc::new(model.with_fallback(config.min_trades, config.min_confidence))
+        }
+        None => Arc::new(HeuristicClassifier),
+    }
+}
+
+async fn labeled_samples(pool: &PgPool) -> anyhow::Result<Vec<(WalletFeatures, Classification)>> {
+    let mut samples = Vec::new();
+    for whale in whale_repo::get_labeled_whales(pool).await? {
+        let Some(label) = whale.classification_override.as_deref().and_then(Classification::parse) else {
+            continue;
+        };
+        let trades = trade_repo::get_trades_by_whale(pool, whale.id).await?;
+        if trades.is_empty() {
+            continue;
+        }
+        samples.push((WalletFeatures::from_trades(&trades), label));
+    }
+    Ok(samples)
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -143,8 +518,10 @@ mod tests {
             price: Decimal::new(50, 2),
             notional: Decimal::from(50),
             tx_hash: None,
+            fill_index: None,
             traded_at: Utc::now() - Duration::days(days_ago),
             created_at: Some(Utc::now()),
+            condition_id: None,
         }
     }
 
@@ -185,4 +562,88 @@ mod tests {
     fn test_classify_empty() {
         assert_eq!(classify_wallet(&[]), Classification::Informed);
     }
+
+    #[test]
+    fn test_explain_wallet_lists_rules() {
+        let trades: Vec<WhaleTrade> = (0..5)
+            .map(|i| make_trade(&format!("market_{i}"), "BUY", i * 30))
+            .collect();
+        let e = explain_wallet(&trades);
+        assert_eq!(e.classification, Classification::Informed);
+        assert_eq!(e.classifier, "heuristic");
+        assert_eq!(e.trade_count, 5);
+        assert_eq!(
+            e.rules,
+            vec![
+                "not market_maker: 0% of markets traded on both sides (<= 50%)",
+                "not bot: only 5 trades (10 needed)",
+                "informed: no exclusion rule matched",
+            ]
+        );
+
+        let mm = vec![make_trade("market_A", "BUY", 10), make_trade("market_A", "SELL", 9)];
+        let e = explain_wallet(&mm);
+        assert_eq!(e.classification, Classification::MarketMaker);
+        assert_eq!(e.rules, vec!["market_maker: 100% of markets traded on both sides (> 50%)"]);
+    }
+
+    #[test]
+    fn test_wallet_features() {
+        let mut trades = vec![
+            make_trade("market_A", "BUY", 10),
+            make_trade("market_A", "SELL", 8),
+            make_trade("market_B", "BUY", 5),
+        ];
+        trades[2].notional = Decimal::from(150);
+
+        let f = WalletFeatures::from_trades(&trades);
+        assert_eq!(f.trades_per_month, 18.0);
+        assert_eq!(f.dual_side_ratio, 0.5);
+        assert!((f.avg_hold_hours - 48.0).abs() < 0.1);
+        assert!(f.notional_cv > 0.5);
+    }
+
+    #[test]
+    fn test_logistic_classifier_learns_labels() {
+        let features = |tpm: f64, dual: f64, hold: f64| WalletFeatures {
+            trades_per_month: tpm,
+            dual_side_ratio: dual,
+            avg_hold_hours: hold,
+            notional_cv: 0.5,
+        };
+        let mut samples = Vec::new();
+        for i in 0..10 {
+            let i = i as f64;
+            samples.push((features(5.0 + i, 0.05, 200.0 + i * 10.0), Classification::Informed));
+            samples.push((features(40.0 + i, 0.8, 2.0 + i), Classification::MarketMaker));
+            samples.push((features(500.0 + i * 50.0, 0.1, 0.1), Classification::Bot));
+        }
+        let model = LogisticClassifier::train(&samples, 500, 0.5).unwrap();
+
+        assert_eq!(model.predict(&features(8.0, 0.0, 300.0)).0, Classification::Informed);
+        assert_eq!(model.predict(&features(50.0, 0.9, 3.0)).0, Classification::MarketMaker);
+        assert_eq!(model.predict(&features(900.0, 0.05, 0.2)).0, Classification::Bot);
+        assert!(LogisticClassifier::train(&[], 10, 0.5).is_none());
+    }
+
+    #[test]
+    fn test_logistic_classifier_falls_back_to_heuristic() {
+        // Trained only on bots, but the wallet is below min_trades
+        let bot = WalletFeatures {
+            trades_per_month: 1000.0,
+            dual_side_ratio: 0.0,
+            avg_hold_hours: 0.0,
+            notional_cv: 0.0,
+        };
+        let model = LogisticClassifier::train(&[(bot, Classification::Bot)], 50, 0.5)
+            .unwrap()
+            .with_fallback(5, 0.6);
+        let trades = vec![make_trade("market_A", "BUY", 3)];
+
+        assert_eq!(model.classify(&trades), Classification::Informed);
+        assert_eq!(model.name(), "logistic");
+        let e = model.explain(&trades);
+        assert_eq!(e.classifier, "logistic");
+        assert_eq!(e.rules[0], "heuristic fallback: 1 trades (5 needed)");
+    }
 }
@@ -0,0 +1,140 @@
+use std::collections::HashMap;
+
+use rust_decimal::Decimal;
+use uuid::Uuid;
+
+use crate::db::basket_repo::BasketTradeVote;
+use crate::models::WhaleCorrelation;
+
+/// Group whales whose pairwise overlap reaches `threshold`, transitively.
+///
+/// Returns each member's cluster representative — the smallest id in its
+/// cluster, or itself when it correlates with no other member. Pairs naming
+/// non-members are ignored.
+pub fn correlation_clusters(
+    members: &[Uuid],
+    pairs: &[WhaleCorrelation],
+    threshold: Decimal,
+) -> HashMap<Uuid, Uuid> {
+    let mut parent: HashMap<Uuid, Uuid> = members.iter().map(|&id| (id, id)).collect();
+
+    fn root(parent: &HashMap<Uuid, Uuid>, mut id: Uuid) -> Uuid {
+        while parent[&id] != id {
+            id = parent[&id];
+        }
+        id
+    }
+
+    for pair in pairs.iter().filter(|p| p.overlap >= threshold) {
+        if !parent.contains_key(&pair.whale_a) || !parent.contains_key(&pair.whale_b) {
+            continue;
+        }
+        let (a, b) = (root(&parent, pair.whale_a), root(&parent, pair.whale_b));
+        if a != b {
+            parent.insert(a.max(b), a.min(b));
+        }
+    }
+
+    members.iter().map(|&id| (id, root(&parent, id))).collect()
+}
+
+/// Number of independent voters: one per cluster.
+pub fn independent_count(clusters: &HashMap<Uuid, Uuid>) -> usize {
+    let mut roots: Vec<Uuid> = clusters.values().copied().collect();
+    roots.sort();
+    roots.dedup();
+    roots.len()
+}
+
+/// Collapse votes from correlated whales into one vote per cluster — the
+/// cluster's most recent — cast under the representative's id. Voters
+/// missing from `clusters` vote on their own.
+pub fn dedup_correlated_votes(
+    votes: &[BasketTradeVote],
+    clusters: &HashMap<Uuid, Uuid>,
+) -> Vec<BasketTradeVote> {
+    let mut latest: HashMap<Uuid, BasketTradeVote> = HashMap::new();
+
+    for vote in votes {
+        let rep = clusters.get(&vote.whale_id).copied().unwrap_or(vote.whale_id);
+        match latest.get(&rep) {
+            Some(existing) if existing.traded_at >= vote.traded_at => {}
+            _ => {
+                latest.insert(
+                    rep,
+                    BasketTradeVote {
+                        whale_id: rep,
+                        ..vote.clone()
+                    },
+                );
+            }
+        }
+    }
+
+    latest.into_values().collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+
+    fn pair(a: Uuid, b: Uuid, overlap: i64) -> WhaleCorrelation {
+        WhaleCorrelation {
+            whale_a: a,
+            address_a: String::new(),
+            whale_b: b,
+            address_b: String::new(),
+            shared_positions: 10,
+            overlap: Decimal::new(overlap, 2),
+        }
+    }
+
+    #[test]
+    fn test_correlation_clusters_are_transitive() {
+        let ids: Vec<Uuid> = (0..5).map(|_| Uuid::new_v4()).collect();
+        let pairs = vec![
+            pair(ids[0], ids[1], 90),
+            pair(ids[1], ids[2], 70),
+            pair(ids[3], ids[4], 40), // below threshold
+        ];
+
+        let clusters = correlation_clusters(&ids, &pairs, Decimal::new(60, 2));
+        assert_eq!(clusters[&ids[0]], clusters[&ids[2]]);
+        assert_ne!(clusters[&ids[3]], clusters[&ids[4]]);
+        assert_eq!(independent_count(&clusters), 3);
+    }
+
+    #[test]
+    fn test_copycat_votes_count_once() {
+        let ids: Vec<Uuid> = (0..4).map(|_| Uuid::new_v4()).collect();
+        let pairs = vec![pair(ids[0], ids[1], 95), pair(ids[0], ids[2], 95)];
+        let clusters = correlation_clusters(&ids, &pairs, Decimal::new(60, 2));
+
+        let now = Utc::now();
+        let vote = |id: Uuid, side: &str, mins: i64| BasketTradeVote {
+            whale_id: id,
+            side: side.into(),
+            traded_at: now - Duration::minutes(mins),
+            notional: Decimal::from(1000),
+            win_rate: None,
+            expected_value: None,
+        };
+        let votes = vec![
+            vote(ids[0], "BUY", 30),
+            vote(ids[1], "BUY", 20),
+            vote(ids[2], "SELL", 10),
+            vote(ids[3], "BUY", 5),
+        ];
+
+        let deduped = dedup_correlated_votes(&votes, &clusters);
+        assert_eq!(deduped.len(), 2);
+        // The copycat cluster keeps its latest vote
+        let cluster_vote = deduped.iter().find(|v| v.whale_id == clusters[&ids[0]]).unwrap();
+        assert_eq!(cluster_vote.side, "SELL");
+    }
+}
@@ -0,0 +1,108 @@
+use rust_decimal::Decimal;
+use sqlx::FromRow;
+
+/// Gates for the market-level net whale flow signal.
+#[derive(Debug, Clone)]
+pub struct FlowConfig {
+    /// Rolling window the flow is summed over (0 = off).
+    pub window_minutes: i32,
+    /// Minimum buy-minus-sell notional (USDC) into the token.
+    pub min_net_notional: Decimal,
+    /// Minimum distinct tracked whales trading the token, so one wallet
+    /// can't make a flow signal on its own.
+    pub min_whales: i64,
+    /// Minimum net / gross notional (0–1): flow must be one-sided, not churn.
+    pub min_imbalance: Decimal,
+}
+
+impl Default for FlowConfig {
+    fn default() -> Self {
+        Self {
+            window_minutes: 0,
+            min_net_notional: Decimal::from(50_000),
+            min_whales: 3,
+            min_imbalance: Decimal::new(6, 1),
+        }
+    }
+}
+
+impl FlowConfig {
+    pub fn enabled(&self) -> bool {
+        self.window_minutes > 0
+    }
+}
+
+/// Tracked-whale trading in one token over the window.
+#[derive(Debug, Clone, Default, PartialEq, FromRow)]
+pub struct TokenFlow {
+    pub buy_notional: Decimal,
+    pub sell_notional: Decimal,
+    pub whales: i64,
+}
+
+impl TokenFlow {
+    pub fn net(&self) -> Decimal {
+        self.buy_notional - self.sell_notional
+    }
+
+    /// Net over gross notional: 1 when every whale bought, 0 when buys and
+    /// sells cancel out.
+    pub fn imbalance(&self) -> Decimal {
+        let gross = self.buy_notional + self.sell_notional;
+        if gross.is_zero() {
+            return Decimal::ZERO;
+        }
+        self.net() / gross
+    }
+}
+
+/// Why net inflow into a token doesn't qualify as a flow signal, or None
+/// when it passes every gate.
+pub fn flow_rejection(flow: &TokenFlow, config: &FlowConfig) -> Option<String> {
+    let net = flow.net();
+    if net < config.min_net_notional {
+        return Some(format!("net flow {} < {}", net.round_dp(0), config.min_net_notional));
+    }
+    if flow.whales < config.min_whales {
+        return Some(format!("{} whales < {}", flow.whales, config.min_whales));
+    }
+    let imbalance = flow.imbalance();
+    if imbalance < config.min_imbalance {
+        return Some(format!("imbalance {} < {}", imbalance.round_dp(2), config.min_imbalance));
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> FlowConfig {
+        FlowConfig { window_minutes: 60, ..Default::default() }
+    }
+
+    fn flow(buy: i64, sell: i64, whales: i64) -> TokenFlow {
+        TokenFlow {
+            buy_notional: Decimal::from(buy),
+            sell_notional: Decimal::from(sell),
+            whales,
+        }
+    }
+
+    #[test]
+    fn test_flow_rejection() {
+        assert_eq!(flow_rejection(&flow(80_000, 10_000, 4), &config()), None);
+
+        let small = flow_rejection(&flow(40_000, 0, 4), &config()).unwrap();
+        assert!(small.contains("net flow"));
+        let lone = flow_rejection(&flow(80_000, 0, 1), &config()).unwrap();
+        assert!(lone.contains("whales"));
+        // 150k in, 90k out: net 60k passes, but it's mostly churn
+        let churn = flow_rejection(&flow(150_000, 90_000, 5), &config()).unwrap();
+        assert!(churn.contains("imbalance"));
+    }
+}
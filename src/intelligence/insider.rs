@@ -0,0 +1,208 @@
+use chrono::{Duration, Utc};
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+
+use crate::db::insider_repo::{self, InsiderCandidate};
+use crate::models::{InsiderFlag, InsiderFlagKind};
+
+/// Thresholds for flagging suspiciously well-timed whale entries.
+#[derive(Debug, Clone)]
+pub struct InsiderConfig {
+    /// Only entries of at least this notional (USDC) are considered.
+    pub min_notional: Decimal,
+    /// A winning entry this close to resolution is a pre-resolution flag.
+    pub resolution_window_hours: i64,
+    /// A favourable move within this long after entry is a pre-jump flag.
+    pub jump_window_hours: i64,
+    /// Minimum favourable price move for either flag, so entries at
+    /// near-certain prices aren't flagged.
+    pub min_price_move: Decimal,
+    /// How far back each scan looks for trades.
+    pub lookback_days: i64,
+    pub interval_secs: u64,
+}
+
+/// Why a trade was flagged.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InsiderEvidence {
+    pub kind: InsiderFlagKind,
+    pub lead_hours: Option<Decimal>,
+    pub price_move: Decimal,
+    pub description: String,
+}
+
+/// A large entry on the winning side of a market that resolved within the
+/// window, from a price that left room to profit.
+pub fn pre_resolution_evidence(c: &InsiderCandidate, config: &InsiderConfig) -> Option<InsiderEvidence> {
+    let resolved_at = c.resolved_at?;
+    if c.notional < config.min_notional {
+        return None;
+    }
+    let lead_minutes = (resolved_at - c.traded_at).num_minutes();
+    if lead_minutes <= 0 || lead_minutes > config.resolution_window_hours * 60 {
+        return None;
+    }
+
+    let (price_move, outcome) = match (c.side.as_str(), c.outcome.as_deref()?) {
+        ("BUY", "resolved_yes") => (Decimal::ONE - c.price, "yes"),
+        ("SELL", "resolved_no") => (c.price, "no"),
+        _ => return None,
+    };
+    if price_move < config.min_price_move {
+        return None;
+    }
+
+    let lead_hours = (Decimal::from(lead_minutes) / Decimal::from(60)).round_dp(2);
+    Some(InsiderEvidence {
+        kind: InsiderFlagKind::PreResolution,
+        lead_hours: Some(lead_hours),
+        price_move,
+        description: format!(
+            "{} {} USDC @ {}; market resolved {} {}h later",
+            c.side,
+            c.notional.round_dp(0),
+            c.price,
+            outcome,
+            lead_hours,
+        ),
+    })
+}
+
+/// A large entry followed within the window by a sharp move in its favour.
+pub fn pre_jump_evidence(c: &InsiderCandidate, config: &InsiderConfig) -> Option<InsiderEvidence> {
+    let extreme = c.extreme_price?;
+    if c.notional < config.min_notional {
+        return None;
+    }
+
+    let price_move = match c.side.as_str() {
+        "BUY" => extreme - c.price,
+        "SELL" => c.price - extreme,
+        _ => return None,
+    };
+    if price_move < config.min_price_move {
+        return None;
+    }
+
+    Some(InsiderEvidence {
+        kind: InsiderFlagKind::PreJump,
+        lead_hours: None,
+        price_move,
+        description: format!(
+            "{} {} USDC @ {}; price reached {} within {}h",
+            c.side,
+            c.notional.round_dp(0),
+            c.price,
+            extreme,
+            config.jump_window_hours,
+        ),
+    })
+}
+
+/// Evaluate recent large trades and record new flags. Returns only the flags
+/// created by this scan, so callers can alert on them once.
+pub async fn scan_insider_flags(pool: &PgPool, config: &InsiderConfig) -> anyhow::Result<Vec<InsiderFlag>> {
+    let since = Utc::now() - Duration::days(config.lookback_days);
+    let resolution_window = config.resolution_window_hours as i32;
+    let jump_window = config.jump_window_hours as i32;
+
+    let mut found = Vec::new();
+    for c in insider_repo::get_pre_resolution_candidates(pool, since, config.min_notional, resolution_window).await? {
+        if let Some(evidence) = pre_resolution_evidence(&c, config) {
+            found.push((c, evidence));
+        }
+    }
+    for c in insider_repo::get_pre_jump_candidates(pool, since, config.min_notional, jump_window).await? {
+        if let Some(evidence) = pre_jump_evidence(&c, config) {
+            found.push((c, evidence));
+        }
+    }
+
+    let mut created = Vec::new();
+    for (candidate, evidence) in &found {
+        if let Some(flag) = insider_repo::insert_flag(pool, candidate, evidence).await? {
+            created.push(flag);
+        }
+    }
+
+    Ok(created)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::DateTime;
+    use uuid::Uuid;
+
+    fn config() -> InsiderConfig {
+        InsiderConfig {
+            min_notional: Decimal::from(5000),
+            resolution_window_hours: 24,
+            jump_window_hours: 6,
+            min_price_move: Decimal::new(25, 2),
+            lookback_days: 30,
+            interval_secs: 3600,
+        }
+    }
+
+    fn candidate(side: &str, price: Decimal, notional: i64) -> InsiderCandidate {
+        InsiderCandidate {
+            trade_id: Uuid::new_v4(),
+            whale_id: Uuid::new_v4(),
+            market_id: "0xcond".into(),
+            token_id: "tok".into(),
+            side: side.into(),
+            price,
+            notional: Decimal::from(notional),
+            traded_at: DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc),
+            outcome: None,
+            resolved_at: None,
+            extreme_price: None,
+        }
+    }
+
+    #[test]
+    fn test_pre_resolution_evidence() {
+        let mut c = candidate("BUY", Decimal::new(40, 2), 10_000);
+        c.outcome = Some("resolved_yes".into());
+        c.resolved_at = Some(c.traded_at + Duration::minutes(90));
+
+        let evidence = pre_resolution_evidence(&c, &config()).unwrap();
+        assert_eq!(evidence.kind, InsiderFlagKind::PreResolution);
+        assert_eq!(evidence.lead_hours, Some(Decimal::new(15, 1)));
+        assert_eq!(evidence.price_move, Decimal::new(60, 2));
+
+        // Losing side, near-certain entry, too early or too small: no flag
+        let mut losing = c.clone();
+        losing.outcome = Some("resolved_no".into());
+        assert!(pre_resolution_evidence(&losing, &config()).is_none());
+        let mut certain = c.clone();
+        certain.price = Decimal::new(95, 2);
+        assert!(pre_resolution_evidence(&certain, &config()).is_none());
+        let mut early = c.clone();
+        early.resolved_at = Some(c.traded_at + Duration::hours(48));
+        assert!(pre_resolution_evidence(&early, &config()).is_none());
+        let mut small = c.clone();
+        small.notional = Decimal::from(1000);
+        assert!(pre_resolution_evidence(&small, &config()).is_none());
+    }
+
+    #[test]
+    fn test_pre_jump_evidence() {
+        let mut buy = candidate("BUY", Decimal::new(30, 2), 8_000);
+        buy.extreme_price = Some(Decimal::new(70, 2));
+        let evidence = pre_jump_evidence(&buy, &config()).unwrap();
+        assert_eq!(evidence.kind, InsiderFlagKind::PreJump);
+        assert_eq!(evidence.price_move, Decimal::new(40, 2));
+
+        let mut sell = candidate("SELL", Decimal::new(60, 2), 8_000);
+        sell.extreme_price = Some(Decimal::new(50, 2));
+        assert!(pre_jump_evidence(&sell, &config()).is_none());
+        sell.extreme_price = Some(Decimal::new(20, 2));
+        assert_eq!(pre_jump_evidence(&sell, &config()).unwrap().price_move, Decimal::new(40, 2));
+    }
+}
@@ -1,7 +1,17 @@
 pub mod basket;
 pub mod classifier;
+pub mod correlation;
+pub mod flow;
+pub mod insider;
 pub mod scorer;
+pub mod tiers;
 
-pub use basket::{check_admission, check_basket_consensus, evaluate_consensus, AdmissionResult, ConsensusCheck};
-pub use classifier::{Classification, classify_wallet};
+pub use basket::{
+    check_admission, check_basket_consensus, evaluate_consensus, resolve_conflict, AdmissionResult, ConflictMode,
+    ConflictResolution, ConsensusCheck,
+};
+pub use classifier::{
+    build_wallet_classifier, classify_wallet, explain_wallet, Classification, ClassificationExplanation,
+    ClassifierConfig, HeuristicClassifier, LogisticClassifier, WalletClassifier, WalletFeatures,
+};
 pub use scorer::{WalletScore, score_wallet};
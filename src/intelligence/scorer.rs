@@ -1,14 +1,31 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use rust_decimal::MathematicalOps;
 use serde::{Deserialize, Serialize};
 
-use crate::models::TradeResult;
+use crate::models::{ExitStyle, TradeResult};
 
 /// Aggregated scoring output for a wallet.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WalletScore {
     pub sharpe_ratio: Decimal,
+    pub sortino_ratio: Decimal,
+    pub calmar_ratio: Decimal,
+    /// Largest peak-to-trough drop of cumulative PnL (USDC), in trade order.
+    pub max_drawdown: Decimal,
+    /// The same drop as a percent of the peak cumulative PnL (capped at 100).
+    pub max_drawdown_pct: Decimal,
+    /// Gross wins / gross losses. None when there are no losing trades.
+    pub profit_factor: Option<Decimal>,
+    /// Average win / average loss. None without both wins and losses.
+    pub payoff_ratio: Option<Decimal>,
+    /// 1 − `luck_probability`: confidence the record reflects a real edge.
+    /// None with fewer than 2 trades.
+    pub skill_score: Option<Decimal>,
     pub win_rate: Decimal,
+    /// 95% Wilson-score lower bound on `win_rate`. The signal gate checks
+    /// this rather than the raw rate, which is noisy over few trades.
+    pub win_rate_lower_bound: Decimal,
     pub kelly_fraction: Decimal,
     pub expected_value: Decimal,
     pub total_trades: i32,
@@ -24,14 +41,36 @@ pub fn score_wallet(trades: &[TradeResult]) -> WalletScore {
     let returns: Vec<Decimal> = trades.iter().map(|t| t.profit).collect();
 
     let sr = sharpe_ratio(&returns);
+    let so = sortino_ratio(&returns);
+    let cr = calmar_ratio(&returns);
+    let pf = profit_factor(&returns);
+    let pr = payoff_ratio(&returns);
+
+    // Drawdown depends on trade order
+    let mut ordered: Vec<&TradeResult> = trades.iter().collect();
+    ordered.sort_by_key(|t| t.traded_at);
+    let ordered_returns: Vec<Decimal> = ordered.iter().map(|t| t.profit).collect();
+    let dd = max_drawdown(&ordered_returns);
+    // The largest single stake is the least capital the wallet started with
+    let starting_capital = trades.iter().map(|t| t.notional).max().unwrap_or(Decimal::ZERO);
+    let dd_pct = max_drawdown_pct(&ordered_returns, starting_capital);
+
     let wr = win_rate(trades);
     let ev = expected_value(trades);
-    let kf = kelly_fraction(wr, avg_odds(trades));
+    let kf = kelly_fraction(wr, pr.unwrap_or(Decimal::ONE));
     let decaying = is_decaying(trades);
 
     WalletScore {
         sharpe_ratio: sr,
+        sortino_ratio: so,
+        calmar_ratio: cr,
+        max_drawdown: dd,
+        max_drawdown_pct: dd_pct,
+        profit_factor: pf,
+        payoff_ratio: pr,
+        skill_score: luck_probability(&returns).map(|p| Decimal::ONE - p),
         win_rate: wr,
+        win_rate_lower_bound: wilson_lower_bound(wr, total_trades as i64),
         kelly_fraction: kf,
         expected_value: ev,
         total_trades,
@@ -72,6 +111,92 @@ pub fn sharpe_ratio(returns: &[Decimal]) -> Decimal {
     mean / std_dev
 }
 
+/// Downside-risk-adjusted return: mean(returns) / downside deviation, where
+/// only losing returns count towards the deviation.
+/// Returns Decimal::ZERO if insufficient data or no losses.
+pub fn sortino_ratio(returns: &[Decimal]) -> Decimal {
+    if returns.len() < 2 {
+        return Decimal::ZERO;
+    }
+
+    let n = Decimal::from(returns.len() as i64);
+    let mean = returns.iter().copied().sum::<Decimal>() / n;
+
+    let downside_variance = returns
+        .iter()
+        .map(|r| {
+            let down = (*r).min(Decimal::ZERO);
+            down * down
+        })
+        .sum::<Decimal>()
+        / n;
+
+    let downside_dev = downside_variance.sqrt().unwrap_or(Decimal::ZERO);
+
+    if downside_dev.is_zero() {
+        return Decimal::ZERO;
+    }
+
+    mean / downside_dev
+}
+
+/// Largest peak-to-trough drop of the cumulative PnL curve (positive amount).
+pub fn max_drawdown(returns: &[Decimal]) -> Decimal {
+    let mut cumulative = Decimal::ZERO;
+    let mut peak = Decimal::ZERO;
+    let mut max_dd = Decimal::ZERO;
+
+    for r in returns {
+        cumulative += *r;
+        peak = peak.max(cumulative);
+        max_dd = max_dd.max(peak - cumulative);
+    }
+
+    max_dd
+}
+
+/// Largest drawdown as a percent of the running peak equity, where equity is
+/// `starting_capital` plus the cumulative PnL, so an early loss is measured
+/// against the capital rather than counted as losing everything. Capped at
+/// 100; with no capital, losing before any profit counts as 100.
+pub fn max_drawdown_pct(returns: &[Decimal], starting_capital: Decimal) -> Decimal {
+    let mut cumulative = starting_capital.max(Decimal::ZERO);
+    let mut peak = cumulative;
+    let mut max_pct = Decimal::ZERO;
+
+    for r in returns {
+        cumulative += *r;
+        peak = peak.max(cumulative);
+        let dd = peak - cumulative;
+        if dd.is_zero() {
+            continue;
+        }
+        let pct = if peak > Decimal::ZERO {
+            (dd / peak * Decimal::ONE_HUNDRED).min(Decimal::ONE_HUNDRED)
+        } else {
+            Decimal::ONE_HUNDRED
+        };
+        max_pct = max_pct.max(pct);
+    }
+
+    max_pct
+}
+
+/// Return over drawdown: sum(returns) / max drawdown of the cumulative PnL.
+/// Returns Decimal::ZERO if insufficient data or no drawdown.
+pub fn calmar_ratio(returns: &[Decimal]) -> Decimal {
+    if returns.len() < 2 {
+        return Decimal::ZERO;
+    }
+
+    let dd = max_drawdown(returns);
+    if dd.is_zero() {
+        return Decimal::ZERO;
+    }
+
+    returns.iter().copied().sum::<Decimal>() / dd
+}
+
 // ---------------------------------------------------------------------------
 // Metric 2: Kelly Fraction
 // ---------------------------------------------------------------------------
@@ -90,27 +215,33 @@ pub fn kelly_fraction(win_rate: Decimal, avg_odds: Decimal) -> Decimal {
     f.max(Decimal::ZERO)
 }
 
-/// Calculate average odds (avg_win / avg_loss) from trade results.
-fn avg_odds(trades: &[TradeResult]) -> Decimal {
-    let wins: Vec<&TradeResult> = trades.iter().filter(|t| t.profit > Decimal::ZERO).collect();
-    let losses: Vec<&TradeResult> = trades.iter().filter(|t| t.profit < Decimal::ZERO).collect();
+/// Gross profit over gross loss: sum(wins) / |sum(losses)|.
+/// Returns None if there are no losing trades.
+pub fn profit_factor(returns: &[Decimal]) -> Option<Decimal> {
+    let gross_win: Decimal = returns.iter().filter(|r| **r > Decimal::ZERO).copied().sum();
+    let gross_loss: Decimal = returns.iter().filter(|r| **r < Decimal::ZERO).map(|r| r.abs()).sum();
 
-    if wins.is_empty() || losses.is_empty() {
-        return Decimal::ONE;
+    if gross_loss.is_zero() {
+        return None;
     }
 
-    let avg_win = wins.iter().map(|t| t.profit).sum::<Decimal>() / Decimal::from(wins.len() as i64);
-    let avg_loss = losses
-        .iter()
-        .map(|t| t.profit.abs())
-        .sum::<Decimal>()
-        / Decimal::from(losses.len() as i64);
+    Some(gross_win / gross_loss)
+}
+
+/// Average payoff ratio (avg_win / avg_loss), the odds used for Kelly sizing.
+/// Returns None unless there are both winning and losing trades.
+pub fn payoff_ratio(returns: &[Decimal]) -> Option<Decimal> {
+    let wins: Vec<Decimal> = returns.iter().filter(|r| **r > Decimal::ZERO).copied().collect();
+    let losses: Vec<Decimal> = returns.iter().filter(|r| **r < Decimal::ZERO).map(|r| r.abs()).collect();
 
-    if avg_loss.is_zero() {
-        return Decimal::ONE;
+    if wins.is_empty() || losses.is_empty() {
+        return None;
     }
 
-    avg_win / avg_loss
+    let avg_win = wins.iter().copied().sum::<Decimal>() / Decimal::from(wins.len() as i64);
+    let avg_loss = losses.iter().copied().sum::<Decimal>() / Decimal::from(losses.len() as i64);
+
+    Some(avg_win / avg_loss)
 }
 
 // ---------------------------------------------------------------------------
@@ -135,21 +266,95 @@ pub fn rolling_win_rate(trades: &[TradeResult], window: usize) -> Decimal {
     Decimal::from(wins as i64) / Decimal::from(recent.len() as i64)
 }
 
-/// Detect performance decay:
-/// - 30-trade rolling WR < 55%, OR
-/// - 30-trade rolling WR < 80% of all-time WR
+/// z for a two-sided 95% confidence interval.
+const WIN_RATE_CONFIDENCE_Z: Decimal = Decimal::from_parts(196, 0, 0, false, 2);
+
+/// Wilson-score lower bound of a `win_rate` observed over `n` trades:
+/// (p + z²/2n − z·√(p(1−p)/n + z²/4n²)) / (1 + z²/n).
+/// Returns Decimal::ZERO with no trades.
+pub fn wilson_lower_bound(win_rate: Decimal, n: i64) -> Decimal {
+    if n <= 0 {
+        return Decimal::ZERO;
+    }
+
+    let p = win_rate.clamp(Decimal::ZERO, Decimal::ONE);
+    let n = Decimal::from(n);
+    let z2 = WIN_RATE_CONFIDENCE_Z * WIN_RATE_CONFIDENCE_Z;
+
+    let center = p + z2 / (Decimal::TWO * n);
+    let spread = (p * (Decimal::ONE - p) / n + z2 / (Decimal::from(4) * n * n))
+        .sqrt()
+        .unwrap_or(Decimal::ZERO);
+    let lower = (center - WIN_RATE_CONFIDENCE_Z * spread) / (Decimal::ONE + z2 / n);
+
+    lower.max(Decimal::ZERO)
+}
+
+/// Trades in the recent window the decay checks look at.
+const DECAY_WINDOW: usize = 30;
+
+/// Exponentially weighted moving average of `returns` with the given span
+/// (α = 2 / (span + 1)), one value per return. Empty for no returns.
+pub fn ewma(returns: &[Decimal], span: usize) -> Vec<Decimal> {
+    let alpha = Decimal::TWO / Decimal::from(span as i64 + 1);
+    let mut smoothed = Vec::with_capacity(returns.len());
+    let mut current = None;
+    for &r in returns {
+        let next = match current {
+            None => r,
+            Some(prev) => alpha * r + (Decimal::ONE - alpha) * prev,
+        };
+        smoothed.push(next);
+        current = Some(next);
+    }
+    smoothed
+}
+
+/// Detect performance decay over the last 30 trades:
+/// - rolling WR < 55%, OR
+/// - rolling WR < 80% of all-time WR, OR
+/// - rolling Sharpe < half the all-time Sharpe, OR
+/// - EWMA-smoothed PnL per trade fell below half its level 30 trades ago.
+///
+/// The last two catch an edge eroding through smaller wins while the win
+/// rate still holds up. Trades may come in any order (the DB hands them
+/// newest first); they are put in time order before looking at the window.
 pub fn is_decaying(trades: &[TradeResult]) -> bool {
-    if trades.len() < 30 {
+    if trades.len() < DECAY_WINDOW {
         return false;
     }
 
+    let mut ordered = trades.to_vec();
+    ordered.sort_by_key(|t| t.traded_at);
+    let trades = ordered.as_slice();
+
     let alltime_wr = rolling_win_rate(trades, trades.len());
-    let recent_wr = rolling_win_rate(trades, 30);
+    let recent_wr = rolling_win_rate(trades, DECAY_WINDOW);
 
     let threshold_absolute = Decimal::new(55, 2); // 0.55
     let threshold_relative = alltime_wr * Decimal::new(80, 2) / Decimal::ONE_HUNDRED;
 
-    recent_wr < threshold_absolute || recent_wr < threshold_relative
+    if recent_wr < threshold_absolute || recent_wr < threshold_relative {
+        return true;
+    }
+
+    let returns: Vec<Decimal> = trades.iter().map(|t| t.profit).collect();
+    let half = Decimal::new(5, 1);
+
+    let alltime_sharpe = sharpe_ratio(&returns);
+    let recent_sharpe = sharpe_ratio(&returns[returns.len() - DECAY_WINDOW..]);
+    if alltime_sharpe > Decimal::ZERO && recent_sharpe < alltime_sharpe * half {
+        return true;
+    }
+
+    // Needs a full window of history before the recent one to compare against
+    let smoothed = ewma(&returns, DECAY_WINDOW);
+    if smoothed.len() < 2 * DECAY_WINDOW {
+        return false;
+    }
+    let before = smoothed[smoothed.len() - 1 - DECAY_WINDOW];
+    let now = smoothed[smoothed.len() - 1];
+    before > Decimal::ZERO && now < before * half
 }
 
 // ---------------------------------------------------------------------------
@@ -186,6 +391,141 @@ pub fn expected_value(trades: &[TradeResult]) -> Decimal {
     wr * avg_win - (Decimal::ONE - wr) * avg_loss
 }
 
+// ---------------------------------------------------------------------------
+// Metric 5: Luck vs Skill
+// ---------------------------------------------------------------------------
+
+/// Probability that a trader with no edge (mean return ≤ 0) produces a record
+/// at least this good: a one-sided t-test on per-trade returns, using the
+/// normal approximation. None with fewer than 2 trades.
+pub fn luck_probability(returns: &[Decimal]) -> Option<Decimal> {
+    if returns.len() < 2 {
+        return None;
+    }
+
+    let n = Decimal::from(returns.len() as i64);
+    let mean = returns.iter().copied().sum::<Decimal>() / n;
+
+    // Sample variance
+    let variance = returns
+        .iter()
+        .map(|r| {
+            let diff = *r - mean;
+            diff * diff
+        })
+        .sum::<Decimal>()
+        / (n - Decimal::ONE);
+    let std_err = (variance / n).sqrt().unwrap_or(Decimal::ZERO);
+
+    if std_err.is_zero() {
+        // Identical returns: either all gains or no edge at all
+        return Some(if mean > Decimal::ZERO { Decimal::ZERO } else { Decimal::ONE });
+    }
+
+    // Beyond ±8 the tail probability is zero at any precision we care about
+    let t = (mean / std_err).clamp(Decimal::from(-8), Decimal::from(8));
+    Some((Decimal::ONE - t.norm_cdf()).clamp(Decimal::ZERO, Decimal::ONE))
+}
+
+// ---------------------------------------------------------------------------
+// Metric 6: Entry Edge
+// ---------------------------------------------------------------------------
+
+/// Profit of a trade in a resolved market: a winning buy at `price` pays
+/// `notional × (1 − price) / price`, a winning sell `notional × price / (1 − price)`,
+/// and the losing side loses its notional. None for unresolved markets or
+/// degenerate prices.
+pub fn resolved_profit(side: &str, price: Decimal, notional: Decimal, outcome: &str) -> Option<Decimal> {
+    if price <= Decimal::ZERO || price >= Decimal::ONE {
+        return None;
+    }
+    let profit = match (outcome, side) {
+        ("resolved_yes", "BUY") => notional * (Decimal::ONE - price) / price,
+        ("resolved_yes", _) => -notional,
+        ("resolved_no", "BUY") => -notional,
+        ("resolved_no", _) => notional * price / (Decimal::ONE - price),
+        _ => return None,
+    };
+    // Zero profit is indistinguishable from unresolved
+    (profit != Decimal::ZERO).then_some(profit)
+}
+
+/// Price move in the trade's favour from `entry` to `later`, in probability
+/// points: up for buys, down for sells.
+pub fn favorable_move(side: &str, entry: Decimal, later: Decimal) -> Decimal {
+    if side == "SELL" {
+        entry - later
+    } else {
+        later - entry
+    }
+}
+
+/// Mean favourable move after a whale's entries. Consistently positive means
+/// the market tends to follow the whale, before any market resolves.
+/// Returns None without any moves.
+pub fn entry_edge(moves: &[Decimal]) -> Option<Decimal> {
+    if moves.is_empty() {
+        return None;
+    }
+    Some(moves.iter().copied().sum::<Decimal>() / Decimal::from(moves.len() as i64))
+}
+
+/// A whale's first buy of a token, its first later sell, and when the market
+/// resolved, if it has.
+pub type HoldingPeriod = (DateTime<Utc>, Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+
+/// How long a whale holds and how it gets out.
+#[derive(Debug, Clone, PartialEq)]
+pub struct HoldingProfile {
+    pub avg_hold_hours: Decimal,
+    /// Share of finished positions sold before the market resolved.
+    pub early_exit_ratio: Decimal,
+    pub samples: usize,
+}
+
+impl HoldingProfile {
+    /// Whales that mostly sell before resolution trade swings and are
+    /// mirrored out; the rest are held to resolution.
+    pub fn exit_style(&self, swing_exit_ratio: Decimal) -> ExitStyle {
+        if self.early_exit_ratio >= swing_exit_ratio {
+            ExitStyle::Mirror
+        } else {
+            ExitStyle::HoldToResolution
+        }
+    }
+}
+
+/// Profile finished positions: a sell before resolution is an early exit,
+/// otherwise the position was held to resolution. Positions still open in
+/// unresolved markets are skipped. Returns None without finished positions.
+pub fn holding_profile(periods: &[HoldingPeriod]) -> Option<HoldingProfile> {
+    let mut total_minutes = 0i64;
+    let mut early_exits = 0i64;
+    let mut samples = 0i64;
+
+    for (opened_at, exited_at, resolved_at) in periods {
+        let (closed_at, early) = match (exited_at, resolved_at) {
+            (Some(exit), Some(resolved)) if exit < resolved => (*exit, true),
+            (_, Some(resolved)) => (*resolved, false),
+            (Some(exit), None) => (*exit, true),
+            (None, None) => continue,
+        };
+        total_minutes += (closed_at - *opened_at).num_minutes().max(0);
+        early_exits += early as i64;
+        samples += 1;
+    }
+
+    if samples == 0 {
+        return None;
+    }
+    let n = Decimal::from(samples);
+    Some(HoldingProfile {
+        avg_hold_hours: (Decimal::from(total_minutes) / Decimal::from(60) / n).round_dp(2),
+        early_exit_ratio: (Decimal::from(early_exits) / n).round_dp(4),
+        samples: samples as usize,
+    })
+}
+
 // ---------------------------------------------------------------------------
 // Tests
 // ---------------------------------------------------------------------------
@@ -200,6 +540,7 @@ mod tests {
             .iter()
             .map(|&p| TradeResult {
                 profit: Decimal::from(p),
+                notional: Decimal::ZERO,
                 traded_at: Utc::now(),
             })
             .collect()
@@ -236,6 +577,80 @@ mod tests {
         assert_eq!(sharpe_ratio(&returns), Decimal::ZERO);
     }
 
+    #[test]
+    fn test_sortino_ratio() {
+        let returns = vec![
+            Decimal::from(30),
+            Decimal::from(-10),
+            Decimal::from(20),
+            Decimal::from(-10),
+        ];
+        // mean 7.5, downside dev sqrt(200 / 4) ≈ 7.07
+        let so = sortino_ratio(&returns);
+        assert!(so > Decimal::ONE && so < Decimal::new(11, 1));
+        // Sortino ignores upside volatility, so it beats Sharpe here
+        assert!(so > sharpe_ratio(&returns));
+        // No losses → no downside deviation
+        assert_eq!(sortino_ratio(&[Decimal::from(10), Decimal::from(20)]), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_max_drawdown_and_calmar() {
+        // Curve: 100, 50, 150, 70, 120 → worst drop 150 → 70
+        let returns: Vec<Decimal> = [100, -50, 100, -80, 50].iter().map(|&r| Decimal::from(r)).collect();
+        assert_eq!(max_drawdown(&returns), Decimal::from(80));
+        assert_eq!(calmar_ratio(&returns), Decimal::new(15, 1));
+        // Monotonic gains → no drawdown
+        assert_eq!(calmar_ratio(&[Decimal::from(10), Decimal::from(20)]), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_max_drawdown_pct() {
+        // Peak 150 → 70: gave back 53.3% of profits
+        let returns: Vec<Decimal> = [100, -50, 100, -80, 50].iter().map(|&r| Decimal::from(r)).collect();
+        let pct = max_drawdown_pct(&returns, Decimal::ZERO);
+        assert!(pct > Decimal::from(53) && pct < Decimal::from(54));
+        // Losing before any profit, with and without starting capital
+        let early_loss = [Decimal::from(-10), Decimal::from(50)];
+        assert_eq!(max_drawdown_pct(&early_loss, Decimal::ZERO), Decimal::ONE_HUNDRED);
+        assert_eq!(max_drawdown_pct(&early_loss, Decimal::from(100)), Decimal::from(10));
+        // Gave back more than everything
+        assert_eq!(max_drawdown_pct(&[Decimal::from(10), Decimal::from(-30)], Decimal::ZERO), Decimal::ONE_HUNDRED);
+        assert_eq!(max_drawdown_pct(&[Decimal::from(10), Decimal::from(20)], Decimal::ZERO), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_profit_factor_and_payoff_ratio() {
+        // Wins 100 + 200 = 300, losses 50 + 100 = 150
+        let returns: Vec<Decimal> = [100, -50, 200, -100].iter().map(|&p| Decimal::from(p)).collect();
+        assert_eq!(profit_factor(&returns), Some(Decimal::from(2)));
+        // avg win 150 / avg loss 75
+        assert_eq!(payoff_ratio(&returns), Some(Decimal::from(2)));
+
+        let no_losses = [Decimal::from(10), Decimal::from(20)];
+        assert_eq!(profit_factor(&no_losses), None);
+        assert_eq!(payoff_ratio(&no_losses), None);
+        assert_eq!(profit_factor(&[Decimal::from(-10)]), Some(Decimal::ZERO));
+        assert_eq!(payoff_ratio(&[Decimal::from(-10)]), None);
+    }
+
+    #[test]
+    fn test_score_wallet_drawdown_uses_trade_order() {
+        let now = Utc::now();
+        // Listed out of order: chronologically +100, -80, +50
+        let trades: Vec<TradeResult> = [(2, 50), (0, 100), (1, -80)]
+            .iter()
+            .map(|&(mins, p)| TradeResult {
+                profit: Decimal::from(p),
+                notional: Decimal::ZERO,
+                traded_at: now + chrono::Duration::minutes(mins),
+            })
+            .collect();
+        let score = score_wallet(&trades);
+        assert_eq!(score.max_drawdown, Decimal::from(80));
+        assert_eq!(score.max_drawdown_pct, Decimal::from(80));
+    }
+
     #[test]
     fn test_kelly_fraction_positive_edge() {
         // 60% win rate, 1.5:1 odds
@@ -281,12 +696,147 @@ mod tests {
         assert!(is_decaying(&trades), "Should detect decay when recent WR drops");
     }
 
+    #[test]
+    fn test_is_decaying_on_shrinking_wins() {
+        // Win rate steady at 80%, but the wins shrink from 100 to 20
+        let pattern = |win: i64| [win, win, win, win, -20];
+        let mut profits: Vec<i64> = (0..12).flat_map(|_| pattern(100)).collect();
+        profits.extend((0..6).flat_map(|_| pattern(20)));
+        let trades = make_trades(&profits);
+
+        assert_eq!(rolling_win_rate(&trades, 30), Decimal::new(8, 1));
+        assert!(is_decaying(&trades), "Should detect decay from shrinking wins");
+
+        // The same record without the shrink is healthy
+        let steady: Vec<i64> = (0..18).flat_map(|_| pattern(100)).collect();
+        assert!(!is_decaying(&make_trades(&steady)));
+    }
+
+    #[test]
+    fn test_is_decaying_with_newest_first_trades() {
+        let now = Utc::now();
+        let dated = |profits: &[i64]| -> Vec<TradeResult> {
+            profits
+                .iter()
+                .enumerate()
+                .map(|(i, &p)| TradeResult {
+                    profit: Decimal::from(p),
+                    notional: Decimal::ZERO,
+                    traded_at: now + chrono::Duration::minutes(i as i64),
+                })
+                .rev() // as `ORDER BY traded_at DESC` returns them
+                .collect()
+        };
+        let pattern = |win: i64| [win, win, win, win, -20];
+
+        // Wins shrinking over time: decaying, whatever the input order
+        let mut shrinking: Vec<i64> = (0..12).flat_map(|_| pattern(100)).collect();
+        shrinking.extend((0..6).flat_map(|_| pattern(20)));
+        assert!(is_decaying(&dated(&shrinking)));
+
+        // Wins growing over time: improving, not decaying
+        let mut growing: Vec<i64> = (0..6).flat_map(|_| pattern(20)).collect();
+        growing.extend((0..12).flat_map(|_| pattern(100)));
+        assert!(!is_decaying(&dated(&growing)));
+    }
+
+    #[test]
+    fn test_ewma() {
+        let returns: Vec<Decimal> = [10, 10, 40].iter().map(|&r| Decimal::from(r)).collect();
+        // span 3 → α = 0.5
+        assert_eq!(ewma(&returns, 3), vec![Decimal::from(10), Decimal::from(10), Decimal::from(25)]);
+        assert!(ewma(&[], 30).is_empty());
+    }
+
+    #[test]
+    fn test_wilson_lower_bound() {
+        // 7/10 is weak evidence; 70/100 is much stronger
+        let small = wilson_lower_bound(Decimal::new(70, 2), 10);
+        let large = wilson_lower_bound(Decimal::new(70, 2), 100);
+        assert_eq!(small.round_dp(3), Decimal::new(397, 3));
+        assert_eq!(large.round_dp(3), Decimal::new(604, 3));
+
+        assert_eq!(wilson_lower_bound(Decimal::ONE, 0), Decimal::ZERO);
+        assert_eq!(wilson_lower_bound(Decimal::ZERO, 50), Decimal::ZERO);
+        assert!(wilson_lower_bound(Decimal::ONE, 5) < Decimal::ONE);
+    }
+
+    #[test]
+    fn test_luck_probability() {
+        // Same mean edge: a long record is far less likely to be luck
+        let returns = |profits: &[i64]| profits.iter().map(|&p| Decimal::from(p)).collect::<Vec<_>>();
+        let mut long = [100, -80].repeat(20);
+        long.push(100);
+        let p_short = luck_probability(&returns(&[100, -80, 100, -80, 100])).unwrap();
+        let p_long = luck_probability(&returns(&long)).unwrap();
+        assert!(p_short > Decimal::new(20, 2), "p_short = {p_short}");
+        assert!(p_long < p_short);
+
+        // Losing record: almost certainly no edge
+        let losing = [Decimal::from(-100), Decimal::from(20), Decimal::from(-90), Decimal::from(-50)];
+        assert!(luck_probability(&losing).unwrap() > Decimal::new(90, 2));
+
+        assert_eq!(luck_probability(&[Decimal::ONE]), None);
+        assert_eq!(luck_probability(&[Decimal::ONE, Decimal::ONE]), Some(Decimal::ZERO));
+    }
+
+    #[test]
+    fn test_entry_edge() {
+        assert_eq!(favorable_move("BUY", Decimal::new(40, 2), Decimal::new(55, 2)), Decimal::new(15, 2));
+        assert_eq!(favorable_move("SELL", Decimal::new(40, 2), Decimal::new(55, 2)), Decimal::new(-15, 2));
+
+        let moves = [Decimal::new(10, 2), Decimal::new(-4, 2), Decimal::new(6, 2)];
+        assert_eq!(entry_edge(&moves), Some(Decimal::new(4, 2)));
+        assert_eq!(entry_edge(&[]), None);
+    }
+
+    #[test]
+    fn test_resolved_profit() {
+        let price = Decimal::new(40, 2);
+        let notional = Decimal::from(100);
+        assert_eq!(resolved_profit("BUY", price, notional, "resolved_yes"), Some(Decimal::from(150)));
+        assert_eq!(resolved_profit("BUY", price, notional, "resolved_no"), Some(Decimal::from(-100)));
+        assert_eq!(resolved_profit("SELL", price, notional, "resolved_no").map(|p| p.round_dp(4)), Some(Decimal::new(666667, 4)));
+        assert_eq!(resolved_profit("SELL", price, notional, "resolved_yes"), Some(Decimal::from(-100)));
+        assert_eq!(resolved_profit("BUY", price, notional, "unresolved"), None);
+        assert_eq!(resolved_profit("BUY", Decimal::ONE, notional, "resolved_yes"), None);
+    }
+
+    #[test]
+    fn test_holding_profile() {
+        let t0 = chrono::DateTime::parse_from_rfc3339("2026-01-01T00:00:00Z").unwrap().with_timezone(&Utc);
+        let h = chrono::Duration::hours;
+        let periods = [
+            // Sold 10h in, market resolved later: early exit
+            (t0, Some(t0 + h(10)), Some(t0 + h(100))),
+            // Held through resolution at 50h; the later sell doesn't count
+            (t0, Some(t0 + h(60)), Some(t0 + h(50))),
+            // Sold 6h in, market still open: early exit
+            (t0, Some(t0 + h(6)), None),
+            // Still holding an unresolved market: skipped
+            (t0, None, None),
+        ];
+
+        let profile = holding_profile(&periods).unwrap();
+        assert_eq!(profile.samples, 3);
+        assert_eq!(profile.avg_hold_hours, Decimal::from(22));
+        assert_eq!(profile.early_exit_ratio, Decimal::new(6667, 4));
+        assert_eq!(profile.exit_style(Decimal::new(5, 1)), ExitStyle::Mirror);
+        assert_eq!(profile.exit_style(Decimal::new(8, 1)), ExitStyle::HoldToResolution);
+
+        assert_eq!(holding_profile(&[(t0, None, None)]), None);
+    }
+
     #[test]
     fn test_score_wallet_integration() {
         let trades = make_trades(&[100, -50, 200, -30, 150, 80, -20, 300]);
         let score = score_wallet(&trades);
         assert!(score.sharpe_ratio != Decimal::ZERO);
+        assert!(score.sortino_ratio > score.sharpe_ratio);
+        assert!(score.calmar_ratio > Decimal::ZERO);
         assert!(score.win_rate > Decimal::ZERO);
+        assert!(score.win_rate_lower_bound < score.win_rate);
+        assert!(score.skill_score.unwrap() > Decimal::new(50, 2));
         assert_eq!(score.total_trades, 8);
         assert!(!score.is_decaying);
     }
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use tokio::sync::RwLock;
+
+use crate::models::{TierPolicy, WhaleTier};
+
+/// Tier policies shared by the pipeline, the copy engine and the API, in
+/// assignment order (A, B, C).
+pub type SharedTierPolicies = Arc<RwLock<Vec<TierPolicy>>>;
+
+/// The best tier a whale's scores qualify for, if any. A whale without a
+/// skill score fails any tier that sets a skill threshold.
+pub fn assign_tier(
+    policies: &[TierPolicy],
+    win_rate_lower_bound: Decimal,
+    skill_score: Option<Decimal>,
+    total_trades: i32,
+) -> Option<&TierPolicy> {
+    let mut ordered: Vec<&TierPolicy> = policies.iter().filter(|p| p.whale_tier().is_some()).collect();
+    ordered.sort_by_key(|p| p.whale_tier());
+    ordered.into_iter().find(|p| {
+        win_rate_lower_bound >= p.min_win_rate
+            && total_trades >= p.min_total_trades
+            && (p.min_skill_score.is_zero() || skill_score.is_some_and(|s| s >= p.min_skill_score))
+    })
+}
+
+/// The policy for `tier`, if configured.
+pub fn policy_for(policies: &[TierPolicy], tier: WhaleTier) -> Option<&TierPolicy> {
+    policies.iter().find(|p| p.whale_tier() == Some(tier))
+}
+
+/// Reject policies that would size or gate nonsensically.
+pub fn validate_policy(policy: &TierPolicy) -> Result<(), String> {
+    if policy.whale_tier().is_none() {
+        return Err(format!("unknown tier '{}'", policy.tier));
+    }
+    if policy.min_win_rate < Decimal::ZERO || policy.min_win_rate > Decimal::ONE {
+        return Err("min_win_rate must be between 0 and 1".into());
+    }
+    if policy.min_skill_score < Decimal::ZERO || policy.min_skill_score > Decimal::ONE {
+        return Err("min_skill_score must be between 0 and 1".into());
+    }
+    if policy.min_total_trades < 0 {
+        return Err("min_total_trades must not be negative".into());
+    }
+    if policy.signal_notional_floor < Decimal::ZERO {
+        return Err("signal_notional_floor must not be negative".into());
+    }
+    if !matches!(policy.sizing.as_str(), "kelly" | "fixed" | "proportional") {
+        return Err(format!("sizing must be kelly, fixed or proportional, not '{}'", policy.sizing));
+    }
+    if policy.size_multiplier <= Decimal::ZERO {
+        return Err("size_multiplier must be positive".into());
+    }
+    if policy.sizing == "fixed" && policy.base_amount <= Decimal::ZERO {
+        return Err("fixed sizing needs a positive base_amount".into());
+    }
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn policy(tier: &str, min_win_rate: i64, min_trades: i32) -> TierPolicy {
+        TierPolicy {
+            tier: tier.into(),
+            min_win_rate: Decimal::new(min_win_rate, 2),
+            min_skill_score: Decimal::ZERO,
+            min_total_trades: min_trades,
+            min_signal_ev: Decimal::ZERO,
+            signal_notional_floor: Decimal::from(1000),
+            sizing: "kelly".into(),
+            size_multiplier: Decimal::ONE,
+            base_amount: Decimal::ZERO,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_assign_tier_picks_best_qualifying() {
+        // Stored out of order; assignment still tries A first
+        let mut a = policy("A", 65, 200);
+        a.min_skill_score = Decimal::new(95, 2);
+        let policies = vec![policy("C", 55, 50), a, policy("B", 60, 100)];
+        let tier = |wr: i64, skill: Option<Decimal>, trades: i32| {
+            assign_tier(&policies, Decimal::new(wr, 2), skill, trades).and_then(|p| p.whale_tier())
+        };
+
+        assert_eq!(tier(70, Some(Decimal::new(97, 2)), 300), Some(WhaleTier::A));
+        // Fails A's skill threshold
+        assert_eq!(tier(70, Some(Decimal::new(90, 2)), 300), Some(WhaleTier::B));
+        // Unscored skill fails A's threshold; B and C set none
+        assert_eq!(tier(70, None, 300), Some(WhaleTier::B));
+        assert_eq!(tier(62, None, 80), Some(WhaleTier::C));
+        assert_eq!(tier(50, None, 500), None);
+    }
+
+    #[test]
+    fn test_validate_policy() {
+        assert!(validate_policy(&policy("A", 65, 200)).is_ok());
+        assert!(validate_policy(&policy("D", 65, 200)).is_err());
+        assert!(validate_policy(&policy("A", 120, 200)).is_err());
+
+        let mut fixed = policy("C", 55, 50);
+        fixed.sizing = "fixed".into();
+        assert!(validate_policy(&fixed).is_err());
+        fixed.base_amount = Decimal::from(10);
+        assert!(validate_policy(&fixed).is_ok());
+        fixed.sizing = "martingale".into();
+        assert!(validate_policy(&fixed).is_err());
+    }
+}
@@ -12,15 +12,22 @@ pub mod services;
 
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
-use tokio::sync::broadcast;
+use tokio::sync::{broadcast, mpsc};
 
 use crate::api::ws_types::WsMessage;
 use crate::config::AppConfig;
+use crate::execution::circuit_breaker::CircuitBreaker;
+use crate::execution::copy_engine::ManualOrder;
+use crate::execution::risk_manager::SharedRiskLimits;
+use crate::ingestion::price_cache::PriceCache;
+use crate::intelligence::tiers::SharedTierPolicies;
 use crate::polymarket::balance::BalanceChecker;
 use crate::polymarket::clob_client::ClobClient;
 use crate::polymarket::trading::TradingClient;
 use crate::polymarket::wallet::PolymarketWallet;
+use crate::services::job_trigger::JobTriggers;
 use crate::services::notifier::Notifier;
+use crate::services::shutdown::Shutdown;
 
 #[derive(Clone)]
 pub struct AppState {
@@ -33,6 +40,20 @@ pub struct AppState {
     pub trading_client: Option<Arc<TradingClient>>,
     pub balance_checker: Option<Arc<BalanceChecker>>,
     pub clob_client: Option<Arc<ClobClient>>,
+    /// Best bid/ask per token from the market WebSocket; read before the CLOB.
+    pub price_cache: PriceCache,
     /// Global pause flag — when true, copy engine skips all signals.
     pub pause_flag: Arc<AtomicBool>,
+    /// Live risk limits shared with the copy engine and position monitor.
+    pub risk_limits: SharedRiskLimits,
+    /// Whale tier policies shared with the pipeline and copy engine.
+    pub tier_policies: SharedTierPolicies,
+    /// Execution circuit breaker — pauses the copy engine after repeated order failures.
+    pub circuit_breaker: CircuitBreaker,
+    /// Manual orders for the copy engine; None when the engine isn't running.
+    pub manual_order_tx: Option<mpsc::Sender<ManualOrder>>,
+    /// Triggers for running background job cycles on demand.
+    pub job_triggers: JobTriggers,
+    /// Set once graceful shutdown begins; the WS endpoint refuses new clients.
+    pub shutdown: Shutdown,
 }
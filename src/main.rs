@@ -1,3 +1,4 @@
+use std::future::IntoFuture;
 use std::sync::atomic::AtomicBool;
 use std::sync::Arc;
 use std::time::Instant;
@@ -7,21 +8,48 @@ use tokio::sync::broadcast;
 use polybot::api::router::create_router;
 use polybot::api::ws_types::WsMessage;
 use polybot::config::AppConfig;
-use polybot::execution::capital_pool::CapitalPool;
-use polybot::execution::copy_engine::{self, CopyEngineConfig};
-use polybot::execution::order_executor::OrderExecutor;
+use polybot::execution::circuit_breaker::{CircuitBreaker, CircuitBreakerConfig};
+use polybot::execution::copy_engine::{self, CopyEngineConfig, ManualOrder, WhaleExitMode};
+use polybot::execution::copy_profiles::parse_profiles;
+use polybot::execution::exit_escalation::{ExitEscalation, ExitEscalationConfig};
+use polybot::execution::fill_costs::{FillCostEstimator, MaticPriceFeed};
+use polybot::execution::hedging::HedgeConfig;
+use polybot::execution::order_executor::{ExecutionMode, ExecutionModes, OrderExecutor};
+use polybot::execution::order_rules::{OrderRulesConfig, PriceRounding};
 use polybot::execution::position_sizer::SizingStrategy;
 use polybot::execution::risk_manager::RiskLimits;
+use polybot::execution::position_sizer::{ConsensusSizing, FlowSizing, RampUpConfig};
+use polybot::execution::scale_in::ScaleInConfig;
+use polybot::execution::sleeves::{SleeveAllocation, SleevePools};
 use polybot::ingestion::chain_listener::run_chain_listener;
+use polybot::ingestion::market_enricher::{enrich_events, MarketEnricher};
 use polybot::ingestion::pipeline::{apply_runtime_overrides, process_trade_event, PipelineConfig};
+use polybot::ingestion::price_cache::PriceCache;
+use polybot::ingestion::priority_lanes::{prioritize, LaneConfig};
+use polybot::ingestion::user_ws_listener::run_user_ws_listener;
+use polybot::ingestion::wallet_workers::{ordering_key, WalletWorkers};
+use polybot::intelligence::flow::FlowConfig;
+use polybot::intelligence::insider::InsiderConfig;
+use polybot::intelligence::{build_wallet_classifier, ClassifierConfig, ConflictMode};
 use polybot::ingestion::ws_listener::run_ws_listener;
-use polybot::models::{CopySignal, WhaleTradeEvent};
+use polybot::models::{CopySignal, PriceTick, StopMode, TapePrint, WhaleTradeEvent};
 use std::collections::HashMap;
 use polybot::polymarket::{
     BalanceChecker, ClobClient, DataClient, GammaClient, PolymarketAuth, PolymarketWallet,
     TradingClient,
 };
-use polybot::services::notifier::Notifier;
+use polybot::services::capital_flows::CapitalFlowConfig;
+use polybot::services::copy_guard::CopyGuardConfig;
+use polybot::services::eod_reconciliation::{EodReconciliationConfig, ReconciliationSources};
+use polybot::services::entry_edge::EntryEdgeConfig;
+use polybot::services::event_queue::QueueAck;
+use polybot::services::holding_profile::HoldingProfileConfig;
+use polybot::services::job_trigger::{JobTrigger, JobTriggers};
+use polybot::services::leaderboard_drift::LeaderboardDriftConfig;
+use polybot::services::notifier::{DigestConfig, Notifier, Severity};
+use polybot::services::position_health::PositionHealthConfig;
+use polybot::services::shutdown::{Shutdown, TaskRegistry};
+use polybot::services::trade_size_stats::{TradeSizeStatsConfig, WhaleNotionalThreshold};
 use polybot::{db, metrics, services, AppState};
 
 #[tokio::main]
@@ -31,16 +59,34 @@ async fn main() -> anyhow::Result<()> {
         .install_default()
         .expect("Failed to install rustls CryptoProvider");
 
-    dotenvy::dotenv().ok();
+    let secrets_loaded = polybot::config::secrets::load_env_layers()?;
     init_tracing();
+    if secrets_loaded > 0 {
+        tracing::info!(count = secrets_loaded, "Loaded secrets from encrypted SECRETS_FILE");
+    }
 
     let config = AppConfig::from_env()?;
     let addr = format!("{}:{}", config.host, config.port);
 
+    // Deployment identity: tags background task logs, metrics and notifications
+    let config_fingerprint = config.fingerprint();
+    let deployment_tag = format!("{} · {}", config.environment, config_fingerprint);
+    tracing::info!(
+        environment = %config.environment,
+        config_fingerprint = %config_fingerprint,
+        "Starting polybot"
+    );
+
     // --- Prometheus metrics ---
-    let metrics_handle = metrics::init_metrics();
+    let metrics_handle = metrics::init_metrics_with_labels(&[("environment", &config.environment)]);
+    metrics::record_deployment_info(&config.environment, &config_fingerprint);
     tracing::info!("Prometheus metrics initialized");
 
+    // `polybot record` — research mode: record market data to disk, no execution
+    if std::env::args().nth(1).as_deref() == Some("record") {
+        return services::market_recorder::run_recorder(&config).await;
+    }
+
     tracing::info!("Connecting to database...");
     let db = db::init_pool(&config.database_url).await?;
     tracing::info!("Database connected");
@@ -51,14 +97,48 @@ async fn main() -> anyhow::Result<()> {
         .await?;
     tracing::info!("Database migrations applied");
 
+    // `polybot bootstrap` — one-shot first-run setup, then exit
+    if std::env::args().nth(1).as_deref() == Some("bootstrap") {
+        return services::bootstrap::run_bootstrap(&config, &db).await;
+    }
+
+    // Background tasks, aborted and reported at shutdown
+    let mut tasks = TaskRegistry::with_span(tracing::info_span!(
+        "deployment",
+        env = %config.environment,
+        config = %config_fingerprint
+    ));
+    let shutdown = Shutdown::default();
+
     // --- Telegram notifier ---
     let notifier: Option<Arc<Notifier>> = if config.notifications_enabled && config.has_telegram() {
-        let n = Notifier::new(
+        let digest_config = DigestConfig {
+            interval_mins: config.notify_digest_interval_mins,
+            severities: Severity::parse_list(&config.notify_digest_severities),
+            small_fill_usd: config.notify_small_fill_usd,
+        };
+        let (n, digest_rx) = Notifier::new(
             config.telegram_bot_token.clone().unwrap(),
             config.telegram_chat_id.clone().unwrap(),
-        );
+        )
+        .with_tag(deployment_tag)
+        .with_digest(&digest_config);
+        let n = Arc::new(n);
         tracing::info!("Telegram notifier enabled");
-        Some(Arc::new(n))
+
+        if let Some(rx) = digest_rx {
+            let digest_notifier = Arc::clone(&n);
+            let interval_mins = digest_config.interval_mins;
+            tasks.spawn("notify_digest", async move {
+                services::notifier::run_digest_dispatcher(digest_notifier, rx, interval_mins).await;
+            });
+            tracing::info!(
+                interval_mins,
+                severities = %config.notify_digest_severities,
+                "Notification digest enabled"
+            );
+        }
+        Some(n)
     } else {
         tracing::info!("Telegram notifications disabled");
         None
@@ -67,6 +147,27 @@ async fn main() -> anyhow::Result<()> {
     // --- Global pause flag ---
     let pause_flag = Arc::new(AtomicBool::new(false));
 
+    // --- Risk limits (persisted; env values seed the table on first run) ---
+    let seed_limits = RiskLimits {
+        max_daily_loss: config.max_daily_loss,
+        max_tail_loss_pct: config.max_tail_loss_pct,
+        ..Default::default()
+    };
+    let risk_limits = db::risk_limits_repo::get_or_init(&db, &seed_limits)
+        .await?
+        .into_shared();
+    tracing::info!(limits = ?*risk_limits.read().await, "Risk limits loaded");
+
+    // --- Whale tier policies (persisted; editable via the API) ---
+    let tier_policies = Arc::new(tokio::sync::RwLock::new(db::tier_repo::get_tier_policies(&db).await?));
+
+    // --- Execution circuit breaker ---
+    let circuit_breaker = CircuitBreaker::new(CircuitBreakerConfig {
+        max_failures: config.circuit_breaker_max_failures,
+        window: chrono::Duration::minutes(config.circuit_breaker_window_mins),
+        cooldown: chrono::Duration::minutes(config.circuit_breaker_cooldown_mins),
+    });
+
     // --- Wallet & trading client initialization ---
     let wallet: Option<Arc<PolymarketWallet>>;
     let trading_client: Option<Arc<TradingClient>>;
@@ -121,18 +222,24 @@ async fn main() -> anyhow::Result<()> {
         None
     };
 
+    // Immediate-cycle triggers for POST /api/admin/run/:job
+    let mut job_triggers = JobTriggers::default();
+
     // --- Whale seeder (periodic: seed new whales + deactivate stale ones) ---
     if config.whale_seeder_enabled {
         let seeder_data_client = DataClient::new(reqwest::Client::new());
         let seeder_db = db.clone();
         let seeder_config = config.clone();
+        let (trigger, seeder_runs) = JobTrigger::channel();
+        job_triggers.whale_seeder = Some(trigger);
         let seeder_interval = 3600; // Re-check every hour
-        tokio::spawn(async move {
+        tasks.spawn("whale_seeder", async move {
             services::whale_seeder::run_whale_seeder_loop(
                 seeder_data_client,
                 seeder_db,
                 seeder_config,
                 seeder_interval,
+                seeder_runs,
             )
             .await;
         });
@@ -146,14 +253,165 @@ async fn main() -> anyhow::Result<()> {
         let poller_db = db.clone();
         let data_client = DataClient::new(reqwest::Client::new());
         let notifier_clone = notifier.clone();
-        tokio::spawn(async move {
-            services::resolution::run_resolution_poller(poller_db, data_client, 300, notifier_clone).await;
+        let (trigger, resolution_runs) = JobTrigger::channel();
+        job_triggers.resolution = Some(trigger);
+        tasks.spawn("resolution_poller", async move {
+            services::resolution::run_resolution_poller(poller_db, data_client, 300, notifier_clone, resolution_runs)
+                .await;
         });
         tracing::info!("Market resolution poller spawned (interval=300s)");
     }
 
+    // --- Copy guard: pause whales whose copies lose money ---
+    {
+        let guard_db = db.clone();
+        let guard_notifier = notifier.clone();
+        let guard_config = CopyGuardConfig {
+            min_closed_positions: config.copy_guard_min_closed,
+            max_copy_loss: config.copy_guard_max_loss,
+            interval_secs: config.copy_guard_interval_secs,
+        };
+        tasks.spawn("copy_guard", async move {
+            services::copy_guard::run_copy_guard(guard_db, guard_config, guard_notifier).await;
+        });
+    }
+
+    // --- Leaderboard drift: flag whales whose leaderboard PnL collapses ---
+    if config.leaderboard_drift_max_drawdown_pct > Decimal::ZERO
+        && config.leaderboard_drift_interval_secs > 0
+    {
+        let drift_db = db.clone();
+        let drift_notifier = notifier.clone();
+        let drift_config = LeaderboardDriftConfig {
+            max_drawdown_pct: config.leaderboard_drift_max_drawdown_pct,
+            auto_pause: config.leaderboard_drift_auto_pause,
+            interval_secs: config.leaderboard_drift_interval_secs,
+        };
+        tasks.spawn("leaderboard_drift", async move {
+            services::leaderboard_drift::run_leaderboard_drift_check(
+                drift_db,
+                DataClient::new(reqwest::Client::new()),
+                drift_config,
+                drift_notifier,
+            )
+            .await;
+        });
+        tracing::info!(
+            max_drawdown_pct = %config.leaderboard_drift_max_drawdown_pct,
+            auto_pause = config.leaderboard_drift_auto_pause,
+            "Leaderboard drift check spawned"
+        );
+    }
+
+    // --- Capital flows: large whale USDC deposits/withdrawals on Polygon ---
+    if config.capital_flow_min_usdc > Decimal::ZERO
+        && config.capital_flow_interval_secs > 0
+        && !config.polygon_rpc_url.is_empty()
+    {
+        let flows_db = db.clone();
+        let flows_notifier = notifier.clone();
+        let flows_rpc_url = config.polygon_rpc_url.clone();
+        let flows_config = CapitalFlowConfig {
+            min_amount: config.capital_flow_min_usdc,
+            withdrawal_alert_pct: config.capital_flow_withdrawal_alert_pct,
+            interval_secs: config.capital_flow_interval_secs,
+        };
+        tasks.spawn("capital_flows", async move {
+            services::capital_flows::run_capital_flow_monitor(flows_db, flows_rpc_url, flows_config, flows_notifier)
+                .await;
+        });
+        tracing::info!(
+            min_usdc = %config.capital_flow_min_usdc,
+            withdrawal_alert_pct = %config.capital_flow_withdrawal_alert_pct,
+            "Whale capital flow monitor spawned"
+        );
+    }
+
+    // --- Insider detection: suspiciously timed large entries, alerted per flag ---
+    if config.insider_scan_interval_secs > 0 {
+        let insider_db = db.clone();
+        let insider_notifier = notifier.clone();
+        let insider_config = InsiderConfig {
+            min_notional: config.insider_min_notional,
+            resolution_window_hours: config.insider_resolution_window_hours,
+            jump_window_hours: config.insider_jump_window_hours,
+            min_price_move: config.insider_min_price_move,
+            lookback_days: config.insider_lookback_days,
+            interval_secs: config.insider_scan_interval_secs,
+        };
+        tasks.spawn("insider_scan", async move {
+            services::insider_scan::run_insider_scan(insider_db, insider_config, insider_notifier).await;
+        });
+        tracing::info!(
+            interval = config.insider_scan_interval_secs,
+            min_notional = %config.insider_min_notional,
+            "Insider scan spawned"
+        );
+    }
+
+    // --- Entry edge: price moves after whale entries, from recorded candles ---
+    if config.entry_edge_horizon_hours > 0 && config.entry_edge_interval_secs > 0 {
+        let edge_db = db.clone();
+        let edge_config = EntryEdgeConfig {
+            horizon_hours: config.entry_edge_horizon_hours,
+            interval_secs: config.entry_edge_interval_secs,
+        };
+        tasks.spawn("entry_edge", async move {
+            services::entry_edge::run_entry_edge_loop(edge_db, edge_config).await;
+        });
+        tracing::info!(
+            horizon_hours = config.entry_edge_horizon_hours,
+            "Entry edge analysis spawned"
+        );
+    }
+
+    // --- Holding profile: per-whale exit style for new copies ---
+    if config.holding_profile_interval_secs > 0 {
+        let profile_db = db.clone();
+        let profile_config = HoldingProfileConfig {
+            min_samples: config.holding_profile_min_samples,
+            swing_exit_ratio: config.holding_profile_swing_ratio,
+            interval_secs: config.holding_profile_interval_secs,
+        };
+        tasks.spawn("holding_profile", async move {
+            services::holding_profile::run_holding_profile_loop(profile_db, profile_config).await;
+        });
+        tracing::info!(
+            interval = config.holding_profile_interval_secs,
+            "Holding profile analysis spawned"
+        );
+    }
+
+    // --- Batch rescoring: whale scores from all resolved trades ---
+    if config.rescore_interval_secs > 0 {
+        let rescore_db = db.clone();
+        let rescore_interval = config.rescore_interval_secs;
+        let (trigger, rescore_runs) = JobTrigger::channel();
+        job_triggers.rescore = Some(trigger);
+        tasks.spawn("rescore", async move {
+            services::rescore::run_rescore_loop(rescore_db, rescore_interval, rescore_runs).await;
+        });
+        tracing::info!(interval = rescore_interval, "Batch rescoring spawned");
+    }
+
+    // --- Basket rebalancing: evict members that stopped qualifying, back-fill open slots ---
+    if config.basket_enabled && config.basket_rebalance_interval_secs > 0 {
+        let rebalance_db = db.clone();
+        let rebalance_config = config.clone();
+        let (trigger, rebalance_runs) = JobTrigger::channel();
+        job_triggers.basket_rebalance = Some(trigger);
+        tasks.spawn("basket_rebalance", async move {
+            services::basket_rebalance::run_basket_rebalance_loop(rebalance_db, rebalance_config, rebalance_runs).await;
+        });
+        tracing::info!(
+            interval = config.basket_rebalance_interval_secs,
+            "Basket rebalancing spawned"
+        );
+    }
+
     // --- Execution layer: copy engine ---
     let (signal_tx, signal_rx) = tokio::sync::mpsc::channel::<CopySignal>(500);
+    let (manual_order_tx, manual_order_rx) = tokio::sync::mpsc::channel::<ManualOrder>(16);
 
     // --- Capital pool ---
     // In dry-run mode always use config.bankroll (no real USDC needed).
@@ -167,8 +425,148 @@ async fn main() -> anyhow::Result<()> {
     } else {
         config.bankroll
     };
-    let capital_pool = CapitalPool::new(initial_balance);
-    tracing::info!(initial_balance = %initial_balance, "Capital pool initialized");
+    let sleeve_allocation =
+        SleeveAllocation::parse(&config.sleeve_weights).with_profiles(&parse_profiles(&config.copy_profiles));
+    // Capital held under a removed copy profile would be booked against single_whale
+    let unknown_labels: Vec<String> = db::position_repo::get_live_capital_labels(&db)
+        .await?
+        .into_iter()
+        .filter(|label| !sleeve_allocation.knows_label(label))
+        .collect();
+    if !unknown_labels.is_empty() {
+        anyhow::bail!(
+            "open positions or orders belong to sleeves or copy profiles that are not configured: {} \
+             (add them back to COPY_PROFILES until they are closed)",
+            unknown_labels.join(", ")
+        );
+    }
+    let capital_pool = SleevePools::new(initial_balance, sleeve_allocation.clone());
+    let reserve_floor_pct = risk_limits.read().await.reserve_floor_pct;
+    capital_pool.set_reserve_floor_pct(reserve_floor_pct).await;
+    tracing::info!(
+        initial_balance = %initial_balance,
+        sleeves = ?sleeve_allocation,
+        reserve_floor_pct = %reserve_floor_pct,
+        "Capital pools initialized per sleeve and copy profile"
+    );
+
+    // --- Position reconciler: DB positions vs account holdings ---
+    // Live only: simulated positions never exist on the account
+    if let Some(w) = wallet.as_ref().filter(|_| !dry_run_mode) {
+        if config.reconcile_interval_secs > 0 {
+            let reconciler_db = db.clone();
+            let data_client = DataClient::new(reqwest::Client::new());
+            let address = w.wallet_address();
+            let reconciler_config = services::position_reconciler::ReconcilerConfig {
+                interval_secs: config.reconcile_interval_secs,
+                auto_correct: config.reconcile_auto_correct,
+            };
+            let notifier_clone = notifier.clone();
+            let reconciler_capital = capital_pool.clone();
+            tasks.spawn("position_reconciler", async move {
+                services::position_reconciler::run_position_reconciler(
+                    reconciler_db,
+                    data_client,
+                    address,
+                    reconciler_config,
+                    reconciler_capital,
+                    notifier_clone,
+                )
+                .await;
+            });
+            tracing::info!(
+                interval = config.reconcile_interval_secs,
+                auto_correct = config.reconcile_auto_correct,
+                "Position reconciler spawned"
+            );
+        }
+    }
+
+    // --- Equity snapshots for the dashboard equity curve ---
+    if config.equity_snapshot_interval_secs > 0 {
+        let snapshot_db = db.clone();
+        let snapshot_capital = capital_pool.clone();
+        let snapshot_interval = config.equity_snapshot_interval_secs;
+        tasks.spawn("equity_snapshots", async move {
+            services::equity_snapshots::run_equity_snapshots(snapshot_db, snapshot_capital, snapshot_interval).await;
+        });
+        tracing::info!(interval = snapshot_interval, "Equity snapshot recorder spawned");
+    }
+
+    // --- Daily position health notification ---
+    if let Some(ref n) = notifier {
+        let health_db = db.clone();
+        let health_notifier = Arc::clone(n);
+        let health_config = PositionHealthConfig::from_config(&config);
+        tasks.spawn("position_health", async move {
+            services::position_health::run_position_health_report(health_db, health_notifier, health_config)
+                .await;
+        });
+        tracing::info!("Position health reporter spawned");
+    }
+
+    // --- End-of-day reconciliation: positions, CLOB orders, capital ledger ---
+    if let Some(ref w) = wallet {
+        if config.eod_reconcile_enabled {
+            let eod_db = db.clone();
+            let eod_notifier = notifier.clone();
+            let eod_sources = ReconciliationSources {
+                data_client: (!dry_run_mode).then(|| DataClient::new(reqwest::Client::new())),
+                wallet_address: w.wallet_address(),
+                trading_client: if dry_run_mode { None } else { trading_client.clone() },
+                balance_checker: if dry_run_mode { None } else { balance_checker.clone() },
+                capital: (!dry_run_mode).then(|| capital_pool.clone()),
+            };
+            let eod_config = EodReconciliationConfig {
+                report_hour_utc: config.eod_reconcile_hour,
+                balance_tolerance: config.eod_reconcile_balance_tolerance,
+            };
+            tasks.spawn("eod_reconciliation", async move {
+                services::eod_reconciliation::run_eod_reconciliation(eod_db, eod_sources, eod_config, eod_notifier)
+                    .await;
+            });
+            tracing::info!(hour_utc = config.eod_reconcile_hour, "End-of-day reconciliation spawned");
+        }
+    }
+
+    let scale_in_config = ScaleInConfig {
+        min_strength: config.scale_in_min_strength,
+        tranches: config.scale_in_tranches,
+        step_pct: config.scale_in_step_pct,
+    };
+    let ramp_up_config = RampUpConfig {
+        enabled: config.ramp_up_enabled,
+        initial_cap: config.ramp_up_initial_cap,
+        launch_trades: config.ramp_up_launch_trades,
+        launch_days: config.ramp_up_launch_days,
+        step_trades: config.ramp_up_step_trades,
+        step_multiplier: config.ramp_up_step_multiplier,
+        max_tracking_error: config.ramp_up_max_tracking_error,
+    };
+    let flow_sizing = FlowSizing {
+        base_amount: config.flow_base_amount,
+        reference_notional: config.flow_min_net_notional,
+        max_multiplier: config.flow_max_multiplier,
+    };
+    let consensus_sizing = ConsensusSizing {
+        base_amount: config.consensus_base_amount,
+        reference_win_rate: config.consensus_reference_win_rate,
+        max_multiplier: config.consensus_max_multiplier,
+    };
+
+    // --- Shared WS price cache (fed by the WS listener, read by the monitor) ---
+    let price_cache = PriceCache::new(chrono::Duration::seconds(config.price_cache_max_age_secs));
+
+    let exit_escalation = ExitEscalation {
+        config: ExitEscalationConfig {
+            reprice_secs: config.exit_reprice_secs,
+            step_pct: config.exit_reprice_step_pct,
+            max_reprices: config.exit_max_reprices,
+        },
+        price_cache: price_cache.clone(),
+        clob_client: clob_client.as_deref().cloned(),
+        notifier: notifier.clone(),
+    };
 
     if config.copy_enabled {
         let clob_client = if config.has_polymarket_auth() {
@@ -195,40 +593,65 @@ async fn main() -> anyhow::Result<()> {
             tracing::info!("Copy engine running in LIVE TAKER mode");
         }
 
-        let mut risk_limits = RiskLimits::default();
-        risk_limits.max_daily_loss = config.max_daily_loss;
+        // Per-origin overrides of the maker/taker default
+        let default_mode = if config.maker_mode { ExecutionMode::Maker } else { ExecutionMode::Taker };
+        let execution_modes = ExecutionModes::parse(&config.execution_modes, default_mode);
+        if !config.execution_modes.is_empty() {
+            tracing::info!(modes = ?execution_modes, "Execution modes per signal origin");
+        }
 
         let engine_config = CopyEngineConfig {
             strategy: SizingStrategy::parse_strategy(&config.copy_strategy),
             bankroll: config.bankroll,
             base_amount: config.base_copy_amount,
-            risk_limits: risk_limits.clone(),
+            risk_limits: Arc::clone(&risk_limits),
             dry_run,
             default_stop_loss_pct: config.default_stop_loss_pct,
             default_take_profit_pct: config.default_take_profit_pct,
+            default_stop_mode: StopMode::parse(&config.default_stop_mode).expect("validated by AppConfig::from_env"),
+            whale_exit_mode: WhaleExitMode::parse(&config.whale_exit_mode).unwrap_or(WhaleExitMode::Full),
             maker_mode: config.maker_mode,
             maker_order_ttl_secs: config.maker_order_ttl_secs,
+            order_size_decimals: config.order_size_decimals,
+            execution_modes: execution_modes.clone(),
+            sleeves: sleeve_allocation.clone(),
+            scale_in: scale_in_config.clone(),
+            ramp_up: ramp_up_config.clone(),
+            flow_sizing: flow_sizing.clone(),
+            consensus_sizing: consensus_sizing.clone(),
+            tiers: Arc::clone(&tier_policies),
         };
 
         // Build OrderExecutor with optional TradingClient for live execution
         let executor_trading = wallet.as_ref().map(|w| TradingClient::new(Arc::clone(w)));
+        let order_rules = OrderRulesConfig {
+            default_tick_size: config.order_tick_size,
+            default_min_order_size: config.order_min_size,
+            size_decimals: config.order_size_decimals,
+            price_rounding: PriceRounding::parse(&config.order_price_rounding)
+                .expect("validated by AppConfig::from_env"),
+        };
         let executor = OrderExecutor::new(
             executor_trading,
             clob_client,
-            risk_limits.clone(),
+            Arc::clone(&risk_limits),
             dry_run,
-            config.maker_mode,
+            order_rules,
         );
 
         let engine_db = db.clone();
         let engine_notifier = notifier.clone();
-        let engine_balance = wallet.as_ref().map(|w| BalanceChecker::new(Arc::clone(w)));
+        let engine_balance = wallet
+            .as_ref()
+            .map(|w| BalanceChecker::new(Arc::clone(w)).with_rpc_url(config.polygon_rpc_url.clone()));
         let engine_pause = Arc::clone(&pause_flag);
         let engine_capital = capital_pool.clone();
+        let engine_breaker = circuit_breaker.clone();
 
-        tokio::spawn(async move {
+        tasks.spawn("copy_engine", async move {
             copy_engine::run_copy_engine(
                 signal_rx,
+                manual_order_rx,
                 engine_db,
                 executor,
                 engine_config,
@@ -236,6 +659,8 @@ async fn main() -> anyhow::Result<()> {
                 engine_balance,
                 engine_pause,
                 engine_capital,
+                GammaClient::new(),
+                engine_breaker,
             )
             .await;
         });
@@ -252,29 +677,64 @@ async fn main() -> anyhow::Result<()> {
                 let poller_db = db.clone();
                 let poller_tc = Arc::clone(tc);
                 let poller_capital = capital_pool.clone();
+                let (trigger, fill_poller_runs) = JobTrigger::channel();
+                job_triggers.fill_poller = Some(trigger.clone());
+                let poller_escalation = exit_escalation.clone();
+                let poller_costs = FillCostEstimator::new(
+                    Arc::clone(tc),
+                    balance_checker.clone(),
+                    MaticPriceFeed::new(config.matic_usd_price_url.clone()),
+                );
                 let poller_config = CopyEngineConfig {
                     strategy: SizingStrategy::parse_strategy(&config.copy_strategy),
                     bankroll: config.bankroll,
                     base_amount: config.base_copy_amount,
-                    risk_limits: risk_limits.clone(),
+                    risk_limits: Arc::clone(&risk_limits),
                     dry_run: false,
                     default_stop_loss_pct: config.default_stop_loss_pct,
                     default_take_profit_pct: config.default_take_profit_pct,
+                    default_stop_mode: StopMode::parse(&config.default_stop_mode).expect("validated by AppConfig::from_env"),
+                    whale_exit_mode: WhaleExitMode::parse(&config.whale_exit_mode).unwrap_or(WhaleExitMode::Full),
                     maker_mode: config.maker_mode,
                     maker_order_ttl_secs: config.maker_order_ttl_secs,
+                    order_size_decimals: config.order_size_decimals,
+                    execution_modes: execution_modes.clone(),
+                    sleeves: sleeve_allocation.clone(),
+                    scale_in: scale_in_config.clone(),
+                    ramp_up: ramp_up_config.clone(),
+                    flow_sizing: flow_sizing.clone(),
+                    consensus_sizing: consensus_sizing.clone(),
+                    tiers: Arc::clone(&tier_policies),
                 };
 
-                tokio::spawn(async move {
+                tasks.spawn("order_fill_poller", async move {
                     services::order_fill_poller::run_order_fill_poller(
                         poller_db,
                         poller_tc,
                         poller_capital,
                         poller_config,
+                        poller_escalation,
+                        poller_costs,
                         10, // poll every 10 seconds
+                        fill_poller_runs,
                     )
                     .await;
                 });
                 tracing::info!("Order fill poller spawned (interval=10s)");
+
+                // User channel pushes our order/trade events: poll as soon as one lands
+                if config.has_polymarket_auth() && !config.polymarket_user_ws_url.is_empty() {
+                    let user_ws_url = config.polymarket_user_ws_url.clone();
+                    let user_auth = PolymarketAuth::new(
+                        config.polymarket_api_key.clone().unwrap(),
+                        config.polymarket_api_secret.clone().unwrap(),
+                        config.polymarket_passphrase.clone().unwrap(),
+                    );
+                    tasks.spawn("user_ws_listener", async move {
+                        run_user_ws_listener(user_ws_url, user_auth, trigger).await;
+                    });
+                    tracing::info!("User channel listener spawned (push fill confirmation)");
+                }
             }
         }
 
@@ -283,7 +743,7 @@ async fn main() -> anyhow::Result<()> {
             if let Some(ref bc_arc) = balance_checker {
                 let sync_capital = capital_pool.clone();
                 let sync_bc = BalanceChecker::new(Arc::clone(bc_arc.wallet()));
-                tokio::spawn(async move {
+                tasks.spawn("balance_sync", async move {
                     let mut ticker = tokio::time::interval(tokio::time::Duration::from_secs(60));
                     loop {
                         ticker.tick().await;
@@ -302,12 +762,22 @@ async fn main() -> anyhow::Result<()> {
         }
     } else {
         tracing::info!("Copy engine disabled (COPY_ENABLED=false)");
-        // Drop the receiver so pipeline doesn't block
+        // Drop the receivers so pipeline doesn't block
         drop(signal_rx);
+        drop(manual_order_rx);
     }
 
     // --- Watch channel for dynamic token subscription ---
-    let initial_tokens = config.ws_subscribe_token_ids.clone();
+    // Start from configured tokens plus markets persisted by previous discovery runs
+    let mut initial_tokens = config.ws_subscribe_token_ids.clone();
+    match db::market_repo::get_active_market_token_ids(&db, chrono::Utc::now()).await {
+        Ok(stored) => {
+            initial_tokens.extend(stored);
+            initial_tokens.sort();
+            initial_tokens.dedup();
+        }
+        Err(e) => tracing::warn!(error = %e, "Failed to load stored market tokens"),
+    }
     let (token_tx, token_rx) = tokio::sync::watch::channel(initial_tokens.clone());
 
     // --- Market discovery ---
@@ -317,8 +787,10 @@ async fn main() -> anyhow::Result<()> {
         let discovery_interval = config.market_discovery_interval_secs;
         let min_volume = config.market_min_volume;
         let min_liquidity = config.market_min_liquidity;
+        let (trigger, discovery_runs) = JobTrigger::channel();
+        job_triggers.market_discovery = Some(trigger);
 
-        tokio::spawn(async move {
+        tasks.spawn("market_discovery", async move {
             services::market_discovery::run_market_discovery(
                 gamma_client,
                 token_tx,
@@ -326,6 +798,7 @@ async fn main() -> anyhow::Result<()> {
                 discovery_interval,
                 min_volume,
                 min_liquidity,
+                discovery_runs,
             )
             .await;
         });
@@ -337,6 +810,9 @@ async fn main() -> anyhow::Result<()> {
         tracing::info!("Market discovery disabled (MARKET_DISCOVERY_ENABLED=false)");
     }
 
+    // --- WebSocket broadcast channel for dashboard ---
+    let (ws_broadcast_tx, _) = broadcast::channel::<WsMessage>(256);
+
     // --- Position monitor (SL/TP) ---
     if config.has_polymarket_auth() {
         let auth = PolymarketAuth::new(
@@ -351,9 +827,17 @@ async fn main() -> anyhow::Result<()> {
         let monitor_pause = Arc::clone(&pause_flag);
         let monitor_interval = config.position_monitor_interval_secs;
         let monitor_notifier = notifier.clone();
-        let monitor_capital = if monitor_dry { Some(capital_pool.clone()) } else { None };
+        let monitor_capital = capital_pool.clone();
+        let monitor_ws_tx = ws_broadcast_tx.clone();
+        let monitor_ws_delta = config.position_ws_delta_pct;
+        let monitor_limits = Arc::clone(&risk_limits);
+        let monitor_hedge = HedgeConfig {
+            loss_pct: config.hedge_loss_pct,
+            ratio_pct: config.hedge_ratio_pct,
+        };
+        let monitor_prices = price_cache.clone();
 
-        tokio::spawn(async move {
+        tasks.spawn("position_monitor", async move {
             services::position_monitor::run_position_monitor(
                 monitor_db,
                 monitor_clob,
@@ -363,6 +847,11 @@ async fn main() -> anyhow::Result<()> {
                 monitor_interval,
                 monitor_notifier,
                 monitor_capital,
+                Some(monitor_ws_tx),
+                monitor_ws_delta,
+                monitor_limits,
+                monitor_hedge,
+                monitor_prices,
             )
             .await;
         });
@@ -375,34 +864,94 @@ async fn main() -> anyhow::Result<()> {
     }
 
     // --- Data pipeline: ingestion → intelligence → execution ---
-    let (trade_tx, mut trade_rx) = tokio::sync::mpsc::channel::<WhaleTradeEvent>(1000);
+    // Kept short: events wait in the priority lanes (or durable queue) instead
+    let (trade_tx, mut trade_rx) = tokio::sync::mpsc::channel::<WhaleTradeEvent>(16);
+
+    // Priority lanes: tracked-whale trades overtake anonymous WS trades, which are shed when backed up
+    let trade_tx = prioritize(
+        &mut tasks,
+        trade_tx,
+        LaneConfig {
+            low_capacity: config.pipeline_low_priority_capacity,
+            low_sample_every: config.pipeline_low_priority_sample,
+        },
+    );
+
+    // Durable queue: whale trades go ingestion → Postgres → lanes, replaying in-flight
+    // events after a restart; anonymous trades go straight to the lanes
+    let (trade_tx, queue_ack) = if config.pipeline_durable_queue {
+        let (ingest_tx, ack) = services::event_queue::durable(
+            &mut tasks,
+            db.clone(),
+            trade_tx,
+            config.pipeline_queue_retention_hours,
+        );
+        tracing::info!(
+            retention_hours = config.pipeline_queue_retention_hours,
+            "Durable pipeline queue enabled"
+        );
+        (ingest_tx, Some(ack))
+    } else {
+        (trade_tx, None)
+    };
+
+    // Trade tape: WS and chain prints for the per-market time-and-sales view
+    let tape_tx = if config.trade_tape_retention_hours > 0 {
+        let (tape_tx, tape_rx) = tokio::sync::mpsc::channel::<TapePrint>(10_000);
+        let tape_db = db.clone();
+        let retention_hours = config.trade_tape_retention_hours;
+        tasks.spawn("trade_tape", async move {
+            services::trade_tape::run_trade_tape(tape_db, tape_rx, retention_hours).await;
+        });
+        Some(tape_tx)
+    } else {
+        None
+    };
 
     // WebSocket listener for market price awareness
     if !initial_tokens.is_empty() || config.market_discovery_enabled {
         let ws_url = config.polymarket_ws_url.clone();
-        let ws_trade_tx = trade_tx.clone();
+        let ws_trade_tx = services::trade_tape::tap(&mut tasks, "ws", &trade_tx, tape_tx.as_ref());
+        let (tick_tx, tick_rx) = tokio::sync::mpsc::channel::<PriceTick>(10_000);
+        let ws_prices = price_cache.clone();
+        let ws_stale_timeout = std::time::Duration::from_secs(config.ws_stale_timeout_secs);
+
+        let recorder_db = db.clone();
+        let retention_days = config.candle_retention_days;
+        tasks.spawn("candle_recorder", async move {
+            services::candle_recorder::run_candle_recorder(recorder_db, tick_rx, retention_days).await;
+        });
+
         tracing::info!(
             initial_tokens = initial_tokens.len(),
             market_discovery = config.market_discovery_enabled,
             "Starting WebSocket listener"
         );
-        tokio::spawn(async move {
-            run_ws_listener(ws_url, token_rx, ws_trade_tx).await;
+        tasks.spawn("ws_listener", async move {
+            run_ws_listener(ws_url, token_rx, ws_trade_tx, tick_tx, ws_prices, ws_stale_timeout).await;
         });
     } else {
         tracing::warn!("No token IDs and market discovery disabled — WebSocket listener will not start");
     }
 
     // Chain listener — low-latency on-chain OrderFilled event monitoring
-    let chain_listener_active = config.chain_listener_enabled && config.polygon_ws_url.is_some();
+    let chain_listener_active = config.chain_listener_enabled && !config.polygon_ws_urls.is_empty();
     if chain_listener_active {
-        let chain_ws_url = config.polygon_ws_url.clone().unwrap();
+        let chain_ws_urls = config.polygon_ws_urls.clone();
+        let chain_rpc_url = config.polygon_rpc_url.clone();
+        let chain_confirmations = config.chain_confirmations;
         let chain_db = db.clone();
-        let chain_tx = trade_tx.clone();
-        tokio::spawn(async move {
-            run_chain_listener(chain_ws_url, chain_db, chain_tx).await;
+        let chain_tx = services::trade_tape::tap(&mut tasks, "chain", &trade_tx, tape_tx.as_ref());
+        // Chain fills only know the outcome token: key them by condition ID first
+        let enricher = MarketEnricher::new(db.clone(), GammaClient::new());
+        let chain_tx = enrich_events(&mut tasks, enricher, &chain_tx);
+        tasks.spawn("chain_listener", async move {
+            run_chain_listener(chain_ws_urls, chain_rpc_url, chain_confirmations, chain_db, chain_tx).await;
         });
-        tracing::info!("Chain listener spawned (Polygon WSS OrderFilled events)");
+        tracing::info!(
+            endpoints = config.polygon_ws_urls.len(),
+            "Chain listener spawned (Polygon WSS OrderFilled + NegRisk adapter events)"
+        );
     } else if config.chain_listener_enabled {
         tracing::warn!("Chain listener enabled but POLYGON_WS_URL not set — skipping");
     }
@@ -413,6 +962,9 @@ async fn main() -> anyhow::Result<()> {
         let poller_data_client = DataClient::new(reqwest::Client::new());
         let poller_db = db.clone();
         let poller_tx = trade_tx.clone();
+        let poller_notifier = notifier.clone();
+        let poller_max_backoff = config.whale_poll_max_backoff_secs;
+        let poller_alert_hours = config.whale_poll_alert_hours;
         let poller_interval = if chain_listener_active {
             tracing::info!("Whale poller interval increased to 300s (chain listener active)");
             300
@@ -420,12 +972,15 @@ async fn main() -> anyhow::Result<()> {
             config.whale_poller_interval_secs
         };
 
-        tokio::spawn(async move {
+        tasks.spawn("whale_trade_poller", async move {
             services::whale_trade_poller::run_whale_trade_poller(
                 poller_data_client,
                 poller_db,
                 poller_tx,
                 poller_interval,
+                poller_max_backoff,
+                poller_alert_hours,
+                poller_notifier,
             )
             .await;
         });
@@ -438,55 +993,145 @@ async fn main() -> anyhow::Result<()> {
     // Drop the original sender so the pipeline shuts down when all senders are done
     drop(trade_tx);
 
+    // Market-wide trade size stats — keeps the UNKNOWN-wallet whale-grade threshold current
+    let whale_notional = WhaleNotionalThreshold::default();
+    {
+        let stats_data_client = DataClient::new(reqwest::Client::new());
+        let stats_config = TradeSizeStatsConfig {
+            percentile: config.whale_notional_percentile,
+            window_hours: config.whale_notional_window_hours,
+            min_samples: config.whale_notional_min_samples,
+            floor: config.whale_notional_floor,
+            interval_secs: config.whale_notional_stats_interval_secs,
+        };
+        let stats_threshold = whale_notional.clone();
+        tasks.spawn("trade_size_stats", async move {
+            services::trade_size_stats::run_trade_size_stats(stats_data_client, stats_config, stats_threshold).await;
+        });
+        tracing::info!(
+            percentile = %config.whale_notional_percentile,
+            window_hours = config.whale_notional_window_hours,
+            "Trade size stats spawned"
+        );
+    }
+
     // Pipeline consumer: intelligence + signal emission
+    let classifier_config = ClassifierConfig {
+        kind: config.wallet_classifier.clone(),
+        min_labels: config.wallet_classifier_min_labels,
+        min_confidence: config.wallet_classifier_min_confidence,
+        min_trades: config.wallet_classifier_min_trades,
+    };
+    let wallet_classifier = build_wallet_classifier(&db, &classifier_config).await;
+    tracing::info!(classifier = wallet_classifier.name(), "Wallet classifier ready");
     {
         let pipeline_db = db.clone();
         let copy_enabled = config.copy_enabled;
         let pipeline_notifier = notifier.clone();
         let pipeline_config = PipelineConfig {
             tracked_whale_min_notional: config.tracked_whale_min_notional,
+            whale_notional,
             min_signal_win_rate: config.min_signal_win_rate,
+            min_signal_win_rate_lb: config.min_signal_win_rate_lb,
             min_resolved_for_signal: config.min_resolved_for_signal,
+            min_entry_edge: config.min_entry_edge,
+            min_entry_edge_samples: config.min_entry_edge_samples,
             min_total_trades_for_signal: config.min_total_trades_for_signal,
             signal_notional_liquidity_pct: config.signal_notional_liquidity_pct,
             signal_notional_floor: config.signal_notional_floor,
             max_signal_notional: config.max_signal_notional,
             min_signal_ev: config.min_signal_ev,
+            min_signal_profit_factor: config.min_signal_profit_factor,
+            min_signal_skill_score: config.min_signal_skill_score,
             assumed_slippage_pct: config.assumed_slippage_pct,
+            copy_lag_min_samples: config.copy_lag_min_samples,
+            cost_rate: Default::default(),
+            signal_ttl_secs: config.signal_ttl_secs,
             signal_dedup_window_secs: 10,
+            first_mover_min_notional: config.first_mover_min_notional,
+            first_mover_size_multiplier: config.first_mover_size_multiplier,
+            basket_max_drawdown_pct: config.basket_max_drawdown_pct,
+            basket_min_skill_score: config.basket_min_skill_score,
+            basket_correlation_threshold: config.basket_correlation_threshold,
+            basket_correlation_days: config.basket_correlation_days,
+            basket_consensus_cooldown_hours: config.basket_consensus_cooldown_hours,
+            basket_building_alert_pct: config
+                .basket_building_alerts
+                .then_some(config.basket_building_threshold),
+            basket_conflict_mode: ConflictMode::parse(&config.basket_conflict_mode).unwrap_or(ConflictMode::Stronger),
+            basket_max_insider_flags: config.basket_max_insider_flags,
+            profiles: parse_profiles(&config.copy_profiles),
+            classifier: wallet_classifier,
+            flow: FlowConfig {
+                window_minutes: config.flow_window_minutes,
+                min_net_notional: config.flow_min_net_notional,
+                min_whales: config.flow_min_whales,
+                min_imbalance: config.flow_min_imbalance,
+            },
+            tiers: config.whale_tiers_enabled.then(|| Arc::clone(&tier_policies)),
+            gate_profile: None,
         };
         let dedup_state = Arc::new(tokio::sync::Mutex::new(HashMap::<String, Instant>::new()));
-        tokio::spawn(async move {
-            let signal_sender = if copy_enabled { Some(&signal_tx) } else { None };
+        let pipeline_config = Arc::new(pipeline_config);
+        let signal_sender = copy_enabled.then_some(signal_tx);
+
+        // Workers process different wallets concurrently, each wallet's trades in order
+        let worker_ack = queue_ack.clone();
+        let workers = WalletWorkers::spawn(
+            &mut tasks,
+            config.pipeline_workers,
+            move |(event, queue_id): (WhaleTradeEvent, Option<i64>)| {
+                let pipeline_db = pipeline_db.clone();
+                let signal_sender = signal_sender.clone();
+                let pipeline_notifier = pipeline_notifier.clone();
+                let pipeline_config = Arc::clone(&pipeline_config);
+                let dedup_state = Arc::clone(&dedup_state);
+                let worker_ack = worker_ack.clone();
+                async move {
+                    let effective_config = apply_runtime_overrides(&pipeline_config, &pipeline_db).await;
+                    if let Err(e) = process_trade_event(
+                        &event,
+                        &pipeline_db,
+                        signal_sender.as_ref(),
+                        pipeline_notifier.as_deref(),
+                        &effective_config,
+                        &dedup_state,
+                    ).await {
+                        tracing::error!(
+                            error = %e,
+                            wallet = %event.wallet,
+                            "Pipeline processing failed"
+                        );
+                    }
+                    // Failures are logged, not retried — the event is done either way
+                    if let (Some(ack), Some(id)) = (&worker_ack, queue_id) {
+                        ack.done(id);
+                    }
+                }
+            },
+        );
+        tracing::info!(workers = config.pipeline_workers.max(1), "Pipeline workers spawned");
+
+        tasks.spawn("pipeline", async move {
             while let Some(event) = trade_rx.recv().await {
                 tracing::debug!(
                     wallet = %event.wallet,
                     notional = %event.notional,
                     "WhaleTradeEvent received in pipeline"
                 );
-                let effective_config = apply_runtime_overrides(&pipeline_config, &pipeline_db).await;
-                if let Err(e) = process_trade_event(
-                    &event,
-                    &pipeline_db,
-                    signal_sender,
-                    pipeline_notifier.as_deref(),
-                    &effective_config,
-                    &dedup_state,
-                ).await {
-                    tracing::error!(
-                        error = %e,
-                        wallet = %event.wallet,
-                        "Pipeline processing failed"
-                    );
+                let queue_id = queue_ack
+                    .as_ref()
+                    .filter(|_| services::event_queue::is_queued(&event))
+                    .and_then(QueueAck::received);
+                if !workers.dispatch(&ordering_key(&event), (event, queue_id)).await {
+                    break;
                 }
             }
             tracing::warn!("WhaleTradeEvent channel closed");
         });
     }
 
-    // --- WebSocket broadcast channel for dashboard ---
-    let (ws_broadcast_tx, _) = broadcast::channel::<WsMessage>(256);
-
+    let manual_order_tx = config.copy_enabled.then_some(manual_order_tx);
     let state = AppState {
         db,
         config,
@@ -497,27 +1142,75 @@ async fn main() -> anyhow::Result<()> {
         trading_client,
         balance_checker,
         clob_client,
+        price_cache: price_cache.clone(),
         pause_flag,
+        risk_limits,
+        tier_policies,
+        circuit_breaker,
+        manual_order_tx,
+        job_triggers,
+        shutdown: shutdown.clone(),
     };
+    let shutdown_deadline = std::time::Duration::from_secs(state.config.shutdown_deadline_secs);
     let router = create_router(state);
 
     let listener = tokio::net::TcpListener::bind(&addr).await?;
     tracing::info!("Server listening on {addr}");
 
     // --- Graceful shutdown ---
-    axum::serve(listener, router)
-        .with_graceful_shutdown(shutdown_signal())
-        .await?;
+    // The signal flips `shutdown`, which closes dashboard sockets; open
+    // connections then get `shutdown_deadline` to drain.
+    let server = axum::serve(listener, router)
+        .with_graceful_shutdown({
+            let shutdown = shutdown.clone();
+            async move {
+                shutdown_signal().await;
+                shutdown.begin();
+            }
+        })
+        .into_future();
+    let deadline = async {
+        shutdown.wait().await;
+        tokio::time::sleep(shutdown_deadline).await;
+    };
+    tokio::select! {
+        result = server => result?,
+        _ = deadline => {
+            tracing::warn!(
+                deadline_secs = shutdown_deadline.as_secs(),
+                "Shutdown deadline reached with connections still open"
+            );
+        }
+    }
 
+    let aborted = tasks.abort_all();
+    tracing::info!(aborted = ?aborted, count = aborted.len(), "Background tasks aborted");
     tracing::info!("Shutting down gracefully...");
     Ok(())
 }
 
 async fn shutdown_signal() {
-    tokio::signal::ctrl_c()
-        .await
-        .expect("failed to listen for ctrl+c");
-    tracing::info!("Received SIGINT (Ctrl+C), starting graceful shutdown...");
+    #[cfg(unix)]
+    {
+        let mut sigterm = tokio::signal::unix::signal(tokio::signal::unix::SignalKind::terminate())
+            .expect("failed to listen for SIGTERM");
+        tokio::select! {
+            result = tokio::signal::ctrl_c() => {
+                result.expect("failed to listen for ctrl+c");
+                tracing::info!("Received SIGINT (Ctrl+C), starting graceful shutdown...");
+            }
+            _ = sigterm.recv() => {
+                tracing::info!("Received SIGTERM, starting graceful shutdown...");
+            }
+        }
+    }
+    #[cfg(not(unix))]
+    {
+        tokio::signal::ctrl_c()
+            .await
+            .expect("failed to listen for ctrl+c");
+        tracing::info!("Received SIGINT (Ctrl+C), starting graceful shutdown...");
+    }
 }
 
 fn init_tracing() {
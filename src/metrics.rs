@@ -8,7 +8,15 @@ use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
 /// Safe to call multiple times (e.g. in tests) — subsequent calls return a
 /// new handle but silently ignore the global recorder installation error.
 pub fn init_metrics() -> PrometheusHandle {
-    let builder = PrometheusBuilder::new();
+    init_metrics_with_labels(&[])
+}
+
+/// Like [`init_metrics`], with labels added to every exported series (e.g.
+/// the deployment environment).
+pub fn init_metrics_with_labels(global_labels: &[(&str, &str)]) -> PrometheusHandle {
+    let builder = global_labels
+        .iter()
+        .fold(PrometheusBuilder::new(), |b, (k, v)| b.add_global_label(*k, *v));
     let handle = match builder.install_recorder() {
         Ok(h) => h,
         Err(_) => {
@@ -22,17 +30,44 @@ pub fn init_metrics() -> PrometheusHandle {
 
     // Pre-register counters so they appear even before the first increment.
     counter!("trade_events_total").absolute(0);
+    counter!("trade_events_duplicate").absolute(0);
     counter!("copy_signals_emitted").absolute(0);
     counter!("orders_filled").absolute(0);
     counter!("orders_failed").absolute(0);
     counter!("consensus_signals_total").absolute(0);
+    counter!("first_mover_trades").absolute(0);
+    counter!("stale_replays_not_copied").absolute(0);
+    counter!("whale_poll_errors_total").absolute(0);
+    counter!("position_price_cache_hits").absolute(0);
+    counter!("position_price_cache_misses").absolute(0);
+    counter!("exit_orders_repriced").absolute(0);
+    counter!("recorder_messages_total").absolute(0);
+    counter!("recorder_messages_dropped").absolute(0);
+    counter!("ws_stale_reconnects_total").absolute(0);
 
     // Pre-register gauges at zero.
     gauge!("active_whales").set(0.0);
     gauge!("open_positions").set(0.0);
+    gauge!("portfolio_value_at_risk").set(0.0);
+    gauge!("circuit_breaker_tripped").set(0.0);
+    gauge!("whales_backed_off").set(0.0);
+    gauge!("position_drift_count").set(0.0);
+    gauge!("portfolio_equity").set(0.0);
+    gauge!("whale_notional_threshold").set(0.0);
+    gauge!("position_ramp_up_cap").set(0.0);
 
     // Histogram is lazily created on first record; force creation.
     histogram!("pipeline_latency_seconds").record(0.0);
 
     handle
 }
+
+/// Constant 1 gauge identifying the running deployment and config.
+pub fn record_deployment_info(environment: &str, config_fingerprint: &str) {
+    gauge!(
+        "deployment_info",
+        "environment" => environment.to_string(),
+        "config_fingerprint" => config_fingerprint.to_string()
+    )
+    .set(1.0);
+}
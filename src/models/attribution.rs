@@ -0,0 +1,44 @@
+use chrono::{DateTime, NaiveDate, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row for pnl_attributions — the part of a settled position's
+/// realized PnL owed to one whale and/or basket.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PnlAttribution {
+    pub id: Uuid,
+    pub position_id: Uuid,
+    pub whale_id: Option<Uuid>,
+    pub basket_id: Option<Uuid>,
+    /// Fraction of the position's filled BUY size from this source.
+    pub share: Decimal,
+    pub realized_pnl: Decimal,
+    pub attributed_at: DateTime<Utc>,
+}
+
+/// PnL of the positions opened from one basket's consensus signals.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BasketPerformance {
+    pub basket_id: Uuid,
+    /// Consensus signals the basket has emitted.
+    pub consensus_signals: i64,
+    pub settled_positions: i64,
+    pub winning_positions: i64,
+    /// Hit rate: winning / settled positions.
+    pub win_rate: Option<Decimal>,
+    pub realized_pnl: Decimal,
+    /// Positions from the basket's signals still open, and their mark-to-market PnL.
+    pub open_positions: i64,
+    pub unrealized_pnl: Decimal,
+    /// Attributed realized PnL per day the positions closed, with its running total.
+    pub pnl_curve: Vec<BasketPnlPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BasketPnlPoint {
+    pub date: NaiveDate,
+    pub daily_pnl: Decimal,
+    pub cumulative_pnl: Decimal,
+}
@@ -4,6 +4,9 @@ use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Longest consensus window a basket may use.
+pub const MAX_BASKET_WINDOW_HOURS: i32 = 720;
+
 /// A whale basket — a group of whales categorised by topic.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct WhaleBasket {
@@ -17,6 +20,56 @@ pub struct WhaleBasket {
     pub is_active: bool,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
+    /// How consensus votes are weighted: `equal`, `win_rate`, `ev` or `notional`.
+    pub vote_weighting: String,
+}
+
+impl WhaleBasket {
+    pub fn weighting(&self) -> VoteWeighting {
+        VoteWeighting::parse(&self.vote_weighting).unwrap_or(VoteWeighting::Equal)
+    }
+
+    /// Check the basket's editable settings.
+    pub fn validate(&self) -> Result<(), String> {
+        if BasketCategory::parse_category(&self.category).is_none() {
+            return Err(format!("category must be politics, crypto or sports, not '{}'", self.category));
+        }
+        if self.consensus_threshold <= Decimal::ZERO || self.consensus_threshold > Decimal::ONE {
+            return Err(format!(
+                "consensus_threshold must be in (0, 1], got {}",
+                self.consensus_threshold
+            ));
+        }
+        if !(1..=MAX_BASKET_WINDOW_HOURS).contains(&self.time_window_hours) {
+            return Err(format!("time_window_hours must be between 1 and {MAX_BASKET_WINDOW_HOURS}"));
+        }
+        if self.min_wallets < 1 {
+            return Err("min_wallets must be at least 1".into());
+        }
+        if self.max_wallets < self.min_wallets {
+            return Err(format!(
+                "max_wallets ({}) must not be below min_wallets ({})",
+                self.max_wallets, self.min_wallets
+            ));
+        }
+        if VoteWeighting::parse(&self.vote_weighting).is_none() {
+            return Err(format!(
+                "vote_weighting must be equal, win_rate, ev or notional, not '{}'",
+                self.vote_weighting
+            ));
+        }
+        Ok(())
+    }
+
+    /// Consensus cooldown on a market: the configured hours, or the basket's
+    /// time window when that's 0.
+    pub fn cooldown_hours(&self, configured: i32) -> i32 {
+        if configured > 0 {
+            configured
+        } else {
+            self.time_window_hours
+        }
+    }
 }
 
 /// Association between a basket and a whale.
@@ -28,6 +81,57 @@ pub struct BasketWallet {
     pub added_at: DateTime<Utc>,
 }
 
+/// A whale added to or removed from a basket, with why (audit log).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct BasketMembershipEvent {
+    pub id: Uuid,
+    pub basket_id: Uuid,
+    pub whale_id: Uuid,
+    /// "added" or "removed".
+    pub action: String,
+    /// Who made the change: "operator", "auto_assign" or "rebalance".
+    pub actor: String,
+    pub reason: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// A consensus that would have fired when replaying a basket's history.
+#[derive(Debug, Clone, Serialize)]
+pub struct BacktestConsensusEvent {
+    pub market_id: String,
+    pub direction: String,
+    pub consensus_pct: Decimal,
+    pub participating: i32,
+    pub total_whales: i32,
+    pub fired_at: DateTime<Utc>,
+    /// Price of the member trade that tipped the basket into consensus.
+    pub price: Decimal,
+    /// The market's outcome; None or "unresolved" while it's open.
+    pub outcome: Option<String>,
+    /// Profit per USDC staked at `price` in `direction`, once resolved.
+    pub return_per_usdc: Option<Decimal>,
+}
+
+/// Replay of a basket's consensus over a date range, with its current members.
+#[derive(Debug, Clone, Serialize)]
+pub struct BasketBacktest {
+    pub basket_id: Uuid,
+    pub from: DateTime<Utc>,
+    pub to: DateTime<Utc>,
+    pub whales: i32,
+    pub trades: usize,
+    /// Consensus events that would have fired.
+    pub fired: usize,
+    pub resolved: usize,
+    pub wins: usize,
+    pub losses: usize,
+    /// wins / resolved; None before any event resolves.
+    pub hit_rate: Option<Decimal>,
+    /// Sum of per-USDC returns over resolved events, i.e. the PnL of staking 1 USDC on each.
+    pub total_return: Decimal,
+    pub events: Vec<BacktestConsensusEvent>,
+}
+
 /// A recorded consensus signal (audit log).
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct ConsensusSignal {
@@ -39,6 +143,51 @@ pub struct ConsensusSignal {
     pub participating_whales: i32,
     pub total_whales: i32,
     pub triggered_at: DateTime<Utc>,
+    /// Until when this signal suppresses further consensus on its basket and market.
+    pub cooldown_until: DateTime<Utc>,
+    /// Held back by an opposite consensus in another basket (block mode); not traded.
+    pub blocked: bool,
+}
+
+/// How much each whale's vote counts towards a basket's consensus.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum VoteWeighting {
+    /// One whale, one vote.
+    Equal,
+    /// By the whale's win rate.
+    WinRate,
+    /// By the whale's current expected value per trade (negative EV counts nothing).
+    Ev,
+    /// By the notional of the voting trade.
+    Notional,
+}
+
+impl VoteWeighting {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VoteWeighting::Equal => "equal",
+            VoteWeighting::WinRate => "win_rate",
+            VoteWeighting::Ev => "ev",
+            VoteWeighting::Notional => "notional",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "equal" => Some(VoteWeighting::Equal),
+            "win_rate" => Some(VoteWeighting::WinRate),
+            "ev" => Some(VoteWeighting::Ev),
+            "notional" => Some(VoteWeighting::Notional),
+            _ => None,
+        }
+    }
+}
+
+impl std::fmt::Display for VoteWeighting {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
 }
 
 /// Basket category taxonomy.
@@ -50,6 +199,12 @@ pub enum BasketCategory {
 }
 
 impl BasketCategory {
+    pub const ALL: [BasketCategory; 3] = [
+        BasketCategory::Politics,
+        BasketCategory::Crypto,
+        BasketCategory::Sports,
+    ];
+
     pub fn as_str(&self) -> &'static str {
         match self {
             BasketCategory::Politics => "politics",
@@ -73,3 +228,45 @@ impl std::fmt::Display for BasketCategory {
         f.write_str(self.as_str())
     }
 }
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_basket_validate() {
+        let now = Utc::now();
+        let basket = WhaleBasket {
+            id: Uuid::new_v4(),
+            name: "Politics Whales".into(),
+            category: "politics".into(),
+            consensus_threshold: Decimal::new(80, 2),
+            time_window_hours: 48,
+            min_wallets: 5,
+            max_wallets: 10,
+            is_active: true,
+            created_at: now,
+            updated_at: now,
+            vote_weighting: "equal".into(),
+        };
+        assert!(basket.validate().is_ok());
+
+        let invalid = [
+            WhaleBasket { category: "weather".into(), ..basket.clone() },
+            WhaleBasket { consensus_threshold: Decimal::ZERO, ..basket.clone() },
+            WhaleBasket { consensus_threshold: Decimal::new(11, 1), ..basket.clone() },
+            WhaleBasket { time_window_hours: 0, ..basket.clone() },
+            WhaleBasket { time_window_hours: MAX_BASKET_WINDOW_HOURS + 1, ..basket.clone() },
+            WhaleBasket { min_wallets: 0, ..basket.clone() },
+            WhaleBasket { max_wallets: 4, ..basket.clone() },
+            WhaleBasket { vote_weighting: "loudest".into(), ..basket.clone() },
+        ];
+        for b in invalid {
+            assert!(b.validate().is_err(), "{b:?} should be invalid");
+        }
+    }
+}
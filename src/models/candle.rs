@@ -0,0 +1,59 @@
+use chrono::{DateTime, DurationRound, TimeDelta, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A single price observation for a token, fed into the candle recorder.
+#[derive(Debug, Clone)]
+pub struct PriceTick {
+    pub token_id: String,
+    pub price: Decimal,
+    /// Traded size — zero for quote updates (price_change midpoints).
+    pub size: Decimal,
+    pub timestamp: DateTime<Utc>,
+}
+
+/// Database row for market_candles table (1-minute OHLC per token).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct Candle {
+    pub token_id: String,
+    pub bucket: DateTime<Utc>,
+    pub open: Decimal,
+    pub high: Decimal,
+    pub low: Decimal,
+    pub close: Decimal,
+    pub volume: Decimal,
+    pub trade_count: i32,
+}
+
+impl Candle {
+    /// Open a new candle from the first tick of its minute.
+    pub fn from_tick(tick: &PriceTick) -> Self {
+        Self {
+            token_id: tick.token_id.clone(),
+            bucket: minute_bucket(tick.timestamp),
+            open: tick.price,
+            high: tick.price,
+            low: tick.price,
+            close: tick.price,
+            volume: tick.size,
+            trade_count: i32::from(tick.size > Decimal::ZERO),
+        }
+    }
+
+    /// Fold a later tick from the same minute into this candle.
+    pub fn apply(&mut self, tick: &PriceTick) {
+        self.high = self.high.max(tick.price);
+        self.low = self.low.min(tick.price);
+        self.close = tick.price;
+        if tick.size > Decimal::ZERO {
+            self.volume += tick.size;
+            self.trade_count += 1;
+        }
+    }
+}
+
+/// Start of the UTC minute containing `ts`.
+pub fn minute_bucket(ts: DateTime<Utc>) -> DateTime<Utc> {
+    ts.duration_trunc(TimeDelta::minutes(1)).unwrap_or(ts)
+}
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use uuid::Uuid;
+
+/// Which way USDC moved relative to the whale's wallet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CapitalFlowDirection {
+    Deposit,
+    Withdrawal,
+}
+
+impl CapitalFlowDirection {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            CapitalFlowDirection::Deposit => "deposit",
+            CapitalFlowDirection::Withdrawal => "withdrawal",
+        }
+    }
+}
+
+impl fmt::Display for CapitalFlowDirection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Database row for whale_capital_flows table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WhaleCapitalFlow {
+    pub id: Uuid,
+    pub whale_id: Uuid,
+    /// One of `deposit`, `withdrawal`.
+    pub direction: String,
+    pub amount: Decimal,
+    pub counterparty: String,
+    pub balance_after: Option<Decimal>,
+    /// Percent of the whale's USDC a withdrawal took out.
+    pub capital_share_pct: Option<Decimal>,
+    pub tx_hash: String,
+    pub log_index: i64,
+    pub block_number: i64,
+    pub occurred_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// A stored classifier decision for a whale: the features measured on its
+/// trades and the rules that decided the classification.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ClassificationAudit {
+    pub id: Uuid,
+    pub whale_id: Uuid,
+    pub classification: String,
+    /// `heuristic` or `logistic`.
+    pub classifier: String,
+    pub trade_count: i32,
+    pub trades_per_month: Decimal,
+    pub dual_side_ratio: Decimal,
+    pub avg_hold_hours: Decimal,
+    pub notional_cv: Decimal,
+    /// Model probability of the chosen class (logistic classifier only).
+    pub confidence: Option<Decimal>,
+    pub rules: Vec<String>,
+    pub created_at: DateTime<Utc>,
+    /// Last time the same classification was re-derived.
+    pub updated_at: DateTime<Utc>,
+}
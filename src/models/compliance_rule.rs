@@ -0,0 +1,60 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use uuid::Uuid;
+
+/// Kind of pre-trade compliance rule an operator can configure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ComplianceRuleType {
+    /// Skip markets carrying this Gamma tag (slug or label).
+    ExcludedTag,
+    /// Skip entries priced above this value (heavy favourites).
+    MaxOdds,
+    /// Skip markets whose question contains this phrase.
+    BannedKeyword,
+}
+
+impl ComplianceRuleType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ComplianceRuleType::ExcludedTag => "excluded_tag",
+            ComplianceRuleType::MaxOdds => "max_odds",
+            ComplianceRuleType::BannedKeyword => "banned_keyword",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "excluded_tag" => Some(ComplianceRuleType::ExcludedTag),
+            "max_odds" => Some(ComplianceRuleType::MaxOdds),
+            "banned_keyword" => Some(ComplianceRuleType::BannedKeyword),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ComplianceRuleType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Database row for compliance_rules table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ComplianceRule {
+    pub id: Uuid,
+    /// One of `excluded_tag`, `max_odds`, `banned_keyword`.
+    pub rule_type: String,
+    pub value: String,
+    pub note: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl ComplianceRule {
+    pub fn rule_type(&self) -> Option<ComplianceRuleType> {
+        ComplianceRuleType::parse(&self.rule_type)
+    }
+}
@@ -0,0 +1,64 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use uuid::Uuid;
+
+/// Kind of market discovery exclusion an operator can configure.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscoveryExclusionType {
+    /// Skip markets in this Gamma category or carrying this tag.
+    Category,
+    /// Skip markets whose question contains this phrase.
+    Keyword,
+    /// Skip markets resolving within this many hours.
+    ResolvesWithinHours,
+    /// Skip markets whose bid/ask spread is wider than this.
+    MaxSpread,
+}
+
+impl DiscoveryExclusionType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiscoveryExclusionType::Category => "category",
+            DiscoveryExclusionType::Keyword => "keyword",
+            DiscoveryExclusionType::ResolvesWithinHours => "resolves_within_hours",
+            DiscoveryExclusionType::MaxSpread => "max_spread",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "category" => Some(DiscoveryExclusionType::Category),
+            "keyword" => Some(DiscoveryExclusionType::Keyword),
+            "resolves_within_hours" => Some(DiscoveryExclusionType::ResolvesWithinHours),
+            "max_spread" => Some(DiscoveryExclusionType::MaxSpread),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for DiscoveryExclusionType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Database row for discovery_exclusions table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct DiscoveryExclusion {
+    pub id: Uuid,
+    /// One of `category`, `keyword`, `resolves_within_hours`, `max_spread`.
+    pub rule_type: String,
+    pub value: String,
+    pub note: Option<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+}
+
+impl DiscoveryExclusion {
+    pub fn rule_type(&self) -> Option<DiscoveryExclusionType> {
+        DiscoveryExclusionType::parse(&self.rule_type)
+    }
+}
@@ -0,0 +1,68 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Number of price levels per side kept in an execution snapshot.
+pub const BOOK_SNAPSHOT_LEVELS: usize = 5;
+
+/// A single price level of a stored book snapshot.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BookLevel {
+    pub price: Decimal,
+    pub size: Decimal,
+}
+
+/// Top N levels of the orderbook as seen by the executor before placing an order.
+#[derive(Debug, Clone)]
+pub struct BookSnapshot {
+    /// Best bid first.
+    pub bids: Vec<BookLevel>,
+    /// Best ask first.
+    pub asks: Vec<BookLevel>,
+    pub book_hash: Option<String>,
+    /// When the book response arrived.
+    pub captured_at: DateTime<Utc>,
+    /// Round-trip time of the orderbook request.
+    pub fetch_ms: i32,
+}
+
+impl BookSnapshot {
+    pub fn best_bid(&self) -> Option<Decimal> {
+        self.bids.first().map(|l| l.price)
+    }
+
+    pub fn best_ask(&self) -> Option<Decimal> {
+        self.asks.first().map(|l| l.price)
+    }
+
+    /// Total size across the stored levels.
+    pub fn depth(levels: &[BookLevel]) -> Decimal {
+        levels.iter().map(|l| l.size).sum()
+    }
+}
+
+/// Database row for execution_snapshots table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ExecutionSnapshot {
+    pub id: Uuid,
+    pub order_id: Uuid,
+    pub token_id: String,
+    pub side: String,
+    /// Whale's price we tried to copy.
+    pub target_price: Decimal,
+    /// Price our order was placed at.
+    pub order_price: Decimal,
+    pub best_bid: Option<Decimal>,
+    pub best_ask: Option<Decimal>,
+    pub bid_depth: Decimal,
+    pub ask_depth: Decimal,
+    /// `[{price, size}]`, best first.
+    pub bids: serde_json::Value,
+    pub asks: serde_json::Value,
+    pub book_hash: Option<String>,
+    pub fetch_ms: i32,
+    pub captured_at: DateTime<Utc>,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,21 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Net buying by tracked whales into one outcome token, recorded when it
+/// passed the flow gates and was emitted as a signal.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct FlowSignal {
+    pub id: Uuid,
+    pub market_id: String,
+    pub token_id: String,
+    pub buy_notional: Decimal,
+    pub sell_notional: Decimal,
+    pub net_notional: Decimal,
+    /// Distinct tracked whales trading the token within the window.
+    pub whales: i32,
+    pub window_minutes: i32,
+    pub triggered_at: DateTime<Utc>,
+}
@@ -0,0 +1,128 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// A named bundle of signal-gate thresholds, persisted in `gate_profiles`.
+/// Gates left NULL keep whatever the env / runtime config sets.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct GateProfile {
+    pub name: String,
+    pub description: Option<String>,
+    pub is_active: bool,
+    pub min_signal_win_rate: Option<Decimal>,
+    pub min_resolved_for_signal: Option<i32>,
+    pub min_total_trades_for_signal: Option<i32>,
+    pub min_signal_ev: Option<Decimal>,
+    pub min_signal_profit_factor: Option<Decimal>,
+    pub min_signal_skill_score: Option<Decimal>,
+    pub min_entry_edge: Option<Decimal>,
+    pub signal_notional_liquidity_pct: Option<Decimal>,
+    pub signal_notional_floor: Option<Decimal>,
+    pub max_signal_notional: Option<Decimal>,
+    pub assumed_slippage_pct: Option<Decimal>,
+    pub first_mover_size_multiplier: Option<Decimal>,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl GateProfile {
+    /// Reject gates that would invert a check or can never pass.
+    pub fn validate(&self) -> Result<(), String> {
+        let name = self.name.trim();
+        if name.is_empty() || name.len() > 64 {
+            return Err("name must be 1-64 characters".into());
+        }
+        let fractions = [
+            ("min_signal_win_rate", self.min_signal_win_rate),
+            ("min_signal_skill_score", self.min_signal_skill_score),
+            ("min_entry_edge", self.min_entry_edge),
+            ("signal_notional_liquidity_pct", self.signal_notional_liquidity_pct),
+            ("assumed_slippage_pct", self.assumed_slippage_pct),
+        ];
+        for (field, value) in fractions {
+            if let Some(v) = value {
+                if v < Decimal::ZERO || v > Decimal::ONE {
+                    return Err(format!("{field} must be in [0, 1], got {v}"));
+                }
+            }
+        }
+        let counts = [
+            ("min_resolved_for_signal", self.min_resolved_for_signal),
+            ("min_total_trades_for_signal", self.min_total_trades_for_signal),
+        ];
+        for (field, value) in counts {
+            if value.is_some_and(|v| v < 0) {
+                return Err(format!("{field} must not be negative"));
+            }
+        }
+        let non_negative = [
+            ("min_signal_ev", self.min_signal_ev),
+            ("min_signal_profit_factor", self.min_signal_profit_factor),
+            ("signal_notional_floor", self.signal_notional_floor),
+        ];
+        for (field, value) in non_negative {
+            if value.is_some_and(|v| v < Decimal::ZERO) {
+                return Err(format!("{field} must not be negative"));
+            }
+        }
+        if let (Some(floor), Some(max)) = (self.signal_notional_floor, self.max_signal_notional) {
+            if max < floor {
+                return Err("max_signal_notional must not be below signal_notional_floor".into());
+            }
+        }
+        if self.max_signal_notional.is_some_and(|v| v <= Decimal::ZERO) {
+            return Err("max_signal_notional must be positive".into());
+        }
+        if self.first_mover_size_multiplier.is_some_and(|v| v <= Decimal::ZERO) {
+            return Err("first_mover_size_multiplier must be positive".into());
+        }
+        Ok(())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn profile() -> GateProfile {
+        GateProfile {
+            name: "conservative".into(),
+            description: None,
+            is_active: false,
+            min_signal_win_rate: Some(Decimal::new(65, 2)),
+            min_resolved_for_signal: Some(30),
+            min_total_trades_for_signal: Some(200),
+            min_signal_ev: Some(Decimal::from(100)),
+            min_signal_profit_factor: None,
+            min_signal_skill_score: None,
+            min_entry_edge: None,
+            signal_notional_liquidity_pct: None,
+            signal_notional_floor: Some(Decimal::from(2500)),
+            max_signal_notional: None,
+            assumed_slippage_pct: None,
+            first_mover_size_multiplier: None,
+            updated_at: None,
+        }
+    }
+
+    #[test]
+    fn test_gate_profile_validation() {
+        assert!(profile().validate().is_ok());
+
+        let mut p = profile();
+        p.min_signal_win_rate = Some(Decimal::new(15, 1));
+        assert!(p.validate().unwrap_err().contains("min_signal_win_rate"));
+
+        let mut p = profile();
+        p.max_signal_notional = Some(Decimal::from(1000));
+        assert!(p.validate().is_err());
+
+        let mut p = profile();
+        p.name = " ".into();
+        assert!(p.validate().is_err());
+    }
+}
@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use uuid::Uuid;
+
+/// Suspicious-timing pattern behind an insider flag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum InsiderFlagKind {
+    /// Large winning entry shortly before the market resolved.
+    PreResolution,
+    /// Large entry shortly before a sharp favourable price move.
+    PreJump,
+}
+
+impl InsiderFlagKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            InsiderFlagKind::PreResolution => "pre_resolution",
+            InsiderFlagKind::PreJump => "pre_jump",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pre_resolution" => Some(InsiderFlagKind::PreResolution),
+            "pre_jump" => Some(InsiderFlagKind::PreJump),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for InsiderFlagKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Database row for insider_flags table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct InsiderFlag {
+    pub id: Uuid,
+    pub whale_id: Uuid,
+    pub trade_id: Uuid,
+    /// One of `pre_resolution`, `pre_jump`.
+    pub kind: String,
+    pub market_id: String,
+    pub token_id: String,
+    pub side: String,
+    pub entry_price: Decimal,
+    pub notional: Decimal,
+    pub traded_at: DateTime<Utc>,
+    /// Hours from entry to resolution (pre_resolution only).
+    pub lead_hours: Option<Decimal>,
+    /// Favourable price move after entry.
+    pub price_move: Decimal,
+    pub evidence: String,
+    pub created_at: DateTime<Utc>,
+}
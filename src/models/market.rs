@@ -13,4 +13,50 @@ pub struct MarketOutcome {
     pub resolved_at: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    pub condition_id: Option<String>,
+}
+
+/// Normalize a market identifier to a `0x`-prefixed condition_id.
+///
+/// Accepts `0x`-prefixed hex or bare 64-char hex containing at least one
+/// a–f letter. Decimal CLOB token ids (all digits) return `None`.
+pub fn normalize_condition_id(id: &str) -> Option<String> {
+    let is_hex64 = |s: &str| s.len() == 64 && s.chars().all(|c| c.is_ascii_hexdigit());
+
+    if let Some(bare) = id.strip_prefix("0x") {
+        return is_hex64(bare).then(|| id.to_string());
+    }
+    if is_hex64(id) && id.chars().any(|c| c.is_ascii_alphabetic()) {
+        return Some(format!("0x{id}"));
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONDITION: &str = "0x1f2e3d4c5b6a79881f2e3d4c5b6a79881f2e3d4c5b6a79881f2e3d4c5b6a7988";
+
+    #[test]
+    fn test_prefixed_condition_id_passes_through() {
+        assert_eq!(normalize_condition_id(CONDITION).as_deref(), Some(CONDITION));
+    }
+
+    #[test]
+    fn test_bare_hex_gets_prefix() {
+        assert_eq!(normalize_condition_id(&CONDITION[2..]).as_deref(), Some(CONDITION));
+    }
+
+    #[test]
+    fn test_token_id_is_not_a_condition_id() {
+        let token = "71321045679252212594626385532706912750332728571942532289631379312455583992563";
+        assert_eq!(normalize_condition_id(token), None);
+        assert_eq!(normalize_condition_id(&"1".repeat(64)), None);
+        assert_eq!(normalize_condition_id("unknown"), None);
+    }
 }
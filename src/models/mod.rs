@@ -1,18 +1,53 @@
+pub mod attribution;
 pub mod basket;
+pub mod candle;
+pub mod capital_flow;
+pub mod classification_audit;
+pub mod compliance_rule;
+pub mod discovery_exclusion;
+pub mod execution_snapshot;
+pub mod flow_signal;
+pub mod gate_profile;
+pub mod insider_flag;
 pub mod market;
 pub mod order;
+pub mod portfolio_snapshot;
 pub mod position;
+pub mod reconciliation;
+pub mod risk_event;
+pub mod seeder_candidate;
 pub mod signal;
+pub mod tape;
+pub mod tier;
 pub mod trade;
 pub mod whale;
 
-pub use basket::{BasketCategory, BasketWallet, ConsensusSignal, WhaleBasket};
-pub use market::MarketOutcome;
+pub use attribution::{BasketPerformance, BasketPnlPoint, PnlAttribution};
+pub use basket::{
+    BacktestConsensusEvent, BasketBacktest, BasketCategory, BasketMembershipEvent, BasketWallet,
+    ConsensusSignal, VoteWeighting, WhaleBasket,
+};
+pub use candle::{Candle, PriceTick};
+pub use capital_flow::{CapitalFlowDirection, WhaleCapitalFlow};
+pub use classification_audit::ClassificationAudit;
+pub use compliance_rule::{ComplianceRule, ComplianceRuleType};
+pub use discovery_exclusion::{DiscoveryExclusion, DiscoveryExclusionType};
+pub use execution_snapshot::{BookLevel, BookSnapshot, ExecutionSnapshot};
+pub use flow_signal::FlowSignal;
+pub use gate_profile::GateProfile;
+pub use insider_flag::{InsiderFlag, InsiderFlagKind};
+pub use market::{normalize_condition_id, MarketOutcome};
 pub use order::CopyOrder;
-pub use position::Position;
-pub use signal::CopySignal;
+pub use portfolio_snapshot::PortfolioSnapshot;
+pub use position::{ExitStyle, Position, StopMode};
+pub use reconciliation::ReconciliationReport;
+pub use risk_event::RiskEvent;
+pub use seeder_candidate::{SeederCandidate, SeederCandidateStatus};
+pub use signal::{CopySignal, SignalOrigin};
+pub use tape::TapePrint;
+pub use tier::{TierPolicy, WhaleTier};
 pub use trade::{TradeResult, WhaleTrade};
-pub use whale::Whale;
+pub use whale::{Whale, WhaleCopyPerformance, WhaleCorrelation};
 
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
@@ -49,10 +84,59 @@ impl fmt::Display for Side {
     }
 }
 
+// ---------------------------------------------------------------------------
+// Sleeve — named slice of the bankroll a strategy trades out of
+// ---------------------------------------------------------------------------
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Sleeve {
+    /// Copying an individual tracked whale.
+    SingleWhale,
+    /// Basket consensus signals.
+    Basket,
+    /// Momentum / trend-following signals.
+    Momentum,
+    /// Market-level net whale flow signals.
+    Flow,
+}
+
+impl Sleeve {
+    pub const ALL: [Sleeve; 4] = [Sleeve::SingleWhale, Sleeve::Basket, Sleeve::Momentum, Sleeve::Flow];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Sleeve::SingleWhale => "single_whale",
+            Sleeve::Basket => "basket",
+            Sleeve::Momentum => "momentum",
+            Sleeve::Flow => "flow",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "single_whale" | "whale" => Some(Sleeve::SingleWhale),
+            "basket" => Some(Sleeve::Basket),
+            "momentum" => Some(Sleeve::Momentum),
+            "flow" => Some(Sleeve::Flow),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for Sleeve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 // ---------------------------------------------------------------------------
 // WhaleTradeEvent — core pipeline message
 // ---------------------------------------------------------------------------
 
+/// Placeholder wallet on WS trade events, which carry no address.
+pub const ANONYMOUS_WALLET: &str = "ws_anonymous";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WhaleTradeEvent {
     pub wallet: String,
@@ -63,6 +147,25 @@ pub struct WhaleTradeEvent {
     pub price: Decimal,
     pub notional: Decimal,
     pub timestamp: DateTime<Utc>,
+    /// Transaction the trade settled in (lowercase hex), when the source
+    /// knows it. Identifies the trade across sources.
+    #[serde(default)]
+    pub tx_hash: Option<String>,
+    /// Log index of the on-chain fill (chain listener only).
+    #[serde(default)]
+    pub fill_index: Option<i64>,
+    /// Delivered late — replayed by a chain gap backfill or from the durable
+    /// queue — rather than as it happened. Not copied once older than the
+    /// signal TTL.
+    #[serde(default)]
+    pub replayed: bool,
+}
+
+impl WhaleTradeEvent {
+    /// WS market-channel trades carry no wallet.
+    pub fn is_anonymous(&self) -> bool {
+        self.wallet == ANONYMOUS_WALLET
+    }
 }
 
 impl fmt::Display for WhaleTradeEvent {
@@ -22,6 +22,57 @@ pub struct CopyOrder {
     pub placed_at: Option<DateTime<Utc>>,
     pub filled_at: Option<DateTime<Utc>>,
     pub clob_order_id: Option<String>,
+    pub sleeve: String,
+    /// Canonical market identifier, when known.
+    pub condition_id: Option<String>,
+    /// Exchange fee paid on the fill, in USDC.
+    pub fee_usdc: Option<Decimal>,
+    /// Gas paid by our signer to settle the fill, in USDC.
+    pub gas_usdc: Option<Decimal>,
+    /// Basket consensus signal that produced this order.
+    pub source_signal_id: Option<Uuid>,
+    /// Fill vs. the copied whale's entry price, as a fraction of the whale's
+    /// price; positive means copying late cost us.
+    pub copy_lag: Option<Decimal>,
+    /// Reprices that led to this exit order (0 for the original exit).
+    #[serde(default)]
+    pub exit_reprices: i32,
+    /// When the alert for this exit order running out of reprices was sent.
+    #[serde(default)]
+    pub exit_alerted_at: Option<DateTime<Utc>>,
+}
+
+#[cfg(test)]
+impl CopyOrder {
+    /// Pending single_whale BUY of 10 tokens `t` in market `m` at 0.50, for
+    /// unit tests to adjust with struct update syntax.
+    pub fn test_fixture() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            whale_trade_id: None,
+            market_id: "m".into(),
+            token_id: "t".into(),
+            side: "BUY".into(),
+            size: Decimal::from(10),
+            target_price: Decimal::new(50, 2),
+            fill_price: None,
+            slippage: None,
+            status: order_status::PENDING.into(),
+            strategy: "copy".into(),
+            error_message: None,
+            placed_at: None,
+            filled_at: None,
+            clob_order_id: None,
+            sleeve: "single_whale".into(),
+            condition_id: None,
+            fee_usdc: None,
+            gas_usdc: None,
+            source_signal_id: None,
+            copy_lag: None,
+            exit_reprices: 0,
+            exit_alerted_at: None,
+        }
+    }
 }
 
 /// Order status constants.
@@ -0,0 +1,22 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row for portfolio_snapshots table (one point of the equity curve).
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct PortfolioSnapshot {
+    pub id: Uuid,
+    /// USDC tracked by the capital pools, including in-flight reservations.
+    pub bankroll: Decimal,
+    /// Cost basis of open and exiting positions.
+    pub open_exposure: Decimal,
+    pub unrealized_pnl: Decimal,
+    /// Cumulative realized PnL at the time of the snapshot.
+    pub realized_pnl: Decimal,
+    /// `bankroll + open_exposure + unrealized_pnl`.
+    pub equity: Decimal,
+    pub open_positions: i32,
+    pub taken_at: DateTime<Utc>,
+}
@@ -1,9 +1,82 @@
+use std::fmt;
+
 use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use uuid::Uuid;
 
+/// Stop-loss used when a position has no explicit `stop_loss_pct`.
+const FALLBACK_STOP_LOSS_PCT: Decimal = Decimal::from_parts(1500, 0, 0, false, 2); // 15.00
+
+/// How a position's stop-loss is evaluated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StopMode {
+    /// Fixed `stop_loss_pct` below the entry price.
+    Static,
+    /// `stop_loss_pct` below the highest price seen (`peak_price`), so it
+    /// only ever moves up.
+    Trailing,
+}
+
+impl StopMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            StopMode::Static => "static",
+            StopMode::Trailing => "trailing",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "static" | "fixed" => Some(StopMode::Static),
+            "trailing" => Some(StopMode::Trailing),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for StopMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// How a copied position is exited, chosen from the copied whale's holding profile.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExitStyle {
+    /// Follow the whale's sells out (swing traders).
+    Mirror,
+    /// Ignore the whale's early sells and take-profit; hold for the payout
+    /// (long-horizon whales). Stop-loss and time exits still apply.
+    HoldToResolution,
+}
+
+impl ExitStyle {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ExitStyle::Mirror => "mirror",
+            ExitStyle::HoldToResolution => "hold_to_resolution",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "mirror" | "swing" => Some(ExitStyle::Mirror),
+            "hold_to_resolution" | "hold" => Some(ExitStyle::HoldToResolution),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for ExitStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 /// Database row for positions table.
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Position {
@@ -25,4 +98,148 @@ pub struct Position {
     pub exit_reason: Option<String>,
     pub exited_at: Option<DateTime<Utc>>,
     pub peak_price: Option<Decimal>,
+    pub sleeve: String,
+    /// Canonical market identifier, when known.
+    pub condition_id: Option<String>,
+    /// `static` or `trailing` (see [`StopMode`]).
+    pub stop_mode: String,
+    /// Whale whose trade opened this position.
+    pub whale_id: Option<Uuid>,
+    /// Whale trade that opened this position.
+    pub whale_trade_id: Option<Uuid>,
+    /// When the complementary outcome was bought to hedge this position.
+    pub hedged_at: Option<DateTime<Utc>>,
+    /// For a hedge leg: the position it hedges.
+    pub hedge_of: Option<Uuid>,
+    /// Market liquidity when the position was opened.
+    pub entry_liquidity: Option<Decimal>,
+    /// Basket consensus signal that opened this position.
+    pub source_signal_id: Option<Uuid>,
+    /// Exit style copied from the whale's holding profile at open (see [`ExitStyle`]).
+    pub exit_style: Option<String>,
+}
+
+impl Position {
+    pub fn stop_mode(&self) -> StopMode {
+        StopMode::parse(&self.stop_mode).unwrap_or(StopMode::Static)
+    }
+
+    /// Positions opened before the whale was profiled mirror its exits.
+    pub fn exit_style(&self) -> ExitStyle {
+        self.exit_style.as_deref().and_then(ExitStyle::parse).unwrap_or(ExitStyle::Mirror)
+    }
+
+    /// Trailing stop level after observing `current_price`, trailing the
+    /// highest price seen. `peak_price` only moves up, so neither does the
+    /// level. Trailing-mode positions trail from entry by `stop_loss_pct`;
+    /// static ones only while in profit, by the global `trailing_stop_pct`
+    /// (None when that is 0 or the position isn't in profit).
+    pub fn trailing_stop_level(&self, current_price: Decimal, trailing_stop_pct: Decimal) -> Option<Decimal> {
+        let peak = self.peak_price.unwrap_or(self.avg_entry_price);
+        let high = peak.max(self.avg_entry_price).max(current_price);
+
+        let stop_pct = match self.stop_mode() {
+            StopMode::Trailing => self.stop_loss_pct.unwrap_or(FALLBACK_STOP_LOSS_PCT),
+            StopMode::Static => {
+                // Within the fixed stop-loss below entry, that stop applies instead
+                let in_profit = peak > self.avg_entry_price && current_price > self.avg_entry_price;
+                if !in_profit || trailing_stop_pct <= Decimal::ZERO {
+                    return None;
+                }
+                trailing_stop_pct
+            }
+        };
+        Some(high * (Decimal::ONE - stop_pct / Decimal::ONE_HUNDRED))
+    }
+
+    /// Key for grouping positions by market: condition_id when known, else market_id.
+    pub fn market_key(&self) -> &str {
+        self.condition_id.as_deref().unwrap_or(&self.market_id)
+    }
+}
+
+#[cfg(test)]
+impl Position {
+    /// Open 100-share single_whale position in token `t` of market `m`,
+    /// entered at 0.50, for unit tests to adjust with struct update syntax.
+    pub fn test_fixture() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            market_id: "m".into(),
+            token_id: "t".into(),
+            outcome: "Yes".into(),
+            size: Decimal::from(100),
+            avg_entry_price: Decimal::new(50, 2),
+            current_price: None,
+            unrealized_pnl: None,
+            status: Some("open".into()),
+            opened_at: None,
+            closed_at: None,
+            realized_pnl: None,
+            stop_loss_pct: None,
+            take_profit_pct: None,
+            last_price_update: None,
+            exit_reason: None,
+            exited_at: None,
+            peak_price: None,
+            sleeve: "single_whale".into(),
+            condition_id: None,
+            stop_mode: StopMode::Static.as_str().into(),
+            whale_id: None,
+            whale_trade_id: None,
+            hedged_at: None,
+            hedge_of: None,
+            entry_liquidity: None,
+            source_signal_id: None,
+            exit_style: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn position(mode: StopMode) -> Position {
+        Position {
+            stop_loss_pct: Some(Decimal::from(10)),
+            stop_mode: mode.as_str().into(),
+            ..Position::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_static_mode_trails_only_in_profit() {
+        let mut pos = position(StopMode::Static);
+        let pct = Decimal::from(20);
+        // Never above entry: the fixed stop-loss applies
+        assert!(pos.trailing_stop_level(Decimal::new(45, 2), pct).is_none());
+
+        pos.peak_price = Some(Decimal::new(80, 2));
+        assert_eq!(pos.trailing_stop_level(Decimal::new(70, 2), pct), Some(Decimal::new(64, 2)));
+        // Global trailing stop off
+        assert!(pos.trailing_stop_level(Decimal::new(70, 2), Decimal::ZERO).is_none());
+        // Back below entry
+        assert!(pos.trailing_stop_level(Decimal::new(49, 2), pct).is_none());
+    }
+
+    #[test]
+    fn test_trailing_stop_ratchets_up_only() {
+        let mut pos = position(StopMode::Trailing);
+
+        // Starts 10% below entry, whatever the global trailing stop
+        assert_eq!(pos.trailing_stop_level(Decimal::new(48, 2), Decimal::ZERO), Some(Decimal::new(45, 2)));
+
+        // Price rises to 0.60 → stop follows to 0.54
+        let level = pos.trailing_stop_level(Decimal::new(60, 2), Decimal::ZERO).unwrap();
+        assert_eq!(level, Decimal::new(54, 2));
+        pos.peak_price = Some(Decimal::new(60, 2));
+
+        // Price falls back — stop stays put
+        assert_eq!(pos.trailing_stop_level(Decimal::new(55, 2), Decimal::ZERO), Some(Decimal::new(54, 2)));
+    }
 }
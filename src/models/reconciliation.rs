@@ -0,0 +1,24 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row for reconciliation_reports — one end-of-day reconciliation run.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ReconciliationReport {
+    pub id: Uuid,
+    /// True when no discrepancies were found.
+    pub passed: bool,
+    pub db_positions: i32,
+    pub account_positions: i32,
+    pub db_open_orders: i32,
+    /// None when the CLOB was not checked (dry run / no trading client).
+    pub clob_open_orders: Option<i32>,
+    /// Capital pool balance vs on-chain USDC; None when not checked.
+    pub ledger_balance: Option<Decimal>,
+    pub chain_balance: Option<Decimal>,
+    /// List of `{kind, subject, detail}` discrepancies.
+    pub discrepancies: serde_json::Value,
+    pub created_at: DateTime<Utc>,
+}
@@ -0,0 +1,28 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use uuid::Uuid;
+
+/// Database row for risk_events table — one per signal rejected by a risk check.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct RiskEvent {
+    pub id: Uuid,
+    /// Violated rule, e.g. `too_many_positions`.
+    pub rule: String,
+    /// Which check rejected it: global, sleeve, portfolio, resolution, execution, wallet or compliance.
+    pub scope: String,
+    pub message: String,
+    /// Rule-specific values (actual vs limit).
+    pub details: serde_json::Value,
+    pub whale_trade_id: Option<Uuid>,
+    pub wallet: Option<String>,
+    pub market_id: Option<String>,
+    pub condition_id: Option<String>,
+    pub token_id: Option<String>,
+    pub side: Option<String>,
+    pub size: Option<Decimal>,
+    pub price: Option<Decimal>,
+    pub sleeve: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
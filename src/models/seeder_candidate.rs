@@ -0,0 +1,61 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::fmt;
+use uuid::Uuid;
+
+/// Evaluation state of a queued seeder candidate.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SeederCandidateStatus {
+    /// Waiting for the next seeder cycle.
+    Pending,
+    /// Passed the filters and is now a tracked whale.
+    Seeded,
+    /// Failed an anti-signal filter.
+    Rejected,
+    /// Evaluation couldn't complete (data API or database error).
+    Failed,
+}
+
+impl SeederCandidateStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SeederCandidateStatus::Pending => "pending",
+            SeederCandidateStatus::Seeded => "seeded",
+            SeederCandidateStatus::Rejected => "rejected",
+            SeederCandidateStatus::Failed => "failed",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "pending" => Some(SeederCandidateStatus::Pending),
+            "seeded" => Some(SeederCandidateStatus::Seeded),
+            "rejected" => Some(SeederCandidateStatus::Rejected),
+            "failed" => Some(SeederCandidateStatus::Failed),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SeederCandidateStatus {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Database row for seeder_candidates table.
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct SeederCandidate {
+    pub id: Uuid,
+    pub address: String,
+    pub note: Option<String>,
+    /// One of `pending`, `seeded`, `rejected`, `failed`.
+    pub status: String,
+    /// Why the candidate was rejected or failed, or how it was seeded.
+    pub verdict: Option<String>,
+    pub whale_id: Option<Uuid>,
+    pub requested_at: DateTime<Utc>,
+    pub evaluated_at: Option<DateTime<Utc>>,
+}
@@ -1,7 +1,53 @@
+use std::fmt;
+
 use rust_decimal::Decimal;
 use uuid::Uuid;
 
-use super::Side;
+use super::{Side, Sleeve, WhaleTier};
+
+/// Where a copy signal came from — execution mode is chosen per origin.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SignalOrigin {
+    /// A tracked whale's entry.
+    Whale,
+    /// Basket consensus.
+    Basket,
+    /// Market-level net whale flow.
+    Flow,
+    /// Operator order placed through the API.
+    Manual,
+    /// A whale exiting a position we also hold.
+    Exit,
+}
+
+impl SignalOrigin {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SignalOrigin::Whale => "whale",
+            SignalOrigin::Basket => "basket",
+            SignalOrigin::Flow => "flow",
+            SignalOrigin::Manual => "manual",
+            SignalOrigin::Exit => "exit",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "whale" => Some(SignalOrigin::Whale),
+            "basket" => Some(SignalOrigin::Basket),
+            "flow" => Some(SignalOrigin::Flow),
+            "manual" => Some(SignalOrigin::Manual),
+            "exit" => Some(SignalOrigin::Exit),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for SignalOrigin {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
 
 /// A validated copy-trade signal ready for the execution layer.
 #[derive(Debug, Clone)]
@@ -10,8 +56,10 @@ pub struct CopySignal {
     pub whale_trade_id: Uuid,
     /// Whale's wallet address.
     pub wallet: String,
-    /// Market condition ID.
+    /// Market key as received from the ingestion source.
     pub market_id: String,
+    /// Canonical market condition ID, when resolved.
+    pub condition_id: Option<String>,
     /// Token (asset) ID for the specific outcome.
     pub asset_id: String,
     /// Buy or Sell.
@@ -26,4 +74,86 @@ pub struct CopySignal {
     pub whale_notional: Decimal,
     /// True if this signal represents a whale exiting a position we also hold.
     pub is_whale_exit: bool,
+    /// Strategy sleeve this signal belongs to; its capital unless `profile` is set.
+    pub sleeve: Sleeve,
+    /// Copy profile whose own capital pool this signal trades out of.
+    pub profile: Option<String>,
+    /// Operator-chosen size for manual orders; bypasses strategy sizing.
+    pub manual_size: Option<Decimal>,
+    /// Copy profile multiplier on the strategy-computed size.
+    pub size_multiplier: Decimal,
+    /// Basket consensus signal this was emitted for (basket sleeve only).
+    pub source_signal_id: Option<Uuid>,
+    /// Share of the basket behind a basket signal; with `whale_win_rate` (the
+    /// voters' mean) it sizes the order instead of the triggering whale's Kelly.
+    pub consensus_pct: Option<Decimal>,
+    /// Net flow signal this was emitted for (flow sleeve only).
+    pub flow_signal_id: Option<Uuid>,
+    /// The whale's tier; its policy sizes the order instead of the engine's strategy.
+    pub tier: Option<WhaleTier>,
+}
+
+impl CopySignal {
+    /// Signal for an operator order placed through the API. It carries no
+    /// whale trade (nil `whale_trade_id`) and trades exactly `size`.
+    pub fn manual(
+        market_id: String,
+        condition_id: Option<String>,
+        asset_id: String,
+        side: Side,
+        size: Decimal,
+        price: Decimal,
+        sleeve: Sleeve,
+    ) -> Self {
+        Self {
+            whale_trade_id: Uuid::nil(),
+            wallet: "manual".into(),
+            market_id,
+            condition_id,
+            asset_id,
+            side,
+            price,
+            whale_win_rate: Decimal::ZERO,
+            whale_kelly: Decimal::ZERO,
+            whale_notional: Decimal::ZERO,
+            is_whale_exit: false,
+            sleeve,
+            profile: None,
+            manual_size: Some(size),
+            size_multiplier: Decimal::ONE,
+            source_signal_id: None,
+            consensus_pct: None,
+            flow_signal_id: None,
+            tier: None,
+        }
+    }
+
+    /// Label of the capital pool this signal trades out of, as stored on its
+    /// orders and positions: the copy profile's name, else the sleeve.
+    pub fn capital_label(&self) -> &str {
+        self.profile.as_deref().unwrap_or(self.sleeve.as_str())
+    }
+
+    pub fn is_manual(&self) -> bool {
+        self.manual_size.is_some()
+    }
+
+    pub fn origin(&self) -> SignalOrigin {
+        if self.is_whale_exit {
+            SignalOrigin::Exit
+        } else if self.is_manual() {
+            SignalOrigin::Manual
+        } else if self.source_signal_id.is_some() {
+            SignalOrigin::Basket
+        } else if self.flow_signal_id.is_some() {
+            SignalOrigin::Flow
+        } else {
+            SignalOrigin::Whale
+        }
+    }
+
+    /// The triggering whale trade, if any.
+    pub fn whale_trade(&self) -> Option<Uuid> {
+        (!self.whale_trade_id.is_nil()).then_some(self.whale_trade_id)
+    }
 }
@@ -0,0 +1,69 @@
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use super::WhaleTradeEvent;
+
+/// One trade print on the tape (market_trades table).
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct TapePrint {
+    pub market_id: String,
+    pub token_id: String,
+    pub side: String,
+    pub size: Decimal,
+    pub price: Decimal,
+    pub wallet: Option<String>,
+    /// Ingestion source: `ws` or `chain`.
+    pub source: String,
+    pub traded_at: DateTime<Utc>,
+}
+
+impl TapePrint {
+    pub fn from_event(event: &WhaleTradeEvent, source: &str) -> Self {
+        Self {
+            market_id: event.market_id.clone(),
+            token_id: event.asset_id.clone(),
+            side: event.side.to_string(),
+            size: event.size,
+            price: event.price,
+            wallet: (!event.is_anonymous()).then(|| event.wallet.clone()),
+            source: source.to_string(),
+            traded_at: event.timestamp,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Side, ANONYMOUS_WALLET};
+
+    #[test]
+    fn test_print_from_event() {
+        let mut event = WhaleTradeEvent {
+            wallet: ANONYMOUS_WALLET.into(),
+            market_id: "0xcond".into(),
+            asset_id: "tok".into(),
+            side: Side::Sell,
+            size: Decimal::from(40),
+            price: Decimal::new(35, 2),
+            notional: Decimal::from(14),
+            timestamp: Utc::now(),
+            tx_hash: None,
+            fill_index: None,
+            replayed: false,
+        };
+        let print = TapePrint::from_event(&event, "ws");
+        assert_eq!(print.side, "SELL");
+        assert_eq!(print.token_id, "tok");
+        assert_eq!(print.wallet, None);
+
+        event.wallet = "0xabc".into();
+        assert_eq!(TapePrint::from_event(&event, "chain").wallet.as_deref(), Some("0xabc"));
+    }
+}
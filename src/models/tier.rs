@@ -0,0 +1,70 @@
+use std::fmt;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Whale quality tier, A best. Each tier has its own signal gates and sizing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub enum WhaleTier {
+    A,
+    B,
+    C,
+}
+
+impl WhaleTier {
+    /// In assignment order: a whale gets the first tier it qualifies for.
+    pub const ALL: [WhaleTier; 3] = [WhaleTier::A, WhaleTier::B, WhaleTier::C];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            WhaleTier::A => "A",
+            WhaleTier::B => "B",
+            WhaleTier::C => "C",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_uppercase().as_str() {
+            "A" => Some(WhaleTier::A),
+            "B" => Some(WhaleTier::B),
+            "C" => Some(WhaleTier::C),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for WhaleTier {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Entry thresholds, signal gates and sizing of one tier, persisted in `whale_tiers`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, FromRow)]
+pub struct TierPolicy {
+    pub tier: String,
+    /// Minimum win rate lower bound to enter the tier.
+    pub min_win_rate: Decimal,
+    /// Minimum skill score to enter the tier (0 = off). Whales without a score fail it.
+    pub min_skill_score: Decimal,
+    pub min_total_trades: i32,
+    /// Replaces the global EV_copy gate for this tier's signals.
+    pub min_signal_ev: Decimal,
+    /// Replaces the global notional floor for this tier's signals.
+    pub signal_notional_floor: Decimal,
+    /// Sizing strategy: `kelly`, `fixed` or `proportional`.
+    pub sizing: String,
+    /// Multiplier on the strategy size; 2 on `kelly` is full Kelly.
+    pub size_multiplier: Decimal,
+    /// Bet size for `fixed` sizing, scaled by the whale's win rate.
+    pub base_amount: Decimal,
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl TierPolicy {
+    pub fn whale_tier(&self) -> Option<WhaleTier> {
+        WhaleTier::parse(&self.tier)
+    }
+}
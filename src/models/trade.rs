@@ -9,6 +9,7 @@ use uuid::Uuid;
 pub struct WhaleTrade {
     pub id: Uuid,
     pub whale_id: Option<Uuid>,
+    /// Market key as received from the ingestion source (condition_id or token id).
     pub market_id: String,
     pub token_id: String,
     pub side: String,
@@ -16,8 +17,19 @@ pub struct WhaleTrade {
     pub price: Decimal,
     pub notional: Decimal,
     pub tx_hash: Option<String>,
+    /// Log index of the on-chain fill, when the chain listener saw it first.
+    pub fill_index: Option<i64>,
     pub traded_at: DateTime<Utc>,
     pub created_at: Option<DateTime<Utc>>,
+    /// Canonical market identifier, when known.
+    pub condition_id: Option<String>,
+}
+
+impl WhaleTrade {
+    /// Key for grouping trades by market: condition_id when known, else market_id.
+    pub fn market_key(&self) -> &str {
+        self.condition_id.as_deref().unwrap_or(&self.market_id)
+    }
 }
 
 /// Lightweight struct for scoring calculations.
@@ -25,5 +37,7 @@ pub struct WhaleTrade {
 #[derive(Debug, Clone)]
 pub struct TradeResult {
     pub profit: Decimal,
+    /// USDC staked on the trade.
+    pub notional: Decimal,
     pub traded_at: DateTime<Utc>,
 }
@@ -21,4 +21,180 @@ pub struct Whale {
     pub last_trade_at: Option<DateTime<Utc>>,
     pub created_at: Option<DateTime<Utc>>,
     pub updated_at: Option<DateTime<Utc>>,
+    /// Set while copying is paused because our copies of this whale lose money.
+    pub copy_paused_at: Option<DateTime<Utc>>,
+    pub copy_pause_reason: Option<String>,
+    /// Last manual re-enable; copy performance is re-evaluated from here.
+    pub copy_resumed_at: Option<DateTime<Utc>>,
+    /// Operator-set classification that takes precedence over automatic classification.
+    pub classification_override: Option<String>,
+    /// When the override lapses; None = until cleared.
+    pub classification_override_until: Option<DateTime<Utc>>,
+    /// Leaderboard PnL/volume when we started copying — the drift baseline.
+    pub seed_leaderboard_pnl: Option<Decimal>,
+    pub seed_leaderboard_volume: Option<Decimal>,
+    /// Leaderboard PnL/volume at the last drift check.
+    pub leaderboard_pnl: Option<Decimal>,
+    pub leaderboard_volume: Option<Decimal>,
+    pub leaderboard_checked_at: Option<DateTime<Utc>>,
+    /// Downside-deviation and drawdown-adjusted returns from resolved trades.
+    pub sortino_ratio: Option<Decimal>,
+    pub calmar_ratio: Option<Decimal>,
+    /// Largest drop of cumulative resolved-trade PnL: USDC, and percent of the
+    /// peak equity (largest stake plus PnL).
+    pub max_drawdown: Option<Decimal>,
+    pub max_drawdown_pct: Option<Decimal>,
+    /// Gross wins / gross losses and average win / average loss.
+    pub profit_factor: Option<Decimal>,
+    pub payoff_ratio: Option<Decimal>,
+    /// Confidence (0–1) that the resolved-trade record is skill, not luck.
+    pub skill_score: Option<Decimal>,
+    /// Mean favourable price move after entries, and how many entries it covers.
+    pub entry_edge: Option<Decimal>,
+    pub entry_edge_samples: Option<i32>,
+    /// Mean hours from entry to exit or resolution, over `hold_profile_samples` positions.
+    pub avg_hold_hours: Option<Decimal>,
+    /// Share of those positions the whale sold before the market resolved.
+    pub early_exit_ratio: Option<Decimal>,
+    pub hold_profile_samples: Option<i32>,
+    /// `mirror` or `hold_to_resolution` (see [`ExitStyle`](super::ExitStyle)).
+    pub exit_style: Option<String>,
+    /// `A`, `B` or `C` when whale tiers are enabled (see [`WhaleTier`](super::WhaleTier)).
+    pub tier: Option<String>,
+}
+
+impl Whale {
+    /// The operator override, if set and not yet expired at `now`.
+    pub fn active_classification_override(&self, now: DateTime<Utc>) -> Option<&str> {
+        let value = self.classification_override.as_deref()?;
+        match self.classification_override_until {
+            Some(until) if until <= now => None,
+            _ => Some(value),
+        }
+    }
+
+    /// Classification the gates should use: an active override, else the automatic one.
+    pub fn effective_classification(&self, now: DateTime<Utc>) -> Option<&str> {
+        self.active_classification_override(now)
+            .or(self.classification.as_deref())
+    }
+}
+
+/// How our copies of a whale have performed — distinct from the whale's own stats.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WhaleCopyPerformance {
+    pub whale_id: Uuid,
+    pub total_orders: i64,
+    pub filled_orders: i64,
+    pub failed_orders: i64,
+    pub filled_notional: Decimal,
+    /// Closed positions this whale contributed fills to.
+    pub closed_positions: i64,
+    pub winning_positions: i64,
+    pub copy_win_rate: Option<Decimal>,
+    /// PnL attributed pro rata by this whale's share of filled BUY size per token.
+    pub realized_pnl: Decimal,
+    pub unrealized_pnl: Decimal,
+    pub avg_slippage: Option<Decimal>,
+    /// Average fill vs. whale entry price, as a fraction of the whale's price.
+    pub avg_copy_lag: Option<Decimal>,
+    /// Seconds from the whale's trade to our order being placed.
+    pub avg_latency_secs: Option<Decimal>,
+}
+
+/// Trade overlap between two tracked whales: Jaccard similarity of the
+/// (token, side) positions each took in the lookback window. Near 1 means one
+/// wallet mirrors the other.
+#[derive(Debug, Clone, Serialize, FromRow)]
+pub struct WhaleCorrelation {
+    pub whale_a: Uuid,
+    pub address_a: String,
+    pub whale_b: Uuid,
+    pub address_b: String,
+    /// Positions both whales took.
+    pub shared_positions: i64,
+    pub overlap: Decimal,
+}
+
+#[cfg(test)]
+impl Whale {
+    /// Active, unscored whale `0xwhale`, for unit tests to adjust with struct
+    /// update syntax.
+    pub fn test_fixture() -> Self {
+        Self {
+            id: Uuid::new_v4(),
+            address: "0xwhale".into(),
+            label: None,
+            category: None,
+            classification: None,
+            sharpe_ratio: None,
+            win_rate: None,
+            total_trades: None,
+            total_pnl: None,
+            kelly_fraction: None,
+            expected_value: None,
+            is_active: Some(true),
+            last_trade_at: None,
+            created_at: None,
+            updated_at: None,
+            copy_paused_at: None,
+            copy_pause_reason: None,
+            copy_resumed_at: None,
+            classification_override: None,
+            classification_override_until: None,
+            seed_leaderboard_pnl: None,
+            seed_leaderboard_volume: None,
+            leaderboard_pnl: None,
+            leaderboard_volume: None,
+            leaderboard_checked_at: None,
+            sortino_ratio: None,
+            calmar_ratio: None,
+            max_drawdown: None,
+            max_drawdown_pct: None,
+            profit_factor: None,
+            payoff_ratio: None,
+            skill_score: None,
+            entry_edge: None,
+            entry_edge_samples: None,
+            avg_hold_hours: None,
+            early_exit_ratio: None,
+            hold_profile_samples: None,
+            exit_style: None,
+            tier: None,
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn whale(classification: &str, over: Option<&str>, until: Option<DateTime<Utc>>) -> Whale {
+        Whale {
+            classification: Some(classification.into()),
+            classification_override: over.map(Into::into),
+            classification_override_until: until,
+            ..Whale::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_classification_override_until_expiry() {
+        let now = Utc::now();
+
+        let w = whale("bot", Some("informed"), Some(now + Duration::hours(1)));
+        assert_eq!(w.effective_classification(now), Some("informed"));
+
+        let w = whale("bot", Some("informed"), Some(now - Duration::hours(1)));
+        assert_eq!(w.active_classification_override(now), None);
+        assert_eq!(w.effective_classification(now), Some("bot"));
+
+        let w = whale("bot", Some("informed"), None);
+        assert_eq!(w.effective_classification(now + Duration::days(365)), Some("informed"));
+    }
 }
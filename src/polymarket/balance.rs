@@ -4,17 +4,31 @@ use polymarket_client_sdk::clob::types::request::BalanceAllowanceRequest;
 use polymarket_client_sdk::clob::types::AssetType;
 use polymarket_client_sdk::types::U256;
 use rust_decimal::Decimal;
+use serde_json::json;
 
 use super::wallet::PolymarketWallet;
 
-/// Queries USDC and CTF token balances via the authenticated CLOB API.
+/// Queries USDC and CTF token balances via the authenticated CLOB API, and
+/// the signer's MATIC (gas) balance via Polygon JSON-RPC.
 pub struct BalanceChecker {
     wallet: Arc<PolymarketWallet>,
+    http: reqwest::Client,
+    rpc_url: Option<String>,
 }
 
 impl BalanceChecker {
     pub fn new(wallet: Arc<PolymarketWallet>) -> Self {
-        Self { wallet }
+        Self {
+            wallet,
+            http: reqwest::Client::new(),
+            rpc_url: None,
+        }
+    }
+
+    /// Enable MATIC balance queries against this Polygon RPC endpoint.
+    pub fn with_rpc_url(mut self, rpc_url: impl Into<String>) -> Self {
+        self.rpc_url = Some(rpc_url.into());
+        self
     }
 
     /// Access the inner wallet reference.
@@ -51,8 +65,111 @@ impl BalanceChecker {
         Ok(resp.balance)
     }
 
+    /// Get the signer's native MATIC balance (pays gas for on-chain actions).
+    pub async fn get_matic_balance(&self) -> anyhow::Result<Decimal> {
+        let address = format!("{}", self.wallet.signer().address());
+        let result = self.rpc("eth_getBalance", json!([address, "latest"])).await?;
+
+        let hex = result
+            .as_str()
+            .ok_or_else(|| anyhow::anyhow!("eth_getBalance returned no result: {result}"))?;
+
+        wei_hex_to_matic(hex).ok_or_else(|| anyhow::anyhow!("invalid eth_getBalance result: {hex}"))
+    }
+
+    /// MATIC our signer paid in gas for a transaction. Zero when another
+    /// account (e.g. the exchange operator settling CLOB trades) sent it.
+    pub async fn get_gas_paid_matic(&self, tx_hash: &str) -> anyhow::Result<Decimal> {
+        let receipt = self.rpc("eth_getTransactionReceipt", json!([tx_hash])).await?;
+        if receipt.is_null() {
+            anyhow::bail!("no receipt for transaction {tx_hash}");
+        }
+
+        let signer = format!("{}", self.wallet.signer().address());
+        let from = receipt["from"].as_str().unwrap_or_default();
+        if !from.eq_ignore_ascii_case(&signer) {
+            return Ok(Decimal::ZERO);
+        }
+
+        let gas_used = receipt["gasUsed"].as_str().and_then(parse_hex_quantity);
+        let gas_price = receipt["effectiveGasPrice"].as_str().and_then(parse_hex_quantity);
+        match (gas_used, gas_price) {
+            (Some(used), Some(price)) => wei_to_matic(used.saturating_mul(price))
+                .ok_or_else(|| anyhow::anyhow!("gas cost out of range for {tx_hash}")),
+            _ => anyhow::bail!("receipt for {tx_hash} has no gas fields"),
+        }
+    }
+
+    /// Polygon JSON-RPC call returning the `result` field.
+    async fn rpc(&self, method: &str, params: serde_json::Value) -> anyhow::Result<serde_json::Value> {
+        let rpc_url = self
+            .rpc_url
+            .as_deref()
+            .ok_or_else(|| anyhow::anyhow!("no Polygon RPC URL configured"))?;
+
+        let body = json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": method,
+            "params": params,
+        });
+
+        let mut resp: serde_json::Value = self
+            .http
+            .post(rpc_url)
+            .json(&body)
+            .send()
+            .await?
+            .error_for_status()?
+            .json()
+            .await?;
+
+        if let Some(err) = resp.get("error") {
+            anyhow::bail!("{method} failed: {err}");
+        }
+        Ok(resp["result"].take())
+    }
+
     /// Return the wallet address.
     pub fn wallet_address(&self) -> String {
         self.wallet.wallet_address()
     }
 }
+
+/// Parse a JSON-RPC hex quantity (`0x...`).
+fn parse_hex_quantity(hex: &str) -> Option<i128> {
+    let digits = hex.strip_prefix("0x")?;
+    if digits.is_empty() {
+        Some(0)
+    } else {
+        i128::from_str_radix(digits, 16).ok()
+    }
+}
+
+/// Convert wei to MATIC (18 decimals).
+fn wei_to_matic(wei: i128) -> Option<Decimal> {
+    Decimal::try_from_i128_with_scale(wei, 18).ok().map(|d| d.normalize())
+}
+
+/// Convert a hex wei quantity (`0x...`) to MATIC (18 decimals).
+fn wei_hex_to_matic(hex: &str) -> Option<Decimal> {
+    wei_to_matic(parse_hex_quantity(hex)?)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wei_hex_to_matic() {
+        // 1.5 MATIC
+        assert_eq!(wei_hex_to_matic("0x14d1120d7b160000"), Some(Decimal::new(15, 1)));
+        assert_eq!(wei_hex_to_matic("0x0"), Some(Decimal::ZERO));
+        assert_eq!(wei_hex_to_matic("0x"), Some(Decimal::ZERO));
+        assert_eq!(wei_hex_to_matic("123"), None);
+    }
+}
@@ -1,8 +1,9 @@
 use reqwest::{Client, RequestBuilder};
+use rust_decimal::Decimal;
 use thiserror::Error;
 
 use super::auth::PolymarketAuth;
-use super::types::{ApiMarket, ApiOrderBook};
+use super::types::{ApiMarket, ApiOrderBook, ApiTickSize};
 
 const CLOB_API_BASE: &str = "https://clob.polymarket.com";
 
@@ -78,4 +79,17 @@ impl ClobClient {
         let book: ApiOrderBook = resp.json().await?;
         Ok(book)
     }
+
+    /// Fetch the minimum price increment for a specific token.
+    pub async fn get_tick_size(&self, token_id: &str) -> Result<Decimal, ClobClientError> {
+        let path = format!("/tick-size?token_id={token_id}");
+        let resp = self
+            .authenticated_get(&path)?
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let tick: ApiTickSize = resp.json().await?;
+        Ok(tick.minimum_tick_size)
+    }
 }
@@ -47,6 +47,38 @@ pub struct UserTrade {
     pub timestamp: Option<serde_json::Value>,
     #[serde(default, alias = "conditionId")]
     pub market: Option<String>,
+    #[serde(default, alias = "transactionHash")]
+    pub transaction_hash: Option<String>,
+}
+
+impl UserTrade {
+    /// Settlement transaction, lowercased like chain listener events.
+    pub fn tx_hash(&self) -> Option<String> {
+        self.transaction_hash
+            .as_deref()
+            .filter(|h| !h.is_empty())
+            .map(str::to_lowercase)
+    }
+}
+
+/// A position held by a user, from the positions endpoint.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct UserPosition {
+    #[serde(default, alias = "asset")]
+    pub token_id: Option<String>,
+    #[serde(default, alias = "conditionId")]
+    pub condition_id: Option<String>,
+    #[serde(default)]
+    pub size: Option<Decimal>,
+    #[serde(default, alias = "avgPrice")]
+    pub avg_price: Option<Decimal>,
+    #[serde(default, alias = "curPrice")]
+    pub cur_price: Option<Decimal>,
+    #[serde(default)]
+    pub outcome: Option<String>,
+    /// True once the market resolved and the tokens only await redemption.
+    #[serde(default)]
+    pub redeemable: Option<bool>,
 }
 
 #[derive(Debug, Clone)]
@@ -151,6 +183,37 @@ impl DataClient {
         Ok(all_entries)
     }
 
+    /// Fetch a single wallet's all-time leaderboard entry.
+    /// Returns `None` if the wallet has no leaderboard record.
+    pub async fn get_leaderboard_entry(
+        &self,
+        address: &str,
+    ) -> Result<Option<LeaderboardEntry>, DataClientError> {
+        let url = format!("{}/v1/leaderboard", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&[
+                ("user", address),
+                ("timePeriod", "ALL"),
+                ("orderBy", "PNL"),
+                ("limit", "1"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let page: Vec<LeaderboardEntry> = resp.json().await?;
+        // The endpoint falls back to the top of the board for unknown users,
+        // so only accept an entry that is actually for `address`.
+        Ok(page.into_iter().find(|entry| {
+            entry
+                .address
+                .as_deref()
+                .is_some_and(|a| a.eq_ignore_ascii_case(address))
+        }))
+    }
+
     /// Look up a market for resolution purposes.
     ///
     /// All paths resolve via the CLOB API which returns a consistent schema
@@ -231,4 +294,41 @@ impl DataClient {
         let trades: Vec<UserTrade> = resp.json().await?;
         Ok(trades)
     }
+
+    /// Fetch the most recent trades across all markets (newest first).
+    pub async fn get_recent_trades(&self, limit: u32) -> Result<Vec<UserTrade>, DataClientError> {
+        let url = format!("{}/trades", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&[("limit", limit.to_string())])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let trades: Vec<UserTrade> = resp.json().await?;
+        Ok(trades)
+    }
+
+    /// Fetch all current positions held by a user address.
+    pub async fn get_user_positions(
+        &self,
+        address: &str,
+    ) -> Result<Vec<UserPosition>, DataClientError> {
+        let url = format!("{}/positions", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&[
+                ("user", address),
+                ("sizeThreshold", "0"),
+                ("limit", "500"),
+            ])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let positions: Vec<UserPosition> = resp.json().await?;
+        Ok(positions)
+    }
 }
@@ -1,4 +1,6 @@
+use chrono::{DateTime, NaiveDate, Utc};
 use reqwest::Client;
+use rust_decimal::Decimal;
 use serde::{Deserialize, Serialize};
 use thiserror::Error;
 
@@ -13,10 +15,20 @@ pub enum GammaClientError {
     Unexpected(String),
 }
 
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct GammaTag {
+    #[serde(default)]
+    pub label: Option<String>,
+    #[serde(default)]
+    pub slug: Option<String>,
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct GammaEvent {
     #[serde(default)]
     pub slug: Option<String>,
+    #[serde(default)]
+    pub tags: Vec<GammaTag>,
 }
 
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -28,6 +40,11 @@ pub struct GammaMarket {
     pub slug: Option<String>,
     #[serde(default)]
     pub events: Vec<GammaEvent>,
+    /// Category tags, only returned with `include_tag=true`.
+    #[serde(default)]
+    pub tags: Vec<GammaTag>,
+    #[serde(default)]
+    pub category: Option<String>,
     /// Outcome labels, e.g. ["Yes","No"] or ["G2 Esports","Karmine Corp"]
     #[serde(default)]
     pub outcomes: Vec<String>,
@@ -40,6 +57,12 @@ pub struct GammaMarket {
     pub liquidity: Option<String>,
     #[serde(default, alias = "endDateIso")]
     pub end_date_iso: Option<String>,
+    /// Full end timestamp; `endDateIso` only carries the date.
+    #[serde(default, alias = "endDate")]
+    pub end_date: Option<String>,
+    /// Best ask minus best bid.
+    #[serde(default)]
+    pub spread: Option<Decimal>,
 }
 
 impl GammaMarket {
@@ -60,6 +83,11 @@ impl GammaMarket {
             .or(self.slug.as_deref())
     }
 
+    /// Most precise end date available, as stored in active_markets.
+    pub fn end_date_raw(&self) -> Option<&str> {
+        self.end_date.as_deref().or(self.end_date_iso.as_deref())
+    }
+
     /// Serialize outcomes to a JSON string for DB storage.
     pub fn outcomes_json(&self) -> Option<String> {
         if self.outcomes.is_empty() {
@@ -68,6 +96,54 @@ impl GammaMarket {
             serde_json::to_string(&self.outcomes).ok()
         }
     }
+
+    /// Lowercased tag slugs and labels from the market and its events.
+    pub fn tag_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self
+            .tags
+            .iter()
+            .chain(self.events.iter().flat_map(|e| e.tags.iter()))
+            .flat_map(|t| [t.slug.as_deref(), t.label.as_deref()])
+            .flatten()
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty())
+            .collect();
+        names.sort();
+        names.dedup();
+        names
+    }
+
+    /// Serialize tag names to a JSON string for DB storage.
+    pub fn tags_json(&self) -> Option<String> {
+        let names = self.tag_names();
+        if names.is_empty() {
+            None
+        } else {
+            serde_json::to_string(&names).ok()
+        }
+    }
+}
+
+#[cfg(test)]
+impl GammaMarket {
+    /// Bare market for tests; override fields with struct update syntax.
+    pub fn test_fixture() -> Self {
+        Self {
+            condition_id: "0xabc".into(),
+            question: "q".into(),
+            slug: None,
+            events: Vec::new(),
+            tags: Vec::new(),
+            category: None,
+            outcomes: Vec::new(),
+            clob_token_ids: None,
+            volume: None,
+            liquidity: None,
+            end_date_iso: None,
+            end_date: None,
+            spread: None,
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -103,6 +179,7 @@ impl GammaClient {
             .query(&[
                 ("active", "true"),
                 ("closed", "false"),
+                ("include_tag", "true"),
                 ("limit", &limit.to_string()),
                 ("offset", &offset.to_string()),
             ])
@@ -113,4 +190,89 @@ impl GammaClient {
         let markets: Vec<GammaMarket> = resp.json().await?;
         Ok(markets)
     }
+
+    /// Look up a single market by its condition_id.
+    pub async fn get_market_by_condition_id(
+        &self,
+        condition_id: &str,
+    ) -> Result<Option<GammaMarket>, GammaClientError> {
+        let url = format!("{}/markets", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&[("condition_ids", condition_id)])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let markets: Vec<GammaMarket> = resp.json().await?;
+        Ok(markets.into_iter().next())
+    }
+
+    /// Look up the market one of whose outcome tokens is `token_id`.
+    pub async fn get_market_by_token_id(&self, token_id: &str) -> Result<Option<GammaMarket>, GammaClientError> {
+        let url = format!("{}/markets", self.base_url);
+        let resp = self
+            .http
+            .get(&url)
+            .query(&[("clob_token_ids", token_id)])
+            .send()
+            .await?
+            .error_for_status()?;
+
+        let markets: Vec<GammaMarket> = resp.json().await?;
+        Ok(markets.into_iter().next())
+    }
+}
+
+/// Parse a Gamma end date: a full RFC 3339 timestamp, or a bare `YYYY-MM-DD`
+/// date taken as midnight UTC (the earliest the market could end that day).
+pub fn parse_end_date(raw: &str) -> Option<DateTime<Utc>> {
+    if let Ok(ts) = DateTime::parse_from_rfc3339(raw) {
+        return Some(ts.with_timezone(&Utc));
+    }
+    NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .ok()
+        .and_then(|d| d.and_hms_opt(0, 0, 0))
+        .map(|dt| dt.and_utc())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_end_date_formats() {
+        let full = parse_end_date("2026-11-03T12:00:00Z").unwrap();
+        assert_eq!(full.to_rfc3339(), "2026-11-03T12:00:00+00:00");
+
+        let date_only = parse_end_date("2026-11-03").unwrap();
+        assert_eq!(date_only.to_rfc3339(), "2026-11-03T00:00:00+00:00");
+
+        assert!(parse_end_date("soon").is_none());
+    }
+
+    #[test]
+    fn test_tag_names_merges_market_and_event_tags() {
+        let market: GammaMarket = serde_json::from_value(serde_json::json!({
+            "conditionId": "0xabc",
+            "question": "Will it rain?",
+            "tags": [{"label": "Weather", "slug": "weather"}],
+            "events": [{"slug": "rain", "tags": [{"label": "US Politics", "slug": "us-politics"}, {"slug": "weather"}]}]
+        }))
+        .unwrap();
+
+        assert_eq!(
+            market.tag_names(),
+            vec!["us politics", "us-politics", "weather"]
+        );
+        assert_eq!(
+            market.tags_json().as_deref(),
+            Some(r#"["us politics","us-politics","weather"]"#)
+        );
+    }
 }
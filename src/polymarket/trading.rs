@@ -1,7 +1,8 @@
 use std::sync::Arc;
 
-use polymarket_client_sdk::clob::types::response::{OpenOrderResponse, PostOrderResponse};
-use polymarket_client_sdk::clob::types::Side as SdkSide;
+use polymarket_client_sdk::clob::types::request::TradesRequest;
+use polymarket_client_sdk::clob::types::response::{OpenOrderResponse, PostOrderResponse, TradeResponse};
+use polymarket_client_sdk::clob::types::{OrderType, Side as SdkSide};
 use polymarket_client_sdk::types::U256;
 use rust_decimal::Decimal;
 
@@ -122,6 +123,55 @@ impl TradingClient {
         Ok(response)
     }
 
+    /// Place a fill-and-kill limit order: it takes whatever liquidity the book
+    /// offers up to `max_price` (down to it for a SELL) and the remainder is
+    /// cancelled instead of resting.
+    pub async fn place_marketable_order(
+        &self,
+        token_id: &str,
+        side: &str,
+        size: Decimal,
+        max_price: Decimal,
+    ) -> anyhow::Result<PostOrderResponse> {
+        let sdk_side = match side.to_uppercase().as_str() {
+            "BUY" => SdkSide::Buy,
+            _ => SdkSide::Sell,
+        };
+
+        let token_id_u256 = U256::from_str_radix(token_id, 10)
+            .or_else(|_| {
+                token_id
+                    .strip_prefix("0x")
+                    .map(|hex| U256::from_str_radix(hex, 16))
+                    .unwrap_or_else(|| U256::from_str_radix(token_id, 16))
+            })?;
+
+        let client = self.wallet.client();
+        let signer = self.wallet.signer();
+
+        let signable_order = client
+            .limit_order()
+            .token_id(token_id_u256)
+            .side(sdk_side)
+            .price(max_price)
+            .size(size)
+            .order_type(OrderType::FAK)
+            .build()
+            .await?;
+
+        let signed_order = client.sign(signer, signable_order).await?;
+        let response = client.post_order(signed_order).await?;
+
+        tracing::info!(
+            order_id = ?response.order_id,
+            status = ?response.status,
+            max_price = %max_price,
+            "Marketable order submitted to CLOB (FAK)"
+        );
+
+        Ok(response)
+    }
+
     /// Cancel a single order by CLOB order ID.
     pub async fn cancel_order(&self, order_id: &str) -> anyhow::Result<()> {
         self.wallet.client().cancel_order(order_id).await?;
@@ -142,6 +192,13 @@ impl TradingClient {
         Ok(order)
     }
 
+    /// Look up one of our trades by its CLOB trade ID.
+    pub async fn get_trade(&self, trade_id: &str) -> anyhow::Result<Option<TradeResponse>> {
+        let request = TradesRequest::builder().id(trade_id).build();
+        let page = self.wallet.client().trades(&request, None).await?;
+        Ok(page.data.into_iter().next())
+    }
+
     /// Query all open orders (first page).
     pub async fn get_open_orders(&self) -> anyhow::Result<Vec<OpenOrderResponse>> {
         let page = self
@@ -93,6 +93,86 @@ impl WsSubscribe {
     }
 }
 
+/// Change to the asset set of an open market channel connection:
+/// {"assets_ids": [...], "operation": "subscribe" | "unsubscribe"}
+#[derive(Debug, Clone, Serialize)]
+pub struct WsSubscriptionUpdate {
+    pub assets_ids: Vec<String>,
+    pub operation: String,
+}
+
+impl WsSubscriptionUpdate {
+    pub fn subscribe(asset_ids: &[String]) -> Self {
+        Self {
+            assets_ids: asset_ids.to_vec(),
+            operation: "subscribe".into(),
+        }
+    }
+
+    pub fn unsubscribe(asset_ids: &[String]) -> Self {
+        Self {
+            assets_ids: asset_ids.to_vec(),
+            operation: "unsubscribe".into(),
+        }
+    }
+}
+
+/// Credentials authenticating a user channel subscription.
+#[derive(Debug, Clone, Serialize)]
+pub struct WsAuth {
+    #[serde(rename = "apiKey")]
+    pub api_key: String,
+    pub secret: String,
+    pub passphrase: String,
+}
+
+/// Subscription to the authenticated user channel: every order and trade
+/// event of the API key, across all markets.
+/// Polymarket WS format: {"type": "user", "auth": {...}, "markets": []}
+#[derive(Debug, Clone, Serialize)]
+pub struct WsUserSubscribe {
+    #[serde(rename = "type")]
+    pub msg_type: String,
+    pub auth: WsAuth,
+    pub markets: Vec<String>,
+}
+
+impl WsUserSubscribe {
+    pub fn user(auth: WsAuth) -> Self {
+        Self {
+            msg_type: "user".into(),
+            auth,
+            markets: Vec::new(),
+        }
+    }
+}
+
+/// An order or trade event on the user channel (event_type: "order" / "trade").
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WsUserEvent {
+    #[serde(default)]
+    pub event_type: Option<String>,
+    /// Order ID for order events, trade ID for trade events.
+    #[serde(default)]
+    pub id: Option<String>,
+    /// Order events: PLACEMENT, UPDATE or CANCELLATION.
+    #[serde(default, rename = "type")]
+    pub msg_type: Option<String>,
+    /// Trade events: MATCHED, MINED, CONFIRMED, RETRYING or FAILED.
+    #[serde(default)]
+    pub status: Option<String>,
+    #[serde(default)]
+    pub taker_order_id: Option<String>,
+    #[serde(default)]
+    pub maker_orders: Vec<WsMakerOrder>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WsMakerOrder {
+    #[serde(default)]
+    pub order_id: Option<String>,
+}
+
 /// A trade event from the WebSocket (event_type: "last_trade_price").
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct WsTradeEvent {
@@ -112,6 +192,55 @@ pub struct WsTradeEvent {
     pub timestamp: Option<String>,
 }
 
+/// A quote update from the WebSocket (event_type: "price_change").
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WsPriceChangeEvent {
+    #[serde(default)]
+    pub event_type: Option<String>,
+    #[serde(default)]
+    pub market: Option<String>,
+    #[serde(default)]
+    pub price_changes: Vec<WsPriceChange>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+/// A full orderbook snapshot from the WebSocket (event_type: "book"), sent
+/// on subscribe and after a trade. Levels are unsorted strings.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WsBookEvent {
+    #[serde(default)]
+    pub event_type: Option<String>,
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub market: Option<String>,
+    #[serde(default)]
+    pub bids: Vec<WsBookLevel>,
+    #[serde(default)]
+    pub asks: Vec<WsBookLevel>,
+    #[serde(default)]
+    pub timestamp: Option<String>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WsBookLevel {
+    pub price: String,
+    pub size: String,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct WsPriceChange {
+    #[serde(default)]
+    pub asset_id: Option<String>,
+    #[serde(default)]
+    pub price: Option<String>,
+    #[serde(default)]
+    pub best_bid: Option<String>,
+    #[serde(default)]
+    pub best_ask: Option<String>,
+}
+
 // ---------------------------------------------------------------------------
 // Order Book (CLOB API)
 // ---------------------------------------------------------------------------
@@ -132,4 +261,13 @@ pub struct ApiOrderBook {
     pub asks: Vec<ApiOrderBookLevel>,
     pub hash: Option<String>,
     pub timestamp: Option<String>,
+    #[serde(default)]
+    pub tick_size: Option<Decimal>,
+    #[serde(default)]
+    pub min_order_size: Option<Decimal>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ApiTickSize {
+    pub minimum_tick_size: Decimal,
 }
@@ -0,0 +1,274 @@
+use std::collections::{HashMap, HashSet};
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::{basket_repo, insider_repo, trade_repo, whale_repo};
+use crate::intelligence::{check_admission, AdmissionResult};
+use crate::models::{BasketCategory, Whale};
+use crate::services::category_baskets::whale_categories;
+use crate::services::job_trigger::RunRequests;
+use crate::services::whale_seeder::SEEDER_TIERS;
+
+/// Outcome of one rebalancing pass.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RebalanceSummary {
+    /// Active baskets checked.
+    pub baskets: usize,
+    /// Members removed for no longer qualifying.
+    pub evicted: u32,
+    /// Whales added to open slots.
+    pub added: u32,
+}
+
+/// Evict basket members that were deactivated or no longer pass basket
+/// admission, then fill each basket's open slots with admitted whales that
+/// mostly trade its category, best win rate first. Every change is logged
+/// with its reason in `basket_membership_events`.
+pub async fn rebalance_baskets(pool: &PgPool, config: &AppConfig) -> anyhow::Result<RebalanceSummary> {
+    let now = Utc::now();
+    let baskets = basket_repo::get_active_baskets(pool).await?;
+    let mut summary = RebalanceSummary {
+        baskets: baskets.len(),
+        ..Default::default()
+    };
+
+    let mut whales = whale_repo::get_active_whales(pool).await?;
+    whales.sort_by_key(|w| std::cmp::Reverse(w.win_rate));
+    let mut members: HashMap<Uuid, Vec<Whale>> = HashMap::new();
+    for basket in &baskets {
+        members.insert(basket.id, basket_repo::get_basket_whales(pool, basket.id).await?);
+    }
+
+    let mut ids: Vec<Uuid> = whales.iter().map(|w| w.id).collect();
+    ids.extend(members.values().flatten().map(|w| w.id));
+    ids.sort();
+    ids.dedup();
+    let first_trades: HashMap<Uuid, DateTime<Utc>> =
+        trade_repo::get_first_trade_times(pool, &ids).await?.into_iter().collect();
+
+    // Admission is checked once per whale, however many baskets it is in
+    let mut admissions: HashMap<Uuid, AdmissionResult> = HashMap::new();
+    for whale in whales.iter().chain(members.values().flatten()) {
+        if admissions.contains_key(&whale.id) {
+            continue;
+        }
+        let insider_flags = if config.basket_max_insider_flags > 0 {
+            insider_repo::count_whale_flags(pool, whale.id).await?
+        } else {
+            0
+        };
+        let admission = admit(whale, first_trades.get(&whale.id).copied(), insider_flags, config, now);
+        admissions.insert(whale.id, admission);
+    }
+
+    let admitted: Vec<&Whale> = whales
+        .iter()
+        .filter(|w| matches!(admissions.get(&w.id), Some(AdmissionResult::Accepted)))
+        .collect();
+    let admitted_ids: Vec<Uuid> = admitted.iter().map(|w| w.id).collect();
+    let mut questions: HashMap<Uuid, Vec<(String, i64)>> = HashMap::new();
+    for (whale_id, question, trades) in trade_repo::get_whale_market_questions(pool, &admitted_ids).await? {
+        questions.entry(whale_id).or_default().push((question, trades));
+    }
+    let categories: HashMap<Uuid, Vec<BasketCategory>> = questions
+        .iter()
+        .map(|(id, traded)| (*id, whale_categories(traded)))
+        .collect();
+
+    for basket in &baskets {
+        let current = members.remove(&basket.id).unwrap_or_default();
+        let mut kept = HashSet::new();
+
+        for whale in &current {
+            match eviction_reason(whale, admissions.get(&whale.id)) {
+                Some(reason) => {
+                    if basket_repo::remove_whale_from_basket(pool, basket.id, whale.id).await? {
+                        basket_repo::record_membership_event(pool, basket.id, whale.id, "removed", "rebalance", &reason).await?;
+                        tracing::info!(basket = %basket.name, wallet = %whale.address, reason = %reason, "Whale evicted from basket");
+                        summary.evicted += 1;
+                    }
+                }
+                None => {
+                    kept.insert(whale.id);
+                }
+            }
+        }
+
+        let Some(category) = BasketCategory::parse_category(&basket.category) else {
+            continue;
+        };
+        let open = (basket.max_wallets as usize).saturating_sub(kept.len());
+        for whale in backfill_candidates(category, &admitted, &categories, &kept, open) {
+            let reason = format!(
+                "back-filled: passes admission and trades {} (win rate {})",
+                category,
+                whale.win_rate.unwrap_or(Decimal::ZERO).round_dp(2),
+            );
+            if !basket_repo::add_whale_to_basket(pool, basket.id, whale.id).await? {
+                continue;
+            }
+            basket_repo::record_membership_event(pool, basket.id, whale.id, "added", "rebalance", &reason).await?;
+            tracing::info!(basket = %basket.name, wallet = %whale.address, "Whale back-filled into basket");
+            summary.added += 1;
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Basket admission for a whale as the pipeline judges it: seeder-vetted
+/// whales are admitted outright, history runs from the first recorded trade
+/// and an operator override vouches for the trading frequency.
+pub fn admit(
+    whale: &Whale,
+    first_trade_at: Option<DateTime<Utc>>,
+    insider_flags: i64,
+    config: &AppConfig,
+    now: DateTime<Utc>,
+) -> AdmissionResult {
+    if whale.classification.as_deref().is_some_and(|c| SEEDER_TIERS.contains(&c)) {
+        return AdmissionResult::Accepted;
+    }
+
+    let months_active = (now - first_trade_at.unwrap_or(now)).num_days() / 30;
+    let months_active = months_active.max(1);
+    let total_trades = whale.total_trades.unwrap_or(0);
+    let avg_monthly_trades = if whale.active_classification_override(now).is_some() {
+        Decimal::ZERO
+    } else {
+        Decimal::from(total_trades) / Decimal::from(months_active)
+    };
+
+    check_admission(
+        whale.win_rate.unwrap_or(Decimal::ZERO),
+        whale.effective_classification(now),
+        months_active,
+        total_trades,
+        avg_monthly_trades,
+        whale.max_drawdown_pct.unwrap_or(Decimal::ZERO),
+        config.basket_max_drawdown_pct,
+        whale.skill_score,
+        config.basket_min_skill_score,
+        insider_flags,
+        config.basket_max_insider_flags,
+    )
+}
+
+/// Why a member should leave its basket, None to keep it. Deactivated whales
+/// are never checked against admission.
+fn eviction_reason(whale: &Whale, admission: Option<&AdmissionResult>) -> Option<String> {
+    if whale.is_active != Some(true) {
+        return Some("whale deactivated".into());
+    }
+    match admission {
+        Some(AdmissionResult::Rejected(reason)) => Some(format!("no longer passes admission: {reason}")),
+        _ => None,
+    }
+}
+
+/// Up to `open` admitted whales (ordered best first) that trade `category`
+/// and aren't already in the basket.
+fn backfill_candidates<'a>(
+    category: BasketCategory,
+    admitted: &[&'a Whale],
+    categories: &HashMap<Uuid, Vec<BasketCategory>>,
+    members: &HashSet<Uuid>,
+    open: usize,
+) -> Vec<&'a Whale> {
+    admitted
+        .iter()
+        .filter(|w| !members.contains(&w.id))
+        .filter(|w| categories.get(&w.id).is_some_and(|c| c.contains(&category)))
+        .take(open)
+        .copied()
+        .collect()
+}
+
+/// Periodically rebalance every active basket.
+/// `run_requests` runs a pass immediately and answers with its summary.
+pub async fn run_basket_rebalance_loop(
+    pool: PgPool,
+    config: AppConfig,
+    mut run_requests: RunRequests<Result<RebalanceSummary, String>>,
+) {
+    let mut ticker = interval(Duration::from_secs(config.basket_rebalance_interval_secs));
+
+    loop {
+        let reply = tokio::select! {
+            _ = ticker.tick() => None,
+            Some(reply) = run_requests.recv() => Some(reply),
+        };
+        let result = rebalance_baskets(&pool, &config).await;
+        match result {
+            Ok(ref s) => tracing::info!(
+                baskets = s.baskets,
+                evicted = s.evicted,
+                added = s.added,
+                "Basket rebalancing complete"
+            ),
+            Err(ref e) => tracing::error!(error = %e, "Basket rebalancing failed"),
+        }
+        if let Some(reply) = reply {
+            let _ = reply.send(result.map_err(|e| e.to_string()));
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn whale(win_rate: Decimal) -> Whale {
+        Whale {
+            win_rate: Some(win_rate),
+            ..Whale::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_eviction_reason() {
+        let mut w = whale(Decimal::new(70, 2));
+        assert_eq!(eviction_reason(&w, Some(&AdmissionResult::Accepted)), None);
+        assert_eq!(
+            eviction_reason(&w, Some(&AdmissionResult::Rejected("win rate below 60%".into()))),
+            Some("no longer passes admission: win rate below 60%".into())
+        );
+
+        w.is_active = Some(false);
+        assert_eq!(
+            eviction_reason(&w, Some(&AdmissionResult::Accepted)),
+            Some("whale deactivated".into())
+        );
+    }
+
+    #[test]
+    fn test_backfill_candidates() {
+        let (a, b, c) = (whale(Decimal::new(80, 2)), whale(Decimal::new(75, 2)), whale(Decimal::new(70, 2)));
+        let admitted = vec![&a, &b, &c];
+        let categories = HashMap::from([
+            (a.id, vec![BasketCategory::Politics]),
+            (b.id, vec![BasketCategory::Crypto, BasketCategory::Politics]),
+            (c.id, vec![BasketCategory::Politics]),
+        ]);
+
+        // a is already a member; one slot goes to the next best politics trader
+        let members = HashSet::from([a.id]);
+        let picked = backfill_candidates(BasketCategory::Politics, &admitted, &categories, &members, 1);
+        assert_eq!(picked.iter().map(|w| w.id).collect::<Vec<_>>(), vec![b.id]);
+
+        let picked = backfill_candidates(BasketCategory::Crypto, &admitted, &categories, &HashSet::new(), 5);
+        assert_eq!(picked.iter().map(|w| w.id).collect::<Vec<_>>(), vec![b.id]);
+        assert!(backfill_candidates(BasketCategory::Sports, &admitted, &categories, &HashSet::new(), 5).is_empty());
+        assert!(backfill_candidates(BasketCategory::Politics, &admitted, &categories, &members, 0).is_empty());
+    }
+}
@@ -0,0 +1,142 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+
+use crate::config::AppConfig;
+use crate::db::{basket_repo, market_repo, whale_repo};
+use crate::models::BasketCategory;
+use crate::polymarket::{
+    BalanceChecker, ClobClient, DataClient, GammaClient, PolymarketAuth, PolymarketWallet,
+};
+
+use super::{category_baskets, market_discovery, whale_seeder};
+
+/// One-shot cold-start setup, run via `polybot bootstrap`.
+///
+/// 1. Verify connectivity to the database and Polymarket APIs
+/// 2. Create a default basket for every category that has none
+/// 3. Run the whale seeder once
+/// 4. Run market discovery once so the WS listener subscribes on next start
+///
+/// Progress is printed to stdout; connectivity failures on optional
+/// services are reported but do not abort the run.
+pub async fn run_bootstrap(config: &AppConfig, pool: &PgPool) -> anyhow::Result<()> {
+    println!("polybot bootstrap");
+    println!();
+
+    // Step 1: Connectivity
+    println!("[1/4] Checking connectivity");
+    sqlx::query("SELECT 1").execute(pool).await?;
+    report("Database", Ok("connected".into()));
+
+    let gamma_client = GammaClient::new();
+    report(
+        "Gamma API",
+        gamma_client
+            .get_active_markets(1, 0)
+            .await
+            .map(|_| "reachable".into())
+            .map_err(|e| e.to_string()),
+    );
+
+    let data_client = DataClient::new(reqwest::Client::new());
+    report(
+        "Data API",
+        data_client
+            .get_leaderboard(1)
+            .await
+            .map(|_| "reachable".into())
+            .map_err(|e| e.to_string()),
+    );
+
+    if config.has_polymarket_auth() {
+        let auth = PolymarketAuth::new(
+            config.polymarket_api_key.clone().unwrap(),
+            config.polymarket_api_secret.clone().unwrap(),
+            config.polymarket_passphrase.clone().unwrap(),
+        );
+        let clob = ClobClient::new(reqwest::Client::new(), auth);
+        report(
+            "CLOB API",
+            clob.get_markets()
+                .await
+                .map(|_| "authenticated".into())
+                .map_err(|e| e.to_string()),
+        );
+    } else {
+        println!("  - CLOB API: skipped (no API credentials)");
+    }
+
+    if config.has_private_key() {
+        let result = match PolymarketWallet::new(config.private_key.as_ref().unwrap()).await {
+            Ok(w) => {
+                let w = Arc::new(w);
+                let address = w.wallet_address();
+                BalanceChecker::new(w)
+                    .get_usdc_balance()
+                    .await
+                    .map(|usdc| format!("{address} — {usdc} USDC"))
+                    .map_err(|e| e.to_string())
+            }
+            Err(e) => Err(e.to_string()),
+        };
+        report("Wallet", result);
+    } else {
+        println!("  - Wallet: skipped (no private key, monitor-only mode)");
+    }
+
+    // Step 2: Default baskets
+    println!();
+    println!("[2/4] Provisioning default baskets");
+    let existing = basket_repo::get_active_baskets(pool).await?;
+    for b in &existing {
+        if BasketCategory::parse_category(&b.category).is_some() {
+            println!("  - {}: exists ({})", b.category, b.name);
+        }
+    }
+    for name in category_baskets::ensure_category_baskets(pool, config).await? {
+        println!("  ✓ created {name}");
+    }
+
+    // Step 3: Whale seeding
+    println!();
+    println!("[3/4] Seeding whales from leaderboard");
+    let before = whale_repo::get_active_whales(pool).await?.len();
+    match whale_seeder::run_whale_seeder(&data_client, pool, config).await {
+        Ok(()) => {
+            let after = whale_repo::get_active_whales(pool).await?.len();
+            println!(
+                "  ✓ {after} active whales ({:+} this run)",
+                after as i64 - before as i64
+            );
+        }
+        Err(e) => println!("  ✗ Seeder failed: {e}"),
+    }
+
+    // Step 4: Market discovery
+    println!();
+    println!("[4/4] Discovering active markets");
+    let discovered = market_discovery::discover_markets(
+        &gamma_client,
+        pool,
+        config.market_min_volume,
+        config.market_min_liquidity,
+    )
+    .await;
+    let subscribed = market_repo::get_active_market_token_ids(pool, chrono::Utc::now()).await?.len();
+    println!(
+        "  ✓ {} markets above volume/liquidity thresholds, {} tokens will be subscribed on startup",
+        discovered.markets_found, subscribed,
+    );
+
+    println!();
+    println!("Bootstrap complete. Start the bot with `polybot`.");
+    Ok(())
+}
+
+fn report(name: &str, result: Result<String, String>) {
+    match result {
+        Ok(detail) => println!("  ✓ {name}: {detail}"),
+        Err(e) => println!("  ✗ {name}: {e}"),
+    }
+}
@@ -0,0 +1,168 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use tokio::time::{interval, Duration};
+
+use crate::db::candle_repo;
+use crate::models::candle::minute_bucket;
+use crate::models::{Candle, PriceTick};
+
+/// How often the in-progress candles are written to the DB.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(10);
+/// How often candles past the retention window are deleted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Folds price ticks into per-token 1-minute candles.
+#[derive(Default)]
+pub struct CandleAggregator {
+    /// Current (in-progress) candle per token.
+    open: HashMap<String, Candle>,
+    /// Candles whose minute has rolled over, waiting to be flushed.
+    closed: Vec<Candle>,
+}
+
+impl CandleAggregator {
+    pub fn push(&mut self, tick: &PriceTick) {
+        let bucket = minute_bucket(tick.timestamp);
+
+        match self.open.get_mut(&tick.token_id) {
+            Some(candle) if candle.bucket == bucket => candle.apply(tick),
+            // Late tick for a minute we've already moved past — drop it
+            Some(candle) if candle.bucket > bucket => {}
+            Some(candle) => {
+                let done = std::mem::replace(candle, Candle::from_tick(tick));
+                self.closed.push(done);
+            }
+            None => {
+                self.open.insert(tick.token_id.clone(), Candle::from_tick(tick));
+            }
+        }
+    }
+
+    /// Candles to write: everything closed plus a snapshot of each open candle.
+    /// Open candles from before `now`'s minute are final and get evicted.
+    pub fn take_flush(&mut self, now: DateTime<Utc>) -> Vec<Candle> {
+        let current = minute_bucket(now);
+        let mut out = std::mem::take(&mut self.closed);
+
+        self.open.retain(|_, candle| {
+            out.push(candle.clone());
+            candle.bucket >= current
+        });
+
+        out
+    }
+}
+
+/// Run the candle recorder: aggregate WS price ticks into 1-minute candles,
+/// flush them periodically, and prune candles older than `retention_days`.
+pub async fn run_candle_recorder(pool: PgPool, mut rx: mpsc::Receiver<PriceTick>, retention_days: i64) {
+    let mut aggregator = CandleAggregator::default();
+    let mut flush_timer = interval(FLUSH_INTERVAL);
+    let mut prune_timer = interval(PRUNE_INTERVAL);
+
+    tracing::info!(retention_days, "Candle recorder started");
+
+    loop {
+        tokio::select! {
+            tick = rx.recv() => {
+                match tick {
+                    Some(tick) => aggregator.push(&tick),
+                    None => break,
+                }
+            }
+            _ = flush_timer.tick() => {
+                let candles = aggregator.take_flush(Utc::now());
+                if candles.is_empty() {
+                    continue;
+                }
+                if let Err(e) = candle_repo::upsert_candles(&pool, &candles).await {
+                    tracing::error!(error = %e, count = candles.len(), "Failed to flush candles");
+                }
+            }
+            _ = prune_timer.tick() => {
+                let cutoff = Utc::now() - chrono::Duration::days(retention_days);
+                match candle_repo::delete_candles_before(&pool, cutoff).await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!(deleted = n, "Pruned old candles"),
+                    Err(e) => tracing::error!(error = %e, "Failed to prune candles"),
+                }
+            }
+        }
+    }
+
+    // Channel closed — write whatever is left
+    let candles = aggregator.take_flush(Utc::now());
+    if let Err(e) = candle_repo::upsert_candles(&pool, &candles).await {
+        tracing::error!(error = %e, "Failed to flush candles on shutdown");
+    }
+    tracing::warn!("Candle tick channel closed — recorder stopped");
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use rust_decimal::Decimal;
+
+    fn tick(secs: i64, price: i64, size: i64) -> PriceTick {
+        PriceTick {
+            token_id: "tok".into(),
+            price: Decimal::new(price, 2),
+            size: Decimal::from(size),
+            timestamp: Utc.timestamp_opt(1_700_000_040 + secs, 0).unwrap(),
+        }
+    }
+
+    #[test]
+    fn test_ticks_fold_into_ohlc() {
+        let mut agg = CandleAggregator::default();
+        agg.push(&tick(0, 50, 10));
+        agg.push(&tick(5, 55, 0));
+        agg.push(&tick(10, 45, 5));
+        agg.push(&tick(15, 52, 0));
+
+        let out = agg.take_flush(tick(20, 0, 0).timestamp);
+        assert_eq!(out.len(), 1);
+        let c = &out[0];
+        assert_eq!(c.open, Decimal::new(50, 2));
+        assert_eq!(c.high, Decimal::new(55, 2));
+        assert_eq!(c.low, Decimal::new(45, 2));
+        assert_eq!(c.close, Decimal::new(52, 2));
+        assert_eq!(c.volume, Decimal::from(15));
+        assert_eq!(c.trade_count, 2);
+    }
+
+    #[test]
+    fn test_minute_rollover_closes_candle() {
+        let mut agg = CandleAggregator::default();
+        agg.push(&tick(0, 50, 1));
+        agg.push(&tick(60, 60, 1));
+        // Late tick for the closed minute is ignored
+        agg.push(&tick(30, 99, 1));
+
+        let out = agg.take_flush(tick(65, 0, 0).timestamp);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].close, Decimal::new(50, 2));
+        assert_eq!(out[1].open, Decimal::new(60, 2));
+        assert!(out[0].bucket < out[1].bucket);
+    }
+
+    #[test]
+    fn test_stale_open_candle_evicted_after_flush() {
+        let mut agg = CandleAggregator::default();
+        agg.push(&tick(0, 50, 1));
+
+        // Still the same minute: flushed but kept open
+        assert_eq!(agg.take_flush(tick(30, 0, 0).timestamp).len(), 1);
+        // Minute has passed with no new ticks: flushed one last time, then evicted
+        assert_eq!(agg.take_flush(tick(90, 0, 0).timestamp).len(), 1);
+        assert!(agg.take_flush(tick(120, 0, 0).timestamp).is_empty());
+    }
+}
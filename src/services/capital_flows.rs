@@ -0,0 +1,376 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
+
+use alloy::primitives::{Address, Bytes};
+use alloy::sol_types::{SolCall, SolEvent};
+use chrono::{DateTime, Utc};
+use metrics::counter;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::capital_flow_repo::{self, NewCapitalFlow};
+use crate::db::whale_repo;
+use crate::ingestion::chain_events::{address_key, balanceOfCall, log_data, token_amount, Transfer};
+use crate::ingestion::chain_listener::{
+    block_timestamp, log_block_number, parse_hex_u64, rpc_call, CTF_EXCHANGE, NEG_RISK_ADAPTER,
+    NEG_RISK_CTF_EXCHANGE,
+};
+use crate::models::{CapitalFlowDirection, Whale};
+use crate::services::notifier::{Notifier, Severity};
+
+/// USDC.e on Polygon, the collateral Polymarket wallets hold.
+const USDC: &str = "0x2791bca1f2de4661ed88a30c99a1a9449aa84174";
+
+/// Conditional Tokens contract (splits and merges move USDC through it).
+const CONDITIONAL_TOKENS: &str = "0x4d97dcd97ec945f40cf65f87097ace5ea0476045";
+
+/// Keccak256 of Transfer(address,address,uint256)
+const TRANSFER_TOPIC: &str = "0xddf252ad1be2c89b69c2b068fc378daa952ba7f163c4a11628f55a4df523b3ef";
+
+const USDC_DECIMALS: u32 = 6;
+
+/// Blocks per eth_getLogs request.
+const CHUNK_BLOCKS: u64 = 1_000;
+/// Most blocks scanned in one pass; after a longer outage older transfers
+/// are skipped.
+const MAX_SCAN_BLOCKS: u64 = 10_000;
+/// Whale addresses per eth_getLogs topic filter; RPC providers cap how many
+/// topics one filter may list.
+const ADDRESSES_PER_FILTER: usize = 100;
+
+/// Thresholds for recording and alerting on whale deposits/withdrawals.
+#[derive(Debug, Clone)]
+pub struct CapitalFlowConfig {
+    /// Smallest transfer recorded, in USDC.
+    pub min_amount: Decimal,
+    /// Alert when a withdrawal takes at least this percent of the whale's USDC.
+    pub withdrawal_alert_pct: Decimal,
+    pub interval_secs: u64,
+}
+
+/// One tracked whale's side of a USDC transfer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhaleTransfer {
+    pub wallet: String,
+    pub direction: CapitalFlowDirection,
+    pub counterparty: String,
+}
+
+/// The tracked whales' sides of a USDC transfer from `from` to `to`: a
+/// withdrawal for a whale sender, a deposit for a whale recipient. Transfers
+/// with Polymarket's own contracts are trading, not capital flows.
+pub fn classify_transfer(from: &str, to: &str, whales: &HashSet<String>) -> Vec<WhaleTransfer> {
+    let trading = [CTF_EXCHANGE, NEG_RISK_CTF_EXCHANGE, NEG_RISK_ADAPTER, CONDITIONAL_TOKENS];
+    if from == to || trading.contains(&from) || trading.contains(&to) {
+        return Vec::new();
+    }
+
+    let mut sides = Vec::new();
+    if whales.contains(from) {
+        sides.push(WhaleTransfer {
+            wallet: from.to_string(),
+            direction: CapitalFlowDirection::Withdrawal,
+            counterparty: to.to_string(),
+        });
+    }
+    if whales.contains(to) {
+        sides.push(WhaleTransfer {
+            wallet: to.to_string(),
+            direction: CapitalFlowDirection::Deposit,
+            counterparty: from.to_string(),
+        });
+    }
+    sides
+}
+
+/// Percent of a whale's USDC a withdrawal took out, given what was left.
+pub fn withdrawal_share_pct(amount: Decimal, balance_after: Decimal) -> Decimal {
+    let before = amount + balance_after;
+    if before <= Decimal::ZERO {
+        return Decimal::ZERO;
+    }
+    (amount / before * Decimal::ONE_HUNDRED).round_dp(2)
+}
+
+/// eth_getLogs filters for USDC transfers out of and into tracked whales in
+/// blocks `from..=to`, `ADDRESSES_PER_FILTER` whales per filter.
+fn transfer_filters(whale_addresses: &HashSet<String>, from: u64, to: u64) -> Vec<serde_json::Value> {
+    let mut whales: Vec<String> = whale_addresses
+        .iter()
+        .map(|a| format!("0x{:0>64}", a.trim_start_matches("0x")))
+        .collect();
+    whales.sort();
+    let (from, to) = (format!("{from:#x}"), format!("{to:#x}"));
+
+    whales
+        .chunks(ADDRESSES_PER_FILTER)
+        .flat_map(|chunk| {
+            [
+                serde_json::json!({
+                    "fromBlock": from, "toBlock": to,
+                    "address": USDC,
+                    "topics": [TRANSFER_TOPIC, chunk]
+                }),
+                serde_json::json!({
+                    "fromBlock": from, "toBlock": to,
+                    "address": USDC,
+                    "topics": [TRANSFER_TOPIC, null, chunk]
+                }),
+            ]
+        })
+        .collect()
+}
+
+/// USDC balance of a wallet as of the end of `block`.
+async fn usdc_balance(http: &reqwest::Client, rpc_url: &str, wallet: &str, block: u64) -> anyhow::Result<Decimal> {
+    let account: Address = wallet.parse()?;
+    let call = balanceOfCall { account };
+    let result = rpc_call(
+        http,
+        rpc_url,
+        "eth_call",
+        serde_json::json!([{ "to": USDC, "data": Bytes::from(call.abi_encode()) }, format!("{block:#x}")]),
+    )
+    .await?;
+
+    let raw: Bytes = result
+        .as_str()
+        .ok_or_else(|| anyhow::anyhow!("eth_call returned no result: {result}"))?
+        .parse()?;
+    let balance = balanceOfCall::abi_decode_returns(&raw)?;
+    Ok(token_amount(balance, USDC_DECIMALS))
+}
+
+/// Periodically scan new Polygon blocks for large USDC transfers into and
+/// out of tracked whale wallets, recording each and alerting when a whale
+/// withdraws most of its USDC — a strong sign it is winding down. Only USDC
+/// held in the wallet counts as capital; open positions don't.
+pub async fn run_capital_flow_monitor(
+    pool: PgPool,
+    rpc_url: String,
+    config: CapitalFlowConfig,
+    notifier: Option<Arc<Notifier>>,
+) {
+    let http = reqwest::Client::new();
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+    // Last block scanned; the first pass only finds the chain head
+    let mut last_block: Option<u64> = None;
+
+    tracing::info!(
+        min_amount = %config.min_amount,
+        withdrawal_alert_pct = %config.withdrawal_alert_pct,
+        "Whale capital flow monitor started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        match scan(&pool, &http, &rpc_url, &config, notifier.as_deref(), last_block).await {
+            Ok(head) => last_block = Some(head),
+            Err(e) => tracing::error!(error = %e, "Capital flows: scan failed"),
+        }
+    }
+}
+
+/// Record whale transfers in the blocks after `last_block` up to the chain
+/// head. Returns the head.
+async fn scan(
+    pool: &PgPool,
+    http: &reqwest::Client,
+    rpc_url: &str,
+    config: &CapitalFlowConfig,
+    notifier: Option<&Notifier>,
+    last_block: Option<u64>,
+) -> anyhow::Result<u64> {
+    let head = rpc_call(http, rpc_url, "eth_blockNumber", serde_json::json!([])).await?;
+    let head = head
+        .as_str()
+        .and_then(parse_hex_u64)
+        .ok_or_else(|| anyhow::anyhow!("invalid eth_blockNumber result: {head}"))?;
+    let Some(last_block) = last_block.filter(|last| *last < head) else {
+        return Ok(head);
+    };
+
+    let whales = whale_repo::get_active_whales(pool).await?;
+    let by_address: HashMap<String, &Whale> = whales.iter().map(|w| (w.address.to_lowercase(), w)).collect();
+    if by_address.is_empty() {
+        return Ok(head);
+    }
+    let addresses: HashSet<String> = by_address.keys().cloned().collect();
+
+    let from = (last_block + 1).max(head.saturating_sub(MAX_SCAN_BLOCKS - 1));
+    let mut logs = Vec::new();
+    for chunk_start in (from..=head).step_by(CHUNK_BLOCKS as usize) {
+        let chunk_end = (chunk_start + CHUNK_BLOCKS - 1).min(head);
+        for filter in transfer_filters(&addresses, chunk_start, chunk_end) {
+            let result = rpc_call(http, rpc_url, "eth_getLogs", serde_json::json!([filter])).await?;
+            logs.extend(result.as_array().cloned().unwrap_or_default());
+        }
+    }
+
+    let mut block_times: HashMap<u64, DateTime<Utc>> = HashMap::new();
+    for log in &logs {
+        let Some(transfer) = log_data(log).and_then(|data| Transfer::decode_log_data(&data).ok()) else {
+            continue;
+        };
+        let amount = token_amount(transfer.value, USDC_DECIMALS);
+        if amount < config.min_amount {
+            continue;
+        }
+        let block = log_block_number(log).unwrap_or(head);
+        let log_index = log
+            .get("logIndex")
+            .and_then(|i| i.as_str())
+            .and_then(parse_hex_u64)
+            .unwrap_or(0);
+        let tx_hash = log
+            .get("transactionHash")
+            .and_then(|h| h.as_str())
+            .unwrap_or_default()
+            .to_lowercase();
+
+        for side in classify_transfer(&address_key(transfer.from), &address_key(transfer.to), &addresses) {
+            let Some(whale) = by_address.get(&side.wallet) else {
+                continue;
+            };
+            let occurred_at = match block_times.get(&block) {
+                Some(ts) => *ts,
+                None => {
+                    let ts = block_timestamp(http, rpc_url, block).await.unwrap_or_else(|_| Utc::now());
+                    block_times.insert(block, ts);
+                    ts
+                }
+            };
+            let balance_after = match usdc_balance(http, rpc_url, &side.wallet, block).await {
+                Ok(balance) => Some(balance),
+                Err(e) => {
+                    tracing::warn!(error = %e, whale = %whale.address, "Capital flows: balance lookup failed");
+                    None
+                }
+            };
+            let capital_share_pct = balance_after
+                .filter(|_| side.direction == CapitalFlowDirection::Withdrawal)
+                .map(|balance| withdrawal_share_pct(amount, balance));
+
+            let flow = NewCapitalFlow {
+                whale_id: whale.id,
+                direction: side.direction,
+                amount,
+                counterparty: side.counterparty,
+                balance_after,
+                capital_share_pct,
+                tx_hash: tx_hash.clone(),
+                log_index: log_index as i64,
+                block_number: block as i64,
+                occurred_at,
+            };
+            let Some(flow) = capital_flow_repo::insert_flow(pool, &flow).await? else {
+                continue;
+            };
+
+            counter!("whale_capital_flows_total", "direction" => side.direction.as_str()).increment(1);
+            tracing::info!(
+                whale = %whale.address,
+                direction = %side.direction,
+                amount = %amount,
+                balance_after = ?balance_after,
+                capital_share_pct = ?capital_share_pct,
+                tx_hash = %flow.tx_hash,
+                "Whale capital flow recorded"
+            );
+
+            let Some(share) = capital_share_pct.filter(|pct| *pct >= config.withdrawal_alert_pct) else {
+                continue;
+            };
+            tracing::warn!(
+                whale = %whale.address,
+                amount = %amount,
+                capital_share_pct = %share,
+                "Whale withdrew most of its capital"
+            );
+            if let Some(n) = notifier {
+                let msg = crate::services::notifier::format_whale_capital_withdrawal(
+                    &whale.address,
+                    whale.label.as_deref(),
+                    amount,
+                    share,
+                    &flow.tx_hash,
+                );
+                n.notify(Severity::Warning, &msg).await;
+            }
+        }
+    }
+
+    Ok(head)
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WHALE: &str = "0xabcdef1234567890abcdef1234567890abcdef12";
+    const OTHER_WHALE: &str = "0x1111111111111111111111111111111111111111";
+    const EXTERNAL: &str = "0x2222222222222222222222222222222222222222";
+
+    #[test]
+    fn test_classify_transfer() {
+        let whales: HashSet<String> = [WHALE.to_string(), OTHER_WHALE.to_string()].into();
+
+        assert_eq!(
+            classify_transfer(WHALE, EXTERNAL, &whales),
+            vec![WhaleTransfer {
+                wallet: WHALE.into(),
+                direction: CapitalFlowDirection::Withdrawal,
+                counterparty: EXTERNAL.into(),
+            }]
+        );
+        assert_eq!(
+            classify_transfer(EXTERNAL, WHALE, &whales)[0].direction,
+            CapitalFlowDirection::Deposit
+        );
+
+        // Between two whales: one withdrawal and one deposit
+        let sides = classify_transfer(WHALE, OTHER_WHALE, &whales);
+        assert_eq!(sides.len(), 2);
+        assert_eq!(sides[1].wallet, OTHER_WHALE);
+
+        // Trading with the exchange, splits via the CTF, unrelated wallets
+        assert!(classify_transfer(WHALE, CTF_EXCHANGE, &whales).is_empty());
+        assert!(classify_transfer(CONDITIONAL_TOKENS, WHALE, &whales).is_empty());
+        assert!(classify_transfer(EXTERNAL, EXTERNAL, &whales).is_empty());
+    }
+
+    #[test]
+    fn test_withdrawal_share_pct() {
+        assert_eq!(withdrawal_share_pct(Decimal::from(90_000), Decimal::from(10_000)), Decimal::from(90));
+        assert_eq!(withdrawal_share_pct(Decimal::from(50_000), Decimal::ZERO), Decimal::ONE_HUNDRED);
+        assert_eq!(withdrawal_share_pct(Decimal::ZERO, Decimal::ZERO), Decimal::ZERO);
+    }
+
+    #[test]
+    fn test_transfer_topic_and_balance_call() {
+        assert_eq!(format!("{:#x}", Transfer::SIGNATURE_HASH), TRANSFER_TOPIC);
+
+        let filters = transfer_filters(&[WHALE.to_string()].into(), 16, 31);
+        assert_eq!(filters[0]["fromBlock"], "0x10");
+        assert_eq!(filters[1]["topics"][1], serde_json::Value::Null);
+        assert_eq!(
+            filters[1]["topics"][2][0],
+            "0x000000000000000000000000abcdef1234567890abcdef1234567890abcdef12"
+        );
+
+        // Long whale lists are split across filters
+        let many: HashSet<String> = (0..250).map(|i| format!("0x{i:040x}")).collect();
+        let filters = transfer_filters(&many, 16, 31);
+        assert_eq!(filters.len(), 6);
+        assert_eq!(filters[0]["topics"][1].as_array().map(Vec::len), Some(ADDRESSES_PER_FILTER));
+        assert_eq!(filters[5]["topics"][2].as_array().map(Vec::len), Some(50));
+
+        assert_eq!(balanceOfCall::SELECTOR, [0x70, 0xa0, 0x82, 0x31]);
+    }
+}
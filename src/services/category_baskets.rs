@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+
+use chrono::{DateTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::PgPool;
+use uuid::Uuid;
+
+use crate::config::AppConfig;
+use crate::db::{basket_repo, insider_repo, trade_repo, whale_repo};
+use crate::intelligence::basket::{auto_assign_to_baskets, infer_market_category};
+use crate::intelligence::AdmissionResult;
+use crate::models::{BasketCategory, Whale};
+
+use super::basket_rebalance::admit;
+use super::whale_seeder::SEEDER_TIERS;
+
+/// A category must account for at least this share of a whale's categorised
+/// trades for the whale to join that category's basket.
+const MIN_CATEGORY_SHARE: Decimal = Decimal::from_parts(25, 0, 0, false, 2); // 0.25
+
+/// Whales that aren't seeder-vetted need at least this win rate (basket admission's bar).
+const MIN_WIN_RATE: Decimal = Decimal::from_parts(60, 0, 0, false, 2); // 0.60
+
+/// Outcome of one category basket pass.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CategoryBasketSummary {
+    /// Names of the baskets created this pass.
+    pub created: Vec<String>,
+    /// Whale-to-basket assignments made.
+    pub assigned: u32,
+}
+
+/// Default name of a category's basket, e.g. "Politics Whales".
+pub fn default_basket_name(category: BasketCategory) -> String {
+    let name = category.as_str();
+    let mut chars = name.chars();
+    let title: String = match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    };
+    format!("{title} Whales")
+}
+
+/// Create a basket for every category that has never had one. A category
+/// whose basket the operator deactivated is left without one. Returns the
+/// names of the baskets created.
+pub async fn ensure_category_baskets(pool: &PgPool, config: &AppConfig) -> anyhow::Result<Vec<String>> {
+    let existing = basket_repo::get_basket_categories(pool).await?;
+    let mut created = Vec::new();
+
+    for category in BasketCategory::ALL {
+        if existing.iter().any(|c| c == category.as_str()) {
+            continue;
+        }
+
+        let basket = basket_repo::create_basket(
+            pool,
+            &default_basket_name(category),
+            category.as_str(),
+            config.basket_consensus_threshold,
+            config.basket_time_window_hours,
+            config.basket_min_wallets,
+            config.basket_max_wallets,
+        )
+        .await?;
+        tracing::info!(basket = %basket.name, category = %category, "Category basket created");
+        created.push(basket.name);
+    }
+
+    Ok(created)
+}
+
+/// Make sure every category has a basket, then put qualifying active whales
+/// that pass basket admission into the baskets of the categories they mostly
+/// trade, best win rate first so full baskets keep the strongest whales.
+pub async fn populate_category_baskets(pool: &PgPool, config: &AppConfig) -> anyhow::Result<CategoryBasketSummary> {
+    let created = ensure_category_baskets(pool, config).await?;
+    let now = Utc::now();
+
+    let mut whales: Vec<Whale> = whale_repo::get_active_whales(pool)
+        .await?
+        .into_iter()
+        .filter(qualifies)
+        .collect();
+    whales.sort_by_key(|w| std::cmp::Reverse(w.win_rate));
+
+    let ids: Vec<Uuid> = whales.iter().map(|w| w.id).collect();
+    let first_trades: HashMap<Uuid, DateTime<Utc>> =
+        trade_repo::get_first_trade_times(pool, &ids).await?.into_iter().collect();
+    let mut admitted = Vec::with_capacity(whales.len());
+    for whale in whales {
+        let insider_flags = if config.basket_max_insider_flags > 0 {
+            insider_repo::count_whale_flags(pool, whale.id).await?
+        } else {
+            0
+        };
+        match admit(&whale, first_trades.get(&whale.id).copied(), insider_flags, config, now) {
+            AdmissionResult::Accepted => admitted.push(whale),
+            AdmissionResult::Rejected(reason) => {
+                tracing::debug!(wallet = %whale.address, reason = %reason, "Whale fails basket admission, not assigned");
+            }
+        }
+    }
+    let whales = admitted;
+
+    let ids: Vec<Uuid> = whales.iter().map(|w| w.id).collect();
+    let mut questions: HashMap<Uuid, Vec<(String, i64)>> = HashMap::new();
+    for (whale_id, question, trades) in trade_repo::get_whale_market_questions(pool, &ids).await? {
+        questions.entry(whale_id).or_default().push((question, trades));
+    }
+
+    let mut assigned = 0u32;
+    for whale in &whales {
+        let Some(traded) = questions.get(&whale.id) else {
+            continue;
+        };
+        let member_of: Vec<String> = basket_repo::get_baskets_for_whale(pool, whale.id)
+            .await?
+            .into_iter()
+            .map(|b| b.category)
+            .collect();
+
+        for category in whale_categories(traded) {
+            if member_of.iter().any(|c| c == category.as_str()) {
+                continue;
+            }
+            let names = auto_assign_to_baskets(pool, whale.id, category.as_str()).await?;
+            for name in &names {
+                tracing::info!(wallet = %whale.address, basket = %name, "Whale added to category basket");
+            }
+            assigned += names.len() as u32;
+        }
+    }
+
+    tracing::info!(created = created.len(), assigned, "Category baskets populated");
+    Ok(CategoryBasketSummary { created, assigned })
+}
+
+/// Seeder-vetted whales qualify outright; others must be informed traders
+/// with basket admission's minimum win rate.
+fn qualifies(whale: &Whale) -> bool {
+    match whale.classification.as_deref() {
+        Some(c) if SEEDER_TIERS.contains(&c) => true,
+        Some("informed") => whale.win_rate.is_some_and(|w| w >= MIN_WIN_RATE),
+        _ => false,
+    }
+}
+
+/// Categories making up at least `MIN_CATEGORY_SHARE` of a whale's trades in
+/// markets with a recognisable category, given (market question, trades) pairs.
+pub fn whale_categories(traded: &[(String, i64)]) -> Vec<BasketCategory> {
+    let mut counts: HashMap<&'static str, i64> = HashMap::new();
+    for (question, trades) in traded {
+        if let Some(category) = infer_market_category(question) {
+            *counts.entry(category.as_str()).or_default() += trades;
+        }
+    }
+    let total: i64 = counts.values().sum();
+    if total == 0 {
+        return Vec::new();
+    }
+
+    BasketCategory::ALL
+        .into_iter()
+        .filter(|c| {
+            let n = counts.get(c.as_str()).copied().unwrap_or(0);
+            n > 0 && Decimal::from(n) / Decimal::from(total) >= MIN_CATEGORY_SHARE
+        })
+        .collect()
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_whale_categories_by_trade_share() {
+        let traded = vec![
+            ("Will Trump win the 2024 election?".to_string(), 12),
+            ("Will the Senate pass the bill?".to_string(), 6),
+            ("Will Bitcoin reach $100k by end of year?".to_string(), 5),
+            ("Who will win the Super Bowl?".to_string(), 1),
+            ("Will it rain in Paris tomorrow?".to_string(), 40),
+        ];
+        // 18 politics, 5 crypto, 1 sports of 24 categorised trades
+        assert_eq!(whale_categories(&traded), vec![BasketCategory::Politics]);
+
+        let traded = vec![
+            ("Will ETH flip BTC in market cap?".to_string(), 3),
+            ("Will the NBA MVP be from the West?".to_string(), 3),
+        ];
+        assert_eq!(whale_categories(&traded), vec![BasketCategory::Crypto, BasketCategory::Sports]);
+        assert!(whale_categories(&[("What is the meaning of life?".to_string(), 9)]).is_empty());
+    }
+
+    #[test]
+    fn test_default_basket_name() {
+        assert_eq!(default_basket_name(BasketCategory::Politics), "Politics Whales");
+    }
+}
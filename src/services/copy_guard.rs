@@ -0,0 +1,134 @@
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::whale_repo;
+use crate::models::WhaleCopyPerformance;
+use crate::services::notifier::Notifier;
+
+/// Thresholds for pausing a whale whose copies lose money.
+#[derive(Debug, Clone)]
+pub struct CopyGuardConfig {
+    /// Closed copy positions required before the rule applies.
+    pub min_closed_positions: i64,
+    /// Pause once realized copy PnL falls below `-max_copy_loss` USDC.
+    pub max_copy_loss: Decimal,
+    pub interval_secs: u64,
+}
+
+/// Returns the pause reason if our copies of this whale breach the loss rule.
+pub fn copy_loss_breach(perf: &WhaleCopyPerformance, config: &CopyGuardConfig) -> Option<String> {
+    if perf.closed_positions < config.min_closed_positions {
+        return None;
+    }
+    if perf.realized_pnl >= -config.max_copy_loss {
+        return None;
+    }
+
+    Some(format!(
+        "copy PnL {} USDC over {} closed positions (limit -{})",
+        perf.realized_pnl.round_dp(2),
+        perf.closed_positions,
+        config.max_copy_loss,
+    ))
+}
+
+/// Periodically pause copying whales whose copies have lost too much.
+/// Their trades are still tracked; copying resumes via the API.
+pub async fn run_copy_guard(pool: PgPool, config: CopyGuardConfig, notifier: Option<Arc<Notifier>>) {
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+
+    tracing::info!(
+        min_closed = config.min_closed_positions,
+        max_loss = %config.max_copy_loss,
+        "Copy guard started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        let whales = match whale_repo::get_copied_whales(&pool).await {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::error!(error = %e, "Copy guard: failed to fetch copied whales");
+                continue;
+            }
+        };
+
+        for whale in whales {
+            let perf = match whale_repo::get_whale_copy_performance(&pool, whale.id, whale.copy_resumed_at).await {
+                Ok(p) => p,
+                Err(e) => {
+                    tracing::warn!(error = %e, whale = %whale.address, "Copy guard: performance query failed");
+                    continue;
+                }
+            };
+
+            let Some(reason) = copy_loss_breach(&perf, &config) else {
+                continue;
+            };
+
+            if let Err(e) = whale_repo::pause_whale_copying(&pool, whale.id, &reason).await {
+                tracing::error!(error = %e, whale = %whale.address, "Copy guard: failed to pause whale");
+                continue;
+            }
+
+            tracing::warn!(
+                whale = %whale.address,
+                realized_pnl = %perf.realized_pnl,
+                closed_positions = perf.closed_positions,
+                "Copying paused — our copies of this whale are losing money"
+            );
+
+            if let Some(n) = &notifier {
+                let msg = crate::services::notifier::format_whale_copy_paused(
+                    &whale.address,
+                    whale.label.as_deref(),
+                    perf.realized_pnl,
+                    perf.closed_positions,
+                );
+                n.notify(crate::services::notifier::Severity::Warning, &msg).await;
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> CopyGuardConfig {
+        CopyGuardConfig {
+            min_closed_positions: 5,
+            max_copy_loss: Decimal::from(100),
+            interval_secs: 900,
+        }
+    }
+
+    fn perf(closed: i64, pnl: i64) -> WhaleCopyPerformance {
+        WhaleCopyPerformance {
+            closed_positions: closed,
+            realized_pnl: Decimal::from(pnl),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_breach_requires_min_sample() {
+        assert!(copy_loss_breach(&perf(4, -500), &config()).is_none());
+        assert!(copy_loss_breach(&perf(5, -500), &config()).is_some());
+    }
+
+    #[test]
+    fn test_breach_requires_loss_beyond_limit() {
+        assert!(copy_loss_breach(&perf(10, -100), &config()).is_none());
+        assert!(copy_loss_breach(&perf(10, 50), &config()).is_none());
+        assert!(copy_loss_breach(&perf(10, -101), &config()).is_some());
+    }
+}
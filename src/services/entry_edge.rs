@@ -0,0 +1,67 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::{trade_repo, whale_repo};
+use crate::intelligence::scorer::{entry_edge, favorable_move};
+
+#[derive(Debug, Clone)]
+pub struct EntryEdgeConfig {
+    /// How long after each entry the price is read.
+    pub horizon_hours: i32,
+    pub interval_secs: u64,
+}
+
+/// Periodically measure each active whale's entry edge from recorded candles.
+/// Whales with no measurable entries keep their last stored value.
+pub async fn run_entry_edge_loop(pool: PgPool, config: EntryEdgeConfig) {
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+
+    tracing::info!(horizon_hours = config.horizon_hours, "Entry edge analysis started");
+
+    loop {
+        ticker.tick().await;
+
+        let whales = match whale_repo::get_active_whales(&pool).await {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::error!(error = %e, "Entry edge: failed to fetch active whales");
+                continue;
+            }
+        };
+
+        let mut updated = 0;
+        for whale in whales {
+            let followups = match trade_repo::get_entry_followups(&pool, whale.id, config.horizon_hours).await {
+                Ok(f) => f,
+                Err(e) => {
+                    tracing::warn!(error = %e, whale = %whale.address, "Entry edge: follow-up query failed");
+                    continue;
+                }
+            };
+
+            let moves: Vec<Decimal> = followups
+                .iter()
+                .map(|(side, entry, later)| favorable_move(side, *entry, *later))
+                .collect();
+            let Some(edge) = entry_edge(&moves) else {
+                continue;
+            };
+
+            if let Err(e) = whale_repo::update_entry_edge(&pool, whale.id, edge, moves.len() as i32).await {
+                tracing::error!(error = %e, whale = %whale.address, "Entry edge: failed to store");
+                continue;
+            }
+            updated += 1;
+
+            tracing::debug!(
+                whale = %whale.address,
+                entry_edge = %edge.round_dp(4),
+                samples = moves.len(),
+                "Entry edge updated"
+            );
+        }
+
+        tracing::info!(whales = updated, "Entry edge analysis complete");
+    }
+}
@@ -0,0 +1,299 @@
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::Utc;
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::db::{order_repo, position_repo, reconciliation_repo};
+use crate::execution::sleeves::SleevePools;
+use crate::models::{CopyOrder, ReconciliationReport};
+use crate::polymarket::balance::BalanceChecker;
+use crate::polymarket::trading::TradingClient;
+use crate::polymarket::DataClient;
+use crate::services::notifier::{Notifier, Severity};
+use crate::services::position_health::next_report_at;
+use crate::services::position_reconciler::{self, DriftKind};
+
+/// Settings for the daily reconciliation run.
+#[derive(Debug, Clone)]
+pub struct EodReconciliationConfig {
+    /// UTC hour (0–23) the reconciliation runs.
+    pub report_hour_utc: u32,
+    /// Ledger vs on-chain USDC difference tolerated before it counts as a discrepancy.
+    pub balance_tolerance: Decimal,
+}
+
+/// Where the books are checked against. Optional sources are skipped
+/// (dry run has no account positions, no CLOB orders and a simulated ledger).
+#[derive(Clone)]
+pub struct ReconciliationSources {
+    pub data_client: Option<DataClient>,
+    pub wallet_address: String,
+    pub trading_client: Option<Arc<TradingClient>>,
+    pub balance_checker: Option<Arc<BalanceChecker>>,
+    pub capital: Option<SleevePools>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DiscrepancyKind {
+    PositionMissingInDb,
+    PositionMissingOnAccount,
+    PositionSizeMismatch,
+    /// A `submitted` order the CLOB no longer lists as open.
+    OrderNotOnClob,
+    /// An open CLOB order with no `submitted` order in the DB.
+    OrderUnknownToDb,
+    /// Capital pool balance differs from on-chain USDC.
+    CapitalMismatch,
+    /// A source could not be queried, so its check did not run.
+    CheckFailed,
+}
+
+impl fmt::Display for DiscrepancyKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DiscrepancyKind::PositionMissingInDb => "position_missing_in_db",
+            DiscrepancyKind::PositionMissingOnAccount => "position_missing_on_account",
+            DiscrepancyKind::PositionSizeMismatch => "position_size_mismatch",
+            DiscrepancyKind::OrderNotOnClob => "order_not_on_clob",
+            DiscrepancyKind::OrderUnknownToDb => "order_unknown_to_db",
+            DiscrepancyKind::CapitalMismatch => "capital_mismatch",
+            DiscrepancyKind::CheckFailed => "check_failed",
+        })
+    }
+}
+
+/// One reconciliation difference.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Discrepancy {
+    pub kind: DiscrepancyKind,
+    /// Token, order ID or ledger the difference concerns.
+    pub subject: String,
+    pub detail: String,
+}
+
+impl Discrepancy {
+    fn new(kind: DiscrepancyKind, subject: impl Into<String>, detail: impl Into<String>) -> Self {
+        Self {
+            kind,
+            subject: subject.into(),
+            detail: detail.into(),
+        }
+    }
+}
+
+/// Compare DB `submitted` orders with the CLOB's open orders by CLOB order ID.
+pub fn reconcile_orders(db_submitted: &[CopyOrder], clob_open_ids: &[String]) -> Vec<Discrepancy> {
+    let clob: HashSet<&str> = clob_open_ids.iter().map(String::as_str).collect();
+    let db: HashSet<&str> = db_submitted
+        .iter()
+        .filter_map(|o| o.clob_order_id.as_deref())
+        .collect();
+
+    let mut out: Vec<Discrepancy> = db_submitted
+        .iter()
+        .filter_map(|o| {
+            let clob_id = o.clob_order_id.as_deref()?;
+            (!clob.contains(clob_id)).then(|| {
+                Discrepancy::new(
+                    DiscrepancyKind::OrderNotOnClob,
+                    clob_id,
+                    format!("{} {} @ {} on {}", o.side, o.size, o.target_price, o.token_id),
+                )
+            })
+        })
+        .collect();
+
+    let mut unknown: Vec<&str> = clob.difference(&db).copied().collect();
+    unknown.sort_unstable();
+    out.extend(unknown.into_iter().map(|id| {
+        Discrepancy::new(DiscrepancyKind::OrderUnknownToDb, id, "open on CLOB, not tracked in DB")
+    }));
+
+    out
+}
+
+/// Flag the capital ledger when it is further than `tolerance` from on-chain USDC.
+pub fn reconcile_capital(ledger: Decimal, chain: Decimal, tolerance: Decimal) -> Option<Discrepancy> {
+    let diff = ledger - chain;
+    (diff.abs() > tolerance).then(|| {
+        Discrepancy::new(
+            DiscrepancyKind::CapitalMismatch,
+            "capital_pool",
+            format!("ledger {} vs chain {} (diff {})", ledger.round_dp(2), chain.round_dp(2), diff.round_dp(2)),
+        )
+    })
+}
+
+/// Run every check once, store the result and return it with its discrepancies.
+pub async fn run_reconciliation(
+    pool: &PgPool,
+    sources: &ReconciliationSources,
+    config: &EodReconciliationConfig,
+) -> anyhow::Result<(ReconciliationReport, Vec<Discrepancy>)> {
+    let mut discrepancies = Vec::new();
+
+    // DB positions vs account holdings
+    let db_positions = position_repo::get_unclosed_positions(pool).await?;
+    let open_positions = db_positions
+        .iter()
+        .filter(|p| p.status.as_deref() == Some("open"))
+        .count();
+    let mut account_positions = 0;
+    if let Some(ref dc) = sources.data_client {
+        match dc.get_user_positions(&sources.wallet_address).await {
+            Ok(account) => {
+                account_positions = account.len();
+                for drift in position_reconciler::reconcile(&db_positions, &account) {
+                    let kind = match drift.kind {
+                        DriftKind::MissingInDb => DiscrepancyKind::PositionMissingInDb,
+                        DriftKind::MissingOnAccount => DiscrepancyKind::PositionMissingOnAccount,
+                        DriftKind::SizeMismatch => DiscrepancyKind::PositionSizeMismatch,
+                    };
+                    discrepancies.push(Discrepancy::new(
+                        kind,
+                        drift.token_id,
+                        format!("db {} vs account {}", drift.db_size.round_dp(2), drift.account_size.round_dp(2)),
+                    ));
+                }
+            }
+            Err(e) => discrepancies.push(Discrepancy::new(DiscrepancyKind::CheckFailed, "positions", e.to_string())),
+        }
+    }
+
+    // DB submitted orders vs CLOB open orders
+    let db_orders = order_repo::get_submitted_orders(pool).await?;
+    let mut clob_open_orders = None;
+    if let Some(ref tc) = sources.trading_client {
+        match tc.get_open_orders().await {
+            Ok(open) => {
+                let ids: Vec<String> = open.into_iter().map(|o| o.id).collect();
+                clob_open_orders = Some(ids.len() as i32);
+                discrepancies.extend(reconcile_orders(&db_orders, &ids));
+            }
+            Err(e) => discrepancies.push(Discrepancy::new(DiscrepancyKind::CheckFailed, "orders", e.to_string())),
+        }
+    }
+
+    // Capital pool ledger vs on-chain USDC
+    let mut ledger_balance = None;
+    let mut chain_balance = None;
+    if let (Some(capital), Some(bc)) = (&sources.capital, &sources.balance_checker) {
+        match bc.get_usdc_balance().await {
+            Ok(chain) => {
+                let ledger = capital.total_balance().await;
+                discrepancies.extend(reconcile_capital(ledger, chain, config.balance_tolerance));
+                ledger_balance = Some(ledger);
+                chain_balance = Some(chain);
+            }
+            Err(e) => discrepancies.push(Discrepancy::new(DiscrepancyKind::CheckFailed, "capital", e.to_string())),
+        }
+    }
+
+    let report = reconciliation_repo::insert_report(
+        pool,
+        discrepancies.is_empty(),
+        open_positions as i32,
+        account_positions as i32,
+        db_orders.len() as i32,
+        clob_open_orders,
+        ledger_balance,
+        chain_balance,
+        serde_json::to_value(&discrepancies)?,
+    )
+    .await?;
+
+    Ok((report, discrepancies))
+}
+
+/// Reconcile the books once a day at `report_hour_utc` and send a pass/fail summary.
+pub async fn run_eod_reconciliation(
+    pool: PgPool,
+    sources: ReconciliationSources,
+    config: EodReconciliationConfig,
+    notifier: Option<Arc<Notifier>>,
+) {
+    tracing::info!(
+        report_hour_utc = config.report_hour_utc,
+        balance_tolerance = %config.balance_tolerance,
+        clob = sources.trading_client.is_some(),
+        capital = sources.capital.is_some(),
+        "End-of-day reconciliation started"
+    );
+
+    loop {
+        let now = Utc::now();
+        let wait = (next_report_at(now, config.report_hour_utc) - now)
+            .to_std()
+            .unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        let (report, discrepancies) = match run_reconciliation(&pool, &sources, &config).await {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::error!(error = %e, "End-of-day reconciliation failed");
+                continue;
+            }
+        };
+
+        if report.passed {
+            tracing::info!(report_id = %report.id, "End-of-day reconciliation passed");
+        } else {
+            tracing::warn!(
+                report_id = %report.id,
+                discrepancies = discrepancies.len(),
+                "End-of-day reconciliation found discrepancies"
+            );
+        }
+
+        if let Some(ref n) = notifier {
+            let severity = if report.passed { Severity::Info } else { Severity::Warning };
+            let msg = crate::services::notifier::format_reconciliation_report(&report, &discrepancies);
+            n.notify(severity, &msg).await;
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn order(clob_order_id: &str) -> CopyOrder {
+        CopyOrder {
+            status: "submitted".into(),
+            clob_order_id: Some(clob_order_id.into()),
+            ..CopyOrder::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_reconcile_orders() {
+        let db = vec![order("a"), order("b")];
+        let clob = vec!["b".to_string(), "c".to_string()];
+
+        let out = reconcile_orders(&db, &clob);
+        assert_eq!(out.len(), 2);
+        assert_eq!(out[0].kind, DiscrepancyKind::OrderNotOnClob);
+        assert_eq!(out[0].subject, "a");
+        assert_eq!(out[1].kind, DiscrepancyKind::OrderUnknownToDb);
+        assert_eq!(out[1].subject, "c");
+
+        assert!(reconcile_orders(&db, &["a".into(), "b".into()]).is_empty());
+    }
+
+    #[test]
+    fn test_reconcile_capital_tolerance() {
+        let tol = Decimal::ONE;
+        assert!(reconcile_capital(Decimal::from(1000), Decimal::new(99950, 2), tol).is_none());
+        let d = reconcile_capital(Decimal::from(1000), Decimal::from(990), tol).unwrap();
+        assert_eq!(d.kind, DiscrepancyKind::CapitalMismatch);
+    }
+}
@@ -0,0 +1,33 @@
+use metrics::gauge;
+use rust_decimal::prelude::ToPrimitive;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::portfolio_snapshot_repo;
+use crate::execution::sleeves::SleevePools;
+
+/// Periodically record bankroll, exposure and PnL into `portfolio_snapshots`
+/// for the dashboard equity curve.
+pub async fn run_equity_snapshots(pool: PgPool, capital: SleevePools, interval_secs: u64) {
+    let mut ticker = interval(Duration::from_secs(interval_secs));
+
+    tracing::info!(interval_secs, "Equity snapshot recorder started");
+
+    loop {
+        ticker.tick().await;
+
+        let bankroll = capital.total_balance().await;
+        match portfolio_snapshot_repo::insert_snapshot(&pool, bankroll).await {
+            Ok(snapshot) => {
+                gauge!("portfolio_equity").set(snapshot.equity.to_f64().unwrap_or(0.0));
+                tracing::debug!(
+                    equity = %snapshot.equity,
+                    bankroll = %snapshot.bankroll,
+                    open_exposure = %snapshot.open_exposure,
+                    "Equity snapshot recorded"
+                );
+            }
+            Err(e) => tracing::error!(error = %e, "Failed to record equity snapshot"),
+        }
+    }
+}
@@ -0,0 +1,269 @@
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+
+use chrono::Utc;
+use metrics::counter;
+use sqlx::PgPool;
+use tokio::sync::{mpsc, Notify};
+use tokio::time::{interval, sleep, Duration};
+
+use crate::db::event_queue_repo;
+use crate::models::WhaleTradeEvent;
+
+use super::shutdown::TaskRegistry;
+
+/// Consumer name the pipeline's offset is stored under.
+pub const PIPELINE_CONSUMER: &str = "pipeline";
+
+/// Queued events read per query.
+const FETCH_BATCH: i64 = 500;
+/// How often the processed offset is committed; also the fallback poll for
+/// events nobody signalled (e.g. left over from a previous run).
+const COMMIT_INTERVAL: Duration = Duration::from_secs(1);
+/// How often consumed events past the retention window are deleted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+/// Pause before retrying a failed queue read or write.
+const RETRY_DELAY: Duration = Duration::from_secs(1);
+
+/// Tracks how far the pipeline has got through the queued events it was
+/// handed. The consumer records each event's offset as it sends it; the
+/// pipeline takes the offset when it receives the event (a single FIFO
+/// channel sits between the two, so they line up) and reports it done once
+/// processed. Events may finish out of order; the processed offset only
+/// advances past events that are all done.
+#[derive(Clone, Default)]
+pub struct QueueAck {
+    state: Arc<Mutex<AckState>>,
+    processed: Arc<AtomicI64>,
+}
+
+#[derive(Default)]
+struct AckState {
+    /// Sent to the pipeline channel, not yet received.
+    sent: VecDeque<i64>,
+    /// Received by the pipeline, in order, with whether each is done.
+    received: VecDeque<(i64, bool)>,
+}
+
+impl QueueAck {
+    fn handed_over(&self, id: i64) {
+        if let Ok(mut state) = self.state.lock() {
+            state.sent.push_back(id);
+        }
+    }
+
+    /// Offset of the event the pipeline just took off its channel.
+    pub fn received(&self) -> Option<i64> {
+        let mut state = self.state.lock().ok()?;
+        let id = state.sent.pop_front()?;
+        state.received.push_back((id, false));
+        Some(id)
+    }
+
+    /// Mark a received event as processed.
+    pub fn done(&self, id: i64) {
+        let Ok(mut state) = self.state.lock() else {
+            return;
+        };
+        if let Some(entry) = state.received.iter_mut().find(|(queued, _)| *queued == id) {
+            entry.1 = true;
+        }
+        while let Some(&(id, true)) = state.received.front() {
+            state.received.pop_front();
+            self.processed.fetch_max(id, Ordering::Relaxed);
+        }
+    }
+
+    /// Highest offset up to which every event was processed this run (0 if
+    /// none yet).
+    pub fn processed(&self) -> i64 {
+        self.processed.load(Ordering::Relaxed)
+    }
+}
+
+/// Whether `event` goes through the durable queue. Anonymous WS trades skip
+/// it: the priority lanes shed them under backpressure anyway, and queued
+/// they would back up in Postgres ahead of whale trades.
+pub fn is_queued(event: &WhaleTradeEvent) -> bool {
+    !event.is_anonymous()
+}
+
+/// Put the durable queue between ingestion and the pipeline. The returned
+/// sender takes the place of `pipeline_tx` for ingestion sources: queued
+/// events are written to Postgres, then fed into `pipeline_tx` from the
+/// pipeline's committed offset, so a crash replays whatever was in flight;
+/// the rest go straight to `pipeline_tx`. The pipeline must call
+/// `QueueAck::received` for each queued event it takes and `QueueAck::done`
+/// once it is processed.
+pub fn durable(
+    tasks: &mut TaskRegistry,
+    pool: PgPool,
+    pipeline_tx: mpsc::Sender<WhaleTradeEvent>,
+    retention_hours: i64,
+) -> (mpsc::Sender<WhaleTradeEvent>, QueueAck) {
+    let (tx, rx) = mpsc::channel::<WhaleTradeEvent>(1000);
+    let enqueued = Arc::new(Notify::new());
+    let ack = QueueAck::default();
+
+    tasks.spawn(
+        "pipeline_queue_writer",
+        run_queue_writer(pool.clone(), rx, pipeline_tx.clone(), Arc::clone(&enqueued)),
+    );
+    tasks.spawn(
+        "pipeline_queue_consumer",
+        run_queue_consumer(pool, PIPELINE_CONSUMER, pipeline_tx, ack.clone(), enqueued, retention_hours),
+    );
+
+    (tx, ack)
+}
+
+/// Append ingested events to the queue, retrying until each is written —
+/// dropping one here would defeat the queue. Events that aren't queued are
+/// passed on to `pipeline_tx` as they come.
+async fn run_queue_writer(
+    pool: PgPool,
+    mut rx: mpsc::Receiver<WhaleTradeEvent>,
+    pipeline_tx: mpsc::Sender<WhaleTradeEvent>,
+    enqueued: Arc<Notify>,
+) {
+    while let Some(event) = rx.recv().await {
+        if !is_queued(&event) {
+            if pipeline_tx.send(event).await.is_err() {
+                break;
+            }
+            continue;
+        }
+        while let Err(e) = event_queue_repo::enqueue_event(&pool, &event).await {
+            tracing::error!(error = %e, wallet = %event.wallet, "Failed to enqueue pipeline event — retrying");
+            sleep(RETRY_DELAY).await;
+        }
+        counter!("pipeline_queue_enqueued_total").increment(1);
+        enqueued.notify_one();
+    }
+    tracing::warn!("Pipeline queue writer stopped — ingestion channel closed");
+}
+
+/// Feed queued events after `consumer`'s committed offset into `pipeline_tx`,
+/// committing the offset `ack` reports as processed and pruning consumed
+/// events older than `retention_hours`. Events queued before this run started
+/// are marked replayed, so the pipeline won't copy them once past the signal TTL.
+async fn run_queue_consumer(
+    pool: PgPool,
+    consumer: &'static str,
+    pipeline_tx: mpsc::Sender<WhaleTradeEvent>,
+    ack: QueueAck,
+    enqueued: Arc<Notify>,
+    retention_hours: i64,
+) {
+    let (mut committed, backlog_end) = loop {
+        let offsets = async {
+            let committed = event_queue_repo::get_offset(&pool, consumer).await?;
+            let backlog_end = event_queue_repo::get_max_event_id(&pool).await?;
+            anyhow::Ok((committed, backlog_end))
+        };
+        match offsets.await {
+            Ok(offsets) => break offsets,
+            Err(e) => {
+                tracing::error!(error = %e, consumer, "Failed to load pipeline queue offset — retrying");
+                sleep(RETRY_DELAY).await;
+            }
+        }
+    };
+    let mut read_through = committed;
+    tracing::info!(
+        consumer,
+        offset = committed,
+        backlog = (backlog_end - committed).max(0),
+        "Pipeline queue consumer started"
+    );
+
+    let mut commit_timer = interval(COMMIT_INTERVAL);
+    let mut prune_timer = interval(PRUNE_INTERVAL);
+
+    loop {
+        match event_queue_repo::fetch_events_after(&pool, read_through, FETCH_BATCH).await {
+            Ok(rows) => {
+                let full_batch = rows.len() as i64 == FETCH_BATCH;
+                for (id, payload) in rows {
+                    read_through = id;
+                    let mut event = match serde_json::from_value::<WhaleTradeEvent>(payload) {
+                        Ok(event) => event,
+                        Err(e) => {
+                            tracing::warn!(id, error = %e, "Skipping undecodable queued pipeline event");
+                            continue;
+                        }
+                    };
+                    // Left over from a previous run: delivered late
+                    if id <= backlog_end {
+                        event.replayed = true;
+                    }
+                    ack.handed_over(id);
+                    if pipeline_tx.send(event).await.is_err() {
+                        tracing::warn!(consumer, "Pipeline channel closed — queue consumer stopped");
+                        return;
+                    }
+                }
+                if full_batch {
+                    continue;
+                }
+            }
+            Err(e) => tracing::error!(error = %e, consumer, "Failed to read pipeline queue"),
+        }
+
+        tokio::select! {
+            _ = enqueued.notified() => {}
+            _ = commit_timer.tick() => {
+                let processed = ack.processed();
+                if processed > committed {
+                    match event_queue_repo::commit_offset(&pool, consumer, processed).await {
+                        Ok(()) => committed = processed,
+                        Err(e) => tracing::error!(error = %e, consumer, "Failed to commit pipeline queue offset"),
+                    }
+                }
+            }
+            _ = prune_timer.tick() => {
+                let cutoff = Utc::now() - chrono::Duration::hours(retention_hours);
+                match event_queue_repo::delete_consumed_before(&pool, cutoff).await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!(deleted = n, "Pruned consumed pipeline queue events"),
+                    Err(e) => tracing::error!(error = %e, "Failed to prune pipeline queue"),
+                }
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_processed_offset_waits_for_every_earlier_event() {
+        let ack = QueueAck::default();
+        assert_eq!(ack.processed(), 0);
+
+        for id in [3, 5, 8] {
+            ack.handed_over(id);
+        }
+        let received: Vec<i64> = (0..3).filter_map(|_| ack.received()).collect();
+        assert_eq!(received, vec![3, 5, 8]);
+        assert_eq!(ack.received(), None);
+
+        // 5 and 8 finish first: 3 still pending, nothing is committable
+        ack.done(8);
+        ack.done(5);
+        assert_eq!(ack.processed(), 0);
+
+        ack.done(3);
+        assert_eq!(ack.processed(), 8);
+
+        // Unknown offsets change nothing
+        ack.done(42);
+        assert_eq!(ack.processed(), 8);
+    }
+}
@@ -0,0 +1,73 @@
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::{trade_repo, whale_repo};
+use crate::intelligence::scorer::holding_profile;
+
+#[derive(Debug, Clone)]
+pub struct HoldingProfileConfig {
+    /// Finished positions needed before an exit style is assigned.
+    pub min_samples: usize,
+    /// Early-exit share at or above which a whale is mirrored out.
+    pub swing_exit_ratio: Decimal,
+    pub interval_secs: u64,
+}
+
+/// Periodically profile each active whale's holding periods and derive the
+/// exit style new copies of it use. Whales without finished positions keep
+/// their last stored profile.
+pub async fn run_holding_profile_loop(pool: PgPool, config: HoldingProfileConfig) {
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+
+    tracing::info!(
+        min_samples = config.min_samples,
+        swing_exit_ratio = %config.swing_exit_ratio,
+        "Holding profile analysis started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        let whales = match whale_repo::get_active_whales(&pool).await {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::error!(error = %e, "Holding profile: failed to fetch active whales");
+                continue;
+            }
+        };
+
+        let mut updated = 0;
+        for whale in whales {
+            let periods = match trade_repo::get_holding_periods(&pool, whale.id).await {
+                Ok(p) => p,
+                Err(e) => {
+                    tracing::warn!(error = %e, whale = %whale.address, "Holding profile: query failed");
+                    continue;
+                }
+            };
+            let Some(profile) = holding_profile(&periods) else {
+                continue;
+            };
+
+            let exit_style = (profile.samples >= config.min_samples)
+                .then(|| profile.exit_style(config.swing_exit_ratio));
+            if let Err(e) = whale_repo::update_holding_profile(&pool, whale.id, &profile, exit_style).await {
+                tracing::error!(error = %e, whale = %whale.address, "Holding profile: failed to store");
+                continue;
+            }
+            updated += 1;
+
+            tracing::debug!(
+                whale = %whale.address,
+                avg_hold_hours = %profile.avg_hold_hours,
+                early_exit_ratio = %profile.early_exit_ratio,
+                samples = profile.samples,
+                exit_style = ?exit_style,
+                "Holding profile updated"
+            );
+        }
+
+        tracing::info!(whales = updated, "Holding profile analysis complete");
+    }
+}
@@ -0,0 +1,62 @@
+use std::sync::Arc;
+
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::{insider_repo, market_repo, whale_repo};
+use crate::intelligence::insider::{scan_insider_flags, InsiderConfig};
+use crate::services::notifier::{self, Notifier, Severity};
+
+/// Periodically flag whales whose entries were suspiciously well timed and
+/// alert on each new flag. Flags feed basket admission via
+/// `BASKET_MAX_INSIDER_FLAGS`; copying itself is left to the operator.
+pub async fn run_insider_scan(pool: PgPool, config: InsiderConfig, notifier: Option<Arc<Notifier>>) {
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+
+    tracing::info!(
+        min_notional = %config.min_notional,
+        resolution_window_hours = config.resolution_window_hours,
+        jump_window_hours = config.jump_window_hours,
+        min_price_move = %config.min_price_move,
+        "Insider scan started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        let flags = match scan_insider_flags(&pool, &config).await {
+            Ok(f) => f,
+            Err(e) => {
+                tracing::error!(error = %e, "Insider scan failed");
+                continue;
+            }
+        };
+
+        for flag in flags {
+            let whale = whale_repo::get_whale_by_id(&pool, flag.whale_id).await.ok().flatten();
+            let address = whale.as_ref().map(|w| w.address.clone()).unwrap_or_else(|| flag.whale_id.to_string());
+            let total = insider_repo::count_whale_flags(&pool, flag.whale_id).await.unwrap_or(1);
+
+            tracing::warn!(
+                whale = %address,
+                kind = %flag.kind,
+                market = %flag.market_id,
+                evidence = %flag.evidence,
+                total_flags = total,
+                "Suspected insider trade flagged"
+            );
+
+            if let Some(n) = &notifier {
+                let question = market_repo::get_market_question(&pool, &flag.market_id).await.ok().flatten();
+                let msg = notifier::format_insider_flag(
+                    &address,
+                    whale.as_ref().and_then(|w| w.label.as_deref()),
+                    question.as_deref(),
+                    &flag,
+                    total,
+                );
+                n.notify(Severity::Warning, &msg).await;
+            }
+        }
+    }
+}
@@ -0,0 +1,127 @@
+use std::fmt;
+
+use tokio::sync::{mpsc, oneshot};
+
+use crate::services::basket_rebalance::RebalanceSummary;
+use crate::services::market_discovery::DiscoverySummary;
+use crate::services::order_fill_poller::FillPollSummary;
+use crate::services::rescore::RescoreSummary;
+use crate::services::resolution::ResolutionSummary;
+use crate::services::whale_seeder::SeederSummary;
+
+/// Requests to run a periodic job's cycle right away, each answered with the
+/// cycle's summary. Polled by the job's loop alongside its interval ticker.
+pub type RunRequests<S> = mpsc::Receiver<oneshot::Sender<S>>;
+
+/// Handle to trigger an immediate cycle of a periodic background job.
+pub struct JobTrigger<S>(mpsc::Sender<oneshot::Sender<S>>);
+
+impl<S> Clone for JobTrigger<S> {
+    fn clone(&self) -> Self {
+        Self(self.0.clone())
+    }
+}
+
+impl<S> JobTrigger<S> {
+    pub fn channel() -> (Self, RunRequests<S>) {
+        let (tx, rx) = mpsc::channel(4);
+        (Self(tx), rx)
+    }
+
+    /// Run one cycle now and wait for its summary. None if the job has stopped.
+    pub async fn run(&self) -> Option<S> {
+        let (reply_tx, reply_rx) = oneshot::channel();
+        self.0.send(reply_tx).await.ok()?;
+        reply_rx.await.ok()
+    }
+}
+
+/// Triggers for the jobs runnable via `POST /api/admin/run/:job`. None when
+/// the job is not running in this deployment. Jobs whose cycle can fail
+/// answer with its error instead of an empty summary.
+#[derive(Clone, Default)]
+pub struct JobTriggers {
+    pub whale_seeder: Option<JobTrigger<Result<SeederSummary, String>>>,
+    pub resolution: Option<JobTrigger<Result<ResolutionSummary, String>>>,
+    pub fill_poller: Option<JobTrigger<Result<FillPollSummary, String>>>,
+    pub market_discovery: Option<JobTrigger<DiscoverySummary>>,
+    pub rescore: Option<JobTrigger<Result<RescoreSummary, String>>>,
+    pub basket_rebalance: Option<JobTrigger<Result<RebalanceSummary, String>>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Job {
+    WhaleSeeder,
+    Resolution,
+    FillPoller,
+    MarketDiscovery,
+    Rescore,
+    BasketRebalance,
+}
+
+impl Job {
+    pub const ALL: [Job; 6] = [
+        Job::WhaleSeeder,
+        Job::Resolution,
+        Job::FillPoller,
+        Job::MarketDiscovery,
+        Job::Rescore,
+        Job::BasketRebalance,
+    ];
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|j| j.as_str() == s)
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Job::WhaleSeeder => "whale_seeder",
+            Job::Resolution => "resolution",
+            Job::FillPoller => "fill_poller",
+            Job::MarketDiscovery => "market_discovery",
+            Job::Rescore => "rescore",
+            Job::BasketRebalance => "basket_rebalance",
+        }
+    }
+}
+
+impl fmt::Display for Job {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_job_parse() {
+        for job in Job::ALL {
+            assert_eq!(Job::parse(job.as_str()), Some(job));
+        }
+        assert_eq!(Job::parse("bogus"), None);
+    }
+
+    #[tokio::test]
+    async fn test_trigger_run_replies_with_summary() {
+        let (trigger, mut requests) = JobTrigger::<u32>::channel();
+        tokio::spawn(async move {
+            while let Some(reply) = requests.recv().await {
+                let _ = reply.send(7);
+            }
+        });
+        assert_eq!(trigger.run().await, Some(7));
+    }
+
+    #[tokio::test]
+    async fn test_trigger_run_on_stopped_job() {
+        let (trigger, requests) = JobTrigger::<u32>::channel();
+        drop(requests);
+        assert_eq!(trigger.run().await, None);
+    }
+}
@@ -0,0 +1,187 @@
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+use uuid::Uuid;
+
+use crate::db::whale_repo;
+use crate::polymarket::DataClient;
+use crate::services::notifier::{Notifier, Severity};
+
+/// Thresholds for flagging whales whose leaderboard PnL is collapsing.
+#[derive(Debug, Clone)]
+pub struct LeaderboardDriftConfig {
+    /// Flag once leaderboard PnL fell this many percent below the baseline.
+    pub max_drawdown_pct: Decimal,
+    /// Also pause copying flagged whales (otherwise warn only).
+    pub auto_pause: bool,
+    pub interval_secs: u64,
+}
+
+/// Leaderboard PnL drawdown since the baseline, in percent (positive = lost ground).
+/// Only meaningful for a profitable baseline.
+pub fn pnl_drawdown_pct(baseline: Decimal, current: Decimal) -> Option<Decimal> {
+    if baseline <= Decimal::ZERO {
+        return None;
+    }
+    Some((baseline - current) / baseline * Decimal::ONE_HUNDRED)
+}
+
+/// Returns the pause reason if the whale's leaderboard PnL breached the drawdown limit.
+pub fn drift_breach(
+    baseline: Decimal,
+    current: Decimal,
+    config: &LeaderboardDriftConfig,
+) -> Option<String> {
+    let drawdown = pnl_drawdown_pct(baseline, current)?;
+    if drawdown < config.max_drawdown_pct {
+        return None;
+    }
+
+    Some(format!(
+        "leaderboard PnL down {}% since seeding ({} → {} USDC)",
+        drawdown.round_dp(1),
+        baseline.round_dp(0),
+        current.round_dp(0),
+    ))
+}
+
+/// Periodically compare tracked whales' leaderboard PnL against their
+/// baseline, warning (and optionally pausing copying) on a big drawdown.
+/// Independent of our own resolved-trade scoring.
+pub async fn run_leaderboard_drift_check(
+    pool: PgPool,
+    data_client: DataClient,
+    config: LeaderboardDriftConfig,
+    notifier: Option<Arc<Notifier>>,
+) {
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+    // Whales already alerted on, until their drawdown recovers
+    let mut alerted: HashSet<Uuid> = HashSet::new();
+
+    tracing::info!(
+        max_drawdown_pct = %config.max_drawdown_pct,
+        auto_pause = config.auto_pause,
+        "Leaderboard drift check started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        let whales = match whale_repo::get_active_whales(&pool).await {
+            Ok(w) => w,
+            Err(e) => {
+                tracing::error!(error = %e, "Leaderboard drift: failed to fetch active whales");
+                continue;
+            }
+        };
+
+        for whale in whales {
+            let entry = match data_client.get_leaderboard_entry(&whale.address).await {
+                Ok(Some(e)) => e,
+                Ok(None) => {
+                    tracing::debug!(whale = %whale.address, "Leaderboard drift: no leaderboard entry");
+                    continue;
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, whale = %whale.address, "Leaderboard drift: fetch failed");
+                    continue;
+                }
+            };
+            let (Some(pnl), Some(volume)) = (entry.pnl, entry.volume) else {
+                continue;
+            };
+
+            if let Err(e) = whale_repo::record_leaderboard_check(&pool, whale.id, pnl, volume).await {
+                tracing::warn!(error = %e, whale = %whale.address, "Leaderboard drift: failed to store check");
+            }
+
+            // Whales tracked before baselines existed start from their first check
+            let Some(baseline) = whale.seed_leaderboard_pnl else {
+                let _ = whale_repo::set_leaderboard_baseline(&pool, whale.id, pnl, volume).await;
+                continue;
+            };
+
+            let Some(reason) = drift_breach(baseline, pnl, &config) else {
+                alerted.remove(&whale.id);
+                continue;
+            };
+            if !alerted.insert(whale.id) {
+                continue;
+            }
+
+            let paused = config.auto_pause
+                && whale.copy_paused_at.is_none()
+                && match whale_repo::pause_whale_copying(&pool, whale.id, &reason).await {
+                    Ok(()) => true,
+                    Err(e) => {
+                        tracing::error!(error = %e, whale = %whale.address, "Leaderboard drift: failed to pause whale");
+                        false
+                    }
+                };
+
+            tracing::warn!(
+                whale = %whale.address,
+                baseline_pnl = %baseline,
+                leaderboard_pnl = %pnl,
+                seed_volume = ?whale.seed_leaderboard_volume,
+                volume = %volume,
+                paused,
+                "Whale leaderboard PnL collapsing since seeding"
+            );
+
+            if let Some(n) = &notifier {
+                let msg = crate::services::notifier::format_whale_leaderboard_drift(
+                    &whale.address,
+                    whale.label.as_deref(),
+                    baseline,
+                    pnl,
+                    paused,
+                );
+                n.notify(Severity::Warning, &msg).await;
+            }
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config() -> LeaderboardDriftConfig {
+        LeaderboardDriftConfig {
+            max_drawdown_pct: Decimal::from(30),
+            auto_pause: false,
+            interval_secs: 21_600,
+        }
+    }
+
+    #[test]
+    fn test_pnl_drawdown_pct() {
+        assert_eq!(
+            pnl_drawdown_pct(Decimal::from(200_000), Decimal::from(150_000)),
+            Some(Decimal::from(25))
+        );
+        // PnL grew: negative drawdown
+        assert_eq!(
+            pnl_drawdown_pct(Decimal::from(100_000), Decimal::from(110_000)),
+            Some(Decimal::from(-10))
+        );
+        assert_eq!(pnl_drawdown_pct(Decimal::ZERO, Decimal::from(-5_000)), None);
+    }
+
+    #[test]
+    fn test_drift_breach_threshold() {
+        let c = config();
+        assert!(drift_breach(Decimal::from(100_000), Decimal::from(71_000), &c).is_none());
+        assert!(drift_breach(Decimal::from(100_000), Decimal::from(70_000), &c).is_some());
+        // Baseline already negative — no meaningful drawdown
+        assert!(drift_breach(Decimal::from(-1_000), Decimal::from(-50_000), &c).is_none());
+    }
+}
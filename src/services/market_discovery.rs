@@ -1,14 +1,20 @@
+use chrono::{DateTime, Utc};
 use rust_decimal::Decimal;
+use serde::Serialize;
 use std::str::FromStr;
 use sqlx::PgPool;
 use tokio::sync::watch;
 use tokio::time::{interval, Duration};
 
-use crate::polymarket::gamma_client::GammaClient;
+use crate::db::{discovery_exclusion_repo, market_repo};
+use crate::models::{DiscoveryExclusion, DiscoveryExclusionType};
+use crate::polymarket::gamma_client::{parse_end_date, GammaClient, GammaMarket};
+use crate::services::job_trigger::RunRequests;
 
 /// Run the market discovery loop. Periodically fetches active markets from the
 /// Gamma API, filters by volume/liquidity thresholds, and broadcasts the
 /// resulting token IDs to the WS listener via a `watch` channel.
+/// `run_requests` runs a scan immediately and answers with its summary.
 pub async fn run_market_discovery(
     gamma_client: GammaClient,
     token_tx: watch::Sender<Vec<String>>,
@@ -16,104 +22,313 @@ pub async fn run_market_discovery(
     interval_secs: u64,
     min_volume: Decimal,
     min_liquidity: Decimal,
+    mut run_requests: RunRequests<DiscoverySummary>,
 ) {
     let mut ticker = interval(Duration::from_secs(interval_secs));
 
     loop {
-        ticker.tick().await;
-
-        tracing::info!("Market discovery: scanning for active markets");
-
-        let mut all_token_ids: Vec<String> = Vec::new();
-        let mut markets_found: usize = 0;
-        let mut offset: u32 = 0;
-        let limit: u32 = 100;
-
-        // Paginate through all active markets
-        loop {
-            match gamma_client.get_active_markets(limit, offset).await {
-                Ok(markets) => {
-                    let batch_len = markets.len();
-
-                    for market in &markets {
-                        let volume = market
-                            .volume
-                            .as_deref()
-                            .and_then(|v| Decimal::from_str(v).ok())
-                            .unwrap_or(Decimal::ZERO);
-
-                        let liquidity = market
-                            .liquidity
-                            .as_deref()
-                            .and_then(|v| Decimal::from_str(v).ok())
-                            .unwrap_or(Decimal::ZERO);
-
-                        if volume >= min_volume && liquidity >= min_liquidity {
-                            markets_found += 1;
-                            for token_id in market.parse_token_ids() {
-                                if !token_id.is_empty() {
-                                    all_token_ids.push(token_id);
-                                }
-                            }
+        let reply = tokio::select! {
+            _ = ticker.tick() => None,
+            Some(reply) = run_requests.recv() => Some(reply),
+        };
+
+        let result = discover_markets(&gamma_client, &pool, min_volume, min_liquidity).await;
+        if let Some(reply) = reply {
+            let _ = reply.send(DiscoverySummary {
+                markets_found: result.markets_found,
+                markets_excluded: result.markets_excluded,
+                token_ids: result.token_ids.len(),
+            });
+        }
+        let all_token_ids = result.token_ids;
+
+        // Broadcast updated token list to WS listener
+        if !all_token_ids.is_empty() {
+            if let Err(e) = token_tx.send(all_token_ids) {
+                tracing::error!(error = %e, "Failed to broadcast token IDs");
+            }
+        }
+    }
+}
+
+/// Outcome of one discovery scan, as reported to the admin API.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct DiscoverySummary {
+    pub markets_found: usize,
+    /// Markets above the thresholds but dropped by an exclusion rule.
+    pub markets_excluded: usize,
+    /// Number of distinct token IDs broadcast to the WS listener.
+    pub token_ids: usize,
+}
+
+/// Result of a single market discovery scan.
+#[derive(Debug, Default)]
+pub struct DiscoveryResult {
+    pub markets_found: usize,
+    pub markets_excluded: usize,
+    /// Sorted, deduplicated CLOB token IDs of all qualifying markets.
+    pub token_ids: Vec<String>,
+}
+
+/// Run one discovery scan: page through active markets on the Gamma API,
+/// keep those above the volume/liquidity thresholds and not excluded by an
+/// enabled discovery exclusion, and persist them to the active_markets table.
+pub async fn discover_markets(
+    gamma_client: &GammaClient,
+    pool: &PgPool,
+    min_volume: Decimal,
+    min_liquidity: Decimal,
+) -> DiscoveryResult {
+    tracing::info!("Market discovery: scanning for active markets");
+
+    let filter = match discovery_exclusion_repo::get_enabled_exclusions(pool).await {
+        Ok(exclusions) => DiscoveryFilter::from_exclusions(&exclusions),
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to load discovery exclusions — scanning unfiltered");
+            DiscoveryFilter::default()
+        }
+    };
+    let now = Utc::now();
+
+    let mut all_token_ids: Vec<String> = Vec::new();
+    let mut markets_found: usize = 0;
+    let mut markets_excluded: usize = 0;
+    let mut excluded_ids: Vec<String> = Vec::new();
+    let mut offset: u32 = 0;
+    let limit: u32 = 100;
 
-                            // Persist to active_markets table for dashboard
-                            if let Err(e) = upsert_active_market(
-                                &pool,
-                                &market.condition_id,
-                                &market.question,
-                                volume,
-                                liquidity,
-                                market.end_date_iso.as_deref(),
-                                market.clob_token_ids.as_deref(),
-                                market.event_slug(),
-                                market.outcomes_json().as_deref(),
-                            )
-                            .await
-                            {
-                                tracing::warn!(
-                                    error = %e,
-                                    condition_id = %market.condition_id,
-                                    "Failed to persist active market"
-                                );
+    // Paginate through all active markets
+    loop {
+        match gamma_client.get_active_markets(limit, offset).await {
+            Ok(markets) => {
+                let batch_len = markets.len();
+
+                for market in &markets {
+                    let volume = market
+                        .volume
+                        .as_deref()
+                        .and_then(|v| Decimal::from_str(v).ok())
+                        .unwrap_or(Decimal::ZERO);
+
+                    let liquidity = market
+                        .liquidity
+                        .as_deref()
+                        .and_then(|v| Decimal::from_str(v).ok())
+                        .unwrap_or(Decimal::ZERO);
+
+                    if volume >= min_volume && liquidity >= min_liquidity {
+                        if let Some(reason) = filter.exclusion_reason(market, now) {
+                            tracing::debug!(
+                                condition_id = %market.condition_id,
+                                reason,
+                                "Market excluded from discovery"
+                            );
+                            markets_excluded += 1;
+                            excluded_ids.push(market.condition_id.clone());
+                            continue;
+                        }
+                        markets_found += 1;
+                        for token_id in market.parse_token_ids() {
+                            if !token_id.is_empty() {
+                                all_token_ids.push(token_id);
                             }
                         }
-                    }
 
-                    if batch_len < limit as usize {
-                        break; // No more pages
+                        // Persist to active_markets table for dashboard
+                        if let Err(e) = upsert_active_market(
+                            pool,
+                            &market.condition_id,
+                            &market.question,
+                            volume,
+                            liquidity,
+                            market.end_date_raw(),
+                            market.clob_token_ids.as_deref(),
+                            market.event_slug(),
+                            market.outcomes_json().as_deref(),
+                            market.tags_json().as_deref(),
+                        )
+                        .await
+                        {
+                            tracing::warn!(
+                                error = %e,
+                                condition_id = %market.condition_id,
+                                "Failed to persist active market"
+                            );
+                        }
                     }
-                    offset += limit;
                 }
-                Err(e) => {
-                    tracing::error!(error = %e, "Failed to fetch markets from Gamma API");
-                    break;
+
+                if batch_len < limit as usize {
+                    break; // No more pages
                 }
+                offset += limit;
+            }
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to fetch markets from Gamma API");
+                break;
             }
         }
+    }
+
+    // Excluded markets persisted by earlier scans leave active_markets too
+    if let Err(e) = market_repo::delete_active_markets(pool, &excluded_ids).await {
+        tracing::warn!(error = %e, "Failed to remove excluded markets from active_markets");
+    }
 
-        // Deduplicate
-        all_token_ids.sort();
-        all_token_ids.dedup();
+    // Deduplicate
+    all_token_ids.sort();
+    all_token_ids.dedup();
 
-        let token_count = all_token_ids.len();
-        tracing::info!(
-            markets = markets_found,
-            tokens = token_count,
-            "Discovered {} active markets with {} tokens",
-            markets_found,
-            token_count,
-        );
+    let token_count = all_token_ids.len();
+    tracing::info!(
+        markets = markets_found,
+        excluded = markets_excluded,
+        tokens = token_count,
+        "Discovered {} active markets with {} tokens",
+        markets_found,
+        token_count,
+    );
 
-        // Broadcast updated token list to WS listener
-        if !all_token_ids.is_empty() {
-            if let Err(e) = token_tx.send(all_token_ids) {
-                tracing::error!(error = %e, "Failed to broadcast token IDs");
+    DiscoveryResult {
+        markets_found,
+        markets_excluded,
+        token_ids: all_token_ids,
+    }
+}
+
+/// Enabled discovery exclusions merged for one scan. The strictest
+/// `resolves_within_hours` and `max_spread` win; invalid rules are skipped.
+#[derive(Debug, Default)]
+pub struct DiscoveryFilter {
+    categories: Vec<String>,
+    keywords: Vec<String>,
+    resolves_within_hours: Option<i64>,
+    max_spread: Option<Decimal>,
+}
+
+impl DiscoveryFilter {
+    pub fn from_exclusions(exclusions: &[DiscoveryExclusion]) -> Self {
+        let mut filter = Self::default();
+
+        for exclusion in exclusions.iter().filter(|e| e.enabled) {
+            let value = exclusion.value.trim();
+            match exclusion.rule_type() {
+                Some(DiscoveryExclusionType::Category) if !value.is_empty() => {
+                    filter.categories.push(value.to_lowercase());
+                }
+                Some(DiscoveryExclusionType::Keyword) if !value.is_empty() => {
+                    filter.keywords.push(value.to_lowercase());
+                }
+                Some(DiscoveryExclusionType::ResolvesWithinHours) => match value.parse::<i64>() {
+                    Ok(hours) => {
+                        filter.resolves_within_hours =
+                            Some(filter.resolves_within_hours.map_or(hours, |h| h.max(hours)))
+                    }
+                    Err(_) => {
+                        tracing::warn!(
+                            exclusion_id = %exclusion.id,
+                            value,
+                            "Invalid resolves_within_hours exclusion — skipped"
+                        );
+                    }
+                },
+                Some(DiscoveryExclusionType::MaxSpread) => match value.parse::<Decimal>() {
+                    Ok(max) => filter.max_spread = Some(filter.max_spread.map_or(max, |m| m.min(max))),
+                    Err(_) => {
+                        tracing::warn!(exclusion_id = %exclusion.id, value, "Invalid max_spread exclusion — skipped");
+                    }
+                },
+                _ => {
+                    tracing::warn!(
+                        exclusion_id = %exclusion.id,
+                        rule_type = %exclusion.rule_type,
+                        "Unusable discovery exclusion — skipped"
+                    );
+                }
             }
         }
+
+        filter
+    }
+
+    /// Why `market` is kept out of discovery, or None if it passes. Markets
+    /// with an unknown end date or spread pass those checks.
+    pub fn exclusion_reason(&self, market: &GammaMarket, now: DateTime<Utc>) -> Option<String> {
+        let mut names = market.tag_names();
+        names.extend(market.category.as_deref().map(|c| c.trim().to_lowercase()));
+        self.reason_for(
+            &market.question,
+            &names,
+            market.end_date_raw().and_then(parse_end_date),
+            market.spread,
+            now,
+        )
+    }
+
+    /// `exclusion_reason` on stored market fields; `names` are lowercased
+    /// category and tag names.
+    fn reason_for(
+        &self,
+        question: &str,
+        names: &[String],
+        end: Option<DateTime<Utc>>,
+        spread: Option<Decimal>,
+        now: DateTime<Utc>,
+    ) -> Option<String> {
+        if let Some(name) = names.iter().find(|n| self.categories.contains(n)) {
+            return Some(format!("category '{name}'"));
+        }
+
+        if !self.keywords.is_empty() {
+            let question = question.to_lowercase();
+            if let Some(keyword) = self.keywords.iter().find(|k| question.contains(k.as_str())) {
+                return Some(format!("question contains '{keyword}'"));
+            }
+        }
+
+        if let Some(hours) = self.resolves_within_hours {
+            if end.is_some_and(|end| end - now < chrono::Duration::hours(hours)) {
+                return Some(format!("resolves within {hours}h"));
+            }
+        }
+
+        if let (Some(max), Some(spread)) = (self.max_spread, spread) {
+            if spread > max {
+                return Some(format!("spread {spread} above {max}"));
+            }
+        }
+
+        None
     }
 }
 
+/// Drop markets already in active_markets that `exclusions` keep out of
+/// discovery, so they leave the dashboard and the startup WS subscriptions
+/// without waiting for the next scan. Spreads are not stored, so `max_spread`
+/// rules apply from the next scan. Returns how many markets were removed.
+pub async fn remove_excluded_markets(pool: &PgPool, exclusions: &[DiscoveryExclusion]) -> anyhow::Result<u64> {
+    let filter = DiscoveryFilter::from_exclusions(exclusions);
+    let now = Utc::now();
+
+    let excluded: Vec<String> = market_repo::get_active_markets_for_exclusion(pool)
+        .await?
+        .into_iter()
+        .filter(|(_, question, tags, end_date_iso)| {
+            let names: Vec<String> = tags
+                .as_deref()
+                .and_then(|t| serde_json::from_str(t).ok())
+                .unwrap_or_default();
+            let end = end_date_iso.as_deref().and_then(parse_end_date);
+            filter.reason_for(question, &names, end, None, now).is_some()
+        })
+        .map(|(condition_id, ..)| condition_id)
+        .collect();
+
+    market_repo::delete_active_markets(pool, &excluded).await
+}
+
 /// Upsert a market into the active_markets table.
+#[allow(clippy::too_many_arguments)]
 async fn upsert_active_market(
     pool: &PgPool,
     condition_id: &str,
@@ -124,11 +339,12 @@ async fn upsert_active_market(
     clob_token_ids: Option<&str>,
     slug: Option<&str>,
     outcomes: Option<&str>,
+    tags: Option<&str>,
 ) -> anyhow::Result<()> {
     sqlx::query(
         r#"
-        INSERT INTO active_markets (condition_id, question, volume, liquidity, end_date_iso, clob_token_ids, slug, outcomes, updated_at)
-        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, NOW())
+        INSERT INTO active_markets (condition_id, question, volume, liquidity, end_date_iso, clob_token_ids, slug, outcomes, tags, updated_at)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, NOW())
         ON CONFLICT (condition_id) DO UPDATE
         SET question = EXCLUDED.question,
             volume = EXCLUDED.volume,
@@ -137,6 +353,7 @@ async fn upsert_active_market(
             clob_token_ids = EXCLUDED.clob_token_ids,
             slug = EXCLUDED.slug,
             outcomes = EXCLUDED.outcomes,
+            tags = EXCLUDED.tags,
             updated_at = NOW()
         "#,
     )
@@ -148,8 +365,108 @@ async fn upsert_active_market(
     .bind(clob_token_ids)
     .bind(slug)
     .bind(outcomes)
+    .bind(tags)
     .execute(pool)
     .await?;
 
     Ok(())
 }
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::polymarket::gamma_client::GammaTag;
+    use uuid::Uuid;
+
+    fn exclusion(rule_type: &str, value: &str) -> DiscoveryExclusion {
+        DiscoveryExclusion {
+            id: Uuid::new_v4(),
+            rule_type: rule_type.into(),
+            value: value.into(),
+            note: None,
+            enabled: true,
+            created_at: Utc::now(),
+        }
+    }
+
+    fn market(question: &str) -> GammaMarket {
+        GammaMarket {
+            question: question.into(),
+            ..GammaMarket::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_filter_excludes_each_rule_type() {
+        let now = parse_end_date("2026-03-01T00:00:00Z").unwrap();
+        let filter = DiscoveryFilter::from_exclusions(&[
+            exclusion("category", "Sports"),
+            exclusion("keyword", "Tweet"),
+            exclusion("resolves_within_hours", "12"),
+            exclusion("resolves_within_hours", "24"),
+            exclusion("max_spread", "0.10"),
+            exclusion("max_spread", "0.05"),
+        ]);
+
+        let ok = GammaMarket {
+            end_date: Some("2026-06-01T00:00:00Z".into()),
+            spread: Some(Decimal::new(2, 2)),
+            category: Some("Crypto".into()),
+            ..market("Will BTC hit 100k?")
+        };
+        assert_eq!(filter.exclusion_reason(&ok, now), None);
+
+        let sports = GammaMarket {
+            category: Some("Sports".into()),
+            ..market("Who wins?")
+        };
+        assert_eq!(filter.exclusion_reason(&sports, now).as_deref(), Some("category 'sports'"));
+        let tagged = GammaMarket {
+            tags: vec![GammaTag {
+                label: None,
+                slug: Some("sports".into()),
+            }],
+            ..market("Who wins?")
+        };
+        assert!(filter.exclusion_reason(&tagged, now).is_some());
+
+        let tweets = market("How many tweets will Elon post?");
+        assert!(filter.exclusion_reason(&tweets, now).unwrap().contains("tweet"));
+
+        // Strictest window: 20h out is inside 24h
+        let soon = GammaMarket {
+            end_date: Some("2026-03-01T20:00:00Z".into()),
+            ..market("Soon?")
+        };
+        assert_eq!(filter.exclusion_reason(&soon, now).as_deref(), Some("resolves within 24h"));
+
+        // Strictest spread: 0.08 is over 0.05
+        let wide = GammaMarket {
+            spread: Some(Decimal::new(8, 2)),
+            ..market("Wide?")
+        };
+        assert!(filter.exclusion_reason(&wide, now).unwrap().starts_with("spread"));
+    }
+
+    #[test]
+    fn test_filter_skips_disabled_and_invalid_exclusions() {
+        let mut disabled = exclusion("keyword", "btc");
+        disabled.enabled = false;
+        let filter = DiscoveryFilter::from_exclusions(&[
+            disabled,
+            exclusion("max_spread", "wide"),
+            exclusion("resolves_within_hours", "soon"),
+            exclusion("unknown", "x"),
+        ]);
+
+        let m = GammaMarket {
+            spread: Some(Decimal::new(5, 1)),
+            ..market("Will BTC hit 100k?")
+        };
+        assert_eq!(filter.exclusion_reason(&m, Utc::now()), None);
+    }
+}
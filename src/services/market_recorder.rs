@@ -0,0 +1,364 @@
+use std::fs::{File, OpenOptions};
+use std::io::{self, BufWriter, Write};
+use std::path::PathBuf;
+use std::str::FromStr;
+
+use chrono::{DateTime, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use metrics::counter;
+use rust_decimal::Decimal;
+use tokio::sync::{mpsc, watch};
+use tokio::time::{interval, Duration};
+
+use crate::config::AppConfig;
+use crate::ingestion::ws_listener::{run_ws_connection, WsTextHandler};
+use crate::polymarket::gamma_client::{GammaClient, GammaMarket};
+
+/// How often buffered records are flushed to the sink.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+/// Messages buffered between the socket and the writer before dropping.
+const CHANNEL_CAPACITY: usize = 50_000;
+
+/// Settings for `polybot record`.
+#[derive(Debug, Clone)]
+pub struct RecorderConfig {
+    /// Root directory of the recorded dataset.
+    pub dir: PathBuf,
+    /// Record at most this many markets, highest volume first.
+    pub max_markets: usize,
+    /// Skip markets with less lifetime volume than this.
+    pub min_volume: Decimal,
+    /// How often the market list is refreshed.
+    pub refresh_secs: u64,
+}
+
+impl RecorderConfig {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            dir: PathBuf::from(&config.recorder_dir),
+            max_markets: config.recorder_max_markets,
+            min_volume: config.recorder_min_volume,
+            refresh_secs: config.recorder_refresh_secs,
+        }
+    }
+}
+
+/// One market-channel WebSocket message as received.
+#[derive(Debug, Clone)]
+pub struct MarketRecord {
+    pub received_at: DateTime<Utc>,
+    /// The raw JSON frame (book snapshots, price changes, trades, tick size changes).
+    pub raw: String,
+}
+
+impl MarketRecord {
+    /// `{"ts":<unix ms>,"msg":<raw frame>}` — one JSON line.
+    fn to_json_line(&self) -> String {
+        format!("{{\"ts\":{},\"msg\":{}}}\n", self.received_at.timestamp_millis(), self.raw)
+    }
+}
+
+/// Where recorded market data goes. Implement this to store the dataset
+/// somewhere other than local gzip files.
+pub trait RecordSink: Send {
+    fn write(&mut self, record: &MarketRecord) -> io::Result<()>;
+    fn flush(&mut self) -> io::Result<()>;
+}
+
+/// Gzip-compressed JSON lines, one file per UTC hour:
+/// `<dir>/<YYYY-MM-DD>/<HH>.jsonl.gz`. Restarts within an hour append a new
+/// gzip member to the same file, which standard readers decode transparently.
+pub struct GzipJsonlSink {
+    dir: PathBuf,
+    current: Option<(String, GzEncoder<BufWriter<File>>)>,
+}
+
+impl GzipJsonlSink {
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            current: None,
+        }
+    }
+
+    fn hour_path(&self, at: DateTime<Utc>) -> PathBuf {
+        self.dir
+            .join(at.format("%Y-%m-%d").to_string())
+            .join(format!("{}.jsonl.gz", at.format("%H")))
+    }
+
+    /// Encoder for the record's hour, rotating files on hour change.
+    fn encoder_for(&mut self, at: DateTime<Utc>) -> io::Result<&mut GzEncoder<BufWriter<File>>> {
+        let key = at.format("%Y-%m-%d %H").to_string();
+        if self.current.as_ref().map(|(k, _)| k != &key).unwrap_or(true) {
+            if let Some((_, done)) = self.current.take() {
+                done.finish()?.flush()?;
+            }
+            let path = self.hour_path(at);
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            let file = OpenOptions::new().create(true).append(true).open(&path)?;
+            tracing::info!(path = %path.display(), "Recorder: writing new file");
+            self.current = Some((key, GzEncoder::new(BufWriter::new(file), Compression::default())));
+        }
+        Ok(&mut self.current.as_mut().expect("encoder just opened").1)
+    }
+}
+
+impl RecordSink for GzipJsonlSink {
+    fn write(&mut self, record: &MarketRecord) -> io::Result<()> {
+        let line = record.to_json_line();
+        self.encoder_for(record.received_at)?.write_all(line.as_bytes())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        match self.current.as_mut() {
+            Some((_, encoder)) => encoder.flush(),
+            None => Ok(()),
+        }
+    }
+}
+
+impl Drop for GzipJsonlSink {
+    fn drop(&mut self) {
+        if let Some((_, encoder)) = self.current.take() {
+            let _ = encoder.finish().and_then(|mut w| w.flush());
+        }
+    }
+}
+
+/// Forwards JSON frames to the writer; never blocks the socket.
+struct RecordingHandler {
+    tx: mpsc::Sender<MarketRecord>,
+}
+
+impl WsTextHandler for RecordingHandler {
+    async fn on_text(&mut self, text: &str) {
+        // Skip non-JSON control frames (e.g. "PONG")
+        if serde_json::from_str::<serde::de::IgnoredAny>(text).is_err() {
+            return;
+        }
+        let record = MarketRecord {
+            received_at: Utc::now(),
+            raw: text.to_string(),
+        };
+        match self.tx.try_send(record) {
+            Ok(()) => counter!("recorder_messages_total").increment(1),
+            Err(_) => counter!("recorder_messages_dropped").increment(1),
+        }
+    }
+}
+
+/// Token IDs of the highest-volume markets at or above `min_volume`, capped
+/// at `max_markets` markets. Sorted and deduplicated.
+pub fn select_tokens(markets: &[GammaMarket], min_volume: Decimal, max_markets: usize) -> Vec<String> {
+    let volume = |m: &GammaMarket| {
+        m.volume
+            .as_deref()
+            .and_then(|v| Decimal::from_str(v).ok())
+            .unwrap_or(Decimal::ZERO)
+    };
+
+    let mut eligible: Vec<&GammaMarket> = markets.iter().filter(|m| volume(m) >= min_volume).collect();
+    eligible.sort_by_key(|m| std::cmp::Reverse(volume(m)));
+
+    let mut tokens: Vec<String> = eligible
+        .into_iter()
+        .take(max_markets)
+        .flat_map(|m| m.parse_token_ids())
+        .collect();
+    tokens.sort();
+    tokens.dedup();
+    tokens
+}
+
+/// Page through all active markets on the Gamma API. Unlike market discovery
+/// this does not persist them — recorded markets are not traded.
+async fn fetch_active_markets(gamma: &GammaClient) -> Vec<GammaMarket> {
+    let mut markets = Vec::new();
+    let mut offset: u32 = 0;
+    let limit: u32 = 100;
+
+    loop {
+        match gamma.get_active_markets(limit, offset).await {
+            Ok(batch) => {
+                let batch_len = batch.len();
+                markets.extend(batch);
+                if batch_len < limit as usize {
+                    break;
+                }
+                offset += limit;
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, offset, "Recorder: failed to fetch markets page");
+                break;
+            }
+        }
+    }
+
+    markets
+}
+
+/// Periodically refresh the recorded token list.
+async fn run_market_scan(gamma: GammaClient, token_tx: watch::Sender<Vec<String>>, config: RecorderConfig) {
+    let mut ticker = interval(Duration::from_secs(config.refresh_secs));
+    ticker.tick().await; // initial scan already done by the caller
+
+    loop {
+        ticker.tick().await;
+        let markets = fetch_active_markets(&gamma).await;
+        let tokens = select_tokens(&markets, config.min_volume, config.max_markets);
+        if tokens.is_empty() {
+            continue;
+        }
+        tracing::info!(markets = markets.len(), tokens = tokens.len(), "Recorder: market list refreshed");
+        token_tx.send_if_modified(|current| {
+            let changed = *current != tokens;
+            if changed {
+                *current = tokens;
+            }
+            changed
+        });
+    }
+}
+
+/// Drain records into the sink, flushing every `FLUSH_INTERVAL`.
+async fn run_record_writer(mut rx: mpsc::Receiver<MarketRecord>, mut sink: Box<dyn RecordSink>) {
+    let mut flush_timer = interval(FLUSH_INTERVAL);
+
+    loop {
+        tokio::select! {
+            record = rx.recv() => {
+                let Some(record) = record else { break };
+                if let Err(e) = sink.write(&record) {
+                    tracing::error!(error = %e, "Recorder: failed to write record");
+                }
+            }
+            _ = flush_timer.tick() => {
+                if let Err(e) = sink.flush() {
+                    tracing::error!(error = %e, "Recorder: flush failed");
+                }
+            }
+        }
+    }
+
+    if let Err(e) = sink.flush() {
+        tracing::error!(error = %e, "Recorder: final flush failed");
+    }
+}
+
+/// `polybot record` — research mode. Subscribes to many markets (beyond the
+/// ones we trade) and writes their raw tick/book stream to compressed files
+/// for the backtester and impact models. No signals or orders are produced.
+/// Runs until Ctrl-C.
+pub async fn run_recorder(config: &AppConfig) -> anyhow::Result<()> {
+    let recorder_config = RecorderConfig::from_config(config);
+    std::fs::create_dir_all(&recorder_config.dir)?;
+
+    let gamma = GammaClient::new();
+    let markets = fetch_active_markets(&gamma).await;
+    let tokens = select_tokens(&markets, recorder_config.min_volume, recorder_config.max_markets);
+    if tokens.is_empty() {
+        anyhow::bail!("recorder: no markets matched (min volume {})", recorder_config.min_volume);
+    }
+    tracing::info!(
+        dir = %recorder_config.dir.display(),
+        markets = markets.len(),
+        tokens = tokens.len(),
+        "Recorder mode: recording market data (no execution)"
+    );
+
+    let (token_tx, token_rx) = watch::channel(tokens);
+    tokio::spawn(run_market_scan(gamma, token_tx, recorder_config.clone()));
+
+    let (record_tx, record_rx) = mpsc::channel(CHANNEL_CAPACITY);
+    let writer = tokio::spawn(run_record_writer(
+        record_rx,
+        Box::new(GzipJsonlSink::new(&recorder_config.dir)),
+    ));
+
+    let handler = RecordingHandler { tx: record_tx };
+    tokio::select! {
+        _ = run_ws_connection(
+            config.polymarket_ws_url.clone(),
+            token_rx,
+            handler,
+            Duration::from_secs(config.ws_stale_timeout_secs),
+        ) => {}
+        _ = tokio::signal::ctrl_c() => {
+            tracing::info!("Recorder: shutting down");
+        }
+    }
+
+    // The handler (and its sender) is gone — let the writer drain and close the file
+    writer.await?;
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use std::io::Read;
+
+    fn market(volume: &str, tokens: &[&str]) -> GammaMarket {
+        GammaMarket {
+            condition_id: format!("0x{volume}"),
+            volume: Some(volume.into()),
+            clob_token_ids: Some(serde_json::to_string(tokens).unwrap()),
+            ..GammaMarket::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_select_tokens() {
+        let markets = vec![
+            market("500", &["a1", "a2"]),
+            market("9000", &["b1", "b2"]),
+            market("50", &["c1", "c2"]),
+            market("2000", &["d1", "d2"]),
+        ];
+
+        assert_eq!(
+            select_tokens(&markets, Decimal::from(100), 2),
+            vec!["b1", "b2", "d1", "d2"]
+        );
+        assert_eq!(select_tokens(&markets, Decimal::ZERO, 10).len(), 8);
+        assert!(select_tokens(&markets, Decimal::from(10_000), 10).is_empty());
+    }
+
+    #[test]
+    fn test_gzip_sink_roundtrip() {
+        let dir = std::env::temp_dir().join(format!("polybot_rec_{}", uuid::Uuid::new_v4().simple()));
+        let at = Utc.with_ymd_and_hms(2026, 3, 1, 14, 5, 0).unwrap();
+
+        {
+            let mut sink = GzipJsonlSink::new(&dir);
+            for raw in [r#"{"event_type":"book"}"#, r#"[{"event_type":"price_change"}]"#] {
+                sink.write(&MarketRecord {
+                    received_at: at,
+                    raw: raw.into(),
+                })
+                .unwrap();
+            }
+        }
+
+        let file = File::open(dir.join("2026-03-01").join("14.jsonl.gz")).unwrap();
+        let mut text = String::new();
+        flate2::read::MultiGzDecoder::new(file).read_to_string(&mut text).unwrap();
+        let lines: Vec<serde_json::Value> = text.lines().map(|l| serde_json::from_str(l).unwrap()).collect();
+
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0]["ts"], at.timestamp_millis());
+        assert_eq!(lines[0]["msg"]["event_type"], "book");
+        assert_eq!(lines[1]["msg"][0]["event_type"], "price_change");
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
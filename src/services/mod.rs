@@ -1,7 +1,28 @@
+pub mod basket_rebalance;
+pub mod bootstrap;
+pub mod candle_recorder;
+pub mod capital_flows;
+pub mod category_baskets;
+pub mod copy_guard;
+pub mod entry_edge;
+pub mod eod_reconciliation;
+pub mod equity_snapshots;
+pub mod event_queue;
+pub mod holding_profile;
+pub mod insider_scan;
+pub mod job_trigger;
+pub mod leaderboard_drift;
+pub mod market_recorder;
 pub mod market_discovery;
 pub mod notifier;
 pub mod order_fill_poller;
+pub mod position_health;
 pub mod position_monitor;
+pub mod position_reconciler;
+pub mod rescore;
 pub mod resolution;
+pub mod shutdown;
+pub mod trade_size_stats;
+pub mod trade_tape;
 pub mod whale_seeder;
 pub mod whale_trade_poller;
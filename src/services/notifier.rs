@@ -1,14 +1,84 @@
+use std::fmt;
+
 use rust_decimal::Decimal;
 use serde_json::json;
+use tokio::sync::mpsc;
+use tokio::time::{interval_at, Duration, Instant};
 
 use crate::models::{CopyOrder, WhaleTradeEvent};
 
+/// Telegram caps messages at 4096 characters; digests are split below that.
+const MAX_MESSAGE_CHARS: usize = 4000;
+/// Queued digest messages beyond this are sent immediately instead.
+const DIGEST_QUEUE_CAPACITY: usize = 500;
+
+/// How urgent a notification is; digest mode batches the configured levels.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    /// Routine activity: signals, small fills, settlements.
+    Info,
+    /// Worth a look soon: failed orders, paused whales, drift.
+    Warning,
+    /// Needs action now: circuit breaker, wallet buffers.
+    Critical,
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Critical => "critical",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "info" => Some(Severity::Info),
+            "warning" | "warn" => Some(Severity::Warning),
+            "critical" => Some(Severity::Critical),
+            _ => None,
+        }
+    }
+
+    /// Parse a comma-separated list, ignoring unknown entries.
+    pub fn parse_list(raw: &str) -> Vec<Self> {
+        raw.split(',').filter_map(Severity::parse).collect()
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Digest mode: batch notifications of the given severities into one message
+/// every `interval_mins` instead of sending each one.
+#[derive(Debug, Clone)]
+pub struct DigestConfig {
+    pub interval_mins: u64,
+    pub severities: Vec<Severity>,
+    /// Fills below this notional (USDC) are `Info`, larger ones `Warning`.
+    pub small_fill_usd: Decimal,
+}
+
+#[derive(Debug, Clone)]
+struct DigestQueue {
+    tx: mpsc::Sender<String>,
+    severities: Vec<Severity>,
+}
+
 /// Telegram notification service. Failures are logged but never block the main flow.
 #[derive(Debug, Clone)]
 pub struct Notifier {
     http: reqwest::Client,
     bot_token: String,
     chat_id: String,
+    small_fill_usd: Decimal,
+    digest: Option<DigestQueue>,
+    /// Deployment tag prefixed to every message.
+    tag: Option<String>,
 }
 
 impl Notifier {
@@ -17,9 +87,69 @@ impl Notifier {
             http: reqwest::Client::new(),
             bot_token,
             chat_id,
+            small_fill_usd: Decimal::ZERO,
+            digest: None,
+            tag: None,
+        }
+    }
+
+    /// Prefix every message with a deployment tag (environment and config
+    /// fingerprint), so alerts from several bots in one chat can be told apart.
+    pub fn with_tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    fn tagged(&self, message: &str) -> String {
+        match &self.tag {
+            Some(tag) => format!("🏷 `{tag}`\n{message}"),
+            None => message.to_string(),
+        }
+    }
+
+    /// Enable digest mode. The returned receiver must be drained by
+    /// [`run_digest_dispatcher`]; with `interval_mins == 0` nothing is batched.
+    pub fn with_digest(mut self, config: &DigestConfig) -> (Self, Option<mpsc::Receiver<String>>) {
+        self.small_fill_usd = config.small_fill_usd;
+        if config.interval_mins == 0 || config.severities.is_empty() {
+            return (self, None);
+        }
+
+        let (tx, rx) = mpsc::channel(DIGEST_QUEUE_CAPACITY);
+        self.digest = Some(DigestQueue {
+            tx,
+            severities: config.severities.clone(),
+        });
+        (self, Some(rx))
+    }
+
+    /// True if notifications of this severity go into the digest.
+    pub fn digests(&self, severity: Severity) -> bool {
+        self.digest
+            .as_ref()
+            .is_some_and(|d| d.severities.contains(&severity))
+    }
+
+    /// Severity of an order-filled notification, by notional.
+    pub fn fill_severity(&self, notional: Decimal) -> Severity {
+        if notional < self.small_fill_usd {
+            Severity::Info
+        } else {
+            Severity::Warning
         }
     }
 
+    /// Send now, or queue for the next digest if this severity is batched.
+    pub async fn notify(&self, severity: Severity, message: &str) {
+        if let Some(digest) = self.digest.as_ref().filter(|d| d.severities.contains(&severity)) {
+            match digest.tx.try_send(message.to_string()) {
+                Ok(()) => return,
+                Err(e) => tracing::warn!(error = %e, "Digest queue unavailable — sending immediately"),
+            }
+        }
+        self.send(message).await;
+    }
+
     /// Send a Telegram message. Failures are logged as warnings.
     pub async fn send(&self, message: &str) {
         let url = format!(
@@ -29,7 +159,7 @@ impl Notifier {
 
         let body = json!({
             "chat_id": self.chat_id,
-            "text": message,
+            "text": self.tagged(message),
             "parse_mode": "Markdown",
         });
 
@@ -49,6 +179,73 @@ impl Notifier {
     }
 }
 
+/// Drain the digest queue, sending everything collected every `interval_mins`
+/// as one message (split to fit Telegram's size limit).
+pub async fn run_digest_dispatcher(
+    notifier: std::sync::Arc<Notifier>,
+    mut rx: mpsc::Receiver<String>,
+    interval_mins: u64,
+) {
+    let period = Duration::from_secs(interval_mins * 60);
+    let mut ticker = interval_at(Instant::now() + period, period);
+    let mut pending: Vec<String> = Vec::new();
+
+    tracing::info!(interval_mins, "Notification digest dispatcher started");
+
+    loop {
+        tokio::select! {
+            msg = rx.recv() => match msg {
+                Some(msg) => pending.push(msg),
+                None => break,
+            },
+            _ = ticker.tick() => {
+                for chunk in format_digest(&pending) {
+                    notifier.send(&chunk).await;
+                }
+                pending.clear();
+            }
+        }
+    }
+
+    // Channel closed — flush whatever is left
+    for chunk in format_digest(&pending) {
+        notifier.send(&chunk).await;
+    }
+}
+
+/// Join queued messages into digest messages no longer than `MAX_MESSAGE_CHARS`.
+fn format_digest(messages: &[String]) -> Vec<String> {
+    if messages.is_empty() {
+        return Vec::new();
+    }
+
+    let separator = "\n\n——————\n\n";
+    let mut chunks: Vec<String> = Vec::new();
+    let mut current = String::new();
+    for msg in messages {
+        if !current.is_empty()
+            && current.chars().count() + separator.chars().count() + msg.chars().count() > MAX_MESSAGE_CHARS
+        {
+            chunks.push(std::mem::take(&mut current));
+        }
+        if !current.is_empty() {
+            current.push_str(separator);
+        }
+        current.push_str(msg);
+    }
+    chunks.push(current);
+
+    let total = chunks.len();
+    chunks
+        .into_iter()
+        .enumerate()
+        .map(|(i, body)| {
+            let part = if total > 1 { format!(" ({}/{})", i + 1, total) } else { String::new() };
+            format!("📬 *通知汇总* — {} 条{part}\n\n{body}", messages.len())
+        })
+        .collect()
+}
+
 // ---------------------------------------------------------------------------
 // Helpers
 // ---------------------------------------------------------------------------
@@ -128,6 +325,7 @@ pub fn format_copy_signal(
 // 2. Basket consensus
 // ---------------------------------------------------------------------------
 
+#[allow(clippy::too_many_arguments)]
 pub fn format_consensus_alert(
     basket_name: &str,
     direction: &str,
@@ -162,6 +360,34 @@ pub fn format_consensus_alert(
     )
 }
 
+/// Early warning that a basket's votes on a market are converging. Nothing is
+/// traded until the basket's own threshold is reached.
+#[allow(clippy::too_many_arguments)]
+pub fn format_consensus_building_alert(
+    basket_name: &str,
+    direction: &str,
+    consensus_pct: Decimal,
+    threshold: Decimal,
+    participating: i32,
+    total: i32,
+    market_id: &str,
+    market_question: Option<&str>,
+    price: Decimal,
+) -> String {
+    let market = market_label(market_question, market_id);
+    let side = side_cn(direction);
+    let pct = (consensus_pct * Decimal::from(100)).round_dp(0);
+    let threshold = (threshold * Decimal::from(100)).round_dp(0);
+
+    format!(
+        "⏳ *篮子共识形成中*\n\n\
+         📦 {basket_name} | 当前 {pct}% ({participating}/{total})，触发线 {threshold}%\n\
+         📍 {market}\n\
+         💰 {side}  当前价 ${price}\n\n\
+         仅提醒，未下单",
+    )
+}
+
 // ---------------------------------------------------------------------------
 // 3 & 4. Order result (filled / failed)
 // ---------------------------------------------------------------------------
@@ -218,6 +444,8 @@ pub fn format_position_exit(
     let reason_cn = match reason {
         "stop_loss" => "止损",
         "take_profit" => "止盈",
+        "time_exit" => "持仓超时",
+        "resolution_exit" => "临近结算",
         _ => reason,
     };
 
@@ -266,3 +494,479 @@ pub fn format_market_settled(
         pnl = pnl_sign(total_pnl.round_dp(2)),
     )
 }
+
+// ---------------------------------------------------------------------------
+// 7. Circuit breaker tripped
+// ---------------------------------------------------------------------------
+
+pub fn format_circuit_breaker_tripped(failures: usize, window_mins: i64, cooldown_mins: i64) -> String {
+    format!(
+        "🚨 *熔断触发*\n\n\
+         ⚠️ {window} 分钟内 {failures} 笔订单执行失败\n\
+         ⏸ 实盘执行已暂停, {cooldown} 分钟后自动恢复 (或手动恢复)",
+        failures = failures,
+        window = window_mins,
+        cooldown = cooldown_mins,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 8. Whale copying paused (copies losing money)
+// ---------------------------------------------------------------------------
+
+pub fn format_whale_copy_paused(
+    wallet: &str,
+    label: Option<&str>,
+    realized_pnl: Decimal,
+    closed_positions: i64,
+) -> String {
+    let name = match label {
+        Some(l) if !l.is_empty() => l.to_string(),
+        _ => shorten_wallet(wallet),
+    };
+
+    format!(
+        "⛔ *暂停跟单*\n\n\
+         🐋 {name}\n\
+         📊 跟单盈亏: {pnl} USDC ({count} 笔已平仓)\n\
+         ▶️ 可通过 API 手动恢复",
+        name = name,
+        pnl = pnl_sign(realized_pnl.round_dp(2)),
+        count = closed_positions,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 9. Wallet buffer breached (gas / fee reserve)
+// ---------------------------------------------------------------------------
+
+pub fn format_wallet_buffer_alert(reason: &str) -> String {
+    format!(
+        "⛽ *钱包余额不足*\n\n\
+         ⚠️ {reason}\n\
+         ⏸ 实盘订单已拦截, 请尽快充值",
+        reason = reason,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 10. Position hedged (opposite outcome bought)
+// ---------------------------------------------------------------------------
+
+pub fn format_position_hedge(
+    market_question: Option<&str>,
+    market_id: &str,
+    hedge_outcome: &str,
+    size: Decimal,
+    price: Decimal,
+    pnl_pct: Decimal,
+) -> String {
+    let market = market_label(market_question, market_id);
+
+    format!(
+        "🛡 *持仓对冲*\n\n\
+         📍 {market}\n\
+         📊 当前盈亏: {pnl_pct}%\n\
+         💰 买入 {outcome}  {size} 份 @ ${price}",
+        market = market,
+        pnl_pct = pnl_sign(pnl_pct.round_dp(2)),
+        outcome = hedge_outcome,
+        size = size,
+        price = price,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 11. Whale unpollable (Data API keeps failing)
+// ---------------------------------------------------------------------------
+
+pub fn format_whale_unpollable(
+    wallet: &str,
+    label: Option<&str>,
+    failures: u32,
+    hours: i64,
+    last_error: &str,
+) -> String {
+    let name = match label {
+        Some(l) if !l.is_empty() => l.to_string(),
+        _ => shorten_wallet(wallet),
+    };
+
+    format!(
+        "📡 *巨鲸无法轮询*\n\n\
+         🐋 {name}\n\
+         ⚠️ 已连续 {hours} 小时查询失败 ({failures} 次)\n\
+         🔍 最近错误: {err}\n\
+         💡 请检查地址格式",
+        name = name,
+        hours = hours,
+        failures = failures,
+        err = last_error,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 12. Position drift (DB vs account holdings)
+// ---------------------------------------------------------------------------
+
+pub fn format_position_drift(
+    drifts: &[&crate::services::position_reconciler::Drift],
+    auto_corrected: bool,
+) -> String {
+    use crate::services::position_reconciler::DriftKind;
+
+    let lines: Vec<String> = drifts
+        .iter()
+        .map(|d| {
+            let kind = match d.kind {
+                DriftKind::MissingInDb => "账户有, 本地无",
+                DriftKind::MissingOnAccount => "本地有, 账户无",
+                DriftKind::SizeMismatch => "数量不一致",
+            };
+            format!(
+                "• {token}: {kind} (本地 {db} / 账户 {acct})",
+                token = shorten_wallet(&d.token_id),
+                kind = kind,
+                db = d.db_size.round_dp(2),
+                acct = d.account_size.round_dp(2),
+            )
+        })
+        .collect();
+
+    format!(
+        "🧮 *持仓对账差异*\n\n\
+         {lines}\n\n\
+         {action}",
+        lines = lines.join("\n"),
+        action = if auto_corrected { "🔧 已按账户自动修正" } else { "👀 请人工核对" },
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 13. Copy signal blocked by a pipeline gate (digest only)
+// ---------------------------------------------------------------------------
+
+pub fn format_signal_blocked(
+    event: &WhaleTradeEvent,
+    reason: &str,
+    market_question: Option<&str>,
+) -> String {
+    format!(
+        "🚫 *信号拦截*\n\n\
+         📍 {market}\n\
+         📊 巨鲸: `{wallet}` | ${notional}\n\
+         ⚠️ {reason}",
+        market = market_label(market_question, &event.market_id),
+        wallet = shorten_wallet(&event.wallet),
+        notional = event.notional.round_dp(2),
+        reason = reason,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 14. Exit order still unfilled after all reprices
+// ---------------------------------------------------------------------------
+
+pub fn format_exit_unfilled(
+    market_question: Option<&str>,
+    market_id: &str,
+    reason: &str,
+    size: Decimal,
+    price: Decimal,
+    reprices: u32,
+) -> String {
+    format!(
+        "🆘 *平仓单未成交*\n\n\
+         📍 {market}\n\
+         ⚡ 触发: {reason}\n\
+         📦 剩余 {size} 份 @ ${price}\n\
+         🔁 已重新定价 {reprices} 次, 请人工处理",
+        market = market_label(market_question, market_id),
+        reason = reason,
+        size = size.round_dp(2),
+        price = price,
+        reprices = reprices,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 15. Daily position health (stale / liquidity collapse)
+// ---------------------------------------------------------------------------
+
+pub fn format_position_health(
+    flagged: &[&crate::services::position_health::PositionAging],
+    open_positions: usize,
+) -> String {
+    use crate::services::position_health::HealthFlag;
+
+    if flagged.is_empty() {
+        return format!("🩺 *持仓健康日报*\n\n✅ {open_positions} 个持仓均正常");
+    }
+
+    let lines: Vec<String> = flagged
+        .iter()
+        .map(|p| {
+            let flags: Vec<String> = p
+                .flags
+                .iter()
+                .map(|f| match f {
+                    HealthFlag::Stale => format!("持仓 {} 天", p.age_days),
+                    HealthFlag::LiquidityCollapse => format!(
+                        "流动性 {}%",
+                        p.liquidity_change_pct.unwrap_or(Decimal::ZERO).round_dp(1)
+                    ),
+                })
+                .collect();
+            format!(
+                "• {market}: {flags}",
+                market = market_label(p.market_question.as_deref(), &p.market_id),
+                flags = flags.join(", "),
+            )
+        })
+        .collect();
+
+    format!(
+        "🩺 *持仓健康日报*\n\n\
+         ⚠️ {flagged}/{total} 个持仓需关注\n\n\
+         {lines}",
+        flagged = flagged.len(),
+        total = open_positions,
+        lines = lines.join("\n"),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 16. Whale leaderboard PnL collapsing since seeding
+// ---------------------------------------------------------------------------
+
+pub fn format_whale_leaderboard_drift(
+    wallet: &str,
+    label: Option<&str>,
+    baseline_pnl: Decimal,
+    current_pnl: Decimal,
+    paused: bool,
+) -> String {
+    let name = match label {
+        Some(l) if !l.is_empty() => l.to_string(),
+        _ => shorten_wallet(wallet),
+    };
+    let drawdown = crate::services::leaderboard_drift::pnl_drawdown_pct(baseline_pnl, current_pnl)
+        .unwrap_or(Decimal::ZERO);
+    let action = if paused {
+        "⛔ 已暂停跟单，可通过 API 手动恢复"
+    } else {
+        "👀 仅提醒，未暂停跟单"
+    };
+
+    format!(
+        "📉 *鲸鱼排行榜盈利回撤*\n\n\
+         🐋 {name}\n\
+         📊 排行榜盈亏: {baseline} → {current} USDC (-{drawdown}%)\n\
+         {action}",
+        name = name,
+        baseline = pnl_sign(baseline_pnl.round_dp(0)),
+        current = pnl_sign(current_pnl.round_dp(0)),
+        drawdown = drawdown.round_dp(1),
+        action = action,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 17. End-of-day reconciliation (positions / orders / capital ledger)
+// ---------------------------------------------------------------------------
+
+/// Discrepancies listed individually before the rest are summarised.
+const MAX_RECONCILIATION_LINES: usize = 10;
+
+pub fn format_reconciliation_report(
+    report: &crate::models::ReconciliationReport,
+    discrepancies: &[crate::services::eod_reconciliation::Discrepancy],
+) -> String {
+    let mut checks = vec![format!(
+        "📦 持仓: 本地 {} / 账户 {}",
+        report.db_positions, report.account_positions
+    )];
+    if let Some(clob) = report.clob_open_orders {
+        checks.push(format!("📝 挂单: 本地 {} / CLOB {}", report.db_open_orders, clob));
+    }
+    if let (Some(ledger), Some(chain)) = (report.ledger_balance, report.chain_balance) {
+        checks.push(format!("💰 资金: 账本 ${} / 链上 ${}", ledger.round_dp(2), chain.round_dp(2)));
+    }
+
+    if discrepancies.is_empty() {
+        return format!("✅ *日终对账通过*\n\n{}", checks.join("\n"));
+    }
+
+    let mut lines: Vec<String> = discrepancies
+        .iter()
+        .take(MAX_RECONCILIATION_LINES)
+        .map(|d| format!("• {}: `{}` {}", d.kind, shorten_wallet(&d.subject), d.detail))
+        .collect();
+    if discrepancies.len() > MAX_RECONCILIATION_LINES {
+        lines.push(format!("…另有 {} 项", discrepancies.len() - MAX_RECONCILIATION_LINES));
+    }
+
+    format!(
+        "❌ *日终对账未通过*\n\n\
+         {checks}\n\n\
+         ⚠️ {count} 项差异:\n\
+         {lines}",
+        checks = checks.join("\n"),
+        count = discrepancies.len(),
+        lines = lines.join("\n"),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 18. Suspected insider (suspicious entry timing)
+// ---------------------------------------------------------------------------
+
+pub fn format_insider_flag(
+    wallet: &str,
+    label: Option<&str>,
+    market_question: Option<&str>,
+    flag: &crate::models::InsiderFlag,
+    total_flags: i64,
+) -> String {
+    let name = match label {
+        Some(l) if !l.is_empty() => l.to_string(),
+        _ => shorten_wallet(wallet),
+    };
+    let pattern = match crate::models::InsiderFlagKind::parse(&flag.kind) {
+        Some(crate::models::InsiderFlagKind::PreResolution) => "临近结算前大额建仓",
+        Some(crate::models::InsiderFlagKind::PreJump) => "价格跳涨前大额建仓",
+        None => flag.kind.as_str(),
+    };
+
+    format!(
+        "🕵️ *疑似内幕交易*\n\n\
+         🐋 {name}\n\
+         🔍 {pattern}\n\
+         📍 {market}\n\
+         📝 {evidence}\n\
+         🚩 累计标记 {total} 次",
+        name = name,
+        pattern = pattern,
+        market = market_label(market_question, &flag.market_id),
+        evidence = flag.evidence,
+        total = total_flags,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 19. Market-level net whale flow
+// ---------------------------------------------------------------------------
+
+pub fn format_flow_signal(
+    signal: &crate::models::FlowSignal,
+    market_question: Option<&str>,
+    price: Decimal,
+) -> String {
+    format!(
+        "🌊 *巨鲸资金净流入*\n\n\
+         📍 {market}\n\
+         💰 买入  当前价 ${price}\n\
+         💵 {window}分钟内净流入 ${net} USDC\n\
+         📊 买入 ${buy} / 卖出 ${sell} | {whales}位巨鲸",
+        market = market_label(market_question, &signal.market_id),
+        price = price,
+        window = signal.window_minutes,
+        net = signal.net_notional.round_dp(0),
+        buy = signal.buy_notional.round_dp(0),
+        sell = signal.sell_notional.round_dp(0),
+        whales = signal.whales,
+    )
+}
+
+// ---------------------------------------------------------------------------
+// 20. Whale withdrawing most of its capital
+// ---------------------------------------------------------------------------
+
+pub fn format_whale_capital_withdrawal(
+    wallet: &str,
+    label: Option<&str>,
+    amount: Decimal,
+    capital_share_pct: Decimal,
+    tx_hash: &str,
+) -> String {
+    let name = match label {
+        Some(l) if !l.is_empty() => l.to_string(),
+        _ => shorten_wallet(wallet),
+    };
+
+    format!(
+        "🏧 *鲸鱼大额提现*\n\n\
+         🐋 {name}\n\
+         💸 转出 ${amount} USDC（占其 USDC 的 {share}%）\n\
+         🔗 {tx}\n\
+         ⚠️ 可能正在撤资，考虑停用跟单",
+        name = name,
+        amount = amount.round_dp(0),
+        share = capital_share_pct.round_dp(1),
+        tx = shorten_wallet(tx_hash),
+    )
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_digest_routes_configured_severities() {
+        let config = DigestConfig {
+            interval_mins: 15,
+            severities: Severity::parse_list("info, bogus,warning"),
+            small_fill_usd: Decimal::from(100),
+        };
+        let (n, rx) = Notifier::new("token".into(), "chat".into()).with_digest(&config);
+
+        assert!(rx.is_some());
+        assert!(n.digests(Severity::Info));
+        assert!(n.digests(Severity::Warning));
+        assert!(!n.digests(Severity::Critical));
+        assert_eq!(n.fill_severity(Decimal::from(25)), Severity::Info);
+        assert_eq!(n.fill_severity(Decimal::from(250)), Severity::Warning);
+
+        let disabled = DigestConfig { interval_mins: 0, ..config };
+        let (n, rx) = Notifier::new("token".into(), "chat".into()).with_digest(&disabled);
+        assert!(rx.is_none());
+        assert!(!n.digests(Severity::Info));
+    }
+
+    #[test]
+    fn test_tagged_message() {
+        let n = Notifier::new("token".into(), "chat".into());
+        assert_eq!(n.tagged("hello"), "hello");
+
+        let n = n.with_tag("prod · 1a2b3c4d5e6f");
+        assert_eq!(n.tagged("hello"), "🏷 `prod · 1a2b3c4d5e6f`\nhello");
+    }
+
+    #[test]
+    fn test_format_digest_splits_long_batches() {
+        assert!(format_digest(&[]).is_empty());
+
+        let single = format_digest(&["a".into(), "b".into()]);
+        assert_eq!(single.len(), 1);
+        assert!(single[0].contains("2 条"));
+
+        let messages: Vec<String> = (0..10).map(|_| "x".repeat(900)).collect();
+        let chunks = format_digest(&messages);
+        assert!(chunks.len() > 1);
+        assert!(chunks.iter().all(|c| c.chars().count() <= MAX_MESSAGE_CHARS + 64));
+        assert!(chunks[0].contains(&format!("(1/{})", chunks.len())));
+    }
+
+    #[test]
+    fn test_format_circuit_breaker_tripped() {
+        let msg = format_circuit_breaker_tripped(3, 10, 30);
+        assert_eq!(
+            msg,
+            "🚨 *熔断触发*\n\n⚠️ 10 分钟内 3 笔订单执行失败\n⏸ 实盘执行已暂停, 30 分钟后自动恢复 (或手动恢复)"
+        );
+    }
+}
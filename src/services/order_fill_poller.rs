@@ -1,24 +1,56 @@
 use std::sync::Arc;
 
 use chrono::Utc;
+use metrics::counter;
+use polymarket_client_sdk::clob::types::response::OpenOrderResponse;
 use polymarket_client_sdk::clob::types::OrderStatusType;
 use rust_decimal::Decimal;
+use serde::Serialize;
 use sqlx::PgPool;
 use tokio::time::{interval, Duration};
+use uuid::Uuid;
 
-use crate::db::{order_repo, position_repo};
-use crate::execution::capital_pool::CapitalPool;
-use crate::execution::copy_engine::CopyEngineConfig;
+use crate::db::{market_repo, order_repo, position_repo};
+use crate::execution::copy_engine::{CopyEngineConfig, MANUAL_STRATEGY};
+use crate::execution::exit_escalation::ExitEscalation;
+use crate::execution::fill_costs::FillCostEstimator;
+use crate::execution::hedging::{self, HEDGE_STRATEGY};
+use crate::execution::order_rules;
+use crate::execution::scale_in::LADDER_STRATEGY;
+use crate::execution::sleeves::SleevePools;
+use crate::models::CopyOrder;
 use crate::polymarket::trading::TradingClient;
+use crate::services::job_trigger::RunRequests;
+use crate::services::notifier::Severity;
+
+/// Outcome of one fill poller cycle.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct FillPollSummary {
+    /// Submitted orders checked against the CLOB.
+    pub checked: usize,
+    pub filled: u32,
+    pub cancelled: u32,
+    /// Exit orders repriced to chase the market.
+    pub repriced: u32,
+    /// Orders still resting on the book.
+    pub live: u32,
+}
 
 /// Run the fill poller loop. Periodically checks submitted orders against the
 /// CLOB to confirm fills, detect cancellations, and auto-cancel stale orders.
+/// Unfilled loss-limiting exit orders are repriced per `exit_escalation`, and
+/// filled orders get their fee and gas cost recorded via `fill_costs`.
+/// `run_requests` runs a cycle immediately and answers with its summary.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_order_fill_poller(
     pool: PgPool,
     trading_client: Arc<TradingClient>,
-    capital_pool: CapitalPool,
+    capital_pools: SleevePools,
     engine_config: CopyEngineConfig,
+    exit_escalation: ExitEscalation,
+    fill_costs: FillCostEstimator,
     poll_interval_secs: u64,
+    mut run_requests: RunRequests<Result<FillPollSummary, String>>,
 ) {
     let order_stale_secs = engine_config.maker_order_ttl_secs as i64;
     let mut ticker = interval(Duration::from_secs(poll_interval_secs));
@@ -26,222 +58,633 @@ pub async fn run_order_fill_poller(
         interval_secs = poll_interval_secs,
         order_stale_secs,
         maker_mode = engine_config.maker_mode,
+        exit_reprice_secs = exit_escalation.config.reprice_secs,
         "Order fill poller started"
     );
 
     loop {
-        ticker.tick().await;
+        let reply = tokio::select! {
+            _ = ticker.tick() => None,
+            Some(reply) = run_requests.recv() => Some(reply),
+        };
+        let result = poll_fills_cycle(
+            &pool,
+            &trading_client,
+            &capital_pools,
+            &engine_config,
+            &exit_escalation,
+            &fill_costs,
+        )
+        .await;
+        if let Err(ref e) = result {
+            tracing::error!(error = %e, "Fill poller: failed to fetch submitted orders");
+        }
+        if let Some(reply) = reply {
+            let _ = reply.send(result.map_err(|e| e.to_string()));
+        }
+    }
+}
 
-        let orders = match order_repo::get_submitted_orders(&pool).await {
-            Ok(o) => o,
-            Err(e) => {
-                tracing::error!(error = %e, "Fill poller: failed to fetch submitted orders");
+/// Check every submitted order against the CLOB once.
+async fn poll_fills_cycle(
+    pool: &PgPool,
+    trading_client: &TradingClient,
+    capital_pools: &SleevePools,
+    engine_config: &CopyEngineConfig,
+    exit_escalation: &ExitEscalation,
+    fill_costs: &FillCostEstimator,
+) -> anyhow::Result<FillPollSummary> {
+    let order_stale_secs = engine_config.maker_order_ttl_secs as i64;
+    let mut summary = FillPollSummary::default();
+
+    let orders = order_repo::get_submitted_orders(pool).await?;
+
+    if orders.is_empty() {
+        tracing::debug!("Fill poller: no submitted orders");
+        return Ok(summary);
+    }
+
+    tracing::debug!(count = orders.len(), "Fill poller: checking submitted orders");
+    summary.checked = orders.len();
+
+    for order in &orders {
+        let clob_order_id = match &order.clob_order_id {
+            Some(id) if !id.is_empty() => id.as_str(),
+            _ => {
+                tracing::warn!(
+                    order_id = %order.id,
+                    "Fill poller: submitted order has no CLOB order ID — cancelling"
+                );
+                let _ = order_repo::cancel_order(pool, order.id).await;
+                if let Some(key) = reservation_key(order) {
+                    capital_pools.get_by_label(&order.sleeve).release(&key).await;
+                }
+                summary.cancelled += 1;
                 continue;
             }
         };
 
-        if orders.is_empty() {
-            tracing::debug!("Fill poller: no submitted orders");
-            continue;
-        }
+        // Check if order is stale (older than 5 minutes)
+        let is_stale = order
+            .placed_at
+            .map(|placed| {
+                let age = Utc::now() - placed;
+                age.num_seconds() > order_stale_secs
+            })
+            .unwrap_or(false);
 
-        tracing::debug!(count = orders.len(), "Fill poller: checking submitted orders");
+        // Query CLOB for order status
+        let clob_status = match trading_client.get_order(clob_order_id).await {
+            Ok(resp) => resp,
+            Err(e) => {
+                tracing::warn!(
+                    order_id = %order.id,
+                    clob_order_id = clob_order_id,
+                    error = %e,
+                    "Fill poller: failed to query CLOB order status"
+                );
 
-        for order in &orders {
-            let clob_order_id = match &order.clob_order_id {
-                Some(id) if !id.is_empty() => id.as_str(),
-                _ => {
+                // If stale and can't query, auto-cancel
+                if is_stale {
                     tracing::warn!(
                         order_id = %order.id,
-                        "Fill poller: submitted order has no CLOB order ID — cancelling"
+                        "Fill poller: stale order unreachable — cancelling"
                     );
-                    let _ = order_repo::cancel_order(&pool, order.id).await;
-                    // Release capital using whale_trade_id as the reservation key
-                    if let Some(wt_id) = order.whale_trade_id {
-                        capital_pool.release(&wt_id).await;
+                    // Try to cancel on CLOB side
+                    let _ = trading_client.cancel_order(clob_order_id).await;
+                    let _ = order_repo::cancel_order(pool, order.id).await;
+                    if let Some(key) = reservation_key(order) {
+                        capital_pools.get_by_label(&order.sleeve).release(&key).await;
                     }
-                    continue;
+                    reopen_exit_position(pool, order).await;
+                    summary.cancelled += 1;
                 }
-            };
-
-            // Check if order is stale (older than 5 minutes)
-            let is_stale = order
-                .placed_at
-                .map(|placed| {
-                    let age = Utc::now() - placed;
-                    age.num_seconds() > order_stale_secs
-                })
-                .unwrap_or(false);
-
-            // Query CLOB for order status
-            let clob_status = match trading_client.get_order(clob_order_id).await {
-                Ok(resp) => resp,
-                Err(e) => {
-                    tracing::warn!(
-                        order_id = %order.id,
-                        clob_order_id = clob_order_id,
-                        error = %e,
-                        "Fill poller: failed to query CLOB order status"
-                    );
+                continue;
+            }
+        };
 
-                    // If stale and can't query, auto-cancel
-                    if is_stale {
-                        tracing::warn!(
-                            order_id = %order.id,
-                            "Fill poller: stale order unreachable — cancelling"
-                        );
-                        // Try to cancel on CLOB side
-                        let _ = trading_client.cancel_order(clob_order_id).await;
-                        let _ = order_repo::cancel_order(&pool, order.id).await;
-                        if let Some(wt_id) = order.whale_trade_id {
-                            capital_pool.release(&wt_id).await;
-                        }
-                    }
-                    continue;
-                }
-            };
-
-            match clob_status.status {
-                OrderStatusType::Matched => {
-                    // Fully filled
-                    let fill_price = clob_status.price;
-                    let slippage = if order.target_price > Decimal::ZERO {
-                        ((fill_price - order.target_price) / order.target_price * Decimal::from(100)).abs()
-                    } else {
-                        Decimal::ZERO
-                    };
+        match clob_status.status {
+            OrderStatusType::Matched => {
+                // Fully filled
+                let fill_price = clob_status.price;
+                let slippage = fill_slippage(order, fill_price);
 
-                    tracing::info!(
-                        order_id = %order.id,
-                        clob_order_id,
-                        fill_price = %fill_price,
-                        size_matched = %clob_status.size_matched,
-                        "Fill poller: order matched"
-                    );
-
-                    // Update order as filled
-                    if let Err(e) = order_repo::fill_order(&pool, order.id, fill_price, slippage).await {
-                        tracing::error!(error = %e, "Fill poller: failed to mark order filled");
-                        continue;
-                    }
+                tracing::info!(
+                    order_id = %order.id,
+                    clob_order_id,
+                    fill_price = %fill_price,
+                    size_matched = %clob_status.size_matched,
+                    "Fill poller: order matched"
+                );
 
-                    // Confirm capital reservation
-                    if let Some(wt_id) = order.whale_trade_id {
-                        capital_pool.confirm(&wt_id).await;
-                    }
+                // Update order as filled
+                if let Err(e) = order_repo::fill_order(pool, order.id, fill_price, slippage).await {
+                    tracing::error!(error = %e, "Fill poller: failed to mark order filled");
+                    continue;
+                }
+                summary.filled += 1;
 
-                    // Handle based on strategy type
-                    if order.strategy == "exit" {
-                        // Exit order filled — close the position
-                        handle_exit_fill(&pool, order, fill_price).await;
-                    } else {
-                        // Entry order filled — create/update position
-                        let outcome = match order.side.as_str() {
-                            "BUY" => "Yes",
-                            _ => "No",
-                        };
-
-                        match position_repo::upsert_position(
-                            &pool,
-                            &order.market_id,
-                            &order.token_id,
-                            outcome,
-                            order.size,
-                            fill_price,
-                        )
-                        .await
-                        {
-                            Ok(position) => {
-                                if let Err(e) = position_repo::set_position_sl_tp(
-                                    &pool,
-                                    position.id,
-                                    engine_config.default_stop_loss_pct,
-                                    engine_config.default_take_profit_pct,
-                                )
-                                .await
-                                {
-                                    tracing::warn!(error = %e, "Fill poller: failed to set SL/TP");
-                                }
-
-                                tracing::info!(
-                                    order_id = %order.id,
-                                    position_id = %position.id,
-                                    "Fill poller: position created/updated from fill"
-                                );
-                            }
-                            Err(e) => {
-                                tracing::error!(
-                                    error = %e,
-                                    order_id = %order.id,
-                                    "Fill poller: failed to upsert position"
-                                );
-                            }
-                        }
+                // Record all-in costs for net PnL and the EV model
+                if let Some(costs) = fill_costs.estimate(&clob_status).await {
+                    if let Err(e) =
+                        order_repo::set_order_costs(pool, order.id, costs.fee_usdc, costs.gas_usdc).await
+                    {
+                        tracing::warn!(error = %e, order_id = %order.id, "Fill poller: failed to record fill costs");
                     }
                 }
 
-                OrderStatusType::Live => {
-                    // Still waiting for fill
-                    if clob_status.size_matched > Decimal::ZERO {
-                        tracing::info!(
-                            order_id = %order.id,
-                            size_matched = %clob_status.size_matched,
-                            original_size = %clob_status.original_size,
-                            "Fill poller: partial fill in progress"
-                        );
-                    }
+                // Confirm capital reservation
+                if let Some(key) = reservation_key(order) {
+                    capital_pools.get_by_label(&order.sleeve).confirm(&key).await;
+                }
 
-                    // Auto-cancel if stale
-                    if is_stale {
-                        tracing::warn!(
-                            order_id = %order.id,
-                            clob_order_id,
-                            "Fill poller: order stale — cancelling"
-                        );
-                        if let Err(e) = trading_client.cancel_order(clob_order_id).await {
-                            tracing::error!(error = %e, "Fill poller: failed to cancel stale order on CLOB");
-                        }
-                        let _ = order_repo::cancel_order(&pool, order.id).await;
-                        if let Some(wt_id) = order.whale_trade_id {
-                            capital_pool.release(&wt_id).await;
-                        }
-                    }
+                // Handle based on strategy type
+                if order.strategy == "exit" {
+                    // Exit order filled — close the position
+                    handle_exit_fill(pool, order, order.size, fill_price, capital_pools, engine_config.order_size_decimals)
+                        .await;
+                } else {
+                    // Entry order filled — create/update position
+                    handle_entry_fill(pool, order, order.size, fill_price, engine_config).await;
                 }
+            }
 
-                OrderStatusType::Canceled | OrderStatusType::Unmatched => {
+            OrderStatusType::Live => {
+                // Still waiting for fill
+                if clob_status.size_matched > Decimal::ZERO {
                     tracing::info!(
                         order_id = %order.id,
-                        clob_order_id,
-                        status = ?clob_status.status,
-                        "Fill poller: order cancelled/unmatched"
+                        size_matched = %clob_status.size_matched,
+                        original_size = %clob_status.original_size,
+                        "Fill poller: partial fill in progress"
                     );
+                }
 
-                    let _ = order_repo::cancel_order(&pool, order.id).await;
-                    if let Some(wt_id) = order.whale_trade_id {
-                        capital_pool.release(&wt_id).await;
-                    }
+                if order.strategy == "exit"
+                    && escalate_exit(
+                        pool,
+                        trading_client,
+                        exit_escalation,
+                        capital_pools,
+                        fill_costs,
+                        order,
+                        clob_order_id,
+                        &clob_status,
+                        engine_config.order_size_decimals,
+                    )
+                    .await
+                {
+                    summary.repriced += 1;
+                    continue;
                 }
 
-                other => {
-                    tracing::debug!(
+                // Auto-cancel if stale
+                if is_stale {
+                    tracing::warn!(
                         order_id = %order.id,
-                        status = ?other,
-                        "Fill poller: unexpected order status"
+                        clob_order_id,
+                        "Fill poller: order stale — cancelling"
                     );
+                    if let Err(e) = trading_client.cancel_order(clob_order_id).await {
+                        tracing::error!(error = %e, "Fill poller: failed to cancel stale order on CLOB");
+                    }
+                    settle_cancelled_order(
+                        pool,
+                        order,
+                        &clob_status,
+                        capital_pools,
+                        engine_config,
+                        fill_costs,
+                        &mut summary,
+                    )
+                    .await;
+                } else {
+                    summary.live += 1;
                 }
             }
+
+            OrderStatusType::Canceled | OrderStatusType::Unmatched => {
+                tracing::info!(
+                    order_id = %order.id,
+                    clob_order_id,
+                    status = ?clob_status.status,
+                    size_matched = %clob_status.size_matched,
+                    "Fill poller: order cancelled/unmatched"
+                );
+
+                settle_cancelled_order(
+                    pool,
+                    order,
+                    &clob_status,
+                    capital_pools,
+                    engine_config,
+                    fill_costs,
+                    &mut summary,
+                )
+                .await;
+            }
+
+            other => {
+                tracing::debug!(
+                    order_id = %order.id,
+                    status = ?other,
+                    "Fill poller: unexpected order status"
+                );
+            }
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Settle an order that left the book before filling completely. The part
+/// already matched (a fill-and-kill order's fill, or a partial fill before a
+/// cancel) is booked as a fill at the order's price; only the rest of the
+/// capital is released, and an exit's position is reopened for what is left.
+#[allow(clippy::too_many_arguments)]
+async fn settle_cancelled_order(
+    pool: &PgPool,
+    order: &CopyOrder,
+    clob_status: &OpenOrderResponse,
+    capital_pools: &SleevePools,
+    engine_config: &CopyEngineConfig,
+    fill_costs: &FillCostEstimator,
+    summary: &mut FillPollSummary,
+) {
+    let size_matched = clob_status.size_matched.min(order.size);
+    let pool_for_order = capital_pools.get_by_label(&order.sleeve);
+
+    if size_matched <= Decimal::ZERO {
+        let _ = order_repo::cancel_order(pool, order.id).await;
+        if let Some(key) = reservation_key(order) {
+            pool_for_order.release(&key).await;
+        }
+        reopen_exit_position(pool, order).await;
+        summary.cancelled += 1;
+        return;
+    }
+
+    let fill_price = clob_status.price;
+    if let Err(e) =
+        order_repo::fill_order_partially(pool, order.id, size_matched, fill_price, fill_slippage(order, fill_price))
+            .await
+    {
+        tracing::error!(error = %e, order_id = %order.id, "Fill poller: failed to book partial fill");
+        return;
+    }
+    summary.filled += 1;
+    tracing::info!(
+        order_id = %order.id,
+        size_matched = %size_matched,
+        original_size = %order.size,
+        fill_price = %fill_price,
+        "Fill poller: order closed with a partial fill"
+    );
+
+    if let Some(costs) = fill_costs.estimate(clob_status).await {
+        if let Err(e) = order_repo::set_order_costs(pool, order.id, costs.fee_usdc, costs.gas_usdc).await {
+            tracing::warn!(error = %e, order_id = %order.id, "Fill poller: failed to record fill costs");
+        }
+    }
+
+    if let Some(key) = reservation_key(order) {
+        pool_for_order.confirm_partial(&key, size_matched / order.size).await;
+    }
+
+    if order.strategy == "exit" {
+        handle_exit_fill(pool, order, size_matched, fill_price, capital_pools, engine_config.order_size_decimals)
+            .await;
+        reopen_exit_position(pool, order).await;
+    } else {
+        handle_entry_fill(pool, order, size_matched, fill_price, engine_config).await;
+    }
+}
+
+/// Slippage of a fill against the order's target price, as a fraction like
+/// the executor's `check_slippage`.
+fn fill_slippage(order: &CopyOrder, fill_price: Decimal) -> Decimal {
+    if order.target_price > Decimal::ZERO {
+        ((fill_price - order.target_price) / order.target_price).abs()
+    } else {
+        Decimal::ZERO
+    }
+}
+
+/// Add a filled entry order to its sleeve's position in the token, with the
+/// default stop-loss and take-profit.
+async fn handle_entry_fill(
+    pool: &PgPool,
+    order: &CopyOrder,
+    filled_size: Decimal,
+    fill_price: Decimal,
+    engine_config: &CopyEngineConfig,
+) {
+    let outcome = match order.side.as_str() {
+        "BUY" => "Yes",
+        _ => "No",
+    };
+
+    match position_repo::upsert_position(
+        pool,
+        &order.market_id,
+        &order.token_id,
+        outcome,
+        filled_size,
+        fill_price,
+        &order.sleeve,
+        order.condition_id.as_deref(),
+        order.whale_trade_id,
+        order.source_signal_id,
+    )
+    .await
+    {
+        Ok(position) => {
+            if let Err(e) = position_repo::set_position_sl_tp(
+                pool,
+                position.id,
+                engine_config.default_stop_loss_pct,
+                engine_config.default_take_profit_pct,
+                engine_config.default_stop_mode,
+            )
+            .await
+            {
+                tracing::warn!(error = %e, "Fill poller: failed to set SL/TP");
+            }
+
+            if order.strategy == HEDGE_STRATEGY {
+                let market_key = order.condition_id.as_deref().unwrap_or(&order.market_id);
+                hedging::link_hedge_leg(pool, &position, market_key).await;
+            }
+
+            tracing::info!(
+                order_id = %order.id,
+                position_id = %position.id,
+                "Fill poller: position created/updated from fill"
+            );
+        }
+        Err(e) => {
+            tracing::error!(
+                error = %e,
+                order_id = %order.id,
+                "Fill poller: failed to upsert position"
+            );
         }
     }
 }
 
-/// Handle a filled exit order: find the "exiting" position and close it with realized PnL.
+/// Capital reservation key for an order: ladder rungs, manual orders and
+/// hedges reserve under their own order id, every other order under its
+/// whale_trade_id.
+pub(crate) fn reservation_key(order: &CopyOrder) -> Option<Uuid> {
+    if order.strategy == LADDER_STRATEGY || order.strategy == MANUAL_STRATEGY || order.strategy == HEDGE_STRATEGY {
+        Some(order.id)
+    } else {
+        order.whale_trade_id
+    }
+}
+
+/// Put the position behind a cancelled exit order back to "open" so the
+/// position monitor re-evaluates it instead of leaving it stuck as exiting.
+async fn reopen_exit_position(pool: &PgPool, order: &CopyOrder) {
+    if order.strategy != "exit" {
+        return;
+    }
+
+    // Another exit order still selling the position keeps it exiting
+    match order_repo::get_pending_exit_size(pool, &order.token_id, &order.sleeve).await {
+        Ok(pending) if pending > Decimal::ZERO => return,
+        Ok(_) => {}
+        Err(e) => {
+            tracing::error!(error = %e, token_id = %order.token_id, "Fill poller: failed to load pending exits");
+            return;
+        }
+    }
+
+    match position_repo::get_position_by_token_id(pool, &order.token_id, &order.sleeve).await {
+        Ok(Some(pos)) => {
+            if let Err(e) = position_repo::reopen_position(pool, pos.id).await {
+                tracing::error!(error = %e, position_id = %pos.id, "Fill poller: failed to reopen position");
+            } else {
+                tracing::info!(
+                    position_id = %pos.id,
+                    "Fill poller: exit order cancelled — position reopened"
+                );
+            }
+        }
+        Ok(None) => {}
+        Err(e) => {
+            tracing::error!(error = %e, token_id = %order.token_id, "Fill poller: failed to look up position");
+        }
+    }
+}
+
+/// Reprice a resting exit order once it has waited `reprice_secs`: cancel it,
+/// book any partial fill at its traded price, and resubmit the remainder
+/// further below the best bid. The reprice count is carried on the orders,
+/// so it survives restarts. Once `max_reprices` is used up the order is left
+/// resting and a critical alert is sent. Returns true if the order was
+/// handled here and must not go through the generic stale-order cancel.
+#[allow(clippy::too_many_arguments)]
+async fn escalate_exit(
+    pool: &PgPool,
+    trading_client: &TradingClient,
+    escalation: &ExitEscalation,
+    capital_pools: &SleevePools,
+    fill_costs: &FillCostEstimator,
+    order: &CopyOrder,
+    clob_order_id: &str,
+    clob_status: &OpenOrderResponse,
+    size_decimals: u32,
+) -> bool {
+    let size_matched = clob_status.size_matched.min(order.size);
+    let config = &escalation.config;
+    let pos = match position_repo::get_position_by_token_id(pool, &order.token_id, &order.sleeve).await {
+        Ok(Some(pos)) => pos,
+        Ok(None) => return false,
+        Err(e) => {
+            tracing::error!(error = %e, token_id = %order.token_id, "Fill poller: failed to look up exiting position");
+            return false;
+        }
+    };
+    let reason = pos.exit_reason.as_deref().unwrap_or("exit");
+    if !config.applies_to(pos.exit_reason.as_deref()) {
+        return false;
+    }
+
+    let age_secs = order
+        .placed_at
+        .map(|placed| (Utc::now() - placed).num_seconds())
+        .unwrap_or(0);
+    if age_secs < config.reprice_secs {
+        return true;
+    }
+
+    let attempts = order.exit_reprices.max(0) as u32;
+    if attempts >= config.max_reprices {
+        if order.exit_alerted_at.is_none() {
+            tracing::error!(
+                order_id = %order.id,
+                position_id = %pos.id,
+                reprices = attempts,
+                "Fill poller: exit order still unfilled after max reprices"
+            );
+            if let Some(ref n) = escalation.notifier {
+                let market_question = market_repo::get_market_question(pool, pos.market_key())
+                    .await
+                    .ok()
+                    .flatten();
+                let msg = crate::services::notifier::format_exit_unfilled(
+                    market_question.as_deref(),
+                    &pos.market_id,
+                    reason,
+                    order.size - size_matched,
+                    order.target_price,
+                    attempts,
+                );
+                n.notify(Severity::Critical, &msg).await;
+            }
+            if let Err(e) = order_repo::mark_exit_alerted(pool, order.id).await {
+                tracing::error!(error = %e, order_id = %order.id, "Fill poller: failed to record exit alert");
+            }
+        }
+        return true;
+    }
+
+    if let Err(e) = trading_client.cancel_order(clob_order_id).await {
+        tracing::error!(error = %e, order_id = %order.id, "Fill poller: failed to cancel exit order for reprice");
+        return true;
+    }
+
+    if size_matched > Decimal::ZERO {
+        let fill_price = fill_costs.fill_price(clob_status).await.unwrap_or(clob_status.price);
+        if let Err(e) =
+            order_repo::fill_order_partially(pool, order.id, size_matched, fill_price, fill_slippage(order, fill_price))
+                .await
+        {
+            tracing::error!(error = %e, order_id = %order.id, "Fill poller: failed to book partial exit fill");
+            return true;
+        }
+        if let Some(costs) = fill_costs.estimate(clob_status).await {
+            if let Err(e) = order_repo::set_order_costs(pool, order.id, costs.fee_usdc, costs.gas_usdc).await {
+                tracing::warn!(error = %e, order_id = %order.id, "Fill poller: failed to record fill costs");
+            }
+        }
+        handle_exit_fill(pool, order, size_matched, fill_price, capital_pools, size_decimals).await;
+    } else if let Err(e) = order_repo::cancel_order(pool, order.id).await {
+        tracing::error!(error = %e, order_id = %order.id, "Fill poller: failed to mark exit order cancelled");
+        return true;
+    }
+    // Less than a lot left can't be sold; the fill above closed the position
+    let remaining = order.size - size_matched;
+    if order_rules::is_dust(remaining, size_decimals) {
+        return true;
+    }
+
+    let attempt = attempts + 1;
+    let best_bid = escalation
+        .best_bid(&order.token_id)
+        .await
+        .unwrap_or(order.target_price);
+    let price = config.reprice(best_bid, order.target_price, attempt);
+
+    let clob_id = match trading_client
+        .place_limit_order(&order.token_id, "SELL", remaining, price)
+        .await
+    {
+        Ok(resp) if resp.success => resp.order_id,
+        Ok(resp) => {
+            tracing::error!(
+                order_id = %order.id,
+                error = %resp.error_msg.unwrap_or_default(),
+                "Fill poller: repriced exit order rejected"
+            );
+            reopen_exit_position(pool, order).await;
+            return true;
+        }
+        Err(e) => {
+            tracing::error!(error = %e, order_id = %order.id, "Fill poller: failed to place repriced exit order");
+            reopen_exit_position(pool, order).await;
+            return true;
+        }
+    };
+
+    match order_repo::insert_order(
+        pool,
+        Uuid::nil(),
+        &order.market_id,
+        &order.token_id,
+        "SELL",
+        remaining,
+        price,
+        "exit",
+        &order.sleeve,
+        order.condition_id.as_deref(),
+        None,
+    )
+    .await
+    {
+        Ok(new_order) => {
+            if let Err(e) = order_repo::set_exit_reprices(pool, new_order.id, attempt as i32).await {
+                tracing::error!(error = %e, "Fill poller: failed to record exit reprice count");
+            }
+            if let Err(e) = order_repo::mark_order_submitted(pool, new_order.id, &clob_id).await {
+                tracing::error!(error = %e, "Fill poller: failed to mark repriced exit order as submitted");
+            }
+        }
+        Err(e) => {
+            tracing::error!(error = %e, "Fill poller: failed to record repriced exit order");
+        }
+    }
+
+    counter!("exit_orders_repriced").increment(1);
+    tracing::warn!(
+        position_id = %pos.id,
+        exit_reason = reason,
+        attempt,
+        previous_price = %order.target_price,
+        price = %price,
+        size = %remaining,
+        "Fill poller: exit order unfilled — repriced"
+    );
+    true
+}
+
+/// Handle a filled exit order: close the position with realized PnL, or shrink
+/// it when the order sold only part of it (proportional whale exit, or the
+/// filled part of a repriced exit).
 async fn handle_exit_fill(
     pool: &PgPool,
-    order: &crate::models::CopyOrder,
+    order: &CopyOrder,
+    sold_size: Decimal,
     fill_price: Decimal,
+    capital_pools: &SleevePools,
+    size_decimals: u32,
 ) {
-    // Find the position by token_id that is in "exiting" state
-    match position_repo::get_position_by_token_id(pool, &order.token_id).await {
+    // Find the position by token_id that is in "exiting" state. A sale that
+    // leaves less than one lot closes it: the dust can never be sold.
+    match position_repo::get_position_by_token_id(pool, &order.token_id, &order.sleeve).await {
+        Ok(Some(pos)) if !order_rules::is_dust(pos.size - sold_size, size_decimals) => {
+            let realized_pnl = (fill_price - pos.avg_entry_price) * sold_size;
+
+            if let Err(e) = position_repo::reduce_position(pool, pos.id, sold_size, realized_pnl).await {
+                tracing::error!(
+                    error = %e,
+                    position_id = %pos.id,
+                    "Fill poller: failed to reduce position on partial exit fill"
+                );
+                return;
+            }
+
+            let returned = pos.avg_entry_price * sold_size + realized_pnl;
+            capital_pools.get_by_label(&pos.sleeve).return_capital(returned).await;
+
+            tracing::info!(
+                position_id = %pos.id,
+                sold = %sold_size,
+                realized_pnl = %realized_pnl,
+                "Fill poller: position reduced from partial exit fill"
+            );
+        }
         Ok(Some(pos)) => {
             let realized_pnl = (fill_price - pos.avg_entry_price) * pos.size;
             let reason = pos.exit_reason.as_deref().unwrap_or("exit");
@@ -259,6 +702,10 @@ async fn handle_exit_fill(
                 return;
             }
 
+            // Credit the proceeds back to the sleeve that opened the position
+            let returned = pos.avg_entry_price * pos.size + realized_pnl;
+            capital_pools.get_by_label(&pos.sleeve).return_capital(returned).await;
+
             tracing::info!(
                 position_id = %pos.id,
                 realized_pnl = %realized_pnl,
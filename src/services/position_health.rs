@@ -0,0 +1,244 @@
+use std::sync::Arc;
+
+use chrono::{DateTime, Duration as ChronoDuration, NaiveTime, Utc};
+use rust_decimal::Decimal;
+use serde::Serialize;
+use sqlx::PgPool;
+
+use crate::config::AppConfig;
+use crate::db::{market_repo, position_repo};
+use crate::models::Position;
+use crate::services::notifier::{Notifier, Severity};
+
+/// Thresholds for the position health report.
+#[derive(Debug, Clone)]
+pub struct PositionHealthConfig {
+    /// Positions held at least this many days are flagged stale.
+    pub stale_days: i64,
+    /// Flag a position when market liquidity fell by at least this percent since entry.
+    pub liquidity_drop_pct: Decimal,
+    /// UTC hour (0–23) the daily notification is sent.
+    pub report_hour_utc: u32,
+}
+
+impl PositionHealthConfig {
+    pub fn from_config(config: &AppConfig) -> Self {
+        Self {
+            stale_days: config.position_stale_days,
+            liquidity_drop_pct: config.position_liquidity_drop_pct,
+            report_hour_utc: config.position_health_report_hour,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthFlag {
+    /// Held longer than `stale_days`.
+    Stale,
+    /// Market liquidity dropped by `liquidity_drop_pct` or more since entry.
+    LiquidityCollapse,
+}
+
+/// One open position in the aging report.
+#[derive(Debug, Clone, Serialize)]
+pub struct PositionAging {
+    pub position_id: uuid::Uuid,
+    pub market_id: String,
+    pub token_id: String,
+    pub market_question: Option<String>,
+    pub sleeve: String,
+    pub size: Decimal,
+    pub avg_entry_price: Decimal,
+    pub current_price: Option<Decimal>,
+    pub unrealized_pnl: Option<Decimal>,
+    pub opened_at: Option<DateTime<Utc>>,
+    pub age_days: i64,
+    pub entry_liquidity: Option<Decimal>,
+    pub current_liquidity: Option<Decimal>,
+    /// Liquidity change since entry in percent (negative = shrinking).
+    pub liquidity_change_pct: Option<Decimal>,
+    pub flags: Vec<HealthFlag>,
+}
+
+/// Liquidity change since entry in percent, when both values are known.
+fn liquidity_change_pct(entry: Option<Decimal>, current: Option<Decimal>) -> Option<Decimal> {
+    match (entry, current) {
+        (Some(entry), Some(current)) if entry > Decimal::ZERO => {
+            Some((current - entry) / entry * Decimal::ONE_HUNDRED)
+        }
+        _ => None,
+    }
+}
+
+/// Health flags for a position given the market's current liquidity.
+pub fn assess(
+    pos: &Position,
+    current_liquidity: Option<Decimal>,
+    now: DateTime<Utc>,
+    config: &PositionHealthConfig,
+) -> Vec<HealthFlag> {
+    let mut flags = Vec::new();
+
+    let age_days = pos.opened_at.map(|t| (now - t).num_days()).unwrap_or(0);
+    if config.stale_days > 0 && age_days >= config.stale_days {
+        flags.push(HealthFlag::Stale);
+    }
+
+    if let Some(change) = liquidity_change_pct(pos.entry_liquidity, current_liquidity) {
+        if config.liquidity_drop_pct > Decimal::ZERO && -change >= config.liquidity_drop_pct {
+            flags.push(HealthFlag::LiquidityCollapse);
+        }
+    }
+
+    flags
+}
+
+/// Aging report over all open positions, oldest first.
+pub async fn aging_report(
+    pool: &PgPool,
+    config: &PositionHealthConfig,
+) -> anyhow::Result<Vec<PositionAging>> {
+    let now = Utc::now();
+    let mut report = Vec::new();
+
+    for pos in position_repo::get_open_positions(pool).await? {
+        let current_liquidity = market_repo::get_market_liquidity(pool, pos.market_key())
+            .await
+            .ok()
+            .flatten();
+        let market_question = market_repo::get_market_question(pool, pos.market_key())
+            .await
+            .ok()
+            .flatten();
+        let flags = assess(&pos, current_liquidity, now, config);
+
+        report.push(PositionAging {
+            position_id: pos.id,
+            market_question,
+            sleeve: pos.sleeve,
+            size: pos.size,
+            avg_entry_price: pos.avg_entry_price,
+            current_price: pos.current_price,
+            unrealized_pnl: pos.unrealized_pnl,
+            opened_at: pos.opened_at,
+            age_days: pos.opened_at.map(|t| (now - t).num_days()).unwrap_or(0),
+            liquidity_change_pct: liquidity_change_pct(pos.entry_liquidity, current_liquidity)
+                .map(|c| c.round_dp(2)),
+            entry_liquidity: pos.entry_liquidity,
+            current_liquidity,
+            flags,
+            market_id: pos.market_id,
+            token_id: pos.token_id,
+        });
+    }
+
+    report.sort_by_key(|r| std::cmp::Reverse(r.age_days));
+    Ok(report)
+}
+
+/// Next time the daily report is due: today at `hour` UTC, or tomorrow if that passed.
+pub(crate) fn next_report_at(now: DateTime<Utc>, hour: u32) -> DateTime<Utc> {
+    let time = NaiveTime::from_hms_opt(hour.min(23), 0, 0).unwrap_or(NaiveTime::MIN);
+    let today = now.date_naive().and_time(time).and_utc();
+    if today > now {
+        today
+    } else {
+        today + ChronoDuration::days(1)
+    }
+}
+
+/// Send a daily position health notification at `report_hour_utc`.
+pub async fn run_position_health_report(
+    pool: PgPool,
+    notifier: Arc<Notifier>,
+    config: PositionHealthConfig,
+) {
+    tracing::info!(
+        stale_days = config.stale_days,
+        liquidity_drop_pct = %config.liquidity_drop_pct,
+        report_hour_utc = config.report_hour_utc,
+        "Position health reporter started"
+    );
+
+    loop {
+        let now = Utc::now();
+        let wait = (next_report_at(now, config.report_hour_utc) - now)
+            .to_std()
+            .unwrap_or_default();
+        tokio::time::sleep(wait).await;
+
+        let report = match aging_report(&pool, &config).await {
+            Ok(r) => r,
+            Err(e) => {
+                tracing::error!(error = %e, "Position health: failed to build aging report");
+                continue;
+            }
+        };
+
+        let flagged: Vec<&PositionAging> = report.iter().filter(|r| !r.flags.is_empty()).collect();
+        tracing::info!(
+            open_positions = report.len(),
+            flagged = flagged.len(),
+            "Position health report"
+        );
+
+        let severity = if flagged.is_empty() { Severity::Info } else { Severity::Warning };
+        let msg = crate::services::notifier::format_position_health(&flagged, report.len());
+        notifier.notify(severity, &msg).await;
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    fn config() -> PositionHealthConfig {
+        PositionHealthConfig {
+            stale_days: 7,
+            liquidity_drop_pct: Decimal::from(50),
+            report_hour_utc: 8,
+        }
+    }
+
+    fn position(opened_days_ago: i64, entry_liquidity: Option<Decimal>, now: DateTime<Utc>) -> Position {
+        Position {
+            opened_at: Some(now - ChronoDuration::days(opened_days_ago)),
+            entry_liquidity,
+            ..Position::test_fixture()
+        }
+    }
+
+    #[test]
+    fn test_assess_flags() {
+        let now = Utc::now();
+        let c = config();
+
+        let fresh = position(1, Some(Decimal::from(10_000)), now);
+        assert!(assess(&fresh, Some(Decimal::from(9_000)), now, &c).is_empty());
+
+        let old = position(7, None, now);
+        assert_eq!(assess(&old, Some(Decimal::from(500)), now, &c), vec![HealthFlag::Stale]);
+
+        let drained = position(2, Some(Decimal::from(10_000)), now);
+        assert_eq!(
+            assess(&drained, Some(Decimal::from(5_000)), now, &c),
+            vec![HealthFlag::LiquidityCollapse]
+        );
+        // Market dropped out of active_markets: no current liquidity, no flag
+        assert!(assess(&drained, None, now, &c).is_empty());
+    }
+
+    #[test]
+    fn test_next_report_at() {
+        let before = Utc.with_ymd_and_hms(2026, 3, 1, 6, 30, 0).unwrap();
+        assert_eq!(next_report_at(before, 8), Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap());
+        let after = Utc.with_ymd_and_hms(2026, 3, 1, 8, 0, 0).unwrap();
+        assert_eq!(next_report_at(after, 8), Utc.with_ymd_and_hms(2026, 3, 2, 8, 0, 0).unwrap());
+    }
+}
@@ -1,20 +1,34 @@
+use std::collections::HashMap;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::Arc;
 
-use chrono::Utc;
+use chrono::{DateTime, Utc};
+use metrics::counter;
 use rust_decimal::Decimal;
 use sqlx::PgPool;
+use tokio::sync::broadcast;
 use tokio::time::{interval, Duration};
+use uuid::Uuid;
 
-use crate::db::{config_repo, market_repo, order_repo, position_repo};
-use crate::execution::capital_pool::CapitalPool;
+use crate::api::ws_types::WsMessage;
+
+use crate::db::{market_repo, order_repo, position_repo};
+use crate::execution::hedging::{self, HedgeConfig, HEDGE_STRATEGY};
+use crate::execution::risk_manager::SharedRiskLimits;
+use crate::execution::sleeves::SleevePools;
+use crate::ingestion::price_cache::PriceCache;
 use crate::polymarket::clob_client::ClobClient;
 use crate::polymarket::trading::TradingClient;
+use crate::models::{CopyOrder, ExitStyle, Position, StopMode};
 use crate::services::notifier::Notifier;
 
 /// Run the position monitor loop. Periodically checks open positions,
-/// fetches current prices from the CLOB orderbook, and triggers stop-loss
-/// or take-profit exits when thresholds are breached.
+/// reads current prices from the WS-fed price cache (falling back to the
+/// CLOB orderbook for tokens without a fresh quote), and triggers stop-loss
+/// or take-profit exits when thresholds are breached. With hedging enabled,
+/// a position past the hedge loss threshold is covered by buying the
+/// complementary outcome instead.
+#[allow(clippy::too_many_arguments)]
 pub async fn run_position_monitor(
     pool: PgPool,
     clob_client: ClobClient,
@@ -23,9 +37,16 @@ pub async fn run_position_monitor(
     pause_flag: Arc<AtomicBool>,
     interval_secs: u64,
     notifier: Option<Arc<Notifier>>,
-    capital_pool: Option<CapitalPool>,
+    capital_pools: SleevePools,
+    ws_tx: Option<broadcast::Sender<WsMessage>>,
+    ws_delta_pct: Decimal,
+    risk_limits: SharedRiskLimits,
+    hedge: HedgeConfig,
+    price_cache: PriceCache,
 ) {
     let mut ticker = interval(Duration::from_secs(interval_secs));
+    // Last price pushed to dashboard clients per position
+    let mut last_pushed: HashMap<Uuid, Decimal> = HashMap::new();
 
     loop {
         ticker.tick().await;
@@ -44,27 +65,22 @@ pub async fn run_position_monitor(
             }
         };
 
+        last_pushed.retain(|id, _| positions.iter().any(|p| p.id == *id));
+
         if positions.is_empty() {
             tracing::debug!("Position monitor: no open positions");
             continue;
         }
 
-        // Read runtime config for trailing stop and time exit
-        let mut trailing_stop_pct = Decimal::new(10, 0); // default 10%
-        let mut max_hold_days: i64 = 7; // default 7 days
-        if let Ok(entries) = config_repo::get_all_config(&pool).await {
-            for entry in &entries {
-                match entry.key.as_str() {
-                    "trailing_stop_pct" => {
-                        if let Ok(v) = entry.value.parse() { trailing_stop_pct = v; }
-                    }
-                    "max_position_hold_days" => {
-                        if let Ok(v) = entry.value.parse() { max_hold_days = v; }
-                    }
-                    _ => {}
-                }
-            }
-        }
+        // Read live risk limits for trailing stop and time exits
+        let (trailing_stop_pct, max_hold_days, exit_hours_before_end) = {
+            let limits = risk_limits.read().await;
+            (
+                limits.trailing_stop_pct,
+                limits.max_position_hold_days,
+                limits.exit_hours_before_resolution,
+            )
+        };
 
         for pos in &positions {
             // Skip positions that already have an exit order in flight
@@ -76,30 +92,39 @@ pub async fn run_position_monitor(
                 continue;
             }
 
-            // Fetch current best price from orderbook
-            let current_price = match clob_client.get_order_book(&pos.token_id).await {
-                Ok(book) => {
-                    // For a position we hold, the exit price is the best (highest) bid.
-                    // CLOB API returns bids in ascending order, so use .last() or max.
-                    match book.bids.iter().max_by_key(|l| l.price) {
-                        Some(level) => level.price,
-                        None => {
-                            tracing::debug!(
-                                token_id = %pos.token_id,
-                                "No bids in orderbook — skipping price update"
-                            );
-                            continue;
+            // Current exit price: cached WS quote, else the orderbook
+            let cached_bid = price_cache.best_bid(&pos.token_id).await;
+            if cached_bid.is_some() {
+                counter!("position_price_cache_hits").increment(1);
+            } else {
+                counter!("position_price_cache_misses").increment(1);
+            }
+            let current_price = match cached_bid {
+                Some(bid) => bid,
+                None => match clob_client.get_order_book(&pos.token_id).await {
+                    Ok(book) => {
+                        // For a position we hold, the exit price is the best (highest) bid.
+                        // CLOB API returns bids in ascending order, so use .last() or max.
+                        match book.bids.iter().max_by_key(|l| l.price) {
+                            Some(level) => level.price,
+                            None => {
+                                tracing::debug!(
+                                    token_id = %pos.token_id,
+                                    "No bids in orderbook — skipping price update"
+                                );
+                                continue;
+                            }
                         }
                     }
-                }
-                Err(e) => {
-                    tracing::warn!(
-                        error = %e,
-                        token_id = %pos.token_id,
-                        "Failed to fetch orderbook for position"
-                    );
-                    continue;
-                }
+                    Err(e) => {
+                        tracing::warn!(
+                            error = %e,
+                            token_id = %pos.token_id,
+                            "Failed to fetch orderbook for position"
+                        );
+                        continue;
+                    }
+                },
             };
 
             // Compute unrealized PnL and update price + pnl in DB
@@ -110,6 +135,24 @@ pub async fn run_position_monitor(
                 tracing::warn!(error = %e, "Failed to update position price/pnl");
             }
 
+            // Trails the peak price, which the price update above just raised
+            let trailing_level = pos.trailing_stop_level(current_price, trailing_stop_pct);
+
+            // Push live price/PnL to dashboard clients on significant moves only
+            if let Some(ref tx) = ws_tx {
+                if is_significant_move(last_pushed.get(&pos.id).copied(), current_price, ws_delta_pct) {
+                    let mut update = pos.clone();
+                    update.current_price = Some(current_price);
+                    update.unrealized_pnl = Some(unrealized_pnl);
+                    update.peak_price = Some(
+                        pos.peak_price.unwrap_or(pos.avg_entry_price).max(current_price),
+                    );
+                    // Err only means no dashboard is connected right now
+                    let _ = tx.send(WsMessage::PositionUpdate(update));
+                    last_pushed.insert(pos.id, current_price);
+                }
+            }
+
             // Calculate PnL percentage
             if pos.avg_entry_price == Decimal::ZERO {
                 continue;
@@ -117,44 +160,74 @@ pub async fn run_position_monitor(
             let pnl_pct =
                 (current_price - pos.avg_entry_price) / pos.avg_entry_price * Decimal::from(100);
 
+            // Hedge legs are held to resolution to offset the position they cover
+            if pos.hedge_of.is_some() {
+                continue;
+            }
+
+            if hedge.should_hedge(pos, pnl_pct) {
+                open_hedge(
+                    &pool,
+                    &clob_client,
+                    &price_cache,
+                    trading_client.as_deref(),
+                    dry_run,
+                    &capital_pools,
+                    notifier.as_deref(),
+                    pos,
+                    &hedge,
+                    pnl_pct,
+                )
+                .await;
+                continue;
+            }
+
             let stop_loss = pos.stop_loss_pct.unwrap_or(Decimal::new(1500, 2)); // 15.00
-            let take_profit = pos.take_profit_pct.unwrap_or(Decimal::new(2000, 2)); // 20.00
+            // Held-to-resolution positions ride to the payout instead of taking profit
+            let take_profit = if pos.exit_style() == ExitStyle::HoldToResolution {
+                Decimal::MAX
+            } else {
+                pos.take_profit_pct.unwrap_or(Decimal::new(2000, 2)) // 20.00
+            };
 
-            let exit_reason = if pnl_pct <= -stop_loss {
+            // In trailing mode the trailing stop replaces the fixed stop-loss
+            let exit_reason = if trailing_level.is_some_and(|level| current_price <= level) {
+                Some("trailing_stop")
+            } else if pos.stop_mode() == StopMode::Static && pnl_pct <= -stop_loss {
                 Some("stop_loss")
             } else if pnl_pct >= take_profit {
                 Some("take_profit")
             } else {
-                // Trailing stop: only active when position was AND IS profitable.
-                // If price drops below entry, the fixed SL handles it instead.
-                let peak = pos.peak_price.unwrap_or(pos.avg_entry_price);
-                if peak > pos.avg_entry_price
-                    && current_price > pos.avg_entry_price
-                    && trailing_stop_pct > Decimal::ZERO
-                {
-                    let trailing_level =
-                        peak * (Decimal::ONE - trailing_stop_pct / Decimal::ONE_HUNDRED);
-                    if current_price <= trailing_level {
-                        Some("trailing_stop")
+                None
+            };
+
+            // Once the hedge leg is filled it caps the downside, so loss exits
+            // no longer apply; take-profit and time exits still do
+            let is_hedged = positions.iter().any(|p| p.hedge_of == Some(pos.id));
+            let exit_reason = exit_reason.filter(|r| !is_hedged || *r == "take_profit");
+
+            // Time-based exits: held too long, or market about to end
+            let exit_reason = match exit_reason {
+                Some(r) => Some(r),
+                None => {
+                    let end_date = if exit_hours_before_end > 0 {
+                        market_repo::get_market_end_date(&pool, pos.market_key())
+                            .await
+                            .ok()
+                            .flatten()
                     } else {
                         None
-                    }
-                } else {
-                    None
+                    };
+                    time_exit_reason(
+                        pos.opened_at,
+                        end_date,
+                        Utc::now(),
+                        max_hold_days,
+                        exit_hours_before_end,
+                    )
                 }
             };
 
-            // Time-based exit: close if held too long
-            let exit_reason = exit_reason.or_else(|| {
-                if let Some(opened) = pos.opened_at {
-                    let hold_days = (Utc::now() - opened).num_days();
-                    if max_hold_days > 0 && hold_days >= max_hold_days {
-                        return Some("time_exit");
-                    }
-                }
-                None
-            });
-
             let Some(reason) = exit_reason else {
                 tracing::debug!(
                     token_id = %pos.token_id,
@@ -177,9 +250,24 @@ pub async fn run_position_monitor(
 
             // Execute sell order
             if !dry_run {
+                // Partial exits still resting on the book already sell part of it
+                let exit_size = match order_repo::get_pending_exit_size(&pool, &pos.token_id, &pos.sleeve).await {
+                    Ok(pending) => pos.size - pending,
+                    Err(e) => {
+                        tracing::error!(error = %e, token_id = %pos.token_id, "Failed to load pending exits");
+                        continue;
+                    }
+                };
+                if exit_size <= Decimal::ZERO {
+                    tracing::debug!(
+                        token_id = %pos.token_id,
+                        "Position already fully covered by pending exit orders"
+                    );
+                    continue;
+                }
                 if let Some(ref tc) = trading_client {
                     match tc
-                        .place_limit_order(&pos.token_id, "SELL", pos.size, current_price)
+                        .place_limit_order(&pos.token_id, "SELL", exit_size, current_price)
                         .await
                     {
                         Ok(resp) => {
@@ -198,9 +286,12 @@ pub async fn run_position_monitor(
                                     &pos.market_id,
                                     &pos.token_id,
                                     "SELL",
-                                    pos.size,
+                                    exit_size,
                                     current_price,
                                     "exit",
+                                    &pos.sleeve,
+                                    pos.condition_id.as_deref(),
+                                    None,
                                 )
                                 .await
                                 {
@@ -272,10 +363,8 @@ pub async fn run_position_monitor(
                 }
 
                 // Return capital to the pool (entry cost + realized PnL)
-                if let Some(ref cp) = capital_pool {
-                    let returned = pos.avg_entry_price * pos.size + realized_pnl;
-                    cp.return_capital(returned).await;
-                }
+                let returned = pos.avg_entry_price * pos.size + realized_pnl;
+                capital_pools.get_by_label(&pos.sleeve).return_capital(returned).await;
 
                 tracing::info!(
                     position_id = %pos.id,
@@ -286,7 +375,7 @@ pub async fn run_position_monitor(
 
                 // Notify
                 if let Some(ref n) = notifier {
-                    let market_question = market_repo::get_market_question(&pool, &pos.market_id)
+                    let market_question = market_repo::get_market_question(&pool, pos.market_key())
                         .await
                         .ok()
                         .flatten();
@@ -299,9 +388,298 @@ pub async fn run_position_monitor(
                         realized_pnl,
                         pnl_pct,
                     );
-                    n.send(&msg).await;
+                    n.notify(crate::services::notifier::Severity::Info, &msg).await;
                 }
             }
         }
     }
 }
+
+/// Buy the complementary outcome token for `hedge.ratio_pct` of a losing
+/// position, paid for from the position's sleeve. In dry-run the hedge leg is
+/// opened immediately; live, the fill poller opens it and links it to `pos`
+/// once the order fills. The position is only marked hedged once the hedge
+/// is recorded, and is left unhedged if its sleeve already holds the
+/// complementary token.
+#[allow(clippy::too_many_arguments)]
+async fn open_hedge(
+    pool: &PgPool,
+    clob_client: &ClobClient,
+    price_cache: &PriceCache,
+    trading_client: Option<&TradingClient>,
+    dry_run: bool,
+    capital_pools: &SleevePools,
+    notifier: Option<&Notifier>,
+    pos: &Position,
+    hedge: &HedgeConfig,
+    pnl_pct: Decimal,
+) {
+    let info = market_repo::get_market_info(pool, pos.market_key()).await.ok().flatten();
+    let Some((_, question, Some(token_ids), outcomes)) = info else {
+        tracing::warn!(token_id = %pos.token_id, "Hedge: market tokens unknown — cannot hedge");
+        return;
+    };
+    let Some((hedge_token, hedge_outcome)) =
+        hedging::complementary_token(&token_ids, outcomes.as_deref(), &pos.token_id)
+    else {
+        tracing::warn!(token_id = %pos.token_id, "Hedge: no complementary outcome token");
+        return;
+    };
+    match hedging::complementary_token_held(pool, pos, &hedge_token).await {
+        Ok(false) => {}
+        Ok(true) => {
+            tracing::debug!(
+                token_id = %pos.token_id,
+                hedge_token_id = %hedge_token,
+                "Hedge: sleeve already holds the complementary token — not hedging"
+            );
+            return;
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, token_id = %pos.token_id, "Hedge: failed to check complementary holdings");
+            return;
+        }
+    }
+
+    // Buying the opposite outcome costs its best ask
+    let price = match price_cache.best_ask(&hedge_token).await {
+        Some(ask) => ask,
+        None => match clob_client.get_order_book(&hedge_token).await {
+            Ok(book) => match book.asks.iter().min_by_key(|l| l.price) {
+                Some(level) => level.price,
+                None => {
+                    tracing::debug!(token_id = %hedge_token, "Hedge: no asks for complementary token");
+                    return;
+                }
+            },
+            Err(e) => {
+                tracing::warn!(error = %e, token_id = %hedge_token, "Hedge: failed to fetch orderbook");
+                return;
+            }
+        },
+    };
+
+    let size = hedge.hedge_size(pos);
+    if size <= Decimal::ZERO {
+        return;
+    }
+
+    tracing::info!(
+        token_id = %pos.token_id,
+        hedge_token_id = %hedge_token,
+        pnl_pct = %pnl_pct,
+        size = %size,
+        price = %price,
+        "Loss past hedge threshold — buying complementary outcome"
+    );
+
+    if !dry_run {
+        let Some(tc) = trading_client else {
+            tracing::warn!(token_id = %pos.token_id, "No trading client — cannot hedge position");
+            return;
+        };
+
+        // Record the order first: its id keys the capital reservation
+        let order = match order_repo::insert_order(
+            pool,
+            Uuid::nil(),
+            &pos.market_id,
+            &hedge_token,
+            "BUY",
+            size,
+            price,
+            HEDGE_STRATEGY,
+            &pos.sleeve,
+            pos.condition_id.as_deref(),
+            None,
+        )
+        .await
+        {
+            Ok(order) => order,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to record hedge order in DB");
+                return;
+            }
+        };
+        if !reserve_hedge_order(capital_pools, &order).await {
+            tracing::warn!(token_id = %pos.token_id, "Hedge: not enough capital in sleeve — not hedging");
+            if let Err(e) = order_repo::fail_order(pool, order.id, "insufficient capital in sleeve").await {
+                tracing::error!(error = %e, "Failed to mark hedge order as failed");
+            }
+            return;
+        }
+
+        let placed = match tc.place_limit_order(&hedge_token, "BUY", size, price).await {
+            Ok(resp) if resp.success => Ok(resp),
+            Ok(resp) => Err(format!("rejected: {}", resp.error_msg.unwrap_or_default())),
+            Err(e) => Err(e.to_string()),
+        };
+        let resp = match placed {
+            Ok(resp) => resp,
+            Err(reason) => {
+                tracing::error!(token_id = %hedge_token, error = %reason, "Failed to place hedge order");
+                if let Err(e) = order_repo::fail_order(pool, order.id, &reason).await {
+                    tracing::error!(error = %e, "Failed to mark hedge order as failed");
+                }
+                capital_pools.get_by_label(&order.sleeve).release(&order.id).await;
+                return;
+            }
+        };
+        if let Err(e) = order_repo::mark_order_submitted(pool, order.id, &resp.order_id).await {
+            tracing::error!(error = %e, "Failed to mark hedge order as submitted");
+        }
+    } else {
+        // Dry-run: pay for the hedge leg from the sleeve and open it right away
+        let reservation = Uuid::new_v4();
+        if !capital_pools.get_by_label(&pos.sleeve).reserve(reservation, size * price).await {
+            return;
+        }
+
+        let leg = match position_repo::upsert_position(
+            pool,
+            &pos.market_id,
+            &hedge_token,
+            &hedge_outcome,
+            size,
+            price,
+            &pos.sleeve,
+            pos.condition_id.as_deref(),
+            None,
+            None,
+        )
+        .await
+        {
+            Ok(leg) => leg,
+            Err(e) => {
+                tracing::error!(error = %e, "Failed to open hedge position");
+                capital_pools.get_by_label(&pos.sleeve).release(&reservation).await;
+                return;
+            }
+        };
+        capital_pools.get_by_label(&pos.sleeve).confirm(&reservation).await;
+        if let Err(e) = position_repo::set_hedge_of(pool, leg.id, pos.id).await {
+            tracing::error!(error = %e, "Failed to link hedge position");
+        }
+
+        tracing::info!(
+            position_id = %pos.id,
+            hedge_position_id = %leg.id,
+            "[DRY-RUN] Hedge position opened"
+        );
+    }
+
+    // Hedge once: a failed or cancelled hedge order leaves the regular exits in charge
+    if let Err(e) = position_repo::mark_position_hedged(pool, pos.id).await {
+        tracing::error!(error = %e, "Failed to mark position as hedged");
+    }
+
+    if let Some(n) = notifier {
+        let msg = crate::services::notifier::format_position_hedge(
+            question.as_deref(),
+            &pos.market_id,
+            &hedge_outcome,
+            size,
+            price,
+            pnl_pct,
+        );
+        n.notify(crate::services::notifier::Severity::Warning, &msg).await;
+    }
+}
+
+/// Reserve the cost of a live hedge order in its sleeve, keyed by the order
+/// id so the fill poller confirms or releases it once the order settles.
+async fn reserve_hedge_order(capital_pools: &SleevePools, order: &CopyOrder) -> bool {
+    capital_pools
+        .get_by_label(&order.sleeve)
+        .reserve(order.id, order.size * order.target_price)
+        .await
+}
+
+/// True if the price moved at least `threshold_pct` percent since the last push
+/// (or nothing has been pushed yet for this position).
+fn is_significant_move(last: Option<Decimal>, current: Decimal, threshold_pct: Decimal) -> bool {
+    match last {
+        None => true,
+        Some(prev) if prev.is_zero() => !current.is_zero(),
+        Some(prev) => ((current - prev) / prev * Decimal::ONE_HUNDRED).abs() >= threshold_pct,
+    }
+}
+
+/// Exit reason for time-based rules, regardless of PnL: `time_exit` once the
+/// position has been held `max_hold_days`, `resolution_exit` once the market
+/// ends within `exit_hours_before_end`. A zero limit disables its rule.
+fn time_exit_reason(
+    opened_at: Option<DateTime<Utc>>,
+    end_date: Option<DateTime<Utc>>,
+    now: DateTime<Utc>,
+    max_hold_days: i64,
+    exit_hours_before_end: i64,
+) -> Option<&'static str> {
+    if let Some(opened) = opened_at {
+        if max_hold_days > 0 && (now - opened).num_days() >= max_hold_days {
+            return Some("time_exit");
+        }
+    }
+    if let Some(end) = end_date {
+        if exit_hours_before_end > 0 && end - now <= chrono::Duration::hours(exit_hours_before_end) {
+            return Some("resolution_exit");
+        }
+    }
+    None
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use crate::execution::sleeves::SleeveAllocation;
+    use crate::services::order_fill_poller::reservation_key;
+
+    #[test]
+    fn test_time_exit_after_max_hold() {
+        let now = Utc::now();
+        let opened = Some(now - Duration::days(8));
+        assert_eq!(time_exit_reason(opened, None, now, 7, 0), Some("time_exit"));
+        assert_eq!(time_exit_reason(opened, None, now, 0, 0), None);
+        assert_eq!(time_exit_reason(Some(now - Duration::days(2)), None, now, 7, 0), None);
+    }
+
+    #[test]
+    fn test_resolution_exit_near_end_date() {
+        let now = Utc::now();
+        let opened = Some(now - Duration::hours(1));
+        let end = Some(now + Duration::hours(5));
+        assert_eq!(time_exit_reason(opened, end, now, 7, 6), Some("resolution_exit"));
+        assert_eq!(time_exit_reason(opened, end, now, 7, 4), None);
+        // Disabled
+        assert_eq!(time_exit_reason(opened, end, now, 7, 0), None);
+        // Unknown end date
+        assert_eq!(time_exit_reason(opened, None, now, 7, 6), None);
+    }
+
+    #[tokio::test]
+    async fn test_live_hedge_reserves_capital_until_fill() {
+        let pools = SleevePools::new(Decimal::from(1000), SleeveAllocation::parse("single_whale:1"));
+        let order = CopyOrder {
+            size: Decimal::from(100),
+            target_price: Decimal::new(40, 2),
+            strategy: HEDGE_STRATEGY.into(),
+            ..CopyOrder::test_fixture()
+        };
+        let sleeve = pools.get_by_label(&order.sleeve);
+
+        assert!(reserve_hedge_order(&pools, &order).await);
+        assert_eq!(sleeve.available().await, Decimal::from(960));
+        assert_eq!(sleeve.total_balance().await, Decimal::from(1000));
+
+        // The fill poller confirms the reservation under the hedge order's id
+        let key = reservation_key(&order).unwrap();
+        sleeve.confirm(&key).await;
+        assert_eq!(sleeve.total_balance().await, Decimal::from(960));
+        assert_eq!(sleeve.available().await, Decimal::from(960));
+    }
+}
@@ -0,0 +1,420 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::sync::Arc;
+
+use metrics::gauge;
+use rust_decimal::Decimal;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+
+use crate::db::position_repo;
+use crate::execution::sleeves::SleevePools;
+use crate::models::{Position, Sleeve};
+use crate::polymarket::data_client::UserPosition;
+use crate::polymarket::DataClient;
+use crate::services::notifier::Notifier;
+
+/// Account holdings below this many tokens are dust and ignored.
+const DUST_SIZE: Decimal = Decimal::from_parts(1, 0, 0, false, 2); // 0.01
+/// Relative size difference tolerated before a position counts as drifted
+/// (fees can be taken in shares, so sizes rarely match exactly).
+const SIZE_TOLERANCE_PCT: Decimal = Decimal::ONE;
+
+/// How a token's size in the `positions` table disagrees with the account.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum DriftKind {
+    /// The account holds the token but we have no open position (manual
+    /// trade outside the bot, or a fill we never recorded).
+    MissingInDb,
+    /// We track an open position the account no longer holds.
+    MissingOnAccount,
+    /// Both sides hold the token with different sizes.
+    SizeMismatch,
+}
+
+impl fmt::Display for DriftKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            DriftKind::MissingInDb => "missing_in_db",
+            DriftKind::MissingOnAccount => "missing_on_account",
+            DriftKind::SizeMismatch => "size_mismatch",
+        })
+    }
+}
+
+/// A single reconciliation difference for one token.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Drift {
+    pub kind: DriftKind,
+    pub token_id: String,
+    pub condition_id: Option<String>,
+    pub db_size: Decimal,
+    pub account_size: Decimal,
+    /// Account's average entry price, when it holds the token.
+    pub account_avg_price: Option<Decimal>,
+    pub outcome: Option<String>,
+}
+
+impl Drift {
+    fn key(&self) -> (String, DriftKind) {
+        (self.token_id.clone(), self.kind)
+    }
+}
+
+/// Settings for the periodic reconciler.
+#[derive(Debug, Clone)]
+pub struct ReconcilerConfig {
+    pub interval_secs: u64,
+    /// Correct the `positions` table to match the account for drift seen on
+    /// two consecutive runs. When false, drift is only reported.
+    pub auto_correct: bool,
+}
+
+/// Compare our open positions with the account's holdings. Sleeves holding
+/// the same token are summed, since the account holds one balance per token.
+///
+/// Tokens with an exit in flight (`exiting`) are skipped, and resolved
+/// account holdings awaiting redemption are ignored.
+pub fn reconcile(db_positions: &[Position], account: &[UserPosition]) -> Vec<Drift> {
+    let mut held: HashMap<&str, &UserPosition> = HashMap::new();
+    for p in account {
+        let Some(token) = p.token_id.as_deref() else {
+            continue;
+        };
+        if p.redeemable == Some(true) || p.size.unwrap_or_default() < DUST_SIZE {
+            continue;
+        }
+        held.insert(token, p);
+    }
+
+    let mut drifts = Vec::new();
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut exiting: HashSet<&str> = HashSet::new();
+    // Open size per token, summed over sleeves, with the first position seen
+    let mut open: Vec<(&Position, Decimal)> = Vec::new();
+
+    for pos in db_positions {
+        seen.insert(&pos.token_id);
+        if pos.status.as_deref() != Some("open") {
+            exiting.insert(&pos.token_id);
+            continue;
+        }
+        match open.iter_mut().find(|(p, _)| p.token_id == pos.token_id) {
+            Some((_, size)) => *size += pos.size,
+            None => open.push((pos, pos.size)),
+        }
+    }
+
+    for (pos, db_size) in open {
+        if exiting.contains(pos.token_id.as_str()) {
+            continue;
+        }
+
+        match held.get(pos.token_id.as_str()) {
+            None => drifts.push(Drift {
+                kind: DriftKind::MissingOnAccount,
+                token_id: pos.token_id.clone(),
+                condition_id: pos.condition_id.clone(),
+                db_size,
+                account_size: Decimal::ZERO,
+                account_avg_price: None,
+                outcome: Some(pos.outcome.clone()),
+            }),
+            Some(acct) => {
+                let account_size = acct.size.unwrap_or_default();
+                let tolerance =
+                    (db_size * SIZE_TOLERANCE_PCT / Decimal::ONE_HUNDRED).max(DUST_SIZE);
+                if (account_size - db_size).abs() > tolerance {
+                    drifts.push(Drift {
+                        kind: DriftKind::SizeMismatch,
+                        token_id: pos.token_id.clone(),
+                        condition_id: pos.condition_id.clone(),
+                        db_size,
+                        account_size,
+                        account_avg_price: acct.avg_price,
+                        outcome: Some(pos.outcome.clone()),
+                    });
+                }
+            }
+        }
+    }
+
+    for (token, acct) in &held {
+        if seen.contains(token) {
+            continue;
+        }
+        drifts.push(Drift {
+            kind: DriftKind::MissingInDb,
+            token_id: token.to_string(),
+            condition_id: acct.condition_id.clone(),
+            db_size: Decimal::ZERO,
+            account_size: acct.size.unwrap_or_default(),
+            account_avg_price: acct.avg_price,
+            outcome: acct.outcome.clone(),
+        });
+    }
+
+    drifts.sort_by(|a, b| a.token_id.cmp(&b.token_id));
+    drifts
+}
+
+/// Periodically reconcile the `positions` table against the wallet's actual
+/// Polymarket holdings, flagging drift and optionally correcting it.
+/// Live only: dry-run positions are never held on the account.
+pub async fn run_position_reconciler(
+    pool: PgPool,
+    data_client: DataClient,
+    wallet_address: String,
+    config: ReconcilerConfig,
+    capital_pools: SleevePools,
+    notifier: Option<Arc<Notifier>>,
+) {
+    let mut ticker = interval(Duration::from_secs(config.interval_secs));
+    // Drift seen on the previous run; only persistent drift is corrected or
+    // alerted, so fills racing the poller don't trigger false positives
+    let mut previous: HashSet<(String, DriftKind)> = HashSet::new();
+    let mut alerted: HashSet<(String, DriftKind)> = HashSet::new();
+
+    tracing::info!(
+        interval_secs = config.interval_secs,
+        auto_correct = config.auto_correct,
+        "Position reconciler started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        let account = match data_client.get_user_positions(&wallet_address).await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::warn!(error = %e, "Reconciler: failed to fetch account positions");
+                continue;
+            }
+        };
+        let db_positions = match position_repo::get_unclosed_positions(&pool).await {
+            Ok(p) => p,
+            Err(e) => {
+                tracing::error!(error = %e, "Reconciler: failed to fetch open positions");
+                continue;
+            }
+        };
+
+        let drifts = reconcile(&db_positions, &account);
+        gauge!("position_drift_count").set(drifts.len() as f64);
+
+        let current: HashSet<_> = drifts.iter().map(Drift::key).collect();
+        alerted.retain(|k| current.contains(k));
+
+        if drifts.is_empty() {
+            tracing::debug!(
+                positions = db_positions.len(),
+                "Reconciler: positions in sync"
+            );
+            previous = current;
+            continue;
+        }
+
+        for drift in drifts.iter().filter(|d| previous.contains(&d.key())) {
+            tracing::warn!(
+                kind = %drift.kind,
+                token_id = %drift.token_id,
+                db_size = %drift.db_size,
+                account_size = %drift.account_size,
+                "Reconciler: position drift"
+            );
+
+            if config.auto_correct {
+                if let Err(e) = correct(&pool, &capital_pools, &db_positions, drift).await {
+                    tracing::error!(error = %e, token_id = %drift.token_id, "Reconciler: correction failed");
+                }
+            }
+        }
+
+        let new_alerts: Vec<&Drift> = drifts
+            .iter()
+            .filter(|d| previous.contains(&d.key()) && !alerted.contains(&d.key()))
+            .collect();
+        if !new_alerts.is_empty() {
+            if let Some(ref n) = notifier {
+                let msg = crate::services::notifier::format_position_drift(
+                    &new_alerts,
+                    config.auto_correct,
+                );
+                n.notify(crate::services::notifier::Severity::Warning, &msg).await;
+            }
+            alerted.extend(new_alerts.iter().map(|d| d.key()));
+        }
+
+        previous = current;
+    }
+}
+
+/// Bring the `positions` table in line with the account for one drift.
+/// A size mismatch on a token several sleeves hold is only reported: the
+/// difference can't be attributed to one of them. A holding taken over from
+/// the account is paid for out of the whale sleeve, or only reported if the
+/// sleeve can't cover it.
+async fn correct(
+    pool: &PgPool,
+    capital_pools: &SleevePools,
+    db_positions: &[Position],
+    drift: &Drift,
+) -> anyhow::Result<()> {
+    let positions: Vec<&Position> = db_positions
+        .iter()
+        .filter(|p| p.token_id == drift.token_id && p.status.as_deref() == Some("open"))
+        .collect();
+
+    match (drift.kind, positions.as_slice()) {
+        (DriftKind::MissingInDb, _) => {
+            let sleeve = Sleeve::SingleWhale;
+            let capital = capital_pools.get(sleeve);
+            let cost = drift.account_size * drift.account_avg_price.unwrap_or_default();
+            let reservation = uuid::Uuid::new_v4();
+            if !capital.reserve(reservation, cost).await {
+                tracing::warn!(
+                    token_id = %drift.token_id,
+                    cost = %cost,
+                    "Reconciler: sleeve can't cover the account holding — reported only"
+                );
+                return Ok(());
+            }
+
+            let condition_id = drift.condition_id.as_deref().unwrap_or(&drift.token_id);
+            let pos = match position_repo::upsert_position(
+                pool,
+                condition_id,
+                &drift.token_id,
+                drift.outcome.as_deref().unwrap_or("Yes"),
+                drift.account_size,
+                drift.account_avg_price.unwrap_or_default(),
+                sleeve.as_str(),
+                drift.condition_id.as_deref(),
+                None,
+                None,
+            )
+            .await
+            {
+                Ok(pos) => pos,
+                Err(e) => {
+                    capital.release(&reservation).await;
+                    return Err(e);
+                }
+            };
+            capital.confirm(&reservation).await;
+            tracing::info!(position_id = %pos.id, token_id = %drift.token_id, cost = %cost, "Reconciler: position added from account");
+        }
+        (DriftKind::MissingOnAccount, positions) => {
+            // Exit price unknown — close at no additional realized PnL
+            for pos in positions {
+                position_repo::close_position_with_reason(pool, pos.id, Decimal::ZERO, "reconciled")
+                    .await?;
+                tracing::info!(position_id = %pos.id, "Reconciler: position closed (not held on account)");
+            }
+        }
+        (DriftKind::SizeMismatch, [pos]) => {
+            position_repo::set_position_size(pool, pos.id, drift.account_size).await?;
+            tracing::info!(
+                position_id = %pos.id,
+                from = %drift.db_size,
+                to = %drift.account_size,
+                "Reconciler: position size corrected"
+            );
+        }
+        (DriftKind::SizeMismatch, _) => {
+            tracing::warn!(
+                token_id = %drift.token_id,
+                sleeves = positions.len(),
+                "Reconciler: size drift on a token held by several sleeves — not corrected"
+            );
+        }
+    }
+
+    Ok(())
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn db_position(token: &str, size: i64, status: &str) -> Position {
+        sleeve_position(token, size, status, "single_whale")
+    }
+
+    fn sleeve_position(token: &str, size: i64, status: &str, sleeve: &str) -> Position {
+        Position {
+            token_id: token.into(),
+            size: Decimal::from(size),
+            status: Some(status.into()),
+            sleeve: sleeve.into(),
+            ..Position::test_fixture()
+        }
+    }
+
+    fn held(token: &str, size: &str) -> UserPosition {
+        serde_json::from_value(serde_json::json!({
+            "asset": token,
+            "conditionId": "0xc",
+            "size": size,
+            "avgPrice": "0.4",
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_reconcile_detects_each_drift_kind() {
+        let db = vec![
+            db_position("in_sync", 100, "open"),
+            db_position("sold_outside", 50, "open"),
+            db_position("partial", 100, "open"),
+            db_position("exiting", 40, "exiting"),
+        ];
+        let account = vec![
+            held("in_sync", "100.5"),
+            held("partial", "60"),
+            held("manual_buy", "25"),
+            held("dust", "0.001"),
+        ];
+
+        let drifts = reconcile(&db, &account);
+        let kinds: Vec<(&str, DriftKind)> = drifts
+            .iter()
+            .map(|d| (d.token_id.as_str(), d.kind))
+            .collect();
+
+        assert_eq!(
+            kinds,
+            vec![
+                ("manual_buy", DriftKind::MissingInDb),
+                ("partial", DriftKind::SizeMismatch),
+                ("sold_outside", DriftKind::MissingOnAccount),
+            ]
+        );
+        assert_eq!(drifts[1].account_size, Decimal::from(60));
+    }
+
+    #[test]
+    fn test_reconcile_sums_sleeves_holding_a_token() {
+        let db = vec![
+            sleeve_position("shared", 60, "open", "single_whale"),
+            sleeve_position("shared", 40, "open", "basket"),
+        ];
+        assert!(reconcile(&db, &[held("shared", "100")]).is_empty());
+
+        let drifts = reconcile(&db, &[held("shared", "70")]);
+        assert_eq!(drifts.len(), 1);
+        assert_eq!(drifts[0].kind, DriftKind::SizeMismatch);
+        assert_eq!(drifts[0].db_size, Decimal::from(100));
+    }
+
+    #[test]
+    fn test_reconcile_ignores_redeemable_holdings() {
+        let mut resolved = held("resolved", "10");
+        resolved.redeemable = Some(true);
+        assert!(reconcile(&[], &[resolved]).is_empty());
+    }
+}
@@ -0,0 +1,79 @@
+use serde::Serialize;
+use sqlx::PgPool;
+use tokio::time::{interval, Duration};
+use uuid::Uuid;
+
+use crate::db::{trade_repo, whale_repo};
+use crate::intelligence::score_wallet;
+use crate::intelligence::scorer::resolved_profit;
+use crate::models::TradeResult;
+use crate::services::job_trigger::RunRequests;
+
+/// Outcome of one batch rescoring run.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct RescoreSummary {
+    /// Whales with at least one resolved trade.
+    pub whales: usize,
+    /// Whale rows updated.
+    pub rescored: u64,
+    /// Resolved trades scored across all whales.
+    pub trades: usize,
+}
+
+/// Recompute scores for `whale_ids` (every whale when None) from all their
+/// trades in resolved markets, with one query for the trades and outcomes
+/// and one for the update. Whales without resolved trades keep their scores.
+pub async fn rescore_whales(pool: &PgPool, whale_ids: Option<&[Uuid]>) -> anyhow::Result<RescoreSummary> {
+    let trades = trade_repo::get_resolved_trades(pool, whale_ids).await?;
+
+    let mut scores = Vec::new();
+    let mut results: Vec<TradeResult> = Vec::new();
+    let mut summary = RescoreSummary::default();
+    // Rows are grouped by whale, so each whale's trades are one contiguous run
+    for (i, t) in trades.iter().enumerate() {
+        if let Some(profit) = resolved_profit(&t.side, t.price, t.notional, &t.outcome) {
+            results.push(TradeResult {
+                profit,
+                notional: t.notional,
+                traded_at: t.traded_at,
+            });
+        }
+        let last_of_whale = trades.get(i + 1).is_none_or(|next| next.whale_id != t.whale_id);
+        if last_of_whale && !results.is_empty() {
+            summary.trades += results.len();
+            scores.push((t.whale_id, score_wallet(&results)));
+            results.clear();
+        }
+    }
+
+    summary.whales = scores.len();
+    summary.rescored = whale_repo::update_whale_scores_bulk(pool, &scores).await?;
+    Ok(summary)
+}
+
+/// Periodically rescore every whale in bulk, backfilling outcomes that
+/// resolved after the whale's trades were scored inline.
+/// `run_requests` runs a cycle immediately and answers with its summary.
+pub async fn run_rescore_loop(pool: PgPool, interval_secs: u64, mut run_requests: RunRequests<Result<RescoreSummary, String>>) {
+    let mut ticker = interval(Duration::from_secs(interval_secs));
+
+    loop {
+        let reply = tokio::select! {
+            _ = ticker.tick() => None,
+            Some(reply) = run_requests.recv() => Some(reply),
+        };
+        let result = rescore_whales(&pool, None).await;
+        match result {
+            Ok(ref s) => tracing::info!(
+                whales = s.whales,
+                rescored = s.rescored,
+                trades = s.trades,
+                "Batch rescoring complete"
+            ),
+            Err(ref e) => tracing::error!(error = %e, "Batch rescoring failed"),
+        }
+        if let Some(reply) = reply {
+            let _ = reply.send(result.map_err(|e| e.to_string()));
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use std::sync::Arc;
 
 use rust_decimal::Decimal;
+use serde::Serialize;
 use sqlx::PgPool;
 use tokio::time::{interval, sleep, Duration};
 
-use crate::db::{market_repo, position_repo};
+use crate::db::{attribution_repo, market_repo, position_repo};
 use crate::polymarket::DataClient;
+use crate::services::job_trigger::RunRequests;
 use crate::services::notifier::Notifier;
 
 /// Max markets to check per cycle (avoid rate limits).
@@ -14,178 +16,276 @@ const BATCH_SIZE: usize = 50;
 /// Delay between API calls to respect rate limits.
 const API_DELAY: Duration = Duration::from_millis(200);
 
+/// Positions closed within this many days are checked for missing PnL attribution.
+const EXIT_ATTRIBUTION_DAYS: i32 = 7;
+
+/// Outcome of one resolution cycle.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ResolutionSummary {
+    /// Markets looked up this cycle.
+    pub checked: usize,
+    pub resolved: u32,
+    pub still_open: u32,
+    pub failed: u32,
+    /// Unresolved markets left for later cycles.
+    pub remaining: usize,
+    /// Positions closed before resolution (stops, whale exits, manual) whose
+    /// PnL was attributed this cycle.
+    pub exits_attributed: u32,
+}
+
 /// Periodically poll unresolved markets and settle positions when outcomes are known.
+/// `run_requests` runs a cycle immediately and answers with its summary.
 pub async fn run_resolution_poller(
     pool: PgPool,
     data_client: DataClient,
     interval_secs: u64,
     notifier: Option<Arc<Notifier>>,
+    mut run_requests: RunRequests<Result<ResolutionSummary, String>>,
 ) {
     let mut ticker = interval(Duration::from_secs(interval_secs));
 
     loop {
-        ticker.tick().await;
-
-        let unresolved = match market_repo::get_unresolved_markets(&pool).await {
-            Ok(m) => m,
-            Err(e) => {
-                tracing::error!(error = %e, "Failed to fetch unresolved markets");
-                continue;
-            }
+        let reply = tokio::select! {
+            _ = ticker.tick() => None,
+            Some(reply) = run_requests.recv() => Some(reply),
         };
-
-        if unresolved.is_empty() {
-            tracing::info!("Resolution poller: no unresolved markets");
-            continue;
+        let result = run_resolution_cycle(&pool, &data_client, notifier.as_deref()).await;
+        if let Err(ref e) = result {
+            tracing::error!(error = %e, "Resolution cycle failed");
+        }
+        if let Some(reply) = reply {
+            let _ = reply.send(result.map_err(|e| e.to_string()));
         }
+    }
+}
 
-        let batch = &unresolved[..unresolved.len().min(BATCH_SIZE)];
-        tracing::info!(
-            total = unresolved.len(),
-            checking = batch.len(),
-            "Resolution poller: checking markets"
-        );
-
-        let mut resolved_count = 0u32;
-        let mut failed_count = 0u32;
-        let mut still_open = 0u32;
-
-        for market_outcome in batch {
-            match data_client.get_market_for_resolution(&market_outcome.market_id).await {
-                Ok(api_market) => {
-                    // Check if market is closed
-                    if api_market.closed != Some(true) {
-                        still_open += 1;
-                        continue;
-                    }
+/// Check one batch of unresolved markets and settle positions in those that resolved.
+pub async fn run_resolution_cycle(
+    pool: &PgPool,
+    data_client: &DataClient,
+    notifier: Option<&Notifier>,
+) -> anyhow::Result<ResolutionSummary> {
+    let exits_attributed = attribute_early_exits(pool).await;
 
-                    // Find winning token
-                    let mut resolved_outcome: Option<&str> = None;
-                    for token in &api_market.tokens {
-                        if token.winner == Some(true) {
-                            let outcome_upper = token.outcome.to_uppercase();
-                            if outcome_upper == "YES" {
-                                resolved_outcome = Some("resolved_yes");
-                            } else if outcome_upper == "NO" {
-                                resolved_outcome = Some("resolved_no");
-                            }
-                            break;
+    let unresolved = market_repo::get_unresolved_markets(pool).await?;
+
+    if unresolved.is_empty() {
+        tracing::info!("Resolution poller: no unresolved markets");
+        return Ok(ResolutionSummary {
+            exits_attributed,
+            ..Default::default()
+        });
+    }
+
+    let batch = &unresolved[..unresolved.len().min(BATCH_SIZE)];
+    tracing::info!(
+        total = unresolved.len(),
+        checking = batch.len(),
+        "Resolution poller: checking markets"
+    );
+
+    let mut resolved_count = 0u32;
+    let mut failed_count = 0u32;
+    let mut still_open = 0u32;
+
+    for market_outcome in batch {
+        match data_client.get_market_for_resolution(&market_outcome.market_id).await {
+            Ok(api_market) => {
+                // Check if market is closed
+                if api_market.closed != Some(true) {
+                    still_open += 1;
+                    continue;
+                }
+
+                // Find winning token
+                let mut resolved_outcome: Option<&str> = None;
+                for token in &api_market.tokens {
+                    if token.winner == Some(true) {
+                        let outcome_upper = token.outcome.to_uppercase();
+                        if outcome_upper == "YES" {
+                            resolved_outcome = Some("resolved_yes");
+                        } else if outcome_upper == "NO" {
+                            resolved_outcome = Some("resolved_no");
                         }
+                        break;
                     }
+                }
 
-                    let Some(outcome_str) = resolved_outcome else {
-                        // Market closed but no winner declared yet
-                        still_open += 1;
-                        continue;
-                    };
+                let Some(outcome_str) = resolved_outcome else {
+                    // Market closed but no winner declared yet
+                    still_open += 1;
+                    continue;
+                };
 
-                    tracing::info!(
-                        market_id = %market_outcome.market_id,
-                        outcome = outcome_str,
-                        question = %api_market.question,
-                        "Market resolved"
-                    );
+                tracing::info!(
+                    market_id = %market_outcome.market_id,
+                    outcome = outcome_str,
+                    question = %api_market.question,
+                    "Market resolved"
+                );
 
-                    // Update market_outcomes table
-                    if let Err(e) = market_repo::resolve_market(&pool, &market_outcome.market_id, outcome_str).await {
-                        tracing::error!(error = %e, market_id = %market_outcome.market_id, "Failed to resolve market");
-                        continue;
-                    }
+                // Update market_outcomes table
+                if let Err(e) = market_repo::resolve_market(pool, &market_outcome.market_id, outcome_str).await {
+                    tracing::error!(error = %e, market_id = %market_outcome.market_id, "Failed to resolve market");
+                    continue;
+                }
 
-                    resolved_count += 1;
+                resolved_count += 1;
 
-                    // Settle positions for this market
-                    let positions = match position_repo::get_positions_for_market(&pool, &market_outcome.market_id).await {
-                        Ok(p) => p,
-                        Err(e) => {
-                            tracing::error!(error = %e, "Failed to get positions for market");
-                            continue;
-                        }
-                    };
+                // Settle positions for this market
+                let market_key = market_outcome
+                    .condition_id
+                    .as_deref()
+                    .unwrap_or(&market_outcome.market_id);
+                let positions = match position_repo::get_positions_for_market(pool, market_key).await {
+                    Ok(p) => p,
+                    Err(e) => {
+                        tracing::error!(error = %e, "Failed to get positions for market");
+                        continue;
+                    }
+                };
 
-                    for pos in &positions {
-                        let pnl = if outcome_str == "resolved_yes" {
-                            if pos.outcome == "Yes" {
-                                pos.size * (Decimal::ONE - pos.avg_entry_price)
-                            } else {
-                                -(pos.size * pos.avg_entry_price)
-                            }
+                for pos in &positions {
+                    let pnl = if outcome_str == "resolved_yes" {
+                        if pos.outcome == "Yes" {
+                            pos.size * (Decimal::ONE - pos.avg_entry_price)
                         } else {
-                            if pos.outcome == "No" {
-                                pos.size * (Decimal::ONE - pos.avg_entry_price)
-                            } else {
-                                -(pos.size * pos.avg_entry_price)
-                            }
-                        };
-
-                        if let Err(e) = position_repo::close_position(&pool, pos.id, pnl).await {
-                            tracing::error!(
-                                error = %e,
-                                position_id = %pos.id,
-                                "Failed to close position"
-                            );
+                            -(pos.size * pos.avg_entry_price)
+                        }
+                    } else {
+                        if pos.outcome == "No" {
+                            pos.size * (Decimal::ONE - pos.avg_entry_price)
                         } else {
-                            tracing::info!(
-                                position_id = %pos.id,
-                                market_id = %market_outcome.market_id,
-                                pnl = %pnl,
-                                "Position settled"
-                            );
+                            -(pos.size * pos.avg_entry_price)
                         }
+                    };
+
+                    if let Err(e) = position_repo::close_position(pool, pos.id, pnl).await {
+                        tracing::error!(
+                            error = %e,
+                            position_id = %pos.id,
+                            "Failed to close position"
+                        );
+                    } else {
+                        tracing::info!(
+                            position_id = %pos.id,
+                            market_id = %market_outcome.market_id,
+                            pnl = %pnl,
+                            "Position settled"
+                        );
+                        attribute_settled_pnl(pool, pos.id).await;
                     }
+                }
 
-                    // Notify settlement
-                    if let Some(ref n) = notifier {
-                        let total_pnl: Decimal = positions.iter().map(|p| {
-                            if outcome_str == "resolved_yes" {
-                                if p.outcome == "Yes" {
-                                    p.size * (Decimal::ONE - p.avg_entry_price)
-                                } else {
-                                    -(p.size * p.avg_entry_price)
-                                }
-                            } else if p.outcome == "No" {
+                // Notify settlement
+                if let Some(n) = notifier {
+                    let total_pnl: Decimal = positions.iter().map(|p| {
+                        if outcome_str == "resolved_yes" {
+                            if p.outcome == "Yes" {
                                 p.size * (Decimal::ONE - p.avg_entry_price)
                             } else {
                                 -(p.size * p.avg_entry_price)
                             }
-                        }).sum();
-
-                        if !positions.is_empty() {
-                            let market_question = market_repo::get_market_question(&pool, &market_outcome.market_id)
-                                .await
-                                .ok()
-                                .flatten();
-                            let msg = crate::services::notifier::format_market_settled(
-                                market_question.as_deref(),
-                                &market_outcome.market_id,
-                                outcome_str,
-                                positions.len(),
-                                total_pnl,
-                            );
-                            n.send(&msg).await;
+                        } else if p.outcome == "No" {
+                            p.size * (Decimal::ONE - p.avg_entry_price)
+                        } else {
+                            -(p.size * p.avg_entry_price)
                         }
+                    }).sum();
+
+                    if !positions.is_empty() {
+                        let market_question = market_repo::get_market_question(pool, &market_outcome.market_id)
+                            .await
+                            .ok()
+                            .flatten();
+                        let msg = crate::services::notifier::format_market_settled(
+                            market_question.as_deref(),
+                            &market_outcome.market_id,
+                            outcome_str,
+                            positions.len(),
+                            total_pnl,
+                        );
+                        n.notify(crate::services::notifier::Severity::Info, &msg).await;
                     }
                 }
-                Err(e) => {
-                    tracing::warn!(
-                        error = %e,
-                        market_id = %market_outcome.market_id,
-                        "Resolution: market lookup failed"
-                    );
-                    failed_count += 1;
-                }
             }
+            Err(e) => {
+                tracing::warn!(
+                    error = %e,
+                    market_id = %market_outcome.market_id,
+                    "Resolution: market lookup failed"
+                );
+                failed_count += 1;
+            }
+        }
+
+        // Rate limit: small delay between API calls
+        sleep(API_DELAY).await;
+    }
+
+    tracing::info!(
+        resolved = resolved_count,
+        still_open = still_open,
+        failed = failed_count,
+        remaining = unresolved.len().saturating_sub(BATCH_SIZE),
+        "Resolution poller cycle complete"
+    );
+
+    Ok(ResolutionSummary {
+        checked: batch.len(),
+        resolved: resolved_count,
+        still_open,
+        failed: failed_count,
+        remaining: unresolved.len().saturating_sub(BATCH_SIZE),
+        exits_attributed,
+    })
+}
+
+/// Attribute positions closed before their market resolved — stop-loss,
+/// take-profit, whale exits, manual closes — so whale and basket performance
+/// covers them too. Returns how many were attributed.
+async fn attribute_early_exits(pool: &PgPool) -> u32 {
+    let positions = match attribution_repo::get_unattributed_closed_positions(pool, EXIT_ATTRIBUTION_DAYS).await {
+        Ok(p) => p,
+        Err(e) => {
+            tracing::warn!(error = %e, "Failed to fetch unattributed closed positions");
+            return 0;
+        }
+    };
 
-            // Rate limit: small delay between API calls
-            sleep(API_DELAY).await;
+    let mut attributed = 0u32;
+    for position_id in positions {
+        match attribution_repo::attribute_position_pnl(pool, position_id).await {
+            Ok(rows) if !rows.is_empty() => attributed += 1,
+            Ok(_) => {}
+            Err(e) => tracing::warn!(error = %e, position_id = %position_id, "Failed to attribute exited position"),
         }
+    }
+    if attributed > 0 {
+        tracing::info!(positions = attributed, "Attributed PnL of exited positions");
+    }
+    attributed
+}
 
-        tracing::info!(
-            resolved = resolved_count,
-            still_open = still_open,
-            failed = failed_count,
-            remaining = unresolved.len().saturating_sub(BATCH_SIZE),
-            "Resolution poller cycle complete"
-        );
+/// Attribute a settled position's PnL back to the whales and baskets that
+/// sourced it. Feeds the per-whale copy performance used by the copy guard.
+async fn attribute_settled_pnl(pool: &PgPool, position_id: uuid::Uuid) {
+    match attribution_repo::attribute_position_pnl(pool, position_id).await {
+        Ok(attributions) => {
+            for a in &attributions {
+                tracing::debug!(
+                    position_id = %position_id,
+                    whale_id = ?a.whale_id,
+                    basket_id = ?a.basket_id,
+                    share = %a.share.round_dp(4),
+                    pnl = %a.realized_pnl.round_dp(4),
+                    "PnL attributed"
+                );
+            }
+        }
+        Err(e) => {
+            tracing::warn!(error = %e, position_id = %position_id, "Failed to attribute settled PnL");
+        }
     }
 }
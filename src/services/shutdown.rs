@@ -0,0 +1,114 @@
+use std::future::Future;
+use std::sync::Arc;
+
+use tokio::sync::watch;
+use tokio::task::JoinHandle;
+use tracing::Instrument;
+
+/// Shared shutdown state. Once `begin` is called the API stops accepting new
+/// dashboard WebSocket connections and open ones flush and close.
+#[derive(Debug, Clone)]
+pub struct Shutdown {
+    tx: Arc<watch::Sender<bool>>,
+}
+
+impl Default for Shutdown {
+    fn default() -> Self {
+        let (tx, _) = watch::channel(false);
+        Self { tx: Arc::new(tx) }
+    }
+}
+
+impl Shutdown {
+    pub fn begin(&self) {
+        self.tx.send_replace(true);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        *self.tx.borrow()
+    }
+
+    /// Resolves once shutdown has begun.
+    pub async fn wait(&self) {
+        let mut rx = self.tx.subscribe();
+        // The sender lives in `self`, so the channel can't close while we wait
+        let _ = rx.wait_for(|draining| *draining).await;
+    }
+}
+
+/// Named handles of the long-running background tasks, so shutdown can abort
+/// and report whatever is still running.
+#[derive(Debug)]
+pub struct TaskRegistry {
+    tasks: Vec<(&'static str, JoinHandle<()>)>,
+    span: tracing::Span,
+}
+
+impl Default for TaskRegistry {
+    fn default() -> Self {
+        Self::with_span(tracing::Span::none())
+    }
+}
+
+impl TaskRegistry {
+    /// Tasks run inside `span`, so their logs carry its fields.
+    pub fn with_span(span: tracing::Span) -> Self {
+        Self { tasks: Vec::new(), span }
+    }
+
+    pub fn spawn<F>(&mut self, name: &'static str, future: F)
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        self.tasks.push((name, tokio::spawn(future.instrument(self.span.clone()))));
+    }
+
+    /// Abort every task still running and return their names; tasks that
+    /// already exited are not reported.
+    pub fn abort_all(self) -> Vec<&'static str> {
+        self.tasks
+            .into_iter()
+            .filter(|(_, handle)| !handle.is_finished())
+            .map(|(name, handle)| {
+                handle.abort();
+                name
+            })
+            .collect()
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn test_shutdown_wait_resolves_on_begin() {
+        let shutdown = Shutdown::default();
+        assert!(!shutdown.is_draining());
+
+        let waiter = tokio::spawn({
+            let shutdown = shutdown.clone();
+            async move { shutdown.wait().await }
+        });
+        shutdown.begin();
+
+        tokio::time::timeout(Duration::from_secs(1), waiter).await.unwrap().unwrap();
+        assert!(shutdown.is_draining());
+    }
+
+    #[tokio::test]
+    async fn test_abort_all_reports_running_tasks() {
+        let mut tasks = TaskRegistry::default();
+        tasks.spawn("finished", async {});
+        tasks.spawn("stuck", std::future::pending());
+        tokio::task::yield_now().await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        assert_eq!(tasks.abort_all(), vec!["stuck"]);
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::VecDeque;
+use std::sync::{Arc, RwLock};
+
+use chrono::{DateTime, Duration, Utc};
+use metrics::gauge;
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use tokio::time::interval;
+
+use crate::polymarket::DataClient;
+use crate::services::whale_trade_poller::parse_trade_timestamp;
+
+/// Whale-grade threshold (USDC) for UNKNOWN wallets until enough market-wide
+/// trades have been sampled.
+pub const FALLBACK_WHALE_NOTIONAL: i64 = 10_000;
+
+/// Trades fetched per poll of the global trade feed.
+const FETCH_LIMIT: u32 = 500;
+
+/// How the whale-grade threshold is derived from market-wide trade sizes.
+#[derive(Debug, Clone)]
+pub struct TradeSizeStatsConfig {
+    /// Trades at or above this percentile (0–100) of recent notionals are whale-grade.
+    pub percentile: Decimal,
+    pub window_hours: i64,
+    /// Keep the previous threshold until the window holds this many trades.
+    pub min_samples: usize,
+    /// Never drop the threshold below this, however quiet the market.
+    pub floor: Decimal,
+    pub interval_secs: u64,
+}
+
+/// Current whale-grade notional for UNKNOWN wallets, shared between the
+/// stats service (writer) and the pipeline (reader).
+#[derive(Debug, Clone)]
+pub struct WhaleNotionalThreshold(Arc<RwLock<Decimal>>);
+
+impl WhaleNotionalThreshold {
+    pub fn new(initial: Decimal) -> Self {
+        Self(Arc::new(RwLock::new(initial)))
+    }
+
+    pub fn get(&self) -> Decimal {
+        *self.0.read().unwrap_or_else(|e| e.into_inner())
+    }
+
+    pub fn set(&self, value: Decimal) {
+        *self.0.write().unwrap_or_else(|e| e.into_inner()) = value;
+    }
+}
+
+impl Default for WhaleNotionalThreshold {
+    fn default() -> Self {
+        Self::new(Decimal::from(FALLBACK_WHALE_NOTIONAL))
+    }
+}
+
+/// Nearest-rank percentile (0–100) of `values`. None when empty.
+pub fn percentile(values: &[Decimal], pct: Decimal) -> Option<Decimal> {
+    if values.is_empty() {
+        return None;
+    }
+
+    let mut sorted = values.to_vec();
+    sorted.sort();
+
+    let n = sorted.len();
+    let rank = (pct.clamp(Decimal::ZERO, Decimal::ONE_HUNDRED) / Decimal::ONE_HUNDRED * Decimal::from(n as i64))
+        .ceil()
+        .to_usize()
+        .unwrap_or(n)
+        .clamp(1, n);
+
+    Some(sorted[rank - 1])
+}
+
+/// Market-wide trade notionals seen within the rolling window.
+#[derive(Debug, Default)]
+pub struct RollingTradeSizes {
+    samples: VecDeque<(DateTime<Utc>, Decimal)>,
+}
+
+impl RollingTradeSizes {
+    pub fn push(&mut self, at: DateTime<Utc>, notional: Decimal) {
+        self.samples.push_back((at, notional));
+    }
+
+    /// Drop samples older than `cutoff`.
+    pub fn prune(&mut self, cutoff: DateTime<Utc>) {
+        self.samples.retain(|(at, _)| *at >= cutoff);
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Whale-grade threshold for the current window, or None while there are
+    /// too few samples to trust.
+    pub fn threshold(&self, config: &TradeSizeStatsConfig) -> Option<Decimal> {
+        if self.samples.len() < config.min_samples.max(1) {
+            return None;
+        }
+        let values: Vec<Decimal> = self.samples.iter().map(|(_, n)| *n).collect();
+        percentile(&values, config.percentile).map(|p| p.max(config.floor))
+    }
+}
+
+/// Sample the global trade feed and keep `threshold` at the configured
+/// percentile of recent trade sizes, so "whale-grade" follows the market
+/// regime instead of a fixed dollar amount.
+pub async fn run_trade_size_stats(
+    data_client: DataClient,
+    config: TradeSizeStatsConfig,
+    threshold: WhaleNotionalThreshold,
+) {
+    let mut ticker = interval(std::time::Duration::from_secs(config.interval_secs));
+    let mut sizes = RollingTradeSizes::default();
+    let mut last_seen: Option<DateTime<Utc>> = None;
+
+    tracing::info!(
+        percentile = %config.percentile,
+        window_hours = config.window_hours,
+        min_samples = config.min_samples,
+        floor = %config.floor,
+        "Trade size stats started"
+    );
+
+    loop {
+        ticker.tick().await;
+
+        let trades = match data_client.get_recent_trades(FETCH_LIMIT).await {
+            Ok(t) => t,
+            Err(e) => {
+                tracing::warn!(error = %e, "Trade size stats: failed to fetch recent trades");
+                continue;
+            }
+        };
+
+        let mut newest = last_seen;
+        for trade in &trades {
+            let (Some(size), Some(price)) = (trade.size, trade.price) else {
+                continue;
+            };
+            let Some(at) = parse_trade_timestamp(trade.timestamp.as_ref()) else {
+                continue;
+            };
+            if last_seen.is_some_and(|seen| at <= seen) {
+                continue;
+            }
+            sizes.push(at, size * price);
+            newest = newest.max(Some(at));
+        }
+        last_seen = newest;
+
+        sizes.prune(Utc::now() - Duration::hours(config.window_hours));
+
+        let Some(value) = sizes.threshold(&config) else {
+            tracing::debug!(
+                samples = sizes.len(),
+                current = %threshold.get(),
+                "Trade size stats: not enough samples — keeping threshold"
+            );
+            continue;
+        };
+
+        let previous = threshold.get();
+        threshold.set(value);
+        gauge!("whale_notional_threshold").set(value.to_f64().unwrap_or(0.0));
+
+        if value != previous {
+            tracing::info!(
+                samples = sizes.len(),
+                previous = %previous.round_dp(2),
+                threshold = %value.round_dp(2),
+                "Whale-grade notional threshold updated"
+            );
+        }
+    }
+}
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(min_samples: usize) -> TradeSizeStatsConfig {
+        TradeSizeStatsConfig {
+            percentile: Decimal::from(90),
+            window_hours: 24,
+            min_samples,
+            floor: Decimal::from(1_000),
+            interval_secs: 60,
+        }
+    }
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<Decimal> = (1..=100).rev().map(Decimal::from).collect();
+        assert_eq!(percentile(&values, Decimal::from(99)), Some(Decimal::from(99)));
+        assert_eq!(percentile(&values, Decimal::from(50)), Some(Decimal::from(50)));
+        assert_eq!(percentile(&values, Decimal::ONE_HUNDRED), Some(Decimal::from(100)));
+        assert_eq!(percentile(&values, Decimal::ZERO), Some(Decimal::ONE));
+        assert_eq!(percentile(&[], Decimal::from(99)), None);
+    }
+
+    #[test]
+    fn test_threshold_needs_samples_and_respects_floor() {
+        let now = Utc::now();
+        let mut sizes = RollingTradeSizes::default();
+        for i in 1..=10 {
+            sizes.push(now, Decimal::from(i * 1_000));
+        }
+        assert_eq!(sizes.threshold(&config(20)), None);
+        assert_eq!(sizes.threshold(&config(10)), Some(Decimal::from(9_000)));
+
+        // Quiet market: percentile below the floor
+        let mut quiet = RollingTradeSizes::default();
+        for _ in 0..10 {
+            quiet.push(now, Decimal::from(50));
+        }
+        assert_eq!(quiet.threshold(&config(10)), Some(Decimal::from(1_000)));
+    }
+
+    #[test]
+    fn test_prune_drops_old_samples() {
+        let now = Utc::now();
+        let mut sizes = RollingTradeSizes::default();
+        sizes.push(now - Duration::hours(30), Decimal::from(100));
+        sizes.push(now - Duration::hours(1), Decimal::from(200));
+        sizes.prune(now - Duration::hours(24));
+        assert_eq!(sizes.len(), 1);
+    }
+
+    #[test]
+    fn test_shared_threshold_defaults_to_fallback() {
+        let t = WhaleNotionalThreshold::default();
+        let reader = t.clone();
+        assert_eq!(reader.get(), Decimal::from(FALLBACK_WHALE_NOTIONAL));
+        t.set(Decimal::from(25_000));
+        assert_eq!(reader.get(), Decimal::from(25_000));
+    }
+}
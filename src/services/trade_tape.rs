@@ -0,0 +1,93 @@
+use chrono::Utc;
+use sqlx::PgPool;
+use tokio::sync::mpsc;
+use tokio::time::{interval, Duration};
+
+use crate::db::tape_repo;
+use crate::models::{TapePrint, WhaleTradeEvent};
+
+use super::shutdown::TaskRegistry;
+
+/// How often buffered prints are written to the DB.
+const FLUSH_INTERVAL: Duration = Duration::from_secs(5);
+/// Buffered prints that force an early flush.
+const MAX_BUFFERED: usize = 1000;
+/// How often prints past the retention window are deleted.
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Sender for an ingestion source's trade events. With a tape, events pass
+/// through a task that copies each print to it before forwarding to the
+/// pipeline; without one, this is the pipeline sender itself.
+pub fn tap(
+    tasks: &mut TaskRegistry,
+    source: &'static str,
+    pipeline_tx: &mpsc::Sender<WhaleTradeEvent>,
+    tape_tx: Option<&mpsc::Sender<TapePrint>>,
+) -> mpsc::Sender<WhaleTradeEvent> {
+    let Some(tape_tx) = tape_tx.cloned() else {
+        return pipeline_tx.clone();
+    };
+    let pipeline_tx = pipeline_tx.clone();
+    let (tx, mut rx) = mpsc::channel::<WhaleTradeEvent>(1000);
+
+    tasks.spawn("trade_tape_tap", async move {
+        while let Some(event) = rx.recv().await {
+            // The tape is best-effort: never hold up the pipeline for it
+            let _ = tape_tx.try_send(TapePrint::from_event(&event, source));
+            if pipeline_tx.send(event).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    tx
+}
+
+/// Run the trade tape recorder: write prints in batches and prune prints
+/// older than `retention_hours`.
+pub async fn run_trade_tape(pool: PgPool, mut rx: mpsc::Receiver<TapePrint>, retention_hours: i64) {
+    let mut buffer: Vec<TapePrint> = Vec::new();
+    let mut flush_timer = interval(FLUSH_INTERVAL);
+    let mut prune_timer = interval(PRUNE_INTERVAL);
+
+    tracing::info!(retention_hours, "Trade tape recorder started");
+
+    loop {
+        tokio::select! {
+            print = rx.recv() => {
+                match print {
+                    Some(print) => {
+                        buffer.push(print);
+                        if buffer.len() >= MAX_BUFFERED {
+                            flush(&pool, &mut buffer).await;
+                        }
+                    }
+                    None => break,
+                }
+            }
+            _ = flush_timer.tick() => flush(&pool, &mut buffer).await,
+            _ = prune_timer.tick() => {
+                let cutoff = Utc::now() - chrono::Duration::hours(retention_hours);
+                match tape_repo::delete_prints_before(&pool, cutoff).await {
+                    Ok(0) => {}
+                    Ok(n) => tracing::info!(deleted = n, "Pruned old trade prints"),
+                    Err(e) => tracing::error!(error = %e, "Failed to prune trade prints"),
+                }
+            }
+        }
+    }
+
+    // Channel closed — write whatever is left
+    flush(&pool, &mut buffer).await;
+    tracing::warn!("Trade tape channel closed — recorder stopped");
+}
+
+async fn flush(pool: &PgPool, buffer: &mut Vec<TapePrint>) {
+    if buffer.is_empty() {
+        return;
+    }
+    let prints = std::mem::take(buffer);
+    if let Err(e) = tape_repo::insert_prints(pool, &prints).await {
+        tracing::error!(error = %e, count = prints.len(), "Failed to flush trade prints");
+    }
+}
@@ -2,12 +2,17 @@ use std::collections::HashSet;
 
 use chrono::Utc;
 use rust_decimal::Decimal;
+use serde::Serialize;
 use sqlx::PgPool;
+use uuid::Uuid;
 
 use crate::config::AppConfig;
-use crate::db::{trade_repo, whale_repo};
-use crate::polymarket::data_client::UserTrade;
+use crate::db::{seeder_candidate_repo, trade_repo, whale_repo};
+use crate::models::{normalize_condition_id, SeederCandidate, SeederCandidateStatus};
+use crate::polymarket::data_client::{LeaderboardEntry, UserTrade};
 use crate::polymarket::DataClient;
+use crate::services::category_baskets::{self, CategoryBasketSummary};
+use crate::services::job_trigger::RunRequests;
 
 /// Maximum number of days since last trade to consider a whale "active".
 /// Stale-deactivation uses this threshold; seeder discovery uses a more
@@ -15,6 +20,25 @@ use crate::polymarket::DataClient;
 const MAX_INACTIVE_DAYS: i64 = 30;
 const SEEDER_RECENCY_DAYS: i64 = 90;
 
+/// Classifications the seeder gives the whales it vets from the leaderboard.
+pub const SEEDER_TIERS: &[&str] = &["top_tier", "high_performer", "profitable"];
+
+/// Outcome of one seeder cycle.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct SeederSummary {
+    pub deactivated: u64,
+    /// Active whales before discovery.
+    pub active: usize,
+    pub seeded: u32,
+    pub skipped_inactive: u32,
+    pub skipped_low_trades: u32,
+    pub skipped_bot_mm: u32,
+    /// Queued candidates evaluated this cycle, with their verdicts.
+    pub candidates: Vec<SeederCandidate>,
+    /// Category baskets created and filled after seeding (None = BASKET_AUTO_CREATE off or failed).
+    pub baskets: Option<CategoryBasketSummary>,
+}
+
 /// Run the whale seeder periodically. Discovers new whales from the Polymarket
 /// leaderboard and deactivates stale ones that haven't traded recently.
 ///
@@ -23,11 +47,17 @@ const SEEDER_RECENCY_DAYS: i64 = 90;
 /// - Require >= min_trades historical trades (small sample = luck, not skill)
 /// - Positive PnL + meaningful volume
 /// - Must have traded within the last 30 days (recency filter)
+///
+/// Addresses queued via `POST /api/seeder/candidates` are evaluated against
+/// the same filters at the start of each cycle.
+///
+/// `run_requests` runs a cycle immediately and answers with its summary.
 pub async fn run_whale_seeder_loop(
     data_client: DataClient,
     pool: PgPool,
     config: AppConfig,
     interval_secs: u64,
+    mut run_requests: RunRequests<Result<SeederSummary, String>>,
 ) {
     // Run immediately on startup
     if let Err(e) = seed_and_cleanup(&data_client, &pool, &config).await {
@@ -39,10 +69,17 @@ pub async fn run_whale_seeder_loop(
     ticker.tick().await; // skip first immediate tick
 
     loop {
-        ticker.tick().await;
-        if let Err(e) = seed_and_cleanup(&data_client, &pool, &config).await {
+        let reply = tokio::select! {
+            _ = ticker.tick() => None,
+            Some(reply) = run_requests.recv() => Some(reply),
+        };
+        let result = seed_and_cleanup(&data_client, &pool, &config).await;
+        if let Err(ref e) = result {
             tracing::warn!(error = %e, "Whale seeder periodic run failed (non-fatal)");
         }
+        if let Some(reply) = reply {
+            let _ = reply.send(result.map_err(|e| e.to_string()));
+        }
     }
 }
 
@@ -52,15 +89,33 @@ pub async fn run_whale_seeder(
     pool: &PgPool,
     config: &AppConfig,
 ) -> anyhow::Result<()> {
-    seed_and_cleanup(data_client, pool, config).await
+    seed_and_cleanup(data_client, pool, config).await.map(|_| ())
 }
 
-/// Core logic: deactivate stale whales, then discover new ones.
+/// One cycle: seed and clean up whales, then fill the category baskets.
 async fn seed_and_cleanup(
     data_client: &DataClient,
     pool: &PgPool,
     config: &AppConfig,
-) -> anyhow::Result<()> {
+) -> anyhow::Result<SeederSummary> {
+    let mut summary = seed_and_cleanup_whales(data_client, pool, config).await?;
+
+    if config.basket_auto_create {
+        match category_baskets::populate_category_baskets(pool, config).await {
+            Ok(baskets) => summary.baskets = Some(baskets),
+            Err(e) => tracing::warn!(error = %e, "Whale seeder: failed to populate category baskets"),
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Core logic: deactivate stale whales, then discover new ones.
+async fn seed_and_cleanup_whales(
+    data_client: &DataClient,
+    pool: &PgPool,
+    config: &AppConfig,
+) -> anyhow::Result<SeederSummary> {
     // Step 1: Deactivate whales that haven't traded in MAX_INACTIVE_DAYS
     let deactivated = whale_repo::deactivate_stale_whales(pool, MAX_INACTIVE_DAYS).await?;
     if deactivated > 0 {
@@ -73,7 +128,16 @@ async fn seed_and_cleanup(
         );
     }
 
-    // Step 2: Check if we need more active whales
+    // Step 2: Evaluate operator-queued candidates
+    let candidates = match evaluate_queued_candidates(data_client, pool, config).await {
+        Ok(c) => c,
+        Err(e) => {
+            tracing::warn!(error = %e, "Whale seeder: failed to evaluate queued candidates");
+            Vec::new()
+        }
+    };
+
+    // Step 3: Check if we need more active whales
     let active = whale_repo::get_active_whales(pool).await?;
     let max_wallets = config.basket_max_wallets as usize;
 
@@ -83,7 +147,12 @@ async fn seed_and_cleanup(
             max = max_wallets,
             "Whale seeder: at capacity, skipping discovery"
         );
-        return Ok(());
+        return Ok(SeederSummary {
+            deactivated,
+            active: active.len(),
+            candidates,
+            ..Default::default()
+        });
     }
 
     let slots_available = max_wallets - active.len();
@@ -94,7 +163,7 @@ async fn seed_and_cleanup(
         slots_available,
     );
 
-    // Step 3: Fetch leaderboard (paginated) and seed new whales
+    // Step 4: Fetch leaderboard (paginated) and seed new whales
     let fetch_count = 500u32;
     let entries = match data_client.get_leaderboard(fetch_count).await {
         Ok(e) => e,
@@ -124,20 +193,7 @@ async fn seed_and_cleanup(
     let filtered_entries: Vec<_> = entries
         .iter()
         .enumerate()
-        .filter(|(rank, entry)| {
-            if *rank < skip_top_n {
-                return false;
-            }
-            let pnl = entry.pnl.unwrap_or(Decimal::ZERO);
-            if pnl <= Decimal::ZERO {
-                return false;
-            }
-            let vol = entry.volume.unwrap_or(Decimal::ZERO);
-            if vol <= Decimal::from(1_000) {
-                return false;
-            }
-            true
-        })
+        .filter(|(rank, entry)| leaderboard_rejection(entry, Some(*rank), skip_top_n).is_none())
         .collect();
 
     tracing::info!(
@@ -166,163 +222,307 @@ async fn seed_and_cleanup(
             continue;
         }
 
-        // Fetch recent trades for this wallet
-        let user_trades = match data_client.get_user_trades(&address, 200).await {
-            Ok(t) => t,
-            Err(e) => {
-                tracing::debug!(error = %e, address = %address, "Failed to fetch trades — skipping");
-                continue;
-            }
-        };
-
-        // Anti-signal filter 2: Minimum trade count
-        if (user_trades.len() as u32) < min_trades {
-            skipped_low_trades += 1;
-            continue;
+        let label = format!("leaderboard_rank_{}", rank + 1);
+        match evaluate_and_seed(data_client, pool, &address, entry, &label, min_trades).await {
+            Ok(_) => seeded_count += 1,
+            Err(Rejection::LowTrades(_)) => skipped_low_trades += 1,
+            Err(Rejection::Inactive(_)) => skipped_inactive += 1,
+            Err(Rejection::BotOrMm(_)) => skipped_bot_mm += 1,
+            Err(Rejection::FetchFailed(_) | Rejection::SeedFailed(_)) => {}
         }
+    }
 
-        // Anti-signal filter 3: Recency — most recent trade must be within SEEDER_RECENCY_DAYS.
-        // Note: stale-deactivation (MAX_INACTIVE_DAYS=30) will later prune whales that go
-        // quiet, so a wider discovery window here is safe.
-        let most_recent_trade = user_trades
-            .iter()
-            .filter_map(|t| parse_trade_timestamp(t.timestamp.as_ref()))
-            .max();
-
-        match most_recent_trade {
-            Some(latest) => {
-                let days_since = (Utc::now() - latest).num_days();
-                if days_since > SEEDER_RECENCY_DAYS {
-                    tracing::debug!(
-                        address = %address,
-                        days_since = days_since,
-                        "Skipping inactive whale (last trade {} days ago)",
-                        days_since,
-                    );
-                    skipped_inactive += 1;
-                    continue;
-                }
-            }
-            None => {
-                // No parseable timestamps — skip
-                skipped_inactive += 1;
-                continue;
-            }
-        }
+    tracing::info!(
+        seeded = seeded_count,
+        skipped_inactive = skipped_inactive,
+        skipped_low_trades = skipped_low_trades,
+        skipped_bot_mm = skipped_bot_mm,
+        "Whale seeder cycle complete",
+    );
 
-        // Anti-signal filter 4: Bot/MM detection from trade patterns
-        if let Some(reason) = detect_bot_or_mm(&user_trades) {
-            tracing::info!(
-                address = %address,
-                reason = %reason,
-                "Skipping suspected bot/MM whale"
-            );
-            skipped_bot_mm += 1;
-            continue;
+    Ok(SeederSummary {
+        deactivated,
+        active: active.len(),
+        seeded: seeded_count,
+        skipped_inactive,
+        skipped_low_trades,
+        skipped_bot_mm,
+        candidates,
+        baskets: None,
+    })
+}
+
+/// Why a wallet wasn't seeded.
+#[derive(Debug, Clone, PartialEq)]
+enum Rejection {
+    FetchFailed(String),
+    LowTrades(usize),
+    Inactive(String),
+    BotOrMm(String),
+    SeedFailed(String),
+}
+
+impl Rejection {
+    fn reason(&self) -> String {
+        match self {
+            Rejection::FetchFailed(e) => format!("failed to fetch trades: {e}"),
+            Rejection::LowTrades(n) => format!("only {n} trades in history"),
+            Rejection::Inactive(r) => r.clone(),
+            Rejection::BotOrMm(r) => r.clone(),
+            Rejection::SeedFailed(e) => format!("failed to store whale: {e}"),
         }
+    }
 
-        let label = format!("leaderboard_rank_{}", rank + 1);
+    /// Errors rather than verdicts on the wallet itself.
+    fn is_failure(&self) -> bool {
+        matches!(self, Rejection::FetchFailed(_) | Rejection::SeedFailed(_))
+    }
+}
 
-        // Upsert whale
-        let whale = match whale_repo::upsert_whale(pool, &address).await {
-            Ok(w) => w,
-            Err(e) => {
-                tracing::warn!(error = %e, address = %address, "Failed to upsert whale");
-                continue;
-            }
-        };
+/// Anti-signal filter 1: skip the top N wallets (`rank` is 0-based; unknown
+/// ranks pass), require positive PnL and meaningful volume.
+fn leaderboard_rejection(entry: &LeaderboardEntry, rank: Option<usize>, skip_top_n: usize) -> Option<String> {
+    if let Some(rank) = rank.filter(|r| *r < skip_top_n) {
+        return Some(format!("leaderboard rank {} is within the top {skip_top_n}", rank + 1));
+    }
+    let pnl = entry.pnl.unwrap_or(Decimal::ZERO);
+    if pnl <= Decimal::ZERO {
+        return Some(format!("leaderboard PnL {} is not positive", pnl.round_dp(2)));
+    }
+    let vol = entry.volume.unwrap_or(Decimal::ZERO);
+    if vol <= Decimal::from(1_000) {
+        return Some(format!("leaderboard volume {} is at most 1000", vol.round_dp(2)));
+    }
+    None
+}
 
-        // Seed trades
-        let mut trade_count = 0i32;
-        for trade in &user_trades {
-            let token_id = trade.token_id.as_deref().unwrap_or("unknown");
-            let market_id = trade.market.as_deref().unwrap_or("unknown");
-            let side = trade.side.as_deref().unwrap_or("BUY");
-            let size = trade.size.unwrap_or(Decimal::ZERO);
-            let price = trade.price.unwrap_or(Decimal::ZERO);
-            let notional = size * price;
-
-            let traded_at = parse_trade_timestamp(trade.timestamp.as_ref())
-                .unwrap_or_else(Utc::now);
-
-            if let Err(e) = trade_repo::insert_trade(
-                pool, whale.id, market_id, token_id, side, size, price, notional, traded_at,
-            )
-            .await
-            {
-                tracing::debug!(error = %e, "Failed to insert seeded trade (may be duplicate)");
-            } else {
-                trade_count += 1;
-            }
+/// Fetch a wallet's history, run anti-signal filters 2–4 and, if it passes,
+/// store it as a whale with its trades and estimated scores.
+async fn evaluate_and_seed(
+    data_client: &DataClient,
+    pool: &PgPool,
+    address: &str,
+    entry: &LeaderboardEntry,
+    label: &str,
+    min_trades: u32,
+) -> Result<Uuid, Rejection> {
+    // Fetch recent trades for this wallet
+    let user_trades = match data_client.get_user_trades(address, 200).await {
+        Ok(t) => t,
+        Err(e) => {
+            tracing::debug!(error = %e, address = %address, "Failed to fetch trades — skipping");
+            return Err(Rejection::FetchFailed(e.to_string()));
         }
+    };
 
-        // Update whale stats from leaderboard data
-        let pnl = entry.pnl.unwrap_or(Decimal::ZERO);
-        let vol = entry.volume.unwrap_or(Decimal::ZERO);
-        let classification = if pnl > Decimal::from(100_000) {
-            "top_tier"
-        } else if pnl > Decimal::from(10_000) {
-            "high_performer"
-        } else {
-            "profitable"
-        };
-
-        let _ = sqlx::query(
-            r#"UPDATE whales
-               SET classification = $2, category = $3, label = $4, updated_at = NOW()
-               WHERE id = $1"#,
-        )
-        .bind(whale.id)
-        .bind(classification)
-        .bind(format!("vol:{}", vol.round()))
-        .bind(&label)
-        .execute(pool)
-        .await;
-
-        // Compute and store initial scores
-        let est_win_rate = if pnl > Decimal::from(100_000) {
-            Decimal::new(68, 2)
-        } else if pnl > Decimal::from(10_000) {
-            Decimal::new(63, 2)
-        } else {
-            Decimal::new(58, 2)
-        };
-        let est_kelly = est_win_rate * Decimal::from(2) - Decimal::ONE;
-        let est_ev = if trade_count > 0 {
-            pnl / Decimal::from(trade_count)
-        } else {
-            Decimal::ZERO
-        };
-        let est_sharpe = if vol > Decimal::ZERO {
-            (pnl / vol * Decimal::from(100)).min(Decimal::from(5))
-        } else {
-            Decimal::ONE
-        };
+    // Anti-signal filter 2: Minimum trade count
+    if (user_trades.len() as u32) < min_trades {
+        return Err(Rejection::LowTrades(user_trades.len()));
+    }
 
-        let _ = whale_repo::update_whale_scores(
-            pool, whale.id, est_sharpe, est_win_rate, est_kelly, est_ev, trade_count, pnl,
-        )
-        .await;
+    // Anti-signal filter 3: Recency — most recent trade must be within SEEDER_RECENCY_DAYS.
+    // Note: stale-deactivation (MAX_INACTIVE_DAYS=30) will later prune whales that go
+    // quiet, so a wider discovery window here is safe.
+    let most_recent_trade = user_trades
+        .iter()
+        .filter_map(|t| parse_trade_timestamp(t.timestamp.as_ref()))
+        .max();
+
+    match most_recent_trade {
+        Some(latest) => {
+            let days_since = (Utc::now() - latest).num_days();
+            if days_since > SEEDER_RECENCY_DAYS {
+                tracing::debug!(
+                    address = %address,
+                    days_since = days_since,
+                    "Skipping inactive whale (last trade {} days ago)",
+                    days_since,
+                );
+                return Err(Rejection::Inactive(format!("last trade {days_since} days ago")));
+            }
+        }
+        None => {
+            // No parseable timestamps — skip
+            return Err(Rejection::Inactive("no parseable trade timestamps".into()));
+        }
+    }
 
+    // Anti-signal filter 4: Bot/MM detection from trade patterns
+    if let Some(reason) = detect_bot_or_mm(&user_trades) {
         tracing::info!(
             address = %address,
-            pnl = %pnl,
-            trades = trade_count,
-            "Seeded new whale"
+            reason = %reason,
+            "Skipping suspected bot/MM whale"
         );
+        return Err(Rejection::BotOrMm(reason));
+    }
+
+    // Upsert whale
+    let whale = match whale_repo::upsert_whale(pool, address).await {
+        Ok(w) => w,
+        Err(e) => {
+            tracing::warn!(error = %e, address = %address, "Failed to upsert whale");
+            return Err(Rejection::SeedFailed(e.to_string()));
+        }
+    };
+
+    // Seed trades
+    let mut trade_count = 0i32;
+    for trade in &user_trades {
+        let token_id = trade.token_id.as_deref().unwrap_or("unknown");
+        let market_id = trade.market.as_deref().unwrap_or("unknown");
+        let side = trade.side.as_deref().unwrap_or("BUY");
+        let size = trade.size.unwrap_or(Decimal::ZERO);
+        let price = trade.price.unwrap_or(Decimal::ZERO);
+        let notional = size * price;
+
+        let traded_at = parse_trade_timestamp(trade.timestamp.as_ref())
+            .unwrap_or_else(Utc::now);
+
+        let condition_id = normalize_condition_id(market_id);
+
+        match trade_repo::insert_trade(
+            pool,
+            whale.id,
+            market_id,
+            token_id,
+            side,
+            size,
+            price,
+            notional,
+            traded_at,
+            condition_id.as_deref(),
+            trade.tx_hash().as_deref(),
+            None,
+        )
+        .await
+        {
+            Ok(Some(_)) => trade_count += 1,
+            Ok(None) => tracing::debug!(tx_hash = ?trade.tx_hash(), "Seeded trade already recorded"),
+            Err(e) => tracing::debug!(error = %e, "Failed to insert seeded trade"),
+        }
+    }
+
+    // Update whale stats from leaderboard data
+    let pnl = entry.pnl.unwrap_or(Decimal::ZERO);
+    let vol = entry.volume.unwrap_or(Decimal::ZERO);
+    let classification = if pnl > Decimal::from(100_000) {
+        "top_tier"
+    } else if pnl > Decimal::from(10_000) {
+        "high_performer"
+    } else {
+        "profitable"
+    };
 
-        seeded_count += 1;
+    let _ = sqlx::query(
+        r#"UPDATE whales
+           SET classification = $2, category = $3, label = $4, updated_at = NOW()
+           WHERE id = $1"#,
+    )
+    .bind(whale.id)
+    .bind(classification)
+    .bind(format!("vol:{}", vol.round()))
+    .bind(label)
+    .execute(pool)
+    .await;
+
+    // Baseline for leaderboard drift detection
+    if let Err(e) = whale_repo::set_leaderboard_baseline(pool, whale.id, pnl, vol).await {
+        tracing::debug!(error = %e, address = %address, "Failed to store leaderboard baseline");
     }
 
+    // Compute and store initial scores
+    let est_win_rate = if pnl > Decimal::from(100_000) {
+        Decimal::new(68, 2)
+    } else if pnl > Decimal::from(10_000) {
+        Decimal::new(63, 2)
+    } else {
+        Decimal::new(58, 2)
+    };
+    let est_kelly = est_win_rate * Decimal::from(2) - Decimal::ONE;
+    let est_ev = if trade_count > 0 {
+        pnl / Decimal::from(trade_count)
+    } else {
+        Decimal::ZERO
+    };
+    let est_sharpe = if vol > Decimal::ZERO {
+        (pnl / vol * Decimal::from(100)).min(Decimal::from(5))
+    } else {
+        Decimal::ONE
+    };
+
+    let _ = whale_repo::update_whale_scores(
+        pool, whale.id, est_sharpe, None, None, None, None, None, None, None, est_win_rate, est_kelly, est_ev, trade_count, pnl,
+    )
+    .await;
+
     tracing::info!(
-        seeded = seeded_count,
-        skipped_inactive = skipped_inactive,
-        skipped_low_trades = skipped_low_trades,
-        skipped_bot_mm = skipped_bot_mm,
-        "Whale seeder cycle complete",
+        address = %address,
+        pnl = %pnl,
+        trades = trade_count,
+        "Seeded new whale"
     );
 
-    Ok(())
+    Ok(whale.id)
+}
+
+/// Evaluate addresses queued through the API. They go through the same
+/// filters as leaderboard candidates, using the wallet's own leaderboard
+/// record, but aren't limited by free basket slots — an operator asked.
+async fn evaluate_queued_candidates(
+    data_client: &DataClient,
+    pool: &PgPool,
+    config: &AppConfig,
+) -> anyhow::Result<Vec<SeederCandidate>> {
+    let pending = seeder_candidate_repo::get_pending_candidates(pool).await?;
+    if pending.is_empty() {
+        return Ok(Vec::new());
+    }
+    let tracked: HashSet<String> = whale_repo::get_all_whale_addresses(pool).await?.into_iter().collect();
+
+    let mut evaluated = Vec::with_capacity(pending.len());
+    for candidate in pending {
+        let (status, verdict, whale_id) = if tracked.contains(&candidate.address) {
+            (SeederCandidateStatus::Rejected, "already tracked".to_string(), None)
+        } else {
+            match data_client.get_leaderboard_entry(&candidate.address).await {
+                Err(e) => (SeederCandidateStatus::Failed, format!("failed to fetch leaderboard record: {e}"), None),
+                Ok(None) => (SeederCandidateStatus::Rejected, "no leaderboard record".to_string(), None),
+                Ok(Some(entry)) => {
+                    let rank = entry.rank.as_deref().and_then(|r| r.parse::<usize>().ok()).filter(|r| *r > 0);
+                    if let Some(reason) = leaderboard_rejection(&entry, rank.map(|r| r - 1), config.whale_seeder_skip_top_n) {
+                        (SeederCandidateStatus::Rejected, reason, None)
+                    } else {
+                        let label = rank.map_or_else(|| "manual_candidate".to_string(), |r| format!("leaderboard_rank_{r}"));
+                        match evaluate_and_seed(
+                            data_client,
+                            pool,
+                            &candidate.address,
+                            &entry,
+                            &label,
+                            config.whale_seeder_min_trades,
+                        )
+                        .await
+                        {
+                            Ok(id) => (SeederCandidateStatus::Seeded, format!("seeded as {label}"), Some(id)),
+                            Err(r) if r.is_failure() => (SeederCandidateStatus::Failed, r.reason(), None),
+                            Err(r) => (SeederCandidateStatus::Rejected, r.reason(), None),
+                        }
+                    }
+                }
+            }
+        };
+
+        tracing::info!(
+            address = %candidate.address,
+            status = %status,
+            verdict = %verdict,
+            "Whale seeder: evaluated queued candidate"
+        );
+        evaluated.push(seeder_candidate_repo::record_verdict(pool, candidate.id, status, &verdict, whale_id).await?);
+    }
+
+    Ok(evaluated)
 }
 
 /// Detect bot or market-maker patterns from API trade data.
@@ -422,3 +622,31 @@ fn parse_trade_timestamp(ts: Option<&serde_json::Value>) -> Option<chrono::DateT
         _ => None,
     })
 }
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pnl: i64, volume: i64) -> LeaderboardEntry {
+        LeaderboardEntry {
+            address: Some("0xabc".into()),
+            volume: Some(Decimal::from(volume)),
+            pnl: Some(Decimal::from(pnl)),
+            rank: None,
+            user_name: None,
+        }
+    }
+
+    #[test]
+    fn test_leaderboard_rejection() {
+        assert_eq!(leaderboard_rejection(&entry(5_000, 50_000), Some(20), 10), None);
+        assert_eq!(leaderboard_rejection(&entry(5_000, 50_000), None, 10), None);
+        assert!(leaderboard_rejection(&entry(5_000, 50_000), Some(3), 10).unwrap().contains("top 10"));
+        assert!(leaderboard_rejection(&entry(-5, 50_000), Some(20), 10).unwrap().contains("PnL"));
+        assert!(leaderboard_rejection(&entry(5_000, 500), Some(20), 10).unwrap().contains("volume"));
+    }
+}
@@ -1,6 +1,8 @@
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Duration;
 use chrono::{DateTime, Utc};
+use metrics::{counter, gauge};
 use rust_decimal::Decimal;
 use sqlx::PgPool;
 use tokio::sync::mpsc;
@@ -9,6 +11,47 @@ use tokio::time::sleep;
 use crate::db::whale_repo;
 use crate::models::{Side, WhaleTradeEvent};
 use crate::polymarket::DataClient;
+use crate::services::notifier::Notifier;
+
+/// Per-whale error budget. Addresses whose Data API queries keep failing are
+/// backed off exponentially so they stop burning cycle time every loop.
+#[derive(Debug, Clone, Default, PartialEq)]
+struct PollHealth {
+    consecutive_failures: u32,
+    /// Start of the current failure streak.
+    failing_since: Option<DateTime<Utc>>,
+    /// Skip the whale until then.
+    next_attempt: Option<DateTime<Utc>>,
+    /// Whether the unpollable alert has been sent for this streak.
+    alerted: bool,
+}
+
+impl PollHealth {
+    fn is_due(&self, now: DateTime<Utc>) -> bool {
+        self.next_attempt.is_none_or(|t| now >= t)
+    }
+
+    /// Record a failed query and schedule the next attempt
+    /// `interval_secs × 2^failures` from now, capped at `max_backoff_secs`.
+    fn record_failure(&mut self, now: DateTime<Utc>, interval_secs: u64, max_backoff_secs: u64) {
+        self.consecutive_failures += 1;
+        self.failing_since.get_or_insert(now);
+
+        let factor = 1u64 << self.consecutive_failures.min(16);
+        let backoff = interval_secs.saturating_mul(factor).min(max_backoff_secs);
+        self.next_attempt = Some(now + chrono::Duration::seconds(backoff as i64));
+    }
+
+    /// True once the whale has been unpollable for `alert_after_hours` and
+    /// no alert has been sent yet for this streak. Zero disables alerts.
+    fn should_alert(&self, now: DateTime<Utc>, alert_after_hours: i64) -> bool {
+        alert_after_hours > 0
+            && !self.alerted
+            && self
+                .failing_since
+                .is_some_and(|since| now - since >= chrono::Duration::hours(alert_after_hours))
+    }
+}
 
 /// Poll each tracked whale's recent trades via the Data API.
 ///
@@ -20,19 +63,29 @@ use crate::polymarket::DataClient;
 /// 2. For each whale, query their recent trades from the Data API
 /// 3. Compare with last-seen trade timestamp to find new trades
 /// 4. Send new trades to the pipeline via the `trade_tx` channel
+///
+/// Whales whose queries fail are backed off (see [`PollHealth`]) and an alert
+/// is sent once one has been unpollable for `alert_after_hours`.
 pub async fn run_whale_trade_poller(
     data_client: DataClient,
     pool: PgPool,
     trade_tx: mpsc::Sender<WhaleTradeEvent>,
     interval_secs: u64,
+    max_backoff_secs: u64,
+    alert_after_hours: i64,
+    notifier: Option<Arc<Notifier>>,
 ) {
     tracing::info!(
         interval_secs = interval_secs,
+        max_backoff_secs,
+        alert_after_hours,
         "Whale trade poller started"
     );
 
     // Track last seen trade timestamp per whale address
     let mut last_seen: HashMap<String, DateTime<Utc>> = HashMap::new();
+    // Error budget per whale address
+    let mut health: HashMap<String, PollHealth> = HashMap::new();
 
     // Initialize last_seen to now so we only capture NEW trades
     if let Ok(whales) = whale_repo::get_active_whales(&pool).await {
@@ -58,16 +111,58 @@ pub async fn run_whale_trade_poller(
         };
 
         let mut total_new_trades = 0u32;
+        health.retain(|address, _| whales.iter().any(|w| &w.address == address));
 
         for whale in &whales {
+            let now = Utc::now();
+            let entry = health.entry(whale.address.clone()).or_default();
+            if !entry.is_due(now) {
+                continue;
+            }
+
             let trades = match data_client.get_user_trades(&whale.address, 10).await {
-                Ok(t) => t,
+                Ok(t) => {
+                    if entry.consecutive_failures > 0 {
+                        tracing::info!(
+                            address = %whale.address,
+                            failures = entry.consecutive_failures,
+                            "Whale poller: whale pollable again"
+                        );
+                    }
+                    *entry = PollHealth::default();
+                    t
+                }
                 Err(e) => {
+                    counter!("whale_poll_errors_total").increment(1);
+                    entry.record_failure(now, interval_secs, max_backoff_secs);
                     tracing::debug!(
                         error = %e,
                         address = %whale.address,
-                        "Whale poller: failed to fetch trades"
+                        failures = entry.consecutive_failures,
+                        next_attempt = ?entry.next_attempt,
+                        "Whale poller: failed to fetch trades — backing off"
                     );
+
+                    if entry.should_alert(now, alert_after_hours) {
+                        entry.alerted = true;
+                        tracing::warn!(
+                            address = %whale.address,
+                            failures = entry.consecutive_failures,
+                            error = %e,
+                            "Whale poller: whale unpollable for {}h — check the address format",
+                            alert_after_hours
+                        );
+                        if let Some(ref n) = notifier {
+                            let msg = crate::services::notifier::format_whale_unpollable(
+                                &whale.address,
+                                whale.label.as_deref(),
+                                entry.consecutive_failures,
+                                alert_after_hours,
+                                &e.to_string(),
+                            );
+                            n.notify(crate::services::notifier::Severity::Warning, &msg).await;
+                        }
+                    }
                     continue;
                 }
             };
@@ -113,6 +208,9 @@ pub async fn run_whale_trade_poller(
                     price,
                     notional,
                     timestamp: traded_at,
+                    tx_hash: trade.tx_hash(),
+                    fill_index: None,
+                    replayed: false,
                 };
 
                 tracing::info!(
@@ -136,6 +234,10 @@ pub async fn run_whale_trade_poller(
             }
         }
 
+        gauge!("whales_backed_off").set(
+            health.values().filter(|h| h.consecutive_failures > 0).count() as f64,
+        );
+
         if total_new_trades > 0 {
             tracing::info!(
                 new_trades = total_new_trades,
@@ -146,7 +248,7 @@ pub async fn run_whale_trade_poller(
     }
 }
 
-fn parse_trade_timestamp(ts: Option<&serde_json::Value>) -> Option<DateTime<Utc>> {
+pub(crate) fn parse_trade_timestamp(ts: Option<&serde_json::Value>) -> Option<DateTime<Utc>> {
     ts.and_then(|t| match t {
         serde_json::Value::Number(n) => {
             let secs = n.as_i64()?;
@@ -174,3 +276,46 @@ fn parse_trade_timestamp(ts: Option<&serde_json::Value>) -> Option<DateTime<Utc>
         _ => None,
     })
 }
+
+// ---------------------------------------------------------------------------
+// Tests
+// ---------------------------------------------------------------------------
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_backoff_doubles_and_caps() {
+        let now = Utc::now();
+        let mut h = PollHealth::default();
+        assert!(h.is_due(now));
+
+        h.record_failure(now, 60, 3600);
+        assert_eq!(h.next_attempt, Some(now + chrono::Duration::seconds(120)));
+        assert!(!h.is_due(now + chrono::Duration::seconds(60)));
+
+        h.record_failure(now, 60, 3600);
+        assert_eq!(h.next_attempt, Some(now + chrono::Duration::seconds(240)));
+
+        for _ in 0..10 {
+            h.record_failure(now, 60, 3600);
+        }
+        assert_eq!(h.next_attempt, Some(now + chrono::Duration::seconds(3600)));
+        assert_eq!(h.failing_since, Some(now));
+    }
+
+    #[test]
+    fn test_alert_once_after_streak() {
+        let start = Utc::now();
+        let mut h = PollHealth::default();
+        h.record_failure(start, 60, 3600);
+
+        assert!(!h.should_alert(start + chrono::Duration::hours(1), 6));
+        assert!(h.should_alert(start + chrono::Duration::hours(6), 6));
+        assert!(!h.should_alert(start + chrono::Duration::hours(6), 0));
+
+        h.alerted = true;
+        assert!(!h.should_alert(start + chrono::Duration::hours(7), 6));
+    }
+}
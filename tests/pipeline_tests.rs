@@ -3,24 +3,55 @@ mod common;
 use chrono::Utc;
 use rust_decimal::Decimal;
 use std::collections::HashMap;
+use std::sync::Arc;
 use std::time::Instant;
 
-use polybot::db::{whale_repo, trade_repo};
-use polybot::ingestion::pipeline::{process_trade_event, PipelineConfig};
-use polybot::models::{Side, WhaleTradeEvent};
+use polybot::db::{basket_repo, candle_repo, event_queue_repo, gate_profile_repo, order_repo, position_repo, whale_repo, trade_repo};
+use polybot::execution::hedging;
+use polybot::ingestion::market_enricher::MarketEnricher;
+use polybot::ingestion::pipeline::{apply_runtime_overrides, process_trade_event, tier_config, PipelineConfig};
+use polybot::intelligence::flow::FlowConfig;
+use polybot::intelligence::scorer::holding_profile;
+use polybot::intelligence::{ConflictMode, HeuristicClassifier};
+use polybot::models::{Candle, ExitStyle, GateProfile, Side, SignalOrigin, Sleeve, TierPolicy, WhaleTradeEvent};
+use polybot::polymarket::GammaClient;
 
 fn default_pipeline_config() -> PipelineConfig {
     PipelineConfig {
         tracked_whale_min_notional: Decimal::from(500),
+        whale_notional: Default::default(),
         min_signal_win_rate: Decimal::new(60, 2),
+        min_signal_win_rate_lb: Decimal::ZERO,
         min_resolved_for_signal: 5,
+        min_entry_edge: Decimal::ZERO,
+        min_entry_edge_samples: 20,
         min_total_trades_for_signal: 100,
         signal_notional_liquidity_pct: Decimal::new(1, 2),
         signal_notional_floor: Decimal::from(1_000),
         max_signal_notional: Decimal::from(500_000),
         min_signal_ev: Decimal::from(50),
+        min_signal_profit_factor: Decimal::ZERO,
+        min_signal_skill_score: Decimal::ZERO,
         assumed_slippage_pct: Decimal::new(2, 2),
+        copy_lag_min_samples: 5,
+        cost_rate: Default::default(),
+        signal_ttl_secs: 300,
         signal_dedup_window_secs: 10,
+        first_mover_min_notional: Decimal::ZERO,
+        first_mover_size_multiplier: Decimal::ONE,
+        basket_max_drawdown_pct: Decimal::from(50),
+        basket_min_skill_score: Decimal::new(90, 2),
+        basket_correlation_threshold: Decimal::ZERO,
+        basket_correlation_days: 30,
+        basket_consensus_cooldown_hours: 0,
+        basket_building_alert_pct: None,
+        basket_conflict_mode: ConflictMode::Stronger,
+        basket_max_insider_flags: 2,
+        classifier: Arc::new(HeuristicClassifier),
+        flow: FlowConfig::default(),
+        tiers: None,
+        gate_profile: None,
+        profiles: Vec::new(),
     }
 }
 
@@ -34,6 +65,9 @@ fn make_trade_event(wallet: &str, notional: i64, side: Side) -> WhaleTradeEvent
         price: Decimal::new(65, 2), // 0.65
         notional: Decimal::from(notional),
         timestamp: Utc::now(),
+        tx_hash: None,
+        fill_index: None,
+        replayed: false,
     }
 }
 
@@ -67,6 +101,219 @@ async fn test_large_trade_creates_whale_and_records_trade() {
     assert_eq!(trades[0].notional, Decimal::from(50_000));
 }
 
+#[tokio::test]
+async fn test_first_large_trade_in_market_has_no_prior() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let market = format!("market_first_mover_{}", uuid::Uuid::new_v4());
+
+    let mut first = make_trade_event("0xWHALE_FIRST_MOVER_1", 20_000, Side::Buy);
+    first.market_id = market.clone();
+    first.timestamp = Utc::now() - chrono::Duration::minutes(5);
+    process_trade_event(&first, &pool, None, None, &config, &dedup).await.unwrap();
+
+    let mut second = make_trade_event("0xWHALE_FIRST_MOVER_2", 20_000, Side::Buy);
+    second.market_id = market.clone();
+    process_trade_event(&second, &pool, None, None, &config, &dedup).await.unwrap();
+
+    let trade_of = |wallet: &'static str| {
+        let pool = pool.clone();
+        async move {
+            let whale = whale_repo::get_whale_by_address(&pool, wallet).await.unwrap().unwrap();
+            trade_repo::get_trades_by_whale(&pool, whale.id).await.unwrap().remove(0)
+        }
+    };
+    let first_trade = trade_of("0xWHALE_FIRST_MOVER_1").await;
+    let second_trade = trade_of("0xWHALE_FIRST_MOVER_2").await;
+    let min = Decimal::from(10_000);
+
+    assert!(!trade_repo::has_prior_large_trade(&pool, &market, first_trade.id, first_trade.traded_at, min)
+        .await
+        .unwrap());
+    assert!(trade_repo::has_prior_large_trade(&pool, &market, second_trade.id, second_trade.traded_at, min)
+        .await
+        .unwrap());
+    // Earlier trade below the threshold doesn't count
+    assert!(!trade_repo::has_prior_large_trade(
+        &pool,
+        &market,
+        second_trade.id,
+        second_trade.traded_at,
+        Decimal::from(50_000)
+    )
+    .await
+    .unwrap());
+}
+
+#[tokio::test]
+async fn test_entry_followups_read_candle_after_horizon() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let token = format!("token_entry_edge_{}", uuid::Uuid::new_v4());
+
+    let mut event = make_trade_event("0xWHALE_ENTRY_EDGE", 20_000, Side::Buy);
+    event.asset_id = token.clone();
+    event.timestamp = Utc::now() - chrono::Duration::hours(30);
+    process_trade_event(&event, &pool, None, None, &config, &dedup).await.unwrap();
+
+    let candle = |bucket, close| Candle {
+        token_id: token.clone(),
+        bucket,
+        open: close,
+        high: close,
+        low: close,
+        close,
+        volume: Decimal::ZERO,
+        trade_count: 1,
+    };
+    candle_repo::upsert_candles(
+        &pool,
+        &[
+            candle(event.timestamp + chrono::Duration::hours(2), Decimal::new(90, 2)),
+            candle(event.timestamp + chrono::Duration::minutes(24 * 60 + 10), Decimal::new(75, 2)),
+        ],
+    )
+    .await
+    .unwrap();
+
+    let whale = whale_repo::get_whale_by_address(&pool, "0xWHALE_ENTRY_EDGE").await.unwrap().unwrap();
+    let followups = trade_repo::get_entry_followups(&pool, whale.id, 24).await.unwrap();
+    assert_eq!(followups, vec![("BUY".to_string(), Decimal::new(65, 2), Decimal::new(75, 2))]);
+
+    // No candle within an hour of a 48h horizon
+    assert!(trade_repo::get_entry_followups(&pool, whale.id, 48).await.unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn test_holding_profile_sets_exit_style_of_new_copies() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let token = format!("token_holding_{}", uuid::Uuid::new_v4());
+
+    // Bought 12h ago, sold 9h later, before the market resolved
+    let mut buy = make_trade_event("0xWHALE_HOLDING", 20_000, Side::Buy);
+    buy.asset_id = token.clone();
+    buy.timestamp = Utc::now() - chrono::Duration::hours(12);
+    process_trade_event(&buy, &pool, None, None, &config, &dedup).await.unwrap();
+    let mut sell = buy.clone();
+    sell.side = Side::Sell;
+    sell.timestamp = buy.timestamp + chrono::Duration::hours(9);
+    process_trade_event(&sell, &pool, None, None, &config, &dedup).await.unwrap();
+
+    let whale = whale_repo::get_whale_by_address(&pool, "0xWHALE_HOLDING").await.unwrap().unwrap();
+    let periods = trade_repo::get_holding_periods(&pool, whale.id).await.unwrap();
+    assert_eq!(periods.len(), 1);
+    let profile = holding_profile(&periods).unwrap();
+    assert_eq!(profile.avg_hold_hours, Decimal::from(9));
+    assert_eq!(profile.early_exit_ratio, Decimal::ONE);
+
+    // Swing trader by the numbers, but force hold-to-resolution to check it's copied
+    whale_repo::update_holding_profile(&pool, whale.id, &profile, Some(ExitStyle::HoldToResolution))
+        .await
+        .unwrap();
+    let trade = trade_repo::get_trades_by_whale(&pool, whale.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|t| t.side == "BUY")
+        .unwrap();
+    let pos = position_repo::upsert_position(
+        &pool,
+        &trade.market_id,
+        &token,
+        "Yes",
+        Decimal::from(10),
+        Decimal::new(65, 2),
+        "single_whale",
+        None,
+        Some(trade.id),
+        None,
+    )
+    .await
+    .unwrap();
+    assert_eq!(pos.whale_id, Some(whale.id));
+    assert_eq!(pos.exit_style(), ExitStyle::HoldToResolution);
+}
+
+#[tokio::test]
+async fn test_net_whale_flow_emits_flow_signal() {
+    let pool = common::setup_test_db().await;
+    let mut config = default_pipeline_config();
+    config.flow = FlowConfig {
+        window_minutes: 60,
+        min_net_notional: Decimal::from(50_000),
+        min_whales: 3,
+        min_imbalance: Decimal::new(6, 1),
+    };
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let token = format!("token_flow_{}", uuid::Uuid::new_v4());
+
+    // Two whales bring 40k net: below the gates
+    for wallet in ["0xWHALE_FLOW_A", "0xWHALE_FLOW_B"] {
+        let mut event = make_trade_event(wallet, 20_000, Side::Buy);
+        event.asset_id = token.clone();
+        process_trade_event(&event, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+    }
+    assert!(rx.try_recv().is_err());
+
+    // A third whale lifts it to 60k from three wallets
+    let mut event = make_trade_event("0xWHALE_FLOW_C", 20_000, Side::Buy);
+    event.asset_id = token.clone();
+    process_trade_event(&event, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+
+    let signal = rx.try_recv().unwrap();
+    assert_eq!(signal.sleeve, Sleeve::Flow);
+    assert_eq!(signal.origin(), SignalOrigin::Flow);
+    assert_eq!(signal.asset_id, token);
+    assert_eq!(signal.whale_notional, Decimal::from(60_000));
+    assert!(signal.flow_signal_id.is_some());
+
+    // Already signalled within the window
+    let mut event = make_trade_event("0xWHALE_FLOW_D", 20_000, Side::Buy);
+    event.asset_id = token.clone();
+    process_trade_event(&event, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+    assert!(rx.try_recv().is_err());
+}
+
+#[tokio::test]
+async fn test_fill_records_copy_lag_against_whale_entry() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+
+    let event = make_trade_event("0xWHALE_COPY_LAG", 20_000, Side::Buy);
+    process_trade_event(&event, &pool, None, None, &config, &dedup).await.unwrap();
+
+    let whale = whale_repo::get_whale_by_address(&pool, "0xWHALE_COPY_LAG").await.unwrap().unwrap();
+    let trade = trade_repo::get_trades_by_whale(&pool, whale.id).await.unwrap().remove(0);
+    let order = order_repo::insert_order(
+        &pool,
+        trade.id,
+        &trade.market_id,
+        &trade.token_id,
+        "BUY",
+        Decimal::from(10),
+        trade.price,
+        "copy",
+        "single_whale",
+        None,
+        None,
+    )
+    .await
+    .unwrap();
+
+    // Whale bought at 0.65, we filled at 0.676: 4% lag cost
+    order_repo::fill_order(&pool, order.id, Decimal::new(676, 3), Decimal::ZERO).await.unwrap();
+
+    let (lag, samples) = order_repo::get_whale_copy_lag(&pool, whale.id).await.unwrap();
+    assert_eq!(samples, 1);
+    assert_eq!(lag.unwrap().round_dp(6), Decimal::new(4, 2));
+}
+
 #[tokio::test]
 async fn test_small_trade_is_filtered() {
     let pool = common::setup_test_db().await;
@@ -104,6 +351,9 @@ async fn test_classification_updates_on_multiple_trades() {
             price: Decimal::new(60, 2),
             notional: Decimal::from(20_000),
             timestamp: Utc::now(),
+            tx_hash: None,
+            fill_index: None,
+            replayed: false,
         };
 
         process_trade_event(&event, &pool, None, None, &config, &dedup)
@@ -129,3 +379,553 @@ async fn test_classification_updates_on_multiple_trades() {
 
     assert_eq!(trades.len(), 5);
 }
+
+#[tokio::test]
+async fn test_consensus_signal_recorded_once_per_window() {
+    let pool = common::setup_test_db().await;
+
+    let basket = basket_repo::create_basket(
+        &pool,
+        "Consensus Dedup Basket",
+        "crypto",
+        Decimal::new(80, 2),
+        48,
+        1,
+        10,
+    )
+    .await
+    .expect("Basket should be created");
+
+    let record = |direction: &'static str| {
+        basket_repo::record_consensus_signal(
+            &pool,
+            basket.id,
+            "market_dedup_001",
+            direction,
+            Decimal::new(90, 2),
+            9,
+            10,
+            basket.time_window_hours,
+        )
+    };
+
+    let first = record("BUY").await.expect("DB query should succeed");
+    assert!(first.is_some());
+
+    let first = first.unwrap();
+    assert_eq!(first.cooldown_until - first.triggered_at, chrono::Duration::hours(48));
+
+    // Same basket/market within the cooldown is suppressed, in either direction
+    let repeat = record("BUY").await.expect("DB query should succeed");
+    assert!(repeat.is_none());
+    let opposite = record("SELL").await.expect("DB query should succeed");
+    assert!(opposite.is_none());
+}
+
+#[tokio::test]
+async fn test_building_alert_dedup() {
+    let pool = common::setup_test_db().await;
+
+    let basket = basket_repo::create_basket(
+        &pool,
+        "Building Alert Basket",
+        "crypto",
+        Decimal::new(80, 2),
+        48,
+        1,
+        10,
+    )
+    .await
+    .expect("Basket should be created");
+
+    let alert = |market: &'static str, direction: &'static str| {
+        basket_repo::record_building_alert(&pool, basket.id, market, direction, Decimal::new(67, 2), 48)
+    };
+
+    assert!(alert("market_building_001", "BUY").await.unwrap());
+    // One alert per basket/market/direction per cooldown
+    assert!(!alert("market_building_001", "BUY").await.unwrap());
+    assert!(alert("market_building_001", "SELL").await.unwrap());
+
+    // No early warning once the market already reached consensus
+    basket_repo::record_consensus_signal(&pool, basket.id, "market_building_002", "BUY", Decimal::new(90, 2), 9, 10, 48)
+        .await
+        .unwrap();
+    assert!(!alert("market_building_002", "BUY").await.unwrap());
+}
+
+#[tokio::test]
+async fn test_opposing_consensus_across_baskets() {
+    let pool = common::setup_test_db().await;
+
+    let create = |name: &'static str| {
+        basket_repo::create_basket(&pool, name, "politics", Decimal::new(80, 2), 48, 1, 10)
+    };
+    let bulls = create("Conflict Basket A").await.expect("Basket should be created");
+    let bears = create("Conflict Basket B").await.expect("Basket should be created");
+
+    basket_repo::record_consensus_signal(&pool, bulls.id, "market_conflict_001", "BUY", Decimal::new(85, 2), 6, 7, 48)
+        .await
+        .unwrap()
+        .expect("First consensus should be recorded");
+
+    let opposing = basket_repo::get_opposing_consensus(&pool, bears.id, "market_conflict_001", "SELL")
+        .await
+        .unwrap();
+    assert_eq!(opposing.len(), 1);
+    assert_eq!(opposing[0].basket_id, bulls.id);
+    assert_eq!(opposing[0].consensus_pct, Decimal::new(85, 2));
+
+    // Same direction and the basket's own signals are not conflicts
+    assert!(basket_repo::get_opposing_consensus(&pool, bears.id, "market_conflict_001", "BUY")
+        .await
+        .unwrap()
+        .is_empty());
+    assert!(basket_repo::get_opposing_consensus(&pool, bulls.id, "market_conflict_001", "SELL")
+        .await
+        .unwrap()
+        .is_empty());
+
+    // A blocked consensus keeps the other side blocked but doesn't hold back
+    // its own basket's traded consensus
+    basket_repo::record_blocked_consensus(&pool, bears.id, "market_conflict_001", "SELL", Decimal::new(82, 2), 5, 6, 48)
+        .await
+        .unwrap();
+    let opposing = basket_repo::get_opposing_consensus(&pool, bulls.id, "market_conflict_001", "BUY")
+        .await
+        .unwrap();
+    assert_eq!(opposing.len(), 1);
+    assert!(opposing[0].blocked);
+    assert!(basket_repo::record_consensus_signal(&pool, bears.id, "market_conflict_001", "SELL", Decimal::new(90, 2), 6, 6, 48)
+        .await
+        .unwrap()
+        .is_some());
+}
+
+#[tokio::test]
+async fn test_active_gate_profile_overrides_gates() {
+    let pool = common::setup_test_db().await;
+    let base = default_pipeline_config();
+
+    let profile = GateProfile {
+        name: "test_strict".into(),
+        description: None,
+        is_active: false,
+        min_signal_win_rate: Some(Decimal::new(72, 2)),
+        min_resolved_for_signal: None,
+        min_total_trades_for_signal: Some(400),
+        min_signal_ev: None,
+        min_signal_profit_factor: None,
+        min_signal_skill_score: None,
+        min_entry_edge: None,
+        signal_notional_liquidity_pct: None,
+        signal_notional_floor: Some(Decimal::from(5000)),
+        max_signal_notional: None,
+        assumed_slippage_pct: None,
+        first_mover_size_multiplier: None,
+        updated_at: None,
+    };
+    gate_profile_repo::upsert_gate_profile(&pool, &profile).await.unwrap();
+    gate_profile_repo::activate_gate_profile(&pool, "test_strict")
+        .await
+        .unwrap()
+        .expect("Profile should exist");
+
+    let cfg = apply_runtime_overrides(&base, &pool).await;
+    assert_eq!(cfg.min_signal_win_rate, Decimal::new(72, 2));
+    assert_eq!(cfg.min_total_trades_for_signal, 400);
+    assert_eq!(cfg.signal_notional_floor, Decimal::from(5000));
+    // Gates the profile leaves unset keep the base value
+    assert_eq!(cfg.min_resolved_for_signal, base.min_resolved_for_signal);
+
+    // The profile also wins over a whale tier's gates
+    let tier = TierPolicy {
+        tier: "B".into(),
+        min_win_rate: Decimal::new(55, 2),
+        min_skill_score: Decimal::ZERO,
+        min_total_trades: 50,
+        min_signal_ev: Decimal::ZERO,
+        signal_notional_floor: Decimal::from(1000),
+        sizing: "fixed".into(),
+        size_multiplier: Decimal::ONE,
+        base_amount: Decimal::from(50),
+        updated_at: None,
+    };
+    let gates = tier_config(&cfg, &tier);
+    assert_eq!(gates.min_total_trades_for_signal, 400);
+    assert_eq!(gates.signal_notional_floor, Decimal::from(5000));
+    assert_eq!(gates.min_signal_win_rate_lb, Decimal::new(55, 2));
+
+    gate_profile_repo::deactivate_gate_profile(&pool, "test_strict").await.unwrap();
+    let cfg = apply_runtime_overrides(&base, &pool).await;
+    assert_eq!(cfg.min_total_trades_for_signal, base.min_total_trades_for_signal);
+}
+
+#[tokio::test]
+async fn test_consensus_votes_net_out_whale_exits() {
+    let pool = common::setup_test_db().await;
+    let market = format!("market_net_{}", uuid::Uuid::new_v4().simple());
+
+    let basket = basket_repo::create_basket(&pool, "Consensus Netting Basket", "crypto", Decimal::new(80, 2), 48, 1, 10)
+        .await
+        .expect("Basket should be created");
+    let mut whales = Vec::new();
+    for i in 0..3 {
+        let address = format!("0xnet_{i}_{}", &uuid::Uuid::new_v4().simple().to_string()[..16]);
+        let whale = common::seed_whale(&pool, &address, Decimal::new(70, 2), "informed").await;
+        basket_repo::add_whale_to_basket(&pool, basket.id, whale.id).await.unwrap();
+        whales.push(whale);
+    }
+
+    // All three bought; one sold out, one trimmed a fifth
+    for whale in &whales {
+        common::seed_trade(&pool, whale.id, &market, "BUY", Decimal::from(1000), 1).await;
+    }
+    common::seed_trade(&pool, whales[0].id, &market, "SELL", Decimal::from(1000), 0).await;
+    common::seed_trade(&pool, whales[1].id, &market, "SELL", Decimal::from(200), 0).await;
+
+    let votes = basket_repo::get_basket_trades_in_window(&pool, basket.id, &market, "token_test", Utc::now() - chrono::Duration::hours(48))
+        .await
+        .expect("DB query should succeed");
+
+    assert_eq!(votes.len(), 2, "the whale that sold out no longer votes");
+    assert!(votes.iter().all(|v| v.side == "BUY"));
+    let trimmed = votes.iter().find(|v| v.whale_id == whales[1].id).unwrap();
+    assert_eq!(trimmed.notional, Decimal::from(800));
+}
+
+#[tokio::test]
+async fn test_chain_event_keyed_by_condition_id() {
+    let pool = common::setup_test_db().await;
+    let suffix = uuid::Uuid::new_v4().simple().to_string();
+    let condition_id = format!("0x{suffix}{suffix}");
+    let token = format!("{}", uuid::Uuid::new_v4().as_u128());
+    sqlx::query(
+        "INSERT INTO active_markets (condition_id, question, volume, liquidity, clob_token_ids) VALUES ($1, $2, 0, 0, $3)",
+    )
+    .bind(&condition_id)
+    .bind("Will the enricher work?")
+    .bind(format!("[\"1{token}\", \"{token}\"]"))
+    .execute(&pool)
+    .await
+    .unwrap();
+
+    let enricher = MarketEnricher::new(pool.clone(), GammaClient::new());
+    let mut event = make_trade_event("0xWHALE_CHAIN_001", 50_000, Side::Buy);
+    event.market_id = token.clone();
+    event.asset_id = token.clone();
+
+    let market = enricher.enrich(&mut event).await.expect("Token should map to its market");
+    assert_eq!(market.question, "Will the enricher work?");
+    assert_eq!(event.market_id, condition_id);
+    assert_eq!(event.asset_id, token);
+
+    // Cached: found even once the market is gone from active_markets
+    sqlx::query("DELETE FROM active_markets WHERE condition_id = $1")
+        .bind(&condition_id)
+        .execute(&pool)
+        .await
+        .unwrap();
+    assert_eq!(enricher.lookup(&token).await, Some(market));
+
+    // Events already keyed by condition ID are left alone
+    let mut event = make_trade_event("0xWHALE_CHAIN_001", 50_000, Side::Buy);
+    event.market_id = condition_id.clone();
+    assert!(enricher.enrich(&mut event).await.is_none());
+    assert_eq!(event.market_id, condition_id);
+}
+
+#[tokio::test]
+async fn test_durable_queue_replays_from_committed_offset() {
+    let pool = common::setup_test_db().await;
+    let consumer = format!("test_{}", uuid::Uuid::new_v4().simple());
+
+    let first = make_trade_event("0xQUEUE_WHALE_001", 20_000, Side::Buy);
+    let second = make_trade_event("0xQUEUE_WHALE_001", 30_000, Side::Sell);
+    let first_id = event_queue_repo::enqueue_event(&pool, &first).await.unwrap();
+    let second_id = event_queue_repo::enqueue_event(&pool, &second).await.unwrap();
+    assert!(second_id > first_id);
+
+    // A consumer that never committed starts from the beginning
+    assert_eq!(event_queue_repo::get_offset(&pool, &consumer).await.unwrap(), 0);
+
+    // Only the first event was processed before the "crash"
+    event_queue_repo::commit_offset(&pool, &consumer, first_id).await.unwrap();
+    let offset = event_queue_repo::get_offset(&pool, &consumer).await.unwrap();
+    assert_eq!(offset, first_id);
+
+    let replayed = event_queue_repo::fetch_events_after(&pool, offset, 10).await.unwrap();
+    let (id, payload) = replayed.into_iter().find(|(id, _)| *id == second_id).expect("second event replayed");
+    let event: WhaleTradeEvent = serde_json::from_value(payload).unwrap();
+    assert_eq!(id, second_id);
+    assert_eq!(event.side, Side::Sell);
+    assert_eq!(event.notional, second.notional);
+
+    // Offsets never move backwards
+    event_queue_repo::commit_offset(&pool, &consumer, first_id - 1).await.unwrap();
+    assert_eq!(event_queue_repo::get_offset(&pool, &consumer).await.unwrap(), first_id);
+}
+
+#[tokio::test]
+async fn test_same_trade_from_two_sources_recorded_once() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let tx_hash = format!("0x{}", uuid::Uuid::new_v4().simple());
+
+    // Chain listener: knows the fill's log index
+    let mut chain_event = make_trade_event("0xWHALE_TX_DEDUP_001", 40_000, Side::Buy);
+    chain_event.tx_hash = Some(tx_hash.clone());
+    chain_event.fill_index = Some(7);
+    // Trade poller: same fill, tx hash only
+    let mut poller_event = chain_event.clone();
+    poller_event.fill_index = None;
+
+    for event in [&chain_event, &poller_event] {
+        process_trade_event(event, &pool, None, None, &config, &dedup)
+            .await
+            .expect("Pipeline should succeed");
+    }
+
+    let whale = whale_repo::get_whale_by_address(&pool, "0xWHALE_TX_DEDUP_001")
+        .await
+        .unwrap()
+        .expect("Whale should exist");
+    let trades = trade_repo::get_trades_by_whale(&pool, whale.id).await.unwrap();
+    let recorded: Vec<_> = trades.iter().filter(|t| t.tx_hash.as_deref() == Some(tx_hash.as_str())).collect();
+    assert_eq!(recorded.len(), 1);
+    assert_eq!(recorded[0].fill_index, Some(7));
+
+    // Another maker leg of the same taker order is a separate fill
+    let mut second_leg = chain_event.clone();
+    second_leg.fill_index = Some(8);
+    for event in [&second_leg, &second_leg, &poller_event] {
+        process_trade_event(event, &pool, None, None, &config, &dedup)
+            .await
+            .expect("Pipeline should succeed");
+    }
+    let trades = trade_repo::get_trades_by_whale(&pool, whale.id).await.unwrap();
+    assert_eq!(trades.iter().filter(|t| t.tx_hash.as_deref() == Some(tx_hash.as_str())).count(), 2);
+
+    // The opposite side in the same transaction is a different trade
+    let mut sell = chain_event.clone();
+    sell.side = Side::Sell;
+    process_trade_event(&sell, &pool, None, None, &config, &dedup)
+        .await
+        .expect("Pipeline should succeed");
+    let trades = trade_repo::get_trades_by_whale(&pool, whale.id).await.unwrap();
+    assert_eq!(trades.iter().filter(|t| t.tx_hash.as_deref() == Some(tx_hash.as_str())).count(), 3);
+}
+
+#[tokio::test]
+async fn test_positions_are_kept_per_sleeve() {
+    let pool = common::setup_test_db().await;
+    let token = format!("token_sleeves_{}", uuid::Uuid::new_v4());
+    let open = |size: i64, sleeve: &'static str| {
+        let (pool, token) = (pool.clone(), token.clone());
+        async move {
+            position_repo::upsert_position(
+                &pool, "market_sleeves", &token, "Yes", Decimal::from(size), Decimal::new(50, 2), sleeve, None, None,
+                None,
+            )
+            .await
+            .unwrap()
+        }
+    };
+
+    let whale_pos = open(100, "single_whale").await;
+    // A basket buying the same token opens its own position
+    let basket_pos = open(40, "basket").await;
+    assert_ne!(whale_pos.id, basket_pos.id);
+    assert_eq!(basket_pos.sleeve, "basket");
+    assert_eq!(basket_pos.size, Decimal::from(40));
+
+    // Adding within a sleeve still merges
+    let added = open(20, "basket").await;
+    assert_eq!(added.id, basket_pos.id);
+    assert_eq!(added.size, Decimal::from(60));
+
+    let whale_held = position_repo::get_position_by_token_id(&pool, &token, "single_whale").await.unwrap().unwrap();
+    assert_eq!(whale_held.size, Decimal::from(100));
+    assert_eq!(position_repo::get_positions_by_token_id(&pool, &token).await.unwrap().len(), 2);
+
+    // An entry filling while the sleeve's exit is in flight adds to that position
+    position_repo::mark_position_exiting(&pool, whale_pos.id, "stop_loss").await.unwrap();
+    let added = open(10, "single_whale").await;
+    assert_eq!(added.id, whale_pos.id);
+    assert_eq!(added.size, Decimal::from(110));
+    assert_eq!(position_repo::get_positions_by_token_id(&pool, &token).await.unwrap().len(), 2);
+}
+
+#[tokio::test]
+async fn test_whale_exit_signalled_for_each_profile_sleeve() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let token = format!("token_profiles_{}", uuid::Uuid::new_v4());
+
+    let mut buy = make_trade_event("0xWHALE_PROFILES_EXIT", 40_000, Side::Buy);
+    buy.asset_id = token.clone();
+    process_trade_event(&buy, &pool, None, None, &config, &dedup).await.unwrap();
+    let whale = whale_repo::get_whale_by_address(&pool, "0xWHALE_PROFILES_EXIT").await.unwrap().unwrap();
+    let trade = trade_repo::get_trades_by_whale(&pool, whale.id)
+        .await
+        .unwrap()
+        .into_iter()
+        .find(|t| t.token_id == token)
+        .unwrap();
+
+    // The whale sleeve and a copy profile each copied the trade out of their own capital
+    for (sleeve, size) in [("single_whale", 50), ("aggressive", 80)] {
+        position_repo::upsert_position(
+            &pool, &buy.market_id, &token, "Yes", Decimal::from(size), buy.price, sleeve, None, Some(trade.id), None,
+        )
+        .await
+        .unwrap();
+    }
+
+    let mut sell = make_trade_event("0xWHALE_PROFILES_EXIT", 40_000, Side::Sell);
+    sell.asset_id = token.clone();
+    process_trade_event(&sell, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+
+    let mut exits = Vec::new();
+    while let Ok(signal) = rx.try_recv() {
+        assert!(signal.is_whale_exit);
+        assert_eq!(signal.sleeve, Sleeve::SingleWhale);
+        exits.push(signal.capital_label().to_string());
+    }
+    exits.sort();
+    assert_eq!(exits, vec!["aggressive", "single_whale"]);
+}
+
+#[tokio::test]
+async fn test_whale_exit_leaves_manual_position_open() {
+    let pool = common::setup_test_db().await;
+    let config = default_pipeline_config();
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let token = format!("token_manual_exit_{}", uuid::Uuid::new_v4());
+
+    // An operator position with no whale behind it
+    position_repo::upsert_position(
+        &pool, "market_manual_exit", &token, "Yes", Decimal::from(50), Decimal::new(50, 2), "single_whale", None,
+        None, None,
+    )
+    .await
+    .unwrap();
+
+    let mut buy = make_trade_event("0xWHALE_UNRELATED_EXIT", 40_000, Side::Buy);
+    buy.asset_id = token.clone();
+    process_trade_event(&buy, &pool, None, None, &config, &dedup).await.unwrap();
+    let mut sell = make_trade_event("0xWHALE_UNRELATED_EXIT", 40_000, Side::Sell);
+    sell.asset_id = token.clone();
+    process_trade_event(&sell, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+
+    while let Ok(signal) = rx.try_recv() {
+        assert!(!signal.is_whale_exit, "manual position must not follow an unrelated whale out");
+    }
+    let pos = position_repo::get_position_by_token_id(&pool, &token, "single_whale").await.unwrap().unwrap();
+    assert_eq!(pos.status.as_deref(), Some("open"));
+}
+
+#[tokio::test]
+async fn test_hedge_skipped_when_sleeve_holds_complementary_token() {
+    let pool = common::setup_test_db().await;
+    let yes = format!("token_hedge_yes_{}", uuid::Uuid::new_v4());
+    let no = format!("token_hedge_no_{}", uuid::Uuid::new_v4());
+    let open = |token: String, sleeve: &'static str| {
+        let pool = pool.clone();
+        async move {
+            position_repo::upsert_position(
+                &pool, "market_hedge_held", &token, "Yes", Decimal::from(100), Decimal::new(50, 2), sleeve, None, None,
+                None,
+            )
+            .await
+            .unwrap()
+        }
+    };
+
+    let losing = open(yes.clone(), "single_whale").await;
+    let basket_losing = open(yes.clone(), "basket").await;
+    assert!(!hedging::complementary_token_held(&pool, &losing, &no).await.unwrap());
+
+    // Once the sleeve holds the other outcome, a hedge would merge into it
+    open(no.clone(), "single_whale").await;
+    assert!(hedging::complementary_token_held(&pool, &losing, &no).await.unwrap());
+    assert!(!hedging::complementary_token_held(&pool, &basket_losing, &no).await.unwrap());
+}
+
+#[tokio::test]
+async fn test_pending_exit_size_counts_unfilled_exits_of_the_sleeve() {
+    let pool = common::setup_test_db().await;
+    let token = format!("token_pending_exit_{}", uuid::Uuid::new_v4());
+    let exit = |size: i64, sleeve: &'static str| {
+        let (pool, token) = (pool.clone(), token.clone());
+        async move {
+            order_repo::insert_order(
+                &pool, uuid::Uuid::nil(), "market_pending_exit", &token, "SELL", Decimal::from(size),
+                Decimal::new(50, 2), "exit", sleeve, None, None,
+            )
+            .await
+            .unwrap()
+        }
+    };
+
+    let first = exit(30, "single_whale").await;
+    let second = exit(20, "single_whale").await;
+    exit(50, "basket").await;
+    order_repo::mark_order_submitted(&pool, second.id, "clob_pending_exit").await.unwrap();
+    assert_eq!(
+        order_repo::get_pending_exit_size(&pool, &token, "single_whale").await.unwrap(),
+        Decimal::from(50)
+    );
+
+    // Filled and cancelled exits no longer hold any of the position
+    order_repo::fill_order(&pool, first.id, Decimal::new(50, 2), Decimal::ZERO).await.unwrap();
+    order_repo::cancel_order(&pool, second.id).await.unwrap();
+    assert_eq!(
+        order_repo::get_pending_exit_size(&pool, &token, "single_whale").await.unwrap(),
+        Decimal::ZERO
+    );
+}
+
+#[tokio::test]
+async fn test_stale_replayed_trade_recorded_but_not_copied() {
+    let pool = common::setup_test_db().await;
+    let mut config = default_pipeline_config();
+    config.flow = FlowConfig {
+        window_minutes: 60,
+        min_net_notional: Decimal::from(50_000),
+        min_whales: 3,
+        min_imbalance: Decimal::new(6, 1),
+    };
+    let dedup = tokio::sync::Mutex::new(HashMap::<String, Instant>::new());
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    let token = format!("token_stale_replay_{}", uuid::Uuid::new_v4());
+
+    for wallet in ["0xWHALE_REPLAY_A", "0xWHALE_REPLAY_B"] {
+        let mut event = make_trade_event(wallet, 20_000, Side::Buy);
+        event.asset_id = token.clone();
+        process_trade_event(&event, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+    }
+
+    // The third whale's buy would complete the flow signal, but it was
+    // replayed ten minutes late
+    let mut event = make_trade_event("0xWHALE_REPLAY_C", 20_000, Side::Buy);
+    event.asset_id = token.clone();
+    event.replayed = true;
+    event.timestamp = Utc::now() - chrono::Duration::minutes(10);
+    process_trade_event(&event, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+    assert!(rx.try_recv().is_err());
+
+    let whale = whale_repo::get_whale_by_address(&pool, "0xWHALE_REPLAY_C").await.unwrap().unwrap();
+    assert_eq!(trade_repo::get_trades_by_whale(&pool, whale.id).await.unwrap().len(), 1);
+
+    // Within the TTL a replay is copied as usual
+    let mut event = make_trade_event("0xWHALE_REPLAY_D", 20_000, Side::Buy);
+    event.asset_id = token.clone();
+    event.replayed = true;
+    process_trade_event(&event, &pool, Some(&tx), None, &config, &dedup).await.unwrap();
+    assert_eq!(rx.try_recv().unwrap().sleeve, Sleeve::Flow);
+}